@@ -7,7 +7,7 @@ use std::io::Write;
 use crate::private_prelude::*;
 use crate::schema::{SCHEMAS_EXTENDED, SCHEMA_DOC_PROPS_V_TYPES};
 
-#[derive(Debug, XmlRead)]
+#[derive(Clone, Debug, XmlRead)]
 #[xml(tag = "Properties")]
 pub struct App<'a> {
     #[xml(flatten_text = "Template")]
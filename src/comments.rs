@@ -2,13 +2,12 @@
 //!
 //! The corresponding ZIP item is `/word/comments.xml`.
 
-use crate::{
-    document::Paragraph,
-    private_prelude::*,
-};
+use std::io::Write;
+
+use crate::{document::Paragraph, private_prelude::*, schema::SCHEMA_MAIN};
 
 /// The root element of the comments document part.
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:comments")]
 pub struct Comments<'a> {
@@ -17,7 +16,31 @@ pub struct Comments<'a> {
     pub comments: Vec<Comment<'a>>,
 }
 
-#[derive(Debug, Default, XmlRead)]
+impl<'a> XmlWrite for Comments<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let Comments { comments } = self;
+
+        log::debug!("[Comments] Started writing.");
+
+        writer.write_element_start("w:comments")?;
+
+        writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
+
+        writer.write_element_end_open()?;
+
+        for ele in comments {
+            ele.to_writer(writer)?;
+        }
+
+        writer.write_element_end_close("w:comments")?;
+
+        log::debug!("[Comments] Finished writing.");
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:comment")]
 pub struct Comment<'a> {
@@ -0,0 +1,282 @@
+/// A step of the edit script turning one sequence into another
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Edit {
+    /// The items at the given positions of both sequences are equal.
+    Equal(usize, usize),
+    /// The item at the given position of the old sequence is removed.
+    Delete(usize),
+    /// The item at the given position of the new sequence is added.
+    Insert(usize),
+}
+
+/// Computes an edit script based on the longest common subsequence.
+///
+/// The common prefix and suffix are skipped, the rest is compared with
+/// Hirschberg's algorithm, which only needs space linear in the length of
+/// the sequences. Deletions are listed before insertions at the same position.
+pub(crate) fn diff<T, U, F>(old: &[T], new: &[U], eq: F) -> Vec<Edit>
+where
+    F: Fn(&T, &U) -> bool,
+{
+    let prefix = old.iter().zip(new).take_while(|(a, b)| eq(a, b)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| eq(a, b))
+        .count();
+
+    let mut edits: Vec<_> = (0..prefix).map(|i| Edit::Equal(i, i)).collect();
+
+    hirschberg(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+        (prefix, prefix),
+        &eq,
+        &mut edits,
+    );
+
+    edits.extend((0..suffix).map(|k| Edit::Equal(old.len() - suffix + k, new.len() - suffix + k)));
+
+    // the halves may end with insertions and start with deletions
+    let mut start = 0;
+    while start < edits.len() {
+        let end = edits[start..]
+            .iter()
+            .position(|edit| matches!(edit, Edit::Equal(..)))
+            .map_or(edits.len(), |len| start + len);
+        edits[start..end].sort_by_key(|edit| matches!(edit, Edit::Insert(_)));
+        start = end + 1;
+    }
+
+    edits
+}
+
+/// Appends the edit script of two sequences starting at the given offsets.
+fn hirschberg<T, U, F>(old: &[T], new: &[U], offset: (usize, usize), eq: &F, edits: &mut Vec<Edit>)
+where
+    F: Fn(&T, &U) -> bool,
+{
+    let (n, m) = (old.len(), new.len());
+
+    if m == 0 {
+        edits.extend((0..n).map(|i| Edit::Delete(offset.0 + i)));
+        return;
+    }
+
+    if n <= 1 {
+        let matched = old
+            .first()
+            .and_then(|item| new.iter().position(|other| eq(item, other)));
+        match matched {
+            Some(j) => {
+                edits.extend((0..j).map(|k| Edit::Insert(offset.1 + k)));
+                edits.push(Edit::Equal(offset.0, offset.1 + j));
+                edits.extend((j + 1..m).map(|k| Edit::Insert(offset.1 + k)));
+            }
+            None => {
+                edits.extend((0..n).map(|i| Edit::Delete(offset.0 + i)));
+                edits.extend((0..m).map(|k| Edit::Insert(offset.1 + k)));
+            }
+        }
+        return;
+    }
+
+    // splits the new sequence where the lcs of both halves of the old one is the longest
+    let middle = n / 2;
+    let forward = lcs_lengths(old[..middle].iter(), new.iter(), |a, b| eq(a, b));
+    let backward = lcs_lengths(old[middle..].iter().rev(), new.iter().rev(), |a, b| {
+        eq(a, b)
+    });
+    let split = (0..=m)
+        .max_by_key(|&j| (forward[j] + backward[m - j], std::cmp::Reverse(j)))
+        .unwrap_or_default();
+
+    hirschberg(&old[..middle], &new[..split], offset, eq, edits);
+    hirschberg(
+        &old[middle..],
+        &new[split..],
+        (offset.0 + middle, offset.1 + split),
+        eq,
+        edits,
+    );
+}
+
+/// Returns the lengths of the lcs of `old` and every prefix of `new`.
+fn lcs_lengths<'s, T: 's, U: 's, F>(
+    old: impl Iterator<Item = &'s T>,
+    new: impl Iterator<Item = &'s U> + Clone,
+    eq: F,
+) -> Vec<usize>
+where
+    F: Fn(&T, &U) -> bool,
+{
+    let mut lengths = vec![0; new.clone().count() + 1];
+    for a in old {
+        let mut diagonal = 0;
+        for (j, b) in new.clone().enumerate() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if eq(a, b) {
+                diagonal + 1
+            } else {
+                above.max(lengths[j])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+/// Returns the similarity of two sequences between `0.0` and `1.0`.
+pub(crate) fn similarity<T: PartialEq>(old: &[T], new: &[T]) -> f64 {
+    if old.is_empty() && new.is_empty() {
+        return 1.0;
+    }
+
+    let common = diff(old, new, |a, b| a == b)
+        .iter()
+        .filter(|edit| matches!(edit, Edit::Equal(..)))
+        .count();
+
+    (2 * common) as f64 / (old.len() + new.len()) as f64
+}
+
+/// A pair of aligned items of two sequences
+pub(crate) enum Alignment {
+    /// The items are equal or similar.
+    Matched(usize, usize),
+    /// The item only exists in the old sequence.
+    Deleted(usize),
+    /// The item only exists in the new sequence.
+    Inserted(usize),
+}
+
+/// Aligns equal items of both sequences, then pairs similar items between them.
+pub(crate) fn align<T: PartialEq, F>(old: &[T], new: &[T], similar: F) -> Vec<Alignment>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let mut alignment = Vec::new();
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+
+    let edits = diff(old, new, |a, b| a == b);
+    // a sentinel flushes the trailing unmatched items
+    for edit in edits
+        .into_iter()
+        .chain(Some(Edit::Equal(old.len(), new.len())))
+    {
+        let (i, j) = match edit {
+            Edit::Delete(i) => {
+                deleted.push(i);
+                continue;
+            }
+            Edit::Insert(j) => {
+                inserted.push(j);
+                continue;
+            }
+            Edit::Equal(i, j) => (i, j),
+        };
+
+        let mut next = 0;
+        for &d in &deleted {
+            match inserted[next..]
+                .iter()
+                .position(|&k| similar(&old[d], &new[k]))
+            {
+                Some(offset) => {
+                    alignment.extend(
+                        inserted[next..next + offset]
+                            .iter()
+                            .map(|&k| Alignment::Inserted(k)),
+                    );
+                    alignment.push(Alignment::Matched(d, inserted[next + offset]));
+                    next += offset + 1;
+                }
+                None => alignment.push(Alignment::Deleted(d)),
+            }
+        }
+        alignment.extend(inserted[next..].iter().map(|&k| Alignment::Inserted(k)));
+        deleted.clear();
+        inserted.clear();
+
+        if i < old.len() {
+            alignment.push(Alignment::Matched(i, j));
+        }
+    }
+
+    alignment
+}
+
+#[test]
+fn test_diff() {
+    use Edit::*;
+
+    assert_eq!(
+        diff(&["a", "b", "c", "d"], &["a", "x", "c", "d", "e"], |a, b| a
+            == b),
+        [
+            Equal(0, 0),
+            Delete(1),
+            Insert(1),
+            Equal(2, 2),
+            Equal(3, 3),
+            Insert(4)
+        ]
+    );
+    assert_eq!(diff::<u8, u8, _>(&[], &[], |a, b| a == b), []);
+    assert_eq!(similarity(&[1, 2], &[3, 4]), 0.0);
+    assert_eq!(similarity(&[1, 2, 3], &[1, 2, 4]), 2.0 / 3.0);
+}
+
+#[test]
+fn test_diff_script() {
+    // pseudo-random sequences over a small alphabet
+    let mut seed = 7u32;
+    let mut sequence = |len: usize| -> Vec<u32> {
+        (0..len)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) % 4
+            })
+            .collect()
+    };
+
+    for (n, m) in [(0, 3), (1, 1), (5, 8), (13, 9), (40, 37)] {
+        let (old, new) = (sequence(n), sequence(m));
+        let edits = diff(&old, &new, |a, b| a == b);
+
+        // the script walks both sequences in order
+        let (mut i, mut j) = (0, 0);
+        for edit in &edits {
+            match *edit {
+                Edit::Equal(a, b) => {
+                    assert_eq!((a, b), (i, j));
+                    assert_eq!(old[a], new[b]);
+                    i += 1;
+                    j += 1;
+                }
+                Edit::Delete(a) => {
+                    assert_eq!(a, i);
+                    i += 1;
+                }
+                Edit::Insert(b) => {
+                    assert_eq!(b, j);
+                    j += 1;
+                }
+            }
+        }
+        assert_eq!((i, j), (n, m));
+
+        let common = edits
+            .iter()
+            .filter(|edit| matches!(edit, Edit::Equal(..)))
+            .count();
+        assert_eq!(
+            common,
+            *lcs_lengths(old.iter(), new.iter(), |a, b| a == b)
+                .last()
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,812 @@
+//! Document comparison
+//!
+//! Compares two documents and produces a redline: a copy of the revised
+//! document whose differences to the original are recorded as tracked changes.
+//!
+//! Paragraphs and tables of both bodies are aligned first. Matched paragraphs
+//! are then compared word by word, differences in text become insertions and
+//! deletions, while differences in run, paragraph and table properties become
+//! formatting changes. Unmatched paragraphs, tables and table rows are marked as
+//! inserted or deleted as a whole.
+//!
+//! Parts other than the main document (styles, numbering, relationships) are
+//! taken from the revised document. Deleted content keeps the relationship
+//! ids of the original document, [`Docx::compare`] copies the hyperlinks and
+//! images it refers to into the redline under new ids.
+//!
+//! ```rust
+//! use docx::compare::CompareOptions;
+//! use docx::document::*;
+//! use docx::Docx;
+//!
+//! let mut original = Docx::default();
+//! original.document.push(Paragraph::default().push_text("The quick brown fox"));
+//!
+//! let mut revised = Docx::default();
+//! revised.document.push(Paragraph::default().push_text("The slow brown fox"));
+//!
+//! let mut redline = original.compare(&revised, CompareOptions::new("Reviewer"));
+//!
+//! assert_eq!(redline.document.revisions().len(), 2);
+//!
+//! redline.document.accept_all();
+//! ```
+//!
+//! [`Docx::compare`]: ../struct.Docx.html#method.compare
+
+pub(crate) mod lcs;
+mod words;
+
+use std::collections::HashMap;
+
+use self::lcs::{align, similarity, Alignment};
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, Deletion, Document, Drawing, Hyperlink, Insertion, Paragraph, SdtProperty,
+        Table, TableCellContent, TableContent, TableRow,
+    },
+    formatting::TablePropertyChange,
+    private_prelude::*,
+    schema::SCHEMA_HYPERLINK,
+    visit::{walk_mut, VisitMut},
+    Docx,
+};
+
+/// Options of a document comparison
+#[derive(Clone, Debug)]
+pub struct CompareOptions<'a> {
+    /// Specifies the author of the recorded revisions.
+    pub author: Cow<'a, str>,
+    /// Specifies the date of the recorded revisions.
+    pub date: Option<Cow<'a, str>>,
+    /// Specifies the minimal share of common words for two paragraphs to be
+    /// compared word by word instead of being replaced as a whole.
+    pub threshold: f64,
+}
+
+impl<'a> CompareOptions<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(author: T) -> Self {
+        CompareOptions {
+            author: author.into(),
+            date: None,
+            threshold: 0.5,
+        }
+    }
+
+    __setter!(date: Option<Cow<'a, str>>);
+    __setter!(threshold: f64);
+}
+
+impl<'a> Docx<'a> {
+    /// Compares the document with a revised version and returns the redline.
+    pub fn compare(&self, revised: &Docx<'a>, options: CompareOptions<'a>) -> Docx<'a> {
+        let mut redline = revised.clone();
+        let mut document = self.document.compare(&revised.document, options);
+
+        let mut relationships = DeletedRelationships {
+            original: self,
+            redline: &mut redline,
+            ids: HashMap::new(),
+            deleted: false,
+        };
+        relationships.visit_document(&mut document);
+
+        redline.document = document;
+        redline
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Compares the document with a revised version and returns the redline.
+    ///
+    /// Revisions already present in either document are accepted before comparing.
+    pub fn compare(&self, revised: &Document<'a>, options: CompareOptions<'a>) -> Document<'a> {
+        let mut old = self.clone();
+        old.accept_all();
+        let mut new = revised.clone();
+        new.accept_all();
+
+        let mut comparer = Comparer {
+            author: options.author,
+            date: options.date,
+            threshold: options.threshold,
+            next_id: 0,
+        };
+
+        let mut redline = new.clone();
+        redline.body.content = comparer.blocks(&old.body.content, &new.body.content);
+        redline
+    }
+}
+
+/// Gives the hyperlinks and images of deleted content, which refer to the
+/// relationships of the original document, the ids of copies in the redline.
+struct DeletedRelationships<'s, 'a> {
+    original: &'s Docx<'a>,
+    redline: &'s mut Docx<'a>,
+    /// Maps the ids of the original document to the ids in the redline.
+    ids: HashMap<String, Option<String>>,
+    deleted: bool,
+}
+
+impl<'s, 'a> DeletedRelationships<'s, 'a> {
+    fn hyperlink(&mut self, id: &str) -> Option<String> {
+        let rel = self
+            .original
+            .document_rels
+            .as_ref()?
+            .relationships
+            .iter()
+            .find(|rel| rel.id == id)?;
+        if rel.target_mode.as_deref() != Some("External") {
+            return None;
+        }
+
+        Some(
+            self.redline
+                .document_rels
+                .get_or_insert_with(Default::default)
+                .add_external_rel(SCHEMA_HYPERLINK, rel.target.clone()),
+        )
+    }
+
+    fn image(&mut self, embed: &str) -> Option<String> {
+        let (name, data) = self.original.embedded_media(embed)?;
+        let extension = name.rsplit('.').next().unwrap_or_default();
+        let (embed, _) = self.redline.embed_media(data.clone(), extension);
+        Some(embed)
+    }
+
+    fn copy<F>(&mut self, id: &Cow<'a, str>, f: F) -> Option<Cow<'a, str>>
+    where
+        F: FnOnce(&mut Self, &str) -> Option<String>,
+    {
+        if !self.ids.contains_key(&**id) {
+            let copy = f(self, id);
+            self.ids.insert(id.to_string(), copy);
+        }
+        self.ids[&**id].clone().map(Into::into)
+    }
+}
+
+impl<'s, 'a> VisitMut<'a> for DeletedRelationships<'s, 'a> {
+    fn visit_deletion(&mut self, del: &mut Deletion<'a>) {
+        self.deleted = true;
+        walk_mut::deletion(self, del);
+        self.deleted = false;
+    }
+
+    fn visit_hyperlink(&mut self, link: &mut Hyperlink<'a>) {
+        if self.deleted {
+            if let Some(id) = &link.id {
+                link.id = self.copy(id, Self::hyperlink);
+            }
+        }
+        walk_mut::hyperlink(self, link);
+    }
+
+    fn visit_drawing(&mut self, drawing: &mut Drawing<'a>) {
+        if self.deleted {
+            if let Some(embed) = &drawing.embed {
+                drawing.embed = self.copy(embed, Self::image);
+            }
+        }
+    }
+}
+
+/// Serializes an element for comparing it with another one.
+pub(crate) fn xml_key<T: XmlWrite>(element: &T) -> String {
+    element.to_string().unwrap_or_default()
+}
+
+/// Returns the words of the text, ignoring whitespace.
+fn words(text: &str) -> Vec<&str> {
+    words::split_words(text)
+        .into_iter()
+        .filter(|word| !word.trim().is_empty())
+        .collect()
+}
+
+pub(crate) fn paragraph_text(para: &Paragraph) -> String {
+    para.iter_text().map(|text| &**text).collect()
+}
+
+pub(crate) fn blocks_text(content: &[BodyContent]) -> String {
+    let mut text = String::new();
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => text.push_str(&paragraph_text(para)),
+            BodyContent::Table(table) => text.push_str(&table_text(table)),
+            BodyContent::Sdt(sdt) => text.push_str(&blocks_text(&sdt.content.content)),
+        }
+        text.push('\n');
+    }
+    text
+}
+
+pub(crate) fn table_text(table: &Table) -> String {
+    table
+        .iter_rows()
+        .map(row_text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn row_text(row: &TableRow) -> String {
+    let mut text = String::new();
+    for cell in row.iter_cells() {
+        text.push_str(&blocks_text(&cell_blocks(&cell.content)));
+        text.push('\t');
+    }
+    text
+}
+
+/// Returns the name identifying a content control.
+fn sdt_name<'s>(property: &'s SdtProperty) -> Option<&'s str> {
+    property
+        .tag
+        .as_ref()
+        .map(|tag| &*tag.value)
+        .or_else(|| property.alias.as_ref().map(|alias| &*alias.value))
+}
+
+/// Returns `true` if two texts have at least the given share of common words.
+fn similar(old: &str, new: &str, threshold: f64) -> bool {
+    similarity(&words(old), &words(new)) >= threshold
+}
+
+/// Aligns two sequences of paragraphs, tables and content controls, pairing
+/// the blocks of the same kind whose texts are similar.
+pub(crate) fn align_blocks(
+    old: &[BodyContent],
+    new: &[BodyContent],
+    threshold: f64,
+) -> Vec<Alignment> {
+    let old_keys: Vec<_> = old.iter().map(block_key).collect();
+    let new_keys: Vec<_> = new.iter().map(block_key).collect();
+
+    align(&old_keys, &new_keys, |a, b| match (a, b) {
+        (BlockKey::Paragraph(a), BlockKey::Paragraph(b)) => similar(a, b, threshold),
+        (BlockKey::Table(a), BlockKey::Table(b)) => similar(a, b, threshold),
+        (BlockKey::Sdt(a), BlockKey::Sdt(b)) => a == b,
+        _ => false,
+    })
+}
+
+/// Aligns two sequences of table rows and row content controls.
+pub(crate) fn align_rows(
+    old: &[TableContent],
+    new: &[TableContent],
+    threshold: f64,
+) -> Vec<Alignment> {
+    let old_keys: Vec<_> = old.iter().map(row_key).collect();
+    let new_keys: Vec<_> = new.iter().map(row_key).collect();
+
+    align(&old_keys, &new_keys, |a, b| match (a, b) {
+        (RowKey::Row(a), RowKey::Row(b)) => similar(a, b, threshold),
+        (RowKey::Sdt(a), RowKey::Sdt(b)) => a == b,
+        _ => false,
+    })
+}
+
+#[derive(PartialEq)]
+enum BlockKey<'s> {
+    Paragraph(String),
+    Table(String),
+    Sdt(Option<&'s str>),
+}
+
+fn block_key<'s>(content: &'s BodyContent) -> BlockKey<'s> {
+    match content {
+        BodyContent::Paragraph(para) => BlockKey::Paragraph(paragraph_text(para)),
+        BodyContent::Table(table) => BlockKey::Table(table_text(table)),
+        BodyContent::Sdt(sdt) => BlockKey::Sdt(sdt_name(&sdt.property)),
+    }
+}
+
+#[derive(PartialEq)]
+enum RowKey<'s> {
+    Row(String),
+    Sdt(Option<&'s str>),
+}
+
+fn row_key<'s>(content: &'s TableContent) -> RowKey<'s> {
+    match content {
+        TableContent::Row(row) => RowKey::Row(row_text(row)),
+        TableContent::Sdt(sdt) => RowKey::Sdt(sdt_name(&sdt.property)),
+    }
+}
+
+/// Records the differences between two documents as revisions.
+pub(crate) struct Comparer<'a> {
+    author: Cow<'a, str>,
+    date: Option<Cow<'a, str>>,
+    threshold: f64,
+    next_id: usize,
+}
+
+impl<'a> Comparer<'a> {
+    fn next_id(&mut self) -> String {
+        let id = self.next_id;
+        self.next_id += 1;
+        id.to_string()
+    }
+
+    fn insertion(&mut self) -> Insertion<'a> {
+        Insertion {
+            id: self.next_id().into(),
+            author: Some(self.author.clone()),
+            date: self.date.clone(),
+            content: Vec::new(),
+        }
+    }
+
+    fn deletion(&mut self) -> Deletion<'a> {
+        Deletion {
+            id: self.next_id().into(),
+            author: Some(self.author.clone()),
+            date: self.date.clone(),
+            content: Vec::new(),
+        }
+    }
+
+    /// Aligns two sequences of paragraphs and tables and compares the matched ones.
+    fn blocks(&mut self, old: &[BodyContent<'a>], new: &[BodyContent<'a>]) -> Vec<BodyContent<'a>> {
+        align_blocks(old, new, self.threshold)
+            .into_iter()
+            .map(|item| match item {
+                Alignment::Matched(i, j) => self.modified_block(&old[i], &new[j]),
+                Alignment::Deleted(i) => self.deleted_block(&old[i]),
+                Alignment::Inserted(j) => self.inserted_block(&new[j]),
+            })
+            .collect()
+    }
+
+    fn modified_block(&mut self, old: &BodyContent<'a>, new: &BodyContent<'a>) -> BodyContent<'a> {
+        match (old, new) {
+            (BodyContent::Paragraph(old), BodyContent::Paragraph(new)) => {
+                self.paragraph(old, new).into()
+            }
+            (BodyContent::Table(old), BodyContent::Table(new)) => self.table(old, new).into(),
+            (BodyContent::Sdt(old), BodyContent::Sdt(new)) => {
+                let mut sdt = new.clone();
+                sdt.content.content = self.blocks(&old.content.content, &new.content.content);
+                sdt.into()
+            }
+            _ => unreachable!("only blocks of the same kind are paired"),
+        }
+    }
+
+    fn inserted_block(&mut self, new: &BodyContent<'a>) -> BodyContent<'a> {
+        match new {
+            BodyContent::Paragraph(para) => self.inserted_paragraph(para).into(),
+            BodyContent::Table(table) => {
+                let mut table = table.clone();
+                for row in table.iter_rows_mut() {
+                    *row = self.inserted_row(row);
+                }
+                table.into()
+            }
+            BodyContent::Sdt(sdt) => {
+                let mut sdt = sdt.clone();
+                for content in &mut sdt.content.content {
+                    *content = self.inserted_block(content);
+                }
+                sdt.into()
+            }
+        }
+    }
+
+    fn deleted_block(&mut self, old: &BodyContent<'a>) -> BodyContent<'a> {
+        match old {
+            BodyContent::Paragraph(para) => self.deleted_paragraph(para).into(),
+            BodyContent::Table(table) => {
+                let mut table = table.clone();
+                for row in table.iter_rows_mut() {
+                    *row = self.deleted_row(row);
+                }
+                table.into()
+            }
+            BodyContent::Sdt(sdt) => {
+                let mut sdt = sdt.clone();
+                for content in &mut sdt.content.content {
+                    *content = self.deleted_block(content);
+                }
+                sdt.into()
+            }
+        }
+    }
+
+    /// Compares two matched tables row by row.
+    fn table(&mut self, old: &Table<'a>, new: &Table<'a>) -> Table<'a> {
+        let mut table = new.clone();
+        table.property.change = None;
+
+        let mut previous = old.property.clone();
+        previous.change = None;
+        if xml_key(&previous) != xml_key(&table.property) {
+            table.property.change = Some(TablePropertyChange {
+                id: self.next_id().into(),
+                author: Some(self.author.clone()),
+                date: self.date.clone(),
+                property: Box::new(previous),
+            });
+        }
+
+        table.rows = self.rows(&old.rows, &new.rows);
+        table
+    }
+
+    /// Aligns two sequences of table rows and compares the matched ones.
+    fn rows(
+        &mut self,
+        old: &[TableContent<'a>],
+        new: &[TableContent<'a>],
+    ) -> Vec<TableContent<'a>> {
+        let alignment = align_rows(old, new, self.threshold);
+
+        let mut rows = Vec::with_capacity(alignment.len());
+        for item in alignment {
+            match item {
+                Alignment::Matched(i, j) => match (&old[i], &new[j]) {
+                    (TableContent::Row(old), TableContent::Row(new))
+                        if old.iter_cells().count() == new.iter_cells().count() =>
+                    {
+                        rows.push(self.row(old, new).into())
+                    }
+                    (TableContent::Sdt(old), TableContent::Sdt(new)) => {
+                        let mut sdt = new.clone();
+                        sdt.content.content = self.rows(&old.content.content, &new.content.content);
+                        rows.push(sdt.into());
+                    }
+                    (old, new) => {
+                        rows.push(self.deleted_rows(old));
+                        rows.push(self.inserted_rows(new));
+                    }
+                },
+                Alignment::Deleted(i) => rows.push(self.deleted_rows(&old[i])),
+                Alignment::Inserted(j) => rows.push(self.inserted_rows(&new[j])),
+            }
+        }
+        rows
+    }
+
+    fn inserted_rows(&mut self, new: &TableContent<'a>) -> TableContent<'a> {
+        let mut content = new.clone();
+        for row in content.iter_rows_mut() {
+            *row = self.inserted_row(row);
+        }
+        content
+    }
+
+    fn deleted_rows(&mut self, old: &TableContent<'a>) -> TableContent<'a> {
+        let mut content = old.clone();
+        for row in content.iter_rows_mut() {
+            *row = self.deleted_row(row);
+        }
+        content
+    }
+
+    /// Compares two matched rows cell by cell.
+    fn row(&mut self, old: &TableRow<'a>, new: &TableRow<'a>) -> TableRow<'a> {
+        let mut row = new.clone();
+
+        for (index, cell) in row.iter_cells_mut().enumerate() {
+            let old_content = match old.iter_cells().nth(index) {
+                Some(old) => cell_blocks(&old.content),
+                None => Vec::new(),
+            };
+            let new_content = cell_blocks(&cell.content);
+
+            let content = match (old_content.split_last(), new_content.split_last()) {
+                // the last paragraph mark of a cell can neither be inserted nor deleted
+                (Some((old_last, old_rest)), Some((new_last, new_rest))) => {
+                    let mut content = self.blocks(old_rest, new_rest);
+                    content.push(self.modified_block(old_last, new_last));
+                    content
+                }
+                _ => self.blocks(&old_content, &new_content),
+            };
+
+            cell.content = cell_content(content);
+        }
+
+        row
+    }
+
+    fn inserted_row(&mut self, new: &TableRow<'a>) -> TableRow<'a> {
+        let mut row = new.clone();
+        row.property.insertion = Some(self.insertion());
+        for cell in row.iter_cells_mut() {
+            let content = cell_blocks(&cell.content)
+                .iter()
+                .map(|content| self.inserted_block(content))
+                .collect();
+            cell.content = cell_content(content);
+        }
+        row
+    }
+
+    fn deleted_row(&mut self, old: &TableRow<'a>) -> TableRow<'a> {
+        let mut row = old.clone();
+        row.property.deletion = Some(self.deletion());
+        for cell in row.iter_cells_mut() {
+            let content = cell_blocks(&cell.content)
+                .iter()
+                .map(|content| self.deleted_block(content))
+                .collect();
+            cell.content = cell_content(content);
+        }
+        row
+    }
+}
+
+pub(crate) fn cell_blocks<'a>(content: &[TableCellContent<'a>]) -> Vec<BodyContent<'a>> {
+    content
+        .iter()
+        .map(|content| match content {
+            TableCellContent::Paragraph(para) => BodyContent::Paragraph(para.clone()),
+            TableCellContent::Sdt(sdt) => BodyContent::Sdt(sdt.clone()),
+            TableCellContent::Table(table) => BodyContent::Table(table.clone()),
+        })
+        .collect()
+}
+
+fn cell_content(content: Vec<BodyContent>) -> Vec<TableCellContent> {
+    content
+        .into_iter()
+        .map(|content| match content {
+            BodyContent::Paragraph(para) => TableCellContent::Paragraph(para),
+            BodyContent::Sdt(sdt) => TableCellContent::Sdt(sdt),
+            BodyContent::Table(table) => TableCellContent::Table(table),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    use crate::{
+        document::{ParagraphContent, Run, RunContent, TableCell},
+        formatting::CharacterProperty,
+        revision::RevisionKind,
+    };
+
+    fn document(paragraphs: &[&str]) -> Document<'static> {
+        let mut document = Document::default();
+        for text in paragraphs {
+            document.push(Paragraph::default().push_text(text.to_string()));
+        }
+        document
+    }
+
+    fn texts(document: &Document) -> Vec<String> {
+        document
+            .body
+            .content
+            .iter()
+            .map(|content| match content {
+                BodyContent::Paragraph(para) => paragraph_text(para),
+                BodyContent::Table(table) => table_text(table),
+                BodyContent::Sdt(sdt) => blocks_text(&sdt.content.content),
+            })
+            .collect()
+    }
+
+    fn kinds(document: &Document) -> Vec<RevisionKind> {
+        document
+            .revisions()
+            .iter()
+            .map(|revision| revision.kind)
+            .collect()
+    }
+
+    #[test]
+    fn compare_paragraphs() {
+        let old = document(&["Title", "The quick brown fox.", "Removed paragraph"]);
+        let new = document(&["Title", "Added paragraph", "The slow brown fox!"]);
+
+        let redline = old.compare(&new, CompareOptions::new("Bot").date("2020-01-01"));
+
+        assert!(redline
+            .revisions()
+            .iter()
+            .all(|revision| revision.author == Some("Bot") && revision.date == Some("2020-01-01")));
+
+        match &redline.body.content[2] {
+            BodyContent::Paragraph(para) => {
+                let kinds: Vec<_> = para
+                    .content
+                    .iter()
+                    .map(|content| match content {
+                        ParagraphContent::Run(_) => "run",
+                        ParagraphContent::Insertion(_) => "ins",
+                        ParagraphContent::Deletion(_) => "del",
+                        _ => "other",
+                    })
+                    .collect();
+                assert_eq!(kinds, ["run", "del", "ins", "run", "del", "ins"]);
+            }
+            _ => panic!("expected a paragraph"),
+        }
+
+        let mut accepted = redline.clone();
+        accepted.accept_all();
+        assert_eq!(texts(&accepted), texts(&new));
+        assert!(accepted.revisions().is_empty());
+
+        let mut rejected = redline;
+        rejected.reject_all();
+        assert_eq!(texts(&rejected), texts(&old));
+    }
+
+    #[test]
+    fn compare_formatting() {
+        let mut old = Document::default();
+        old.push(Paragraph::default().push(Run::default().push_text("bold text")));
+        let mut new = Document::default();
+        new.push(
+            Paragraph::default()
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().bold(true))
+                        .push_text("bold"),
+                )
+                .push(Run::default().push_text(" text")),
+        );
+
+        let mut redline = old.compare(&new, CompareOptions::new("Bot"));
+        assert_eq!(kinds(&redline), [RevisionKind::CharacterProperty]);
+
+        redline.reject_all();
+        match &redline.body.content[0] {
+            BodyContent::Paragraph(para) => match &para.content[0] {
+                ParagraphContent::Run(run) => assert!(run.property.bold.is_none()),
+                _ => panic!("expected a run"),
+            },
+            _ => panic!("expected a paragraph"),
+        }
+    }
+
+    #[test]
+    fn compare_tables() {
+        fn table(rows: &[[&str; 2]]) -> Table<'static> {
+            let mut table = Table::default();
+            for row in rows {
+                let mut table_row = TableRow::default();
+                for text in row {
+                    table_row = table_row.push_cell(TableCell::pargraph(
+                        Paragraph::default().push_text(text.to_string()),
+                    ));
+                }
+                table = table.push_row(table_row);
+            }
+            table
+        }
+
+        let mut old = Document::default();
+        old.push(table(&[["a", "b"], ["one two", "three"], ["gone", "row"]]));
+        let mut new = Document::default();
+        new.push(table(&[
+            ["a", "b"],
+            ["one two", "four"],
+            ["new", "row"],
+            ["added", "cells"],
+        ]));
+
+        let redline = old.compare(&new, CompareOptions::new("Bot"));
+
+        let mut accepted = redline.clone();
+        accepted.accept_all();
+        assert_eq!(texts(&accepted), texts(&new));
+
+        let mut rejected = redline;
+        rejected.reject_all();
+        assert_eq!(texts(&rejected), texts(&old));
+
+        // tables without common content are replaced as a whole
+        let mut old = Document::default();
+        old.push(table(&[["alpha", "beta"]]));
+        let mut new = Document::default();
+        new.push(table(&[["gamma", "delta"]]));
+
+        let redline = old.compare(&new, CompareOptions::new("Bot"));
+        match &redline.body.content[..] {
+            [BodyContent::Table(deleted), BodyContent::Table(inserted)] => {
+                assert!(deleted
+                    .iter_rows()
+                    .all(|row| row.property.deletion.is_some()));
+                assert!(inserted
+                    .iter_rows()
+                    .all(|row| row.property.insertion.is_some()));
+            }
+            _ => panic!("expected two tables"),
+        }
+    }
+
+    #[test]
+    fn compare_deleted_links_and_images() {
+        let mut original = Docx::default();
+        original
+            .document
+            .push(Paragraph::default().push_text("kept"));
+        let link = original
+            .document_rels
+            .get_or_insert_with(Default::default)
+            .add_external_rel(SCHEMA_HYPERLINK, "https://example.com");
+        let (embed, _) = original.embed_media(Bytes::from_static(b"old image"), "png");
+        original.document.push(
+            Paragraph::default()
+                .push(
+                    Hyperlink::default()
+                        .id(link)
+                        .content(Run::default().push_text("link")),
+                )
+                .push(Run::default().push(Drawing::picture(embed, 10, 10))),
+        );
+
+        // the same ids are taken by other targets in the revised document
+        let mut revised = Docx::default();
+        revised
+            .document
+            .push(Paragraph::default().push_text("kept"));
+        revised
+            .document_rels
+            .get_or_insert_with(Default::default)
+            .add_external_rel(SCHEMA_HYPERLINK, "https://example.org");
+        revised.embed_media(Bytes::from_static(b"new image"), "png");
+
+        let redline = original.compare(&revised, CompareOptions::new("Bot"));
+
+        let mut links = Vec::new();
+        let mut embeds = Vec::new();
+        let para = match &redline.document.body.content[1] {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("expected a paragraph"),
+        };
+        match &para.content[..] {
+            [ParagraphContent::Deletion(del)] => {
+                for content in &del.content {
+                    match content {
+                        ParagraphContent::Link(link) => links.push(link.id.clone().unwrap()),
+                        ParagraphContent::Run(run) => {
+                            for content in &run.content {
+                                if let RunContent::Drawing(drawing) = content {
+                                    embeds.push(drawing.embed.clone().unwrap());
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            _ => panic!("expected a deletion"),
+        }
+
+        let rels = redline.document_rels.as_ref().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(rels.get_target(&links[0]), Some("https://example.com"));
+        assert_eq!(embeds.len(), 1);
+        assert_eq!(
+            redline.embedded_media(&embeds[0]).unwrap().1,
+            &Bytes::from_static(b"old image")
+        );
+        // the revised document is untouched
+        assert_eq!(rels.get_target("rId1"), Some("https://example.org"));
+        assert_eq!(
+            redline.embedded_media("rId2").unwrap().1,
+            &Bytes::from_static(b"new image")
+        );
+    }
+
+    #[test]
+    fn compare_unchanged() {
+        let old = document(&["same", "text"]);
+        let redline = old.compare(&old, CompareOptions::new("Bot"));
+        assert!(redline.revisions().is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use std::{mem, ptr};
+
+use crate::{
+    document::{
+        Deletion, Hyperlink, Insertion, MoveTo, Paragraph, ParagraphContent, Run, RunContent, Text,
+        TextSpace,
+    },
+    formatting::{
+        CharacterProperty, CharacterPropertyChange, ParagraphMarkProperty, ParagraphProperty,
+        ParagraphPropertyChange,
+    },
+    private_prelude::*,
+    revision::mark_deleted,
+};
+
+use super::{
+    lcs::{self, Edit},
+    xml_key, Comparer,
+};
+
+/// The smallest unit of a paragraph compared by the word-level diff
+struct Token<'p, 'a> {
+    key: String,
+    piece: Piece<'a>,
+    property: Option<&'p CharacterProperty<'a>>,
+    link: Option<&'p Hyperlink<'a>>,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Piece<'a> {
+    /// A word, a whitespace sequence or a punctuation character
+    Text(String),
+    /// Any other content of a run
+    Run(RunContent<'a>),
+    /// Content outside of runs, like bookmarks and comment ranges
+    Marker(ParagraphContent<'a>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Splits text into words, whitespace sequences and single other characters.
+pub(super) fn split_words(text: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+
+    fn class(c: char) -> Class {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        let current = class(c);
+        let end = match chars.peek() {
+            Some(&(next, c)) if current == Class::Other || class(c) != current => next,
+            Some(_) => continue,
+            None => text.len(),
+        };
+        words.push(&text[start..end]);
+        start = end;
+    }
+
+    words
+}
+
+fn tokenize<'p, 'a>(
+    content: &'p [ParagraphContent<'a>],
+    markers: bool,
+    link: Option<&'p Hyperlink<'a>>,
+    tokens: &mut Vec<Token<'p, 'a>>,
+) {
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => tokenize_run(run, link, tokens),
+            ParagraphContent::Link(hyperlink) => {
+                if let Some(run) = &hyperlink.content {
+                    tokenize_run(run, Some(hyperlink), tokens);
+                }
+            }
+            ParagraphContent::SmartTag(tag) => tokenize(&tag.content, markers, link, tokens),
+            ParagraphContent::SimpleField(field) => tokenize(&field.content, markers, link, tokens),
+            ParagraphContent::Sdt(sdt) => tokenize(&sdt.content.content, markers, link, tokens),
+            ParagraphContent::Insertion(Insertion { content, .. })
+            | ParagraphContent::MoveTo(MoveTo { content, .. }) => {
+                tokenize(content, markers, link, tokens)
+            }
+            ParagraphContent::Deletion(_) | ParagraphContent::MoveFrom(_) => (),
+            other if markers => tokens.push(Token {
+                // markers are never considered equal, they only come from the revised paragraph
+                key: String::from("\0marker"),
+                piece: Piece::Marker(other.clone()),
+                property: None,
+                link: None,
+            }),
+            _ => (),
+        }
+    }
+}
+
+fn tokenize_run<'p, 'a>(
+    run: &'p Run<'a>,
+    link: Option<&'p Hyperlink<'a>>,
+    tokens: &mut Vec<Token<'p, 'a>>,
+) {
+    for content in &run.content {
+        match content {
+            RunContent::Text(text) => {
+                for word in split_words(&text.text) {
+                    tokens.push(Token {
+                        key: word.to_string(),
+                        piece: Piece::Text(word.to_string()),
+                        property: Some(&run.property),
+                        link,
+                    });
+                }
+            }
+            RunContent::DeletedText(_) => (),
+            RunContent::Drawing(drawing) => tokens.push(Token {
+                key: format!("\0drawing:{}", drawing.embed.as_deref().unwrap_or_default()),
+                piece: Piece::Run(content.clone()),
+                property: Some(&run.property),
+                link,
+            }),
+            other => tokens.push(Token {
+                key: format!("\0{}", xml_key(other)),
+                piece: Piece::Run(other.clone()),
+                property: Some(&run.property),
+                link,
+            }),
+        }
+    }
+}
+
+impl<'a> Comparer<'a> {
+    /// Compares two matched paragraphs word by word.
+    pub(crate) fn paragraph(&mut self, old: &Paragraph<'a>, new: &Paragraph<'a>) -> Paragraph<'a> {
+        let mut old_tokens = Vec::new();
+        tokenize(&old.content, false, None, &mut old_tokens);
+        let mut new_tokens = Vec::new();
+        tokenize(&new.content, true, None, &mut new_tokens);
+
+        let old_keys: Vec<_> = old_tokens.iter().map(property_key).collect();
+        let new_keys: Vec<_> = new_tokens.iter().map(property_key).collect();
+
+        let mut para = Paragraph {
+            para_id: new.para_id,
+            property: self.paragraph_property(&old.property, &new.property),
+            content: Vec::new(),
+        };
+
+        let edits = lcs::diff(&old_tokens, &new_tokens, |a, b| a.key == b.key);
+
+        let mut index = 0;
+        while index < edits.len() {
+            // collects the longest stretch of tokens sharing the same formatting
+            let (op, first_old, first_new) = match edits[index] {
+                Edit::Equal(i, j) => (Op::Equal, Some(i), Some(j)),
+                Edit::Delete(i) => (Op::Delete, Some(i), None),
+                Edit::Insert(j) => (Op::Insert, None, Some(j)),
+            };
+
+            let same_group = |edit: &Edit| match (*edit, op) {
+                (Edit::Equal(i, j), Op::Equal) => {
+                    old_keys[i] == old_keys[first_old.unwrap()]
+                        && new_keys[j] == new_keys[first_new.unwrap()]
+                        && same_link(new_tokens[j].link, new_tokens[first_new.unwrap()].link)
+                        && !is_marker(&new_tokens[j])
+                }
+                (Edit::Delete(i), Op::Delete) => {
+                    old_keys[i] == old_keys[first_old.unwrap()]
+                        && same_link(old_tokens[i].link, old_tokens[first_old.unwrap()].link)
+                }
+                (Edit::Insert(j), Op::Insert) => {
+                    new_keys[j] == new_keys[first_new.unwrap()]
+                        && same_link(new_tokens[j].link, new_tokens[first_new.unwrap()].link)
+                        && !is_marker(&new_tokens[j])
+                }
+                _ => false,
+            };
+
+            let mut end = index + 1;
+            let marker = first_new.is_some_and(|j| is_marker(&new_tokens[j]));
+            if !marker {
+                while end < edits.len() && same_group(&edits[end]) {
+                    end += 1;
+                }
+            }
+
+            let group: Vec<&Token> = edits[index..end]
+                .iter()
+                .map(|edit| match *edit {
+                    Edit::Equal(_, j) | Edit::Insert(j) => &new_tokens[j],
+                    Edit::Delete(i) => &old_tokens[i],
+                })
+                .collect();
+
+            if let Piece::Marker(content) = &group[0].piece {
+                para.content.push(content.clone());
+                index = end;
+                continue;
+            }
+
+            let mut run = Run {
+                property: group[0].property.cloned().unwrap_or_default(),
+                content: Vec::new(),
+            };
+            for token in &group {
+                match &token.piece {
+                    Piece::Text(text) => match run.content.last_mut() {
+                        Some(RunContent::Text(last)) => last.text.to_mut().push_str(text),
+                        _ => run.content.push(RunContent::Text(Text {
+                            space: None,
+                            text: Cow::Owned(text.clone()),
+                        })),
+                    },
+                    Piece::Run(content) => run.content.push(content.clone()),
+                    Piece::Marker(_) => unreachable!(),
+                }
+            }
+            for content in &mut run.content {
+                if let RunContent::Text(text) = content {
+                    if text.text.starts_with(char::is_whitespace)
+                        || text.text.ends_with(char::is_whitespace)
+                    {
+                        text.space = Some(TextSpace::Preserve);
+                    }
+                }
+            }
+
+            if op == Op::Equal && old_keys[first_old.unwrap()] != new_keys[first_new.unwrap()] {
+                let previous = old_tokens[first_old.unwrap()].property.cloned();
+                run.property.change = Some(CharacterPropertyChange {
+                    id: self.next_id().into(),
+                    author: Some(self.author.clone()),
+                    date: self.date.clone(),
+                    property: Box::new(previous.unwrap_or_default()),
+                });
+            }
+
+            let content = match group[0].link {
+                Some(link) => ParagraphContent::Link(Hyperlink {
+                    id: link.id.clone(),
+                    anchor: link.anchor.clone(),
+                    content: Some(run),
+                }),
+                None => ParagraphContent::Run(run),
+            };
+
+            match op {
+                Op::Equal => para.content.push(content),
+                Op::Insert => match para.content.last_mut() {
+                    Some(ParagraphContent::Insertion(ins)) => ins.content.push(content),
+                    _ => {
+                        let ins = self.insertion().push(content);
+                        para.content.push(ins.into());
+                    }
+                },
+                Op::Delete => {
+                    let mut content = content;
+                    mark_deleted(&mut content);
+                    match para.content.last_mut() {
+                        Some(ParagraphContent::Deletion(del)) => del.content.push(content),
+                        _ => {
+                            let del = self.deletion().push(content);
+                            para.content.push(del.into());
+                        }
+                    }
+                }
+            }
+
+            index = end;
+        }
+
+        para
+    }
+
+    /// Returns the revised paragraph properties, recording the original ones if they differ.
+    fn paragraph_property(
+        &mut self,
+        old: &ParagraphProperty<'a>,
+        new: &ParagraphProperty<'a>,
+    ) -> ParagraphProperty<'a> {
+        let mut property = new.clone();
+        property.change = None;
+        property.mark = None;
+
+        let mut previous = old.clone();
+        previous.change = None;
+        previous.mark = None;
+
+        if xml_key(&property) != xml_key(&previous) {
+            property.change = Some(ParagraphPropertyChange {
+                id: self.next_id().into(),
+                author: Some(self.author.clone()),
+                date: self.date.clone(),
+                property: Box::new(previous),
+            });
+        }
+
+        property
+    }
+
+    /// Returns the paragraph with all of its content and its mark inserted.
+    pub(crate) fn inserted_paragraph(&mut self, new: &Paragraph<'a>) -> Paragraph<'a> {
+        let mut para = new.clone();
+        para.property.change = None;
+
+        let ins = self.insertion();
+        para.property.mark = Some(ParagraphMarkProperty::default().insertion(ins.clone()));
+        if !para.content.is_empty() {
+            let content = mem::take(&mut para.content);
+            para.content.push(Insertion { content, ..ins }.into());
+        }
+
+        para
+    }
+
+    /// Returns the paragraph with all of its content and its mark deleted.
+    pub(crate) fn deleted_paragraph(&mut self, old: &Paragraph<'a>) -> Paragraph<'a> {
+        let mut para = old.clone();
+        para.property.change = None;
+
+        let del = self.deletion();
+        para.property.mark = Some(ParagraphMarkProperty::default().deletion(del.clone()));
+        if !para.content.is_empty() {
+            let mut content = mem::take(&mut para.content);
+            content.iter_mut().for_each(mark_deleted);
+            para.content.push(Deletion { content, ..del }.into());
+        }
+
+        para
+    }
+}
+
+fn property_key(token: &Token) -> String {
+    token.property.map(xml_key).unwrap_or_default()
+}
+
+fn is_marker(token: &Token) -> bool {
+    matches!(token.piece, Piece::Marker(_))
+}
+
+fn same_link(a: Option<&Hyperlink>, b: Option<&Hyperlink>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => ptr::eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn test_split_words() {
+    assert_eq!(
+        split_words("Hello,  world_1 !"),
+        ["Hello", ",", "  ", "world_1", " ", "!"]
+    );
+    assert!(split_words("").is_empty());
+}
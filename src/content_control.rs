@@ -0,0 +1,840 @@
+//! Content controls
+//!
+//! Finds the content controls (`w:sdt`) of the main document by tag or alias,
+//! and sets their contents.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//!
+//! docx.document.push(
+//!     Paragraph::default().push_text("Dear ").push(
+//!         RunSdt::default()
+//!             .property(SdtProperty::default().tag("name").ty(SdtText::default()))
+//!             .push(Run::default().push_text("Click here")),
+//!     ),
+//! );
+//!
+//! assert!(docx.document.content_control_mut("name").unwrap().set_text("John"));
+//! assert_eq!(docx.document.content_control("name").unwrap().text(), "John");
+//! ```
+
+use crate::{
+    document::{
+        BlockSdt, BodyContent, CellSdt, Document, Paragraph, ParagraphContent, RowSdt, Run,
+        RunContent, RunSdt, SdtProperty, SdtType, TableCell, TableCellContent, TableContent,
+        TableRowContent, Text, TextSpace,
+    },
+    formatting::CharacterProperty,
+};
+
+/// Style applied by Word to the placeholder text of content controls.
+const PLACEHOLDER_STYLE: &str = "PlaceholderText";
+
+/// A content control found in the document
+#[derive(Clone, Copy, Debug)]
+pub enum ContentControl<'s, 'a> {
+    Block(&'s BlockSdt<'a>),
+    Run(&'s RunSdt<'a>),
+    Row(&'s RowSdt<'a>),
+    Cell(&'s CellSdt<'a>),
+}
+
+/// A content control found in the document, borrowed mutably
+#[derive(Debug)]
+pub enum ContentControlMut<'s, 'a> {
+    Block(&'s mut BlockSdt<'a>),
+    Run(&'s mut RunSdt<'a>),
+    Row(&'s mut RowSdt<'a>),
+    Cell(&'s mut CellSdt<'a>),
+}
+
+impl<'s, 'a> ContentControl<'s, 'a> {
+    pub fn property(&self) -> &'s SdtProperty<'a> {
+        match self {
+            ContentControl::Block(sdt) => &sdt.property,
+            ContentControl::Run(sdt) => &sdt.property,
+            ContentControl::Row(sdt) => &sdt.property,
+            ContentControl::Cell(sdt) => &sdt.property,
+        }
+    }
+
+    /// Returns the programmatic tag of the control.
+    pub fn tag(&self) -> Option<&'s str> {
+        self.property().tag.as_ref().map(|tag| &*tag.value)
+    }
+
+    /// Returns the friendly name of the control.
+    pub fn alias(&self) -> Option<&'s str> {
+        self.property().alias.as_ref().map(|alias| &*alias.value)
+    }
+
+    /// Returns the type of the control, `None` means rich text.
+    pub fn ty(&self) -> Option<&'s SdtType<'a>> {
+        self.property().ty.as_ref()
+    }
+
+    /// Returns the current text of the control, paragraphs are separated by new lines.
+    pub fn text(&self) -> String {
+        match self {
+            ContentControl::Block(sdt) => blocks_text(&sdt.content.content),
+            ContentControl::Run(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .flat_map(ParagraphContent::iter_text)
+                .map(|text| &**text)
+                .collect(),
+            ContentControl::Row(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .flat_map(TableContent::iter_rows)
+                .flat_map(|row| row.iter_cells())
+                .map(cell_text)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ContentControl::Cell(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .flat_map(TableRowContent::iter_cells)
+                .map(cell_text)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.tag() == Some(name) || self.alias() == Some(name)
+    }
+}
+
+impl<'s, 'a> ContentControlMut<'s, 'a> {
+    pub fn property(&mut self) -> &mut SdtProperty<'a> {
+        match self {
+            ContentControlMut::Block(sdt) => &mut sdt.property,
+            ContentControlMut::Run(sdt) => &mut sdt.property,
+            ContentControlMut::Row(sdt) => &mut sdt.property,
+            ContentControlMut::Cell(sdt) => &mut sdt.property,
+        }
+    }
+
+    /// Replaces the content of the control with the given text.
+    ///
+    /// The formatting of the first paragraph and run is kept, new lines start
+    /// new paragraphs in block-level controls and line breaks in run-level
+    /// controls. In row- and cell-level controls, the lines fill the cells in
+    /// order, the last cell taking the remaining lines as paragraphs, and the
+    /// cells left without a line are emptied. Returns `false` if the content
+    /// is locked.
+    pub fn set_text(&mut self, text: &str) -> bool {
+        let property = self.property();
+        if property.is_content_locked() {
+            return false;
+        }
+        let placeholder = property.is_showing_placeholder();
+        let lines: Vec<_> = text.split('\n').collect();
+
+        match self {
+            ContentControlMut::Block(sdt) => {
+                let content = &mut sdt.content.content;
+                *content = text_paragraphs(first_paragraph(content), &lines, placeholder)
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+            }
+            ContentControlMut::Run(sdt) => {
+                let content = &mut sdt.content.content;
+                let run_property = text_property(first_run_property(content), placeholder);
+
+                let mut run = Run::default().property(run_property);
+                for (index, line) in lines.iter().enumerate() {
+                    if index > 0 {
+                        run = run.push_break(None);
+                    }
+                    run = run.push_text(((*line).to_owned(), TextSpace::Preserve));
+                }
+                *content = vec![run.into()];
+            }
+            ContentControlMut::Row(sdt) => {
+                let cells = sdt
+                    .content
+                    .content
+                    .iter_mut()
+                    .flat_map(TableContent::iter_rows_mut)
+                    .flat_map(|row| row.iter_cells_mut())
+                    .collect();
+                set_cells_text(cells, &lines, placeholder);
+            }
+            ContentControlMut::Cell(sdt) => {
+                let cells = sdt
+                    .content
+                    .content
+                    .iter_mut()
+                    .flat_map(TableRowContent::iter_cells_mut)
+                    .collect();
+                set_cells_text(cells, &lines, placeholder);
+            }
+        }
+
+        self.property().showing_placeholder = None;
+        true
+    }
+
+    /// Checks or unchecks a checkbox control and updates its symbol.
+    ///
+    /// Returns `false` if the control isn't a checkbox or its content is locked.
+    pub fn set_checked(&mut self, checked: bool) -> bool {
+        let symbol = match &mut self.property().ty {
+            Some(SdtType::Checkbox(checkbox)) => checkbox.symbol(checked),
+            _ => return false,
+        };
+
+        if !self.set_text(&symbol.to_string()) {
+            return false;
+        }
+
+        if let Some(SdtType::Checkbox(checkbox)) = &mut self.property().ty {
+            checkbox.checked = Some(checked.into());
+        }
+        true
+    }
+
+    /// Sets the date of a date picker control, together with the displayed text.
+    ///
+    /// The date is expected in ISO 8601 format, e.g. `2020-01-02T00:00:00Z`.
+    /// Returns `false` if the control isn't a date picker or its content is locked.
+    pub fn set_date(&mut self, full_date: &str, text: &str) -> bool {
+        if !matches!(self.property().ty, Some(SdtType::Date(_))) || !self.set_text(text) {
+            return false;
+        }
+
+        if let Some(SdtType::Date(date)) = &mut self.property().ty {
+            date.full_date = Some(full_date.to_owned().into());
+        }
+        true
+    }
+
+    /// Selects the item with the given value in a drop-down list or a combo box.
+    ///
+    /// Combo boxes also accept values which are not in their list. Returns
+    /// `false` if the control isn't a list, the value isn't found in a drop-down
+    /// list or the content is locked.
+    pub fn select_item(&mut self, value: &str) -> bool {
+        let text = match &self.property().ty {
+            Some(SdtType::DropDownList(list)) => list
+                .items
+                .iter()
+                .find(|item| item.value.as_deref() == Some(value))
+                .map(|item| item.text().to_owned()),
+            Some(SdtType::ComboBox(combo)) => Some(
+                combo
+                    .items
+                    .iter()
+                    .find(|item| item.value.as_deref() == Some(value))
+                    .map_or(value, |item| item.text())
+                    .to_owned(),
+            ),
+            _ => None,
+        };
+
+        match text {
+            Some(text) if self.set_text(&text) => (),
+            _ => return false,
+        }
+
+        match &mut self.property().ty {
+            Some(SdtType::DropDownList(list)) => list.last_value = Some(value.to_owned().into()),
+            Some(SdtType::ComboBox(combo)) => combo.last_value = Some(value.to_owned().into()),
+            _ => (),
+        }
+        true
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns all content controls in document order, including nested ones.
+    pub fn content_controls(&self) -> Vec<ContentControl<'_, 'a>> {
+        let mut controls = Vec::new();
+        collect_blocks(&self.body.content, &mut controls);
+        controls
+    }
+
+    /// Returns the first content control with the given tag or alias.
+    pub fn content_control(&self, name: &str) -> Option<ContentControl<'_, 'a>> {
+        self.content_controls()
+            .into_iter()
+            .find(|control| control.matches(name))
+    }
+
+    /// Returns the first content control with the given tag or alias, borrowed mutably.
+    pub fn content_control_mut(&mut self, name: &str) -> Option<ContentControlMut<'_, 'a>> {
+        find_blocks(&mut self.body.content, name)
+    }
+}
+
+fn collect_blocks<'s, 'a>(
+    content: &'s [BodyContent<'a>],
+    controls: &mut Vec<ContentControl<'s, 'a>>,
+) {
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => collect_runs(&para.content, controls),
+            BodyContent::Table(table) => collect_rows(&table.rows, controls),
+            BodyContent::Sdt(sdt) => {
+                controls.push(ContentControl::Block(sdt));
+                collect_blocks(&sdt.content.content, controls);
+            }
+        }
+    }
+}
+
+fn collect_rows<'s, 'a>(
+    content: &'s [TableContent<'a>],
+    controls: &mut Vec<ContentControl<'s, 'a>>,
+) {
+    for content in content {
+        match content {
+            TableContent::Row(row) => collect_cells(&row.cells, controls),
+            TableContent::Sdt(sdt) => {
+                controls.push(ContentControl::Row(sdt));
+                collect_rows(&sdt.content.content, controls);
+            }
+        }
+    }
+}
+
+fn collect_cells<'s, 'a>(
+    content: &'s [TableRowContent<'a>],
+    controls: &mut Vec<ContentControl<'s, 'a>>,
+) {
+    for content in content {
+        match content {
+            TableRowContent::Cell(cell) => {
+                for content in &cell.content {
+                    match content {
+                        TableCellContent::Paragraph(para) => collect_runs(&para.content, controls),
+                        TableCellContent::Sdt(sdt) => {
+                            controls.push(ContentControl::Block(sdt));
+                            collect_blocks(&sdt.content.content, controls);
+                        }
+                        TableCellContent::Table(table) => collect_rows(&table.rows, controls),
+                    }
+                }
+            }
+            TableRowContent::Sdt(sdt) => {
+                controls.push(ContentControl::Cell(sdt));
+                collect_cells(&sdt.content.content, controls);
+            }
+        }
+    }
+}
+
+fn collect_runs<'s, 'a>(
+    content: &'s [ParagraphContent<'a>],
+    controls: &mut Vec<ContentControl<'s, 'a>>,
+) {
+    for content in content {
+        match content {
+            ParagraphContent::Sdt(sdt) => {
+                controls.push(ContentControl::Run(sdt));
+                collect_runs(&sdt.content.content, controls);
+            }
+            ParagraphContent::SmartTag(tag) => collect_runs(&tag.content, controls),
+            ParagraphContent::SimpleField(field) => collect_runs(&field.content, controls),
+            ParagraphContent::Insertion(ins) => collect_runs(&ins.content, controls),
+            ParagraphContent::Deletion(del) => collect_runs(&del.content, controls),
+            ParagraphContent::MoveFrom(from) => collect_runs(&from.content, controls),
+            ParagraphContent::MoveTo(to) => collect_runs(&to.content, controls),
+            _ => (),
+        }
+    }
+}
+
+fn find_blocks<'s, 'a>(
+    content: &'s mut [BodyContent<'a>],
+    name: &str,
+) -> Option<ContentControlMut<'s, 'a>> {
+    for content in content {
+        let found = match content {
+            BodyContent::Paragraph(para) => find_runs(&mut para.content, name),
+            BodyContent::Table(table) => find_rows(&mut table.rows, name),
+            BodyContent::Sdt(sdt) => find_block(sdt, name),
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn find_block<'s, 'a>(sdt: &'s mut BlockSdt<'a>, name: &str) -> Option<ContentControlMut<'s, 'a>> {
+    if ContentControl::Block(sdt).matches(name) {
+        Some(ContentControlMut::Block(sdt))
+    } else {
+        find_blocks(&mut sdt.content.content, name)
+    }
+}
+
+fn find_rows<'s, 'a>(
+    content: &'s mut [TableContent<'a>],
+    name: &str,
+) -> Option<ContentControlMut<'s, 'a>> {
+    for content in content {
+        let found = match content {
+            TableContent::Row(row) => find_cells(&mut row.cells, name),
+            TableContent::Sdt(sdt) => {
+                if ContentControl::Row(sdt).matches(name) {
+                    Some(ContentControlMut::Row(sdt))
+                } else {
+                    find_rows(&mut sdt.content.content, name)
+                }
+            }
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn find_cells<'s, 'a>(
+    content: &'s mut [TableRowContent<'a>],
+    name: &str,
+) -> Option<ContentControlMut<'s, 'a>> {
+    for content in content {
+        let found = match content {
+            TableRowContent::Cell(cell) => find_cell(cell, name),
+            TableRowContent::Sdt(sdt) => {
+                if ContentControl::Cell(sdt).matches(name) {
+                    Some(ContentControlMut::Cell(sdt))
+                } else {
+                    find_cells(&mut sdt.content.content, name)
+                }
+            }
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn find_cell<'s, 'a>(cell: &'s mut TableCell<'a>, name: &str) -> Option<ContentControlMut<'s, 'a>> {
+    for content in &mut cell.content {
+        let found = match content {
+            TableCellContent::Paragraph(para) => find_runs(&mut para.content, name),
+            TableCellContent::Sdt(sdt) => find_block(sdt, name),
+            TableCellContent::Table(table) => find_rows(&mut table.rows, name),
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn find_runs<'s, 'a>(
+    content: &'s mut [ParagraphContent<'a>],
+    name: &str,
+) -> Option<ContentControlMut<'s, 'a>> {
+    for content in content {
+        let found = match content {
+            ParagraphContent::Sdt(sdt) => {
+                if ContentControl::Run(sdt).matches(name) {
+                    Some(ContentControlMut::Run(sdt))
+                } else {
+                    find_runs(&mut sdt.content.content, name)
+                }
+            }
+            ParagraphContent::SmartTag(tag) => find_runs(&mut tag.content, name),
+            ParagraphContent::SimpleField(field) => find_runs(&mut field.content, name),
+            ParagraphContent::Insertion(ins) => find_runs(&mut ins.content, name),
+            ParagraphContent::Deletion(del) => find_runs(&mut del.content, name),
+            ParagraphContent::MoveFrom(from) => find_runs(&mut from.content, name),
+            ParagraphContent::MoveTo(to) => find_runs(&mut to.content, name),
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// Calls `f` on every content control of the blocks in document order,
+/// before visiting the content of the control.
+pub(crate) fn visit_blocks_mut<'a>(
+    content: &mut [BodyContent<'a>],
+    f: &mut dyn FnMut(ContentControlMut<'_, 'a>),
+) {
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => visit_runs_mut(&mut para.content, f),
+            BodyContent::Table(table) => visit_rows_mut(&mut table.rows, f),
+            BodyContent::Sdt(sdt) => {
+                f(ContentControlMut::Block(sdt));
+                visit_blocks_mut(&mut sdt.content.content, f);
+            }
+        }
+    }
+}
+
+fn visit_rows_mut<'a>(
+    content: &mut [TableContent<'a>],
+    f: &mut dyn FnMut(ContentControlMut<'_, 'a>),
+) {
+    for content in content {
+        match content {
+            TableContent::Row(row) => visit_cells_mut(&mut row.cells, f),
+            TableContent::Sdt(sdt) => {
+                f(ContentControlMut::Row(sdt));
+                visit_rows_mut(&mut sdt.content.content, f);
+            }
+        }
+    }
+}
+
+fn visit_cells_mut<'a>(
+    content: &mut [TableRowContent<'a>],
+    f: &mut dyn FnMut(ContentControlMut<'_, 'a>),
+) {
+    for content in content {
+        match content {
+            TableRowContent::Cell(cell) => {
+                for content in &mut cell.content {
+                    match content {
+                        TableCellContent::Paragraph(para) => visit_runs_mut(&mut para.content, f),
+                        TableCellContent::Sdt(sdt) => {
+                            f(ContentControlMut::Block(sdt));
+                            visit_blocks_mut(&mut sdt.content.content, f);
+                        }
+                        TableCellContent::Table(table) => visit_rows_mut(&mut table.rows, f),
+                    }
+                }
+            }
+            TableRowContent::Sdt(sdt) => {
+                f(ContentControlMut::Cell(sdt));
+                visit_cells_mut(&mut sdt.content.content, f);
+            }
+        }
+    }
+}
+
+fn visit_runs_mut<'a>(
+    content: &mut [ParagraphContent<'a>],
+    f: &mut dyn FnMut(ContentControlMut<'_, 'a>),
+) {
+    for content in content {
+        match content {
+            ParagraphContent::Sdt(sdt) => {
+                f(ContentControlMut::Run(sdt));
+                visit_runs_mut(&mut sdt.content.content, f);
+            }
+            ParagraphContent::SmartTag(tag) => visit_runs_mut(&mut tag.content, f),
+            ParagraphContent::SimpleField(field) => visit_runs_mut(&mut field.content, f),
+            ParagraphContent::Insertion(ins) => visit_runs_mut(&mut ins.content, f),
+            ParagraphContent::Deletion(del) => visit_runs_mut(&mut del.content, f),
+            ParagraphContent::MoveFrom(from) => visit_runs_mut(&mut from.content, f),
+            ParagraphContent::MoveTo(to) => visit_runs_mut(&mut to.content, f),
+            _ => (),
+        }
+    }
+}
+
+fn blocks_text(content: &[BodyContent]) -> String {
+    let mut lines = Vec::new();
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => lines.push(para.iter_text().map(|t| &**t).collect()),
+            BodyContent::Table(table) => lines.extend(
+                table
+                    .iter_rows()
+                    .flat_map(|row| row.iter_cells())
+                    .map(cell_text),
+            ),
+            BodyContent::Sdt(sdt) => lines.push(blocks_text(&sdt.content.content)),
+        }
+    }
+    lines.join("\n")
+}
+
+fn cell_text(cell: &TableCell) -> String {
+    let mut lines = Vec::new();
+    for content in &cell.content {
+        match content {
+            TableCellContent::Paragraph(para) => {
+                lines.push(para.iter_text().map(|t| &**t).collect())
+            }
+            TableCellContent::Sdt(sdt) => lines.push(blocks_text(&sdt.content.content)),
+            TableCellContent::Table(table) => lines.extend(
+                table
+                    .iter_rows()
+                    .flat_map(|row| row.iter_cells())
+                    .map(cell_text),
+            ),
+        }
+    }
+    lines.join("\n")
+}
+
+/// Returns paragraphs of the lines, formatted like the first paragraph and run.
+fn text_paragraphs<'a>(
+    first: Option<&Paragraph<'a>>,
+    lines: &[&str],
+    placeholder: bool,
+) -> Vec<Paragraph<'a>> {
+    let (para_property, run_property) = match first {
+        Some(para) => (para.property.clone(), first_run_property(&para.content)),
+        None => Default::default(),
+    };
+    let run_property = text_property(run_property, placeholder);
+
+    lines
+        .iter()
+        .map(|line| Paragraph {
+            property: para_property.clone(),
+            content: vec![text_run(run_property.clone(), line).into()],
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Puts a line in each cell, and the remaining lines in the last one.
+fn set_cells_text(cells: Vec<&mut TableCell>, lines: &[&str], placeholder: bool) {
+    let count = cells.len();
+    for (index, cell) in cells.into_iter().enumerate() {
+        let lines = match lines.get(index..) {
+            Some(rest) if index + 1 == count => rest,
+            Some(rest) if !rest.is_empty() => &rest[..1],
+            _ => &[""],
+        };
+
+        let first = cell.content.iter().find_map(|content| match content {
+            TableCellContent::Paragraph(para) => Some(para),
+            TableCellContent::Sdt(sdt) => first_paragraph(&sdt.content.content),
+            TableCellContent::Table(_) => None,
+        });
+        cell.content = text_paragraphs(first, lines, placeholder)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+    }
+}
+
+fn first_paragraph<'s, 'a>(content: &'s [BodyContent<'a>]) -> Option<&'s Paragraph<'a>> {
+    content.iter().find_map(|content| match content {
+        BodyContent::Paragraph(para) => Some(para),
+        BodyContent::Sdt(sdt) => first_paragraph(&sdt.content.content),
+        BodyContent::Table(_) => None,
+    })
+}
+
+fn first_run_property<'a>(content: &[ParagraphContent<'a>]) -> CharacterProperty<'a> {
+    content
+        .iter()
+        .find_map(|content| match content {
+            ParagraphContent::Run(run) => Some(run.property.clone()),
+            ParagraphContent::Sdt(sdt) => Some(first_run_property(&sdt.content.content)),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Drops the placeholder style when the placeholder text gets replaced.
+fn text_property(mut property: CharacterProperty, placeholder: bool) -> CharacterProperty {
+    let is_placeholder_style = property
+        .style_id
+        .as_ref()
+        .is_some_and(|style| style.value == PLACEHOLDER_STYLE);
+    if placeholder && is_placeholder_style {
+        property.style_id = None;
+    }
+    property.change = None;
+    property
+}
+
+fn text_run<'a>(property: CharacterProperty<'a>, text: &str) -> Run<'a> {
+    Run {
+        property,
+        content: vec![RunContent::Text(Text {
+            space: Some(TextSpace::Preserve),
+            text: text.to_owned().into(),
+        })],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{
+        SdtCheckbox, SdtDate, SdtDropDownList, SdtLockVal, SdtText, ShowingPlaceholder, TableRow,
+    };
+
+    fn sample() -> Document<'static> {
+        let mut document = Document::default();
+        document.push(
+            BlockSdt::default()
+                .property(
+                    SdtProperty::default()
+                        .alias("Summary")
+                        .tag("summary")
+                        .showing_placeholder(ShowingPlaceholder::default()),
+                )
+                .push(
+                    Paragraph::default().push(
+                        Run::default()
+                            .property(CharacterProperty::default().style_id(PLACEHOLDER_STYLE))
+                            .push_text("Enter a summary"),
+                    ),
+                ),
+        );
+        document.push(
+            Paragraph::default()
+                .push(
+                    RunSdt::default()
+                        .property(
+                            SdtProperty::default()
+                                .tag("agree")
+                                .ty(SdtCheckbox::default().checked(false)),
+                        )
+                        .push(Run::default().push_text("\u{2610}")),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(SdtProperty::default().tag("date").ty(SdtDate::default()))
+                        .push(Run::default()),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(
+                            SdtProperty::default()
+                                .tag("color")
+                                .ty(SdtDropDownList::default()
+                                    .push_item("Red")
+                                    .push_item("Blue")),
+                        )
+                        .push(Run::default()),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(
+                            SdtProperty::default()
+                                .tag("locked")
+                                .lock(SdtLockVal::ContentLocked)
+                                .ty(SdtText::default()),
+                        )
+                        .push(Run::default().push_text("fixed")),
+                ),
+        );
+        document.push(
+            crate::document::Table::default().push_row(
+                RowSdt::default()
+                    .property(SdtProperty::default().tag("rows"))
+                    .push(
+                        TableRow::default().push_cell(
+                            Paragraph::default().push(
+                                RunSdt::default()
+                                    .property(SdtProperty::default().tag("cell"))
+                                    .push(Run::default().push_text("value")),
+                            ),
+                        ),
+                    ),
+            ),
+        );
+        document
+    }
+
+    #[test]
+    fn find_content_controls() {
+        let document = sample();
+        let tags: Vec<_> = document
+            .content_controls()
+            .iter()
+            .map(|control| control.tag().unwrap())
+            .collect();
+        assert_eq!(
+            tags,
+            ["summary", "agree", "date", "color", "locked", "rows", "cell"]
+        );
+
+        let summary = document.content_control("Summary").unwrap();
+        assert_eq!(summary.tag(), Some("summary"));
+        assert_eq!(summary.text(), "Enter a summary");
+        assert_eq!(document.content_control("rows").unwrap().text(), "value");
+        assert!(document.content_control("missing").is_none());
+    }
+
+    #[test]
+    fn set_content_controls() {
+        let mut document = sample();
+
+        let mut summary = document.content_control_mut("summary").unwrap();
+        assert!(summary.set_text("first\nsecond"));
+        assert!(!summary.property().is_showing_placeholder());
+        match &document.body.content[0] {
+            BodyContent::Sdt(sdt) => {
+                assert_eq!(sdt.content.content.len(), 2);
+                match &sdt.content.content[0] {
+                    BodyContent::Paragraph(para) => match &para.content[0] {
+                        ParagraphContent::Run(run) => assert!(run.property.style_id.is_none()),
+                        _ => panic!("expected a run"),
+                    },
+                    _ => panic!("expected a paragraph"),
+                }
+            }
+            _ => panic!("expected a content control"),
+        }
+
+        assert!(document
+            .content_control_mut("agree")
+            .unwrap()
+            .set_checked(true));
+        let agree = document.content_control("agree").unwrap();
+        assert_eq!(agree.text(), "\u{2612}");
+        match agree.ty() {
+            Some(SdtType::Checkbox(checkbox)) => assert!(checkbox.is_checked()),
+            _ => panic!("expected a checkbox"),
+        }
+
+        let mut date = document.content_control_mut("date").unwrap();
+        assert!(date.set_date("2020-01-02T00:00:00Z", "1/2/2020"));
+        assert!(!date.set_checked(true));
+        assert_eq!(document.content_control("date").unwrap().text(), "1/2/2020");
+
+        let mut color = document.content_control_mut("color").unwrap();
+        assert!(!color.select_item("Green"));
+        assert!(color.select_item("Blue"));
+        assert_eq!(document.content_control("color").unwrap().text(), "Blue");
+
+        assert!(!document
+            .content_control_mut("locked")
+            .unwrap()
+            .set_text("changed"));
+        assert_eq!(document.content_control("locked").unwrap().text(), "fixed");
+
+        assert!(document
+            .content_control_mut("cell")
+            .unwrap()
+            .set_text("new"));
+        assert_eq!(document.content_control("rows").unwrap().text(), "new");
+
+        // the rows of a row-level control get a line per cell
+        let mut rows = document.content_control_mut("rows").unwrap();
+        assert!(rows.set_text("one\ntwo\nthree"));
+        match &document.body.content[2] {
+            BodyContent::Table(table) => {
+                let cell = table.iter_rows().next().unwrap().iter_cells().next().unwrap();
+                assert_eq!(cell.content.len(), 3);
+            }
+            _ => panic!("expected a table"),
+        }
+        assert_eq!(
+            document.content_control("rows").unwrap().text(),
+            "one\ntwo\nthree"
+        );
+    }
+}
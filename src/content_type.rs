@@ -16,8 +16,39 @@ const CONTENT_TYPE_DOCUMENT: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml";
 const CONTENT_TYPE_STYLES: &str =
     "application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml";
+pub(crate) const CONTENT_TYPE_SETTINGS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml";
+pub(crate) const CONTENT_TYPE_NUMBERING: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml";
+pub(crate) const CONTENT_TYPE_COMMENTS: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.comments+xml";
+pub(crate) const CONTENT_TYPE_FOOTNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml";
+pub(crate) const CONTENT_TYPE_ENDNOTES: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.endnotes+xml";
+pub(crate) const CONTENT_TYPE_HEADER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml";
+pub(crate) const CONTENT_TYPE_FOOTER: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml";
+pub(crate) const CONTENT_TYPE_CUSTOM_XML_PROPERTIES: &str =
+    "application/vnd.openxmlformats-officedocument.customXmlProperties+xml";
 
-#[derive(Debug, XmlRead)]
+/// Returns the content type of an image by its file extension.
+pub(crate) fn image_content_type(ext: &str) -> Option<&'static str> {
+    match ext.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "bmp" => Some("image/bmp"),
+        "tif" | "tiff" => Some("image/tiff"),
+        "svg" => Some("image/svg+xml"),
+        "emf" => Some("image/x-emf"),
+        "wmf" => Some("image/x-wmf"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, XmlRead)]
 #[xml(tag = "Types")]
 pub struct ContentTypes<'a> {
     #[xml(child = "Default")]
@@ -61,6 +92,37 @@ impl Default for ContentTypes<'static> {
     }
 }
 
+impl<'a> ContentTypes<'a> {
+    /// Adds an override for the given part, unless the part is already declared.
+    pub fn add_override<T: Into<Cow<'a, str>>>(&mut self, part: T, ty: &'a str) {
+        let part = part.into();
+
+        if self.overrides.iter().any(|o| o.part == part) {
+            return;
+        }
+
+        self.overrides.push(OverrideContentType {
+            part,
+            ty: ty.into(),
+        });
+    }
+
+    /// Adds a default content type for the given extension, unless the
+    /// extension is already declared.
+    pub fn add_default<T: Into<Cow<'a, str>>>(&mut self, ext: T, ty: &'a str) {
+        let ext = ext.into();
+
+        if self.defaults.iter().any(|d| d.ext.eq_ignore_ascii_case(&ext)) {
+            return;
+        }
+
+        self.defaults.push(DefaultContentType {
+            ext,
+            ty: ty.into(),
+        });
+    }
+}
+
 impl<'a> XmlWrite for ContentTypes<'a> {
     fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
         let ContentTypes {
@@ -93,7 +155,7 @@ impl<'a> XmlWrite for ContentTypes<'a> {
     }
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[xml(tag = "Default")]
 pub struct DefaultContentType<'a> {
     #[xml(attr = "Extension")]
@@ -102,7 +164,7 @@ pub struct DefaultContentType<'a> {
     pub ty: Cow<'a, str>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[xml(tag = "Override")]
 pub struct OverrideContentType<'a> {
     #[xml(attr = "PartName")]
@@ -0,0 +1,567 @@
+//! Shared options and helpers of the format converters
+//!
+//! Mail merge and the Markdown, HTML and pandoc importers insert images given
+//! as [`Image`]s, and the converters map the constructs which documents don't
+//! have, like block quotes and code blocks, to the styles of [`StyleIds`].
+//! The Markdown, HTML and LaTeX exporters share the writing of inline
+//! content, walked with the [`Visit`] trait, and of nested lists.
+//!
+//! [`Visit`]: crate::visit::Visit
+
+use bytes::Bytes;
+
+use crate::{
+    base64,
+    document::{
+        BodyContent, BookmarkStart, Deletion, Hyperlink, MoveFrom, Paragraph, ParagraphContent,
+        Run, RunContent, TableCell, TableCellContent, Text, TextSpace,
+    },
+    formatting::{
+        BorderStyle, Borders, CharacterProperty, LeftBorder, ParagraphProperty, TableCellProperty,
+        UnderlineStyle, VerticalMergeVal,
+    },
+    numbering::ListItem,
+    private_prelude::*,
+    styles::{Style, StyleType, Styles},
+    visit::{walk, Visit},
+    Docx,
+};
+
+/// Total width of the tables created by the converters, in twentieths of a
+/// point: the text width of a Letter page with one-inch margins.
+pub(crate) const TABLE_WIDTH: usize = 9360;
+
+/// An image inserted as an inline picture
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    /// Specifies the content of the image file.
+    pub data: Bytes,
+    /// Specifies the file extension, e.g. `png`.
+    pub extension: String,
+    /// Specifies the width, in EMUs.
+    pub width: usize,
+    /// Specifies the height, in EMUs.
+    pub height: usize,
+}
+
+impl Image {
+    pub fn new<D, E>(data: D, extension: E, width: usize, height: usize) -> Self
+    where
+        D: Into<Bytes>,
+        E: Into<String>,
+    {
+        Image {
+            data: data.into(),
+            extension: extension.into(),
+            width,
+            height,
+        }
+    }
+}
+
+/// The style ids of constructs without a direct counterpart in documents,
+/// e.g. block quotes and code blocks
+#[derive(Clone, Debug)]
+pub struct StyleIds<'a> {
+    /// Specifies the paragraph styles of the headings of each level.
+    pub headings: [Cow<'a, str>; 6],
+    /// Specifies the paragraph style of block quotes.
+    pub quote: Cow<'a, str>,
+    /// Specifies the paragraph style of the lines of code blocks.
+    pub code_block: Cow<'a, str>,
+    /// Specifies the character style of code spans.
+    pub code_span: Cow<'a, str>,
+    /// Specifies the paragraph style of list items.
+    pub list: Cow<'a, str>,
+    /// Specifies the character style of links.
+    pub hyperlink: Cow<'a, str>,
+    /// Specifies the style of tables.
+    pub table: Cow<'a, str>,
+}
+
+impl Default for StyleIds<'_> {
+    fn default() -> Self {
+        StyleIds {
+            headings: [
+                "Heading1".into(),
+                "Heading2".into(),
+                "Heading3".into(),
+                "Heading4".into(),
+                "Heading5".into(),
+                "Heading6".into(),
+            ],
+            quote: "Quote".into(),
+            code_block: "SourceCode".into(),
+            code_span: "VerbatimChar".into(),
+            list: "ListParagraph".into(),
+            hyperlink: "Hyperlink".into(),
+            table: "Table".into(),
+        }
+    }
+}
+
+impl<'a> StyleIds<'a> {
+    /// Returns the built-in definitions of the styles.
+    pub fn definitions(&self) -> Vec<Style<'a>> {
+        const HEADING_SIZES: [usize; 6] = [32, 28, 26, 24, 22, 22];
+
+        let mut styles: Vec<Style<'a>> = self
+            .headings
+            .iter()
+            .zip(HEADING_SIZES.iter())
+            .enumerate()
+            .map(|(level, (style_id, size))| {
+                Style::new(StyleType::Paragraph, style_id.clone())
+                    .name(format!("heading {}", level + 1))
+                    .paragraph(ParagraphProperty::default().outline_level(level))
+                    .character(
+                        CharacterProperty::default()
+                            .bold(true)
+                            .size(*size)
+                            .color("2F5496"),
+                    )
+            })
+            .collect();
+
+        styles.push(
+            Style::new(StyleType::Paragraph, self.quote.clone())
+                .name("Quote")
+                .paragraph(
+                    ParagraphProperty::default().border(
+                        Borders::default().left(
+                            LeftBorder::default()
+                                .style(BorderStyle::Single)
+                                .size(12usize)
+                                .space(8usize)
+                                .color("BFBFBF"),
+                        ),
+                    ),
+                )
+                .character(CharacterProperty::default().italics(true).color("595959")),
+        );
+        styles.push(
+            Style::new(StyleType::Paragraph, self.code_block.clone())
+                .name("Source Code")
+                .character(CharacterProperty::default().size(20usize)),
+        );
+        styles.push(
+            Style::new(StyleType::Character, self.code_span.clone())
+                .name("Verbatim Char")
+                .character(CharacterProperty::default().size(20usize).color("C7254E")),
+        );
+        styles.push(Style::new(StyleType::Paragraph, self.list.clone()).name("List Paragraph"));
+        styles.push(
+            Style::new(StyleType::Character, self.hyperlink.clone())
+                .name("Hyperlink")
+                .character(
+                    CharacterProperty::default()
+                        .color("0563C1")
+                        .underline(UnderlineStyle::Single),
+                ),
+        );
+        styles.push(Style::new(StyleType::Table, self.table.clone()).name("Table"));
+
+        styles
+    }
+
+    /// Adds the definitions of the styles which aren't defined yet.
+    pub(crate) fn add_definitions(&self, styles: &mut Styles<'a>) {
+        for style in self.definitions() {
+            if styles.get(&style.style_id).is_none() {
+                styles.push(style);
+            }
+        }
+    }
+}
+
+/// Creates a text, preserving its leading and trailing spaces.
+pub(crate) fn text_of<'a>(text: &str) -> Text<'a> {
+    if text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace) {
+        Text::from((text.to_owned(), TextSpace::Preserve))
+    } else {
+        Text::from(text.to_owned())
+    }
+}
+
+/// Creates a table cell spanning columns, and rows if it starts or continues
+/// a vertical merge.
+pub(crate) fn merged_cell<'a>(
+    span: usize,
+    merge: Option<VerticalMergeVal>,
+    mut content: Vec<TableCellContent<'a>>,
+) -> TableCell<'a> {
+    let mut property = TableCellProperty::default();
+    if span > 1 {
+        property = property.grid_span(span);
+    }
+    if let Some(merge) = merge {
+        property = property.vertical_merge(merge);
+    }
+    // a cell contains at least one paragraph
+    if content.is_empty() {
+        content.push(Paragraph::default().into());
+    }
+    TableCell { property, content }
+}
+
+/// The cells merged with the cells below them, by column: the number of
+/// rows left and the number of columns spanned
+#[derive(Default)]
+pub(crate) struct VerticalMerges(Vec<(usize, usize)>);
+
+impl VerticalMerges {
+    /// Starts a merge at a column if the cell spans rows, returning its
+    /// vertical merge.
+    pub fn start(
+        &mut self,
+        column: usize,
+        span: usize,
+        rows_spanned: usize,
+    ) -> Option<VerticalMergeVal> {
+        if rows_spanned < 2 {
+            return None;
+        }
+        if self.0.len() < column + 1 {
+            self.0.resize(column + 1, (0, 0));
+        }
+        self.0[column] = (rows_spanned - 1, span);
+        Some(VerticalMergeVal::Restart)
+    }
+
+    /// Continues the merge at a column, returning the number of columns it
+    /// spans.
+    pub fn next(&mut self, column: usize) -> Option<usize> {
+        let (rows_left, span) = self
+            .0
+            .get_mut(column)
+            .filter(|(rows_left, _)| *rows_left > 0)?;
+        *rows_left -= 1;
+        Some(*span)
+    }
+
+    /// Returns the first column from the given one where a merge continues.
+    pub fn next_column(&self, column: usize) -> Option<usize> {
+        (column..self.0.len()).find(|&index| self.0[index].0 > 0)
+    }
+}
+
+/// Collects the paragraphs of a cell, including the ones of nested tables.
+pub(crate) fn cell_paragraphs<'p, 'a>(
+    content: &'p [TableCellContent<'a>],
+    paragraphs: &mut Vec<&'p Paragraph<'a>>,
+) {
+    for content in content {
+        match content {
+            TableCellContent::Paragraph(para) => paragraphs.push(para),
+            TableCellContent::Sdt(sdt) => {
+                for content in &sdt.content.content {
+                    if let BodyContent::Paragraph(para) = content {
+                        paragraphs.push(para);
+                    }
+                }
+            }
+            TableCellContent::Table(table) => {
+                for cell in table.iter_rows().flat_map(|row| row.iter_cells()) {
+                    cell_paragraphs(&cell.content, paragraphs);
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` unless a toggle property is turned off.
+pub(crate) fn is_on(value: Option<bool>) -> bool {
+    value != Some(false)
+}
+
+/// A piece of inline content
+pub(crate) struct Piece<F> {
+    pub format: F,
+    pub link: Option<String>,
+    pub text: String,
+}
+
+/// A converter writing the inline content of paragraphs
+///
+/// The content is split into pieces, which are written by groups of the
+/// same link and, within them, of the same formatting.
+pub(crate) trait InlineWriter: Sized {
+    /// Formatting of a piece of inline content
+    type Format: Clone + Default + PartialEq;
+
+    fn docx(&self) -> &Docx<'_>;
+
+    /// Returns the formatting of a run.
+    fn format(&self, property: &CharacterProperty) -> Self::Format;
+
+    /// Converts the content of a run, or returns `None` to skip it.
+    fn run_content(&mut self, content: &RunContent, format: &Self::Format) -> Option<String>;
+
+    /// Writes the content of pieces of the same formatting.
+    fn formatted(format: &Self::Format, content: String) -> String;
+
+    /// Writes the text of a link.
+    fn link(&self, href: &str, text: String) -> String;
+
+    /// Returns the target of a link to a bookmark.
+    fn anchor_href(&self, anchor: &str) -> String {
+        format!("#{}", anchor)
+    }
+
+    /// Starts a bookmark, returning the anchor to write if any.
+    fn bookmark(&mut self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// Converts the content of a paragraph.
+    fn inline(&mut self, content: &[ParagraphContent]) -> String {
+        let mut pieces = Pieces {
+            writer: &mut *self,
+            link: None,
+            pieces: Vec::new(),
+        };
+        for content in content {
+            pieces.visit_paragraph_content(content);
+        }
+        let pieces = pieces.pieces;
+
+        groups(&pieces, |piece| &piece.link)
+            .map(|group| {
+                let text: String = groups(group, |piece| &piece.format)
+                    .map(|group| {
+                        let content = group.iter().map(|piece| piece.text.as_str()).collect();
+                        Self::formatted(&group[0].format, content)
+                    })
+                    .collect();
+                match &group[0].link {
+                    Some(href) => self.link(href, text),
+                    None => text,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Collects the pieces of inline content, with the link they belong to
+struct Pieces<'w, W: InlineWriter> {
+    writer: &'w mut W,
+    link: Option<String>,
+    pieces: Vec<Piece<W::Format>>,
+}
+
+impl<'a, W: InlineWriter> Visit<'a> for Pieces<'_, W> {
+    fn visit_run(&mut self, run: &Run<'a>) {
+        let property = &run.property;
+        if property
+            .vanish
+            .as_ref()
+            .is_some_and(|vanish| is_on(vanish.value))
+        {
+            return;
+        }
+        let format = self.writer.format(property);
+
+        for content in &run.content {
+            if let Some(text) = self.writer.run_content(content, &format) {
+                self.pieces.push(Piece {
+                    format: format.clone(),
+                    link: self.link.clone(),
+                    text,
+                });
+            }
+        }
+    }
+
+    fn visit_hyperlink(&mut self, link: &Hyperlink<'a>) {
+        let href = match (&link.id, &link.anchor) {
+            (Some(id), _) => self
+                .writer
+                .docx()
+                .document_rels
+                .as_ref()
+                .and_then(|rels| rels.get_target(id))
+                .map(str::to_owned),
+            (None, Some(anchor)) => Some(self.writer.anchor_href(anchor)),
+            (None, None) => None,
+        };
+        let outer = self.link.clone();
+        self.link = href.or_else(|| outer.clone());
+        walk::hyperlink(self, link);
+        self.link = outer;
+    }
+
+    fn visit_bookmark_start(&mut self, bookmark: &BookmarkStart<'a>) {
+        match bookmark.name.as_deref() {
+            // the bookmark of the last editing position
+            Some("_GoBack") | None => (),
+            Some(name) => {
+                if let Some(text) = self.writer.bookmark(name) {
+                    self.pieces.push(Piece {
+                        format: Default::default(),
+                        link: None,
+                        text,
+                    });
+                }
+            }
+        }
+    }
+
+    // deleted content isn't written
+    fn visit_deletion(&mut self, _: &Deletion<'a>) {}
+
+    fn visit_move_from(&mut self, _: &MoveFrom<'a>) {}
+}
+
+/// Splits items into runs of consecutive items with equal keys.
+fn groups<T, K: PartialEq>(items: &[T], key: impl Fn(&T) -> &K) -> impl Iterator<Item = &[T]> {
+    let mut rest = items;
+    std::iter::from_fn(move || {
+        let first = rest.first()?;
+        let end = rest
+            .iter()
+            .position(|item| key(item) != key(first))
+            .unwrap_or(rest.len());
+        let (group, tail) = rest.split_at(end);
+        rest = tail;
+        Some(group)
+    })
+}
+
+/// A list being written
+pub(crate) struct OpenList {
+    pub num_id: usize,
+    pub level: usize,
+    pub ordered: bool,
+}
+
+/// A converter writing nested lists, whose ends are written explicitly
+pub(crate) trait ListWriter {
+    /// Returns the lists being written, the innermost last.
+    fn open_lists(&mut self) -> &mut Vec<OpenList>;
+
+    /// Writes the start of the list of an item.
+    fn start_list(&mut self, item: &ListItem);
+
+    /// Writes the end of a list.
+    fn end_list(&mut self, list: &OpenList);
+
+    /// Closes the lists an item doesn't belong to, and starts its list
+    /// unless it's open. Returns `true` if the item continues the innermost
+    /// list.
+    fn enter_list(&mut self, item: &ListItem) -> bool {
+        let ordered = !item.is_bullet();
+        while self.open_lists().last().is_some_and(|list| {
+            let other_list = list.num_id != item.num_id || list.ordered != ordered;
+            list.level > item.level || (list.level == item.level && other_list)
+        }) {
+            self.close_list();
+        }
+
+        if matches!(self.open_lists().last(), Some(list) if list.level == item.level) {
+            return true;
+        }
+        self.start_list(item);
+        self.open_lists().push(OpenList {
+            num_id: item.num_id,
+            level: item.level,
+            ordered,
+        });
+        false
+    }
+
+    fn close_list(&mut self) {
+        if let Some(list) = self.open_lists().pop() {
+            self.end_list(&list);
+        }
+    }
+
+    fn close_lists(&mut self) {
+        while !self.open_lists().is_empty() {
+            self.close_list();
+        }
+    }
+}
+
+/// Returns the properties of a style, following its `basedOn` chain.
+pub(crate) fn resolve<'a>(
+    styles: &Styles<'a>,
+    style: &Style<'a>,
+) -> (CharacterProperty<'a>, ParagraphProperty<'a>) {
+    let mut chain = vec![style];
+    // guards against cyclic `basedOn` chains
+    for _ in 0..styles.styles.len() {
+        let base = chain
+            .last()
+            .and_then(|style| style.based_on.as_ref())
+            .and_then(|based_on| styles.get(&based_on.value));
+        match base {
+            Some(base) => chain.push(base),
+            None => break,
+        }
+    }
+
+    let mut character = CharacterProperty::default();
+    let mut paragraph = ParagraphProperty::default();
+    for style in chain.iter().rev() {
+        character.merge(&style.character);
+        paragraph.merge(&style.paragraph);
+    }
+    (character, paragraph)
+}
+
+/// Decodes an image inlined as a base64 data URI, returning its content and
+/// its extension.
+pub(crate) fn decode_data_uri(uri: &str) -> Option<(Vec<u8>, &'static str)> {
+    let (header, data) = uri.strip_prefix("data:")?.split_once(',')?;
+    let header = header.to_ascii_lowercase();
+    let extension = match header.split(';').next()? {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/bmp" => "bmp",
+        "image/tiff" => "tiff",
+        "image/svg+xml" => "svg",
+        _ => return None,
+    };
+    if !header.ends_with(";base64") {
+        return None;
+    }
+    Some((base64::decode(data)?, extension))
+}
+
+/// Reads the size of a PNG, GIF or JPEG image from its header.
+pub(crate) fn pixel_size(data: &[u8]) -> Option<(usize, usize)> {
+    let be16 = |index: usize| {
+        Some(u16::from_be_bytes([*data.get(index)?, *data.get(index + 1)?]) as usize)
+    };
+    let le16 = |index: usize| {
+        Some(u16::from_le_bytes([*data.get(index)?, *data.get(index + 1)?]) as usize)
+    };
+    let be32 = |index: usize| {
+        let bytes = data.get(index..index + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    };
+
+    let size = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        (be32(16)?, be32(20)?)
+    } else if data.starts_with(b"GIF8") {
+        (le16(6)?, le16(8)?)
+    } else if data.starts_with(&[0xff, 0xd8]) {
+        // the size is in the frame header, after a start of frame marker
+        let mut index = 2;
+        loop {
+            if *data.get(index)? != 0xff {
+                return None;
+            }
+            let marker = *data.get(index + 1)?;
+            if matches!(marker, 0xc0..=0xc3 | 0xc5..=0xc7 | 0xc9..=0xcb | 0xcd..=0xcf) {
+                break (be16(index + 7)?, be16(index + 5)?);
+            }
+            index += 2 + be16(index + 2)?;
+        }
+    } else {
+        return None;
+    };
+
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
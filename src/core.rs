@@ -7,7 +7,7 @@ use std::io::Write;
 use crate::private_prelude::*;
 use crate::schema::SCHEMA_CORE;
 
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[xml(tag = "cp:coreProperties")]
 pub struct Core<'a> {
     #[xml(flatten_text = "dc:title")]
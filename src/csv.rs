@@ -0,0 +1,111 @@
+//! Delimiter-separated values, as described by RFC 4180.
+
+/// Parses delimiter-separated values into rows of fields.
+///
+/// Fields may be quoted with `"`, in which case they can contain delimiters,
+/// line breaks and doubled quotes. Lines end with `\n` or `\r\n`, and an empty
+/// last line is ignored.
+pub(crate) fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let text = text.trim_start_matches('\u{feff}');
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            c if c == delimiter => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Writes rows of fields as delimiter-separated values, lines ending with
+/// `\r\n`.
+///
+/// Fields containing delimiters, quotes, line breaks or surrounding spaces
+/// are quoted.
+pub(crate) fn write<S: AsRef<str>>(rows: &[Vec<S>], delimiter: char) -> String {
+    let mut text = String::new();
+    for row in rows {
+        for (index, field) in row.iter().enumerate() {
+            if index > 0 {
+                text.push(delimiter);
+            }
+            let field = field.as_ref();
+            if field.contains([delimiter, '"', '\n', '\r']) || field.trim() != field {
+                text.push('"');
+                text += &field.replace('"', "\"\"");
+                text.push('"');
+            } else {
+                text += field;
+            }
+        }
+        text += "\r\n";
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rows() {
+        assert_eq!(
+            parse("a,b\r\n\"1,5\",\"say \"\"hi\"\"\"\n\"multi\nline\",\n", ','),
+            vec![
+                vec!["a", "b"],
+                vec!["1,5", "say \"hi\""],
+                vec!["multi\nline", ""],
+            ]
+        );
+        assert_eq!(
+            parse("a\tb\n\nc", '\t'),
+            vec![vec!["a", "b"], vec![""], vec!["c"]]
+        );
+        assert!(parse("", ',').is_empty());
+    }
+
+    #[test]
+    fn write_rows() {
+        let rows = vec![
+            vec!["a", "1,5"],
+            vec!["say \"hi\"", " padded"],
+            vec!["multi\nline", ""],
+        ];
+        let text = write(&rows, ',');
+        assert_eq!(
+            text,
+            "a,\"1,5\"\r\n\"say \"\"hi\"\"\",\" padded\"\r\n\"multi\nline\",\r\n"
+        );
+        assert_eq!(parse(&text, ','), rows);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    error::DocxResult,
+    xml_tree::{self, Element, SCHEMA_XML},
+};
+
+/// A parsed custom XML data document
+///
+/// Evaluates the XPath subset used by data bindings: absolute location paths
+/// made of element names, optionally prefixed, `*`, positional predicates
+/// like `[1]`, and a trailing `@attribute` or `text()` step.
+///
+/// ```rust
+/// use docx::custom_xml::DataStore;
+///
+/// let store = DataStore::parse(
+///     r#"<invoice xmlns="urn:invoice" number="42"><customer>John</customer></invoice>"#,
+/// )
+/// .unwrap();
+///
+/// let mappings = Some("xmlns:ns0='urn:invoice'");
+/// assert_eq!(store.select("/ns0:invoice[1]/ns0:customer[1]", mappings).as_deref(), Some("John"));
+/// assert_eq!(store.select("/ns0:invoice/@number", mappings).as_deref(), Some("42"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataStore {
+    root: Element,
+}
+
+impl DataStore {
+    /// Parses the root element of a custom XML part.
+    pub fn parse(xml: &str) -> DocxResult<DataStore> {
+        let root = xml_tree::parse(xml, &[])?;
+        Ok(DataStore { root })
+    }
+
+    /// Returns the string value of the first node selected by the XPath
+    /// expression, or `None` if no node is selected or the expression isn't
+    /// supported.
+    ///
+    /// `prefix_mappings` declares the prefixes used in the expression, in the
+    /// form of `w:prefixMappings`, e.g. `xmlns:ns0='urn:invoice'`.
+    pub fn select(&self, xpath: &str, prefix_mappings: Option<&str>) -> Option<String> {
+        let mappings = parse_prefix_mappings(prefix_mappings.unwrap_or_default());
+        let resolve = |prefix: Option<&str>| -> Option<Option<&str>> {
+            match prefix {
+                None => Some(None),
+                Some("xml") => Some(Some(SCHEMA_XML)),
+                Some(prefix) => mappings
+                    .iter()
+                    .find(|(key, _)| *key == prefix)
+                    .map(|(_, uri)| Some(*uri)),
+            }
+        };
+
+        let path = xpath.trim().strip_prefix('/')?;
+        let steps: Vec<&str> = path.split('/').map(str::trim).collect();
+
+        // the document node has the root element as its only child
+        let mut context: Vec<&Element> = Vec::new();
+        let mut is_document = true;
+
+        for (index, step) in steps.iter().enumerate() {
+            let is_last = index + 1 == steps.len();
+            let (test, predicates) = parse_step(step)?;
+
+            if is_last && test == "text()" && !is_document {
+                return context.first().map(|element| element.text());
+            }
+
+            if let Some(name) = test.strip_prefix('@') {
+                if !is_last || !predicates.is_empty() {
+                    return None;
+                }
+                let (prefix, local) = split_name(name);
+                let namespace = resolve(prefix)?;
+                return context.iter().find_map(|element| {
+                    element
+                        .attrs
+                        .iter()
+                        .find(|attr| {
+                            (local == "*" || attr.local == local)
+                                && attr.namespace.as_deref() == namespace
+                        })
+                        .map(|attr| attr.value.clone())
+                });
+            }
+
+            let (prefix, local) = split_name(test);
+            let namespace = if local == "*" && prefix.is_none() {
+                None
+            } else {
+                Some(resolve(prefix)?)
+            };
+            let matches = |element: &&Element| {
+                (local == "*" || element.local == local)
+                    && namespace.map_or(true, |ns| element.namespace.as_deref() == ns)
+            };
+
+            let candidates: Vec<Vec<&Element>> = if is_document {
+                vec![Some(&self.root).into_iter().filter(matches).collect()]
+            } else {
+                context
+                    .iter()
+                    .map(|element| element.elements().filter(matches).collect())
+                    .collect()
+            };
+
+            context = candidates
+                .into_iter()
+                .flat_map(|mut nodes| {
+                    for &position in &predicates {
+                        nodes = nodes.into_iter().skip(position - 1).take(1).collect();
+                    }
+                    nodes
+                })
+                .collect();
+            is_document = false;
+        }
+
+        context.first().map(|element| element.text())
+    }
+}
+
+/// Splits a location step into its node test and positional predicates.
+fn parse_step(step: &str) -> Option<(&str, Vec<usize>)> {
+    let (test, mut rest) = match step.find('[') {
+        Some(index) => (step[..index].trim(), &step[index..]),
+        None => (step, ""),
+    };
+    if test.is_empty() {
+        return None;
+    }
+
+    let mut predicates = Vec::new();
+    while !rest.is_empty() {
+        let end = rest.find(']')?;
+        let position: usize = rest[1..end].trim().parse().ok()?;
+        if position == 0 {
+            return None;
+        }
+        predicates.push(position);
+        rest = rest[end + 1..].trim_start();
+        if !rest.is_empty() && !rest.starts_with('[') {
+            return None;
+        }
+    }
+
+    Some((test, predicates))
+}
+
+fn split_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, name),
+    }
+}
+
+/// Parses `xmlns:ns0='urn:a' xmlns:ns1="urn:b"` into pairs of prefix and uri.
+fn parse_prefix_mappings(mappings: &str) -> Vec<(&str, &str)> {
+    let mut result = Vec::new();
+    let mut rest = mappings.trim();
+
+    while let Some(declaration) = rest.strip_prefix("xmlns:") {
+        let Some((prefix, value)) = declaration.split_once('=') else {
+            break;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+            break;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            break;
+        };
+        result.push((prefix.trim(), &value[1..end + 1]));
+        rest = value[end + 2..].trim_start();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_nodes() {
+        let store = DataStore::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ns0:root xmlns:ns0="urn:a" xmlns:b="urn:b">
+                <ns0:item b:id="1">First</ns0:item>
+                <ns0:item b:id="2">Second <![CDATA[&]]> more</ns0:item>
+                <b:item>Other</b:item>
+                <plain>Text &amp; more</plain>
+            </ns0:root>"#,
+        )
+        .unwrap();
+
+        let mappings = Some("xmlns:x='urn:a' xmlns:y=\"urn:b\"");
+        let select = |xpath| store.select(xpath, mappings);
+
+        assert_eq!(select("/x:root[1]/x:item[1]").as_deref(), Some("First"));
+        assert_eq!(
+            select("/x:root/x:item[2]").as_deref(),
+            Some("Second & more")
+        );
+        assert_eq!(
+            select("/x:root/x:item[2]/text()").as_deref(),
+            Some("Second & more")
+        );
+        assert_eq!(select("/x:root/x:item[2]/@y:id").as_deref(), Some("2"));
+        assert_eq!(select("/x:root/y:item").as_deref(), Some("Other"));
+        assert_eq!(select("/x:root/plain").as_deref(), Some("Text & more"));
+        assert_eq!(select("/x:root/*[3]").as_deref(), Some("Other"));
+        assert_eq!(select("/x:root/x:item[3]"), None);
+        assert_eq!(select("/root/x:item"), None);
+        assert_eq!(select("/z:root"), None);
+        assert_eq!(select("x:root"), None);
+    }
+}
@@ -0,0 +1,466 @@
+//! Custom XML data parts and data binding
+//!
+//! The corresponding ZIP items are `/customXml/item{n}.xml`, holding the data,
+//! and `/customXml/itemProps{n}.xml`, identifying the data store item.
+//!
+//! Content controls bound with `w:dataBinding` display the value of a node of
+//! a custom XML part. Replacing the data of the part and updating the bindings
+//! fills the template:
+//!
+//! ```rust
+//! use docx::custom_xml::{CustomXmlPart, DataStoreItem};
+//! use docx::document::*;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//!
+//! docx.custom_xml.insert(
+//!     "customXml/item1.xml".into(),
+//!     CustomXmlPart::from("<invoice><customer>Jane</customer></invoice>")
+//!         .properties(DataStoreItem::from("{5B3E0F2C-7A1D-4C4B-9E0E-0A1B2C3D4E5F}")),
+//! );
+//!
+//! docx.document.push(
+//!     Paragraph::default().push(
+//!         RunSdt::default()
+//!             .property(
+//!                 SdtProperty::default().tag("customer").data_binding(
+//!                     DataBinding::from("/invoice[1]/customer[1]")
+//!                         .store_item_id("{5B3E0F2C-7A1D-4C4B-9E0E-0A1B2C3D4E5F}"),
+//!                 ),
+//!             )
+//!             .push(Run::default().push_text("Customer")),
+//!     ),
+//! );
+//!
+//! docx.set_custom_xml_data(
+//!     "{5B3E0F2C-7A1D-4C4B-9E0E-0A1B2C3D4E5F}",
+//!     "<invoice><customer>John</customer></invoice>",
+//! );
+//!
+//! assert_eq!(docx.update_data_bindings().unwrap(), 1);
+//! assert_eq!(docx.document.content_control("customer").unwrap().text(), "John");
+//! ```
+
+mod data_store;
+
+pub use self::data_store::DataStore;
+
+use std::io::Write;
+
+use crate::{
+    __setter, __xml_test_suites,
+    content_control::{visit_blocks_mut, ContentControlMut},
+    date_format::DateTime,
+    document::{BodyContent, DataBinding, Document, SdtType},
+    error::DocxResult,
+    private_prelude::*,
+    schema::SCHEMA_CUSTOM_XML_DATA_STORE,
+    Docx,
+};
+
+/// Date format used by Word when a date picker doesn't specify one.
+const DEFAULT_DATE_FORMAT: &str = "M/d/yyyy";
+
+/// A custom XML part
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct CustomXmlPart<'a> {
+    /// Specifies the XML data, kept as it is.
+    pub data: Cow<'a, str>,
+    /// Specifies the properties part of the data.
+    pub properties: Option<DataStoreItem<'a>>,
+}
+
+impl<'a> CustomXmlPart<'a> {
+    __setter!(properties: Option<DataStoreItem<'a>>);
+
+    /// Returns the data store item id, e.g. `{5B3E0F2C-7A1D-4C4B-9E0E-0A1B2C3D4E5F}`.
+    pub fn item_id(&self) -> Option<&str> {
+        self.properties.as_ref().map(|props| &*props.item_id)
+    }
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for CustomXmlPart<'a> {
+    fn from(data: T) -> Self {
+        CustomXmlPart {
+            data: data.into(),
+            properties: None,
+        }
+    }
+}
+
+/// The root element of a custom XML properties part
+#[derive(Clone, Debug, Default, XmlRead)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "ds:datastoreItem")]
+pub struct DataStoreItem<'a> {
+    /// Specifies the unique id of the data, referenced by `w:storeItemID`.
+    #[xml(attr = "ds:itemID")]
+    pub item_id: Cow<'a, str>,
+    /// Specifies the XML schemas the data conforms to.
+    #[xml(child = "ds:schemaRefs")]
+    pub schema_refs: Option<SchemaRefs<'a>>,
+}
+
+impl<'a> DataStoreItem<'a> {
+    __setter!(schema_refs: Option<SchemaRefs<'a>>);
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for DataStoreItem<'a> {
+    fn from(item_id: T) -> Self {
+        DataStoreItem {
+            item_id: item_id.into(),
+            schema_refs: None,
+        }
+    }
+}
+
+impl<'a> XmlWrite for DataStoreItem<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let DataStoreItem {
+            item_id,
+            schema_refs,
+        } = self;
+
+        log::debug!("[DataStoreItem] Started writing.");
+
+        writer.write_element_start("ds:datastoreItem")?;
+
+        writer.write_attribute("ds:itemID", item_id)?;
+
+        writer.write_attribute("xmlns:ds", SCHEMA_CUSTOM_XML_DATA_STORE)?;
+
+        if let Some(schema_refs) = schema_refs {
+            writer.write_element_end_open()?;
+            schema_refs.to_writer(writer)?;
+            writer.write_element_end_close("ds:datastoreItem")?;
+        } else {
+            writer.write_element_end_empty()?;
+        }
+
+        log::debug!("[DataStoreItem] Finished writing.");
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "ds:schemaRefs")]
+pub struct SchemaRefs<'a> {
+    #[xml(child = "ds:schemaRef")]
+    pub refs: Vec<SchemaRef<'a>>,
+}
+
+impl<'a> SchemaRefs<'a> {
+    pub fn push_ref<T: Into<SchemaRef<'a>>>(mut self, uri: T) -> Self {
+        self.refs.push(uri.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "ds:schemaRef")]
+pub struct SchemaRef<'a> {
+    #[xml(attr = "ds:uri")]
+    pub uri: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SchemaRef<'a> {
+    fn from(uri: T) -> Self {
+        SchemaRef { uri: uri.into() }
+    }
+}
+
+/// Returns the ZIP item name of the properties part of a custom XML part,
+/// e.g. `customXml/itemProps1.xml` for `customXml/item1.xml`.
+pub(crate) fn properties_name(name: &str) -> String {
+    let (dir, file) = name.rsplit_once('/').unwrap_or(("", name));
+    let file = match file.strip_prefix("item") {
+        Some(rest) => format!("itemProps{}", rest),
+        None => file.replacen(".xml", "Props.xml", 1),
+    };
+    if dir.is_empty() {
+        file
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the custom XML part with the given data store item id.
+    ///
+    /// Item ids are compared case-insensitively.
+    pub fn custom_xml_part(&self, item_id: &str) -> Option<&CustomXmlPart<'a>> {
+        self.custom_xml.values().find(|part| is_item(part, item_id))
+    }
+
+    /// Replaces the data of the custom XML part with the given data store item id.
+    ///
+    /// Returns `false` if there is no such part. Call
+    /// [`update_data_bindings`](Docx::update_data_bindings) to refresh the bound
+    /// content controls.
+    pub fn set_custom_xml_data<T: Into<Cow<'a, str>>>(&mut self, item_id: &str, data: T) -> bool {
+        match self
+            .custom_xml
+            .values_mut()
+            .find(|part| is_item(part, item_id))
+        {
+            Some(part) => {
+                part.data = data.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pushes the values of the custom XML parts into the bound content
+    /// controls of the document, headers and footers.
+    ///
+    /// Bindings without a store item id, or whose store item isn't found,
+    /// use the first part in which the XPath selects a node. Returns the
+    /// number of updated controls.
+    pub fn update_data_bindings(&mut self) -> DocxResult<usize> {
+        let stores = self
+            .custom_xml
+            .values()
+            .map(|part| {
+                Ok((
+                    part.item_id().map(str::to_owned),
+                    DataStore::parse(&part.data)?,
+                ))
+            })
+            .collect::<DocxResult<Vec<_>>>()?;
+
+        let mut select = |binding: &DataBinding| {
+            let prefix_mappings = binding.prefix_mappings.as_deref();
+            let store_item_id = binding.store_item_id.as_deref();
+
+            stores
+                .iter()
+                .find(|(id, _)| matches!((id, store_item_id), (Some(id), Some(store)) if id.eq_ignore_ascii_case(store)))
+                .and_then(|(_, store)| store.select(&binding.xpath, prefix_mappings))
+                .or_else(|| {
+                    stores
+                        .iter()
+                        .find_map(|(_, store)| store.select(&binding.xpath, prefix_mappings))
+                })
+        };
+
+        Ok(self.bind(&mut select))
+    }
+
+    /// Pushes the values of the given data into all bound content controls
+    /// of the document, headers and footers, regardless of their store item id.
+    ///
+    /// Returns the number of updated controls.
+    pub fn bind_data(&mut self, data: &DataStore) -> usize {
+        self.bind(&mut |binding| data.select(&binding.xpath, binding.prefix_mappings.as_deref()))
+    }
+
+    fn bind(&mut self, select: &mut dyn FnMut(&DataBinding) -> Option<String>) -> usize {
+        let mut count = bind_blocks(&mut self.document.body.content, select);
+        for header in self.headers.values_mut() {
+            count += bind_blocks(&mut header.content, select);
+        }
+        for footer in self.footers.values_mut() {
+            count += bind_blocks(&mut footer.content, select);
+        }
+        count
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Pushes the values of the given data into all bound content controls,
+    /// regardless of their store item id.
+    ///
+    /// Returns the number of updated controls.
+    pub fn bind_data(&mut self, data: &DataStore) -> usize {
+        bind_blocks(&mut self.body.content, &mut |binding| {
+            data.select(&binding.xpath, binding.prefix_mappings.as_deref())
+        })
+    }
+}
+
+fn is_item(part: &CustomXmlPart, item_id: &str) -> bool {
+    part.item_id()
+        .is_some_and(|id| id.eq_ignore_ascii_case(item_id))
+}
+
+fn bind_blocks(
+    content: &mut [BodyContent],
+    select: &mut dyn FnMut(&DataBinding) -> Option<String>,
+) -> usize {
+    let mut count = 0;
+    visit_blocks_mut(content, &mut |mut control| {
+        let value = match &control.property().data_binding {
+            Some(binding) => select(binding),
+            None => return,
+        };
+        if let Some(value) = value {
+            if apply(&mut control, &value) {
+                count += 1;
+            }
+        }
+    });
+    count
+}
+
+/// Sets the value of a bound control according to its type.
+///
+/// Bound controls are updated even if their content is locked, as Word does.
+fn apply(control: &mut ContentControlMut, value: &str) -> bool {
+    let lock = control.property().lock.take();
+
+    let updated = match &control.property().ty {
+        Some(SdtType::Checkbox(_)) => control.set_checked(matches!(value.trim(), "true" | "1")),
+        Some(SdtType::Date(date)) => {
+            let format = date
+                .format
+                .as_ref()
+                .map_or(DEFAULT_DATE_FORMAT, |format| &*format.value)
+                .to_owned();
+            match DateTime::parse(value) {
+                Some(date) => control.set_date(value.trim(), &date.format(&format)),
+                None => control.set_text(value),
+            }
+        }
+        Some(SdtType::DropDownList(_)) | Some(SdtType::ComboBox(_)) => {
+            control.select_item(value) || control.set_text(value)
+        }
+        Some(SdtType::Picture(_))
+        | Some(SdtType::RepeatingSection(_))
+        | Some(SdtType::RepeatingSectionItem(_)) => false,
+        Some(SdtType::RichText(_)) | Some(SdtType::Text(_)) | None => control.set_text(value),
+    };
+
+    control.property().lock = lock;
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{
+        DataBinding, Paragraph, Run, RunSdt, SdtCheckbox, SdtDate, SdtLockVal, SdtProperty,
+    };
+
+    fn bound(tag: &str, xpath: &str) -> SdtProperty<'static> {
+        SdtProperty::default().tag(tag.to_owned()).data_binding(
+            DataBinding::from(xpath.to_owned())
+                .prefix_mappings("xmlns:ns0='urn:test'")
+                .store_item_id("{ITEM}"),
+        )
+    }
+
+    #[test]
+    fn update_data_bindings() {
+        let mut docx = Docx::default();
+
+        docx.custom_xml.insert(
+            "customXml/item1.xml".into(),
+            CustomXmlPart::from("<root xmlns=\"urn:test\"/>")
+                .properties(DataStoreItem::from("{item}")),
+        );
+
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    RunSdt::default()
+                        .property(
+                            bound("name", "/ns0:root/ns0:name").lock(SdtLockVal::ContentLocked),
+                        )
+                        .push(Run::default().push_text("Name")),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(bound("paid", "/ns0:root/ns0:paid").ty(SdtCheckbox::default()))
+                        .push(Run::default().push_text("☐")),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(
+                            bound("due", "/ns0:root/ns0:due")
+                                .ty(SdtDate::default().format("MMMM d, yyyy")),
+                        )
+                        .push(Run::default().push_text("Date")),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(bound("missing", "/ns0:root/ns0:missing"))
+                        .push(Run::default().push_text("Missing")),
+                ),
+        );
+
+        assert!(docx.set_custom_xml_data(
+            "{ITEM}",
+            r#"<root xmlns="urn:test"><name>John</name><paid>true</paid><due>2020-01-02</due></root>"#,
+        ));
+        assert!(!docx.set_custom_xml_data("{OTHER}", "<root/>"));
+        assert_eq!(docx.update_data_bindings().unwrap(), 3);
+
+        let control = |name| docx.document.content_control(name).unwrap();
+        assert_eq!(control("name").text(), "John");
+        assert!(control("name").property().is_content_locked());
+        assert_eq!(control("paid").text(), "☒");
+        assert_eq!(control("due").text(), "January 2, 2020");
+        assert_eq!(control("missing").text(), "Missing");
+        match control("due").ty() {
+            Some(SdtType::Date(date)) => assert_eq!(date.full_date.as_deref(), Some("2020-01-02")),
+            _ => panic!("expected a date picker"),
+        }
+    }
+
+    #[test]
+    fn read_write_custom_xml() {
+        use crate::DocxFile;
+        use std::io::Cursor;
+
+        let mut docx = Docx::default();
+        docx.custom_xml.insert(
+            "customXml/item1.xml".into(),
+            CustomXmlPart::from("<root>Data</root>").properties(DataStoreItem::from("{ITEM}")),
+        );
+        docx.custom_xml
+            .insert("customXml/item2.xml".into(), "<other/>".into());
+
+        let cursor = docx.write(Cursor::new(Vec::new())).unwrap();
+        let file = DocxFile::from_reader(cursor).unwrap();
+        let read = file.parse().unwrap();
+
+        assert_eq!(read.custom_xml, docx.custom_xml);
+        assert_eq!(
+            read.custom_xml_part("{item}").unwrap().data,
+            "<root>Data</root>"
+        );
+        assert!(read
+            .content_types
+            .overrides
+            .iter()
+            .any(|o| o.part == "/customXml/itemProps1.xml"));
+        assert!(read
+            .document_rels
+            .unwrap()
+            .relationships
+            .iter()
+            .any(|rel| rel.target == "../customXml/item2.xml"));
+    }
+}
+
+__xml_test_suites!(
+    DataStoreItem,
+    DataStoreItem::from("{0}"),
+    format!(
+        r#"<ds:datastoreItem ds:itemID="{{0}}" xmlns:ds="{}"/>"#,
+        SCHEMA_CUSTOM_XML_DATA_STORE
+    )
+    .as_str(),
+    DataStoreItem::from("{0}").schema_refs(SchemaRefs::default().push_ref("urn:test")),
+    format!(
+        r#"<ds:datastoreItem ds:itemID="{{0}}" xmlns:ds="{}"><ds:schemaRefs><ds:schemaRef ds:uri="urn:test"/></ds:schemaRefs></ds:datastoreItem>"#,
+        SCHEMA_CUSTOM_XML_DATA_STORE
+    )
+    .as_str(),
+);
@@ -0,0 +1,231 @@
+//! Date formatting with the date pictures used by Word, e.g. `dddd, MMMM d, yyyy`.
+
+pub(crate) const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub(crate) const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A date and time of day, without time zone
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct DateTime {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    /// Parses an ISO 8601 date, e.g. `2020-01-02` or `2020-01-02T13:04:05Z`.
+    ///
+    /// The time zone designator is ignored.
+    pub fn parse(value: &str) -> Option<DateTime> {
+        let value = value.trim();
+        let (date, time) = match value.find(['T', ' ']) {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+
+        let mut parts = date.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let (mut hour, mut minute, mut second) = (0, 0, 0);
+        if let Some(time) = time {
+            let time = time
+                .split(['Z', '+', '-'])
+                .next()
+                .unwrap_or_default()
+                .split('.')
+                .next()
+                .unwrap_or_default();
+            let mut parts = time.split(':');
+            hour = parts.next()?.parse().ok()?;
+            minute = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+            second = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+        }
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Converts seconds since the Unix epoch, in UTC.
+    pub fn from_timestamp(seconds: u64) -> DateTime {
+        let (days, time) = ((seconds / 86400) as i64, (seconds % 86400) as u32);
+
+        // Hinnant's civil from days, counting from 0000-03-01
+        let days = days + 719468;
+        let era = days / 146097;
+        let day_of_era = days % 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+        let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + i32::from(month <= 2);
+
+        DateTime {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+        }
+    }
+
+    /// Returns the day of the week, `0` being Sunday.
+    pub fn weekday(&self) -> usize {
+        // Sakamoto's method
+        const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let day = year + year / 4 - year / 100
+            + year / 400
+            + OFFSETS[self.month as usize - 1]
+            + self.day as i32;
+        day.rem_euclid(7) as usize
+    }
+
+    /// Formats the date with a date picture.
+    ///
+    /// Supports `d`, `dd`, `ddd`, `dddd`, `M`, `MM`, `MMM`, `MMMM`, `yy`,
+    /// `yyyy`, `H`, `HH`, `h`, `hh`, `m`, `mm`, `s`, `ss`, `AM/PM` and
+    /// literals in single quotes. Other characters are copied as they are.
+    pub fn format(&self, picture: &str) -> String {
+        self.format_with_names(picture, &MONTHS, &WEEKDAYS)
+    }
+
+    /// Formats the date with a date picture, using the given names of
+    /// months and days of the week, starting with January and Sunday.
+    pub fn format_with_names(&self, picture: &str, months: &[&str], weekdays: &[&str]) -> String {
+        let chars: Vec<char> = picture.chars().collect();
+        let mut result = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let mut len = 1;
+            while i + len < chars.len() && chars[i + len] == c {
+                len += 1;
+            }
+
+            match c {
+                'd' => match len {
+                    1 => result.push_str(&self.day.to_string()),
+                    2 => result.push_str(&format!("{:02}", self.day)),
+                    3 => result.extend(weekdays[self.weekday()].chars().take(3)),
+                    _ => result.push_str(weekdays[self.weekday()]),
+                },
+                'M' => {
+                    let name = months[self.month as usize - 1];
+                    match len {
+                        1 => result.push_str(&self.month.to_string()),
+                        2 => result.push_str(&format!("{:02}", self.month)),
+                        3 => result.extend(name.chars().take(3)),
+                        _ => result.push_str(name),
+                    }
+                }
+                'y' => match len {
+                    1 | 2 => result.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                    _ => result.push_str(&format!("{:04}", self.year)),
+                },
+                'H' => push_number(&mut result, self.hour, len),
+                'h' => push_number(&mut result, (self.hour + 11) % 12 + 1, len),
+                'm' => push_number(&mut result, self.minute, len),
+                's' => push_number(&mut result, self.second, len),
+                'A' | 'a' if is_am_pm(&chars[i..]) => {
+                    result.push_str(if self.hour < 12 { "AM" } else { "PM" });
+                    len = 5;
+                }
+                '\'' => match chars[i + 1..].iter().position(|&c| c == '\'') {
+                    Some(end) => {
+                        result.extend(&chars[i + 1..i + 1 + end]);
+                        len = end + 2;
+                    }
+                    None => {
+                        result.extend(&chars[i + 1..]);
+                        len = chars.len() - i;
+                    }
+                },
+                _ => result.extend(&chars[i..i + len]),
+            }
+
+            i += len;
+        }
+
+        result
+    }
+}
+
+fn is_am_pm(chars: &[char]) -> bool {
+    chars.len() >= 5
+        && chars[..5]
+            .iter()
+            .collect::<String>()
+            .eq_ignore_ascii_case("AM/PM")
+}
+
+fn push_number(result: &mut String, value: u32, len: usize) {
+    if len > 1 {
+        result.push_str(&format!("{:02}", value));
+    } else {
+        result.push_str(&value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_dates() {
+        let date = DateTime::parse("2020-01-02T13:04:05Z").unwrap();
+        assert_eq!(date.format("M/d/yyyy"), "1/2/2020");
+        assert_eq!(date.format("dddd, MMMM dd, yy"), "Thursday, January 02, 20");
+        assert_eq!(date.format("ddd d MMM"), "Thu 2 Jan");
+        assert_eq!(date.format("h:mm AM/PM"), "1:04 PM");
+        assert_eq!(date.format("HH:mm:ss"), "13:04:05");
+        assert_eq!(date.format("'Day' d"), "Day 2");
+        assert_eq!(DateTime::parse("2020-13-01"), None);
+        assert_eq!(
+            DateTime::from_timestamp(1709210645),
+            DateTime::parse("2024-02-29T12:44:05Z").unwrap()
+        );
+    }
+}
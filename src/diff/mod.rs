@@ -0,0 +1,1174 @@
+//! Structural diff
+//!
+//! Lists the differences between two documents as changes with a path and
+//! the old and new value, covering the body, headers and footers, notes,
+//! styles, numbering, comments, package metadata and media files.
+//!
+//! The documents are compared through their model. Paragraphs, tables and
+//! table rows are aligned by their text, like [`Docx::compare`] does, so that
+//! an inserted paragraph is reported as a single addition. Matched paragraphs
+//! report changes of their text and properties, and of the properties of the
+//! runs with the same text. Matched tables report changes of their
+//! properties, grid, rows and cells.
+//!
+//! Paths follow the document model: `body[12].paragraph.run[3].property.bold`
+//! is the bold property of the fourth run of the thirteenth body element.
+//! Removed blocks and rows are numbered after their position in the old
+//! document, the other ones after their position in the new document. Styles,
+//! numbering definitions, comments, notes and relationships are identified by
+//! their id instead of their position, e.g. `styles[Heading1].name`.
+//!
+//! ```rust
+//! use docx::diff::ChangeKind;
+//! use docx::document::*;
+//! use docx::formatting::*;
+//! use docx::Docx;
+//!
+//! let mut old = Docx::default();
+//! old.document.push(Paragraph::default().push_text("Hello"));
+//!
+//! let mut new = Docx::default();
+//! new.document.push(
+//!     Paragraph::default().push(
+//!         Run::default()
+//!             .property(CharacterProperty::default().bold(true))
+//!             .push_text("Hello"),
+//!     ),
+//! );
+//!
+//! let diff = old.diff(&new);
+//!
+//! let change = diff.get("body[0].paragraph.run[0].property.bold").unwrap();
+//! assert_eq!(change.kind, ChangeKind::Added);
+//! assert_eq!(change.new.as_deref(), Some("true"));
+//!
+//! println!("{}", diff.summary());
+//! ```
+//!
+//! [`Docx::compare`]: ../struct.Docx.html#method.compare
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, slice,
+};
+
+use bytes::Bytes;
+use hard_xml::utils::xml_unescape;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{
+    comments::Comment,
+    compare::{
+        align_blocks, align_rows, blocks_text, cell_blocks,
+        lcs::{self, Alignment, Edit},
+        paragraph_text,
+    },
+    document::{BodyContent, Paragraph, Run, Table, TableCell, TableContent, TableRow},
+    find::runs_of,
+    formatting::{CharacterProperty, ParagraphProperty, TableCellProperty, TableProperty},
+    numbering::{AbstractNumbering, Numbering},
+    private_prelude::*,
+    rels::Relationships,
+    styles::{Style, Styles},
+    Docx,
+};
+
+/// Share of common words for two blocks to be compared instead of being
+/// reported as removed and added, the default of a comparison.
+const THRESHOLD: f64 = 0.5;
+
+/// Compares optional fields of two optional parents, which are elements.
+macro_rules! fields {
+    ($differ:expr, $path:expr, $old:expr, $new:expr, [$($field:ident),* $(,)?]) => {
+        $(
+            $differ.element(
+                &format!("{}.{}", $path, stringify!($field)),
+                $old.and_then(|parent| parent.$field.as_ref()),
+                $new.and_then(|parent| parent.$field.as_ref()),
+            );
+        )*
+    };
+}
+
+/// Compares optional text fields of two optional parents.
+macro_rules! texts {
+    ($differ:expr, $path:expr, $old:expr, $new:expr, [$($field:ident),* $(,)?]) => {
+        $(
+            $differ.value(
+                format!("{}.{}", $path, stringify!($field)),
+                $old.and_then(|parent| parent.$field.as_deref()).map(String::from),
+                $new.and_then(|parent| parent.$field.as_deref()).map(String::from),
+            );
+        )*
+    };
+}
+
+/// The kind of a change
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "lowercase"))]
+pub enum ChangeKind {
+    /// The value only exists in the new document.
+    Added,
+    /// The value only exists in the old document.
+    Removed,
+    /// The value differs between both documents.
+    Modified,
+}
+
+/// A single difference between two documents
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Change {
+    /// Specifies the location of the value.
+    pub path: String,
+    /// Specifies the kind of the change.
+    pub kind: ChangeKind,
+    /// Specifies the value in the old document.
+    pub old: Option<String>,
+    /// Specifies the value in the new document.
+    pub new: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let old = self.old.as_deref().map(preview).unwrap_or_default();
+        let new = self.new.as_deref().map(preview).unwrap_or_default();
+        match self.kind {
+            ChangeKind::Added => write!(f, "+ {}: {}", self.path, new),
+            ChangeKind::Removed => write!(f, "- {}: {}", self.path, old),
+            ChangeKind::Modified => write!(f, "~ {}: {} -> {}", self.path, old, new),
+        }
+    }
+}
+
+/// Shortens long values for the summary.
+fn preview(value: &str) -> String {
+    const LIMIT: usize = 60;
+
+    let value = value.replace('\n', "\\n");
+    match value.char_indices().nth(LIMIT) {
+        Some((index, _)) => format!("{}...", &value[..index]),
+        None => value,
+    }
+}
+
+/// The list of differences between two documents
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Diff {
+    /// Specifies the changes in document order.
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Returns `true` if both documents are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the change at the given path.
+    pub fn get(&self, path: &str) -> Option<&Change> {
+        self.changes.iter().find(|change| change.path == path)
+    }
+
+    /// Returns the changes whose path starts with the given prefix.
+    pub fn under<'d>(&'d self, prefix: &'d str) -> impl Iterator<Item = &'d Change> {
+        self.changes
+            .iter()
+            .filter(move |change| change.path.starts_with(prefix))
+    }
+
+    /// Returns a readable summary with one line per change.
+    pub fn summary(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = |kind| {
+            self.changes
+                .iter()
+                .filter(|change| change.kind == kind)
+                .count()
+        };
+
+        writeln!(
+            f,
+            "{} changes ({} added, {} removed, {} modified)",
+            self.changes.len(),
+            count(ChangeKind::Added),
+            count(ChangeKind::Removed),
+            count(ChangeKind::Modified),
+        )?;
+
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Lists the differences between this document and another one.
+    pub fn diff(&self, other: &Docx) -> Diff {
+        diff(self, other)
+    }
+}
+
+/// Lists the differences between two documents.
+pub fn diff(old: &Docx, new: &Docx) -> Diff {
+    let mut differ = Differ::default();
+
+    differ.blocks(
+        "body",
+        &old.document.body.content,
+        &new.document.body.content,
+    );
+    differ.element(
+        "body.section",
+        old.document.body.section.as_ref(),
+        new.document.body.section.as_ref(),
+    );
+
+    fn headers<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<&'d str, &'d [BodyContent<'a>]> {
+        docx.headers
+            .iter()
+            .map(|(name, header)| (&**name, &*header.content))
+            .collect()
+    }
+    differ.keyed("headers", &headers(old), &headers(new), Differ::part);
+    fn footers<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<&'d str, &'d [BodyContent<'a>]> {
+        docx.footers
+            .iter()
+            .map(|(name, footer)| (&**name, &*footer.content))
+            .collect()
+    }
+    differ.keyed("footers", &footers(old), &footers(new), Differ::part);
+
+    fn footnotes<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<&'d str, &'d [BodyContent<'a>]> {
+        docx.footnotes
+            .iter()
+            .flat_map(|notes| &notes.notes)
+            .map(|note| (&*note.id, &*note.content))
+            .collect()
+    }
+    differ.keyed("footnotes", &footnotes(old), &footnotes(new), Differ::part);
+    fn endnotes<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<&'d str, &'d [BodyContent<'a>]> {
+        docx.endnotes
+            .iter()
+            .flat_map(|notes| &notes.notes)
+            .map(|note| (&*note.id, &*note.content))
+            .collect()
+    }
+    differ.keyed("endnotes", &endnotes(old), &endnotes(new), Differ::part);
+
+    fn comments<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<&'d str, &'d Comment<'a>> {
+        docx.comments
+            .iter()
+            .flat_map(|comments| &comments.comments)
+            .map(|comment| (&*comment.id, comment))
+            .collect()
+    }
+    differ.keyed(
+        "comments",
+        &comments(old),
+        &comments(new),
+        |differ, path, old, new| {
+            let text = |paras: &[Paragraph]| {
+                let text: Vec<_> = paras.iter().map(paragraph_text).collect();
+                text.join("\n")
+            };
+            match (old, new) {
+                (Some(old), Some(new)) => {
+                    differ.value(
+                        format!("{}.author", path),
+                        Some(old.author.to_string()),
+                        Some(new.author.to_string()),
+                    );
+                    differ.value(
+                        format!("{}.date", path),
+                        Some(old.date.to_string()),
+                        Some(new.date.to_string()),
+                    );
+                    differ.value(
+                        format!("{}.text", path),
+                        Some(text(&old.content)),
+                        Some(text(&new.content)),
+                    );
+                }
+                (old, new) => differ.value(
+                    path.to_string(),
+                    old.map(|comment| text(&comment.content)),
+                    new.map(|comment| text(&comment.content)),
+                ),
+            }
+        },
+    );
+
+    differ.styles(&old.styles, &new.styles);
+
+    fn abstract_nums<'d, 'a>(docx: &'d Docx<'a>) -> BTreeMap<usize, &'d AbstractNumbering<'a>> {
+        docx.numbering
+            .iter()
+            .flat_map(|numbering| &numbering.abstract_nums)
+            .map(|abs| (abs.abstract_num_id, abs))
+            .collect()
+    }
+    differ.keyed(
+        "numbering.abstract",
+        &abstract_nums(old),
+        &abstract_nums(new),
+        Differ::abstract_numbering,
+    );
+    fn nums<'d>(docx: &'d Docx) -> BTreeMap<usize, &'d Numbering> {
+        docx.numbering
+            .iter()
+            .flat_map(|numbering| &numbering.nums)
+            .map(|num| (num.num_id, num))
+            .collect()
+    }
+    differ.keyed(
+        "numbering.num",
+        &nums(old),
+        &nums(new),
+        |differ, path, old: Option<&&Numbering>, new: Option<&&Numbering>| {
+            differ.element(
+                &format!("{}.abstract_num_id", path),
+                old.map(|num| &num.abstract_num_id),
+                new.map(|num| &num.abstract_num_id),
+            )
+        },
+    );
+
+    texts!(
+        differ,
+        "core",
+        old.core.as_ref(),
+        new.core.as_ref(),
+        [
+            title,
+            subject,
+            creator,
+            keywords,
+            description,
+            last_modified_by,
+            revision,
+        ]
+    );
+    texts!(
+        differ,
+        "app",
+        old.app.as_ref(),
+        new.app.as_ref(),
+        [
+            template,
+            total_time,
+            pages,
+            words,
+            characters,
+            application,
+            doc_security,
+            lines,
+            paragraphs,
+            scale_crop,
+            company,
+            links_up_to_date,
+            characters_with_spaces,
+            shared_doc,
+            hyperlinks_changed,
+            app_version,
+        ]
+    );
+
+    let (old_settings, new_settings) = (old.settings.as_ref(), new.settings.as_ref());
+    fields!(
+        differ,
+        "settings",
+        old_settings,
+        new_settings,
+        [track_revisions, mail_merge, update_fields]
+    );
+    fn settings(docx: &Docx) -> BTreeMap<String, String> {
+        let settings = docx.settings.iter();
+        let attributes = settings
+            .clone()
+            .flat_map(|settings| &settings.attributes)
+            .map(|(name, value)| (format!("@{}", name), value.to_string()));
+        let others = settings
+            .flat_map(|settings| &settings.others)
+            .map(|element| (element.tag.to_string(), element.xml.to_string()));
+        attributes.chain(others).collect()
+    }
+    differ.keyed("settings", &settings(old), &settings(new), Differ::string);
+
+    fn fonts(docx: &Docx) -> BTreeMap<String, String> {
+        docx.font_table
+            .iter()
+            .flat_map(|table| &table.fonts)
+            .map(|font| (font.name.to_string(), element_value(font)))
+            .collect()
+    }
+    differ.keyed("font_table", &fonts(old), &fonts(new), Differ::string);
+
+    fn content_types(docx: &Docx) -> BTreeMap<String, String> {
+        let types = &docx.content_types;
+        let defaults = types
+            .defaults
+            .iter()
+            .map(|default| (format!("*.{}", default.ext), default.ty.to_string()));
+        let overrides = types
+            .overrides
+            .iter()
+            .map(|part| (part.part.to_string(), part.ty.to_string()));
+        defaults.chain(overrides).collect()
+    }
+    differ.keyed(
+        "content_types",
+        &content_types(old),
+        &content_types(new),
+        Differ::string,
+    );
+
+    differ.relationships("rels", Some(&old.rels), Some(&new.rels));
+    differ.relationships(
+        "document_rels",
+        old.document_rels.as_ref(),
+        new.document_rels.as_ref(),
+    );
+    differ.keyed(
+        "part_rels",
+        &old.part_rels,
+        &new.part_rels,
+        |differ, path, old, new| differ.relationships(path, old, new),
+    );
+
+    fn media<'d>(docx: &'d Docx) -> BTreeMap<&'d str, &'d Bytes> {
+        docx.media
+            .iter()
+            .map(|(name, bytes)| (&**name, bytes))
+            .collect()
+    }
+    differ.keyed(
+        "media",
+        &media(old),
+        &media(new),
+        |differ, path, old, new| {
+            let size = |bytes: &&Bytes| format!("{} bytes", bytes.len());
+            let kind = match (old, new) {
+                (Some(old), Some(new)) if old != new => ChangeKind::Modified,
+                (Some(_), None) => ChangeKind::Removed,
+                (None, Some(_)) => ChangeKind::Added,
+                _ => return,
+            };
+            differ.push(path.to_string(), kind, old.map(size), new.map(size));
+        },
+    );
+
+    Diff {
+        changes: differ.changes,
+    }
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, path: String, kind: ChangeKind, old: Option<String>, new: Option<String>) {
+        self.changes.push(Change {
+            path,
+            kind,
+            old,
+            new,
+        });
+    }
+
+    /// Records the change of a value, if any.
+    fn value(&mut self, path: String, old: Option<String>, new: Option<String>) {
+        let kind = match (&old, &new) {
+            (Some(old), Some(new)) if old != new => ChangeKind::Modified,
+            (Some(_), None) => ChangeKind::Removed,
+            (None, Some(_)) => ChangeKind::Added,
+            _ => return,
+        };
+        self.push(path, kind, old, new);
+    }
+
+    fn string(&mut self, path: &str, old: Option<&String>, new: Option<&String>) {
+        self.value(path.to_string(), old.cloned(), new.cloned());
+    }
+
+    /// Records the change of an element of the model, like a property.
+    fn element<T: XmlWrite>(&mut self, path: &str, old: Option<&T>, new: Option<&T>) {
+        self.value(
+            path.to_string(),
+            old.map(element_value),
+            new.map(element_value),
+        );
+    }
+
+    /// Compares the entries of two maps, identified by their key.
+    fn keyed<K, V, F>(&mut self, path: &str, old: &BTreeMap<K, V>, new: &BTreeMap<K, V>, mut f: F)
+    where
+        K: Ord + fmt::Display,
+        F: FnMut(&mut Self, &str, Option<&V>, Option<&V>),
+    {
+        let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+        for key in keys {
+            f(
+                self,
+                &format!("{}[{}]", path, key),
+                old.get(key),
+                new.get(key),
+            );
+        }
+    }
+
+    /// Compares the content of a header, footer or note.
+    fn part(&mut self, path: &str, old: Option<&&[BodyContent]>, new: Option<&&[BodyContent]>) {
+        match (old, new) {
+            (Some(old), Some(new)) => self.blocks(&format!("{}.content", path), old, new),
+            (old, new) => self.value(
+                path.to_string(),
+                old.map(|content| block_texts(content)),
+                new.map(|content| block_texts(content)),
+            ),
+        }
+    }
+
+    /// Aligns two sequences of blocks and compares the matched ones.
+    fn blocks(&mut self, path: &str, old: &[BodyContent], new: &[BodyContent]) {
+        for item in align_blocks(old, new, THRESHOLD) {
+            match item {
+                Alignment::Matched(i, j) => self.block(path, (i, &old[i]), (j, &new[j])),
+                Alignment::Deleted(i) => self.push(
+                    format!("{}[{}].{}", path, i, block_kind(&old[i])),
+                    ChangeKind::Removed,
+                    Some(block_texts(slice::from_ref(&old[i]))),
+                    None,
+                ),
+                Alignment::Inserted(j) => self.push(
+                    format!("{}[{}].{}", path, j, block_kind(&new[j])),
+                    ChangeKind::Added,
+                    None,
+                    Some(block_texts(slice::from_ref(&new[j]))),
+                ),
+            }
+        }
+    }
+
+    /// Compares two paired blocks, which are replaced if they are of
+    /// different kinds.
+    fn block(&mut self, path: &str, old: (usize, &BodyContent), new: (usize, &BodyContent)) {
+        let block_path = format!("{}[{}].{}", path, new.0, block_kind(new.1));
+        match (old.1, new.1) {
+            (BodyContent::Paragraph(old), BodyContent::Paragraph(new)) => {
+                self.paragraph(&block_path, old, new)
+            }
+            (BodyContent::Table(old), BodyContent::Table(new)) => self.table(&block_path, old, new),
+            (BodyContent::Sdt(old), BodyContent::Sdt(new)) => {
+                self.element(
+                    &format!("{}.property", block_path),
+                    Some(&old.property),
+                    Some(&new.property),
+                );
+                self.blocks(
+                    &format!("{}.content", block_path),
+                    &old.content.content,
+                    &new.content.content,
+                );
+            }
+            _ => {
+                self.push(
+                    format!("{}[{}].{}", path, old.0, block_kind(old.1)),
+                    ChangeKind::Removed,
+                    Some(block_texts(slice::from_ref(old.1))),
+                    None,
+                );
+                self.push(
+                    block_path,
+                    ChangeKind::Added,
+                    None,
+                    Some(block_texts(slice::from_ref(new.1))),
+                );
+            }
+        }
+    }
+
+    /// Compares two matched paragraphs, and the properties of their runs
+    /// with the same text.
+    fn paragraph(&mut self, path: &str, old: &Paragraph, new: &Paragraph) {
+        self.value(
+            format!("{}.text", path),
+            Some(paragraph_text(old)),
+            Some(paragraph_text(new)),
+        );
+        self.paragraph_property(&format!("{}.property", path), &old.property, &new.property);
+
+        let mut old_runs = Vec::new();
+        runs_of(&old.content, &mut old_runs);
+        let mut new_runs = Vec::new();
+        runs_of(&new.content, &mut new_runs);
+
+        let old_texts: Vec<_> = old_runs.iter().map(|run| run_text(run)).collect();
+        let new_texts: Vec<_> = new_runs.iter().map(|run| run_text(run)).collect();
+
+        for edit in lcs::diff(&old_texts, &new_texts, |a, b| a == b) {
+            if let Edit::Equal(i, j) = edit {
+                self.character_property(
+                    &format!("{}.run[{}].property", path, j),
+                    &old_runs[i].property,
+                    &new_runs[j].property,
+                );
+            }
+        }
+    }
+
+    fn paragraph_property(&mut self, path: &str, old: &ParagraphProperty, new: &ParagraphProperty) {
+        let (old, new) = (Some(old), Some(new));
+        fields!(
+            self,
+            path,
+            old,
+            new,
+            [
+                style_id,
+                justification,
+                border,
+                shading,
+                tabs,
+                numbering,
+                outline_level,
+                mark,
+                section,
+                change,
+            ]
+        );
+    }
+
+    fn character_property(&mut self, path: &str, old: &CharacterProperty, new: &CharacterProperty) {
+        let (old, new) = (Some(old), Some(new));
+        fields!(
+            self,
+            path,
+            old,
+            new,
+            [
+                style_id,
+                color,
+                size,
+                bold,
+                italics,
+                strike,
+                dstrike,
+                outline,
+                underline,
+                vanish,
+                shading,
+                vertical_align,
+                change,
+            ]
+        );
+    }
+
+    /// Compares two matched tables, aligning their rows.
+    fn table(&mut self, path: &str, old: &Table, new: &Table) {
+        self.table_property(&format!("{}.property", path), &old.property, &new.property);
+
+        let widths = |table: &Table| {
+            let widths: Vec<_> = table
+                .grids
+                .iter()
+                .flat_map(|grid| &grid.columns)
+                .map(|column| column.width.to_string())
+                .collect();
+            widths.join(" ")
+        };
+        self.value(
+            format!("{}.grid", path),
+            Some(widths(old)),
+            Some(widths(new)),
+        );
+
+        self.rows(path, &old.rows, &new.rows);
+    }
+
+    fn table_property(&mut self, path: &str, old: &TableProperty, new: &TableProperty) {
+        let (old, new) = (Some(old), Some(new));
+        fields!(
+            self,
+            path,
+            old,
+            new,
+            [style_id, justification, borders, indent, width, change]
+        );
+    }
+
+    fn rows(&mut self, path: &str, old: &[TableContent], new: &[TableContent]) {
+        let text = |content: &TableContent| {
+            let rows: Vec<_> = content.iter_rows().map(row_value).collect();
+            rows.join("\n")
+        };
+
+        for item in align_rows(old, new, THRESHOLD) {
+            match item {
+                Alignment::Matched(i, j) => {
+                    let path = format!("{}.row[{}]", path, j);
+                    match (&old[i], &new[j]) {
+                        (TableContent::Row(old), TableContent::Row(new)) => {
+                            self.row(&path, old, new)
+                        }
+                        (TableContent::Sdt(old), TableContent::Sdt(new)) => {
+                            let path = format!("{}.sdt", path);
+                            self.element(
+                                &format!("{}.property", path),
+                                Some(&old.property),
+                                Some(&new.property),
+                            );
+                            self.rows(&path, &old.content.content, &new.content.content);
+                        }
+                        _ => unreachable!("only rows of the same kind are paired"),
+                    }
+                }
+                Alignment::Deleted(i) => self.push(
+                    format!("{}.row[{}]", path, i),
+                    ChangeKind::Removed,
+                    Some(text(&old[i])),
+                    None,
+                ),
+                Alignment::Inserted(j) => self.push(
+                    format!("{}.row[{}]", path, j),
+                    ChangeKind::Added,
+                    None,
+                    Some(text(&new[j])),
+                ),
+            }
+        }
+    }
+
+    /// Compares two matched rows cell by cell.
+    fn row(&mut self, path: &str, old: &TableRow, new: &TableRow) {
+        let (old_property, new_property) = (Some(&old.property), Some(&new.property));
+        fields!(
+            self,
+            format!("{}.property", path),
+            old_property,
+            new_property,
+            [header, justification, insertion, deletion]
+        );
+
+        let old: Vec<_> = old.iter_cells().collect();
+        let new: Vec<_> = new.iter_cells().collect();
+        for index in 0..old.len().max(new.len()) {
+            let path = format!("{}.cell[{}]", path, index);
+            match (old.get(index), new.get(index)) {
+                (Some(old), Some(new)) => self.cell(&path, old, new),
+                (old, new) => self.value(
+                    path,
+                    old.map(|cell| block_texts(&cell_blocks(&cell.content))),
+                    new.map(|cell| block_texts(&cell_blocks(&cell.content))),
+                ),
+            }
+        }
+    }
+
+    fn cell(&mut self, path: &str, old: &TableCell, new: &TableCell) {
+        self.cell_property(&format!("{}.property", path), &old.property, &new.property);
+
+        let path = format!("{}.content", path);
+        let old = cell_blocks(&old.content);
+        let new = cell_blocks(&new.content);
+        match (old.split_last(), new.split_last()) {
+            // like the last paragraph marks of cells, the last blocks are paired
+            (Some((old_last, old_rest)), Some((new_last, new_rest))) => {
+                self.blocks(&path, old_rest, new_rest);
+                self.block(
+                    &path,
+                    (old_rest.len(), old_last),
+                    (new_rest.len(), new_last),
+                );
+            }
+            _ => self.blocks(&path, &old, &new),
+        }
+    }
+
+    fn cell_property(&mut self, path: &str, old: &TableCellProperty, new: &TableCellProperty) {
+        let (old, new) = (Some(old), Some(new));
+        fields!(self, path, old, new, [grid_span, vertical_merge]);
+    }
+
+    fn styles(&mut self, old: &Styles, new: &Styles) {
+        self.element("styles.default", Some(&old.default), Some(&new.default));
+
+        fn styles<'s, 'a>(styles: &'s Styles<'a>) -> BTreeMap<&'s str, &'s Style<'a>> {
+            styles
+                .styles
+                .iter()
+                .map(|style| (&*style.style_id, style))
+                .collect()
+        }
+        self.keyed(
+            "styles",
+            &styles(old),
+            &styles(new),
+            |differ, path, old, new| match (old, new) {
+                (Some(old), Some(new)) => differ.style(path, old, new),
+                (old, new) => differ.value(
+                    path.to_string(),
+                    old.map(|style| style_name(style)),
+                    new.map(|style| style_name(style)),
+                ),
+            },
+        );
+    }
+
+    fn style(&mut self, path: &str, old: &Style, new: &Style) {
+        self.value(
+            format!("{}.type", path),
+            Some(old.ty.to_string()),
+            Some(new.ty.to_string()),
+        );
+        self.value(
+            format!("{}.is_default", path),
+            old.is_default.map(|value| value.to_string()),
+            new.is_default.map(|value| value.to_string()),
+        );
+        let (old_style, new_style) = (Some(old), Some(new));
+        fields!(self, path, old_style, new_style, [name, based_on]);
+        self.paragraph_property(
+            &format!("{}.paragraph", path),
+            &old.paragraph,
+            &new.paragraph,
+        );
+        self.character_property(
+            &format!("{}.character", path),
+            &old.character,
+            &new.character,
+        );
+    }
+
+    fn abstract_numbering(
+        &mut self,
+        path: &str,
+        old: Option<&&AbstractNumbering>,
+        new: Option<&&AbstractNumbering>,
+    ) {
+        let (old, new) = match (old, new) {
+            (Some(old), Some(new)) => (old, new),
+            (old, new) => {
+                return self.value(
+                    path.to_string(),
+                    old.map(|abs| element_value(*abs)),
+                    new.map(|abs| element_value(*abs)),
+                )
+            }
+        };
+
+        let (old_abs, new_abs) = (Some(old), Some(new));
+        fields!(self, path, old_abs, new_abs, [num_style_link]);
+
+        let levels = |abs: &AbstractNumbering| -> BTreeMap<_, _> {
+            abs.levels
+                .iter()
+                .map(|level| (level.index, element_value(level)))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}.level", path),
+            &levels(old),
+            &levels(new),
+            Differ::string,
+        );
+    }
+
+    fn relationships(
+        &mut self,
+        path: &str,
+        old: Option<&Relationships>,
+        new: Option<&Relationships>,
+    ) {
+        let rels = |rels: Option<&Relationships>| -> BTreeMap<_, _> {
+            rels.iter()
+                .flat_map(|rels| &rels.relationships)
+                .map(|rel| {
+                    let mut value = format!("{} {}", rel.ty, rel.target);
+                    if let Some(mode) = &rel.target_mode {
+                        value.push_str(&format!(" ({})", mode));
+                    }
+                    (rel.id.to_string(), value)
+                })
+                .collect()
+        };
+        self.keyed(path, &rels(old), &rels(new), Differ::string);
+    }
+}
+
+/// Returns the name of a block in paths.
+fn block_kind(content: &BodyContent) -> &'static str {
+    match content {
+        BodyContent::Paragraph(_) => "paragraph",
+        BodyContent::Table(_) => "table",
+        BodyContent::Sdt(_) => "sdt",
+    }
+}
+
+/// Returns the text of blocks, one line per paragraph or table row.
+fn block_texts(content: &[BodyContent]) -> String {
+    let mut text = blocks_text(content);
+    text.pop();
+    text
+}
+
+/// Returns the text of a row, with the cells separated by tabs.
+fn row_value(row: &TableRow) -> String {
+    let cells: Vec<_> = row
+        .iter_cells()
+        .map(|cell| block_texts(&cell_blocks(&cell.content)))
+        .collect();
+    cells.join("\t")
+}
+
+fn run_text(run: &Run) -> String {
+    run.iter_text().map(|text| &**text).collect()
+}
+
+fn style_name(style: &Style) -> String {
+    match &style.name {
+        Some(name) => name.value.to_string(),
+        None => style.style_id.to_string(),
+    }
+}
+
+/// Returns the value of an element of the model: the `w:val` attribute of a
+/// simple element, its XML otherwise.
+fn element_value<T: XmlWrite>(element: &T) -> String {
+    let xml = element.to_string().unwrap_or_default();
+
+    let value = xml.strip_suffix("\"/>").and_then(|rest| {
+        let (tag, value) = rest.split_once(" w:val=\"")?;
+        if tag.contains(' ') || value.contains('"') {
+            return None;
+        }
+        xml_unescape(value).ok().map(String::from)
+    });
+
+    value.unwrap_or(xml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Core,
+        document::{Run, TableCell},
+        formatting::{JustificationVal, TableCellProperty},
+        header_footer::Header,
+        styles::StyleType,
+    };
+
+    fn sample() -> Docx<'static> {
+        let mut docx = Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("Alpha beta gamma."));
+        docx.document
+            .push(Paragraph::default().push_text("Delta epsilon zeta."));
+        docx.document
+            .push(Paragraph::default().push_text("Eta theta iota."));
+        docx
+    }
+
+    fn paths(diff: &Diff) -> Vec<&str> {
+        diff.changes.iter().map(|change| &*change.path).collect()
+    }
+
+    #[test]
+    fn equal_documents() {
+        assert!(sample().diff(&sample()).is_empty());
+    }
+
+    #[test]
+    fn insert_paragraph() {
+        let mut new = sample();
+        new.document
+            .body
+            .content
+            .insert(0, Paragraph::default().push_text("New first.").into());
+
+        let diff = sample().diff(&new);
+        assert_eq!(
+            diff.changes,
+            [Change {
+                path: "body[0].paragraph".into(),
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some("New first.".into()),
+            }]
+        );
+        assert_eq!(
+            diff.summary(),
+            "1 changes (1 added, 0 removed, 0 modified)\n+ body[0].paragraph: New first.\n"
+        );
+    }
+
+    #[test]
+    fn diff_documents() {
+        let old = sample();
+
+        let mut new = sample();
+        new.document.body.content.remove(2);
+        new.document.body.content[1] = Paragraph::default()
+            .property(ParagraphProperty::default().justification(JustificationVal::Center))
+            .push_text("Delta epsilon omega.")
+            .into();
+        new.document.body.content[0] = Paragraph::default()
+            .push(
+                Run::default()
+                    .property(CharacterProperty::default().bold(true))
+                    .push_text("Alpha beta gamma."),
+            )
+            .into();
+        new.styles.push(
+            Style::new(StyleType::Paragraph, "Quote")
+                .name("Quote")
+                .paragraph(ParagraphProperty::default().justification(JustificationVal::Center)),
+        );
+        new.core = Some(Core {
+            title: Some("Title".into()),
+            ..Default::default()
+        });
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            paths(&diff),
+            [
+                "body[0].paragraph.run[0].property.bold",
+                "body[1].paragraph.text",
+                "body[1].paragraph.property.justification",
+                "body[2].paragraph",
+                "styles[Quote]",
+                "core.title",
+            ]
+        );
+
+        assert_eq!(
+            diff.changes[1],
+            Change {
+                path: "body[1].paragraph.text".into(),
+                kind: ChangeKind::Modified,
+                old: Some("Delta epsilon zeta.".into()),
+                new: Some("Delta epsilon omega.".into()),
+            }
+        );
+        assert_eq!(diff.changes[2].new.as_deref(), Some("center"));
+        assert_eq!(diff.changes[3].kind, ChangeKind::Removed);
+        assert_eq!(diff.changes[3].old.as_deref(), Some("Eta theta iota."));
+        assert_eq!(diff.changes[4].new.as_deref(), Some("Quote"));
+
+        let summary = diff.summary();
+
+        assert!(summary.starts_with("6 changes (4 added, 1 removed, 1 modified)\n"));
+        assert!(summary
+            .contains("~ body[1].paragraph.text: Delta epsilon zeta. -> Delta epsilon omega.\n"));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&diff.changes[1]).unwrap();
+            assert_eq!(
+                json,
+                r#"{"path":"body[1].paragraph.text","kind":"modified","old":"Delta epsilon zeta.","new":"Delta epsilon omega."}"#
+            );
+        }
+
+        // styles are compared by id
+        let mut newer = new.clone();
+        newer.styles.styles[0].name = Some("Block Quote".into());
+        let diff = new.diff(&newer);
+        assert_eq!(
+            diff.changes,
+            [Change {
+                path: "styles[Quote].name".into(),
+                kind: ChangeKind::Modified,
+                old: Some("Quote".into()),
+                new: Some("Block Quote".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_tables() {
+        let cell = |text: &'static str| TableCell::from(Paragraph::default().push_text(text));
+        let row = |a: &'static str, b: &'static str| {
+            TableRow::default().push_cell(cell(a)).push_cell(cell(b))
+        };
+
+        let mut old = Docx::default();
+        old.document.push(
+            Table::default()
+                .push_grid(vec![2000, 2000])
+                .push_row(row("Name", "Value"))
+                .push_row(row("width", "10")),
+        );
+
+        let mut new = Docx::default();
+        new.document.push(
+            Table::default()
+                .push_grid(vec![2000, 3000])
+                .push_row(row("Name", "Value"))
+                .push_row(row("height", "20"))
+                .push_row(TableRow::default().push_cell(cell("width")).push_cell(
+                    cell("12").property(TableCellProperty::default().grid_span(1usize)),
+                )),
+        );
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            paths(&diff),
+            [
+                "body[0].table.grid",
+                "body[0].table.row[1]",
+                "body[0].table.row[2].cell[1].property.grid_span",
+                "body[0].table.row[2].cell[1].content[0].paragraph.text",
+            ]
+        );
+        assert_eq!(diff.changes[0].new.as_deref(), Some("2000 3000"));
+        assert_eq!(diff.changes[1].new.as_deref(), Some("height\t20"));
+        assert_eq!(diff.changes[2].new.as_deref(), Some("1"));
+        assert_eq!(diff.changes[3].old.as_deref(), Some("10"));
+    }
+
+    #[test]
+    fn diff_parts() {
+        let mut old = Docx::default();
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("Draft"));
+        old.headers.insert("word/header1.xml".into(), header);
+        old.media
+            .insert("word/media/image1.png".into(), Bytes::from_static(b"old"));
+
+        let mut new = Docx::default();
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("Final"));
+        new.headers.insert("word/header1.xml".into(), header);
+        new.media
+            .insert("word/media/image1.png".into(), Bytes::from_static(b"new!"));
+
+        let diff = old.diff(&new);
+        let changes: Vec<_> = diff
+            .changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "- headers[word/header1.xml].content[0].paragraph: Draft",
+                "+ headers[word/header1.xml].content[0].paragraph: Final",
+                "~ media[word/media/image1.png]: 3 bytes -> 4 bytes",
+            ]
+        );
+    }
+}
@@ -1,19 +1,23 @@
 use derive_more::From;
 
 use crate::__xml_test_suites;
-use crate::document::{Paragraph, Table};
+use crate::document::{BlockSdt, Paragraph, Table};
+use crate::formatting::SectionProperty;
 use crate::private_prelude::*;
 
 /// Document Body
 ///
 /// This is the main document editing surface.
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:body")]
 pub struct Body<'a> {
     /// Specifies the contents of the body of the document.
-    #[xml(child = "w:p", child = "w:tbl")]
+    #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
     pub content: Vec<BodyContent<'a>>,
+    /// Specifies the properties of the last section of the document.
+    #[xml(child = "w:sectPr")]
+    pub section: Option<SectionProperty<'a>>,
 }
 
 impl<'a> Body<'a> {
@@ -42,13 +46,16 @@ impl<'a> Body<'a> {
 }
 
 /// A set of elements that can be contained in the body
-#[derive(Debug, From, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum BodyContent<'a> {
     #[xml(tag = "w:p")]
     Paragraph(Paragraph<'a>),
     #[xml(tag = "w:tbl")]
     Table(Table<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(BlockSdt<'a>),
     // SecProp,
 }
 
@@ -57,11 +64,18 @@ __xml_test_suites!(
     Body::default(),
     r#"<w:body/>"#,
     Body {
-        content: vec![Paragraph::default().into()]
+        content: vec![Paragraph::default().into()],
+        section: None,
     },
     r#"<w:body><w:p><w:pPr/></w:p></w:body>"#,
     Body {
-        content: vec![Table::default().into()]
+        content: vec![Table::default().into()],
+        section: None,
     },
     r#"<w:body><w:tbl><w:tblPr/></w:tbl></w:body>"#,
+    Body {
+        content: vec![Paragraph::default().into()],
+        section: Some(SectionProperty::default()),
+    },
+    r#"<w:body><w:p><w:pPr/></w:p><w:sectPr/></w:body>"#,
 );
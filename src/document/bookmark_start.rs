@@ -2,7 +2,7 @@ use crate::{__setter, __xml_test_suites};
 use crate::private_prelude::*;
 
 /// Beginning of bookmark
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:bookmarkStart")]
 pub struct BookmarkStart<'a> {
@@ -8,7 +8,7 @@ use crate::private_prelude::*;
 ///
 /// let br = Break::from(BreakType::Page);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:br")]
 pub struct Break {
@@ -26,7 +26,7 @@ impl<T: Into<Option<BreakType>>> From<T> for Break {
 /// Specifies the break type of a break
 ///
 /// The default value is TextWrapping.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum BreakType {
     /// Text restarts on the next column.
@@ -2,7 +2,7 @@ use crate::{__setter};
 use crate::private_prelude::*;
 
 /// Start of comment
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:commentRangeStart")]
 pub struct CommentRangeStart<'a> {
@@ -16,7 +16,7 @@ impl<'a> CommentRangeStart<'a> {
 }
 
 /// End of comment
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:commentRangeEnd")]
 pub struct CommentRangeEnd<'a> {
@@ -29,6 +29,24 @@ impl<'a> CommentRangeEnd<'a> {
     __setter!(id: Cow<'a, str>);
 }
 
+/// Comment Reference
+///
+/// Shows the mark of a comment, placed in a run after the end of its range.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:commentReference")]
+pub struct CommentReference<'a> {
+    /// Specifies the id of the comment.
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for CommentReference<'a> {
+    fn from(id: T) -> Self {
+        CommentReference { id: id.into() }
+    }
+}
+
 // __xml_test_suites!(
 //     BookmarkEnd,
 //     BookmarkEnd::default(),
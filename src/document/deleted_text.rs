@@ -0,0 +1,67 @@
+use crate::{__xml_test_suites, document::TextSpace, private_prelude::*};
+
+/// Deleted Text
+///
+/// A literal text which has been deleted as a tracked revision.
+///
+/// ```rust
+/// use docx::document::{DeletedText, TextSpace};
+///
+/// let text = DeletedText::from("text");
+/// let text = DeletedText::from(("text", TextSpace::Preserve));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:delText")]
+pub struct DeletedText<'a> {
+    /// Specifies how to handle whitespace
+    #[xml(attr = "xml:space")]
+    pub space: Option<TextSpace>,
+    /// Specifies a literal text
+    #[xml(text)]
+    pub text: Cow<'a, str>,
+}
+
+impl<'a> From<&'a str> for DeletedText<'a> {
+    fn from(val: &'a str) -> Self {
+        DeletedText {
+            text: val.into(),
+            space: None,
+        }
+    }
+}
+
+impl From<String> for DeletedText<'_> {
+    fn from(val: String) -> Self {
+        DeletedText {
+            text: val.into(),
+            space: None,
+        }
+    }
+}
+
+impl<'a> From<(&'a str, TextSpace)> for DeletedText<'a> {
+    fn from(val: (&'a str, TextSpace)) -> Self {
+        DeletedText {
+            text: val.0.into(),
+            space: Some(val.1),
+        }
+    }
+}
+
+impl From<(String, TextSpace)> for DeletedText<'_> {
+    fn from(val: (String, TextSpace)) -> Self {
+        DeletedText {
+            text: val.0.into(),
+            space: Some(val.1),
+        }
+    }
+}
+
+__xml_test_suites!(
+    DeletedText,
+    DeletedText::from("text"),
+    "<w:delText>text</w:delText>",
+    DeletedText::from(("text", TextSpace::Preserve)),
+    r#"<w:delText xml:space="preserve">text</w:delText>"#,
+);
@@ -0,0 +1,76 @@
+use crate::{__setter, __xml_test_suites, document::ParagraphContent, private_prelude::*};
+
+/// Deleted Run Content
+///
+/// Content which has been deleted as a tracked revision.
+///
+/// The text of deleted runs is stored as `DeletedText`.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let del = Deletion::new("1")
+///     .author("John")
+///     .date("2020-01-01T00:00:00Z")
+///     .push(Run::default().push(DeletedText::from("text")));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:del")]
+pub struct Deletion<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Option<Cow<'a, str>>,
+    /// Specifies the date of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+    /// Specifies the deleted content
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:smartTag",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+impl<'a> Deletion<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(id: T) -> Self {
+        Deletion {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    __setter!(author: Option<Cow<'a, str>>);
+    __setter!(date: Option<Cow<'a, str>>);
+
+    #[inline(always)]
+    pub fn push<T: Into<ParagraphContent<'a>>>(mut self, content: T) -> Self {
+        self.content.push(content.into());
+        self
+    }
+}
+
+#[cfg(test)]
+use crate::document::Run;
+
+__xml_test_suites!(
+    Deletion,
+    Deletion::new("1"),
+    r#"<w:del w:id="1"/>"#,
+    Deletion::new("1").author("John").push(Run::default()),
+    r#"<w:del w:id="1" w:author="John"><w:r><w:rPr/></w:r></w:del>"#,
+);
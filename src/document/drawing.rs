@@ -8,20 +8,55 @@ use hard_xml::{
     XmlRead, XmlReader, XmlResult, XmlWrite, XmlWriter
 };
 
+use crate::schema::{SCHEMA_DRAWINGML, SCHEMA_PICTURE, SCHEMA_WORDPROCESSING_DRAWING};
+
 /// Drawing
-#[derive(Debug, Default)]
+///
+/// An inline picture. Only the picture is kept, so a drawing without an
+/// embedded image isn't written back.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// // a 2 x 1 inches picture, the size being in EMUs
+/// let drawing = Drawing::picture("rId5", 1828800, 914400).id(1usize);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
 pub struct Drawing<'a> {
+    /// Specifies the id of the drawing object, unique in the document.
+    pub id: Option<usize>,
+    /// Specifies the name of the drawing object.
+    pub name: Option<Cow<'a, str>>,
+    /// Specifies the relationship id of the embedded image.
     pub embed: Option<Cow<'a, str>>,
+    /// Specifies the width, in EMUs.
     pub width: Option<usize>,
+    /// Specifies the height, in EMUs.
     pub height: Option<usize>,
 }
 
 impl<'a> Drawing<'a> {
+    /// Creates a picture of the embedded image with the given size in EMUs.
+    pub fn picture<T: Into<Cow<'a, str>>>(embed: T, width: usize, height: usize) -> Self {
+        Drawing {
+            embed: Some(embed.into()),
+            width: Some(width),
+            height: Some(height),
+            ..Default::default()
+        }
+    }
+
+    crate::__setter!(id: Option<usize>);
+    crate::__setter!(name: Option<Cow<'a, str>>);
+
     fn consume_any(&mut self, reader: &mut XmlReader<'a>, token: Token<'a>) -> XmlResult<()> {
         match token {
             Token::ElementStart { local, .. } => { 
                 if local.as_bytes() == b"blip" {
                     consume(reader, |r, t| self.consume_blip(r, t))?;
+                } else if local.as_bytes() == b"docPr" {
+                    consume(reader, |r, t| self.consume_doc_pr(r, t))?;
                 } else if local.as_bytes() == b"ext" {
                     consume(reader, |r, t| self.consume_ext(r, t))?;
                 } else {
@@ -67,14 +102,42 @@ impl<'a> Drawing<'a> {
         Ok(())
     }
 
-    fn consume_blip(&mut self, reader: &mut XmlReader<'a>, token: Token<'a>) -> XmlResult<()> {
+    fn consume_doc_pr(&mut self, reader: &mut XmlReader<'a>, token: Token<'a>) -> XmlResult<()> {
         match token {
-            Token::Attribute { local, value, .. } => { 
-                if local.as_bytes() == b"embed" {
-                    self.embed = Some(Cow::Borrowed(value.as_str()));
+            Token::Attribute { local, value, .. } => {
+                match local.as_bytes() {
+                    b"id" => {
+                        self.id = value.as_str().parse().ok();
+                    }
+
+                    b"name" => {
+                        self.name = Some(Cow::Borrowed(value.as_str()));
+                    }
+
+                    _ => {
+                        // Ignore
+                    }
                 }
             }
 
+            Token::ElementStart { .. } => {
+                consume(reader, |r, t| self.consume_any(r, t))?;
+            }
+
+            _ => {
+                // Ignore
+            }
+        }
+
+        Ok(())
+    }
+
+    fn consume_blip(&mut self, reader: &mut XmlReader<'a>, token: Token<'a>) -> XmlResult<()> {
+        match token {
+            Token::Attribute { local, value, .. } if local.as_bytes() == b"embed" => {
+                self.embed = Some(Cow::Borrowed(value.as_str()));
+            }
+
             Token::ElementStart { .. } => { 
                 consume(reader, |r, t| self.consume_blip(r, t))?;
             }
@@ -97,7 +160,101 @@ impl<'a> XmlRead<'a> for Drawing<'a> {
 }
 
 impl<'a> XmlWrite for Drawing<'a> {
-    fn to_writer<W: Write>(&self, _writer: &mut XmlWriter<W>) -> XmlResult<()> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let embed = match &self.embed {
+            Some(embed) => embed,
+            None => return Ok(()),
+        };
+
+        log::debug!("[Drawing] Started writing.");
+
+        let id = self.id.unwrap_or(1).to_string();
+        let name = self.name.as_deref().unwrap_or("Picture");
+        let width = self.width.unwrap_or_default().to_string();
+        let height = self.height.unwrap_or_default().to_string();
+
+        writer.write_element_start("w:drawing")?;
+        writer.write_element_end_open()?;
+
+        writer.write_element_start("wp:inline")?;
+        writer.write_attribute("xmlns:wp", SCHEMA_WORDPROCESSING_DRAWING)?;
+        for dist in &["distT", "distB", "distL", "distR"] {
+            writer.write_attribute(dist, "0")?;
+        }
+        writer.write_element_end_open()?;
+
+        writer.write_element_start("wp:extent")?;
+        writer.write_attribute("cx", &width)?;
+        writer.write_attribute("cy", &height)?;
+        writer.write_element_end_empty()?;
+
+        writer.write_element_start("wp:docPr")?;
+        writer.write_attribute("id", &id)?;
+        writer.write_attribute("name", name)?;
+        writer.write_element_end_empty()?;
+
+        writer.write_element_start("a:graphic")?;
+        writer.write_attribute("xmlns:a", SCHEMA_DRAWINGML)?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:graphicData")?;
+        writer.write_attribute("uri", SCHEMA_PICTURE)?;
+        writer.write_element_end_open()?;
+
+        writer.write_element_start("pic:pic")?;
+        writer.write_attribute("xmlns:pic", SCHEMA_PICTURE)?;
+        writer.write_element_end_open()?;
+
+        writer.write_element_start("pic:nvPicPr")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("pic:cNvPr")?;
+        writer.write_attribute("id", "0")?;
+        writer.write_attribute("name", name)?;
+        writer.write_element_end_empty()?;
+        writer.write_element_start("pic:cNvPicPr")?;
+        writer.write_element_end_empty()?;
+        writer.write_element_end_close("pic:nvPicPr")?;
+
+        writer.write_element_start("pic:blipFill")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:blip")?;
+        writer.write_attribute("r:embed", embed)?;
+        writer.write_element_end_empty()?;
+        writer.write_element_start("a:stretch")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:fillRect")?;
+        writer.write_element_end_empty()?;
+        writer.write_element_end_close("a:stretch")?;
+        writer.write_element_end_close("pic:blipFill")?;
+
+        writer.write_element_start("pic:spPr")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:xfrm")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:off")?;
+        writer.write_attribute("x", "0")?;
+        writer.write_attribute("y", "0")?;
+        writer.write_element_end_empty()?;
+        writer.write_element_start("a:ext")?;
+        writer.write_attribute("cx", &width)?;
+        writer.write_attribute("cy", &height)?;
+        writer.write_element_end_empty()?;
+        writer.write_element_end_close("a:xfrm")?;
+        writer.write_element_start("a:prstGeom")?;
+        writer.write_attribute("prst", "rect")?;
+        writer.write_element_end_open()?;
+        writer.write_element_start("a:avLst")?;
+        writer.write_element_end_empty()?;
+        writer.write_element_end_close("a:prstGeom")?;
+        writer.write_element_end_close("pic:spPr")?;
+
+        writer.write_element_end_close("pic:pic")?;
+        writer.write_element_end_close("a:graphicData")?;
+        writer.write_element_end_close("a:graphic")?;
+        writer.write_element_end_close("wp:inline")?;
+        writer.write_element_end_close("w:drawing")?;
+
+        log::debug!("[Drawing] Finished writing.");
+
         Ok(())
     }
 }
@@ -151,3 +308,18 @@ fn is_close(end: &ElementEnd) -> bool {
 }
 
 /**************************************************************************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read_picture() -> XmlResult<()> {
+        let drawing = Drawing::picture("rId5", 100, 200).id(3usize).name("Photo");
+        let xml = drawing.to_string()?;
+        assert!(xml.contains(r#"<a:blip r:embed="rId5"/>"#));
+        assert_eq!(Drawing::from_str(&xml)?, drawing);
+        assert_eq!(Drawing::default().to_string()?, "");
+        Ok(())
+    }
+}
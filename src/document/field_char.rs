@@ -0,0 +1,74 @@
+use crate::document::FormFieldData;
+use crate::private_prelude::*;
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+/// Complex Field Character
+///
+/// Marks the start, separator or end of a complex field.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let fld = FieldChar::from(FieldCharType::Begin);
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:fldChar")]
+pub struct FieldChar<'a> {
+    /// Specifies the type of this field character.
+    #[xml(attr = "w:fldCharType")]
+    pub ty: FieldCharType,
+    /// Specifies that the field result shall be recalculated.
+    #[xml(attr = "w:dirty")]
+    pub dirty: Option<bool>,
+    /// Specifies the properties of a legacy form field.
+    #[xml(child = "w:ffData")]
+    pub form_data: Option<FormFieldData<'a>>,
+}
+
+impl<'a> FieldChar<'a> {
+    __setter!(form_data: Option<FormFieldData<'a>>);
+}
+
+impl From<FieldCharType> for FieldChar<'_> {
+    fn from(ty: FieldCharType) -> Self {
+        FieldChar {
+            ty,
+            dirty: None,
+            form_data: None,
+        }
+    }
+}
+
+/// Specifies the type of a complex field character
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldCharType {
+    /// Start of the field instructions.
+    Begin,
+    /// Separator between the field instructions and the field result.
+    Separate,
+    /// End of the field.
+    End,
+}
+
+__string_enum! {
+    FieldCharType {
+        Begin = "begin",
+        Separate = "separate",
+        End = "end",
+    }
+}
+
+__xml_test_suites!(
+    FieldChar,
+    FieldChar::from(FieldCharType::Begin),
+    r#"<w:fldChar w:fldCharType="begin"/>"#,
+    FieldChar {
+        ty: FieldCharType::End,
+        dirty: Some(true),
+        form_data: None,
+    },
+    r#"<w:fldChar w:fldCharType="end" w:dirty="true"/>"#,
+    FieldChar::from(FieldCharType::Begin).form_data(FormFieldData::default()),
+    r#"<w:fldChar w:fldCharType="begin"><w:ffData/></w:fldChar>"#,
+);
@@ -0,0 +1,315 @@
+use derive_more::From;
+
+use crate::{__setter, __string_enum, __xml_test_suites, private_prelude::*};
+
+/// Form Field Properties
+///
+/// Specifies the properties of a legacy form field, attached to the
+/// character beginning the field.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let data = FormFieldData::default()
+///     .name("Agree")
+///     .ty(FormCheckBox::default().checked(true));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:ffData")]
+pub struct FormFieldData<'a> {
+    /// Specifies the name of the form field.
+    #[xml(child = "w:name")]
+    pub name: Option<FormFieldName<'a>>,
+    /// Specifies the type of the form field.
+    #[xml(child = "w:checkBox", child = "w:ddList", child = "w:textInput")]
+    pub ty: Option<FormFieldType<'a>>,
+}
+
+impl<'a> FormFieldData<'a> {
+    __setter!(name: Option<FormFieldName<'a>>);
+    __setter!(ty: Option<FormFieldType<'a>>);
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:name")]
+pub struct FormFieldName<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FormFieldName<'a> {
+    fn from(val: T) -> Self {
+        FormFieldName { value: val.into() }
+    }
+}
+
+/// The type of a legacy form field
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum FormFieldType<'a> {
+    #[xml(tag = "w:checkBox")]
+    CheckBox(FormCheckBox),
+    #[xml(tag = "w:ddList")]
+    DropDownList(FormDropDownList<'a>),
+    #[xml(tag = "w:textInput")]
+    TextInput(FormTextInput<'a>),
+}
+
+/// Checkbox form field
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:checkBox")]
+pub struct FormCheckBox {
+    /// Specifies the default state of the checkbox.
+    #[xml(child = "w:default")]
+    pub default_checked: Option<CheckBoxDefault>,
+    /// Specifies the current state of the checkbox.
+    #[xml(child = "w:checked")]
+    pub checked: Option<CheckBoxChecked>,
+}
+
+impl FormCheckBox {
+    __setter!(default_checked: Option<CheckBoxDefault>);
+    __setter!(checked: Option<CheckBoxChecked>);
+
+    /// Returns `true` if the checkbox is checked.
+    pub fn is_checked(&self) -> bool {
+        match (&self.checked, &self.default_checked) {
+            (Some(checked), _) => checked.value != Some(false),
+            (None, Some(default)) => default.value != Some(false),
+            (None, None) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct CheckBoxDefault {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for CheckBoxDefault {
+    fn from(val: T) -> Self {
+        CheckBoxDefault { value: val.into() }
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:checked")]
+pub struct CheckBoxChecked {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for CheckBoxChecked {
+    fn from(val: T) -> Self {
+        CheckBoxChecked { value: val.into() }
+    }
+}
+
+/// Drop-down list form field
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:ddList")]
+pub struct FormDropDownList<'a> {
+    /// Specifies the index of the selected entry.
+    #[xml(child = "w:result")]
+    pub result: Option<DropDownResult>,
+    /// Specifies the index of the entry selected by default.
+    #[xml(child = "w:default")]
+    pub default_index: Option<DropDownDefault>,
+    /// Specifies the entries of the list.
+    #[xml(child = "w:listEntry")]
+    pub entries: Vec<ListEntry<'a>>,
+}
+
+impl<'a> FormDropDownList<'a> {
+    __setter!(result: Option<DropDownResult>);
+    __setter!(default_index: Option<DropDownDefault>);
+
+    pub fn push_entry<T: Into<ListEntry<'a>>>(mut self, entry: T) -> Self {
+        self.entries.push(entry.into());
+        self
+    }
+
+    /// Returns the selected entry.
+    pub fn selected(&self) -> Option<&str> {
+        let index = match (&self.result, &self.default_index) {
+            (Some(result), _) => result.value,
+            (None, Some(default)) => default.value,
+            (None, None) => 0,
+        };
+        self.entries.get(index).map(|entry| &*entry.value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:result")]
+pub struct DropDownResult {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+impl From<usize> for DropDownResult {
+    fn from(value: usize) -> Self {
+        DropDownResult { value }
+    }
+}
+
+#[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct DropDownDefault {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+impl From<usize> for DropDownDefault {
+    fn from(value: usize) -> Self {
+        DropDownDefault { value }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:listEntry")]
+pub struct ListEntry<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for ListEntry<'a> {
+    fn from(val: T) -> Self {
+        ListEntry { value: val.into() }
+    }
+}
+
+/// Text box form field
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:textInput")]
+pub struct FormTextInput<'a> {
+    /// Specifies the type of the text box.
+    #[xml(child = "w:type")]
+    pub ty: Option<TextInputType>,
+    /// Specifies the default text of the text box.
+    #[xml(child = "w:default")]
+    pub default_text: Option<TextInputDefault<'a>>,
+    /// Specifies the maximal length of the text.
+    #[xml(child = "w:maxLength")]
+    pub max_length: Option<TextInputMaxLength>,
+    /// Specifies the format of the text, e.g. `UPPERCASE` or `M/d/yyyy`.
+    #[xml(child = "w:format")]
+    pub format: Option<TextInputFormat<'a>>,
+}
+
+impl<'a> FormTextInput<'a> {
+    __setter!(ty: Option<TextInputType>);
+    __setter!(default_text: Option<TextInputDefault<'a>>);
+    __setter!(max_length: Option<TextInputMaxLength>);
+    __setter!(format: Option<TextInputFormat<'a>>);
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:type")]
+pub struct TextInputType {
+    #[xml(attr = "w:val")]
+    pub value: TextInputTypeVal,
+}
+
+impl From<TextInputTypeVal> for TextInputType {
+    fn from(value: TextInputTypeVal) -> Self {
+        TextInputType { value }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextInputTypeVal {
+    Regular,
+    Number,
+    Date,
+    CurrentDate,
+    CurrentTime,
+    Calculated,
+}
+
+__string_enum! {
+    TextInputTypeVal {
+        Regular = "regular",
+        Number = "number",
+        Date = "date",
+        CurrentDate = "currentDate",
+        CurrentTime = "currentTime",
+        Calculated = "calculated",
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:default")]
+pub struct TextInputDefault<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for TextInputDefault<'a> {
+    fn from(val: T) -> Self {
+        TextInputDefault { value: val.into() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:maxLength")]
+pub struct TextInputMaxLength {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+impl From<usize> for TextInputMaxLength {
+    fn from(value: usize) -> Self {
+        TextInputMaxLength { value }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:format")]
+pub struct TextInputFormat<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for TextInputFormat<'a> {
+    fn from(val: T) -> Self {
+        TextInputFormat { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    FormFieldData,
+    FormFieldData::default(),
+    r#"<w:ffData/>"#,
+    FormFieldData::default()
+        .name("Agree")
+        .ty(FormCheckBox::default().default_checked(false).checked(true)),
+    r#"<w:ffData><w:name w:val="Agree"/><w:checkBox><w:default w:val="false"/><w:checked w:val="true"/></w:checkBox></w:ffData>"#,
+    FormFieldData::default().ty(FormDropDownList::default()
+        .result(1)
+        .push_entry("a")
+        .push_entry("b")),
+    r#"<w:ffData><w:ddList><w:result w:val="1"/><w:listEntry w:val="a"/><w:listEntry w:val="b"/></w:ddList></w:ffData>"#,
+    FormFieldData::default().ty(FormTextInput::default()
+        .ty(TextInputTypeVal::Date)
+        .default_text("none")
+        .max_length(10)
+        .format("M/d/yyyy")),
+    r#"<w:ffData><w:textInput><w:type w:val="date"/><w:default w:val="none"/><w:maxLength w:val="10"/><w:format w:val="M/d/yyyy"/></w:textInput></w:ffData>"#,
+);
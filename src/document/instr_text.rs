@@ -0,0 +1,39 @@
+use crate::__xml_test_suites;
+use crate::document::TextSpace;
+use crate::private_prelude::*;
+
+/// Field Code
+///
+/// The instructions of a complex field, e.g. ` TOC \o "1-3" `.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let instr = InstrText::from(" PAGE ");
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:instrText")]
+pub struct InstrText<'a> {
+    /// Specifies how to handle whitespace
+    #[xml(attr = "xml:space")]
+    pub space: Option<TextSpace>,
+    /// Specifies the field instructions
+    #[xml(text)]
+    pub text: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for InstrText<'a> {
+    fn from(val: T) -> Self {
+        InstrText {
+            space: Some(TextSpace::Preserve),
+            text: val.into(),
+        }
+    }
+}
+
+__xml_test_suites!(
+    InstrText,
+    InstrText::from(" PAGE "),
+    r#"<w:instrText xml:space="preserve"> PAGE </w:instrText>"#,
+);
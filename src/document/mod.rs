@@ -7,11 +7,23 @@ mod bookmark_end;
 mod bookmark_start;
 mod r#break;
 mod comment_range;
+mod deleted_text;
+mod deletion;
 mod drawing;
+mod field_char;
+mod form_field;
 mod grid_column;
 mod hyperlink;
+mod insertion;
+mod instr_text;
+mod move_from;
+mod move_to;
+mod note_reference;
 mod paragraph;
 mod run;
+mod sdt;
+mod sdt_property;
+mod simple_field;
 mod smart_tag;
 mod tab;
 mod table;
@@ -25,13 +37,26 @@ pub use self::{
     bookmark_end::*, 
     bookmark_start::*, 
     comment_range::*, 
+    deleted_text::*,
+    deletion::*,
     drawing::*,
+    field_char::*,
+    form_field::*,
     grid_column::*, 
     hyperlink::*, 
+    insertion::*,
+    instr_text::*,
+    move_from::*,
+    move_to::*,
+    note_reference::*,
     paragraph::*,
     r#break::*, 
     run::*, 
+    sdt::*,
+    sdt_property::*,
+    simple_field::*,
     smart_tag::SmartTag,
+    tab::*,
     table::*, 
     table_cell::*, 
     table_grid::*, 
@@ -43,10 +68,12 @@ use hard_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use std::io::Write;
 
 use crate::__xml_test_suites;
-use crate::schema::SCHEMA_MAIN;
+use crate::schema::{
+    SCHEMA_MAIN, SCHEMA_MARKUP_COMPATIBILITY, SCHEMA_DOCUMENT_RELATIONSHIPS, SCHEMA_WORD_2010, SCHEMA_WORD_2012,
+};
 
 /// The root element of the main document part.
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:document")]
 pub struct Document<'a> {
@@ -72,6 +99,16 @@ impl<'a> XmlWrite for Document<'a> {
 
         writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
 
+        writer.write_attribute("xmlns:r", SCHEMA_DOCUMENT_RELATIONSHIPS)?;
+
+        writer.write_attribute("xmlns:w14", SCHEMA_WORD_2010)?;
+
+        writer.write_attribute("xmlns:w15", SCHEMA_WORD_2012)?;
+
+        writer.write_attribute("xmlns:mc", SCHEMA_MARKUP_COMPATIBILITY)?;
+
+        writer.write_attribute("mc:Ignorable", "w14 w15")?;
+
         writer.write_element_end_open()?;
 
         body.to_writer(writer)?;
@@ -88,8 +125,8 @@ __xml_test_suites!(
     Document,
     Document::default(),
     format!(
-        r#"<w:document xmlns:w="{}"><w:body/></w:document>"#,
-        SCHEMA_MAIN
+        r#"<w:document xmlns:w="{}" xmlns:r="{}" xmlns:w14="{}" xmlns:w15="{}" xmlns:mc="{}" mc:Ignorable="w14 w15"><w:body/></w:document>"#,
+        SCHEMA_MAIN, SCHEMA_DOCUMENT_RELATIONSHIPS, SCHEMA_WORD_2010, SCHEMA_WORD_2012, SCHEMA_MARKUP_COMPATIBILITY
     )
     .as_str(),
 );
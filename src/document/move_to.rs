@@ -0,0 +1,74 @@
+use crate::{__setter, __xml_test_suites, document::ParagraphContent, private_prelude::*};
+
+/// Move Destination Run Content
+///
+/// Content which has been moved to this location as a tracked revision.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let to = MoveTo::new("1")
+///     .author("John")
+///     .date("2020-01-01T00:00:00Z")
+///     .push(Run::default().push_text("text"));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:moveTo")]
+pub struct MoveTo<'a> {
+    /// Specifies a unique identifier for the revision.
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+    /// Specifies the author of the revision.
+    #[xml(attr = "w:author")]
+    pub author: Option<Cow<'a, str>>,
+    /// Specifies the date of the revision.
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+    /// Specifies the moved content
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:smartTag",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+impl<'a> MoveTo<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(id: T) -> Self {
+        MoveTo {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    __setter!(author: Option<Cow<'a, str>>);
+    __setter!(date: Option<Cow<'a, str>>);
+
+    #[inline(always)]
+    pub fn push<T: Into<ParagraphContent<'a>>>(mut self, content: T) -> Self {
+        self.content.push(content.into());
+        self
+    }
+}
+
+#[cfg(test)]
+use crate::document::Run;
+
+__xml_test_suites!(
+    MoveTo,
+    MoveTo::new("1"),
+    r#"<w:moveTo w:id="1"/>"#,
+    MoveTo::new("1").author("John").push(Run::default()),
+    r#"<w:moveTo w:id="1" w:author="John"><w:r><w:rPr/></w:r></w:moveTo>"#,
+);
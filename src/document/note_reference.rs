@@ -0,0 +1,65 @@
+use crate::{__xml_test_suites, private_prelude::*};
+
+/// Footnote Reference
+///
+/// References a footnote by its id, and shows its number.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let run = Run::default().push(FootnoteReference::from("1"));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:footnoteReference")]
+pub struct FootnoteReference<'a> {
+    /// Specifies the id of the footnote.
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for FootnoteReference<'a> {
+    fn from(id: T) -> Self {
+        FootnoteReference { id: id.into() }
+    }
+}
+
+/// Endnote Reference
+///
+/// References an endnote by its id, and shows its number.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:endnoteReference")]
+pub struct EndnoteReference<'a> {
+    /// Specifies the id of the endnote.
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for EndnoteReference<'a> {
+    fn from(id: T) -> Self {
+        EndnoteReference { id: id.into() }
+    }
+}
+
+/// Footnote Reference Mark
+///
+/// Shows the number of the footnote containing it.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:footnoteRef")]
+pub struct FootnoteRef;
+
+/// Endnote Reference Mark
+///
+/// Shows the number of the endnote containing it.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:endnoteRef")]
+pub struct EndnoteRef;
+
+__xml_test_suites!(
+    FootnoteReference,
+    FootnoteReference::from("1"),
+    r#"<w:footnoteReference w:id="1"/>"#,
+);
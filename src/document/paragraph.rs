@@ -26,7 +26,7 @@ use crate::{
 ///     .push(BookmarkStart::default())
 ///     .push(BookmarkEnd::default());
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:p")]
 pub struct Paragraph<'a> {
@@ -50,7 +50,13 @@ pub struct Paragraph<'a> {
         child = "w:hyperlink",
         child = "w:bookmarkStart",
         child = "w:bookmarkEnd",
-        child = "w:smartTag"
+        child = "w:smartTag",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo"
     )]
     pub content: Vec<ParagraphContent<'a>>,
 }
@@ -74,31 +80,18 @@ impl<'a> Paragraph<'a> {
     }
 
     pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
-        self.content
-            .iter()
-            .filter_map(|content| match content {
-                ParagraphContent::Run(run) => Some(run.iter_text()),
-                ParagraphContent::Link(link) => link.content.as_ref().map(|c| c.iter_text()),
-                _ => None,
-            })
-            .flatten()
+        self.content.iter().flat_map(ParagraphContent::iter_text)
     }
 
     pub fn iter_text_mut(&mut self) -> impl Iterator<Item = &mut Cow<'a, str>> {
-        self.content
-            .iter_mut()
-            .filter_map(|content| match content {
-                ParagraphContent::Run(run) => Some(run.iter_text_mut()),
-                ParagraphContent::Link(link) => link.content.as_mut().map(|c| c.iter_text_mut()),
-                _ => None,
-            })
-            .flatten()
+        self.content.iter_mut().flat_map(ParagraphContent::iter_text_mut)
     }
 }
 
 /// A set of elements that can be contained as the content of a paragraph.
-#[derive(Debug, From, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum ParagraphContent<'a> {
     #[xml(tag = "w:commentRangeStart")]
     CommentRangeStart(CommentRangeStart<'a>),
@@ -114,9 +107,58 @@ pub enum ParagraphContent<'a> {
     BookmarkEnd(BookmarkEnd<'a>),
     #[xml(tag = "w:smartTag")]
     SmartTag(SmartTag<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(RunSdt<'a>),
+    #[xml(tag = "w:fldSimple")]
+    SimpleField(SimpleField<'a>),
+    #[xml(tag = "w:ins")]
+    Insertion(Insertion<'a>),
+    #[xml(tag = "w:del")]
+    Deletion(Deletion<'a>),
+    #[xml(tag = "w:moveFrom")]
+    MoveFrom(MoveFrom<'a>),
+    #[xml(tag = "w:moveTo")]
+    MoveTo(MoveTo<'a>),
+}
+
+impl<'a> ParagraphContent<'a> {
+    /// Iterates over the current text, including insertions but not deletions.
+    pub fn iter_text(&self) -> Box<dyn Iterator<Item = &Cow<'a, str>> + '_> {
+        match self {
+            ParagraphContent::Run(run) => Box::new(run.iter_text()),
+            ParagraphContent::Link(link) => Box::new(link.content.iter().flat_map(Run::iter_text)),
+            ParagraphContent::Insertion(Insertion { content, .. })
+            | ParagraphContent::MoveTo(MoveTo { content, .. })
+            | ParagraphContent::SimpleField(SimpleField { content, .. })
+            | ParagraphContent::Sdt(RunSdt {
+                content: RunSdtContent { content },
+                ..
+            }) => Box::new(content.iter().flat_map(ParagraphContent::iter_text)),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Iterates over the current text mutably, including insertions but not deletions.
+    pub fn iter_text_mut(&mut self) -> Box<dyn Iterator<Item = &mut Cow<'a, str>> + '_> {
+        match self {
+            ParagraphContent::Run(run) => Box::new(run.iter_text_mut()),
+            ParagraphContent::Link(link) => {
+                Box::new(link.content.iter_mut().flat_map(Run::iter_text_mut))
+            }
+            ParagraphContent::Insertion(Insertion { content, .. })
+            | ParagraphContent::MoveTo(MoveTo { content, .. })
+            | ParagraphContent::SimpleField(SimpleField { content, .. })
+            | ParagraphContent::Sdt(RunSdt {
+                content: RunSdtContent { content },
+                ..
+            }) => Box::new(content.iter_mut().flat_map(ParagraphContent::iter_text_mut)),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
 pub struct ParagraphId {
     pub value: u32
 }
@@ -143,7 +185,7 @@ __xml_test_suites!(
     Paragraph::default().push(Run::default()),
     r#"<w:p><w:pPr/><w:r><w:rPr/></w:r></w:p>"#,
     Paragraph::default().push(Hyperlink::default()),
-    r#"<w:p><w:pPr/><w:hyperlink><w:r><w:rPr/></w:r></w:hyperlink></w:p>"#,
+    r#"<w:p><w:pPr/><w:hyperlink/></w:p>"#,
     Paragraph::default().push(BookmarkStart::default()),
     r#"<w:p><w:pPr/><w:bookmarkStart/></w:p>"#,
     Paragraph::default().push(BookmarkEnd::default()),
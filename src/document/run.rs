@@ -4,7 +4,10 @@ use std::borrow::Cow;
 
 use crate::{
     __setter, __xml_test_suites,
-    document::{r#break::Break, tab::Tab, text::Text, Drawing},
+    document::{
+        r#break::Break, tab::Tab, text::Text, CommentReference, DeletedText, Drawing, EndnoteRef,
+        EndnoteReference, FieldChar, FootnoteRef, FootnoteReference, InstrText,
+    },
     formatting::CharacterProperty,
 };
 
@@ -23,7 +26,7 @@ use crate::{
 ///     .push_text((" text ", TextSpace::Preserve))
 ///     .push_break(BreakType::Column);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:r")]
 pub struct Run<'a> {
@@ -36,7 +39,15 @@ pub struct Run<'a> {
         child = "w:br",
         child = "w:t",
         child = "w:tab",
-        child = "w:drawing"
+        child = "w:drawing",
+        child = "w:fldChar",
+        child = "w:instrText",
+        child = "w:delText",
+        child = "w:footnoteReference",
+        child = "w:endnoteReference",
+        child = "w:footnoteRef",
+        child = "w:endnoteRef",
+        child = "w:commentReference"
     )]
     /// Specifies the content of a run
     pub content: Vec<RunContent<'a>>,
@@ -65,25 +76,21 @@ impl<'a> Run<'a> {
 
     pub fn iter_text(&self) -> impl Iterator<Item = &Cow<'a, str>> {
         self.content.iter().filter_map(|content| match content {
-            RunContent::Break(_) => None,
-            RunContent::Drawing(_) => None,
-            RunContent::Tab(_) => None,
             RunContent::Text(Text { text, .. }) => Some(text),
+            _ => None,
         })
     }
 
     pub fn iter_text_mut(&mut self) -> impl Iterator<Item = &mut Cow<'a, str>> {
         self.content.iter_mut().filter_map(|content| match content {
-            RunContent::Break(_) => None,
-            RunContent::Drawing(_) => None,
-            RunContent::Tab(_) => None,
             RunContent::Text(Text { text, .. }) => Some(text),
+            _ => None,
         })
     }
 }
 
 /// A set of elements that can be contained as the content of a run.
-#[derive(Debug, From, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum RunContent<'a> {
     #[xml(tag = "w:br")]
@@ -97,6 +104,30 @@ pub enum RunContent<'a> {
 
     #[xml(tag = "w:t")]
     Text(Text<'a>),
+
+    #[xml(tag = "w:fldChar")]
+    FieldChar(FieldChar<'a>),
+
+    #[xml(tag = "w:instrText")]
+    InstrText(InstrText<'a>),
+
+    #[xml(tag = "w:delText")]
+    DeletedText(DeletedText<'a>),
+
+    #[xml(tag = "w:footnoteReference")]
+    FootnoteReference(FootnoteReference<'a>),
+
+    #[xml(tag = "w:endnoteReference")]
+    EndnoteReference(EndnoteReference<'a>),
+
+    #[xml(tag = "w:footnoteRef")]
+    FootnoteRef(FootnoteRef),
+
+    #[xml(tag = "w:endnoteRef")]
+    EndnoteRef(EndnoteRef),
+
+    #[xml(tag = "w:commentReference")]
+    CommentReference(CommentReference<'a>),
 }
 
 __xml_test_suites!(
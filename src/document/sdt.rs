@@ -0,0 +1,158 @@
+use crate::{
+    __setter, __xml_test_suites,
+    document::{BodyContent, ParagraphContent, SdtProperty, TableContent, TableRowContent},
+    formatting::CharacterProperty,
+    private_prelude::*,
+};
+
+// The four kinds of content controls only differ by the content they wrap,
+// which depends on where they appear in the document.
+macro_rules! sdt {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $content:ident, $item:ident, [$($child:literal),*]
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = "w:sdt")]
+        pub struct $name<'a> {
+            /// Specifies the properties of the content control.
+            #[xml(default, child = "w:sdtPr")]
+            pub property: SdtProperty<'a>,
+            /// Specifies the formatting of the end character of the content control.
+            #[xml(child = "w:sdtEndPr")]
+            pub end_property: Option<SdtEndProperty<'a>>,
+            /// Specifies the content of the content control.
+            #[xml(default, child = "w:sdtContent")]
+            pub content: $content<'a>,
+        }
+
+        impl<'a> $name<'a> {
+            __setter!(property: SdtProperty<'a>);
+
+            pub fn push<T: Into<$item<'a>>>(mut self, content: T) -> Self {
+                self.content.content.push(content.into());
+                self
+            }
+        }
+
+        /// Content of a content control
+        #[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = "w:sdtContent")]
+        pub struct $content<'a> {
+            #[xml($(child = $child),*)]
+            pub content: Vec<$item<'a>>,
+        }
+    };
+}
+
+sdt!(
+    /// Block-Level Structured Document Tag
+    ///
+    /// A content control wrapping paragraphs and tables.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    ///
+    /// let sdt = BlockSdt::default()
+    ///     .property(SdtProperty::default().tag("summary").ty(SdtRichText::default()))
+    ///     .push(Paragraph::default().push_text("text"));
+    /// ```
+    BlockSdt,
+    BlockSdtContent,
+    BodyContent,
+    ["w:p", "w:tbl", "w:sdt"]
+);
+
+sdt!(
+    /// Run-Level Structured Document Tag
+    ///
+    /// A content control wrapping runs within a paragraph.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    ///
+    /// let sdt = RunSdt::default()
+    ///     .property(SdtProperty::default().tag("name").ty(SdtText::default()))
+    ///     .push(Run::default().push_text("John"));
+    /// ```
+    RunSdt,
+    RunSdtContent,
+    ParagraphContent,
+    [
+        "w:commentRangeStart",
+        "w:commentRangeEnd",
+        "w:r",
+        "w:hyperlink",
+        "w:bookmarkStart",
+        "w:bookmarkEnd",
+        "w:smartTag",
+        "w:sdt",
+        "w:fldSimple",
+        "w:ins",
+        "w:del",
+        "w:moveFrom",
+        "w:moveTo"
+    ]
+);
+
+sdt!(
+    /// Row-Level Structured Document Tag
+    ///
+    /// A content control wrapping rows of a table.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    ///
+    /// let sdt = RowSdt::default()
+    ///     .property(SdtProperty::default().tag("items"))
+    ///     .push(TableRow::default().push_cell(Paragraph::default()));
+    /// ```
+    RowSdt,
+    RowSdtContent,
+    TableContent,
+    ["w:tr", "w:sdt"]
+);
+
+sdt!(
+    /// Cell-Level Structured Document Tag
+    ///
+    /// A content control wrapping cells of a table row.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    ///
+    /// let sdt = CellSdt::default()
+    ///     .property(SdtProperty::default().tag("price"))
+    ///     .push(TableCell::from(Paragraph::default()));
+    /// ```
+    CellSdt,
+    CellSdtContent,
+    TableRowContent,
+    ["w:tc", "w:sdt"]
+);
+
+/// Structured Document Tag End Property
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:sdtEndPr")]
+pub struct SdtEndProperty<'a> {
+    #[xml(child = "w:rPr")]
+    pub property: Option<CharacterProperty<'a>>,
+}
+
+#[cfg(test)]
+use crate::document::Paragraph;
+
+__xml_test_suites!(
+    BlockSdt,
+    BlockSdt::default(),
+    r#"<w:sdt><w:sdtPr/><w:sdtContent/></w:sdt>"#,
+    BlockSdt::default()
+        .property(SdtProperty::default().tag("a"))
+        .push(Paragraph::default())
+        .push(BlockSdt::default()),
+    r#"<w:sdt><w:sdtPr><w:tag w:val="a"/></w:sdtPr><w:sdtContent><w:p><w:pPr/></w:p><w:sdt><w:sdtPr/><w:sdtContent/></w:sdt></w:sdtContent></w:sdt>"#,
+);
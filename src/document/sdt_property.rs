@@ -0,0 +1,554 @@
+use derive_more::From;
+
+use crate::{__setter, __string_enum, __xml_test_suites, private_prelude::*};
+
+/// Structured Document Tag Property
+///
+/// Specifies the identity, the behavior and the type of a content control.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let prop = SdtProperty::default()
+///     .alias("Customer Name")
+///     .tag("customer")
+///     .id(42)
+///     .lock(SdtLockVal::SdtLocked)
+///     .ty(SdtText::default());
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:sdtPr")]
+pub struct SdtProperty<'a> {
+    /// Specifies the friendly name of the content control.
+    #[xml(child = "w:alias")]
+    pub alias: Option<SdtAlias<'a>>,
+    /// Specifies the programmatic tag of the content control.
+    #[xml(child = "w:tag")]
+    pub tag: Option<SdtTag<'a>>,
+    /// Specifies the unique numeric identifier of the content control.
+    #[xml(child = "w:id")]
+    pub id: Option<SdtId>,
+    /// Specifies whether the content control or its content can be edited or deleted.
+    #[xml(child = "w:lock")]
+    pub lock: Option<SdtLock>,
+    /// Specifies the document part used as placeholder text.
+    #[xml(child = "w:placeholder")]
+    pub placeholder: Option<SdtPlaceholder<'a>>,
+    /// Specifies that the content control currently displays its placeholder text.
+    #[xml(child = "w:showingPlcHdr")]
+    pub showing_placeholder: Option<ShowingPlaceholder>,
+    /// Specifies the custom XML data the content of the control is bound to.
+    #[xml(child = "w:dataBinding")]
+    pub data_binding: Option<DataBinding<'a>>,
+    /// Specifies the type of the content control.
+    #[xml(
+        child = "w:comboBox",
+        child = "w:date",
+        child = "w:dropDownList",
+        child = "w:picture",
+        child = "w:richText",
+        child = "w:text",
+        child = "w14:checkbox",
+        child = "w15:repeatingSection",
+        child = "w15:repeatingSectionItem"
+    )]
+    pub ty: Option<SdtType<'a>>,
+}
+
+impl<'a> SdtProperty<'a> {
+    __setter!(alias: Option<SdtAlias<'a>>);
+    __setter!(tag: Option<SdtTag<'a>>);
+    __setter!(id: Option<SdtId>);
+    __setter!(lock: Option<SdtLock>);
+    __setter!(placeholder: Option<SdtPlaceholder<'a>>);
+    __setter!(showing_placeholder: Option<ShowingPlaceholder>);
+    __setter!(data_binding: Option<DataBinding<'a>>);
+    __setter!(ty: Option<SdtType<'a>>);
+
+    /// Returns `true` if the content of the control may not be edited.
+    pub fn is_content_locked(&self) -> bool {
+        matches!(
+            self.lock,
+            Some(SdtLock {
+                value: SdtLockVal::ContentLocked
+            }) | Some(SdtLock {
+                value: SdtLockVal::SdtContentLocked
+            })
+        )
+    }
+
+    /// Returns `true` if the control currently displays its placeholder text.
+    pub fn is_showing_placeholder(&self) -> bool {
+        self.showing_placeholder
+            .as_ref()
+            .is_some_and(|showing| showing.value != Some(false))
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:alias")]
+pub struct SdtAlias<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SdtAlias<'a> {
+    fn from(val: T) -> Self {
+        SdtAlias { value: val.into() }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tag")]
+pub struct SdtTag<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SdtTag<'a> {
+    fn from(val: T) -> Self {
+        SdtTag { value: val.into() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:id")]
+pub struct SdtId {
+    #[xml(attr = "w:val")]
+    pub value: isize,
+}
+
+impl From<isize> for SdtId {
+    fn from(value: isize) -> Self {
+        SdtId { value }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:lock")]
+pub struct SdtLock {
+    #[xml(attr = "w:val")]
+    pub value: SdtLockVal,
+}
+
+impl From<SdtLockVal> for SdtLock {
+    fn from(value: SdtLockVal) -> Self {
+        SdtLock { value }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SdtLockVal {
+    SdtLocked,
+    ContentLocked,
+    Unlocked,
+    SdtContentLocked,
+}
+
+__string_enum! {
+    SdtLockVal {
+        SdtLocked = "sdtLocked",
+        ContentLocked = "contentLocked",
+        Unlocked = "unlocked",
+        SdtContentLocked = "sdtContentLocked",
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:placeholder")]
+pub struct SdtPlaceholder<'a> {
+    /// Specifies the name of the document part holding the placeholder text.
+    #[xml(child = "w:docPart")]
+    pub doc_part: Option<DocPart<'a>>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for SdtPlaceholder<'a> {
+    fn from(val: T) -> Self {
+        SdtPlaceholder {
+            doc_part: Some(DocPart { value: val.into() }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:docPart")]
+pub struct DocPart<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:showingPlcHdr")]
+pub struct ShowingPlaceholder {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for ShowingPlaceholder {
+    fn from(val: T) -> Self {
+        ShowingPlaceholder { value: val.into() }
+    }
+}
+
+/// Data Binding
+///
+/// Maps the content of a control to a node of a custom XML part.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let binding = DataBinding::from("/ns0:invoice[1]/ns0:customer[1]")
+///     .prefix_mappings("xmlns:ns0='urn:invoice'")
+///     .store_item_id("{5B3E0F2C-7A1D-4C4B-9E0E-0A1B2C3D4E5F}");
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dataBinding")]
+pub struct DataBinding<'a> {
+    /// Specifies the namespace prefixes used in the XPath expression,
+    /// e.g. `xmlns:ns0='urn:invoice'`.
+    #[xml(attr = "w:prefixMappings")]
+    pub prefix_mappings: Option<Cow<'a, str>>,
+    /// Specifies the XPath expression selecting the bound node.
+    #[xml(attr = "w:xpath")]
+    pub xpath: Cow<'a, str>,
+    /// Specifies the item id of the custom XML part.
+    #[xml(attr = "w:storeItemID")]
+    pub store_item_id: Option<Cow<'a, str>>,
+}
+
+impl<'a> DataBinding<'a> {
+    __setter!(prefix_mappings: Option<Cow<'a, str>>);
+    __setter!(store_item_id: Option<Cow<'a, str>>);
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for DataBinding<'a> {
+    fn from(val: T) -> Self {
+        DataBinding {
+            prefix_mappings: None,
+            xpath: val.into(),
+            store_item_id: None,
+        }
+    }
+}
+
+/// The type of a content control
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum SdtType<'a> {
+    #[xml(tag = "w:comboBox")]
+    ComboBox(SdtComboBox<'a>),
+    #[xml(tag = "w:date")]
+    Date(SdtDate<'a>),
+    #[xml(tag = "w:dropDownList")]
+    DropDownList(SdtDropDownList<'a>),
+    #[xml(tag = "w:picture")]
+    Picture(SdtPicture),
+    #[xml(tag = "w:richText")]
+    RichText(SdtRichText),
+    #[xml(tag = "w:text")]
+    Text(SdtText),
+    #[xml(tag = "w14:checkbox")]
+    Checkbox(SdtCheckbox<'a>),
+    #[xml(tag = "w15:repeatingSection")]
+    RepeatingSection(SdtRepeatingSection<'a>),
+    #[xml(tag = "w15:repeatingSectionItem")]
+    RepeatingSectionItem(SdtRepeatingSectionItem),
+}
+
+/// Plain text content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:text")]
+pub struct SdtText {
+    /// Specifies whether line breaks are allowed.
+    #[xml(attr = "w:multiLine")]
+    pub multi_line: Option<bool>,
+}
+
+impl SdtText {
+    __setter!(multi_line: Option<bool>);
+}
+
+/// Rich text content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:richText")]
+pub struct SdtRichText {}
+
+/// Picture content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:picture")]
+pub struct SdtPicture {}
+
+/// Date picker content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:date")]
+pub struct SdtDate<'a> {
+    /// Specifies the selected date in ISO 8601 format.
+    #[xml(attr = "w:fullDate")]
+    pub full_date: Option<Cow<'a, str>>,
+    /// Specifies the format of the displayed date, e.g. `M/d/yyyy`.
+    #[xml(child = "w:dateFormat")]
+    pub format: Option<DateFormat<'a>>,
+    /// Specifies the language used to display the date, e.g. `en-US`.
+    #[xml(child = "w:lid")]
+    pub language: Option<DateLanguage<'a>>,
+}
+
+impl<'a> SdtDate<'a> {
+    __setter!(full_date: Option<Cow<'a, str>>);
+    __setter!(format: Option<DateFormat<'a>>);
+    __setter!(language: Option<DateLanguage<'a>>);
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dateFormat")]
+pub struct DateFormat<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for DateFormat<'a> {
+    fn from(val: T) -> Self {
+        DateFormat { value: val.into() }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:lid")]
+pub struct DateLanguage<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for DateLanguage<'a> {
+    fn from(val: T) -> Self {
+        DateLanguage { value: val.into() }
+    }
+}
+
+macro_rules! list_control {
+    ($(#[$attr:meta])* $name:ident, $tag:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            /// Specifies the value of the selected item.
+            #[xml(attr = "w:lastValue")]
+            pub last_value: Option<Cow<'a, str>>,
+            /// Specifies the items of the list.
+            #[xml(child = "w:listItem")]
+            pub items: Vec<ListItem<'a>>,
+        }
+
+        impl<'a> $name<'a> {
+            crate::__setter!(last_value: Option<Cow<'a, str>>);
+
+            pub fn push_item<T: Into<ListItem<'a>>>(mut self, item: T) -> Self {
+                self.items.push(item.into());
+                self
+            }
+        }
+    };
+}
+
+list_control!(
+    /// Combo box content control
+    SdtComboBox,
+    "w:comboBox"
+);
+
+list_control!(
+    /// Drop-down list content control
+    SdtDropDownList,
+    "w:dropDownList"
+);
+
+/// An item of a combo box or drop-down list
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:listItem")]
+pub struct ListItem<'a> {
+    /// Specifies the text displayed for the item.
+    #[xml(attr = "w:displayText")]
+    pub display_text: Option<Cow<'a, str>>,
+    /// Specifies the value of the item.
+    #[xml(attr = "w:value")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for ListItem<'a> {
+    fn from(val: T) -> Self {
+        let value = val.into();
+        ListItem {
+            display_text: Some(value.clone()),
+            value: Some(value),
+        }
+    }
+}
+
+impl<'a> ListItem<'a> {
+    /// Returns the text displayed for the item, falling back to its value.
+    pub fn text(&self) -> &str {
+        self.display_text
+            .as_deref()
+            .or(self.value.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Checkbox content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:checkbox")]
+pub struct SdtCheckbox<'a> {
+    /// Specifies whether the checkbox is checked.
+    #[xml(child = "w14:checked")]
+    pub checked: Option<CheckboxChecked>,
+    /// Specifies the symbol displayed when checked.
+    #[xml(child = "w14:checkedState")]
+    pub checked_state: Option<CheckboxCheckedState<'a>>,
+    /// Specifies the symbol displayed when unchecked.
+    #[xml(child = "w14:uncheckedState")]
+    pub unchecked_state: Option<CheckboxUncheckedState<'a>>,
+}
+
+impl<'a> SdtCheckbox<'a> {
+    __setter!(checked: Option<CheckboxChecked>);
+
+    /// Returns `true` if the checkbox is checked.
+    pub fn is_checked(&self) -> bool {
+        self.checked.as_ref().is_some_and(|checked| checked.value)
+    }
+
+    /// Returns the symbol displayed for the given state.
+    pub fn symbol(&self, checked: bool) -> char {
+        let (code, default) = if checked {
+            (self.checked_state.as_ref().map(|s| &*s.value), '\u{2612}')
+        } else {
+            (self.unchecked_state.as_ref().map(|s| &*s.value), '\u{2610}')
+        };
+        code.and_then(|code| u32::from_str_radix(code, 16).ok())
+            .and_then(std::char::from_u32)
+            .unwrap_or(default)
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w14:checked")]
+pub struct CheckboxChecked {
+    #[xml(attr = "w14:val")]
+    pub value: bool,
+}
+
+impl From<bool> for CheckboxChecked {
+    fn from(value: bool) -> Self {
+        CheckboxChecked { value }
+    }
+}
+
+macro_rules! checkbox_state {
+    ($name:ident, $tag:expr) => {
+        #[derive(Clone, Debug, XmlRead, XmlWrite)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            /// Specifies the hexadecimal code of the symbol.
+            #[xml(attr = "w14:val")]
+            pub value: Cow<'a, str>,
+            /// Specifies the font of the symbol.
+            #[xml(attr = "w14:font")]
+            pub font: Option<Cow<'a, str>>,
+        }
+    };
+}
+
+checkbox_state!(CheckboxCheckedState, "w14:checkedState");
+checkbox_state!(CheckboxUncheckedState, "w14:uncheckedState");
+
+/// Repeating section content control
+///
+/// Its content consists of content controls of type `RepeatingSectionItem`.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:repeatingSection")]
+pub struct SdtRepeatingSection<'a> {
+    /// Specifies the title of the section items.
+    #[xml(child = "w15:sectionTitle")]
+    pub section_title: Option<SectionTitle<'a>>,
+    /// Specifies whether items may be inserted or deleted.
+    #[xml(child = "w15:doNotAllowInsertDeleteSection")]
+    pub do_not_allow_insert_delete: Option<DoNotAllowInsertDeleteSection>,
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:sectionTitle")]
+pub struct SectionTitle<'a> {
+    #[xml(attr = "w15:val")]
+    pub value: Cow<'a, str>,
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:doNotAllowInsertDeleteSection")]
+pub struct DoNotAllowInsertDeleteSection {
+    #[xml(attr = "w15:val")]
+    pub value: Option<bool>,
+}
+
+/// Item of a repeating section content control
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w15:repeatingSectionItem")]
+pub struct SdtRepeatingSectionItem {}
+
+__xml_test_suites!(
+    SdtProperty,
+    SdtProperty::default(),
+    r#"<w:sdtPr/>"#,
+    SdtProperty::default().alias("Name").tag("name").id(1),
+    r#"<w:sdtPr><w:alias w:val="Name"/><w:tag w:val="name"/><w:id w:val="1"/></w:sdtPr>"#,
+    SdtProperty::default()
+        .lock(SdtLockVal::ContentLocked)
+        .placeholder("DefaultPlaceholder")
+        .showing_placeholder(ShowingPlaceholder::default()),
+    r#"<w:sdtPr><w:lock w:val="contentLocked"/><w:placeholder><w:docPart w:val="DefaultPlaceholder"/></w:placeholder><w:showingPlcHdr/></w:sdtPr>"#,
+    SdtProperty::default().data_binding(DataBinding::from("/root[1]/name[1]").store_item_id("{0}")),
+    r#"<w:sdtPr><w:dataBinding w:xpath="/root[1]/name[1]" w:storeItemID="{0}"/></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtText::default().multi_line(true)),
+    r#"<w:sdtPr><w:text w:multiLine="true"/></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtDate::default()
+        .full_date("2020-01-02T00:00:00Z")
+        .format("M/d/yyyy")
+        .language("en-US")),
+    r#"<w:sdtPr><w:date w:fullDate="2020-01-02T00:00:00Z"><w:dateFormat w:val="M/d/yyyy"/><w:lid w:val="en-US"/></w:date></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtDropDownList::default()
+        .last_value("b")
+        .push_item("a")
+        .push_item("b")),
+    r#"<w:sdtPr><w:dropDownList w:lastValue="b"><w:listItem w:displayText="a" w:value="a"/><w:listItem w:displayText="b" w:value="b"/></w:dropDownList></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtComboBox::default()),
+    r#"<w:sdtPr><w:comboBox/></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtCheckbox::default().checked(true)),
+    r#"<w:sdtPr><w14:checkbox><w14:checked w14:val="true"/></w14:checkbox></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtRepeatingSection::default()),
+    r#"<w:sdtPr><w15:repeatingSection/></w:sdtPr>"#,
+    SdtProperty::default().ty(SdtPicture::default()),
+    r#"<w:sdtPr><w:picture/></w:sdtPr>"#,
+);
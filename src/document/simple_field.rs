@@ -0,0 +1,75 @@
+use crate::{__setter, __xml_test_suites, document::ParagraphContent, private_prelude::*};
+
+/// Simple Field
+///
+/// A field whose instructions are stored in a single attribute, e.g.
+/// ` MERGEFIELD FirstName `. Its content is the current field result.
+///
+/// ```rust
+/// use docx::document::*;
+///
+/// let field = SimpleField::new(" MERGEFIELD FirstName ")
+///     .push(Run::default().push_text("«FirstName»"));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:fldSimple")]
+pub struct SimpleField<'a> {
+    /// Specifies the field instructions.
+    #[xml(attr = "w:instr")]
+    pub instr: Cow<'a, str>,
+    /// Specifies that the field result shall not be recalculated.
+    #[xml(attr = "w:fldLock")]
+    pub lock: Option<bool>,
+    /// Specifies that the field result shall be recalculated.
+    #[xml(attr = "w:dirty")]
+    pub dirty: Option<bool>,
+    /// Specifies the field result
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:bookmarkEnd",
+        child = "w:smartTag",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+impl<'a> SimpleField<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(instr: T) -> Self {
+        SimpleField {
+            instr: instr.into(),
+            ..Default::default()
+        }
+    }
+
+    __setter!(lock: Option<bool>);
+    __setter!(dirty: Option<bool>);
+
+    #[inline(always)]
+    pub fn push<T: Into<ParagraphContent<'a>>>(mut self, content: T) -> Self {
+        self.content.push(content.into());
+        self
+    }
+}
+
+#[cfg(test)]
+use crate::document::Run;
+
+__xml_test_suites!(
+    SimpleField,
+    SimpleField::new(" PAGE "),
+    r#"<w:fldSimple w:instr=" PAGE "/>"#,
+    SimpleField::new(" MERGEFIELD Name ")
+        .dirty(true)
+        .push(Run::default().push_text("«Name»")),
+    r#"<w:fldSimple w:instr=" MERGEFIELD Name " w:dirty="true"><w:r><w:rPr/><w:t>«Name»</w:t></w:r></w:fldSimple>"#,
+);
@@ -4,7 +4,7 @@ use crate::{
 };
 
 /// SmartTag
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:smartTag")]
 pub struct SmartTag<'a> {
@@ -16,7 +16,13 @@ pub struct SmartTag<'a> {
         child = "w:hyperlink",
         child = "w:bookmarkStart",
         child = "w:bookmarkEnd",
-        child = "w:smartTag"
+        child = "w:smartTag",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo"
     )]
     pub content: Vec<ParagraphContent<'a>>,
 }
@@ -1,7 +1,7 @@
 use hard_xml::{XmlRead, XmlWrite};
 
 /// Tab
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tab")]
 pub struct Tab;
@@ -1,8 +1,9 @@
+use derive_more::From;
 use hard_xml::{XmlRead, XmlWrite};
 
 use crate::{
     __setter, __xml_test_suites,
-    document::{TableGrid, TableRow},
+    document::{RowSdt, TableGrid, TableRow},
     formatting::TableProperty,
 };
 
@@ -18,7 +19,7 @@ use crate::{
 ///     .push_grid(TableGrid::default())
 ///     .push_row(TableRow::default());
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tbl")]
 pub struct Table<'a> {
@@ -26,8 +27,11 @@ pub struct Table<'a> {
     pub property: TableProperty<'a>,
     #[xml(child = "w:tblGrid")]
     pub grids: Vec<TableGrid>,
-    #[xml(child = "w:tr")]
-    pub rows: Vec<TableRow<'a>>,
+    /// Specifies the rows, some of which may be wrapped in content controls.
+    ///
+    /// Use `iter_rows` to iterate over all rows.
+    #[xml(child = "w:tr", child = "w:sdt")]
+    pub rows: Vec<TableContent<'a>>,
 }
 
 impl<'a> Table<'a> {
@@ -38,10 +42,57 @@ impl<'a> Table<'a> {
         self
     }
 
-    pub fn push_row<T: Into<TableRow<'a>>>(mut self, row: T) -> Self {
+    pub fn push_row<T: Into<TableContent<'a>>>(mut self, row: T) -> Self {
         self.rows.push(row.into());
         self
     }
+
+    /// Iterates over the rows of the table, including the ones wrapped in content controls.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &TableRow<'a>> {
+        self.rows.iter().flat_map(TableContent::iter_rows)
+    }
+
+    /// Iterates over the rows of the table mutably, including the ones wrapped in content controls.
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = &mut TableRow<'a>> {
+        self.rows.iter_mut().flat_map(TableContent::iter_rows_mut)
+    }
+}
+
+/// A set of elements that can be contained in a table
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
+pub enum TableContent<'a> {
+    #[xml(tag = "w:tr")]
+    Row(TableRow<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(RowSdt<'a>),
+}
+
+impl<'a> TableContent<'a> {
+    pub fn iter_rows(&self) -> Box<dyn Iterator<Item = &TableRow<'a>> + '_> {
+        match self {
+            TableContent::Row(row) => Box::new(std::iter::once(row)),
+            TableContent::Sdt(sdt) => Box::new(
+                sdt.content
+                    .content
+                    .iter()
+                    .flat_map(TableContent::iter_rows),
+            ),
+        }
+    }
+
+    pub fn iter_rows_mut(&mut self) -> Box<dyn Iterator<Item = &mut TableRow<'a>> + '_> {
+        match self {
+            TableContent::Row(row) => Box::new(std::iter::once(row)),
+            TableContent::Sdt(sdt) => Box::new(
+                sdt.content
+                    .content
+                    .iter_mut()
+                    .flat_map(TableContent::iter_rows_mut),
+            ),
+        }
+    }
 }
 
 __xml_test_suites!(
@@ -52,4 +103,6 @@ __xml_test_suites!(
     "<w:tbl><w:tblPr/><w:tblGrid/></w:tbl>",
     Table::default().push_row(TableRow::default()),
     "<w:tbl><w:tblPr/><w:tr><w:trPr/></w:tr></w:tbl>",
+    Table::default().push_row(RowSdt::default().push(TableRow::default())),
+    "<w:tbl><w:tblPr/><w:sdt><w:sdtPr/><w:sdtContent><w:tr><w:trPr/></w:tr></w:sdtContent></w:sdt></w:tbl>",
 );
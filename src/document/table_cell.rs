@@ -3,7 +3,7 @@ use derive_more::From;
 use crate::{
     __setter, 
     __xml_test_suites, 
-    document::Paragraph, 
+    document::{BlockSdt, Paragraph, Table},
     formatting::TableCellProperty,
     private_prelude::*,
 };
@@ -19,13 +19,13 @@ use crate::{
 /// let cell = TableCell::pargraph(Paragraph::default())
 ///     .property(TableCellProperty::default());
 /// ```
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tc")]
 pub struct TableCell<'a> {
     #[xml(default, child = "w:tcPr")]
     pub property: TableCellProperty,
-    #[xml(child = "w:p")]
+    #[xml(child = "w:p", child = "w:sdt", child = "w:tbl")]
     pub content: Vec<TableCellContent<'a>>,
 }
 
@@ -49,13 +49,16 @@ impl<'a, T: Into<TableCellContent<'a>>> From<T> for TableCell<'a> {
     }
 }
 
-#[derive(Debug, From, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
 pub enum TableCellContent<'a> {
     #[xml(tag = "w:p")]
     Paragraph(Paragraph<'a>),
-    // #[xml(tag = "w:tbl")]
-    // Table(Table<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(BlockSdt<'a>),
+    #[xml(tag = "w:tbl")]
+    Table(Table<'a>),
 }
 
 __xml_test_suites!(
@@ -1,7 +1,9 @@
+use derive_more::From;
+
 use crate::{
     __setter, 
     __xml_test_suites, 
-    document::TableCell, 
+    document::{CellSdt, TableCell},
     formatting::TableRowProperty,
     private_prelude::*,
 };
@@ -20,23 +22,78 @@ use crate::{
 ///             .property(TableCellProperty::default())
 ///     );
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tr")]
 pub struct TableRow<'a> {
     #[xml(default, child = "w:trPr")]
-    pub property: TableRowProperty,
-    #[xml(child = "w:tc")]
-    pub cells: Vec<TableCell<'a>>,
+    pub property: TableRowProperty<'a>,
+    /// Specifies the cells, some of which may be wrapped in content controls.
+    ///
+    /// Use `iter_cells` to iterate over all cells.
+    #[xml(child = "w:tc", child = "w:sdt")]
+    pub cells: Vec<TableRowContent<'a>>,
 }
 
 impl<'a> TableRow<'a> {
-    __setter!(property: TableRowProperty);
+    __setter!(property: TableRowProperty<'a>);
 
     pub fn push_cell<T: Into<TableCell<'a>>>(mut self, cell: T) -> Self {
-        self.cells.push(cell.into());
+        self.cells.push(TableRowContent::Cell(cell.into()));
+        self
+    }
+
+    pub fn push_sdt(mut self, sdt: CellSdt<'a>) -> Self {
+        self.cells.push(TableRowContent::Sdt(sdt));
         self
     }
+
+    /// Iterates over the cells of the row, including the ones wrapped in content controls.
+    pub fn iter_cells(&self) -> impl Iterator<Item = &TableCell<'a>> {
+        self.cells.iter().flat_map(TableRowContent::iter_cells)
+    }
+
+    /// Iterates over the cells of the row mutably, including the ones wrapped in content controls.
+    pub fn iter_cells_mut(&mut self) -> impl Iterator<Item = &mut TableCell<'a>> {
+        self.cells.iter_mut().flat_map(TableRowContent::iter_cells_mut)
+    }
+}
+
+/// A set of elements that can be contained in a table row
+#[derive(Clone, Debug, From, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[allow(clippy::large_enum_variant)]
+pub enum TableRowContent<'a> {
+    #[xml(tag = "w:tc")]
+    Cell(TableCell<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(CellSdt<'a>),
+}
+
+impl<'a> TableRowContent<'a> {
+    pub fn iter_cells(&self) -> Box<dyn Iterator<Item = &TableCell<'a>> + '_> {
+        match self {
+            TableRowContent::Cell(cell) => Box::new(std::iter::once(cell)),
+            TableRowContent::Sdt(sdt) => Box::new(
+                sdt.content
+                    .content
+                    .iter()
+                    .flat_map(TableRowContent::iter_cells),
+            ),
+        }
+    }
+
+    pub fn iter_cells_mut(&mut self) -> Box<dyn Iterator<Item = &mut TableCell<'a>> + '_> {
+        match self {
+            TableRowContent::Cell(cell) => Box::new(std::iter::once(cell)),
+            TableRowContent::Sdt(sdt) => Box::new(
+                sdt.content
+                    .content
+                    .iter_mut()
+                    .flat_map(TableRowContent::iter_cells_mut),
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -48,4 +105,6 @@ __xml_test_suites!(
     "<w:tr><w:trPr/></w:tr>",
     TableRow::default().push_cell(Paragraph::default()),
     "<w:tr><w:trPr/><w:tc><w:tcPr/><w:p><w:pPr/></w:p></w:tc></w:tr>",
+    TableRow::default().push_sdt(CellSdt::default().push(TableCell::from(Paragraph::default()))),
+    "<w:tr><w:trPr/><w:sdt><w:sdtPr/><w:sdtContent><w:tc><w:tcPr/><w:p><w:pPr/></w:p></w:tc></w:sdtContent></w:sdt></w:tr>",
 );
@@ -1,7 +1,7 @@
 use bytes::Bytes;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{Read, Seek, Write},
     path::Path,
@@ -18,27 +18,39 @@ use zip::{
 use crate::{
     app::App,
     comments::Comments,
-    content_type::ContentTypes,
+    content_type::{
+        self, ContentTypes, CONTENT_TYPE_COMMENTS, CONTENT_TYPE_CUSTOM_XML_PROPERTIES,
+        CONTENT_TYPE_ENDNOTES, CONTENT_TYPE_FOOTER, CONTENT_TYPE_FOOTNOTES, CONTENT_TYPE_HEADER,
+        CONTENT_TYPE_NUMBERING, CONTENT_TYPE_SETTINGS,
+    },
     core::Core,
+    custom_xml::{self, CustomXmlPart, DataStoreItem},
     document::Document,
     error::DocxResult,
     font_table::FontTable,
+    footnotes::{Endnotes, Footnotes},
+    header_footer::{Footer, Header},
     numbering::Numberings,
     private_prelude::*,
-    rels::Relationships,
+    rels::{self, Relationships},
     schema::{
-        SCHEMA_CORE, SCHEMA_FONT_TABLE, SCHEMA_OFFICE_DOCUMENT, SCHEMA_REL_EXTENDED, SCHEMA_STYLES,
+        SCHEMA_COMMENTS, SCHEMA_CORE, SCHEMA_CUSTOM_XML, SCHEMA_CUSTOM_XML_PROPS,
+        SCHEMA_ENDNOTES, SCHEMA_FONT_TABLE, SCHEMA_FOOTNOTES, SCHEMA_IMAGE, SCHEMA_NUMBERING,
+        SCHEMA_OFFICE_DOCUMENT, SCHEMA_REL_EXTENDED, SCHEMA_SETTINGS, SCHEMA_STYLES,
     },
+    settings::Settings,
     styles::Styles,
 };
 
 /// A WordprocessingML package
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Docx<'a> {
     /// Specifies package-level properties part
     pub app: Option<App<'a>>,
     /// Specifies core properties part
     pub core: Option<Core<'a>>,
+    /// Specifies the custom XML data parts, by ZIP item name
+    pub custom_xml: BTreeMap<String, CustomXmlPart<'a>>,
     /// Specifies comments part
     pub comments: Option<Comments<'a>>,
     /// Specifies the content type of relationship parts and the main document part.
@@ -47,14 +59,30 @@ pub struct Docx<'a> {
     pub document: Document<'a>,
     /// Specifies the font table part
     pub font_table: Option<FontTable<'a>>,
+    /// Specifies the footnotes part
+    pub footnotes: Option<Footnotes<'a>>,
+    /// Specifies the endnotes part
+    pub endnotes: Option<Endnotes<'a>>,
+    /// Specifies the header parts, by ZIP item name, e.g. `word/header1.xml`
+    ///
+    /// The parts are referenced from `document_rels` by their target, which
+    /// is relative to the `word` folder, e.g. `header1.xml`.
+    pub headers: BTreeMap<String, Header<'a>>,
+    /// Specifies the footer parts, by ZIP item name, e.g. `word/footer1.xml`
+    pub footers: BTreeMap<String, Footer<'a>>,
     /// Specifies the numbering part
     pub numbering: Option<Numberings<'a>>,
     /// Specifies the style definitions part
     pub styles: Styles<'a>,
+    /// Specifies the document settings part
+    pub settings: Option<Settings<'a>>,
     /// Specifies the package-level relationship to the main document part
     pub rels: Relationships<'a>,
     /// Specifies the part-level relationship to the main document part
     pub document_rels: Option<Relationships<'a>>,
+    /// Specifies the part-level relationships of the header and footer
+    /// parts, by the ZIP item name of the part, e.g. `word/header1.xml`
+    pub part_rels: BTreeMap<String, Relationships<'a>>,
     /// Media files
     pub media: HashMap<String, Bytes>,
 }
@@ -90,6 +118,75 @@ impl<'a> Docx<'a> {
                 .add_rel(SCHEMA_FONT_TABLE, "fontTable.xml");
         }
 
+        if self.numbering.is_some() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_NUMBERING, "numbering.xml");
+            self.content_types
+                .add_override("/word/numbering.xml", CONTENT_TYPE_NUMBERING);
+        }
+
+        if self.comments.is_some() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_COMMENTS, "comments.xml");
+            self.content_types
+                .add_override("/word/comments.xml", CONTENT_TYPE_COMMENTS);
+        }
+
+        if self.footnotes.is_some() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_FOOTNOTES, "footnotes.xml");
+            self.content_types
+                .add_override("/word/footnotes.xml", CONTENT_TYPE_FOOTNOTES);
+        }
+
+        if self.endnotes.is_some() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_ENDNOTES, "endnotes.xml");
+            self.content_types
+                .add_override("/word/endnotes.xml", CONTENT_TYPE_ENDNOTES);
+        }
+
+        if self.settings.is_some() {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_SETTINGS, "settings.xml");
+            self.content_types
+                .add_override("/word/settings.xml", CONTENT_TYPE_SETTINGS);
+        }
+
+        for name in self.headers.keys() {
+            self.content_types
+                .add_override(format!("/{}", name), CONTENT_TYPE_HEADER);
+        }
+
+        for name in self.footers.keys() {
+            self.content_types
+                .add_override(format!("/{}", name), CONTENT_TYPE_FOOTER);
+        }
+
+        for (name, part) in &self.custom_xml {
+            self.document_rels
+                .get_or_insert(Relationships::default())
+                .add_rel(SCHEMA_CUSTOM_XML, format!("../{}", name));
+            if part.properties.is_some() {
+                self.content_types.add_override(
+                    format!("/{}", custom_xml::properties_name(name)),
+                    CONTENT_TYPE_CUSTOM_XML_PROPERTIES,
+                );
+            }
+        }
+
+        for name in self.media.keys() {
+            let ext = name.rsplit('.').next().unwrap_or_default();
+            if let Some(ty) = content_type::image_content_type(ext) {
+                self.content_types.add_default(ext.to_ascii_lowercase(), ty);
+            }
+        }
+
         // ==== Write Zip Item ====
 
         macro_rules! write_xml {
@@ -120,9 +217,51 @@ impl<'a> Docx<'a> {
             self.document             => "word/document.xml"
             self.styles               => "word/styles.xml"
             Some(self.font_table)     => "word/fontTable.xml"
+            Some(self.numbering)      => "word/numbering.xml"
+            Some(self.comments)       => "word/comments.xml"
+            Some(self.footnotes)      => "word/footnotes.xml"
+            Some(self.endnotes)       => "word/endnotes.xml"
+            Some(self.settings)       => "word/settings.xml"
             Some(self.document_rels)  => "word/_rels/document.xml.rels"
         );
 
+        for (name, header) in &self.headers {
+            write_xml!(header => name);
+        }
+
+        for (name, footer) in &self.footers {
+            write_xml!(footer => name);
+        }
+
+        for (name, part_rels) in &self.part_rels {
+            let rels_name = rels::rels_name(name);
+            write_xml!(part_rels => rels_name);
+        }
+
+        for (name, part) in &self.custom_xml {
+            writer.inner.start_file(name, opt)?;
+            writer.inner.write_all(part.data.as_bytes())?;
+
+            if let Some(properties) = &part.properties {
+                let properties_name = custom_xml::properties_name(name);
+                let rels_name = rels::rels_name(name);
+
+                let mut rels = Relationships::default();
+                let target = properties_name.rsplit('/').next().unwrap_or_default();
+                rels.add_rel(SCHEMA_CUSTOM_XML_PROPS, target.to_owned());
+
+                write_xml!(
+                    properties => properties_name
+                    rels       => rels_name
+                );
+            }
+        }
+
+        for (name, data) in &self.media {
+            writer.inner.start_file(name.as_str(), opt)?;
+            writer.inner.write_all(data)?;
+        }
+
         Ok(writer.inner.finish()?)
     }
 
@@ -130,6 +269,74 @@ impl<'a> Docx<'a> {
         let file = File::create(path)?;
         self.write(file)
     }
+
+    /// Returns the name and the content of the media file embedded by the
+    /// main document part with the given relationship id.
+    pub(crate) fn embedded_media(&self, embed: &str) -> Option<(&str, &Bytes)> {
+        self.part_media(None, embed)
+    }
+
+    /// Returns the name and the content of the media file embedded by the
+    /// given header or footer part, or by the main document part for `None`.
+    pub(crate) fn part_media(&self, part: Option<&str>, embed: &str) -> Option<(&str, &Bytes)> {
+        let target = self.relationships(part)?.get_target(embed)?;
+        let target = target.trim_start_matches('/');
+        let name = if target.starts_with("word/") {
+            target.to_owned()
+        } else {
+            format!("word/{}", target)
+        };
+        self.media
+            .get_key_value(&name)
+            .map(|(name, data)| (name.as_str(), data))
+    }
+
+    /// Adds a media file to the main document part and returns its
+    /// relationship id and its number, which is unique among the media.
+    pub(crate) fn embed_media(&mut self, data: Bytes, extension: &str) -> (String, usize) {
+        self.embed_part_media(None, data, extension)
+    }
+
+    /// Adds a media file to the given header or footer part, or to the main
+    /// document part for `None`, like [`Docx::embed_media`].
+    pub(crate) fn embed_part_media(
+        &mut self,
+        part: Option<&str>,
+        data: Bytes,
+        extension: &str,
+    ) -> (String, usize) {
+        let mut number = self.media.len() + 1;
+        while self
+            .media
+            .contains_key(&format!("word/media/image{}.{}", number, extension))
+        {
+            number += 1;
+        }
+        let name = format!("media/image{}.{}", number, extension);
+        self.media.insert(format!("word/{}", name), data);
+        let embed = self.relationships_mut(part).add_rel(SCHEMA_IMAGE, name);
+        (embed, number)
+    }
+
+    /// Returns the relationships of the given header or footer part, or of
+    /// the main document part for `None`.
+    pub(crate) fn relationships(&self, part: Option<&str>) -> Option<&Relationships<'a>> {
+        match part {
+            Some(part) => self.part_rels.get(part),
+            None => self.document_rels.as_ref(),
+        }
+    }
+
+    /// Returns the relationships of the given header or footer part, or of
+    /// the main document part for `None`, creating them if missing.
+    pub(crate) fn relationships_mut(&mut self, part: Option<&str>) -> &mut Relationships<'a> {
+        match part {
+            Some(part) => self.part_rels.entry(part.to_owned()).or_default(),
+            None => self
+                .document_rels
+                .get_or_insert_with(Relationships::default),
+        }
+    }
 }
 
 /// An extracted docx file
@@ -138,11 +345,18 @@ pub struct DocxFile {
     content_types: String,
     comments: Option<String>,
     core: Option<String>,
+    custom_xml: BTreeMap<String, String>,
     document: String,
     document_rels: Option<String>,
     font_table: Option<String>,
+    footnotes: Option<String>,
+    endnotes: Option<String>,
+    headers: Vec<(String, String)>,
+    footers: Vec<(String, String)>,
     numbering: Option<String>,
+    part_rels: Vec<(String, String)>,
     rels: String,
+    settings: Option<String>,
     styles: Option<String>,
     media: HashMap<String, Bytes>,
 }
@@ -182,17 +396,52 @@ impl DocxFile {
         let document_rels = option_read!(Relationships, "word/_rels/document.xml.rels");
         let document = read!(Document, "word/document.xml");
         let font_table = option_read!(FontTable, "word/fontTable.xml");
-        let numbering = option_read!(FontTable, "word/numbering.xml");
+        let footnotes = option_read!(Footnotes, "word/footnotes.xml");
+        let endnotes = option_read!(Endnotes, "word/endnotes.xml");
+        let numbering = option_read!(Numberings, "word/numbering.xml");
         let rels = read!(Relationships, "_rels/.rels");
+        let settings = option_read!(Settings, "word/settings.xml");
         let styles = option_read!(Styles, "word/styles.xml");
 
         let mut media = HashMap::new();
+        let mut headers = Vec::new();
+        let mut footers = Vec::new();
+        let mut part_rels = Vec::new();
+        let mut custom_xml = BTreeMap::new();
 
         for i in 0..zip.len() {
             let mut entry = zip.by_index(i)?;
             let name = entry.name();
 
-            if name.starts_with("word/media/") {
+            let part = name
+                .strip_prefix("word/")
+                .filter(|part| part.ends_with(".xml") && !part.contains('/'));
+
+            if let Some(part) = part {
+                let parts = if part.starts_with("header") {
+                    &mut headers
+                } else if part.starts_with("footer") {
+                    &mut footers
+                } else {
+                    continue;
+                };
+
+                let name = name.to_string();
+                let mut buffer = String::new();
+                entry.read_to_string(&mut buffer)?;
+                parts.push((name, buffer));
+            } else if let Some(part) = header_footer_rels(name) {
+                let mut buffer = String::new();
+                entry.read_to_string(&mut buffer)?;
+                part_rels.push((part, buffer));
+            } else if name.starts_with("customXml/") {
+                let name = name.to_string();
+
+                let mut data = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut data)?;
+
+                custom_xml.insert(name, decode_text(data));
+            } else if name.starts_with("word/media/") {
                 let name = name.to_string();
 
                 let mut data = Vec::with_capacity(entry.size() as usize);
@@ -207,11 +456,18 @@ impl DocxFile {
             comments,
             content_types,
             core,
+            custom_xml,
             document_rels,
             document,
             font_table,
+            footnotes,
+            endnotes,
+            headers,
+            footers,
             numbering,
+            part_rels,
             rels,
+            settings,
             styles,
             media,
         })
@@ -260,6 +516,53 @@ impl DocxFile {
             .map(|c| FontTable::from_str(c))
             .transpose()?;
 
+        let footnotes = self
+            .footnotes
+            .as_ref()
+            .map(|content| Footnotes::from_str(content))
+            .transpose()?;
+
+        let endnotes = self
+            .endnotes
+            .as_ref()
+            .map(|content| Endnotes::from_str(content))
+            .transpose()?;
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, content)| Ok((name.clone(), Header::from_str(content)?)))
+            .collect::<DocxResult<_>>()?;
+
+        let footers = self
+            .footers
+            .iter()
+            .map(|(name, content)| Ok((name.clone(), Footer::from_str(content)?)))
+            .collect::<DocxResult<_>>()?;
+
+        let part_rels = self
+            .part_rels
+            .iter()
+            .map(|(name, content)| Ok((name.clone(), Relationships::from_str(content)?)))
+            .collect::<DocxResult<_>>()?;
+
+        let custom_xml = self
+            .custom_xml
+            .iter()
+            .filter(|(name, _)| is_custom_xml_item(name))
+            .map(|(name, data)| {
+                let properties = self
+                    .custom_xml_properties(name)?
+                    .map(DataStoreItem::from_str)
+                    .transpose()?;
+                let part = CustomXmlPart {
+                    data: data.as_str().into(),
+                    properties,
+                };
+                Ok((name.clone(), part))
+            })
+            .collect::<DocxResult<_>>()?;
+
         let numbering = if let Some(content) = &self.numbering {
             Some(Numberings::from_str(content)?)
         } else {
@@ -268,10 +571,16 @@ impl DocxFile {
 
         let rels = Relationships::from_str(&self.rels)?;
 
+        let settings = self
+            .settings
+            .as_ref()
+            .map(|content| Settings::from_str(content))
+            .transpose()?;
+
         let styles = self
             .styles
             .as_ref()
-            .map(|content| Styles::from_str(&content))
+            .map(|content| Styles::from_str(content))
             .transpose()?
             .unwrap_or_default();
 
@@ -282,13 +591,96 @@ impl DocxFile {
             comments,
             content_types,
             core,
+            custom_xml,
             document,
             document_rels,
             font_table,
+            footnotes,
+            endnotes,
+            headers,
+            footers,
             numbering,
+            part_rels,
             rels,
+            settings,
             styles,
             media,
         })
     }
+
+    /// Returns the properties part of a custom XML part, found through the
+    /// relationships of the part or by naming convention.
+    fn custom_xml_properties(&self, name: &str) -> DocxResult<Option<&str>> {
+        let mut properties_name = custom_xml::properties_name(name);
+
+        if let Some(rels) = self.custom_xml.get(&rels::rels_name(name)) {
+            let rels = Relationships::from_str(rels)?;
+            let target = rels
+                .relationships
+                .iter()
+                .find(|rel| rel.ty == SCHEMA_CUSTOM_XML_PROPS)
+                .map(|rel| &*rel.target);
+            if let Some(target) = target {
+                properties_name = match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_owned(),
+                    None => format!("customXml/{}", target),
+                };
+            }
+        }
+
+        Ok(self.custom_xml.get(&properties_name).map(String::as_str))
+    }
+}
+
+/// Returns the name of the header or footer part whose relationships are
+/// stored in the given ZIP item, e.g. `word/header1.xml` for
+/// `word/_rels/header1.xml.rels`.
+fn header_footer_rels(name: &str) -> Option<String> {
+    let part = name.strip_prefix("word/_rels/")?.strip_suffix(".rels")?;
+    if (part.starts_with("header") || part.starts_with("footer")) && !part.contains('/') {
+        Some(format!("word/{}", part))
+    } else {
+        None
+    }
+}
+
+/// Returns `true` for the data items of the `customXml` folder, leaving
+/// out their properties and relationships.
+fn is_custom_xml_item(name: &str) -> bool {
+    name.strip_prefix("customXml/").is_some_and(|file| {
+        file.ends_with(".xml") && !file.contains('/') && !file.starts_with("itemProps")
+    })
+}
+
+/// Decodes a text part, which may be encoded in UTF-16 with a byte order mark.
+fn decode_text(data: Vec<u8>) -> String {
+    let utf16 = match data.get(..2) {
+        Some([0xFF, 0xFE]) => Some(u16::from_le_bytes as fn([u8; 2]) -> u16),
+        Some([0xFE, 0xFF]) => Some(u16::from_be_bytes as fn([u8; 2]) -> u16),
+        _ => None,
+    };
+
+    match utf16 {
+        Some(from_bytes) => {
+            let units = data[2..]
+                .chunks_exact(2)
+                .map(|pair| from_bytes([pair[0], pair[1]]));
+            let text: String = char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            // the declaration would still claim UTF-16 once written back as UTF-8
+            let declaration_end = text
+                .strip_prefix("<?xml")
+                .and_then(|rest| rest.find("?>"))
+                .map(|end| end + "<?xml?>".len());
+            match declaration_end {
+                Some(end) => text[end..].trim_start().to_owned(),
+                None => text,
+            }
+        }
+        None => match String::from_utf8(data) {
+            Ok(text) => text.trim_start_matches('\u{feff}').to_owned(),
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        },
+    }
 }
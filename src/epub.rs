@@ -0,0 +1,650 @@
+//! EPUB export
+//!
+//! Converts a document to an EPUB 3 package: the body is split into XHTML
+//! chapters, the headings make up the navigation document, the core
+//! properties the metadata of the package, and the images of the document
+//! are carried along.
+//!
+//! ```rust
+//! use docx::document::Paragraph;
+//! use docx::epub::EpubOptions;
+//! use docx::formatting::ParagraphProperty;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//! for title in ["Installation", "Usage"] {
+//!     docx.document.push(
+//!         Paragraph::default()
+//!             .property(ParagraphProperty::default().style_id("Heading1"))
+//!             .push_text(title),
+//!     );
+//!     docx.document.push(Paragraph::default().push_text("..."));
+//! }
+//!
+//! let epub = docx.to_epub(&EpubOptions::default());
+//! assert_eq!(epub.chapters.len(), 2);
+//! assert!(epub.nav.contains("<li><a href=\"chapter2.xhtml#heading-2\">Usage</a>"));
+//! ```
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fs::File,
+    hash::{Hash, Hasher},
+    io::{Seek, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use hard_xml::utils::xml_escape;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    __setter,
+    content_type::image_content_type,
+    date_format::DateTime,
+    document::BodyContent,
+    error::DocxResult,
+    html::{stylesheet, HtmlOptions, OutlineHeading, XhtmlParts},
+    private_prelude::*,
+    toc::TableOfContents,
+    Docx,
+};
+
+/// The media type of EPUB packages.
+const MIME_TYPE: &str = "application/epub+zip";
+
+/// The folder of the package document and its resources.
+const ROOT: &str = "EPUB";
+
+/// Where chapters start
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChapterSplit {
+    /// Each heading of the first level starts a chapter.
+    Heading,
+    /// Each section starts a chapter.
+    Section,
+}
+
+/// Options of EPUB export
+#[derive(Clone, Debug)]
+pub struct EpubOptions<'a> {
+    /// Specifies where chapters start.
+    pub split: ChapterSplit,
+    /// Specifies the language of the publication.
+    pub language: Cow<'a, str>,
+    /// Specifies the unique identifier of the publication, e.g. its ISBN.
+    ///
+    /// An identifier derived from the content is used otherwise.
+    pub identifier: Option<Cow<'a, str>>,
+    /// Specifies the date of the last modification, e.g.
+    /// `2024-01-02T13:04:05Z`, the current time being used otherwise.
+    pub modified: Option<Cow<'a, str>>,
+    /// Specifies whether to keep the formatting in a stylesheet, like
+    /// [`HtmlOptions::faithful`].
+    pub faithful: bool,
+}
+
+impl Default for EpubOptions<'_> {
+    fn default() -> Self {
+        EpubOptions {
+            split: ChapterSplit::Heading,
+            language: "en".into(),
+            identifier: None,
+            modified: None,
+            faithful: false,
+        }
+    }
+}
+
+impl<'a> EpubOptions<'a> {
+    __setter!(split: ChapterSplit);
+    __setter!(language: Cow<'a, str>);
+    __setter!(identifier: Option<Cow<'a, str>>);
+    __setter!(modified: Option<Cow<'a, str>>);
+    __setter!(faithful: bool);
+}
+
+/// A document converted to an EPUB package
+#[derive(Clone, Debug, Default)]
+pub struct Epub {
+    /// The package document, `EPUB/package.opf`.
+    pub package: String,
+    /// The navigation document, `EPUB/nav.xhtml`.
+    pub nav: String,
+    /// The chapters, `EPUB/chapter1.xhtml` and so on.
+    pub chapters: Vec<String>,
+    /// The stylesheet, `EPUB/style.css`, in the faithful mode.
+    pub stylesheet: Option<String>,
+    /// The images, by path relative to the `EPUB` folder.
+    pub images: BTreeMap<String, Bytes>,
+}
+
+impl Epub {
+    /// Writes the package.
+    pub fn write<W: Write + Seek>(&self, writer: W) -> DocxResult<W> {
+        let mut zip = ZipWriter::new(writer);
+
+        // the media type comes first and uncompressed, so that it can be
+        // recognized without unzipping
+        zip.start_file(
+            "mimetype",
+            FileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(MIME_TYPE.as_bytes())?;
+
+        let opt = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file("META-INF/container.xml", opt)?;
+        zip.write_all(
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                 <container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\
+                 <rootfiles><rootfile full-path=\"{}/package.opf\" media-type=\"application/oebps-package+xml\"/></rootfiles>\
+                 </container>",
+                ROOT
+            )
+            .as_bytes(),
+        )?;
+
+        zip.start_file(format!("{}/package.opf", ROOT), opt)?;
+        zip.write_all(self.package.as_bytes())?;
+        zip.start_file(format!("{}/nav.xhtml", ROOT), opt)?;
+        zip.write_all(self.nav.as_bytes())?;
+
+        for (index, chapter) in self.chapters.iter().enumerate() {
+            zip.start_file(format!("{}/{}", ROOT, chapter_file(index)), opt)?;
+            zip.write_all(chapter.as_bytes())?;
+        }
+
+        if let Some(stylesheet) = &self.stylesheet {
+            zip.start_file(format!("{}/style.css", ROOT), opt)?;
+            zip.write_all(stylesheet.as_bytes())?;
+        }
+
+        for (name, data) in &self.images {
+            zip.start_file(format!("{}/{}", ROOT, name), opt)?;
+            zip.write_all(data)?;
+        }
+
+        Ok(zip.finish()?)
+    }
+
+    /// Writes the package to a file.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> DocxResult<File> {
+        let file = File::create(path)?;
+        self.write(file)
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts the document to an EPUB package.
+    ///
+    /// Lists continue across chapters, while notes are written at the end of
+    /// the chapter referencing them. Links to bookmarks of other chapters
+    /// are pointed to their chapter.
+    pub fn to_epub(&self, options: &EpubOptions) -> Epub {
+        let html_options = HtmlOptions::default()
+            .faithful(options.faithful)
+            .image_dir("images");
+        let parts = self.chapters(options.split);
+        let XhtmlParts {
+            parts: bodies,
+            headings,
+            images,
+        } = self.to_xhtml_parts(&html_options, &parts, chapter_file);
+
+        let core = self.core.as_ref();
+        let title = core
+            .and_then(|core| core.title.as_deref())
+            .filter(|title| !title.trim().is_empty())
+            .map(str::to_owned)
+            .or_else(|| headings.first().map(|heading| heading.text.clone()))
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| "Untitled".to_owned());
+
+        let chapter_titles: Vec<String> = (0..bodies.len())
+            .map(|index| {
+                headings
+                    .iter()
+                    .find(|heading| heading.part == index && !heading.text.trim().is_empty())
+                    .map_or_else(
+                        || format!("Chapter {}", index + 1),
+                        |heading| heading.text.clone(),
+                    )
+            })
+            .collect();
+
+        let stylesheet = if options.faithful {
+            Some(stylesheet(&self.styles))
+        } else {
+            None
+        };
+        let chapters: Vec<String> = bodies
+            .iter()
+            .zip(&chapter_titles)
+            .map(|(body, title)| {
+                xhtml_document(&options.language, title, stylesheet.is_some(), body)
+            })
+            .collect();
+
+        let nav = xhtml_document(
+            &options.language,
+            &title,
+            false,
+            &nav_body(&headings, &chapter_titles),
+        );
+
+        let identifier = options
+            .identifier
+            .as_deref()
+            .map(str::to_owned)
+            .unwrap_or_else(|| {
+                let mut hasher = DefaultHasher::new();
+                chapters.hash(&mut hasher);
+                format!("urn:docx:{:016x}", hasher.finish())
+            });
+        let modified = options
+            .modified
+            .as_deref()
+            .map(str::to_owned)
+            .unwrap_or_else(|| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |now| now.as_secs());
+                DateTime::from_timestamp(now).format("yyyy-MM-dd'T'HH:mm:ss'Z'")
+            });
+
+        let mut metadata = String::new();
+        let mut element = |name: &str, value: &str| {
+            if !value.trim().is_empty() {
+                metadata += &format!("<{}>{}</{}>", name, xml_escape(value.trim()), name);
+            }
+        };
+        element("dc:title", &title);
+        element("dc:language", &options.language);
+        if let Some(core) = core {
+            element("dc:creator", core.creator.as_deref().unwrap_or_default());
+            element("dc:subject", core.subject.as_deref().unwrap_or_default());
+            for keyword in core
+                .keywords
+                .iter()
+                .flat_map(|keywords| keywords.split([',', ';']))
+            {
+                element("dc:subject", keyword);
+            }
+            element(
+                "dc:description",
+                core.description.as_deref().unwrap_or_default(),
+            );
+            element(
+                "dc:contributor",
+                core.last_modified_by.as_deref().unwrap_or_default(),
+            );
+        }
+
+        let mut manifest = String::from(
+            "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>",
+        );
+        let mut spine = String::new();
+        for index in 0..chapters.len() {
+            manifest += &format!(
+                "<item id=\"chapter{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>",
+                index + 1,
+                chapter_file(index)
+            );
+            spine += &format!("<itemref idref=\"chapter{}\"/>", index + 1);
+        }
+        if stylesheet.is_some() {
+            manifest += "<item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>";
+        }
+        for (index, name) in images.keys().enumerate() {
+            let ext = name.rsplit('.').next().unwrap_or_default();
+            manifest += &format!(
+                "<item id=\"image{}\" href=\"{}\" media-type=\"{}\"/>",
+                index + 1,
+                xml_escape(name),
+                image_content_type(ext).unwrap_or("application/octet-stream")
+            );
+        }
+
+        let package = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"uid\" xml:lang=\"{lang}\">\n\
+             <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\
+             <dc:identifier id=\"uid\">{id}</dc:identifier>{metadata}\
+             <meta property=\"dcterms:modified\">{modified}</meta></metadata>\n\
+             <manifest>{manifest}</manifest>\n\
+             <spine>{spine}</spine>\n\
+             </package>\n",
+            lang = xml_escape(&options.language),
+            id = xml_escape(&identifier),
+            metadata = metadata,
+            modified = xml_escape(&modified),
+            manifest = manifest,
+            spine = spine,
+        );
+
+        Epub {
+            package,
+            nav,
+            chapters,
+            stylesheet,
+            images,
+        }
+    }
+
+    /// Splits the body into chapters, keeping at least one.
+    fn chapters(&self, split: ChapterSplit) -> Vec<&[BodyContent<'a>]> {
+        let toc = TableOfContents::default().heading_style("Heading1", 1);
+        let content = &self.document.body.content;
+
+        let mut chapters = Vec::new();
+        let mut start = 0;
+        for (index, block) in content.iter().enumerate() {
+            let BodyContent::Paragraph(para) = block else {
+                continue;
+            };
+            match split {
+                ChapterSplit::Heading => {
+                    if index > start && toc.heading_level(para, &self.styles) == Some(1) {
+                        chapters.push(&content[start..index]);
+                        start = index;
+                    }
+                }
+                ChapterSplit::Section => {
+                    if para.property.section.is_some() {
+                        chapters.push(&content[start..=index]);
+                        start = index + 1;
+                    }
+                }
+            }
+        }
+        if start < content.len() || chapters.is_empty() {
+            chapters.push(&content[start..]);
+        }
+
+        chapters
+    }
+}
+
+fn chapter_file(index: usize) -> String {
+    format!("chapter{}.xhtml", index + 1)
+}
+
+/// Wraps the body of a chapter or of the navigation document.
+fn xhtml_document(language: &str, title: &str, stylesheet: bool, body: &str) -> String {
+    let link = if stylesheet {
+        "<link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>\n"
+    } else {
+        ""
+    };
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"{lang}\" lang=\"{lang}\">\n\
+         <head>\n<meta charset=\"utf-8\"/>\n<title>{title}</title>\n{link}</head>\n\
+         <body>\n{body}</body>\n</html>\n",
+        lang = xml_escape(language),
+        title = xml_escape(title),
+        link = link,
+        body = body
+    )
+}
+
+/// Writes the table of contents of the navigation document, with an entry
+/// per chapter if there's no heading.
+fn nav_body(headings: &[OutlineHeading], chapter_titles: &[String]) -> String {
+    let mut entries: Vec<(usize, String, &str)> = headings
+        .iter()
+        .map(|heading| {
+            let text = if heading.text.trim().is_empty() {
+                &chapter_titles[heading.part]
+            } else {
+                heading.text.trim()
+            };
+            let href = format!("{}#{}", chapter_file(heading.part), heading.id);
+            (heading.level, href, text)
+        })
+        .collect();
+    if entries.is_empty() {
+        entries = chapter_titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| (1, chapter_file(index), title.as_str()))
+            .collect();
+    }
+
+    let mut nav = String::from("<nav epub:type=\"toc\" id=\"toc\">\n<h1>Contents</h1>\n");
+    // the nesting of lists can't skip levels
+    let mut depth = 0;
+    for (level, href, text) in entries {
+        let level = level.clamp(1, depth + 1);
+        if level > depth {
+            nav += "<ol>\n";
+            depth = level;
+        } else {
+            nav += "</li>\n";
+            while depth > level {
+                nav += "</ol>\n</li>\n";
+                depth -= 1;
+            }
+        }
+        nav += &format!(
+            "<li><a href=\"{}\">{}</a>",
+            xml_escape(&href),
+            xml_escape(text)
+        );
+    }
+    nav += "</li>\n";
+    while depth > 1 {
+        nav += "</ol>\n</li>\n";
+        depth -= 1;
+    }
+    nav += "</ol>\n</nav>\n";
+    nav
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use zip::ZipArchive;
+
+    use super::*;
+    use crate::{
+        core::Core,
+        document::{BookmarkStart, Drawing, Hyperlink, Paragraph, Run, Table, TableRow},
+        formatting::{ParagraphProperty, SectionProperty},
+    };
+
+    #[test]
+    fn to_epub() {
+        let mut docx = Docx {
+            core: Some(Core {
+                title: Some("Manual".into()),
+                creator: Some("Ada".into()),
+                keywords: Some("setup, usage".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let (embed, _) = docx.embed_media(Bytes::from_static(b"png"), "png");
+
+        let heading = |level: usize, text: &'static str| {
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id(format!("Heading{}", level)))
+                .push_text(text)
+        };
+        docx.document
+            .push(Paragraph::default().push_text("Preface"));
+        docx.document.push(heading(1, "Setup"));
+        docx.document.push(heading(3, "Download"));
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    Hyperlink::default()
+                        .anchor("usage")
+                        .content(Run::default().push_text("next")),
+                )
+                .property(ParagraphProperty::default().section(SectionProperty::default())),
+        );
+        docx.document
+            .push(heading(1, "Usage").push(BookmarkStart::default().id("0").name("usage")));
+        docx.document.push(
+            Paragraph::default().push(Run::default().push(Drawing::picture(embed, 914400, 914400))),
+        );
+
+        let options = EpubOptions::default()
+            .identifier("urn:isbn:123")
+            .modified("2024-01-02T13:04:05Z");
+        let epub = docx.to_epub(&options);
+
+        assert_eq!(epub.chapters.len(), 3);
+        assert!(epub.chapters[1].contains("<a href=\"chapter3.xhtml#usage\">next</a>"));
+        assert!(epub.chapters[2]
+            .contains("<img src=\"images/image1.png\" alt=\"\" width=\"96\" height=\"96\"/>"));
+        assert!(epub.nav.contains(
+            "<ol>\n<li><a href=\"chapter2.xhtml#heading-1\">Setup</a><ol>\n\
+             <li><a href=\"chapter2.xhtml#heading-2\">Download</a></li>\n</ol>\n</li>\n\
+             <li><a href=\"chapter3.xhtml#heading-3\">Usage</a></li>\n</ol>\n"
+        ));
+        assert!(epub.package.contains(
+            "<dc:identifier id=\"uid\">urn:isbn:123</dc:identifier><dc:title>Manual</dc:title>\
+             <dc:language>en</dc:language><dc:creator>Ada</dc:creator>\
+             <dc:subject>setup</dc:subject><dc:subject>usage</dc:subject>\
+             <meta property=\"dcterms:modified\">2024-01-02T13:04:05Z</meta>"
+        ));
+        assert!(epub
+            .package
+            .contains("<item id=\"image1\" href=\"images/image1.png\" media-type=\"image/png\"/>"));
+
+        let sections = docx.to_epub(&options.clone().split(ChapterSplit::Section));
+        assert_eq!(sections.chapters.len(), 2);
+
+        let mut zip = ZipArchive::new(epub.write(Cursor::new(Vec::new())).unwrap()).unwrap();
+        assert_eq!(zip.by_index(0).unwrap().name(), "mimetype");
+        let mut container = String::new();
+        zip.by_name("META-INF/container.xml")
+            .unwrap()
+            .read_to_string(&mut container)
+            .unwrap();
+        assert!(container.contains("full-path=\"EPUB/package.opf\""));
+        assert!(zip.by_name("EPUB/images/image1.png").is_ok());
+    }
+
+    fn heading(text: &'static str) -> Paragraph<'static> {
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id("Heading1"))
+            .push_text(text)
+    }
+
+    fn link(anchor: &'static str, text: &'static str) -> Paragraph<'static> {
+        Paragraph::default().push(
+            Hyperlink::default()
+                .anchor(anchor)
+                .content(Run::default().push_text(text)),
+        )
+    }
+
+    #[test]
+    fn cross_chapter_links() {
+        let mut docx = Docx::default();
+        docx.document.push(heading("One"));
+        docx.document
+            .push(link("later", "forward").push(BookmarkStart::default().id("0").name("early")));
+        docx.document.push(link("early", "here"));
+        docx.document.push(heading("Two"));
+        docx.document.push(Table::default().push_row(
+            TableRow::default().push_cell(
+                Paragraph::default().push(BookmarkStart::default().id("1").name("later")),
+            ),
+        ));
+        docx.document.push(link("early", "back"));
+        docx.document.push(link("missing", "nowhere"));
+
+        let epub = docx.to_epub(&EpubOptions::default());
+        assert_eq!(epub.chapters.len(), 2);
+        assert!(epub.chapters[0].contains("<a href=\"chapter2.xhtml#later\">forward</a>"));
+        assert!(epub.chapters[0].contains("<a href=\"#early\">here</a>"));
+        assert!(epub.chapters[1].contains("<a href=\"chapter1.xhtml#early\">back</a>"));
+        assert!(epub.chapters[1].contains("<a href=\"#missing\">nowhere</a>"));
+    }
+
+    #[test]
+    fn package_contents() {
+        let mut docx = Docx::default();
+        let (png, _) = docx.embed_media(Bytes::from_static(b"png data"), "png");
+        let (jpeg, _) = docx.embed_media(Bytes::from_static(b"jpeg data"), "jpeg");
+        docx.document.push(heading("Pictures"));
+        for embed in [png, jpeg] {
+            docx.document.push(
+                Paragraph::default()
+                    .push(Run::default().push(Drawing::picture(embed, 914400, 914400))),
+            );
+        }
+        docx.document.push(heading(""));
+
+        let options = EpubOptions::default()
+            .language("fr")
+            .identifier("urn:uuid:42")
+            .modified("2024-01-02T13:04:05Z")
+            .faithful(true);
+        let epub = docx.to_epub(&options);
+
+        // an untitled document takes the title of its first heading
+        assert!(epub.package.contains(
+            "<dc:identifier id=\"uid\">urn:uuid:42</dc:identifier>\
+             <dc:title>Pictures</dc:title><dc:language>fr</dc:language>\
+             <meta property=\"dcterms:modified\">2024-01-02T13:04:05Z</meta>"
+        ));
+        assert!(epub.package.contains(
+            "<manifest><item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\
+             <item id=\"chapter1\" href=\"chapter1.xhtml\" media-type=\"application/xhtml+xml\"/>\
+             <item id=\"chapter2\" href=\"chapter2.xhtml\" media-type=\"application/xhtml+xml\"/>\
+             <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\
+             <item id=\"image1\" href=\"images/image1.png\" media-type=\"image/png\"/>\
+             <item id=\"image2\" href=\"images/image2.jpeg\" media-type=\"image/jpeg\"/></manifest>"
+        ));
+        assert!(epub
+            .package
+            .contains("<spine><itemref idref=\"chapter1\"/><itemref idref=\"chapter2\"/></spine>"));
+
+        // the empty heading is named after its chapter
+        assert!(epub.nav.contains(
+            "<ol>\n<li><a href=\"chapter1.xhtml#heading-1\">Pictures</a></li>\n\
+             <li><a href=\"chapter2.xhtml#heading-2\">Chapter 2</a></li>\n</ol>\n"
+        ));
+        assert!(epub
+            .nav
+            .contains("<html xmlns=\"http://www.w3.org/1999/xhtml\""));
+        assert!(epub.chapters[0].contains("<title>Pictures</title>"));
+        assert!(epub.chapters[0]
+            .contains("<link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>"));
+        assert!(epub.chapters[0].contains("<img src=\"images/image2.jpeg\""));
+
+        let mut zip = ZipArchive::new(epub.write(Cursor::new(Vec::new())).unwrap()).unwrap();
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "EPUB/chapter1.xhtml",
+                "EPUB/chapter2.xhtml",
+                "EPUB/images/image1.png",
+                "EPUB/images/image2.jpeg",
+                "EPUB/nav.xhtml",
+                "EPUB/package.opf",
+                "EPUB/style.css",
+                "META-INF/container.xml",
+                "mimetype",
+            ]
+        );
+        let mut data = Vec::new();
+        zip.by_name("EPUB/images/image2.jpeg")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(data, b"jpeg data");
+    }
+}
@@ -0,0 +1,780 @@
+//! Find and replace
+//!
+//! Searches the text of paragraphs as a whole, so a phrase is found even if
+//! Word split it into several runs. Tabs, breaks and pictures are searched
+//! as `\t`, `\n` and `\u{fffc}`.
+//!
+//! Replacing edits the text in place: text before and after a match stays in
+//! its run with its formatting, the replacement takes the formatting of the
+//! run where the match starts, and runs emptied by a replacement are removed.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::CharacterProperty;
+//! use docx::find::Pattern;
+//!
+//! let mut document = Document::default();
+//! document.push(
+//!     Paragraph::default()
+//!         .push_text("Hello, Wo")
+//!         .push(Run::default().property(CharacterProperty::default().bold(true)).push_text("rld!")),
+//! );
+//!
+//! let matches = document.find("World");
+//! assert_eq!(matches.len(), 1);
+//! assert_eq!(matches[0].start.run, 0);
+//! assert_eq!(matches[0].end.run, 1);
+//!
+//! # #[cfg(feature = "regex")]
+//! # {
+//! let pattern = Pattern::regex(r"(\w+), (\w+)").unwrap();
+//! assert_eq!(document.replace_all(pattern, "$2, $1"), 1);
+//! # }
+//! ```
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::{
+    comments::Comments,
+    document::{
+        BodyContent, Document, Paragraph, ParagraphContent, Run, RunContent, TableCellContent,
+        TableContent, TableRowContent, Text, TextSpace,
+    },
+    footnotes::{Endnotes, Footnotes},
+    Docx,
+};
+
+/// A pattern to search for
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    /// Matches the text as it is.
+    Literal(String),
+    /// Matches a regular expression. Replacements can refer to capture
+    /// groups, e.g. `$1` or `${name}`.
+    #[cfg(feature = "regex")]
+    Regex(Regex),
+}
+
+impl Pattern {
+    /// Compiles a regular expression pattern.
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Pattern::Regex)
+    }
+
+    /// Returns `true` if the pattern matches somewhere in the text.
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        match self {
+            Pattern::Literal(literal) => text.contains(literal.as_str()),
+            #[cfg(feature = "regex")]
+            Pattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// Returns the byte ranges of matches in the text, with their replacements.
+    fn matches(&self, text: &str, replacement: &str) -> Vec<(usize, usize, String)> {
+        match self {
+            Pattern::Literal(literal) if literal.is_empty() => Vec::new(),
+            Pattern::Literal(literal) => text
+                .match_indices(literal.as_str())
+                .map(|(start, found)| (start, start + found.len(), replacement.to_owned()))
+                .collect(),
+            #[cfg(feature = "regex")]
+            Pattern::Regex(regex) => regex
+                .captures_iter(text)
+                .filter_map(|captures| {
+                    let found = captures.get(0)?;
+                    let mut expanded = String::new();
+                    captures.expand(replacement, &mut expanded);
+                    Some((found.start(), found.end(), expanded))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&str> for Pattern {
+    fn from(literal: &str) -> Self {
+        Pattern::Literal(literal.to_owned())
+    }
+}
+
+impl From<String> for Pattern {
+    fn from(literal: String) -> Self {
+        Pattern::Literal(literal)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<Regex> for Pattern {
+    fn from(regex: Regex) -> Self {
+        Pattern::Regex(regex)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<&Regex> for Pattern {
+    fn from(regex: &Regex) -> Self {
+        Pattern::Regex(regex.clone())
+    }
+}
+
+/// A part of the package containing paragraphs
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    /// The main document
+    Body,
+    /// A header, by ZIP item name
+    Header(String),
+    /// A footer, by ZIP item name
+    Footer(String),
+    /// A footnote, by id
+    Footnote(String),
+    /// An endnote, by id
+    Endnote(String),
+    /// A comment, by id
+    Comment(String),
+}
+
+/// A position in the text of a paragraph
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TextPosition {
+    /// The index of the run in the paragraph, counting the runs of
+    /// hyperlinks, smart tags, content controls, simple fields, insertions
+    /// and moves in document order.
+    pub run: usize,
+    /// The offset in characters in the text of the run.
+    pub offset: usize,
+}
+
+/// A match of a pattern
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    /// The part containing the paragraph.
+    pub part: Part,
+    /// The indices leading to the paragraph in its part: the index of the
+    /// block, then for tables the index of the row, of the cell and of the
+    /// block in the cell, and for content controls the index in their
+    /// content.
+    pub path: Vec<usize>,
+    /// The position where the match starts.
+    pub start: TextPosition,
+    /// The position where the match ends.
+    pub end: TextPosition,
+    /// The matched text.
+    pub text: String,
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the matches of a pattern in the document, headers, footers,
+    /// footnotes, endnotes and comments, in this order.
+    pub fn find<P: Into<Pattern>>(&self, pattern: P) -> Vec<Match> {
+        let pattern = pattern.into();
+        let mut matches = Vec::new();
+
+        find_blocks(
+            &self.document.body.content,
+            &Part::Body,
+            &pattern,
+            &mut matches,
+        );
+        for (name, header) in &self.headers {
+            let part = Part::Header(name.clone());
+            find_blocks(&header.content, &part, &pattern, &mut matches);
+        }
+        for (name, footer) in &self.footers {
+            let part = Part::Footer(name.clone());
+            find_blocks(&footer.content, &part, &pattern, &mut matches);
+        }
+        for note in self.footnotes.iter().flat_map(|notes| &notes.notes) {
+            let part = Part::Footnote(note.id.to_string());
+            find_blocks(&note.content, &part, &pattern, &mut matches);
+        }
+        for note in self.endnotes.iter().flat_map(|notes| &notes.notes) {
+            let part = Part::Endnote(note.id.to_string());
+            find_blocks(&note.content, &part, &pattern, &mut matches);
+        }
+        for comment in self.comments.iter().flat_map(|comments| &comments.comments) {
+            let part = Part::Comment(comment.id.to_string());
+            for (index, para) in comment.content.iter().enumerate() {
+                find_paragraph(para, &part, &[index], &pattern, &mut matches);
+            }
+        }
+
+        matches
+    }
+
+    /// Replaces all matches of a pattern in the document, headers, footers,
+    /// footnotes, endnotes and comments.
+    ///
+    /// Returns the number of replaced matches.
+    pub fn replace_all<P: Into<Pattern>>(&mut self, pattern: P, replacement: &str) -> usize {
+        let pattern = pattern.into();
+        let mut count = 0;
+        let mut replace = |_: &[usize], para: &mut Paragraph<'a>| {
+            count += replace_paragraph(para, &pattern, replacement);
+        };
+
+        visit_paragraphs_mut(
+            &mut self.document.body.content,
+            &mut Vec::new(),
+            &mut replace,
+        );
+        for header in self.headers.values_mut() {
+            visit_paragraphs_mut(&mut header.content, &mut Vec::new(), &mut replace);
+        }
+        for footer in self.footers.values_mut() {
+            visit_paragraphs_mut(&mut footer.content, &mut Vec::new(), &mut replace);
+        }
+        if let Some(Footnotes { notes }) = &mut self.footnotes {
+            for note in notes {
+                visit_paragraphs_mut(&mut note.content, &mut Vec::new(), &mut replace);
+            }
+        }
+        if let Some(Endnotes { notes }) = &mut self.endnotes {
+            for note in notes {
+                visit_paragraphs_mut(&mut note.content, &mut Vec::new(), &mut replace);
+            }
+        }
+        if let Some(Comments { comments }) = &mut self.comments {
+            for comment in comments {
+                for para in &mut comment.content {
+                    replace(&[], para);
+                }
+            }
+        }
+
+        count
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns the matches of a pattern in the document.
+    pub fn find<P: Into<Pattern>>(&self, pattern: P) -> Vec<Match> {
+        let mut matches = Vec::new();
+        find_blocks(
+            &self.body.content,
+            &Part::Body,
+            &pattern.into(),
+            &mut matches,
+        );
+        matches
+    }
+
+    /// Replaces all matches of a pattern in the document.
+    ///
+    /// Returns the number of replaced matches.
+    pub fn replace_all<P: Into<Pattern>>(&mut self, pattern: P, replacement: &str) -> usize {
+        let pattern = pattern.into();
+        let mut count = 0;
+        visit_paragraphs_mut(&mut self.body.content, &mut Vec::new(), &mut |_, para| {
+            count += replace_paragraph(para, &pattern, replacement);
+        });
+        count
+    }
+}
+
+fn find_blocks(content: &[BodyContent], part: &Part, pattern: &Pattern, matches: &mut Vec<Match>) {
+    visit_paragraphs(content, &mut Vec::new(), &mut |path, para| {
+        find_paragraph(para, part, path, pattern, matches)
+    });
+}
+
+fn find_paragraph(
+    para: &Paragraph,
+    part: &Part,
+    path: &[usize],
+    pattern: &Pattern,
+    matches: &mut Vec<Match>,
+) {
+    let index = TextIndex::new(para);
+
+    for (start, end, _) in pattern.matches(&index.text, "") {
+        matches.push(Match {
+            part: part.clone(),
+            path: path.to_vec(),
+            start: index.position(start, false),
+            end: index.position(end, start < end),
+            text: index.text[start..end].to_owned(),
+        });
+    }
+}
+
+/// Returns the number of replaced matches.
+fn replace_paragraph(para: &mut Paragraph, pattern: &Pattern, replacement: &str) -> usize {
+    let index = TextIndex::new(para);
+    let matches = pattern.matches(&index.text, replacement);
+    if matches.is_empty() {
+        return 0;
+    }
+
+    replace_text(para, &index, &matches);
+
+    matches.len()
+}
+
+/// Replaces byte ranges of the text of a paragraph, given in increasing
+/// order, and removes the runs they empty.
+pub(crate) fn replace_text(
+    para: &mut Paragraph,
+    index: &TextIndex,
+    edits: &[(usize, usize, String)],
+) {
+    let mut runs = Vec::new();
+    runs_mut(&mut para.content, &mut runs);
+    let had_text: Vec<bool> = (0..runs.len())
+        .map(|run| index.pieces.iter().any(|piece| piece.run == run))
+        .collect();
+
+    let mut slots: Vec<&mut RunContent> = runs
+        .into_iter()
+        .flat_map(|run| run.content.iter_mut())
+        .filter(|content| piece_text(content).is_some())
+        .collect();
+
+    for (start, end, replacement) in edits.iter().rev() {
+        replace_range(&mut slots, &index.pieces, *start, *end, replacement);
+    }
+
+    prune(&mut para.content, &mut 0, &had_text);
+}
+
+/// Replaces the text between `start` and `end`, the replacement going into
+/// the first piece of the range.
+fn replace_range(
+    slots: &mut [&mut RunContent],
+    pieces: &[Piece],
+    start: usize,
+    end: usize,
+    replacement: &str,
+) {
+    let mut first = true;
+
+    for (slot, piece) in slots.iter_mut().zip(pieces) {
+        let overlaps = if start == end {
+            piece.start <= start && start <= piece.end && matches!(slot, RunContent::Text(_))
+        } else {
+            piece.start < end && start < piece.end
+        };
+        if !overlaps {
+            continue;
+        }
+
+        let inserted = if first { replacement } else { "" };
+        let local_start = start.max(piece.start) - piece.start;
+        let local_end = end.min(piece.end) - piece.start;
+
+        match slot {
+            RunContent::Text(text) => {
+                text.text
+                    .to_mut()
+                    .replace_range(local_start..local_end, inserted);
+                text.space = Some(TextSpace::Preserve);
+            }
+            other => {
+                **other = RunContent::Text(Text::from((inserted.to_owned(), TextSpace::Preserve)))
+            }
+        }
+
+        first = false;
+        if start == end {
+            break;
+        }
+    }
+}
+
+/// Removes emptied texts, and runs emptied by a replacement.
+fn prune(content: &mut Vec<ParagraphContent>, counter: &mut usize, had_text: &[bool]) {
+    content.retain_mut(|content| match content {
+        ParagraphContent::Run(run) => {
+            let index = *counter;
+            *counter += 1;
+            run.content.retain(
+                |content| !matches!(content, RunContent::Text(text) if text.text.is_empty()),
+            );
+            !(run.content.is_empty() && had_text.get(index).copied().unwrap_or_default())
+        }
+        ParagraphContent::Link(link) => {
+            if let Some(run) = &mut link.content {
+                *counter += 1;
+                run.content.retain(
+                    |content| !matches!(content, RunContent::Text(text) if text.text.is_empty()),
+                );
+            }
+            true
+        }
+        ParagraphContent::SmartTag(tag) => {
+            prune(&mut tag.content, counter, had_text);
+            true
+        }
+        ParagraphContent::Sdt(sdt) => {
+            prune(&mut sdt.content.content, counter, had_text);
+            true
+        }
+        ParagraphContent::SimpleField(field) => {
+            prune(&mut field.content, counter, had_text);
+            true
+        }
+        ParagraphContent::Insertion(ins) => {
+            prune(&mut ins.content, counter, had_text);
+            true
+        }
+        ParagraphContent::MoveTo(to) => {
+            prune(&mut to.content, counter, had_text);
+            true
+        }
+        _ => true,
+    });
+}
+
+/// A searchable piece of the text of a paragraph
+pub(crate) struct Piece {
+    pub run: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The text of a paragraph, with the runs its pieces come from
+pub(crate) struct TextIndex {
+    pub text: String,
+    pub pieces: Vec<Piece>,
+    /// The byte offset of the first piece of every run
+    pub run_starts: Vec<usize>,
+}
+
+impl TextIndex {
+    pub fn new(para: &Paragraph) -> Self {
+        let mut runs = Vec::new();
+        runs_of(&para.content, &mut runs);
+
+        let mut text = String::new();
+        let mut pieces = Vec::new();
+        let mut run_starts = Vec::with_capacity(runs.len());
+
+        for (index, run) in runs.iter().enumerate() {
+            run_starts.push(text.len());
+            for piece in run.content.iter().filter_map(piece_text) {
+                let start = text.len();
+                text.push_str(piece);
+                pieces.push(Piece {
+                    run: index,
+                    start,
+                    end: text.len(),
+                });
+            }
+        }
+
+        TextIndex {
+            text,
+            pieces,
+            run_starts,
+        }
+    }
+
+    /// Returns the position of a byte offset, in the run ending there if
+    /// `is_end` is `true`, or in the run starting there otherwise.
+    pub fn position(&self, offset: usize, is_end: bool) -> TextPosition {
+        let piece = if is_end {
+            self.pieces
+                .iter()
+                .find(|piece| piece.start < offset && offset <= piece.end)
+        } else {
+            self.pieces
+                .iter()
+                .find(|piece| piece.start <= offset && offset < piece.end)
+        };
+        let run = piece
+            .or_else(|| self.pieces.last())
+            .map(|piece| piece.run)
+            .unwrap_or_default();
+        let run_start = self.run_starts.get(run).copied().unwrap_or_default();
+
+        TextPosition {
+            run,
+            offset: self.text[run_start..offset].chars().count(),
+        }
+    }
+}
+
+/// Returns the searchable text of a run content.
+pub(crate) fn piece_text<'s>(content: &'s RunContent) -> Option<&'s str> {
+    match content {
+        RunContent::Text(text) => Some(&text.text),
+        RunContent::Tab(_) => Some("\t"),
+        RunContent::Break(_) => Some("\n"),
+        RunContent::Drawing(_) => Some("\u{fffc}"),
+        _ => None,
+    }
+}
+
+/// Collects the runs of paragraph content in document order, leaving out
+/// deleted runs.
+pub(crate) fn runs_of<'s, 'a>(content: &'s [ParagraphContent<'a>], runs: &mut Vec<&'s Run<'a>>) {
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => runs.push(run),
+            ParagraphContent::Link(link) => runs.extend(&link.content),
+            ParagraphContent::SmartTag(tag) => runs_of(&tag.content, runs),
+            ParagraphContent::Sdt(sdt) => runs_of(&sdt.content.content, runs),
+            ParagraphContent::SimpleField(field) => runs_of(&field.content, runs),
+            ParagraphContent::Insertion(ins) => runs_of(&ins.content, runs),
+            ParagraphContent::MoveTo(to) => runs_of(&to.content, runs),
+            _ => (),
+        }
+    }
+}
+
+pub(crate) fn runs_mut<'s, 'a>(
+    content: &'s mut [ParagraphContent<'a>],
+    runs: &mut Vec<&'s mut Run<'a>>,
+) {
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => runs.push(run),
+            ParagraphContent::Link(link) => runs.extend(&mut link.content),
+            ParagraphContent::SmartTag(tag) => runs_mut(&mut tag.content, runs),
+            ParagraphContent::Sdt(sdt) => runs_mut(&mut sdt.content.content, runs),
+            ParagraphContent::SimpleField(field) => runs_mut(&mut field.content, runs),
+            ParagraphContent::Insertion(ins) => runs_mut(&mut ins.content, runs),
+            ParagraphContent::MoveTo(to) => runs_mut(&mut to.content, runs),
+            _ => (),
+        }
+    }
+}
+
+/// Calls `f` on every paragraph of the blocks with its path.
+pub(crate) fn visit_paragraphs<'s, 'a>(
+    content: &'s [BodyContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &'s Paragraph<'a>),
+) {
+    for (index, content) in content.iter().enumerate() {
+        path.push(index);
+        match content {
+            BodyContent::Paragraph(para) => f(path, para),
+            BodyContent::Table(table) => visit_rows(&table.rows, path, f),
+            BodyContent::Sdt(sdt) => visit_paragraphs(&sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+fn visit_rows<'s, 'a>(
+    content: &'s [TableContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &'s Paragraph<'a>),
+) {
+    for (index, content) in content.iter().enumerate() {
+        path.push(index);
+        match content {
+            TableContent::Row(row) => visit_cells(&row.cells, path, f),
+            TableContent::Sdt(sdt) => visit_rows(&sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+fn visit_cells<'s, 'a>(
+    content: &'s [TableRowContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &'s Paragraph<'a>),
+) {
+    for (index, content) in content.iter().enumerate() {
+        path.push(index);
+        match content {
+            TableRowContent::Cell(cell) => {
+                for (index, content) in cell.content.iter().enumerate() {
+                    path.push(index);
+                    match content {
+                        TableCellContent::Paragraph(para) => f(path, para),
+                        TableCellContent::Sdt(sdt) => {
+                            visit_paragraphs(&sdt.content.content, path, f)
+                        }
+                        TableCellContent::Table(table) => visit_rows(&table.rows, path, f),
+                    }
+                    path.pop();
+                }
+            }
+            TableRowContent::Sdt(sdt) => visit_cells(&sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+/// Calls `f` on every paragraph of the blocks with its path.
+pub(crate) fn visit_paragraphs_mut<'a>(
+    content: &mut [BodyContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &mut Paragraph<'a>),
+) {
+    for (index, content) in content.iter_mut().enumerate() {
+        path.push(index);
+        match content {
+            BodyContent::Paragraph(para) => f(path, para),
+            BodyContent::Table(table) => visit_rows_mut(&mut table.rows, path, f),
+            BodyContent::Sdt(sdt) => visit_paragraphs_mut(&mut sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+fn visit_rows_mut<'a>(
+    content: &mut [TableContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &mut Paragraph<'a>),
+) {
+    for (index, content) in content.iter_mut().enumerate() {
+        path.push(index);
+        match content {
+            TableContent::Row(row) => visit_cells_mut(&mut row.cells, path, f),
+            TableContent::Sdt(sdt) => visit_rows_mut(&mut sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+fn visit_cells_mut<'a>(
+    content: &mut [TableRowContent<'a>],
+    path: &mut Vec<usize>,
+    f: &mut dyn FnMut(&[usize], &mut Paragraph<'a>),
+) {
+    for (index, content) in content.iter_mut().enumerate() {
+        path.push(index);
+        match content {
+            TableRowContent::Cell(cell) => {
+                for (index, content) in cell.content.iter_mut().enumerate() {
+                    path.push(index);
+                    match content {
+                        TableCellContent::Paragraph(para) => f(path, para),
+                        TableCellContent::Sdt(sdt) => {
+                            visit_paragraphs_mut(&mut sdt.content.content, path, f)
+                        }
+                        TableCellContent::Table(table) => {
+                            visit_rows_mut(&mut table.rows, path, f)
+                        }
+                    }
+                    path.pop();
+                }
+            }
+            TableRowContent::Sdt(sdt) => visit_cells_mut(&mut sdt.content.content, path, f),
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        comments::Comment,
+        document::{Hyperlink, Table, TableRow},
+        footnotes::Footnote,
+        formatting::CharacterProperty,
+        header_footer::Header,
+    };
+
+    fn run(text: &'static str, bold: bool) -> Run<'static> {
+        Run::default()
+            .property(CharacterProperty::default().bold(bold))
+            .push_text(text)
+    }
+
+    fn texts(para: &Paragraph) -> Vec<(String, bool)> {
+        let mut runs = Vec::new();
+        runs_of(&para.content, &mut runs);
+        runs.iter()
+            .map(|run| {
+                let text = run.iter_text().map(|text| text.to_string()).collect();
+                let bold = run.property.bold.as_ref().and_then(|bold| bold.value);
+                (text, bold == Some(true))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replace_across_runs() {
+        let mut para = Paragraph::default()
+            .push(run("The qu", false))
+            .push(run("ick", true))
+            .push(run(" brown", false))
+            .push(run(" fox", true));
+
+        let index = TextIndex::new(&para);
+        let found = Pattern::from("quick brown").matches(&index.text, "");
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            index.position(found[0].0, false),
+            TextPosition { run: 0, offset: 4 }
+        );
+        assert_eq!(
+            index.position(found[0].1, true),
+            TextPosition { run: 2, offset: 6 }
+        );
+
+        assert_eq!(
+            replace_paragraph(&mut para, &"quick brown".into(), "slow"),
+            1
+        );
+        assert_eq!(
+            texts(&para),
+            vec![("The slow".to_owned(), false), (" fox".to_owned(), true)]
+        );
+
+        #[cfg(feature = "regex")]
+        {
+            let pattern = Pattern::regex(r"(\w+) (\w+)$").unwrap();
+            assert_eq!(replace_paragraph(&mut para, &pattern, "$2 $1"), 1);
+            assert_eq!(texts(&para), vec![("The fox slow".to_owned(), false)]);
+        }
+    }
+
+    #[test]
+    fn find_in_parts() {
+        let table = Table::default().push_row(
+            TableRow::default().push_cell(Paragraph::default().push_text("a\u{2014}needle")),
+        );
+        let link = Hyperlink::default().content(Run::default().push_text("needle"));
+
+        let mut docx = Docx::default();
+        docx.document.push(Paragraph::default().push_text("none"));
+        docx.document.push(table);
+        docx.document.push(Paragraph::default().push(link));
+
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("needle"));
+        docx.headers.insert("word/header1.xml".into(), header);
+
+        let mut footnotes = Footnotes::default();
+        footnotes.push(Footnote::new("1").push(Paragraph::default().push_text("a needle")));
+        docx.footnotes = Some(footnotes);
+
+        docx.comments = Some(Comments {
+            comments: vec![Comment {
+                id: "0".into(),
+                content: vec![Paragraph::default().push_text("needles")],
+                ..Default::default()
+            }],
+        });
+
+        let found: Vec<_> = docx
+            .find("needle")
+            .into_iter()
+            .map(|found| (found.part, found.path, found.start.offset))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Part::Body, vec![1, 0, 0, 0], 2),
+                (Part::Body, vec![2], 0),
+                (Part::Header("word/header1.xml".into()), vec![0], 0),
+                (Part::Footnote("1".into()), vec![0], 2),
+                (Part::Comment("0".into()), vec![0], 0),
+            ]
+        );
+
+        assert_eq!(docx.replace_all("needle", "pin"), 5);
+        assert!(docx.find("needle").is_empty());
+        assert_eq!(docx.find("pins").len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::private_prelude::*;
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:family")]
 pub struct Family<'a> {
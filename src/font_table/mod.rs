@@ -26,7 +26,7 @@ use crate::{
 ///     .push_font("Arial")
 ///     .push_font(Font::new("Helvetica").family("swiss"));
 /// ```
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:fonts")]
 pub struct FontTable<'a> {
@@ -0,0 +1,167 @@
+//! Footnotes and endnotes parts
+//!
+//! The corresponding ZIP items are `/word/footnotes.xml` and `/word/endnotes.xml`.
+
+use std::io::Write;
+
+use crate::{
+    __setter, __string_enum, __xml_test_suites,
+    document::BodyContent,
+    private_prelude::*,
+    schema::{SCHEMA_DOCUMENT_RELATIONSHIPS, SCHEMA_MAIN},
+};
+
+macro_rules! notes {
+    ($(#[$attr:meta])* $name:ident, $tag:literal, $(#[$note_attr:meta])* $note:ident, $note_tag:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, XmlRead)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            /// Specifies the notes, including separators.
+            #[xml(child = $note_tag)]
+            pub notes: Vec<$note<'a>>,
+        }
+
+        impl<'a> $name<'a> {
+            pub fn push(&mut self, note: $note<'a>) -> &mut Self {
+                self.notes.push(note);
+                self
+            }
+
+            /// Returns the note with the given id.
+            pub fn get(&self, id: &str) -> Option<&$note<'a>> {
+                self.notes.iter().find(|note| note.id == id)
+            }
+        }
+
+        impl<'a> XmlWrite for $name<'a> {
+            fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+                let $name { notes } = self;
+
+                log::debug!(concat!("[", stringify!($name), "] Started writing."));
+
+                writer.write_element_start($tag)?;
+
+                writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
+
+                writer.write_attribute("xmlns:r", SCHEMA_DOCUMENT_RELATIONSHIPS)?;
+
+                if notes.is_empty() {
+                    writer.write_element_end_empty()?;
+                } else {
+                    writer.write_element_end_open()?;
+                    for ele in notes {
+                        ele.to_writer(writer)?;
+                    }
+                    writer.write_element_end_close($tag)?;
+                }
+
+                log::debug!(concat!("[", stringify!($name), "] Finished writing."));
+
+                Ok(())
+            }
+        }
+
+        $(#[$note_attr])*
+        #[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $note_tag)]
+        pub struct $note<'a> {
+            /// Specifies the kind of the note, a normal note if omitted.
+            #[xml(attr = "w:type")]
+            pub ty: Option<NoteType>,
+            /// Specifies the id of the note, referenced from the main document.
+            #[xml(attr = "w:id")]
+            pub id: Cow<'a, str>,
+            /// Specifies the content of the note.
+            #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
+            pub content: Vec<BodyContent<'a>>,
+        }
+
+        impl<'a> $note<'a> {
+            pub fn new<T: Into<Cow<'a, str>>>(id: T) -> Self {
+                $note {
+                    id: id.into(),
+                    ..Default::default()
+                }
+            }
+
+            __setter!(ty: Option<NoteType>);
+
+            #[inline(always)]
+            pub fn push<T: Into<BodyContent<'a>>>(mut self, content: T) -> Self {
+                self.content.push(content.into());
+                self
+            }
+        }
+    };
+}
+
+notes!(
+    /// The root element of the footnotes part.
+    ///
+    /// ```rust
+    /// use docx::document::Paragraph;
+    /// use docx::footnotes::*;
+    ///
+    /// let mut footnotes = Footnotes::default();
+    /// footnotes.push(Footnote::new("1").push(Paragraph::default().push_text("See page 2.")));
+    /// ```
+    Footnotes,
+    "w:footnotes",
+    /// Footnote
+    Footnote,
+    "w:footnote"
+);
+
+notes!(
+    /// The root element of the endnotes part.
+    Endnotes,
+    "w:endnotes",
+    /// Endnote
+    Endnote,
+    "w:endnote"
+);
+
+/// Specifies the kind of a footnote or endnote
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoteType {
+    Normal,
+    Separator,
+    ContinuationSeparator,
+    ContinuationNotice,
+}
+
+__string_enum! {
+    NoteType {
+        Normal = "normal",
+        Separator = "separator",
+        ContinuationSeparator = "continuationSeparator",
+        ContinuationNotice = "continuationNotice",
+    }
+}
+
+#[cfg(test)]
+use crate::document::Paragraph;
+
+__xml_test_suites!(
+    Footnotes,
+    Footnotes::default(),
+    format!(
+        r#"<w:footnotes xmlns:w="{}" xmlns:r="{}"/>"#,
+        SCHEMA_MAIN, SCHEMA_DOCUMENT_RELATIONSHIPS
+    )
+    .as_str(),
+    Footnotes {
+        notes: vec![
+            Footnote::new("-1").ty(NoteType::Separator),
+            Footnote::new("1").push(Paragraph::default())
+        ]
+    },
+    format!(
+        r#"<w:footnotes xmlns:w="{}" xmlns:r="{}"><w:footnote w:type="separator" w:id="-1"/><w:footnote w:id="1"><w:p><w:pPr/></w:p></w:footnote></w:footnotes>"#,
+        SCHEMA_MAIN, SCHEMA_DOCUMENT_RELATIONSHIPS
+    )
+    .as_str(),
+);
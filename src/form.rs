@@ -0,0 +1,482 @@
+//! Form data
+//!
+//! Extracts the values of a filled-in form, from both content controls and
+//! legacy form fields (`w:ffData`).
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::form::FormValue;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//!
+//! docx.document.push(
+//!     Paragraph::default().push(
+//!         RunSdt::default()
+//!             .property(SdtProperty::default().tag("name").ty(SdtText::default()))
+//!             .push(Run::default().push_text("John")),
+//!     ),
+//! );
+//!
+//! let values = docx.form_values();
+//!
+//! assert_eq!(values["name"], FormValue::Text("John".into()));
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{
+    content_control::ContentControl,
+    document::{
+        BodyContent, Document, FieldCharType, FormFieldType, ParagraphContent, RunContent,
+        SdtComboBox, SdtDropDownList, SdtType, TableCellContent, TableContent, TableRowContent,
+        TextInputTypeVal,
+    },
+    Docx,
+};
+
+/// Form values by content control tag or alias, or by legacy form field name
+pub type FormValues = BTreeMap<String, FormValue>;
+
+/// The current value of a form field
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "snake_case"))]
+pub enum FormValue {
+    /// Text entered in a text field
+    Text(String),
+    /// State of a checkbox
+    Checked(bool),
+    /// Item selected in a drop-down list or combo box
+    Selected { value: Option<String>, text: String },
+    /// Date selected in a date picker, in ISO 8601 format, with the displayed text
+    Date { date: Option<String>, text: String },
+    /// Values of each item of a repeating section
+    Section(Vec<FormValues>),
+    /// Placeholder text of a field which hasn't been filled in
+    Placeholder(String),
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the values of the form fields in the main document, its headers and footers.
+    ///
+    /// If several fields share a name, the first one in the document wins.
+    pub fn form_values(&self) -> FormValues {
+        let mut collector = Collector::default();
+        collector.blocks(&self.document.body.content);
+        for header in self.headers.values() {
+            collector.blocks(&header.content);
+        }
+        for footer in self.footers.values() {
+            collector.blocks(&footer.content);
+        }
+        collector.values
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns the values of the form fields in the main document.
+    pub fn form_values(&self) -> FormValues {
+        let mut collector = Collector::default();
+        collector.blocks(&self.body.content);
+        collector.values
+    }
+}
+
+impl<'s, 'a> ContentControl<'s, 'a> {
+    /// Returns the current value of the content control.
+    ///
+    /// Pictures, repeating sections and their items don't have a value.
+    pub fn value(&self) -> Option<FormValue> {
+        let property = self.property();
+        let text = self.text();
+
+        match &property.ty {
+            Some(SdtType::Checkbox(checkbox)) => {
+                return Some(FormValue::Checked(checkbox.is_checked()))
+            }
+            Some(SdtType::Picture(_))
+            | Some(SdtType::RepeatingSection(_))
+            | Some(SdtType::RepeatingSectionItem(_)) => return None,
+            _ if property.is_showing_placeholder() => return Some(FormValue::Placeholder(text)),
+            _ => (),
+        }
+
+        let value = match &property.ty {
+            Some(SdtType::ComboBox(SdtComboBox { last_value, items }))
+            | Some(SdtType::DropDownList(SdtDropDownList { last_value, items })) => {
+                let value = last_value.as_deref().or_else(|| {
+                    items
+                        .iter()
+                        .find(|item| item.text() == text)?
+                        .value
+                        .as_deref()
+                });
+                FormValue::Selected {
+                    value: value.map(Into::into),
+                    text,
+                }
+            }
+            Some(SdtType::Date(date)) => FormValue::Date {
+                date: date.full_date.as_deref().map(Into::into),
+                text,
+            },
+            _ => FormValue::Text(text),
+        };
+
+        Some(value)
+    }
+
+    /// Returns the name used as a key in the form values.
+    fn name(&self) -> Option<&'s str> {
+        self.tag().or_else(|| self.alias())
+    }
+
+    /// Returns the content controls directly contained in this one.
+    fn children(&self) -> Vec<ContentControl<'s, 'a>> {
+        match self {
+            ContentControl::Block(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    BodyContent::Sdt(sdt) => Some(ContentControl::Block(sdt)),
+                    _ => None,
+                })
+                .collect(),
+            ContentControl::Run(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    ParagraphContent::Sdt(sdt) => Some(ContentControl::Run(sdt)),
+                    _ => None,
+                })
+                .collect(),
+            ContentControl::Row(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    TableContent::Sdt(sdt) => Some(ContentControl::Row(sdt)),
+                    _ => None,
+                })
+                .collect(),
+            ContentControl::Cell(sdt) => sdt
+                .content
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    TableRowContent::Sdt(sdt) => Some(ContentControl::Cell(sdt)),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A legacy form field whose result is being read
+struct LegacyField<'s, 'a> {
+    name: Option<&'s str>,
+    ty: Option<&'s FormFieldType<'a>>,
+    /// Depth of the fields nested in the result
+    depth: usize,
+    /// Text of the result, once the separator has been reached
+    result: Option<String>,
+}
+
+#[derive(Default)]
+struct Collector<'s, 'a> {
+    values: FormValues,
+    field: Option<LegacyField<'s, 'a>>,
+}
+
+impl<'s, 'a> Collector<'s, 'a> {
+    fn insert(&mut self, name: Option<&str>, value: FormValue) {
+        if let Some(name) = name {
+            self.values.entry(name.to_owned()).or_insert(value);
+        }
+    }
+
+    fn control(&mut self, control: ContentControl<'s, 'a>) {
+        if let Some(SdtType::RepeatingSection(_)) = &control.property().ty {
+            let items = control
+                .children()
+                .into_iter()
+                .filter(|item| matches!(item.property().ty, Some(SdtType::RepeatingSectionItem(_))))
+                .map(|item| {
+                    let mut collector = Collector::default();
+                    collector.children(item);
+                    collector.values
+                })
+                .collect();
+            self.insert(control.name(), FormValue::Section(items));
+            return;
+        }
+
+        if let Some(value) = control.value() {
+            self.insert(control.name(), value);
+        }
+
+        self.children(control);
+    }
+
+    fn children(&mut self, control: ContentControl<'s, 'a>) {
+        match control {
+            ContentControl::Block(sdt) => self.blocks(&sdt.content.content),
+            ContentControl::Run(sdt) => self.runs(&sdt.content.content),
+            ContentControl::Row(sdt) => self.rows(&sdt.content.content),
+            ContentControl::Cell(sdt) => self.cells(&sdt.content.content),
+        }
+    }
+
+    fn blocks(&mut self, content: &'s [BodyContent<'a>]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.runs(&para.content),
+                BodyContent::Table(table) => self.rows(&table.rows),
+                BodyContent::Sdt(sdt) => self.control(ContentControl::Block(sdt)),
+            }
+        }
+    }
+
+    fn rows(&mut self, content: &'s [TableContent<'a>]) {
+        for content in content {
+            match content {
+                TableContent::Row(row) => self.cells(&row.cells),
+                TableContent::Sdt(sdt) => self.control(ContentControl::Row(sdt)),
+            }
+        }
+    }
+
+    fn cells(&mut self, content: &'s [TableRowContent<'a>]) {
+        for content in content {
+            match content {
+                TableRowContent::Cell(cell) => {
+                    for content in &cell.content {
+                        match content {
+                            TableCellContent::Paragraph(para) => self.runs(&para.content),
+                            TableCellContent::Sdt(sdt) => self.control(ContentControl::Block(sdt)),
+                            TableCellContent::Table(table) => self.rows(&table.rows),
+                        }
+                    }
+                }
+                TableRowContent::Sdt(sdt) => self.control(ContentControl::Cell(sdt)),
+            }
+        }
+    }
+
+    fn runs(&mut self, content: &'s [ParagraphContent<'a>]) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => {
+                    for content in &run.content {
+                        self.legacy_field(content);
+                    }
+                }
+                ParagraphContent::Link(link) => {
+                    for content in link.content.iter().flat_map(|run| &run.content) {
+                        self.legacy_field(content);
+                    }
+                }
+                ParagraphContent::Sdt(sdt) => self.control(ContentControl::Run(sdt)),
+                ParagraphContent::SmartTag(tag) => self.runs(&tag.content),
+                ParagraphContent::SimpleField(field) => self.runs(&field.content),
+                ParagraphContent::Insertion(ins) => self.runs(&ins.content),
+                ParagraphContent::MoveTo(to) => self.runs(&to.content),
+                _ => (),
+            }
+        }
+    }
+
+    /// Reads the legacy form fields, which span from their beginning to their
+    /// end character, possibly across runs and paragraphs.
+    fn legacy_field(&mut self, content: &'s RunContent<'a>) {
+        match content {
+            RunContent::FieldChar(field_char) => match (field_char.ty, &mut self.field) {
+                (FieldCharType::Begin, Some(field)) => field.depth += 1,
+                (FieldCharType::Begin, None) => {
+                    if let Some(data) = &field_char.form_data {
+                        self.field = Some(LegacyField {
+                            name: data.name.as_ref().map(|name| &*name.value),
+                            ty: data.ty.as_ref(),
+                            depth: 0,
+                            result: None,
+                        });
+                    }
+                }
+                (FieldCharType::Separate, Some(field)) if field.depth == 0 => {
+                    field.result = Some(String::new());
+                }
+                (FieldCharType::End, Some(field)) if field.depth > 0 => field.depth -= 1,
+                (FieldCharType::End, Some(_)) => {
+                    let field = self.field.take().unwrap();
+                    let value = legacy_value(&field);
+                    self.insert(field.name, value);
+                }
+                _ => (),
+            },
+            RunContent::Text(text) => {
+                if let Some(LegacyField {
+                    result: Some(result),
+                    ..
+                }) = &mut self.field
+                {
+                    result.push_str(&text.text);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn legacy_value(field: &LegacyField) -> FormValue {
+    let text = field.result.clone().unwrap_or_default();
+    match field.ty {
+        Some(FormFieldType::CheckBox(checkbox)) => FormValue::Checked(checkbox.is_checked()),
+        Some(FormFieldType::DropDownList(list)) => {
+            let value = list.selected().map(String::from);
+            FormValue::Selected {
+                text: value.clone().unwrap_or(text),
+                value,
+            }
+        }
+        Some(FormFieldType::TextInput(input))
+            if input
+                .ty
+                .as_ref()
+                .is_some_and(|ty| ty.value == TextInputTypeVal::Date) =>
+        {
+            FormValue::Date { date: None, text }
+        }
+        _ => FormValue::Text(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{document::*, header_footer::Header};
+
+    fn legacy_field(data: FormFieldData<'static>, result: &'static str) -> Paragraph<'static> {
+        Paragraph::default()
+            .push(Run::default().push(FieldChar::from(FieldCharType::Begin).form_data(data)))
+            .push(Run::default().push(InstrText::from(" FORMTEXT ")))
+            .push(Run::default().push(FieldChar::from(FieldCharType::Separate)))
+            .push(Run::default().push_text(result))
+            .push(Run::default().push(FieldChar::from(FieldCharType::End)))
+    }
+
+    fn text_control(tag: &'static str, text: &'static str) -> RunSdt<'static> {
+        RunSdt::default()
+            .property(SdtProperty::default().tag(tag).ty(SdtText::default()))
+            .push(Run::default().push_text(text))
+    }
+
+    #[test]
+    fn extract_form_values() {
+        let mut docx = Docx::default();
+
+        docx.document.push(legacy_field(
+            FormFieldData::default()
+                .name("Agree")
+                .ty(FormCheckBox::default().checked(true)),
+            "",
+        ));
+        docx.document.push(legacy_field(
+            FormFieldData::default()
+                .name("Comment")
+                .ty(FormTextInput::default()),
+            "Looks good",
+        ));
+        docx.document.push(legacy_field(
+            FormFieldData::default()
+                .name("Size")
+                .ty(FormDropDownList::default()
+                    .result(1)
+                    .push_entry("S")
+                    .push_entry("M")),
+            "",
+        ));
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    RunSdt::default()
+                        .property(
+                            SdtProperty::default()
+                                .alias("Email")
+                                .showing_placeholder(ShowingPlaceholder::default())
+                                .ty(SdtText::default()),
+                        )
+                        .push(Run::default().push_text("Click here")),
+                )
+                .push(
+                    RunSdt::default()
+                        .property(
+                            SdtProperty::default()
+                                .tag("date")
+                                .ty(SdtDate::default().full_date("2020-01-02T00:00:00Z")),
+                        )
+                        .push(Run::default().push_text("1/2/2020")),
+                ),
+        );
+        docx.document.push(
+            Table::default().push_row(
+                RowSdt::default()
+                    .property(
+                        SdtProperty::default()
+                            .tag("items")
+                            .ty(SdtRepeatingSection::default()),
+                    )
+                    .push(
+                        RowSdt::default()
+                            .property(SdtProperty::default().ty(SdtRepeatingSectionItem::default()))
+                            .push(TableRow::default().push_cell(
+                                Paragraph::default().push(text_control("item", "first")),
+                            )),
+                    )
+                    .push(
+                        RowSdt::default()
+                            .property(SdtProperty::default().ty(SdtRepeatingSectionItem::default()))
+                            .push(TableRow::default().push_cell(
+                                Paragraph::default().push(text_control("item", "second")),
+                            )),
+                    ),
+            ),
+        );
+
+        let mut header = Header::default();
+        header.push(Paragraph::default().push(text_control("company", "ACME")));
+        docx.headers.insert("word/header1.xml".into(), header);
+
+        let values = docx.form_values();
+
+        assert_eq!(values["Agree"], FormValue::Checked(true));
+        assert_eq!(values["Comment"], FormValue::Text("Looks good".into()));
+        assert_eq!(
+            values["Size"],
+            FormValue::Selected {
+                value: Some("M".into()),
+                text: "M".into()
+            }
+        );
+        assert_eq!(values["Email"], FormValue::Placeholder("Click here".into()));
+        assert_eq!(
+            values["date"],
+            FormValue::Date {
+                date: Some("2020-01-02T00:00:00Z".into()),
+                text: "1/2/2020".into()
+            }
+        );
+        match &values["items"] {
+            FormValue::Section(items) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1]["item"], FormValue::Text("second".into()));
+            }
+            value => panic!("unexpected value {:?}", value),
+        }
+        assert!(!values.contains_key("item"));
+        assert_eq!(values["company"], FormValue::Text("ACME".into()));
+    }
+}
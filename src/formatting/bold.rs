@@ -11,7 +11,7 @@ use crate::{
 /// let bold = Bold::from(false);
 /// let bold = Bold::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:b")]
 pub struct Bold {
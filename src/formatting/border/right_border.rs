@@ -5,7 +5,7 @@ use crate::{
     private_prelude::*,
 };
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:right")]
 pub struct RightBorder<'a> {
@@ -1,7 +1,10 @@
 use crate::{
     __setter, 
     __xml_test_suites,
-    formatting::{Bold, Color, Dstrike, Italics, Outline, Size, Strike, Underline},
+    formatting::{
+        Bold, CharacterPropertyChange, Color, Dstrike, Italics, Outline, Shading, Size, Strike,
+        Underline, Vanish, VerticalAlign,
+    },
     private_prelude::*,
 };
 
@@ -24,7 +27,7 @@ use crate::{
 ///     .underline("00ff00")
 ///     .underline(("ff0000", UnderlineStyle::Dash));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rPr")]
 pub struct CharacterProperty<'a> {
@@ -55,6 +58,18 @@ pub struct CharacterProperty<'a> {
     /// Specifies that the content should be displayed with an underline
     #[xml(child = "w:u")]
     pub underline: Option<Underline<'a>>,
+    /// Specifies that the contents are hidden.
+    #[xml(child = "w:vanish")]
+    pub vanish: Option<Vanish>,
+    /// Specifies the background of the text.
+    #[xml(child = "w:shd")]
+    pub shading: Option<Shading<'a>>,
+    /// Specifies that the text is raised or lowered, e.g. as a superscript.
+    #[xml(child = "w:vertAlign")]
+    pub vertical_align: Option<VerticalAlign>,
+    /// Specifies the previous character properties of a tracked formatting change.
+    #[xml(child = "w:rPrChange")]
+    pub change: Option<CharacterPropertyChange<'a>>,
 }
 
 impl<'a> CharacterProperty<'a> {
@@ -67,9 +82,41 @@ impl<'a> CharacterProperty<'a> {
     __setter!(strike: Option<Strike>);
     __setter!(size: Option<Size>);
     __setter!(underline: Option<Underline<'a>>);
+    __setter!(vanish: Option<Vanish>);
+    __setter!(shading: Option<Shading<'a>>);
+    __setter!(vertical_align: Option<VerticalAlign>);
+    __setter!(change: Option<CharacterPropertyChange<'a>>);
+
+    /// Overrides the properties specified in `other`, leaving the others as they are.
+    pub fn merge(&mut self, other: &CharacterProperty<'a>) {
+        macro_rules! merge {
+            ($($field:ident),*) => {
+                $(
+                    if other.$field.is_some() {
+                        self.$field = other.$field.clone();
+                    }
+                )*
+            };
+        }
+
+        merge!(
+            style_id,
+            color,
+            size,
+            bold,
+            italics,
+            strike,
+            dstrike,
+            outline,
+            underline,
+            vanish,
+            shading,
+            vertical_align
+        );
+    }
 }
 
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rStyle")]
 pub struct CharacterStyleId<'a> {
@@ -105,4 +152,8 @@ __xml_test_suites!(
     r#"<w:rPr><w:dstrike w:val="true"/></w:rPr>"#,
     CharacterProperty::default().underline(Underline::default()),
     r#"<w:rPr><w:u/></w:rPr>"#,
+    CharacterProperty::default()
+        .bold(true)
+        .change(CharacterPropertyChange::new("1", CharacterProperty::default())),
+    r#"<w:rPr><w:b w:val="true"/><w:rPrChange w:id="1"><w:rPr/></w:rPrChange></w:rPr>"#,
 );
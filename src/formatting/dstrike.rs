@@ -11,7 +11,7 @@ use crate::{
 /// let dstrike = Dstrike::from(false);
 /// let dstrike = Dstrike::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:dstrike")]
 pub struct Dstrike {
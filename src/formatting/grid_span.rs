@@ -0,0 +1,30 @@
+use crate::{__xml_test_suites, private_prelude::*};
+
+/// Grid Span
+///
+/// Specifies the number of grid columns spanned by a table cell.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let span = GridSpan::from(2usize);
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:gridSpan")]
+pub struct GridSpan {
+    #[xml(attr = "w:val")]
+    pub value: usize,
+}
+
+impl<T: Into<usize>> From<T> for GridSpan {
+    fn from(val: T) -> Self {
+        GridSpan { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    GridSpan,
+    GridSpan::from(2usize),
+    r#"<w:gridSpan w:val="2"/>"#,
+);
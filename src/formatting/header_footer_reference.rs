@@ -0,0 +1,91 @@
+use crate::{__setter, __string_enum, __xml_test_suites, private_prelude::*};
+
+/// Header Reference
+///
+/// Specifies the header part of a section, by its relationship id.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let reference = HeaderReference::new("rId5");
+/// let reference = HeaderReference::new("rId6").ty(HeaderFooterType::First);
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:headerReference")]
+pub struct HeaderReference<'a> {
+    /// Specifies the pages of the header, the default being all the pages.
+    #[xml(attr = "w:type")]
+    pub ty: Option<HeaderFooterType>,
+    /// Specifies the relationship id of the header part.
+    #[xml(attr = "r:id")]
+    pub id: Cow<'a, str>,
+}
+
+impl<'a> HeaderReference<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(id: T) -> Self {
+        HeaderReference {
+            ty: None,
+            id: id.into(),
+        }
+    }
+
+    __setter!(ty: Option<HeaderFooterType>);
+}
+
+/// Footer Reference
+///
+/// Specifies the footer part of a section, by its relationship id.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let reference = FooterReference::new("rId7").ty(HeaderFooterType::Default);
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:footerReference")]
+pub struct FooterReference<'a> {
+    /// Specifies the pages of the footer, the default being all the pages.
+    #[xml(attr = "w:type")]
+    pub ty: Option<HeaderFooterType>,
+    /// Specifies the relationship id of the footer part.
+    #[xml(attr = "r:id")]
+    pub id: Cow<'a, str>,
+}
+
+impl<'a> FooterReference<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(id: T) -> Self {
+        FooterReference {
+            ty: None,
+            id: id.into(),
+        }
+    }
+
+    __setter!(ty: Option<HeaderFooterType>);
+}
+
+/// Specifies the pages of a header or a footer
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum HeaderFooterType {
+    Default,
+    First,
+    Even,
+}
+
+__string_enum! {
+    HeaderFooterType {
+        Default = "default",
+        First = "first",
+        Even = "even",
+    }
+}
+
+__xml_test_suites!(
+    HeaderReference,
+    HeaderReference::new("rId5"),
+    r#"<w:headerReference r:id="rId5"/>"#,
+    HeaderReference::new("rId6").ty(HeaderFooterType::First),
+    r#"<w:headerReference w:type="first" r:id="rId6"/>"#,
+);
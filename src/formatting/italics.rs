@@ -11,7 +11,7 @@ use crate::{
 /// let i = Italics::from(false);
 /// let i = Italics::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:i")]
 pub struct Italics {
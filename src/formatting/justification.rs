@@ -11,7 +11,7 @@ use crate::{
 ///
 /// let jc = Justification::from(JustificationVal::Start);
 /// ```
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:jc")]
 pub struct Justification {
@@ -25,7 +25,7 @@ impl From<JustificationVal> for Justification {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum JustificationVal {
     Both,
@@ -9,29 +9,42 @@ mod borders;
 mod character_property;
 mod color;
 mod dstrike;
+mod grid_span;
+mod header_footer_reference;
 mod indent_level;
 mod italics;
 mod justification;
 mod numbering_id;
 mod numbering_property;
 mod outline;
+mod page_margin;
+mod page_size;
+mod paragraph_mark_property;
 mod paragraph_property;
+mod property_change;
+mod shading;
 mod size;
 mod strike;
 mod table_borders;
 mod table_cell_property;
+mod table_header;
 mod table_indent;
 mod table_justification;
 mod table_property;
 mod table_row_property;
 mod table_width;
+mod tabs;
 mod underline;
+mod vanish;
+mod vertical_align;
+mod vertical_merge;
 
 // re-export
 pub use self::{
-    bold::*, border::*, borders::*, character_property::*, color::*, dstrike::*, indent_level::*,
-    italics::*, justification::*, numbering_id::*, numbering_property::*, outline::*,
-    paragraph_property::*, size::*, strike::*, table_borders::*, table_cell_property::*,
-    table_indent::*, table_justification::*, table_property::*, table_row_property::*,
-    table_width::*, underline::*,
+    bold::*, border::*, borders::*, character_property::*, color::*, dstrike::*, grid_span::*,
+    header_footer_reference::*, indent_level::*, italics::*, justification::*, numbering_id::*,
+    numbering_property::*, outline::*, page_margin::*, page_size::*, paragraph_mark_property::*,
+    paragraph_property::*, property_change::*, shading::*, size::*, strike::*, table_borders::*, table_cell_property::*, table_header::*, table_indent::*,
+    table_justification::*, table_property::*, table_row_property::*, table_width::*, tabs::*,
+    underline::*, vanish::*, vertical_align::*, vertical_merge::*,
 };
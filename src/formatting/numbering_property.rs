@@ -36,7 +36,7 @@ impl From<(usize, usize)> for NumberingProperty {
 __xml_test_suites!(
     NumberingProperty,
     NumberingProperty::default(),
-    r#"<w:numPr><w:numId w:val="0"/><w:ilvl w:val="0"/></w:numPr>"#,
+    r#"<w:numPr/>"#,
     NumberingProperty::from((20, 40)),
     r#"<w:numPr><w:numId w:val="20"/><w:ilvl w:val="40"/></w:numPr>"#,
 );
@@ -11,7 +11,7 @@ use crate::{
 /// let outline = Outline::from(false);
 /// let outline = Outline::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:outline")]
 pub struct Outline {
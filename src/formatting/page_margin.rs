@@ -0,0 +1,63 @@
+use crate::{__setter, __xml_test_suites, private_prelude::*};
+
+/// Page Margin
+///
+/// Specifies the margins of the pages of a section, in twentieths of a
+/// point. The header and the footer are placed at their distance from the
+/// top and the bottom edge of the page.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let margin = PageMargin::default()
+///     .top(1440isize)
+///     .bottom(1440isize)
+///     .left(1800usize)
+///     .right(1800usize);
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:pgMar")]
+pub struct PageMargin {
+    /// Specifies the top margin, the text not overlapping the header if
+    /// negative.
+    #[xml(attr = "w:top")]
+    pub top: Option<isize>,
+    #[xml(attr = "w:right")]
+    pub right: Option<usize>,
+    /// Specifies the bottom margin, the text not overlapping the footer if
+    /// negative.
+    #[xml(attr = "w:bottom")]
+    pub bottom: Option<isize>,
+    #[xml(attr = "w:left")]
+    pub left: Option<usize>,
+    #[xml(attr = "w:header")]
+    pub header: Option<usize>,
+    #[xml(attr = "w:footer")]
+    pub footer: Option<usize>,
+    #[xml(attr = "w:gutter")]
+    pub gutter: Option<usize>,
+}
+
+impl PageMargin {
+    __setter!(top: Option<isize>);
+    __setter!(right: Option<usize>);
+    __setter!(bottom: Option<isize>);
+    __setter!(left: Option<usize>);
+    __setter!(header: Option<usize>);
+    __setter!(footer: Option<usize>);
+    __setter!(gutter: Option<usize>);
+}
+
+__xml_test_suites!(
+    PageMargin,
+    PageMargin::default(),
+    r#"<w:pgMar/>"#,
+    PageMargin::default()
+        .top(1440isize)
+        .right(1800usize)
+        .bottom(-1440isize)
+        .left(1800usize)
+        .header(720usize),
+    r#"<w:pgMar w:top="1440" w:right="1800" w:bottom="-1440" w:left="1800" w:header="720"/>"#,
+);
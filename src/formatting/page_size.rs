@@ -0,0 +1,67 @@
+use crate::{__setter, __string_enum, __xml_test_suites, private_prelude::*};
+
+/// Page Size
+///
+/// Specifies the size of the pages of a section, in twentieths of a point.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// // A4
+/// let size = PageSize::from((11906usize, 16838usize));
+/// let size = PageSize::from((16838usize, 11906usize)).orientation(PageOrientation::Landscape);
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:pgSz")]
+pub struct PageSize {
+    /// Specifies the page width.
+    #[xml(attr = "w:w")]
+    pub width: Option<usize>,
+    /// Specifies the page height.
+    #[xml(attr = "w:h")]
+    pub height: Option<usize>,
+    /// Specifies the orientation, which doesn't swap the width and the height.
+    #[xml(attr = "w:orient")]
+    pub orientation: Option<PageOrientation>,
+}
+
+impl PageSize {
+    __setter!(width: Option<usize>);
+    __setter!(height: Option<usize>);
+    __setter!(orientation: Option<PageOrientation>);
+}
+
+impl From<(usize, usize)> for PageSize {
+    fn from((width, height): (usize, usize)) -> Self {
+        PageSize {
+            width: Some(width),
+            height: Some(height),
+            orientation: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum PageOrientation {
+    Portrait,
+    Landscape,
+}
+
+__string_enum! {
+    PageOrientation {
+        Portrait = "portrait",
+        Landscape = "landscape",
+    }
+}
+
+__xml_test_suites!(
+    PageSize,
+    PageSize::default(),
+    r#"<w:pgSz/>"#,
+    PageSize::from((11906usize, 16838usize)),
+    r#"<w:pgSz w:w="11906" w:h="16838"/>"#,
+    PageSize::from((16838usize, 11906usize)).orientation(PageOrientation::Landscape),
+    r#"<w:pgSz w:w="16838" w:h="11906" w:orient="landscape"/>"#,
+);
@@ -0,0 +1,43 @@
+use crate::{
+    __setter, __xml_test_suites,
+    document::{Deletion, Insertion},
+    private_prelude::*,
+};
+
+/// Paragraph Mark Property
+///
+/// Specifies the revisions of the paragraph mark itself. An inserted mark
+/// splits the paragraph from its successor, a deleted mark merges them.
+///
+/// ```rust
+/// use docx::document::*;
+/// use docx::formatting::*;
+///
+/// let prop = ParagraphMarkProperty::default().insertion(Insertion::new("1").author("John"));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:rPr")]
+pub struct ParagraphMarkProperty<'a> {
+    /// Specifies that the paragraph mark was inserted as a tracked revision.
+    #[xml(child = "w:ins")]
+    pub insertion: Option<Insertion<'a>>,
+    /// Specifies that the paragraph mark was deleted as a tracked revision.
+    #[xml(child = "w:del")]
+    pub deletion: Option<Deletion<'a>>,
+}
+
+impl<'a> ParagraphMarkProperty<'a> {
+    __setter!(insertion: Option<Insertion<'a>>);
+    __setter!(deletion: Option<Deletion<'a>>);
+}
+
+__xml_test_suites!(
+    ParagraphMarkProperty,
+    ParagraphMarkProperty::default(),
+    r#"<w:rPr/>"#,
+    ParagraphMarkProperty::default().insertion(Insertion::new("1")),
+    r#"<w:rPr><w:ins w:id="1"/></w:rPr>"#,
+    ParagraphMarkProperty::default().deletion(Deletion::new("2").author("John")),
+    r#"<w:rPr><w:del w:id="2" w:author="John"/></w:rPr>"#,
+);
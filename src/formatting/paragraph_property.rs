@@ -1,7 +1,11 @@
 use crate::{
     __setter, 
+    __string_enum,
     __xml_test_suites,
-    formatting::{Borders, Justification, NumberingProperty},
+    formatting::{
+        Borders, FooterReference, HeaderReference, Justification, NumberingProperty, PageMargin,
+        PageSize, ParagraphMarkProperty, ParagraphPropertyChange, Shading, Tabs,
+    },
     private_prelude::*,
 };
 
@@ -15,7 +19,7 @@ use crate::{
 ///     .justification(JustificationVal::Start)
 ///     .numbering((10usize, 20usize));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pPr")]
 pub struct ParagraphProperty<'a> {
@@ -31,6 +35,14 @@ pub struct ParagraphProperty<'a> {
     #[xml(child = "w:pBdr")]
     pub border: Option<Borders<'a>>,
 
+    /// Specifies the background of the paragraph.
+    #[xml(child = "w:shd")]
+    pub shading: Option<Shading<'a>>,
+
+    /// Specifies the custom tab stops of the paragraph.
+    #[xml(child = "w:tabs")]
+    pub tabs: Option<Tabs>,
+
     /// Specifies that the paragraph should be numbered.
     #[xml(child = "w:numPr")]
     pub numbering: Option<NumberingProperty>,
@@ -39,16 +51,45 @@ pub struct ParagraphProperty<'a> {
     #[xml(child = "w:outlineLvl")]
     pub outline_level: Option<OutlineLevel>,
 
+    /// Specifies the properties of the paragraph mark
+    #[xml(child = "w:rPr")]
+    pub mark: Option<ParagraphMarkProperty<'a>>,
+
     /// Specifies section properties
     #[xml(child = "w:sectPr")]
-    pub section: Option<SectionProperty>,
+    pub section: Option<SectionProperty<'a>>,
+
+    /// Specifies the previous paragraph properties of a tracked formatting change.
+    #[xml(child = "w:pPrChange")]
+    pub change: Option<ParagraphPropertyChange<'a>>,
 }
 
 impl<'a> ParagraphProperty<'a> {
     __setter!(style_id: Option<ParagraphStyleId<'a>>);
     __setter!(justification: Option<Justification>);
     __setter!(border: Option<Borders<'a>>);
+    __setter!(shading: Option<Shading<'a>>);
+    __setter!(tabs: Option<Tabs>);
     __setter!(numbering: Option<NumberingProperty>);
+    __setter!(outline_level: Option<OutlineLevel>);
+    __setter!(mark: Option<ParagraphMarkProperty<'a>>);
+    __setter!(section: Option<SectionProperty<'a>>);
+    __setter!(change: Option<ParagraphPropertyChange<'a>>);
+
+    /// Overrides the properties specified in `other`, leaving the others as they are.
+    pub fn merge(&mut self, other: &ParagraphProperty<'a>) {
+        macro_rules! merge {
+            ($($field:ident),*) => {
+                $(
+                    if other.$field.is_some() {
+                        self.$field = other.$field.clone();
+                    }
+                )*
+            };
+        }
+
+        merge!(style_id, justification, border, shading, tabs, numbering, outline_level, mark);
+    }
 }
 
 #[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
@@ -59,12 +100,97 @@ pub struct OutlineLevel {
     pub value: usize,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+impl From<usize> for OutlineLevel {
+    fn from(value: usize) -> Self {
+        OutlineLevel { value }
+    }
+}
+
+/// Section Property
+///
+/// Placed in the properties of the last paragraph of a section, and at the
+/// end of the body for the last section.
+///
+/// ```rust
+/// use docx::formatting::{PageMargin, SectionProperty, SectionType};
+///
+/// let prop = SectionProperty::default()
+///     .ty(SectionType::NextPage)
+///     .page_size((12240usize, 15840usize))
+///     .page_margin(PageMargin::default().top(1440isize).bottom(1440isize));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:sectPr")]
-pub struct SectionProperty { }
+pub struct SectionProperty<'a> {
+    /// Specifies the headers of the section.
+    #[xml(child = "w:headerReference")]
+    pub headers: Vec<HeaderReference<'a>>,
+    /// Specifies the footers of the section.
+    #[xml(child = "w:footerReference")]
+    pub footers: Vec<FooterReference<'a>>,
+    /// Specifies where the next section starts.
+    #[xml(child = "w:type")]
+    pub ty: Option<SectionTypeValue>,
+    /// Specifies the size of the pages.
+    #[xml(child = "w:pgSz")]
+    pub page_size: Option<PageSize>,
+    /// Specifies the margins of the pages.
+    #[xml(child = "w:pgMar")]
+    pub page_margin: Option<PageMargin>,
+}
+
+impl<'a> SectionProperty<'a> {
+    __setter!(ty: Option<SectionTypeValue>);
+    __setter!(page_size: Option<PageSize>);
+    __setter!(page_margin: Option<PageMargin>);
+
+    pub fn push_header<T: Into<HeaderReference<'a>>>(mut self, header: T) -> Self {
+        self.headers.push(header.into());
+        self
+    }
+
+    pub fn push_footer<T: Into<FooterReference<'a>>>(mut self, footer: T) -> Self {
+        self.footers.push(footer.into());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:type")]
+pub struct SectionTypeValue {
+    #[xml(attr = "w:val")]
+    pub value: SectionType,
+}
+
+impl From<SectionType> for SectionTypeValue {
+    fn from(value: SectionType) -> Self {
+        SectionTypeValue { value }
+    }
+}
+
+/// Specifies where a section starts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SectionType {
+    NextPage,
+    NextColumn,
+    Continuous,
+    EvenPage,
+    OddPage,
+}
+
+__string_enum! {
+    SectionType {
+        NextPage = "nextPage",
+        NextColumn = "nextColumn",
+        Continuous = "continuous",
+        EvenPage = "evenPage",
+        OddPage = "oddPage",
+    }
+}
 
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pStyle")]
 pub struct ParagraphStyleId<'a> {
@@ -92,5 +218,24 @@ __xml_test_suites!(
     ParagraphProperty::default().border(Borders::default()),
     r#"<w:pPr><w:pBdr/></w:pPr>"#,
     ParagraphProperty::default().numbering(NumberingProperty::default()),
-    r#"<w:pPr><w:numPr><w:numId w:val="0"/><w:ilvl w:val="0"/></w:numPr></w:pPr>"#,
+    r#"<w:pPr><w:numPr/></w:pPr>"#,
+    ParagraphProperty::default().tabs(Tabs::default()),
+    r#"<w:pPr><w:tabs/></w:pPr>"#,
+    ParagraphProperty::default().outline_level(1),
+    r#"<w:pPr><w:outlineLvl w:val="1"/></w:pPr>"#,
+    ParagraphProperty::default().mark(ParagraphMarkProperty::default()),
+    r#"<w:pPr><w:rPr/></w:pPr>"#,
+    ParagraphProperty::default().section(SectionProperty::default().ty(SectionType::NextPage)),
+    r#"<w:pPr><w:sectPr><w:type w:val="nextPage"/></w:sectPr></w:pPr>"#,
+    ParagraphProperty::default().section(
+        SectionProperty::default()
+            .push_header(HeaderReference::new("rId1"))
+            .page_size((12240usize, 15840usize))
+    ),
+    r#"<w:pPr><w:sectPr><w:headerReference r:id="rId1"/><w:pgSz w:w="12240" w:h="15840"/></w:sectPr></w:pPr>"#,
+    ParagraphProperty::default().change(ParagraphPropertyChange::new(
+        "1",
+        ParagraphProperty::default().style_id("id")
+    )),
+    r#"<w:pPr><w:pPrChange w:id="1"><w:pPr><w:pStyle w:val="id"/></w:pPr></w:pPrChange></w:pPr>"#,
 );
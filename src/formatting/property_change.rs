@@ -0,0 +1,151 @@
+use std::io::Write;
+
+use hard_xml::{
+    xmlparser::{ElementEnd, Token},
+    XmlError, XmlReader,
+};
+
+use crate::{
+    __xml_test_suites,
+    formatting::{CharacterProperty, ParagraphProperty, TableProperty},
+    private_prelude::*,
+};
+
+// Property changes contain the previous set of properties, which in turn may
+// contain a property change, so they box their content and implement
+// `XmlRead`/`XmlWrite` by hand.
+macro_rules! property_change {
+    ($(#[$attr:meta])* $name:ident, $tag:expr, $ty:ident, $inner_tag:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default)]
+        #[cfg_attr(test, derive(PartialEq))]
+        pub struct $name<'a> {
+            /// Specifies a unique identifier for the revision.
+            pub id: Cow<'a, str>,
+            /// Specifies the author of the revision.
+            pub author: Option<Cow<'a, str>>,
+            /// Specifies the date of the revision.
+            pub date: Option<Cow<'a, str>>,
+            /// Specifies the properties before the revision.
+            pub property: Box<$ty<'a>>,
+        }
+
+        impl<'a> $name<'a> {
+            pub fn new<T: Into<Cow<'a, str>>>(id: T, property: $ty<'a>) -> Self {
+                $name {
+                    id: id.into(),
+                    author: None,
+                    date: None,
+                    property: Box::new(property),
+                }
+            }
+
+            crate::__setter!(author: Option<Cow<'a, str>>);
+            crate::__setter!(date: Option<Cow<'a, str>>);
+        }
+
+        impl<'i: 'a, 'a> XmlRead<'i> for $name<'a> {
+            fn from_reader(reader: &mut XmlReader<'i>) -> XmlResult<Self> {
+                log::debug!(concat!("[", stringify!($name), "] Started reading."));
+
+                reader.read_till_element_start($tag)?;
+
+                let mut this = $name::default();
+
+                while let Some((key, value)) = reader.find_attribute()? {
+                    match key {
+                        "w:id" => this.id = value,
+                        "w:author" => this.author = Some(value),
+                        "w:date" => this.date = Some(value),
+                        _ => (),
+                    }
+                }
+
+                if let Token::ElementEnd {
+                    end: ElementEnd::Empty,
+                    ..
+                } = reader.next().ok_or(XmlError::UnexpectedEof)??
+                {
+                    return Ok(this);
+                }
+
+                while let Some(tag) = reader.find_element_start(Some($tag))? {
+                    if tag == $inner_tag {
+                        this.property = Box::new($ty::from_reader(reader)?);
+                    } else {
+                        reader.next();
+                        reader.read_to_end(tag)?;
+                    }
+                }
+
+                log::debug!(concat!("[", stringify!($name), "] Finished reading."));
+
+                Ok(this)
+            }
+        }
+
+        impl<'a> XmlWrite for $name<'a> {
+            fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+                log::debug!(concat!("[", stringify!($name), "] Started writing."));
+
+                writer.write_element_start($tag)?;
+                writer.write_attribute("w:id", &self.id)?;
+                if let Some(author) = &self.author {
+                    writer.write_attribute("w:author", author)?;
+                }
+                if let Some(date) = &self.date {
+                    writer.write_attribute("w:date", date)?;
+                }
+                writer.write_element_end_open()?;
+                self.property.to_writer(writer)?;
+                writer.write_element_end_close($tag)?;
+
+                log::debug!(concat!("[", stringify!($name), "] Finished writing."));
+
+                Ok(())
+            }
+        }
+    };
+}
+
+property_change!(
+    /// Revision Information for Run Properties
+    ///
+    /// ```rust
+    /// use docx::formatting::*;
+    ///
+    /// let change = CharacterPropertyChange::new("1", CharacterProperty::default().bold(true))
+    ///     .author("John")
+    ///     .date("2020-01-01T00:00:00Z");
+    /// ```
+    CharacterPropertyChange,
+    "w:rPrChange",
+    CharacterProperty,
+    "w:rPr"
+);
+
+property_change!(
+    /// Revision Information for Paragraph Properties
+    ParagraphPropertyChange,
+    "w:pPrChange",
+    ParagraphProperty,
+    "w:pPr"
+);
+
+property_change!(
+    /// Revision Information for Table Properties
+    TablePropertyChange,
+    "w:tblPrChange",
+    TableProperty,
+    "w:tblPr"
+);
+
+__xml_test_suites!(
+    CharacterPropertyChange,
+    CharacterPropertyChange::new("1", CharacterProperty::default()),
+    r#"<w:rPrChange w:id="1"><w:rPr/></w:rPrChange>"#,
+    CharacterPropertyChange::new("1", CharacterProperty::default().bold(true))
+        .author("John")
+        .date("2020-01-01T00:00:00Z"),
+    r#"<w:rPrChange w:id="1" w:author="John" w:date="2020-01-01T00:00:00Z"><w:rPr><w:b w:val="true"/></w:rPr></w:rPrChange>"#,
+);
@@ -0,0 +1,54 @@
+use crate::{__setter, __xml_test_suites, private_prelude::*};
+
+/// Shading
+///
+/// Specifies the background of a paragraph or of text: a pattern of the
+/// given color over the fill color.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let shading = Shading::from("FFFF00");
+/// let shading = Shading::default().pattern("pct25").color("auto").fill("FFFFFF");
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:shd")]
+pub struct Shading<'a> {
+    /// Specifies the pattern, e.g. `clear` or `pct25`.
+    #[xml(attr = "w:val")]
+    pub pattern: Option<Cow<'a, str>>,
+    /// Specifies the color of the pattern.
+    #[xml(attr = "w:color")]
+    pub color: Option<Cow<'a, str>>,
+    /// Specifies the fill color.
+    #[xml(attr = "w:fill")]
+    pub fill: Option<Cow<'a, str>>,
+}
+
+impl<'a> Shading<'a> {
+    __setter!(pattern: Option<Cow<'a, str>>);
+    __setter!(color: Option<Cow<'a, str>>);
+    __setter!(fill: Option<Cow<'a, str>>);
+}
+
+/// Creates a solid background of the given fill color.
+impl<'a> From<&'a str> for Shading<'a> {
+    fn from(fill: &'a str) -> Self {
+        Shading::default().pattern("clear").color("auto").fill(fill)
+    }
+}
+
+impl From<String> for Shading<'_> {
+    fn from(fill: String) -> Self {
+        Shading::default().pattern("clear").color("auto").fill(fill)
+    }
+}
+
+__xml_test_suites!(
+    Shading,
+    Shading::default(),
+    r#"<w:shd/>"#,
+    Shading::from("FFFF00"),
+    r#"<w:shd w:val="clear" w:color="auto" w:fill="FFFF00"/>"#,
+);
@@ -11,7 +11,7 @@ use crate::{
 /// let strike = Strike::from(false);
 /// let strike = Strike::from(true);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:strike")]
 pub struct Strike {
@@ -1,17 +1,42 @@
 use crate::{
+    __setter,
     __xml_test_suites,
+    formatting::{GridSpan, VerticalMerge},
     private_prelude::*,
 };
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+/// Table Cell Property
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let prop = TableCellProperty::default()
+///     .grid_span(2usize)
+///     .vertical_merge(VerticalMergeVal::Restart);
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tcPr")]
-pub struct TableCellProperty {}
+pub struct TableCellProperty {
+    /// Specifies the number of grid columns spanned by the cell.
+    #[xml(child = "w:gridSpan")]
+    pub grid_span: Option<GridSpan>,
+    /// Specifies that the cell is merged with the cells above or below it.
+    #[xml(child = "w:vMerge")]
+    pub vertical_merge: Option<VerticalMerge>,
+}
 
-impl TableCellProperty {}
+impl TableCellProperty {
+    __setter!(grid_span: Option<GridSpan>);
+    __setter!(vertical_merge: Option<VerticalMerge>);
+}
 
 __xml_test_suites!(
     TableCellProperty,
     TableCellProperty::default(),
     r#"<w:tcPr/>"#,
+    TableCellProperty::default().grid_span(2usize),
+    r#"<w:tcPr><w:gridSpan w:val="2"/></w:tcPr>"#,
+    TableCellProperty::default().vertical_merge(crate::formatting::VerticalMergeVal::Restart),
+    r#"<w:tcPr><w:vMerge w:val="restart"/></w:tcPr>"#,
 );
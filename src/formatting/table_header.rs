@@ -0,0 +1,33 @@
+use crate::{__xml_test_suites, private_prelude::*};
+
+/// Table Header
+///
+/// Specifies that the row is repeated at the top of each page the table
+/// spans.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let header = TableHeader::from(true);
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tblHeader")]
+pub struct TableHeader {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for TableHeader {
+    fn from(val: T) -> Self {
+        TableHeader { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    TableHeader,
+    TableHeader::default(),
+    r#"<w:tblHeader/>"#,
+    TableHeader::from(false),
+    r#"<w:tblHeader w:val="false"/>"#,
+);
@@ -13,7 +13,7 @@ use crate::{
 /// let ind = TableIndent::from(TableIndentUnit::Pct);
 /// let ind = TableIndent::from((42, TableIndentUnit::Dxa));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tblInd")]
 pub struct TableIndent {
@@ -50,7 +50,7 @@ impl From<(isize, TableIndentUnit)> for TableIndent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum TableIndentUnit {
     Auto,
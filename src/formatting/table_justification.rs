@@ -11,7 +11,7 @@ use crate::{
 ///
 /// let jc = TableJustification::from(TableJustificationVal::Start);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:jc")]
 pub struct TableJustification {
@@ -25,7 +25,7 @@ impl From<TableJustificationVal> for TableJustification {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum TableJustificationVal {
     Start,
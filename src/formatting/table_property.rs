@@ -1,7 +1,7 @@
 use crate::{
     __setter, 
     __xml_test_suites,
-    formatting::{TableBorders, TableIndent, TableJustification, TableWidth},
+    formatting::{TableBorders, TableIndent, TableJustification, TablePropertyChange, TableWidth},
     private_prelude::*,
 };
 
@@ -16,7 +16,7 @@ use crate::{
 ///     .indent((50, TableIndentUnit::Pct))
 ///     .width((50, TableWidthUnit::Pct));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tblPr")]
 pub struct TableProperty<'a> {
@@ -30,6 +30,9 @@ pub struct TableProperty<'a> {
     pub indent: Option<TableIndent>,
     #[xml(child = "w:tblW")]
     pub width: Option<TableWidth>,
+    /// Specifies the previous table properties of a tracked formatting change.
+    #[xml(child = "w:tblPrChange")]
+    pub change: Option<TablePropertyChange<'a>>,
 }
 
 impl<'a> TableProperty<'a> {
@@ -38,9 +41,10 @@ impl<'a> TableProperty<'a> {
     __setter!(borders: Option<TableBorders<'a>>);
     __setter!(indent: Option<TableIndent>);
     __setter!(width: Option<TableWidth>);
+    __setter!(change: Option<TablePropertyChange<'a>>);
 }
 
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tblStyle")]
 pub struct TableStyleId<'a> {
@@ -68,4 +72,6 @@ __xml_test_suites!(
     r#"<w:tblPr><w:tblInd/></w:tblPr>"#,
     TableProperty::default().width(TableWidth::default()),
     r#"<w:tblPr><w:tblW/></w:tblPr>"#,
+    TableProperty::default().change(TablePropertyChange::new("1", TableProperty::default())),
+    r#"<w:tblPr><w:tblPrChange w:id="1"><w:tblPr/></w:tblPrChange></w:tblPr>"#,
 );
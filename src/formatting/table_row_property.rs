@@ -1,7 +1,8 @@
 use crate::{
     __setter, 
     __xml_test_suites, 
-    formatting::TableJustification,
+    document::{Deletion, Insertion},
+    formatting::{TableHeader, TableJustification},
     private_prelude::*,
 };
 
@@ -13,17 +14,29 @@ use crate::{
 /// let prop = TableRowProperty::default()
 ///     .justification(TableJustificationVal::Start);
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:trPr")]
-pub struct TableRowProperty {
+pub struct TableRowProperty<'a> {
+    /// Specifies that the row is repeated at the top of each page.
+    #[xml(child = "w:tblHeader")]
+    pub header: Option<TableHeader>,
     /// Specifies the alignment of the row with respect to the text margins in the section.
     #[xml(child = "w:jc")]
     pub justification: Option<TableJustification>,
+    /// Specifies that the row was inserted as a tracked revision.
+    #[xml(child = "w:ins")]
+    pub insertion: Option<Insertion<'a>>,
+    /// Specifies that the row was deleted as a tracked revision.
+    #[xml(child = "w:del")]
+    pub deletion: Option<Deletion<'a>>,
 }
 
-impl TableRowProperty {
+impl<'a> TableRowProperty<'a> {
+    __setter!(header: Option<TableHeader>);
     __setter!(justification: Option<TableJustification>);
+    __setter!(insertion: Option<Insertion<'a>>);
+    __setter!(deletion: Option<Deletion<'a>>);
 }
 
 __xml_test_suites!(
@@ -32,4 +45,10 @@ __xml_test_suites!(
     r#"<w:trPr/>"#,
     TableRowProperty::default().justification(crate::formatting::TableJustificationVal::Start),
     r#"<w:trPr><w:jc w:val="start"/></w:trPr>"#,
+    TableRowProperty::default()
+        .header(TableHeader::default())
+        .justification(crate::formatting::TableJustificationVal::Center),
+    r#"<w:trPr><w:tblHeader/><w:jc w:val="center"/></w:trPr>"#,
+    TableRowProperty::default().deletion(Deletion::new("1")),
+    r#"<w:trPr><w:del w:id="1"/></w:trPr>"#,
 );
@@ -10,7 +10,7 @@ use crate::private_prelude::*;
 /// let width = TableWidth::from(TableWidthUnit::Pct);
 /// let width = TableWidth::from((42, TableWidthUnit::Dxa));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:tblW")]
 pub struct TableWidth {
@@ -47,7 +47,7 @@ impl From<(usize, TableWidthUnit)> for TableWidth {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum TableWidthUnit {
     Auto,
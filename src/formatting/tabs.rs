@@ -0,0 +1,115 @@
+use crate::private_prelude::*;
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+/// Tab Stops
+///
+/// Specifies a sequence of custom tab stops for the paragraph.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let tabs = Tabs::default()
+///     .push(TabStop::new(TabStopType::Right, 9350).leader(TabLeader::Dot));
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tabs")]
+pub struct Tabs {
+    #[xml(child = "w:tab")]
+    pub tabs: Vec<TabStop>,
+}
+
+impl Tabs {
+    pub fn push<T: Into<TabStop>>(mut self, tab: T) -> Self {
+        self.tabs.push(tab.into());
+        self
+    }
+}
+
+/// Custom Tab Stop
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:tab")]
+pub struct TabStop {
+    /// Specifies the style of the tab stop.
+    #[xml(attr = "w:val")]
+    pub ty: TabStopType,
+    /// Specifies the character used to fill the space before the tab stop.
+    #[xml(attr = "w:leader")]
+    pub leader: Option<TabLeader>,
+    /// Specifies the position of the tab stop in twentieths of a point.
+    #[xml(attr = "w:pos")]
+    pub position: isize,
+}
+
+impl TabStop {
+    pub fn new(ty: TabStopType, position: isize) -> Self {
+        TabStop {
+            ty,
+            leader: None,
+            position,
+        }
+    }
+
+    __setter!(leader: Option<TabLeader>);
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum TabStopType {
+    Bar,
+    Center,
+    Clear,
+    Decimal,
+    End,
+    Left,
+    Num,
+    Right,
+    Start,
+}
+
+__string_enum! {
+    TabStopType {
+        Bar = "bar",
+        Center = "center",
+        Clear = "clear",
+        Decimal = "decimal",
+        End = "end",
+        Left = "left",
+        Num = "num",
+        Right = "right",
+        Start = "start",
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum TabLeader {
+    Dot,
+    Heavy,
+    Hyphen,
+    MiddleDot,
+    None,
+    Underscore,
+}
+
+__string_enum! {
+    TabLeader {
+        Dot = "dot",
+        Heavy = "heavy",
+        Hyphen = "hyphen",
+        MiddleDot = "middleDot",
+        None = "none",
+        Underscore = "underscore",
+    }
+}
+
+__xml_test_suites!(
+    Tabs,
+    Tabs::default(),
+    r#"<w:tabs/>"#,
+    Tabs::default().push(TabStop::new(TabStopType::Left, 720)),
+    r#"<w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs>"#,
+    Tabs::default().push(TabStop::new(TabStopType::Right, 9350).leader(TabLeader::Dot)),
+    r#"<w:tabs><w:tab w:val="right" w:leader="dot" w:pos="9350"/></w:tabs>"#,
+);
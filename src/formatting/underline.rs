@@ -11,7 +11,7 @@ use crate::private_prelude::*;
 /// let udl = Underline::from(("00ff00", UnderlineStyle::Dash));
 /// let udl = Underline::from((String::from("ff0000"), UnderlineStyle::DotDash));
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:u")]
 pub struct Underline<'a> {
@@ -66,7 +66,7 @@ impl<'a> From<(&'a str, UnderlineStyle)> for Underline<'a> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum UnderlineStyle {
     Dash,
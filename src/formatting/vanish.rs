@@ -0,0 +1,32 @@
+use crate::{__xml_test_suites, private_prelude::*};
+
+/// Vanish
+///
+/// Specifies that the contents are hidden.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let vanish = Vanish::from(true);
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:vanish")]
+pub struct Vanish {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for Vanish {
+    fn from(val: T) -> Self {
+        Vanish { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    Vanish,
+    Vanish::default(),
+    r#"<w:vanish/>"#,
+    Vanish::from(false),
+    r#"<w:vanish w:val="false"/>"#,
+);
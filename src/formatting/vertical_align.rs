@@ -0,0 +1,48 @@
+use crate::{__string_enum, __xml_test_suites, private_prelude::*};
+
+/// Vertical Alignment
+///
+/// Specifies that the text is raised or lowered, e.g. as a superscript.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let align = VerticalAlign::from(VerticalAlignVal::Superscript);
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:vertAlign")]
+pub struct VerticalAlign {
+    #[xml(attr = "w:val")]
+    pub value: VerticalAlignVal,
+}
+
+impl From<VerticalAlignVal> for VerticalAlign {
+    fn from(value: VerticalAlignVal) -> Self {
+        VerticalAlign { value }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum VerticalAlignVal {
+    Baseline,
+    Superscript,
+    Subscript,
+}
+
+__string_enum! {
+    VerticalAlignVal {
+        Baseline = "baseline",
+        Superscript = "superscript",
+        Subscript = "subscript",
+    }
+}
+
+__xml_test_suites!(
+    VerticalAlign,
+    VerticalAlign::from(VerticalAlignVal::Superscript),
+    r#"<w:vertAlign w:val="superscript"/>"#,
+    VerticalAlign::from(VerticalAlignVal::Subscript),
+    r#"<w:vertAlign w:val="subscript"/>"#,
+);
@@ -0,0 +1,58 @@
+use crate::{__string_enum, __xml_test_suites, private_prelude::*};
+
+/// Vertical Merge
+///
+/// Specifies that a table cell is merged with the cells above or below it.
+/// The first cell of a merge restarts it, and the following cells continue
+/// it.
+///
+/// ```rust
+/// use docx::formatting::*;
+///
+/// let first = VerticalMerge::from(VerticalMergeVal::Restart);
+/// let next = VerticalMerge::default();
+/// ```
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:vMerge")]
+pub struct VerticalMerge {
+    /// Specifies whether the cell restarts or continues a merge, the default
+    /// being to continue it.
+    #[xml(attr = "w:val")]
+    pub value: Option<VerticalMergeVal>,
+}
+
+impl From<VerticalMergeVal> for VerticalMerge {
+    fn from(value: VerticalMergeVal) -> Self {
+        VerticalMerge { value: Some(value) }
+    }
+}
+
+impl VerticalMerge {
+    /// Returns `true` if the cell restarts a merge.
+    pub fn is_restart(&self) -> bool {
+        matches!(self.value, Some(VerticalMergeVal::Restart))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum VerticalMergeVal {
+    Continue,
+    Restart,
+}
+
+__string_enum! {
+    VerticalMergeVal {
+        Continue = "continue",
+        Restart = "restart",
+    }
+}
+
+__xml_test_suites!(
+    VerticalMerge,
+    VerticalMerge::default(),
+    r#"<w:vMerge/>"#,
+    VerticalMerge::from(VerticalMergeVal::Restart),
+    r#"<w:vMerge w:val="restart"/>"#,
+);
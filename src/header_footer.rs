@@ -0,0 +1,163 @@
+//! Header and footer parts
+//!
+//! The corresponding ZIP items are `/word/header{n}.xml` and `/word/footer{n}.xml`,
+//! with their relationships in `/word/_rels/header{n}.xml.rels` and
+//! `/word/_rels/footer{n}.xml.rels`.
+
+use std::io::Write;
+
+use crate::{
+    __xml_test_suites,
+    document::BodyContent,
+    private_prelude::*,
+    schema::{
+        SCHEMA_DOCUMENT_RELATIONSHIPS, SCHEMA_MAIN, SCHEMA_MARKUP_COMPATIBILITY, SCHEMA_WORD_2010,
+        SCHEMA_WORD_2012,
+    },
+};
+
+macro_rules! header_footer {
+    ($(#[$attr:meta])* $name:ident, $tag:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, XmlRead)]
+        #[cfg_attr(test, derive(PartialEq))]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            /// Specifies the content of the part.
+            #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
+            pub content: Vec<BodyContent<'a>>,
+        }
+
+        impl<'a> $name<'a> {
+            pub fn push<T: Into<BodyContent<'a>>>(&mut self, content: T) -> &mut Self {
+                self.content.push(content.into());
+                self
+            }
+        }
+
+        impl<'a> XmlWrite for $name<'a> {
+            fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+                let $name { content } = self;
+
+                log::debug!(concat!("[", stringify!($name), "] Started writing."));
+
+                writer.write_element_start($tag)?;
+
+                writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
+
+                writer.write_attribute("xmlns:r", SCHEMA_DOCUMENT_RELATIONSHIPS)?;
+
+                writer.write_attribute("xmlns:w14", SCHEMA_WORD_2010)?;
+
+                writer.write_attribute("xmlns:w15", SCHEMA_WORD_2012)?;
+
+                writer.write_attribute("xmlns:mc", SCHEMA_MARKUP_COMPATIBILITY)?;
+
+                writer.write_attribute("mc:Ignorable", "w14 w15")?;
+
+                writer.write_element_end_open()?;
+
+                for ele in content {
+                    ele.to_writer(writer)?;
+                }
+
+                writer.write_element_end_close($tag)?;
+
+                log::debug!(concat!("[", stringify!($name), "] Finished writing."));
+
+                Ok(())
+            }
+        }
+    };
+}
+
+header_footer!(
+    /// The root element of a header part.
+    ///
+    /// ```rust
+    /// use docx::document::Paragraph;
+    /// use docx::header_footer::Header;
+    ///
+    /// let mut header = Header::default();
+    /// header.push(Paragraph::default().push_text("Confidential"));
+    /// ```
+    Header,
+    "w:hdr"
+);
+
+header_footer!(
+    /// The root element of a footer part.
+    Footer,
+    "w:ftr"
+);
+
+#[cfg(test)]
+use crate::document::Paragraph;
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        document::{Drawing, Run},
+        schema::SCHEMA_HEADER,
+        Docx, DocxFile,
+    };
+
+    #[test]
+    fn read_write_part_rels() {
+        let mut docx = Docx::default();
+        let (embed, _) =
+            docx.embed_part_media(Some("word/header1.xml"), Bytes::from_static(b"png"), "png");
+        docx.headers.insert(
+            "word/header1.xml".into(),
+            Header {
+                content: vec![Paragraph::default()
+                    .push(Run::default().push(Drawing::picture(embed.clone(), 914400, 914400)))
+                    .into()],
+            },
+        );
+        docx.relationships_mut(None)
+            .add_rel(SCHEMA_HEADER, "header1.xml");
+
+        let cursor = docx.write(Cursor::new(Vec::new())).unwrap();
+        let file = DocxFile::from_reader(cursor).unwrap();
+        let read = file.parse().unwrap();
+
+        assert_eq!(read.part_rels.keys().collect::<Vec<_>>(), ["word/header1.xml"]);
+        assert_eq!(
+            read.part_media(Some("word/header1.xml"), &embed).unwrap(),
+            ("word/media/image1.png", &Bytes::from_static(b"png"))
+        );
+        // the picture is not related to the main document part
+        assert!(read.embedded_media(&embed).is_none());
+    }
+}
+
+__xml_test_suites!(
+    Header,
+    Header::default(),
+    format!(
+        r#"<w:hdr xmlns:w="{}" xmlns:r="{}" xmlns:w14="{}" xmlns:w15="{}" xmlns:mc="{}" mc:Ignorable="w14 w15"></w:hdr>"#,
+        SCHEMA_MAIN,
+        SCHEMA_DOCUMENT_RELATIONSHIPS,
+        SCHEMA_WORD_2010,
+        SCHEMA_WORD_2012,
+        SCHEMA_MARKUP_COMPATIBILITY
+    )
+    .as_str(),
+    Header {
+        content: vec![Paragraph::default().into()]
+    },
+    format!(
+        r#"<w:hdr xmlns:w="{}" xmlns:r="{}" xmlns:w14="{}" xmlns:w15="{}" xmlns:mc="{}" mc:Ignorable="w14 w15"><w:p><w:pPr/></w:p></w:hdr>"#,
+        SCHEMA_MAIN,
+        SCHEMA_DOCUMENT_RELATIONSHIPS,
+        SCHEMA_WORD_2010,
+        SCHEMA_WORD_2012,
+        SCHEMA_MARKUP_COMPATIBILITY
+    )
+    .as_str(),
+);
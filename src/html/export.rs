@@ -0,0 +1,1168 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    __setter,
+    convert::{cell_paragraphs, is_on, resolve, InlineWriter, ListWriter, OpenList},
+    document::{
+        BodyContent, BookmarkStart, Break, BreakType, Drawing, Paragraph, RunContent, Table,
+        TableCellContent,
+    },
+    formatting::{
+        BorderStyle, CharacterProperty, JustificationVal, ParagraphProperty, TableJustificationVal,
+        TableProperty, TableWidthUnit, UnderlineStyle, VerticalAlignVal,
+    },
+    html::{class_name, escape},
+    numbering::{ListCounter, ListItem, NumberingFormatVal, Numberings},
+    private_prelude::*,
+    styles::{StyleType, Styles},
+    toc::TableOfContents,
+    visit::Visit,
+    Docx,
+};
+
+/// Options of HTML export
+#[derive(Clone, Debug)]
+pub struct HtmlOptions<'a> {
+    /// Specifies whether to keep the formatting: styles become CSS classes
+    /// and direct formatting inline styles.
+    pub faithful: bool,
+    /// Specifies whether to write a complete HTML document rather than a
+    /// fragment.
+    pub standalone: bool,
+    /// Specifies the title of a complete document, the title of the core
+    /// properties being used otherwise.
+    pub title: Option<Cow<'a, str>>,
+    /// Specifies the folder of the extracted images, relative to the HTML
+    /// file. Images are inlined as data URIs otherwise.
+    pub image_dir: Option<Cow<'a, str>>,
+}
+
+impl Default for HtmlOptions<'_> {
+    fn default() -> Self {
+        HtmlOptions {
+            faithful: false,
+            standalone: true,
+            title: None,
+            image_dir: None,
+        }
+    }
+}
+
+impl<'a> HtmlOptions<'a> {
+    __setter!(faithful: bool);
+    __setter!(standalone: bool);
+    __setter!(title: Option<Cow<'a, str>>);
+    __setter!(image_dir: Option<Cow<'a, str>>);
+}
+
+/// A document converted to HTML
+#[derive(Clone, Debug, Default)]
+pub struct Html {
+    /// The HTML text.
+    pub text: String,
+    /// The images referenced by the text, by path relative to the HTML file.
+    ///
+    /// It's empty unless [`HtmlOptions::image_dir`] is specified.
+    pub images: BTreeMap<String, Bytes>,
+}
+
+impl Html {
+    /// Writes the HTML text to a file, and the images next to it.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, &self.text)?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for (name, data) in &self.images {
+            let image_path = dir.join(name);
+            if let Some(parent) = image_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(image_path, data)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts the document to HTML.
+    ///
+    /// Numbered and bulleted paragraphs become `ol` and `ul` lists.
+    /// Footnotes, endnotes and comments are linked from their references
+    /// and written at the end, and bookmarks become anchors.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    /// use docx::formatting::{CharacterProperty, ParagraphProperty};
+    /// use docx::html::HtmlOptions;
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Release notes"),
+    /// );
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .push_text("Now ")
+    ///         .push(Run::default().property(CharacterProperty::default().bold(true)).push_text("faster")),
+    /// );
+    ///
+    /// let html = docx.to_html(&HtmlOptions::default().standalone(false));
+    /// assert_eq!(html.text, "<h1>Release notes</h1>\n<p>Now <strong>faster</strong></p>\n");
+    /// ```
+    pub fn to_html(&self, options: &HtmlOptions) -> Html {
+        let numbering = Default::default();
+        let mut writer = Writer::new(self, options, &numbering);
+
+        writer.blocks(&self.document.body.content);
+        writer.close_lists();
+        writer.notes();
+
+        let body = std::mem::take(&mut writer.out);
+        let mut text = String::new();
+
+        if options.standalone {
+            text += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+            let title = options
+                .title
+                .as_deref()
+                .or_else(|| self.core.as_ref().and_then(|core| core.title.as_deref()));
+            if let Some(title) = title {
+                text += &format!("<title>{}</title>\n", escape(title));
+            }
+        }
+        if options.faithful {
+            text += &format!("<style>\n{}</style>\n", stylesheet(&self.styles));
+        }
+        if options.standalone {
+            text += "</head>\n<body>\n";
+        }
+        text += &body;
+        if options.standalone {
+            text += "</body>\n</html>\n";
+        }
+
+        Html {
+            text,
+            images: writer.images,
+        }
+    }
+
+    /// Converts parts of the document to XHTML fragments, the chapters of
+    /// an e-book.
+    ///
+    /// Lists continue across parts, while notes are numbered and written at
+    /// the end of each part. Headings get ids and make up the outline, and
+    /// links to bookmarks of other parts point to the file of their part,
+    /// named by `part_file`.
+    pub(crate) fn to_xhtml_parts(
+        &self,
+        options: &HtmlOptions,
+        parts: &[&[BodyContent<'a>]],
+        part_file: fn(usize) -> String,
+    ) -> XhtmlParts {
+        let numbering = Default::default();
+        let mut writer = Writer::new(self, options, &numbering);
+        writer.xhtml = true;
+        writer.headings = Some(Vec::new());
+        writer.part_file = Some(part_file);
+
+        // links can point forward, so bookmarks are located beforehand
+        for (index, part) in parts.iter().enumerate() {
+            let mut bookmarks = BookmarkParts {
+                part: index,
+                parts: &mut writer.bookmark_parts,
+            };
+            for content in part.iter() {
+                bookmarks.visit_body_content(content);
+            }
+        }
+
+        let mut texts = Vec::new();
+        for part in parts {
+            writer.blocks(part);
+            writer.close_lists();
+            writer.notes();
+            writer.footnotes.clear();
+            writer.endnotes.clear();
+            writer.comments.clear();
+            texts.push(std::mem::take(&mut writer.out));
+            writer.part += 1;
+        }
+
+        XhtmlParts {
+            parts: texts,
+            headings: writer.headings.unwrap_or_default(),
+            images: writer.images,
+        }
+    }
+}
+
+/// Parts of a document converted to XHTML
+pub(crate) struct XhtmlParts {
+    pub parts: Vec<String>,
+    pub headings: Vec<OutlineHeading>,
+    pub images: BTreeMap<String, Bytes>,
+}
+
+/// Records the part containing each bookmark
+struct BookmarkParts<'p> {
+    part: usize,
+    parts: &'p mut HashMap<String, usize>,
+}
+
+impl<'a> Visit<'a> for BookmarkParts<'_> {
+    fn visit_bookmark_start(&mut self, bookmark: &BookmarkStart<'a>) {
+        if let Some(name) = &bookmark.name {
+            self.parts.entry(name.to_string()).or_insert(self.part);
+        }
+    }
+}
+
+/// A heading of the outline of a document
+pub(crate) struct OutlineHeading {
+    /// The index of the part containing the heading.
+    pub part: usize,
+    /// The level of the heading, from 1.
+    pub level: usize,
+    pub id: String,
+    pub text: String,
+}
+
+struct Writer<'d, 'o, 'n, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'o HtmlOptions<'o>,
+    toc: TableOfContents<'static>,
+    lists: ListCounter<'n, 'a>,
+    /// The lists being written, the innermost last.
+    open_lists: Vec<OpenList>,
+    out: String,
+    images: BTreeMap<String, Bytes>,
+    /// Ids of the referenced footnotes, endnotes and comments.
+    footnotes: Vec<String>,
+    endnotes: Vec<String>,
+    comments: Vec<String>,
+    /// Whether void elements are closed, as XML requires.
+    xhtml: bool,
+    /// The headings written so far, if headings get ids.
+    headings: Option<Vec<OutlineHeading>>,
+    /// The index of the part being written.
+    part: usize,
+    /// Names the file of a part, if the document is written in several
+    /// files.
+    part_file: Option<fn(usize) -> String>,
+    /// The index of the part containing each bookmark.
+    bookmark_parts: HashMap<String, usize>,
+}
+
+/// Formatting of a piece of inline content
+#[derive(Clone, Default, PartialEq)]
+struct Format {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    /// The class and the inline style of the faithful mode.
+    class: Option<String>,
+    style: String,
+}
+
+impl<'d, 'o, 'n, 'a> Writer<'d, 'o, 'n, 'a> {
+    fn new(docx: &'d Docx<'a>, options: &'o HtmlOptions<'o>, numbering: &'n Numberings<'a>) -> Self
+    where
+        'd: 'n,
+    {
+        Writer {
+            docx,
+            options,
+            toc: (1..=6).fold(TableOfContents::default(), |toc, level| {
+                toc.heading_style(format!("Heading{}", level), level)
+            }),
+            lists: ListCounter::new(docx.numbering.as_ref().unwrap_or(numbering)),
+            open_lists: Vec::new(),
+            out: String::new(),
+            images: BTreeMap::new(),
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
+            xhtml: false,
+            headings: None,
+            part: 0,
+            part_file: None,
+            bookmark_parts: HashMap::new(),
+        }
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para),
+                BodyContent::Table(table) => {
+                    self.close_lists();
+                    self.table(table);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph) {
+        let inline = self.inline(&para.content);
+        let attrs = self.paragraph_attrs(&para.property);
+
+        if let Some(level) = self.toc.heading_level(para, &self.docx.styles) {
+            self.close_lists();
+            let level = level.min(6);
+            let mut attrs = attrs;
+            if let Some(headings) = &mut self.headings {
+                let id = format!("heading-{}", headings.len() + 1);
+                attrs = format!(" id=\"{}\"{}", id, attrs);
+                headings.push(OutlineHeading {
+                    part: self.part,
+                    level,
+                    id,
+                    text: para.iter_text().map(|text| &**text).collect(),
+                });
+            }
+            self.out += &format!("<h{}{}>{}</h{}>\n", level, attrs, inline, level);
+            return;
+        }
+
+        if let Some(item) = para
+            .property
+            .numbering
+            .as_ref()
+            .and_then(|numbering| self.lists.next(numbering))
+        {
+            self.list_item(&item, &attrs, &inline);
+            return;
+        }
+
+        self.close_lists();
+        if inline.is_empty() {
+            // empty paragraphs are kept as blank lines in the faithful mode
+            if self.options.faithful {
+                self.out += &format!("<p{}>{}</p>\n", attrs, self.void("<br"));
+            }
+            return;
+        }
+        self.out += &format!("<p{}>{}</p>\n", attrs, inline);
+    }
+
+    fn list_item(&mut self, item: &ListItem, attrs: &str, inline: &str) {
+        if self.enter_list(item) {
+            self.out += "</li>\n";
+        }
+        self.out += &format!("<li{}>{}", attrs, inline);
+    }
+
+    fn paragraph_attrs(&self, property: &ParagraphProperty) -> String {
+        if !self.options.faithful {
+            return String::new();
+        }
+        let mut css = Vec::new();
+        paragraph_css(property, &mut css);
+        attrs(
+            property
+                .style_id
+                .as_ref()
+                .map(|style_id| class_name(&style_id.value)),
+            &css,
+        )
+    }
+
+    fn table(&mut self, table: &Table) {
+        let table_attrs = if self.options.faithful {
+            let mut css = Vec::new();
+            table_css(&table.property, &mut css);
+            attrs(
+                table
+                    .property
+                    .style_id
+                    .as_ref()
+                    .map(|style_id| class_name(&style_id.value)),
+                &css,
+            )
+        } else {
+            String::new()
+        };
+
+        self.out += &format!("<table{}>\n", table_attrs);
+        for row in table.iter_rows() {
+            self.out += "<tr>\n";
+            for cell in row.iter_cells() {
+                let mut paragraphs = Vec::new();
+                cell_paragraphs(&cell.content, &mut paragraphs);
+
+                self.out += "<td>";
+                match (paragraphs.as_slice(), &cell.content[..]) {
+                    // a single plain paragraph is written inline
+                    ([para], [TableCellContent::Paragraph(_)])
+                        if para.property.numbering.is_none()
+                            && self.toc.heading_level(para, &self.docx.styles).is_none()
+                            && !self.options.faithful =>
+                    {
+                        let inline = self.inline(&para.content);
+                        self.out += &inline;
+                    }
+                    _ => {
+                        self.out += "\n";
+                        for content in &cell.content {
+                            match content {
+                                TableCellContent::Paragraph(para) => self.paragraph(para),
+                                TableCellContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+                                TableCellContent::Table(table) => {
+                                    self.close_lists();
+                                    self.table(table);
+                                }
+                            }
+                        }
+                        self.close_lists();
+                    }
+                }
+                self.out += "</td>\n";
+            }
+            self.out += "</tr>\n";
+        }
+        self.out += "</table>\n";
+    }
+
+    /// Writes the footnotes, endnotes and comments referenced so far.
+    fn notes(&mut self) {
+        let docx = self.docx;
+
+        // notes may reference other notes, which are appended while writing
+        let mut index = 0;
+        while index < self.footnotes.len() {
+            if index == 0 {
+                self.out += "<section class=\"footnotes\">\n<ol>\n";
+            }
+            let id = self.footnotes[index].clone();
+            let content = docx.footnotes.as_ref().and_then(|notes| notes.get(&id));
+            self.note("footnote", &id, None, content.map(|note| &note.content[..]));
+            index += 1;
+            if index == self.footnotes.len() {
+                self.out += "</ol>\n</section>\n";
+            }
+        }
+
+        let mut index = 0;
+        while index < self.endnotes.len() {
+            if index == 0 {
+                self.out += "<section class=\"endnotes\">\n<ol>\n";
+            }
+            let id = self.endnotes[index].clone();
+            let content = docx.endnotes.as_ref().and_then(|notes| notes.get(&id));
+            self.note("endnote", &id, None, content.map(|note| &note.content[..]));
+            index += 1;
+            if index == self.endnotes.len() {
+                self.out += "</ol>\n</section>\n";
+            }
+        }
+
+        let mut index = 0;
+        while index < self.comments.len() {
+            if index == 0 {
+                self.out += "<section class=\"comments\">\n<ol>\n";
+            }
+            let id = self.comments[index].clone();
+            let comment = docx
+                .comments
+                .as_ref()
+                .and_then(|comments| comments.comments.iter().find(|comment| comment.id == id));
+            if let Some(comment) = comment {
+                let content: Vec<BodyContent> = comment
+                    .content
+                    .iter()
+                    .map(|para| BodyContent::Paragraph(para.clone()))
+                    .collect();
+                self.note("comment", &id, Some(&comment.author), Some(&content));
+            } else {
+                self.note("comment", &id, None, None);
+            }
+            index += 1;
+            if index == self.comments.len() {
+                self.out += "</ol>\n</section>\n";
+            }
+        }
+    }
+
+    fn note(
+        &mut self,
+        kind: &str,
+        id: &str,
+        author: Option<&str>,
+        content: Option<&[BodyContent]>,
+    ) {
+        let id = escape(id);
+        self.out += &format!("<li id=\"{}-{}\">\n", kind, id);
+        if let Some(author) = author {
+            self.out += &format!("<p class=\"author\">{}</p>\n", escape(author));
+        }
+        if let Some(content) = content {
+            self.blocks(content);
+            self.close_lists();
+        }
+        self.out += &format!(
+            "<p><a href=\"#{}-ref-{}\" class=\"back\">↩</a></p>\n</li>\n",
+            kind, id
+        );
+    }
+
+    fn image(&mut self, drawing: &Drawing) -> Option<String> {
+        let (name, data) = self.docx.embedded_media(drawing.embed.as_deref()?)?;
+        let file_name = name.rsplit('/').next()?;
+
+        let src = match &self.options.image_dir {
+            Some(dir) => {
+                let path = format!("{}/{}", dir.trim_end_matches('/'), file_name);
+                self.images.insert(path.clone(), data.clone());
+                path
+            }
+            None => {
+                let ext = file_name.rsplit('.').next().unwrap_or_default();
+                let ty = crate::content_type::image_content_type(&ext.to_ascii_lowercase())
+                    .unwrap_or("application/octet-stream");
+                format!("data:{};base64,{}", ty, crate::base64::encode(data))
+            }
+        };
+
+        let mut html = format!(
+            "<img src=\"{}\" alt=\"{}\"",
+            escape(&src),
+            escape(drawing.name.as_deref().unwrap_or_default())
+        );
+        // 9525 EMUs per pixel
+        if let (Some(width), Some(height)) = (drawing.width, drawing.height) {
+            html += &format!(" width=\"{}\" height=\"{}\"", width / 9525, height / 9525);
+        }
+
+        Some(self.void(&html))
+    }
+
+    /// Ends the start tag of a void element.
+    fn void(&self, tag: &str) -> String {
+        if self.xhtml {
+            format!("{}/>", tag)
+        } else {
+            format!("{}>", tag)
+        }
+    }
+}
+
+impl ListWriter for Writer<'_, '_, '_, '_> {
+    fn open_lists(&mut self) -> &mut Vec<OpenList> {
+        &mut self.open_lists
+    }
+
+    fn start_list(&mut self, item: &ListItem) {
+        let mut list_attrs = String::new();
+        if !item.is_bullet() {
+            let ty = match item.format {
+                NumberingFormatVal::LowerLetter => Some("a"),
+                NumberingFormatVal::UpperLetter => Some("A"),
+                NumberingFormatVal::LowerRoman => Some("i"),
+                NumberingFormatVal::UpperRoman => Some("I"),
+                _ => None,
+            };
+            if let Some(ty) = ty {
+                list_attrs += &format!(" type=\"{}\"", ty);
+            }
+            if item.number != 1 {
+                list_attrs += &format!(" start=\"{}\"", item.number);
+            }
+        }
+        let tag = if item.is_bullet() { "ul" } else { "ol" };
+        self.out += &format!("<{}{}>\n", tag, list_attrs);
+    }
+
+    fn end_list(&mut self, list: &OpenList) {
+        let tag = if list.ordered { "ol" } else { "ul" };
+        self.out += &format!("</li>\n</{}>\n", tag);
+    }
+}
+
+impl InlineWriter for Writer<'_, '_, '_, '_> {
+    type Format = Format;
+
+    fn docx(&self) -> &Docx<'_> {
+        self.docx
+    }
+
+    fn format(&self, prop: &CharacterProperty) -> Format {
+        let styles = &self.docx.styles;
+
+        if self.options.faithful {
+            let mut css = Vec::new();
+            character_css(prop, &mut css);
+            return Format {
+                class: prop
+                    .style_id
+                    .as_ref()
+                    .map(|style_id| class_name(&style_id.value)),
+                style: css.join("; "),
+                ..Default::default()
+            };
+        }
+
+        // the formatting of the character style, overridden by the direct formatting
+        let mut effective = prop
+            .style_id
+            .as_ref()
+            .and_then(|style_id| styles.get(&style_id.value))
+            .map(|style| resolve(styles, style).0)
+            .unwrap_or_default();
+        effective.merge(prop);
+
+        Format {
+            bold: effective.bold.is_some_and(|bold| is_on(bold.value)),
+            italic: effective
+                .italics
+                .is_some_and(|italics| is_on(italics.value)),
+            underline: effective
+                .underline
+                .as_ref()
+                .is_some_and(|underline| !matches!(underline.val, Some(UnderlineStyle::None))),
+            strike: effective.strike.is_some_and(|strike| is_on(strike.value))
+                || effective
+                    .dstrike
+                    .is_some_and(|dstrike| is_on(dstrike.value)),
+            ..Default::default()
+        }
+    }
+
+    fn run_content(&mut self, content: &RunContent, _: &Format) -> Option<String> {
+        let html = match content {
+            RunContent::Text(text) => escape(&text.text),
+            // XML doesn't define the entities of HTML
+            RunContent::Tab(_) if self.options.faithful && self.xhtml => "&#8195;".to_owned(),
+            RunContent::Tab(_) if self.options.faithful => "&emsp;".to_owned(),
+            RunContent::Tab(_) => " ".to_owned(),
+            RunContent::Break(Break {
+                ty: Some(BreakType::Page),
+            }) if self.options.faithful => self.void("<br style=\"page-break-after: always\""),
+            RunContent::Break(_) => self.void("<br"),
+            RunContent::Drawing(drawing) => self.image(drawing)?,
+            RunContent::FootnoteReference(reference) => {
+                reference_html("footnote", &reference.id, &mut self.footnotes)
+            }
+            RunContent::EndnoteReference(reference) => {
+                reference_html("endnote", &reference.id, &mut self.endnotes)
+            }
+            RunContent::CommentReference(reference) => {
+                reference_html("comment", &reference.id, &mut self.comments)
+            }
+            _ => return None,
+        };
+        Some(html)
+    }
+
+    fn formatted(format: &Format, content: String) -> String {
+        let mut open = String::new();
+        let mut close = Vec::new();
+        let mut tag = |name: &'static str, attrs: String| {
+            open += &format!("<{}{}>", name, attrs);
+            close.push(name);
+        };
+        if format.class.is_some() || !format.style.is_empty() {
+            tag(
+                "span",
+                attrs(format.class.clone(), std::slice::from_ref(&format.style)),
+            );
+        }
+        for (on, name) in [
+            (format.bold, "strong"),
+            (format.italic, "em"),
+            (format.underline, "u"),
+            (format.strike, "s"),
+        ] {
+            if on {
+                tag(name, String::new());
+            }
+        }
+
+        let mut html = open + &content;
+        for name in close.iter().rev() {
+            html += &format!("</{}>", name);
+        }
+        html
+    }
+
+    fn link(&self, href: &str, text: String) -> String {
+        format!("<a href=\"{}\">{}</a>", escape(href), text)
+    }
+
+    /// Links to a bookmark, in the file of its part if it's another one.
+    fn anchor_href(&self, anchor: &str) -> String {
+        match (self.part_file, self.bookmark_parts.get(anchor)) {
+            (Some(part_file), Some(&part)) if part != self.part => {
+                format!("{}#{}", part_file(part), anchor)
+            }
+            _ => format!("#{}", anchor),
+        }
+    }
+
+    fn bookmark(&mut self, name: &str) -> Option<String> {
+        Some(format!("<a id=\"{}\"></a>", escape(name)))
+    }
+}
+
+/// Writes a reference to a note or a comment, numbered in order of first
+/// reference.
+fn reference_html(kind: &str, id: &str, ids: &mut Vec<String>) -> String {
+    let number = match ids.iter().position(|other| other == id) {
+        Some(index) => index + 1,
+        None => {
+            ids.push(id.to_owned());
+            ids.len()
+        }
+    };
+    let label = if kind == "comment" {
+        format!("[{}]", number)
+    } else {
+        number.to_string()
+    };
+    let id = escape(id);
+    format!(
+        "<sup><a href=\"#{kind}-{id}\" id=\"{kind}-ref-{id}\">{label}</a></sup>",
+        kind = kind,
+        id = id,
+        label = label
+    )
+}
+
+/// Writes the `class` and `style` attributes.
+fn attrs(class: Option<String>, css: &[String]) -> String {
+    let mut attrs = String::new();
+    if let Some(class) = class {
+        attrs += &format!(" class=\"{}\"", class);
+    }
+    let css: Vec<&str> = css
+        .iter()
+        .map(String::as_str)
+        .filter(|css| !css.is_empty())
+        .collect();
+    if !css.is_empty() {
+        attrs += &format!(" style=\"{}\"", escape(&css.join("; ")));
+    }
+    attrs
+}
+
+/// Converts the styles to CSS, the document defaults applying to the body.
+pub(crate) fn stylesheet(styles: &Styles) -> String {
+    let mut css = String::from("table { border-collapse: collapse; }\n");
+
+    let mut declarations = Vec::new();
+    character_css(&styles.default.character.inner, &mut declarations);
+    paragraph_css(&styles.default.paragraph.inner, &mut declarations);
+    if !declarations.is_empty() {
+        css += &format!("body {{ {}; }}\n", declarations.join("; "));
+    }
+
+    for style in &styles.styles {
+        let (character, paragraph) = resolve(styles, style);
+        let mut declarations = Vec::new();
+        character_css(&character, &mut declarations);
+        if style.ty == StyleType::Paragraph {
+            paragraph_css(&paragraph, &mut declarations);
+        }
+        if !declarations.is_empty() {
+            css += &format!(
+                ".{} {{ {}; }}\n",
+                class_name(&style.style_id),
+                declarations.join("; ")
+            );
+        }
+    }
+
+    css
+}
+
+fn character_css(prop: &CharacterProperty, css: &mut Vec<String>) {
+    if let Some(bold) = &prop.bold {
+        let weight = if is_on(bold.value) { "bold" } else { "normal" };
+        css.push(format!("font-weight: {}", weight));
+    }
+    if let Some(italics) = &prop.italics {
+        let style = if is_on(italics.value) {
+            "italic"
+        } else {
+            "normal"
+        };
+        css.push(format!("font-style: {}", style));
+    }
+
+    let underline = prop
+        .underline
+        .as_ref()
+        .map(|underline| !matches!(underline.val, Some(UnderlineStyle::None)));
+    let strike = match (&prop.strike, &prop.dstrike) {
+        (None, None) => None,
+        (strike, dstrike) => Some(
+            strike.as_ref().is_some_and(|strike| is_on(strike.value))
+                || dstrike.as_ref().is_some_and(|dstrike| is_on(dstrike.value)),
+        ),
+    };
+    if underline.is_some() || strike.is_some() {
+        let mut lines = Vec::new();
+        if underline == Some(true) {
+            lines.push("underline");
+        }
+        if strike == Some(true) {
+            lines.push("line-through");
+        }
+        if lines.is_empty() {
+            lines.push("none");
+        }
+        css.push(format!("text-decoration: {}", lines.join(" ")));
+    }
+
+    if let Some(color) = &prop.color {
+        if color.value != "auto" {
+            css.push(format!("color: #{}", color.value));
+        }
+    }
+    if let Some(size) = &prop.size {
+        // in half-points
+        css.push(format!("font-size: {}pt", size.value as f64 / 2.0));
+    }
+    if let Some(fill) = prop
+        .shading
+        .as_ref()
+        .and_then(|shading| shading.fill.as_ref())
+    {
+        if fill != "auto" {
+            css.push(format!("background-color: #{}", fill));
+        }
+    }
+    if let Some(vertical_align) = &prop.vertical_align {
+        let align = match vertical_align.value {
+            VerticalAlignVal::Superscript => "super",
+            VerticalAlignVal::Subscript => "sub",
+            VerticalAlignVal::Baseline => "baseline",
+        };
+        css.push(format!("vertical-align: {}", align));
+    }
+    if prop
+        .vanish
+        .as_ref()
+        .is_some_and(|vanish| is_on(vanish.value))
+    {
+        css.push("display: none".to_owned());
+    }
+}
+
+fn paragraph_css(prop: &ParagraphProperty, css: &mut Vec<String>) {
+    if let Some(justification) = &prop.justification {
+        let align = match justification.value {
+            JustificationVal::Left | JustificationVal::Start => "left",
+            JustificationVal::Center => "center",
+            JustificationVal::Right | JustificationVal::End => "right",
+            _ => "justify",
+        };
+        css.push(format!("text-align: {}", align));
+    }
+    if let Some(fill) = prop
+        .shading
+        .as_ref()
+        .and_then(|shading| shading.fill.as_ref())
+    {
+        if fill != "auto" {
+            css.push(format!("background-color: #{}", fill));
+        }
+    }
+
+    if let Some(borders) = &prop.border {
+        macro_rules! border {
+            ($($side:ident),*) => {
+                $(
+                    if let Some(border) = &borders.$side {
+                        css.push(border_css(
+                            stringify!($side),
+                            border.style.as_ref(),
+                            border.size,
+                            border.color.as_deref(),
+                        ));
+                    }
+                )*
+            };
+        }
+        border!(top, bottom, left, right);
+    }
+}
+
+fn table_css(prop: &TableProperty, css: &mut Vec<String>) {
+    if let Some(justification) = prop
+        .justification
+        .as_ref()
+        .and_then(|justification| justification.value.as_ref())
+    {
+        match justification {
+            TableJustificationVal::Center => {
+                css.push("margin-left: auto; margin-right: auto".to_owned())
+            }
+            TableJustificationVal::Right | TableJustificationVal::End => {
+                css.push("margin-left: auto".to_owned())
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(width) = &prop.width {
+        match (width.value, &width.unit) {
+            // in fiftieths of a percent
+            (Some(value), Some(TableWidthUnit::Pct)) => {
+                css.push(format!("width: {}%", value as f64 / 50.0))
+            }
+            // in twentieths of a point
+            (Some(value), Some(TableWidthUnit::Dxa)) | (Some(value), None) => {
+                css.push(format!("width: {}pt", value as f64 / 20.0))
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(borders) = &prop.borders {
+        if let Some(top) = &borders.top {
+            css.push(border_css(
+                "top",
+                top.style.as_ref(),
+                top.size,
+                top.color.as_deref(),
+            ));
+        }
+        if let Some(bottom) = &borders.bottom {
+            css.push(border_css(
+                "bottom",
+                bottom.style.as_ref(),
+                bottom.size,
+                bottom.color.as_deref(),
+            ));
+        }
+    }
+}
+
+fn border_css(
+    side: &str,
+    style: Option<&BorderStyle>,
+    size: Option<usize>,
+    color: Option<&str>,
+) -> String {
+    let style = match style {
+        Some(BorderStyle::None) | Some(BorderStyle::Nil) => {
+            return format!("border-{}: none", side)
+        }
+        Some(BorderStyle::Double) => "double",
+        Some(BorderStyle::Dotted) => "dotted",
+        Some(BorderStyle::Dashed) | Some(BorderStyle::DashSmallGap) => "dashed",
+        _ => "solid",
+    };
+    // in eighths of a point
+    let width = size.map_or(0.5, |size| size as f64 / 8.0);
+    let color = match color {
+        Some(color) if color != "auto" => format!("#{}", color),
+        _ => "currentColor".to_owned(),
+    };
+    format!("border-{}: {}pt {} {}", side, width, style, color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        comments::{Comment, Comments},
+        document::{BookmarkStart, CommentReference, FootnoteReference, Hyperlink, Run, TableRow},
+        footnotes::{Footnote, Footnotes},
+        formatting::{Justification, NumberingProperty},
+        numbering::{
+            AbstractNumbering, AbstractNumberingId, Level, LevelText, Numbering, NumberingFormat,
+            Numberings, Start,
+        },
+        rels::Relationships,
+        schema::{SCHEMA_HYPERLINK, SCHEMA_IMAGE},
+        styles::{BasedOn, Style},
+    };
+
+    fn item(num_id: usize, level: usize, text: &'static str) -> Paragraph<'static> {
+        Paragraph::default()
+            .property(
+                ParagraphProperty::default().numbering(NumberingProperty::from((num_id, level))),
+            )
+            .push_text(text)
+    }
+
+    fn docx() -> Docx<'static> {
+        let levels = |format: NumberingFormatVal, start: usize| {
+            (0..2)
+                .map(|index| Level {
+                    index,
+                    start: Some(Start { value: start }),
+                    numbering_format: Some(NumberingFormat { value: format }),
+                    level_text: LevelText {
+                        value: "%1.".into(),
+                    },
+                })
+                .collect()
+        };
+
+        let mut rels = Relationships::default();
+        let link = rels.add_external_rel(SCHEMA_HYPERLINK, "https://example.com/?a&b");
+        let image = rels.add_rel(SCHEMA_IMAGE, "media/image1.png");
+
+        let mut docx = Docx {
+            numbering: Some(Numberings {
+                abstract_nums: vec![
+                    AbstractNumbering {
+                        abstract_num_id: 0,
+                        levels: levels(NumberingFormatVal::Bullet, 1),
+                        ..Default::default()
+                    },
+                    AbstractNumbering {
+                        abstract_num_id: 1,
+                        levels: levels(NumberingFormatVal::LowerRoman, 3),
+                        ..Default::default()
+                    },
+                ],
+                nums: vec![
+                    Numbering {
+                        num_id: 1,
+                        abstract_num_id: AbstractNumberingId { value: 0 },
+                    },
+                    Numbering {
+                        num_id: 2,
+                        abstract_num_id: AbstractNumberingId { value: 1 },
+                    },
+                ],
+            }),
+            document_rels: Some(rels),
+            footnotes: Some(Footnotes {
+                notes: vec![Footnote::new("1").push(Paragraph::default().push_text("A note."))],
+            }),
+            comments: Some(Comments {
+                comments: vec![Comment {
+                    id: "0".into(),
+                    author: "Ann".into(),
+                    content: vec![Paragraph::default().push_text("Why?")],
+                    ..Default::default()
+                }],
+            }),
+            ..Default::default()
+        };
+        docx.media.insert(
+            "word/media/image1.png".to_owned(),
+            Bytes::from_static(b"png"),
+        );
+        docx.styles
+            .push(
+                Style::new(StyleType::Paragraph, "Title")
+                    .character(CharacterProperty::default().size(48usize).color("1F3864")),
+            )
+            .push(
+                Style::new(StyleType::Paragraph, "Subtitle")
+                    .based_on(BasedOn::from("Title"))
+                    .paragraph(
+                        ParagraphProperty::default()
+                            .justification(Justification::from(JustificationVal::Center)),
+                    ),
+            );
+
+        let document = &mut docx.document;
+        document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Subtitle"))
+                .push(BookmarkStart::default().id("0").name("intro"))
+                .push_text("Intro"),
+        );
+        document.push(
+            Paragraph::default()
+                .push_text("a < b ")
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().bold(true).italics(true))
+                        .push_text("both"),
+                )
+                .push(
+                    Hyperlink::default()
+                        .id(link)
+                        .content(Run::default().push_text("link")),
+                )
+                .push(
+                    Hyperlink::default()
+                        .anchor("intro")
+                        .content(Run::default().push_text("back")),
+                )
+                .push(Run::default().push(FootnoteReference::from("1")))
+                .push(Run::default().push(CommentReference::from("0"))),
+        );
+        document.push(item(1, 0, "one"));
+        document.push(item(1, 1, "nested"));
+        document.push(item(2, 0, "three"));
+        document.push(
+            Table::default().push_row(
+                TableRow::default()
+                    .push_cell(Paragraph::default().push_text("x"))
+                    .push_cell(Paragraph::default().push(
+                        Run::default().push(Drawing::picture(image, 95250, 190500).name("Logo")),
+                    )),
+            ),
+        );
+        docx
+    }
+
+    #[test]
+    fn to_html() {
+        let html = docx().to_html(&HtmlOptions::default().standalone(false));
+        assert_eq!(
+            html.text,
+            "<p><a id=\"intro\"></a>Intro</p>\n\
+             <p>a &lt; b <strong><em>both</em></strong>\
+             <a href=\"https://example.com/?a&amp;b\">link</a><a href=\"#intro\">back</a>\
+             <sup><a href=\"#footnote-1\" id=\"footnote-ref-1\">1</a></sup>\
+             <sup><a href=\"#comment-0\" id=\"comment-ref-0\">[1]</a></sup></p>\n\
+             <ul>\n<li>one<ul>\n<li>nested</li>\n</ul>\n</li>\n</ul>\n\
+             <ol type=\"i\" start=\"3\">\n<li>three</li>\n</ol>\n\
+             <table>\n<tr>\n<td>x</td>\n\
+             <td><img src=\"data:image/png;base64,cG5n\" alt=\"Logo\" width=\"10\" height=\"20\"></td>\n\
+             </tr>\n</table>\n\
+             <section class=\"footnotes\">\n<ol>\n<li id=\"footnote-1\">\n<p>A note.</p>\n\
+             <p><a href=\"#footnote-ref-1\" class=\"back\">↩</a></p>\n</li>\n</ol>\n</section>\n\
+             <section class=\"comments\">\n<ol>\n<li id=\"comment-0\">\n<p class=\"author\">Ann</p>\n<p>Why?</p>\n\
+             <p><a href=\"#comment-ref-0\" class=\"back\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+        );
+        assert!(html.images.is_empty());
+    }
+
+    #[test]
+    fn to_html_faithful() {
+        let html = docx().to_html(
+            &HtmlOptions::default()
+                .faithful(true)
+                .title("Notes")
+                .image_dir("img"),
+        );
+        let text = &html.text;
+        assert!(text.starts_with("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Notes</title>\n<style>\n"));
+        assert!(text.contains(".Title { color: #1F3864; font-size: 24pt; }\n"));
+        // inherited from the base style
+        assert!(
+            text.contains(".Subtitle { color: #1F3864; font-size: 24pt; text-align: center; }\n")
+        );
+        assert!(text.contains("<p class=\"Subtitle\"><a id=\"intro\"></a>Intro</p>"));
+        assert!(text.contains("<span style=\"font-weight: bold; font-style: italic\">both</span>"));
+        assert!(text.contains("<img src=\"img/image1.png\""));
+        assert!(text.ends_with("</body>\n</html>\n"));
+        assert_eq!(
+            html.images.keys().collect::<Vec<_>>(),
+            vec!["img/image1.png"]
+        );
+    }
+}
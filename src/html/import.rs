@@ -0,0 +1,982 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    __setter,
+    convert::{
+        decode_data_uri, merged_cell, pixel_size, text_of, StyleIds, VerticalMerges, TABLE_WIDTH,
+    },
+    document::{
+        BodyContent, Break, Drawing, Hyperlink, Paragraph, ParagraphContent, Run, RunContent,
+        Table, TableCellContent, TableRow,
+    },
+    formatting::{
+        BorderStyle, Borders, BottomBorder, CharacterProperty, Justification, JustificationVal,
+        NumberingProperty, ParagraphProperty, Shading, TableBorders, TableProperty, TopBorder,
+        UnderlineStyle, VerticalAlign, VerticalAlignVal, VerticalMergeVal,
+    },
+    html::parse::{self, Element, Node},
+    numbering::NumberingFormatVal,
+    rels::Relationships,
+    schema::SCHEMA_HYPERLINK,
+    Docx,
+};
+
+/// Options of HTML import
+#[derive(Clone, Debug, Default)]
+pub struct HtmlImportOptions<'a> {
+    /// Specifies the styles of headings, quotes, code, lists, links and tables.
+    pub styles: StyleIds<'a>,
+    /// Specifies the folder which the paths of local images are relative to.
+    ///
+    /// Local images are only read from this folder and its subfolders. Other
+    /// images are replaced by their alternative text, unless they're inlined
+    /// as data URIs.
+    pub base_dir: Option<PathBuf>,
+}
+
+impl<'a> HtmlImportOptions<'a> {
+    __setter!(styles: StyleIds<'a>);
+    __setter!(base_dir: Option<PathBuf>);
+}
+
+/// EMUs per CSS pixel.
+const EMUS_PER_PIXEL: usize = 9525;
+
+impl<'a> Docx<'a> {
+    /// Converts HTML to a document.
+    ///
+    /// Headings, paragraphs, emphasis, links, lists, tables, images, line
+    /// breaks, block quotes and preformatted text are kept, along with the
+    /// colors, font sizes, alignment and backgrounds of inline styles. Other
+    /// elements are replaced by their content, and scripts are dropped.
+    ///
+    /// ```rust
+    /// use docx::html::HtmlImportOptions;
+    /// use docx::Docx;
+    ///
+    /// let docx = Docx::from_html(
+    ///     r#"<h1>Report</h1>
+    ///     <p style="text-align: center">A <strong>bold</strong> <a href="https://example.com">link</a>
+    ///     <ol><li>first<li>second</ol>"#,
+    ///     &HtmlImportOptions::default(),
+    /// );
+    ///
+    /// assert_eq!(docx.document.body.content.len(), 4);
+    /// assert!(docx.styles.get("Heading1").is_some());
+    /// assert_eq!(docx.numbering.unwrap().nums.len(), 1);
+    /// ```
+    pub fn from_html(html: &str, options: &HtmlImportOptions<'a>) -> Docx<'a> {
+        let mut docx = Docx::default();
+        docx.import_html(html, options);
+        docx
+    }
+
+    /// Appends HTML to the document.
+    ///
+    /// Styles which the document doesn't define yet are added from
+    /// [`StyleIds::definitions`], so a template can provide its own.
+    pub fn import_html(&mut self, html: &str, options: &HtmlImportOptions<'a>) {
+        options.styles.add_definitions(&mut self.styles);
+
+        let nodes = parse::parse(html);
+
+        let mut reader = Reader {
+            docx: self,
+            options,
+            blocks: Vec::new(),
+            paragraph: None,
+            space: true,
+            item: None,
+        };
+        reader.nodes(&nodes, &Context::default());
+        reader.end_paragraph();
+
+        let blocks = reader.blocks;
+        self.document.body.content.extend(blocks);
+    }
+}
+
+struct Reader<'d, 'o, 'a> {
+    docx: &'d mut Docx<'a>,
+    options: &'o HtmlImportOptions<'a>,
+    /// The blocks of the body, or of the current table cell.
+    blocks: Vec<BodyContent<'a>>,
+    paragraph: Option<Paragraph<'a>>,
+    /// Whether the text so far ends with a space, which the following
+    /// whitespace collapses into.
+    space: bool,
+    /// The numbering of the first paragraph of the current list item.
+    item: Option<NumberingProperty>,
+}
+
+/// The formatting inherited from the enclosing elements
+#[derive(Clone, Default)]
+struct Context<'a> {
+    paragraph: ParagraphProperty<'a>,
+    character: CharacterProperty<'a>,
+    /// The numbering ids of the open lists.
+    lists: Vec<usize>,
+    /// Whether the whitespace is preserved.
+    pre: bool,
+}
+
+impl<'d, 'o, 'a> Reader<'d, 'o, 'a> {
+    fn nodes(&mut self, nodes: &[Node], context: &Context<'a>) {
+        for node in nodes {
+            match node {
+                Node::Element(element) => self.element(element, context),
+                Node::Text(text) => self.text(text, context),
+            }
+        }
+    }
+
+    fn element(&mut self, element: &Element, context: &Context<'a>) {
+        let options = self.options;
+        let styles = &options.styles;
+        let name = element.name.as_str();
+        let mut context = context.clone();
+        apply_css(element, &mut context, is_block(name));
+
+        let character = std::mem::take(&mut context.character);
+        context.character = match name {
+            "b" | "strong" => character.bold(true),
+            "i" | "em" | "cite" | "dfn" | "var" => character.italics(true),
+            "u" | "ins" => character.underline(UnderlineStyle::Single),
+            "s" | "strike" | "del" => character.strike(true),
+            "sup" => character.vertical_align(VerticalAlignVal::Superscript),
+            "sub" => character.vertical_align(VerticalAlignVal::Subscript),
+            "mark" => character.shading("FFFF00"),
+            "code" | "kbd" | "samp" | "tt" if !context.pre => {
+                character.style_id(styles.code_span.clone())
+            }
+            _ => character,
+        };
+
+        match name {
+            "head" | "title" | "script" | "style" | "template" => (),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = usize::from(name.as_bytes()[1] - b'1');
+                context.paragraph.style_id = Some(styles.headings[level].clone().into());
+                self.block(&element.children, &context);
+            }
+            "blockquote" => {
+                context.paragraph.style_id = Some(styles.quote.clone().into());
+                self.block(&element.children, &context);
+            }
+            "pre" => self.pre(element, context),
+            "ul" | "ol" => self.list(element, context),
+            "li" => {
+                self.end_paragraph();
+                if let Some(num_id) = context.lists.last() {
+                    self.item = Some(NumberingProperty::from((*num_id, context.lists.len() - 1)));
+                }
+                self.block(&element.children, &context);
+                self.item = None;
+            }
+            "table" => self.table(element, &context),
+            "hr" => {
+                self.end_paragraph();
+                let border = BottomBorder::default()
+                    .style(BorderStyle::Single)
+                    .size(6usize)
+                    .color("auto");
+                self.paragraph = Some(Paragraph::default().property(
+                    ParagraphProperty::default().border(Borders::default().bottom(border)),
+                ));
+                self.end_paragraph();
+            }
+            "br" => {
+                self.push(Run::default().push_break(Break::default()), &context);
+                self.space = true;
+            }
+            "img" => self.image(element, &context),
+            "a" => self.link(element, &context),
+            _ if is_block(name) => self.block(&element.children, &context),
+            _ => self.nodes(&element.children, &context),
+        }
+    }
+
+    /// Reads the content of a block, in paragraphs of its own.
+    fn block(&mut self, children: &[Node], context: &Context<'a>) {
+        self.end_paragraph();
+        self.nodes(children, context);
+        self.end_paragraph();
+    }
+
+    /// Reads preformatted text, each line being a paragraph.
+    fn pre(&mut self, element: &Element, mut context: Context<'a>) {
+        context.paragraph.style_id = Some(self.options.styles.code_block.clone().into());
+        context.pre = true;
+
+        // a newline right after the start tag, or right before the end tag,
+        // doesn't count
+        let mut children = element.children.clone();
+        if let Some(Node::Text(text)) = children.first_mut() {
+            if let Some(stripped) = text.strip_prefix('\n') {
+                *text = stripped.to_owned();
+            }
+        }
+        if let Some(Node::Text(text)) = children.last_mut() {
+            if let Some(stripped) = text.strip_suffix('\n') {
+                *text = stripped.to_owned();
+            }
+        }
+
+        self.start_paragraph(&context);
+        self.nodes(&children, &context);
+        self.end_paragraph();
+    }
+
+    fn list(&mut self, element: &Element, mut context: Context<'a>) {
+        let format = match (element.name.as_str(), element.attr("type")) {
+            ("ul", _) => NumberingFormatVal::Bullet,
+            (_, Some("a")) => NumberingFormatVal::LowerLetter,
+            (_, Some("A")) => NumberingFormatVal::UpperLetter,
+            (_, Some("i")) => NumberingFormatVal::LowerRoman,
+            (_, Some("I")) => NumberingFormatVal::UpperRoman,
+            _ => NumberingFormatVal::Decimal,
+        };
+        let start = element
+            .attr("start")
+            .and_then(|start| start.trim().parse().ok())
+            .unwrap_or(1);
+
+        let num_id = self
+            .docx
+            .numbering
+            .get_or_insert_with(Default::default)
+            .push_list(format, start);
+        context.lists.push(num_id);
+        context.paragraph.style_id = Some(self.options.styles.list.clone().into());
+
+        self.block(&element.children, &context);
+    }
+
+    fn table(&mut self, element: &Element, context: &Context<'a>) {
+        self.end_paragraph();
+
+        let mut rows = Vec::new();
+        for child in children(element) {
+            match child.name.as_str() {
+                "caption" => self.block(&child.children, context),
+                "tr" => rows.push(child),
+                "thead" | "tbody" | "tfoot" => {
+                    rows.extend(children(child).filter(|row| row.name == "tr"))
+                }
+                _ => (),
+            }
+        }
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut table = Table::default().property(
+            TableProperty::default()
+                .style_id(self.options.styles.table.clone())
+                .borders(
+                    TableBorders::default()
+                        .top(TopBorder::default().style(BorderStyle::Single))
+                        .bottom(BottomBorder::default().style(BorderStyle::Single)),
+                ),
+        );
+        let mut merges = VerticalMerges::default();
+        let mut columns = 0;
+
+        for row in rows {
+            let mut cells = Vec::new();
+            let mut column = 0;
+            let mut html_cells =
+                children(row).filter(|cell| matches!(cell.name.as_str(), "td" | "th"));
+
+            loop {
+                // the continuations of merged cells come first
+                if let Some(span) = merges.next(column) {
+                    cells.push(merged_cell(
+                        span,
+                        Some(VerticalMergeVal::Continue),
+                        Vec::new(),
+                    ));
+                    column += span;
+                    continue;
+                }
+
+                let Some(html_cell) = html_cells.next() else {
+                    // the row ends, unless cells are merged further right
+                    match merges.next_column(column) {
+                        Some(next) => {
+                            cells.push(merged_cell(next - column, None, Vec::new()));
+                            column = next;
+                            continue;
+                        }
+                        None => break,
+                    }
+                };
+
+                let span = span_attr(html_cell, "colspan");
+                let rows_spanned = span_attr(html_cell, "rowspan");
+                let merge = merges.start(column, span, rows_spanned);
+
+                let content = self.cell(html_cell, context);
+                cells.push(merged_cell(span, merge, content));
+                column += span;
+            }
+
+            columns = columns.max(column);
+            table = table.push_row(
+                cells
+                    .into_iter()
+                    .fold(TableRow::default(), TableRow::push_cell),
+            );
+        }
+
+        let columns = columns.max(1);
+        table = table.push_grid(vec![TABLE_WIDTH / columns; columns]);
+        self.blocks.push(table.into());
+    }
+
+    /// Reads the content of a table cell.
+    fn cell(&mut self, element: &Element, context: &Context<'a>) -> Vec<TableCellContent<'a>> {
+        let mut context = Context {
+            character: context.character.clone(),
+            ..Default::default()
+        };
+        apply_css(element, &mut context, true);
+        if element.name == "th" {
+            context.character = context.character.bold(true);
+        }
+
+        let blocks = std::mem::take(&mut self.blocks);
+        let item = self.item.take();
+        self.block(&element.children, &context);
+        let content = std::mem::replace(&mut self.blocks, blocks);
+        self.item = item;
+
+        let mut cell_content = Vec::new();
+        for block in content {
+            match block {
+                BodyContent::Paragraph(paragraph) => cell_content.push(paragraph.into()),
+                BodyContent::Table(table) => cell_content.push(table.into()),
+                BodyContent::Sdt(_) => (),
+            }
+        }
+        cell_content
+    }
+
+    fn link(&mut self, element: &Element, context: &Context<'a>) {
+        let href = element
+            .attr("href")
+            .map(str::trim)
+            .filter(|href| !href.is_empty() && !is_script(href));
+        let text = self.collapse(&text_content(element));
+        let Some(href) = href.filter(|_| !text.trim().is_empty()) else {
+            // e.g. an image link, which loses its destination
+            return self.nodes(&element.children, context);
+        };
+
+        let hyperlink = if let Some(anchor) = href.strip_prefix('#') {
+            Hyperlink::default().anchor(anchor.to_owned())
+        } else {
+            let id = self
+                .docx
+                .document_rels
+                .get_or_insert_with(Relationships::default)
+                .add_external_rel(SCHEMA_HYPERLINK, href.to_owned());
+            Hyperlink::default().id(id)
+        };
+        let property = context
+            .character
+            .clone()
+            .style_id(self.options.styles.hyperlink.clone());
+        let run = Run::default().property(property).push_text(text_of(&text));
+        self.push(hyperlink.content(run), context);
+    }
+
+    fn image(&mut self, element: &Element, context: &Context<'a>) {
+        let alt = element.attr("alt").unwrap_or_default();
+        let Some((data, extension)) = element.attr("src").and_then(|src| self.image_data(src))
+        else {
+            self.text(alt, context);
+            return;
+        };
+
+        let (width, height) = image_size(element, &data);
+        let (embed, number) = self.docx.embed_media(data.into(), extension);
+        let drawing = Drawing::picture(embed, width * EMUS_PER_PIXEL, height * EMUS_PER_PIXEL)
+            .id(number)
+            .name(alt.to_owned());
+        self.push(Run::default().push(drawing), context);
+        self.space = false;
+    }
+
+    /// Returns the content of an image and its extension.
+    fn image_data(&self, src: &str) -> Option<(Vec<u8>, &'static str)> {
+        let src = src.trim();
+
+        if src.starts_with("data:") {
+            return decode_data_uri(src);
+        }
+
+        // only relative paths inside the base folder are read
+        let base_dir = self.options.base_dir.as_ref()?;
+        let path = Path::new(src.split(['?', '#']).next()?);
+        if src.contains(':')
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return None;
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let extension = match extension.as_str() {
+            "png" => "png",
+            "jpg" | "jpeg" => "jpg",
+            "gif" => "gif",
+            "bmp" => "bmp",
+            "tif" | "tiff" => "tiff",
+            "svg" => "svg",
+            _ => return None,
+        };
+        let data = fs::read(base_dir.join(path)).ok()?;
+        Some((data, extension))
+    }
+
+    fn text(&mut self, text: &str, context: &Context<'a>) {
+        if context.pre {
+            for (index, line) in text.split('\n').enumerate() {
+                if index > 0 {
+                    self.start_paragraph(context);
+                }
+                if !line.is_empty() {
+                    let run = Run::default()
+                        .property(context.character.clone())
+                        .push_text(text_of(line));
+                    self.push(run, context);
+                }
+            }
+            return;
+        }
+
+        let text = self.collapse(text);
+        if text.is_empty() {
+            return;
+        }
+        let run = Run::default()
+            .property(context.character.clone())
+            .push_text(text_of(&text));
+        self.push(run, context);
+    }
+
+    /// Collapses whitespace, the way browsers display text.
+    fn collapse(&mut self, text: &str) -> String {
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                if !self.space {
+                    collapsed.push(' ');
+                    self.space = true;
+                }
+            } else {
+                collapsed.push(c);
+                self.space = false;
+            }
+        }
+        collapsed
+    }
+
+    fn start_paragraph(&mut self, context: &Context<'a>) {
+        self.end_paragraph();
+
+        let mut property = context.paragraph.clone();
+        if let Some(numbering) = self.item.take() {
+            property = property.numbering(numbering);
+        }
+        self.paragraph = Some(Paragraph::default().property(property));
+    }
+
+    fn end_paragraph(&mut self) {
+        self.space = true;
+        let Some(mut paragraph) = self.paragraph.take() else {
+            return;
+        };
+
+        // the whitespace at the end of a paragraph isn't displayed
+        if let Some(ParagraphContent::Run(run)) = paragraph.content.last_mut() {
+            if let Some(RunContent::Text(text)) = run.content.last_mut() {
+                if text.text.ends_with(' ') {
+                    *text = text_of(text.text.trim_end_matches(' '));
+                }
+            }
+        }
+
+        self.blocks.push(paragraph.into());
+    }
+
+    /// Appends content to the current paragraph.
+    fn push<T: Into<ParagraphContent<'a>>>(&mut self, content: T, context: &Context<'a>) {
+        if self.paragraph.is_none() {
+            self.start_paragraph(context);
+        }
+        if let Some(paragraph) = &mut self.paragraph {
+            paragraph.content.push(content.into());
+        }
+    }
+}
+
+/// Elements whose content is in paragraphs of its own.
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "ul",
+];
+
+fn is_block(name: &str) -> bool {
+    BLOCKS.contains(&name)
+}
+
+fn is_script(href: &str) -> bool {
+    let scheme = href.split(':').next().unwrap_or_default();
+    href.contains(':')
+        && matches!(
+            scheme.trim().to_ascii_lowercase().as_str(),
+            "javascript" | "vbscript"
+        )
+}
+
+fn children(element: &Element) -> impl Iterator<Item = &Element> {
+    element.children.iter().filter_map(|node| match node {
+        Node::Element(element) => Some(element),
+        Node::Text(_) => None,
+    })
+}
+
+fn text_content(element: &Element) -> String {
+    let mut text = String::new();
+    for node in &element.children {
+        match node {
+            Node::Text(content) => text.push_str(content),
+            Node::Element(child) if child.name == "br" => text.push(' '),
+            Node::Element(child) => text.push_str(&text_content(child)),
+        }
+    }
+    text
+}
+
+fn span_attr(element: &Element, name: &str) -> usize {
+    element
+        .attr(name)
+        .and_then(|span| span.trim().parse().ok())
+        .unwrap_or(1)
+        .clamp(1, 1000)
+}
+
+/// Applies the inline style, and the presentational attributes.
+fn apply_css<'a>(element: &Element, context: &mut Context<'a>, block: bool) {
+    if let Some(color) = element.attr("color").and_then(parse_color) {
+        context.character.color = Some(color.into());
+    }
+    if block {
+        if let Some(align) = element.attr("align") {
+            apply_declaration("text-align", align, context, block);
+        }
+    }
+
+    for (property, value) in declarations(element) {
+        apply_declaration(&property, &value, context, block);
+    }
+}
+
+fn apply_declaration<'a>(property: &str, value: &str, context: &mut Context<'a>, block: bool) {
+    let value = value.to_ascii_lowercase();
+    let character = &mut context.character;
+    match property {
+        "color" => {
+            if let Some(color) = parse_color(&value) {
+                character.color = Some(color.into());
+            }
+        }
+        "background" | "background-color" => {
+            let color =
+                parse_color(&value).or_else(|| value.split_whitespace().find_map(parse_color));
+            if let Some(color) = color {
+                if block {
+                    context.paragraph.shading = Some(Shading::from(color));
+                } else {
+                    character.shading = Some(Shading::from(color));
+                }
+            }
+        }
+        "font-size" => {
+            if let Some(size) = parse_font_size(&value) {
+                character.size = Some(size.into());
+            }
+        }
+        "font-weight" => {
+            let bold = match value.as_str() {
+                "bold" | "bolder" => Some(true),
+                "normal" | "lighter" => Some(false),
+                weight => weight.parse::<usize>().ok().map(|weight| weight >= 600),
+            };
+            if let Some(bold) = bold {
+                character.bold = Some(bold.into());
+            }
+        }
+        "font-style" => match value.as_str() {
+            "italic" | "oblique" => character.italics = Some(true.into()),
+            "normal" => character.italics = Some(false.into()),
+            _ => (),
+        },
+        "text-decoration" | "text-decoration-line" => {
+            if value.contains("underline") {
+                character.underline = Some(UnderlineStyle::Single.into());
+            }
+            if value.contains("line-through") {
+                character.strike = Some(true.into());
+            }
+            if value == "none" {
+                character.underline = Some(UnderlineStyle::None.into());
+                character.strike = Some(false.into());
+            }
+        }
+        "vertical-align" => match value.as_str() {
+            "super" => {
+                character.vertical_align = Some(VerticalAlign::from(VerticalAlignVal::Superscript))
+            }
+            "sub" => {
+                character.vertical_align = Some(VerticalAlign::from(VerticalAlignVal::Subscript))
+            }
+            _ => (),
+        },
+        "text-align" if block => {
+            let justification = match value.as_str() {
+                "left" | "start" => JustificationVal::Left,
+                "center" => JustificationVal::Center,
+                "right" | "end" => JustificationVal::Right,
+                "justify" => JustificationVal::Both,
+                _ => return,
+            };
+            context.paragraph.justification = Some(Justification::from(justification));
+        }
+        _ => (),
+    }
+}
+
+/// Returns the declarations of the inline style, by lowercase property.
+fn declarations(element: &Element) -> Vec<(String, String)> {
+    let Some(style) = element.attr("style") else {
+        return Vec::new();
+    };
+    style
+        .split(';')
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let value = value.trim();
+            let value = value.strip_suffix("!important").unwrap_or(value);
+            Some((
+                property.trim().to_ascii_lowercase(),
+                value.trim().to_owned(),
+            ))
+        })
+        .collect()
+}
+
+/// Converts a CSS color to a hexadecimal color.
+fn parse_color(value: &str) -> Option<String> {
+    let value = value.trim().to_ascii_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => Some(
+                hex.chars()
+                    .flat_map(|c| [c, c])
+                    .collect::<String>()
+                    .to_ascii_uppercase(),
+            ),
+            6 => Some(hex.to_ascii_uppercase()),
+            _ => None,
+        };
+    }
+
+    if let Some(args) = value
+        .strip_prefix("rgb(")
+        .or_else(|| value.strip_prefix("rgba("))
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = args
+            .split([',', ' ', '/'])
+            .filter(|channel| !channel.is_empty())
+            .take(3)
+            .map(|channel| match channel.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().ok().map(|p| (p * 2.55).round()),
+                None => channel.parse::<f64>().ok(),
+            })
+            .map(|channel| channel.map(|channel| channel.clamp(0.0, 255.0) as u8))
+            .collect::<Option<_>>()?;
+        return match channels.as_slice() {
+            [r, g, b] => Some(format!("{:02X}{:02X}{:02X}", r, g, b)),
+            _ => None,
+        };
+    }
+
+    let hex = match value.as_str() {
+        "black" => "000000",
+        "white" => "FFFFFF",
+        "red" => "FF0000",
+        "green" => "008000",
+        "blue" => "0000FF",
+        "yellow" => "FFFF00",
+        "orange" => "FFA500",
+        "purple" => "800080",
+        "gray" | "grey" => "808080",
+        "silver" => "C0C0C0",
+        "maroon" => "800000",
+        "olive" => "808000",
+        "lime" => "00FF00",
+        "teal" => "008080",
+        "navy" => "000080",
+        "aqua" | "cyan" => "00FFFF",
+        "fuchsia" | "magenta" => "FF00FF",
+        _ => return None,
+    };
+    Some(hex.to_owned())
+}
+
+/// Converts a CSS font size to half-points, the medium size being 12pt.
+fn parse_font_size(value: &str) -> Option<usize> {
+    let number = |number: &str| number.trim().parse::<f64>().ok();
+    let points = if let Some(points) = value.strip_suffix("pt") {
+        number(points)?
+    } else if let Some(pixels) = value.strip_suffix("px") {
+        number(pixels)? * 0.75
+    } else if let Some(ems) = value
+        .strip_suffix("rem")
+        .or_else(|| value.strip_suffix("em"))
+    {
+        number(ems)? * 12.0
+    } else if let Some(percent) = value.strip_suffix('%') {
+        number(percent)? * 0.12
+    } else {
+        match value {
+            "xx-small" => 7.0,
+            "x-small" => 7.5,
+            "small" => 10.0,
+            "medium" => 12.0,
+            "large" => 13.5,
+            "x-large" => 18.0,
+            "xx-large" => 24.0,
+            _ => return None,
+        }
+    };
+    let half_points = (points * 2.0).round();
+    (half_points >= 1.0).then_some(half_points as usize)
+}
+
+/// Returns the size of an image in pixels, from its attributes or its
+/// inline style, keeping the proportions of the image if only one is given.
+fn image_size(element: &Element, data: &[u8]) -> (usize, usize) {
+    let css = declarations(element);
+    let dimension = |name: &str| {
+        let value = css
+            .iter()
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value.as_str())
+            .or_else(|| element.attr(name))?;
+        let value = value.trim();
+        let pixels: f64 = value
+            .strip_suffix("px")
+            .unwrap_or(value)
+            .trim()
+            .parse()
+            .ok()?;
+        (pixels >= 1.0).then_some(pixels.round() as usize)
+    };
+
+    let natural = pixel_size(data);
+    match (dimension("width"), dimension("height"), natural) {
+        (Some(width), Some(height), _) => (width, height),
+        (Some(width), None, Some((natural_width, natural_height))) => {
+            (width, (width * natural_height / natural_width).max(1))
+        }
+        (None, Some(height), Some((natural_width, natural_height))) => {
+            ((height * natural_width / natural_height).max(1), height)
+        }
+        (width, height, natural) => {
+            let (natural_width, natural_height) = natural.unwrap_or((96, 96));
+            (
+                width.unwrap_or(natural_width),
+                height.unwrap_or(natural_height),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base64, html::HtmlOptions};
+
+    #[test]
+    fn from_html() {
+        // a 3 x 2 PNG header
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x03\0\0\0\x02";
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html><head><title>Ignored</title><style>p {{ color: red }}</style></head>
+<body>
+<h2>Title</h2>
+<p style="text-align: right; background: #ff0">Plain <b>bold</b>, <em>italic</em> and
+   <span style="color: rgb(255, 0, 0); font-size: 16px">red</span> x<sup>2</sup>
+   with a <a href="https://example.com">link</a><br>and <code>code</code>.</p>
+<blockquote><p>Quoted</blockquote>
+<ol start="3"><li>three<ul><li>nested</ul><li>four</ol>
+<pre>
+let a = 1;
+
+a *= 2;
+</pre>
+<table border="1">
+<tr><th colspan="2">Head</th><th>Last</th></tr>
+<tr><td rowspan="2">A</td><td>B</td><td>C</td></tr>
+<tr><td>D</td><td>E</td></tr>
+</table>
+<p><img src="data:image/png;base64,{}" alt="Dot" width="30"> <img src="missing.png" alt="Missing"></p>
+<script>alert("no")</script>
+</body></html>"#,
+            base64::encode(png)
+        );
+        let docx = Docx::from_html(&html, &HtmlImportOptions::default());
+
+        let BodyContent::Paragraph(paragraph) = &docx.document.body.content[1] else {
+            panic!("expected a paragraph");
+        };
+        let property = &paragraph.property;
+        assert!(matches!(
+            property.justification.as_ref().map(|j| &j.value),
+            Some(JustificationVal::Right)
+        ));
+        assert_eq!(
+            property.shading.as_ref().and_then(|s| s.fill.as_deref()),
+            Some("FFFF00")
+        );
+        let runs: Vec<_> = paragraph
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Run(run) => Some((
+                    run.iter_text().map(|t| t.to_string()).collect::<String>(),
+                    run,
+                )),
+                _ => None,
+            })
+            .collect();
+        let (_, red) = runs.iter().find(|(text, _)| text == "red").unwrap();
+        assert_eq!(red.property.color.as_ref().unwrap().value, "FF0000");
+        assert_eq!(red.property.size.as_ref().unwrap().value, 24);
+        let (_, square) = runs.iter().find(|(text, _)| text == "2").unwrap();
+        assert!(square.property.vertical_align.is_some());
+
+        // the merged cells are continued in the next row
+        let BodyContent::Table(table) = &docx.document.body.content[9] else {
+            panic!("expected a table");
+        };
+        let rows: Vec<_> = table.iter_rows().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(table.grids.len(), 1);
+        let spans: Vec<Vec<_>> = rows
+            .iter()
+            .map(|row| {
+                row.iter_cells()
+                    .map(|cell| {
+                        (
+                            cell.property.grid_span.as_ref().map(|span| span.value),
+                            cell.property
+                                .vertical_merge
+                                .as_ref()
+                                .map(|m| m.is_restart()),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                vec![(Some(2), None), (None, None)],
+                vec![(None, Some(true)), (None, None), (None, None)],
+                vec![(None, Some(false)), (None, None), (None, None)],
+            ]
+        );
+
+        let (name, _) = docx.media.iter().next().unwrap();
+        assert_eq!(name, "word/media/image1.png");
+
+        // the export reads the import back
+        let exported = docx.to_html(&HtmlOptions::default().standalone(false));
+        assert_eq!(
+            exported.text,
+            format!(
+                "<h2>Title</h2>\n\
+                 <p>Plain <strong>bold</strong>, <em>italic</em> and red x2 with a \
+                 <a href=\"https://example.com\"><u>link</u></a><br>and code.</p>\n\
+                 <p>Quoted</p>\n\
+                 <ol start=\"3\">\n<li>three<ul>\n<li>nested</li>\n</ul>\n</li>\n<li>four</li>\n</ol>\n\
+                 <p>let a = 1;</p>\n<p>a *= 2;</p>\n\
+                 <table>\n\
+                 <tr>\n<td><strong>Head</strong></td>\n<td><strong>Last</strong></td>\n</tr>\n\
+                 <tr>\n<td>A</td>\n<td>B</td>\n<td>C</td>\n</tr>\n\
+                 <tr>\n<td></td>\n<td>D</td>\n<td>E</td>\n</tr>\n\
+                 </table>\n\
+                 <p><img src=\"data:image/png;base64,{}\" alt=\"Dot\" width=\"30\" height=\"20\"> \
+                 Missing</p>\n",
+                base64::encode(png)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_css() {
+        assert_eq!(parse_color("#F0a").as_deref(), Some("FF00AA"));
+        assert_eq!(parse_color("rgb(0 128 255)").as_deref(), Some("0080FF"));
+        assert_eq!(parse_color("Navy").as_deref(), Some("000080"));
+        assert_eq!(parse_color("inherit"), None);
+        assert_eq!(parse_font_size("11pt"), Some(22));
+        assert_eq!(parse_font_size("1.5em"), Some(36));
+        assert_eq!(parse_font_size("large"), Some(27));
+        assert_eq!(parse_font_size("bigger"), None);
+    }
+}
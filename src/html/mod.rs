@@ -0,0 +1,51 @@
+//! HTML conversion
+//!
+//! Converts documents to HTML5. By default only semantic markup is written:
+//! headings, paragraphs, lists, tables, links and emphasis. The faithful
+//! mode keeps the formatting as well, writing styles as CSS classes and
+//! direct formatting as inline styles.
+//!
+//! HTML is converted to documents as well, keeping the same constructs and a
+//! subset of inline CSS. Like in Markdown import, the constructs without a
+//! direct counterpart are mapped to styles, see
+//! [`StyleIds`](crate::convert::StyleIds).
+
+mod export;
+mod import;
+mod parse;
+
+pub use self::{export::*, import::*};
+
+/// Escapes the characters with a meaning in HTML text and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns a CSS class name for a style id.
+pub(crate) fn class_name(style_id: &str) -> String {
+    let mut name: String = style_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    // class names can't start with a digit
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, 's');
+    }
+    name
+}
@@ -0,0 +1,387 @@
+//! A tolerant HTML parser
+//!
+//! Builds a tree of elements and text the way browsers mostly do: end tags
+//! may be omitted where HTML allows it, stray end tags are ignored and the
+//! contents of scripts and style sheets are dropped.
+
+/// A node of an HTML tree
+#[derive(Clone, Debug)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An HTML element, with its lowercase name and attributes
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Returns the value of an attribute.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Elements without content nor end tag.
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements which close an open paragraph.
+const CLOSE_P: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "div",
+    "dl",
+    "fieldset",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Parses an HTML document or fragment.
+pub(crate) fn parse(html: &str) -> Vec<Node> {
+    let mut builder = Builder {
+        stack: vec![Element::default()],
+    };
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let name = tag[..end]
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            builder.end(&name);
+            rest = tag.get(end + 1..).unwrap_or_default();
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let (element, closed, after) = start_tag(&rest[1..]);
+            rest = after;
+
+            let name = element.name.clone();
+            builder.start(element);
+            if closed || VOID.contains(&name.as_str()) {
+                builder.end(&name);
+            } else if matches!(name.as_str(), "script" | "style") {
+                // the content is dropped, up to the end tag
+                let lower = rest.to_ascii_lowercase();
+                let end = lower.find(&format!("</{}", name)).unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+        } else {
+            let end = rest[1..].find('<').map_or(rest.len(), |end| end + 1);
+            builder.text(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    while builder.stack.len() > 1 {
+        builder.close();
+    }
+    builder.stack.pop().unwrap_or_default().children
+}
+
+/// Reads a start tag, after its `<`, and returns the element, whether it's
+/// self-closing and the remaining text.
+fn start_tag(tag: &str) -> (Element, bool, &str) {
+    let name_end = tag
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(tag.len());
+    let mut element = Element {
+        name: tag[..name_end].to_ascii_lowercase(),
+        ..Default::default()
+    };
+    let mut rest = &tag[name_end..];
+
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (element, true, after);
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return (element, false, after);
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        } else if rest.is_empty() {
+            return (element, false, rest);
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let content = &after[1..];
+                    let end = content.find(quote).unwrap_or(content.len());
+                    (&content[..end], content.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = remaining;
+        }
+        element.attrs.push((name, value));
+    }
+}
+
+struct Builder {
+    /// The open elements, the first one being the root.
+    stack: Vec<Element>,
+}
+
+impl Builder {
+    fn is_open(&self, name: &str, scope: &[&str]) -> bool {
+        for element in self.stack.iter().rev() {
+            if element.name == name {
+                return true;
+            }
+            if scope.contains(&element.name.as_str()) {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn start(&mut self, element: Element) {
+        let name = element.name.as_str();
+
+        // end tags which may be omitted
+        if CLOSE_P.contains(&name) && self.is_open("p", &["table", "td", "th", "li", "blockquote"])
+        {
+            self.end("p");
+        }
+        match name {
+            "li" if self.is_open("li", &["ul", "ol"]) => self.end("li"),
+            "td" | "th" => {
+                for cell in ["td", "th"] {
+                    if self.is_open(cell, &["tr", "table"]) {
+                        self.end(cell);
+                    }
+                }
+            }
+            "tr" => {
+                for cell in ["td", "th"] {
+                    if self.is_open(cell, &["table"]) {
+                        self.end(cell);
+                    }
+                }
+                if self.is_open("tr", &["table"]) {
+                    self.end("tr");
+                }
+            }
+            _ => (),
+        }
+
+        self.stack.push(element);
+    }
+
+    /// Closes the innermost open element of the given name, and the
+    /// elements inside it.
+    fn end(&mut self, name: &str) {
+        let Some(index) = self.stack[1..]
+            .iter()
+            .rposition(|element| element.name == name)
+        else {
+            return;
+        };
+        while self.stack.len() > index + 1 {
+            self.close();
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(element) = self.stack.pop() {
+            if let Some(parent) = self.stack.last_mut() {
+                parent.children.push(Node::Element(element));
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let Some(parent) = self.stack.last_mut() else {
+            return;
+        };
+        if let Some(Node::Text(last)) = parent.children.last_mut() {
+            last.push_str(text);
+        } else {
+            parent.children.push(Node::Text(text.to_owned()));
+        }
+    }
+}
+
+/// Replaces character references by their characters.
+///
+/// Unknown references are kept as is.
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest[1..]
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+            .map_or(rest.len(), |end| end + 1);
+        let name = &rest[1..end];
+        let c = if let Some(number) = name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+            u32::from_str_radix(number, 16)
+                .ok()
+                .and_then(char::from_u32)
+        } else if let Some(number) = name.strip_prefix('#') {
+            number.parse().ok().and_then(char::from_u32)
+        } else {
+            named_entity(name)
+        };
+
+        match c {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end..];
+                rest = rest.strip_prefix(';').unwrap_or(rest);
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn named_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "shy" => '\u{ad}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "times" => '×',
+        "divide" => '÷',
+        "middot" => '·',
+        "bull" => '•',
+        "hellip" => '…',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "sect" => '§',
+        "para" => '¶',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes the tree back, without the attributes.
+    fn outline(nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Element(element) => format!(
+                    "<{}>{}</{}>",
+                    element.name,
+                    outline(&element.children),
+                    element.name
+                ),
+                Node::Text(text) => text.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_html() {
+        let nodes = parse(
+            "<!DOCTYPE html><P class=a>one<p>two &amp; &#x41;&copy<br>three\
+             <ul><li>x<li>y</ul><table><tr><td>1<td>2<tr><td>3</table>\
+             <script>if (a < b) {}</script><!-- note --></em>",
+        );
+        assert_eq!(
+            outline(&nodes),
+            "<p>one</p><p>two & A©<br></br>three</p>\
+             <ul><li>x</li><li>y</li></ul>\
+             <table><tr><td>1</td><td>2</td></tr><tr><td>3</td></tr></table>\
+             <script></script>"
+        );
+
+        let Node::Element(p) = &nodes[0] else {
+            unreachable!()
+        };
+        assert_eq!(p.attr("class"), Some("a"));
+    }
+
+    #[test]
+    fn parse_attributes() {
+        let (element, closed, rest) =
+            start_tag(r#"img src="a.png" alt='A &lt; B' width=10 hidden/>after"#);
+        assert_eq!(element.name, "img");
+        assert_eq!(element.attr("src"), Some("a.png"));
+        assert_eq!(element.attr("alt"), Some("A < B"));
+        assert_eq!(element.attr("width"), Some("10"));
+        assert_eq!(element.attr("hidden"), Some(""));
+        assert!(closed);
+        assert_eq!(rest, "after");
+    }
+}
@@ -0,0 +1,1028 @@
+//! LaTeX export
+//!
+//! Converts a document to LaTeX: headings become sectioning commands,
+//! numbered and bulleted paragraphs `enumerate` and `itemize` lists, tables
+//! `tabular` or `longtable` environments, and images `\includegraphics`
+//! commands referencing the extracted media. Footnotes and endnotes both
+//! become `\footnote` commands, LaTeX having no endnotes of its own.
+//!
+//! Styles can be mapped to custom macros, e.g. to write the paragraphs of a
+//! `Quote` style in a `quote` environment.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::ParagraphProperty;
+//! use docx::latex::{LatexMacro, LatexOptions};
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .property(ParagraphProperty::default().style_id("Heading1"))
+//!         .push_text("Costs & benefits"),
+//! );
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .property(ParagraphProperty::default().style_id("Quote"))
+//!         .push_text("Saves 50% of the time"),
+//! );
+//!
+//! let options = LatexOptions::default()
+//!     .standalone(false)
+//!     .style_macro("Quote", LatexMacro::Environment("quote".into()));
+//! assert_eq!(
+//!     docx.to_latex(&options).text,
+//!     "\\section{Costs \\& benefits}\n\n\\begin{quote}\nSaves 50\\% of the time\n\\end{quote}\n\n"
+//! );
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    __setter,
+    convert::{cell_paragraphs, is_on, resolve, InlineWriter, ListWriter, OpenList, TABLE_WIDTH},
+    document::{BodyContent, Break, BreakType, Drawing, Paragraph, RunContent, Table, TableCell},
+    formatting::{CharacterProperty, JustificationVal, UnderlineStyle, VerticalAlignVal},
+    numbering::{ListCounter, ListItem, NumberingFormatVal},
+    odt::{emus_length, twips_length},
+    private_prelude::*,
+    toc::TableOfContents,
+    Docx,
+};
+
+/// A LaTeX macro which the content of a style is written with
+#[derive(Clone, Debug, PartialEq)]
+pub enum LatexMacro<'a> {
+    /// A command taking the content as its argument, e.g. `\textsc{...}`.
+    Command(Cow<'a, str>),
+    /// An environment around the content, e.g. `\begin{quote}...\end{quote}`.
+    ///
+    /// Consecutive paragraphs of a style share one environment.
+    Environment(Cow<'a, str>),
+}
+
+impl LatexMacro<'_> {
+    fn wrap(&self, content: &str) -> String {
+        match self {
+            LatexMacro::Command(name) => format!("\\{}{{{}}}", name, content),
+            LatexMacro::Environment(name) => {
+                format!("\\begin{{{}}}{}\\end{{{}}}", name, content, name)
+            }
+        }
+    }
+}
+
+/// Options of LaTeX export
+#[derive(Clone, Debug)]
+pub struct LatexOptions<'a> {
+    /// Specifies whether to write a complete document with a preamble rather
+    /// than a fragment.
+    pub standalone: bool,
+    /// Specifies the document class of a complete document.
+    ///
+    /// Headings of the first level become chapters with the `book` and
+    /// `report` classes, and sections otherwise.
+    pub document_class: Cow<'a, str>,
+    /// Specifies the folder of the extracted images, relative to the LaTeX
+    /// file.
+    pub image_dir: Cow<'a, str>,
+    /// Specifies whether tables may break across pages, using `longtable`
+    /// rather than `tabular`.
+    pub long_tables: bool,
+    /// Specifies the macros of paragraph and character styles, by style id.
+    ///
+    /// They take precedence over headings being written as sectioning
+    /// commands.
+    pub macros: HashMap<String, LatexMacro<'a>>,
+}
+
+impl Default for LatexOptions<'_> {
+    fn default() -> Self {
+        LatexOptions {
+            standalone: true,
+            document_class: "article".into(),
+            image_dir: "images".into(),
+            long_tables: false,
+            macros: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> LatexOptions<'a> {
+    __setter!(standalone: bool);
+    __setter!(document_class: Cow<'a, str>);
+    __setter!(image_dir: Cow<'a, str>);
+    __setter!(long_tables: bool);
+    __setter!(macros: HashMap<String, LatexMacro<'a>>);
+
+    /// Adds the macro of a style.
+    pub fn style_macro<T: Into<String>>(mut self, style_id: T, latex: LatexMacro<'a>) -> Self {
+        self.macros.insert(style_id.into(), latex);
+        self
+    }
+}
+
+/// A document converted to LaTeX
+#[derive(Clone, Debug, Default)]
+pub struct Latex {
+    /// The LaTeX text.
+    pub text: String,
+    /// The images referenced by the text, by path relative to the LaTeX file.
+    pub images: BTreeMap<String, Bytes>,
+}
+
+impl Latex {
+    /// Writes the LaTeX text to a file, and the images next to it.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, &self.text)?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for (name, data) in &self.images {
+            let image_path = dir.join(name);
+            if let Some(parent) = image_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(image_path, data)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts the document to LaTeX.
+    ///
+    /// Bookmarks become hyperlink targets, which internal links point to.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    /// use docx::formatting::CharacterProperty;
+    /// use docx::latex::LatexOptions;
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .push_text("Costs ")
+    ///         .push(Run::default().property(CharacterProperty::default().bold(true)).push_text("$5_000")),
+    /// );
+    ///
+    /// let latex = docx.to_latex(&LatexOptions::default().standalone(false));
+    /// assert_eq!(latex.text, "Costs \\textbf{\\$5\\_000}\n\n");
+    /// ```
+    pub fn to_latex(&self, options: &LatexOptions) -> Latex {
+        let numbering = Default::default();
+        let mut writer = Writer {
+            docx: self,
+            options,
+            toc: (1..=6).fold(TableOfContents::default(), |toc, level| {
+                toc.heading_style(format!("Heading{}", level), level)
+            }),
+            lists: ListCounter::new(self.numbering.as_ref().unwrap_or(&numbering)),
+            open_lists: Vec::new(),
+            environment: None,
+            anchors: Vec::new(),
+            out: String::new(),
+            images: BTreeMap::new(),
+        };
+
+        writer.blocks(&self.document.body.content);
+        writer.close_lists();
+        writer.close_environment();
+
+        let body = std::mem::take(&mut writer.out);
+        let mut text = String::new();
+
+        if options.standalone {
+            text += &format!("\\documentclass{{{}}}\n", options.document_class);
+            for package in [
+                "[utf8]{inputenc}",
+                "[T1]{fontenc}",
+                "{graphicx}",
+                "{longtable}",
+                "{multirow}",
+                "{enumitem}",
+                "[normalem]{ulem}",
+                "{hyperref}",
+            ] {
+                text += &format!("\\usepackage{}\n", package);
+            }
+
+            let core = self.core.as_ref();
+            let title = core.and_then(|core| core.title.as_deref());
+            if let Some(title) = title {
+                text += &format!("\\title{{{}}}\n", escape(title));
+                let author = core.and_then(|core| core.creator.as_deref());
+                text += &format!("\\author{{{}}}\n", escape(author.unwrap_or_default()));
+                text += "\\date{}\n";
+            }
+            text += "\\begin{document}\n";
+            if title.is_some() {
+                text += "\\maketitle\n";
+            }
+            text += "\n";
+        }
+        text += &body;
+        if options.standalone {
+            text += "\\end{document}\n";
+        }
+
+        Latex {
+            text,
+            images: writer.images,
+        }
+    }
+}
+
+struct Writer<'d, 'o, 'n, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'o LatexOptions<'o>,
+    toc: TableOfContents<'static>,
+    lists: ListCounter<'n, 'a>,
+    /// The lists being written, the innermost last.
+    open_lists: Vec<OpenList>,
+    /// The environment of the paragraph style being written.
+    environment: Option<String>,
+    /// The bookmarks of the paragraph being written.
+    anchors: Vec<String>,
+    out: String,
+    images: BTreeMap<String, Bytes>,
+}
+
+/// Formatting of a piece of inline content
+#[derive(Clone, Default, PartialEq)]
+struct Format<'o> {
+    /// The macro of the character style.
+    latex: Option<&'o LatexMacro<'o>>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    vertical_align: Option<&'static str>,
+}
+
+/// How a table cell is merged with the cells above and below
+enum Merge {
+    None,
+    /// The first cell of a vertical merge, spanning rows.
+    Restart(usize),
+    Continue,
+}
+
+/// A table cell placed in the grid
+struct GridCell<'t, 'a> {
+    column: usize,
+    span: usize,
+    merge: Merge,
+    cell: &'t TableCell<'a>,
+}
+
+impl<'d, 'o, 'n, 'a> Writer<'d, 'o, 'n, 'a> {
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para),
+                BodyContent::Table(table) => {
+                    self.close_lists();
+                    self.close_environment();
+                    self.table(table);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph) {
+        let inline = self.inline(&para.content);
+        let anchors = self.take_anchors();
+
+        let style_id = para
+            .property
+            .style_id
+            .as_ref()
+            .map(|style_id| &*style_id.value);
+        let options = self.options;
+        let latex = style_id.and_then(|style_id| options.macros.get(style_id));
+
+        if let Some(LatexMacro::Environment(name)) = latex {
+            self.close_lists();
+            if self.environment.as_deref() != Some(&**name) {
+                self.close_environment();
+                self.out += &format!("\\begin{{{}}}\n", name);
+                self.environment = Some(name.to_string());
+            } else {
+                self.out += "\n";
+            }
+            self.out += &format!("{}{}\n", anchors, inline);
+            return;
+        }
+        self.close_environment();
+
+        if let Some(latex @ LatexMacro::Command(_)) = latex {
+            self.close_lists();
+            self.out += &format!("{}{}\n\n", latex.wrap(&inline), anchors);
+            return;
+        }
+
+        if let Some(level) = self.toc.heading_level(para, &self.docx.styles) {
+            self.close_lists();
+            let chapters = matches!(&*options.document_class, "book" | "report");
+            let commands = [
+                "chapter",
+                "section",
+                "subsection",
+                "subsubsection",
+                "paragraph",
+                "subparagraph",
+            ];
+            let index = if chapters { level - 1 } else { level };
+            let command = commands[index.min(commands.len() - 1)];
+            self.out += &format!("\\{}{{{}}}{}\n\n", command, inline, anchors);
+            return;
+        }
+
+        if let Some(item) = para
+            .property
+            .numbering
+            .as_ref()
+            .and_then(|numbering| self.lists.next(numbering))
+        {
+            self.list_item(&item, &format!("{}{}", anchors, inline));
+            return;
+        }
+
+        self.close_lists();
+        if inline.is_empty() && anchors.is_empty() {
+            return;
+        }
+        let environment = match para.property.justification.as_ref().map(|jc| &jc.value) {
+            Some(JustificationVal::Center) => Some("center"),
+            Some(JustificationVal::Right) | Some(JustificationVal::End) => Some("flushright"),
+            _ => None,
+        };
+        match environment {
+            Some(name) => {
+                self.out += &format!(
+                    "\\begin{{{}}}\n{}{}\n\\end{{{}}}\n\n",
+                    name, anchors, inline, name
+                )
+            }
+            None => self.out += &format!("{}{}\n\n", anchors, inline),
+        }
+    }
+
+    /// Writes the bookmarks of the paragraph being written as hyperlink
+    /// targets.
+    fn take_anchors(&mut self) -> String {
+        std::mem::take(&mut self.anchors)
+            .iter()
+            .map(|name| format!("\\hypertarget{{{}}}{{}}", escape_url(name)))
+            .collect()
+    }
+
+    fn close_environment(&mut self) {
+        if let Some(name) = self.environment.take() {
+            self.out += &format!("\\end{{{}}}\n\n", name);
+        }
+    }
+
+    fn list_item(&mut self, item: &ListItem, inline: &str) {
+        self.close_environment();
+        self.enter_list(item);
+        self.out += &format!("\\item {}\n", inline);
+    }
+
+    fn table(&mut self, table: &Table) {
+        let rows = grid_rows(table);
+        let columns = rows
+            .iter()
+            .filter_map(|row| row.last())
+            .map(|cell| cell.column + cell.span)
+            .max()
+            .unwrap_or_default();
+        if columns == 0 {
+            return;
+        }
+
+        // the widths of the grid, or an even split of the text width
+        let grid: Vec<usize> = table
+            .grids
+            .first()
+            .map(|grid| grid.columns.iter().map(|column| column.width).collect())
+            .unwrap_or_default();
+        let widths: Vec<usize> = (0..columns)
+            .map(|index| grid.get(index).copied().unwrap_or(TABLE_WIDTH / columns))
+            .collect();
+        let spec = |column: usize, span: usize| {
+            let width: usize = widths[column..column + span].iter().sum();
+            let left = if column == 0 { "|" } else { "" };
+            format!("{}p{{{}}}|", left, twips_length(width as isize))
+        };
+
+        let name = if self.options.long_tables {
+            "longtable"
+        } else {
+            "tabular"
+        };
+        let table_spec: String = (0..columns).map(|column| spec(column, 1)).collect();
+        self.out += &format!("\\begin{{{}}}{{{}}}\n\\hline\n", name, table_spec);
+
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells = Vec::new();
+            let mut column = 0;
+            for cell in row {
+                // cells missing before this one
+                cells.extend((column..cell.column).map(|_| String::new()));
+                column = cell.column + cell.span;
+
+                let content = match cell.merge {
+                    Merge::Continue => String::new(),
+                    Merge::Restart(rows) => format!(
+                        "\\multirow{{{}}}{{*}}{{{}}}",
+                        rows,
+                        self.cell_content(cell.cell, " ")
+                    ),
+                    Merge::None => self.cell_content(cell.cell, " \\par "),
+                };
+                if cell.span > 1 {
+                    cells.push(format!(
+                        "\\multicolumn{{{}}}{{{}}}{{{}}}",
+                        cell.span,
+                        spec(cell.column, cell.span),
+                        content
+                    ));
+                } else {
+                    cells.push(content);
+                }
+            }
+            cells.extend((column..columns).map(|_| String::new()));
+            self.out += &format!("{} \\\\\n", cells.join(" & "));
+
+            // rules below the row, except across cells merged with the next row
+            let continued: Vec<bool> = (0..columns)
+                .map(|column| {
+                    rows.get(index + 1).is_some_and(|next| {
+                        next.iter().any(|cell| {
+                            matches!(cell.merge, Merge::Continue)
+                                && (cell.column..cell.column + cell.span).contains(&column)
+                        })
+                    })
+                })
+                .collect();
+            if continued.iter().all(|continued| !continued) {
+                self.out += "\\hline\n";
+                continue;
+            }
+            let mut column = 0;
+            while column < columns {
+                if continued[column] {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < columns && !continued[column] {
+                    column += 1;
+                }
+                self.out += &format!("\\cline{{{}-{}}}\n", start + 1, column);
+            }
+        }
+
+        self.out += &format!("\\end{{{}}}\n\n", name);
+    }
+
+    /// Converts the paragraphs of a cell, separated by `separator`.
+    fn cell_content(&mut self, cell: &TableCell, separator: &str) -> String {
+        let mut paragraphs = Vec::new();
+        cell_paragraphs(&cell.content, &mut paragraphs);
+        let content: Vec<String> = paragraphs
+            .iter()
+            .map(|para| {
+                let inline = self.inline(&para.content);
+                self.take_anchors() + &inline
+            })
+            .filter(|content| !content.is_empty())
+            .collect();
+        content.join(separator)
+    }
+
+    /// Writes the content of a note as a footnote.
+    fn note(&mut self, content: &[BodyContent]) -> String {
+        // the anchors of the paragraph referencing the note
+        let anchors = std::mem::take(&mut self.anchors);
+
+        let mut paragraphs = Vec::new();
+        body_paragraphs(content, &mut paragraphs);
+        let text: Vec<String> = paragraphs
+            .iter()
+            .map(|para| self.inline(&para.content).trim().to_owned())
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        self.anchors = anchors;
+        format!("\\footnote{{{}}}", text.join(" \\par "))
+    }
+
+    fn image(&mut self, drawing: &Drawing) -> Option<String> {
+        let (name, data) = self.docx.embedded_media(drawing.embed.as_deref()?)?;
+        let file_name = name.rsplit('/').next()?;
+
+        let path = format!(
+            "{}/{}",
+            self.options.image_dir.trim_end_matches('/'),
+            file_name
+        );
+        self.images.insert(path.clone(), data.clone());
+
+        let size = match (drawing.width, drawing.height) {
+            (Some(width), Some(height)) => format!(
+                "[width={},height={}]",
+                emus_length(width),
+                emus_length(height)
+            ),
+            _ => String::new(),
+        };
+        Some(format!("\\includegraphics{}{{{}}}", size, path))
+    }
+}
+
+impl ListWriter for Writer<'_, '_, '_, '_> {
+    fn open_lists(&mut self) -> &mut Vec<OpenList> {
+        &mut self.open_lists
+    }
+
+    fn start_list(&mut self, item: &ListItem) {
+        if item.is_bullet() {
+            self.out += "\\begin{itemize}\n";
+        } else {
+            self.out += &format!("\\begin{{enumerate}}{}\n", enumerate_options(item));
+        }
+    }
+
+    fn end_list(&mut self, list: &OpenList) {
+        let name = if list.ordered { "enumerate" } else { "itemize" };
+        self.out += &format!("\\end{{{}}}\n", name);
+        if self.open_lists.is_empty() {
+            self.out += "\n";
+        }
+    }
+}
+
+impl<'o> InlineWriter for Writer<'_, 'o, '_, '_> {
+    type Format = Format<'o>;
+
+    fn docx(&self) -> &Docx<'_> {
+        self.docx
+    }
+
+    fn format(&self, prop: &CharacterProperty) -> Format<'o> {
+        let styles = &self.docx.styles;
+        let options: &'o LatexOptions<'o> = self.options;
+        let latex = prop
+            .style_id
+            .as_ref()
+            .and_then(|style_id| options.macros.get(&*style_id.value));
+
+        // the formatting of the character style, overridden by the direct
+        // formatting, unless the style is written as a macro
+        let mut effective = prop
+            .style_id
+            .as_ref()
+            .filter(|_| latex.is_none())
+            .and_then(|style_id| styles.get(&style_id.value))
+            .map(|style| resolve(styles, style).0)
+            .unwrap_or_default();
+        effective.merge(prop);
+
+        Format {
+            latex,
+            bold: effective.bold.is_some_and(|bold| is_on(bold.value)),
+            italic: effective
+                .italics
+                .is_some_and(|italics| is_on(italics.value)),
+            underline: effective
+                .underline
+                .as_ref()
+                .is_some_and(|underline| !matches!(underline.val, Some(UnderlineStyle::None))),
+            strike: effective.strike.is_some_and(|strike| is_on(strike.value))
+                || effective
+                    .dstrike
+                    .is_some_and(|dstrike| is_on(dstrike.value)),
+            vertical_align: match effective.vertical_align.map(|align| align.value) {
+                Some(VerticalAlignVal::Superscript) => Some("textsuperscript"),
+                Some(VerticalAlignVal::Subscript) => Some("textsubscript"),
+                _ => None,
+            },
+        }
+    }
+
+    fn run_content(&mut self, content: &RunContent, _: &Format<'o>) -> Option<String> {
+        let latex = match content {
+            RunContent::Text(text) => escape(&text.text),
+            RunContent::Tab(_) => "\\quad{}".to_owned(),
+            RunContent::Break(Break {
+                ty: Some(BreakType::Page),
+            }) => "\\newpage{}".to_owned(),
+            RunContent::Break(_) => "\\newline{}".to_owned(),
+            RunContent::Drawing(drawing) => self.image(drawing)?,
+            RunContent::FootnoteReference(reference) => {
+                let docx = self.docx;
+                let note = docx
+                    .footnotes
+                    .as_ref()
+                    .and_then(|notes| notes.get(&reference.id))?;
+                self.note(&note.content)
+            }
+            RunContent::EndnoteReference(reference) => {
+                let docx = self.docx;
+                let note = docx
+                    .endnotes
+                    .as_ref()
+                    .and_then(|notes| notes.get(&reference.id))?;
+                self.note(&note.content)
+            }
+            _ => return None,
+        };
+        Some(latex)
+    }
+
+    fn formatted(format: &Format<'o>, mut content: String) -> String {
+        // the innermost command first
+        if let Some(command) = format.vertical_align {
+            content = format!("\\{}{{{}}}", command, content);
+        }
+        for (on, command) in [
+            (format.strike, "sout"),
+            (format.underline, "uline"),
+            (format.italic, "emph"),
+            (format.bold, "textbf"),
+        ] {
+            if on {
+                content = format!("\\{}{{{}}}", command, content);
+            }
+        }
+        match format.latex {
+            Some(latex) => latex.wrap(&content),
+            None => content,
+        }
+    }
+
+    fn link(&self, href: &str, text: String) -> String {
+        match href.strip_prefix('#') {
+            Some(anchor) => format!("\\hyperlink{{{}}}{{{}}}", escape_url(anchor), text),
+            None => format!("\\href{{{}}}{{{}}}", escape_url(href), text),
+        }
+    }
+
+    fn bookmark(&mut self, name: &str) -> Option<String> {
+        self.anchors.push(name.to_owned());
+        None
+    }
+}
+
+/// Places the cells of a table in its grid, computing the rows spanned by
+/// vertical merges.
+fn grid_rows<'t, 'a>(table: &'t Table<'a>) -> Vec<Vec<GridCell<'t, 'a>>> {
+    let mut rows: Vec<Vec<GridCell>> = table
+        .iter_rows()
+        .map(|row| {
+            let mut column = 0;
+            row.iter_cells()
+                .map(|cell| {
+                    let span = cell
+                        .property
+                        .grid_span
+                        .as_ref()
+                        .map_or(1, |span| span.value.max(1));
+                    let merge = match &cell.property.vertical_merge {
+                        Some(merge) if merge.is_restart() => Merge::Restart(1),
+                        Some(_) => Merge::Continue,
+                        None => Merge::None,
+                    };
+                    let grid_cell = GridCell {
+                        column,
+                        span,
+                        merge,
+                        cell,
+                    };
+                    column += span;
+                    grid_cell
+                })
+                .collect()
+        })
+        .collect();
+
+    for index in 0..rows.len() {
+        for position in 0..rows[index].len() {
+            if !matches!(rows[index][position].merge, Merge::Restart(_)) {
+                continue;
+            }
+            let column = rows[index][position].column;
+            let spanned = 1 + rows[index + 1..]
+                .iter()
+                .take_while(|row| {
+                    row.iter()
+                        .any(|cell| cell.column == column && matches!(cell.merge, Merge::Continue))
+                })
+                .count();
+            rows[index][position].merge = Merge::Restart(spanned);
+        }
+    }
+
+    rows
+}
+
+fn body_paragraphs<'p, 'a>(
+    content: &'p [BodyContent<'a>],
+    paragraphs: &mut Vec<&'p Paragraph<'a>>,
+) {
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => paragraphs.push(para),
+            BodyContent::Sdt(sdt) => body_paragraphs(&sdt.content.content, paragraphs),
+            BodyContent::Table(_) => (),
+        }
+    }
+}
+
+/// Returns the options of an `enumerate` environment, with the label and the
+/// start number of `enumitem`.
+fn enumerate_options(item: &ListItem) -> String {
+    let counter = match item.format {
+        NumberingFormatVal::LowerLetter => "\\alph*",
+        NumberingFormatVal::UpperLetter => "\\Alph*",
+        NumberingFormatVal::LowerRoman => "\\roman*",
+        NumberingFormatVal::UpperRoman => "\\Roman*",
+        _ => "\\arabic*",
+    };
+    let delimiter = if item.label.ends_with(')') { ")" } else { "." };
+
+    let mut options = Vec::new();
+    if counter != "\\arabic*" || delimiter != "." {
+        options.push(format!("label={}{}", counter, delimiter));
+    }
+    if item.number != 1 {
+        options.push(format!("start={}", item.number));
+    }
+    if options.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", options.join(", "))
+    }
+}
+
+/// Escapes the characters of text which LaTeX treats specially.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\u{a0}' => escaped.push('~'),
+            '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters of URLs and anchors which `hyperref` requires.
+fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        if matches!(c, '#' | '%' | '{' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Core,
+        document::{BookmarkStart, FootnoteReference, Hyperlink, Run, TableGrid, TableRow},
+        footnotes::{Footnote, Footnotes},
+        formatting::{NumberingProperty, ParagraphProperty, TableCellProperty, VerticalMergeVal},
+        numbering::{NumberingFormatVal as Format, Numberings},
+        rels::Relationships,
+        schema::SCHEMA_HYPERLINK,
+    };
+
+    #[test]
+    fn to_latex() {
+        let mut docx = Docx::default();
+        let mut numbering = Numberings::default();
+        let bullets = numbering.push_list(Format::Bullet, 1);
+        let numbers = numbering.push_list(Format::LowerLetter, 1);
+        docx.numbering = Some(numbering);
+
+        let mut footnotes = Footnotes::default();
+        footnotes.push(Footnote::new("1").push(Paragraph::default().push_text(" In 2024.")));
+        docx.footnotes = Some(footnotes);
+
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Heading2"))
+                .push(BookmarkStart::default().id("0").name("intro"))
+                .push_text("Intro"),
+        );
+        docx.document.push(
+            Paragraph::default()
+                .push_text("See ")
+                .push(
+                    Hyperlink::default()
+                        .anchor("intro")
+                        .content(Run::default().push_text("above")),
+                )
+                .push(Run::default().push(FootnoteReference::from("1"))),
+        );
+        docx.document.push(
+            Paragraph::default()
+                .property(
+                    ParagraphProperty::default().numbering(NumberingProperty::from((bullets, 0))),
+                )
+                .push_text("one"),
+        );
+        docx.document.push(
+            Paragraph::default()
+                .property(
+                    ParagraphProperty::default().numbering(NumberingProperty::from((numbers, 1))),
+                )
+                .push_text("two"),
+        );
+        docx.document.push(
+            Table::default()
+                .push_grid(TableGrid::default().push_column(1440).push_column(2880))
+                .push_row(
+                    TableRow::default()
+                        .push_cell(
+                            TableCell::from(Paragraph::default().push_text("a")).property(
+                                TableCellProperty::default()
+                                    .vertical_merge(VerticalMergeVal::Restart),
+                            ),
+                        )
+                        .push_cell(Paragraph::default().push_text("b")),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(TableCell::from(Paragraph::default()).property(
+                            TableCellProperty::default().vertical_merge(VerticalMergeVal::Continue),
+                        ))
+                        .push_cell(Paragraph::default().push_text("c")),
+                )
+                .push_row(
+                    TableRow::default().push_cell(
+                        TableCell::from(Paragraph::default().push_text("d"))
+                            .property(TableCellProperty::default().grid_span(2usize)),
+                    ),
+                ),
+        );
+
+        let latex = docx.to_latex(&LatexOptions::default().standalone(false));
+        assert_eq!(
+            latex.text,
+            "\\subsection{Intro}\\hypertarget{intro}{}\n\n\
+             See \\hyperlink{intro}{above}\\footnote{In 2024.}\n\n\
+             \\begin{itemize}\n\\item one\n\\begin{enumerate}[label=\\alph*.]\n\\item two\n\\end{enumerate}\n\\end{itemize}\n\n\
+             \\begin{tabular}{|p{1in}|p{2in}|}\n\\hline\n\
+             \\multirow{2}{*}{a} & b \\\\\n\\cline{2-2}\n \
+             & c \\\\\n\\hline\n\
+             \\multicolumn{2}{|p{3in}|}{d} \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(
+            escape("a\\b ~c^ & 50% $5 #1 x_y {z}\u{a0}end\nline"),
+            "a\\textbackslash{}b \\textasciitilde{}c\\textasciicircum{} \\& 50\\% \\$5 \\#1 \
+             x\\_y \\{z\\}~end line"
+        );
+        assert_eq!(
+            escape_url("https://example.com/a%20b#top"),
+            "https://example.com/a\\%20b\\#top"
+        );
+
+        let mut rels = Relationships::default();
+        let link = rels.add_external_rel(SCHEMA_HYPERLINK, "https://example.com/?q=100%#end");
+        let mut docx = Docx {
+            document_rels: Some(rels),
+            core: Some(Core {
+                title: Some("R&D {draft}".into()),
+                creator: Some("A_B".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    Hyperlink::default()
+                        .id(link)
+                        .content(Run::default().push_text("50% off")),
+                )
+                .push(
+                    Hyperlink::default()
+                        .anchor("part_2")
+                        .content(Run::default().push_text(" & more")),
+                ),
+        );
+
+        let latex = docx.to_latex(&LatexOptions::default());
+        assert!(latex
+            .text
+            .contains("\\title{R\\&D \\{draft\\}}\n\\author{A\\_B}\n"));
+        assert!(latex.text.contains(
+            "\\href{https://example.com/?q=100\\%\\#end}{50\\% off}\
+             \\hyperlink{part_2}{ \\& more}\n\n"
+        ));
+    }
+
+    #[test]
+    fn merged_cells() {
+        let cell = |text: &'static str, property: TableCellProperty| {
+            TableCell::from(Paragraph::default().push_text(text)).property(property)
+        };
+        let merged = |merge: VerticalMergeVal| {
+            TableCellProperty::default()
+                .grid_span(2usize)
+                .vertical_merge(merge)
+        };
+        let table = Table::default()
+            .push_grid(
+                TableGrid::default()
+                    .push_column(1440)
+                    .push_column(1440)
+                    .push_column(1440),
+            )
+            .push_row(
+                TableRow::default()
+                    .push_cell(cell("a", merged(VerticalMergeVal::Restart)))
+                    .push_cell(Paragraph::default().push_text("b")),
+            )
+            .push_row(
+                TableRow::default()
+                    .push_cell(cell("", merged(VerticalMergeVal::Continue)))
+                    .push_cell(Paragraph::default().push_text("c")),
+            )
+            .push_row(TableRow::default().push_cell(TableCell {
+                property: TableCellProperty::default(),
+                content: vec![
+                    Paragraph::default().push_text("d").into(),
+                    Paragraph::default().push_text("e").into(),
+                ],
+            }));
+        let mut docx = Docx::default();
+        docx.document.push(table);
+
+        let latex = docx.to_latex(&LatexOptions::default().standalone(false).long_tables(true));
+        assert_eq!(
+            latex.text,
+            "\\begin{longtable}{|p{1in}|p{1in}|p{1in}|}\n\\hline\n\
+             \\multicolumn{2}{|p{2in}|}{\\multirow{2}{*}{a}} & b \\\\\n\\cline{3-3}\n\
+             \\multicolumn{2}{|p{2in}|}{} & c \\\\\n\\hline\n\
+             d \\par e &  &  \\\\\n\\hline\n\\end{longtable}\n\n"
+        );
+    }
+
+    #[test]
+    fn image_references() {
+        let mut docx = Docx::default();
+        let (embed, _) = docx.embed_media(Bytes::from_static(b"png data"), "png");
+        docx.document.push(
+            Paragraph::default()
+                .push(Run::default().push(Drawing::picture(embed, 914400, 457200)))
+                .push(Run::default().push(Drawing::picture("rId404", 914400, 914400))),
+        );
+
+        let latex = docx.to_latex(
+            &LatexOptions::default()
+                .standalone(false)
+                .image_dir("figures/"),
+        );
+        // the image of a missing relationship is left out
+        assert_eq!(
+            latex.text,
+            "\\includegraphics[width=1in,height=0.5in]{figures/image1.png}\n\n"
+        );
+        assert_eq!(
+            latex.images.get("figures/image1.png"),
+            Some(&Bytes::from_static(b"png data"))
+        );
+        assert_eq!(latex.images.len(), 1);
+    }
+}
@@ -66,6 +66,15 @@
 //!
 //! [`DocxFile::from_reader`]: struct.DocxFile.html#method.from_reader
 //!
+//! # Features
+//!
+//! These features are enabled by default:
+//!
+//! - `markdown`: the `markdown` module, with `pulldown-cmark`.
+//! - `pandoc`: the `pandoc` module, which implies `serde`.
+//! - `regex`: regular expression patterns of [`find`] and [`query`].
+//! - `serde`: the JSON serialization of diffs, form values and table data.
+//!
 //! # Similar Projects
 //!
 //! [`bokuweb/docx-rs`]: A .docx file writer with Rust/WebAssembly.
@@ -80,18 +89,49 @@
 mod macros;
 
 pub mod app;
+mod base64;
 pub mod comments;
+pub mod compare;
+pub mod content_control;
 pub mod content_type;
+pub mod convert;
 pub mod core;
+mod csv;
+pub mod custom_xml;
+mod date_format;
+pub mod diff;
 pub mod document;
 mod docx;
+pub mod epub;
 mod error;
+pub mod find;
 pub mod font_table;
+pub mod footnotes;
+pub mod form;
 pub mod formatting;
+pub mod header_footer;
+pub mod html;
+pub mod latex;
+pub mod mail_merge;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 pub mod numbering;
+pub mod odt;
+#[cfg(feature = "pandoc")]
+pub mod pandoc;
+pub mod plain_text;
+pub mod query;
+pub mod range;
 pub mod rels;
+pub mod revision;
 mod schema;
+pub mod settings;
 pub mod styles;
+pub mod table_data;
+pub mod template;
+pub mod toc;
+pub mod visit;
+mod xml_tree;
 
 pub use crate::docx::{Docx, DocxFile};
 pub use crate::error::{DocxError, DocxResult};
@@ -0,0 +1,1046 @@
+//! Mail merge
+//!
+//! Fills the `MERGEFIELD` fields of a main document with the values of data
+//! records, either producing one document per record or a single document
+//! where every record starts a new section on a new page. Both complex fields
+//! and simple fields are merged, and their field results are replaced with
+//! plain runs keeping the formatting of the previous result.
+//!
+//! Field switches are supported:
+//!
+//! - `\@ "dd MMMM yyyy"` formats an ISO 8601 date, e.g. `2020-01-02`.
+//! - `\# "#,##0.00"` formats a number.
+//! - `\* Upper`, `\* Lower`, `\* Caps` and `\* FirstCap` change the case.
+//! - `\b "text"` and `\f "text"` insert text before and after a non-empty value.
+//!
+//! Names of months and days, and separators of numbers, are taken from the
+//! [`Locale`] of the merge. A `MERGEREC` field is replaced with the number of
+//! the record, and other fields are kept as they are.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::mail_merge::{MailMerge, Record};
+//! use docx::Docx;
+//!
+//! let mut template = Docx::default();
+//! template.document.push(
+//!     Paragraph::default()
+//!         .push_text("Dear ")
+//!         .push(SimpleField::new(r#" MERGEFIELD Name \* Upper "#)),
+//! );
+//!
+//! let records = docx::mail_merge::read_csv("Name,City\njohn,Paris\njane,Rome\n".as_bytes(), ',')
+//!     .unwrap();
+//!
+//! let merge = MailMerge::new(template);
+//! let letters: Vec<Docx> = merge.merge_each(&records).collect();
+//! assert_eq!(letters.len(), 2);
+//!
+//! let all = merge.merge_all(&records);
+//! // two letters separated by a section break
+//! assert_eq!(all.document.body.content.len(), 3);
+//! ```
+
+use bytes::Bytes;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    mem,
+};
+
+use crate::{
+    convert::Image,
+    csv,
+    date_format::{self, DateTime},
+    document::{
+        BodyContent, Break, Drawing, FieldCharType, Paragraph, ParagraphContent, Run, RunContent,
+        TableCellContent, TableContent, TableRowContent, Text, TextSpace,
+    },
+    error::DocxResult,
+    formatting::{CharacterProperty, ParagraphProperty, SectionType},
+    rels::Relationships,
+    schema::SCHEMA_IMAGE,
+    Docx,
+};
+
+/// A data record, mapping field names to values
+pub type Record = BTreeMap<String, MergeValue>;
+
+/// A value merged into a `MERGEFIELD`
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeValue {
+    Text(String),
+    /// An image inserted as an inline picture
+    Image(Image),
+}
+
+impl From<&str> for MergeValue {
+    fn from(text: &str) -> Self {
+        MergeValue::Text(text.to_owned())
+    }
+}
+
+impl From<String> for MergeValue {
+    fn from(text: String) -> Self {
+        MergeValue::Text(text)
+    }
+}
+
+impl From<Image> for MergeValue {
+    fn from(image: Image) -> Self {
+        MergeValue::Image(image)
+    }
+}
+
+/// Names and separators used to format dates and numbers
+#[derive(Clone, Debug, PartialEq)]
+pub struct Locale {
+    /// Names of months, starting with January
+    pub months: [&'static str; 12],
+    /// Names of days of the week, starting with Sunday
+    pub weekdays: [&'static str; 7],
+    pub decimal_separator: char,
+    pub group_separator: char,
+}
+
+impl Locale {
+    /// English (United States)
+    pub fn en_us() -> Self {
+        Locale {
+            months: date_format::MONTHS,
+            weekdays: date_format::WEEKDAYS,
+            decimal_separator: '.',
+            group_separator: ',',
+        }
+    }
+
+    /// German (Germany)
+    pub fn de_de() -> Self {
+        Locale {
+            months: [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            weekdays: [
+                "Sonntag",
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+            ],
+            decimal_separator: ',',
+            group_separator: '.',
+        }
+    }
+
+    /// French (France)
+    pub fn fr_fr() -> Self {
+        Locale {
+            months: [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            weekdays: [
+                "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+            ],
+            decimal_separator: ',',
+            group_separator: '\u{a0}',
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::en_us()
+    }
+}
+
+/// Reads records from delimiter-separated values, the first row holding
+/// the field names.
+pub fn read_csv<R: Read>(mut reader: R, delimiter: char) -> DocxResult<Vec<Record>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut rows = csv::parse(&text, delimiter).into_iter();
+    let names = rows.next().unwrap_or_default();
+
+    Ok(rows
+        .map(|row| {
+            let mut values = row.into_iter();
+            names
+                .iter()
+                .map(|name| {
+                    let value = values.next().unwrap_or_default();
+                    (name.trim().to_owned(), MergeValue::Text(value))
+                })
+                .filter(|(name, _)| !name.is_empty())
+                .collect()
+        })
+        .collect())
+}
+
+/// A mail merge of a main document
+#[derive(Clone, Debug)]
+pub struct MailMerge<'a> {
+    template: Docx<'a>,
+    locale: Locale,
+}
+
+impl<'a> MailMerge<'a> {
+    pub fn new(template: Docx<'a>) -> Self {
+        MailMerge {
+            template,
+            locale: Locale::default(),
+        }
+    }
+
+    /// Sets the locale used to format dates and numbers.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Merges a record into a new document.
+    pub fn merge(&self, record: &Record) -> Docx<'a> {
+        self.merge_record(record, 1)
+    }
+
+    /// Merges every record into its own document.
+    pub fn merge_each<'s, I, R>(&'s self, records: I) -> impl Iterator<Item = Docx<'a>> + 's
+    where
+        I: IntoIterator<Item = R>,
+        I::IntoIter: 's,
+        R: Borrow<Record>,
+    {
+        records
+            .into_iter()
+            .enumerate()
+            .map(move |(index, record)| self.merge_record(record.borrow(), index + 1))
+    }
+
+    /// Merges all records into one document, every record starting a new
+    /// section on a new page. The sections keep the page setup, headers and
+    /// footers of the last section of the template.
+    ///
+    /// Headers and footers are merged with the first record.
+    pub fn merge_all<I, R>(&self, records: I) -> Docx<'a>
+    where
+        I: IntoIterator<Item = R>,
+        R: Borrow<Record>,
+    {
+        let mut docx = self.output();
+        let template = mem::take(&mut docx.document.body.content);
+        let mut pictures = Pictures::new(&docx);
+
+        for (index, record) in records.into_iter().enumerate() {
+            let record = record.borrow();
+
+            if index == 0 {
+                merge_headers_footers(&mut docx, record, &self.locale);
+            } else {
+                let section = docx
+                    .document
+                    .body
+                    .section
+                    .clone()
+                    .unwrap_or_default()
+                    .ty(SectionType::NextPage);
+                let property = ParagraphProperty::default().section(section);
+                docx.document.push(Paragraph::default().property(property));
+            }
+
+            let mut content = template.clone();
+            let mut merger = Merger {
+                record,
+                number: index + 1,
+                locale: &self.locale,
+                pictures: Some(&mut pictures),
+                media: &mut docx.media,
+                rels: docx.document_rels.get_or_insert_with(Default::default),
+            };
+            merger.blocks(&mut content);
+            docx.document.body.content.extend(content);
+        }
+
+        docx
+    }
+
+    fn merge_record(&self, record: &Record, number: usize) -> Docx<'a> {
+        let mut docx = self.output();
+        let mut pictures = Pictures::new(&docx);
+
+        merge_headers_footers(&mut docx, record, &self.locale);
+
+        let mut merger = Merger {
+            record,
+            number,
+            locale: &self.locale,
+            pictures: Some(&mut pictures),
+            media: &mut docx.media,
+            rels: docx.document_rels.get_or_insert_with(Default::default),
+        };
+        merger.blocks(&mut docx.document.body.content);
+
+        docx
+    }
+
+    /// Returns a copy of the main document, which isn't connected to the
+    /// data source anymore.
+    fn output(&self) -> Docx<'a> {
+        let mut docx = self.template.clone();
+        if let Some(settings) = &mut docx.settings {
+            settings.mail_merge = None;
+        }
+        docx
+    }
+}
+
+/// Merges the headers and footers, without pictures as their relationships
+/// aren't kept.
+fn merge_headers_footers<'a>(docx: &mut Docx<'a>, record: &Record, locale: &Locale) {
+    let mut media = HashMap::new();
+    let mut rels = Relationships::default();
+    let mut merger = Merger {
+        record,
+        number: 1,
+        locale,
+        pictures: None,
+        media: &mut media,
+        rels: &mut rels,
+    };
+
+    for header in docx.headers.values_mut() {
+        merger.blocks(&mut header.content);
+    }
+    for footer in docx.footers.values_mut() {
+        merger.blocks(&mut footer.content);
+    }
+}
+
+/// Allocates names of media files and ids of drawings
+struct Pictures {
+    next_media: usize,
+    next_id: usize,
+}
+
+impl Pictures {
+    fn new(docx: &Docx) -> Self {
+        let mut next_id = 1;
+        visit_runs(&docx.document.body.content, &mut |run| {
+            for content in &run.content {
+                if let RunContent::Drawing(Drawing { id: Some(id), .. }) = content {
+                    next_id = next_id.max(id + 1);
+                }
+            }
+        });
+
+        Pictures {
+            next_media: docx.media.len() + 1,
+            next_id,
+        }
+    }
+}
+
+struct Merger<'m, 'a> {
+    record: &'m Record,
+    number: usize,
+    locale: &'m Locale,
+    pictures: Option<&'m mut Pictures>,
+    media: &'m mut HashMap<String, Bytes>,
+    rels: &'m mut Relationships<'a>,
+}
+
+/// A complex field being read
+struct Field<'a> {
+    depth: usize,
+    separated: bool,
+    nested: bool,
+    instr: String,
+    /// The properties of the first run of the field result, or of the field begin
+    property: CharacterProperty<'a>,
+    has_result: bool,
+    /// The original content of the field, kept unless it's merged
+    original: Vec<ParagraphContent<'a>>,
+}
+
+impl<'m, 'a> Merger<'m, 'a> {
+    fn blocks(&mut self, content: &mut [BodyContent<'a>]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(&mut para.content),
+                BodyContent::Table(table) => self.rows(&mut table.rows),
+                BodyContent::Sdt(sdt) => self.blocks(&mut sdt.content.content),
+            }
+        }
+    }
+
+    fn rows(&mut self, content: &mut [TableContent<'a>]) {
+        for content in content {
+            match content {
+                TableContent::Row(row) => self.cells(&mut row.cells),
+                TableContent::Sdt(sdt) => self.rows(&mut sdt.content.content),
+            }
+        }
+    }
+
+    fn cells(&mut self, content: &mut [TableRowContent<'a>]) {
+        for content in content {
+            match content {
+                TableRowContent::Cell(cell) => {
+                    for content in &mut cell.content {
+                        match content {
+                            TableCellContent::Paragraph(para) => self.paragraph(&mut para.content),
+                            TableCellContent::Sdt(sdt) => self.blocks(&mut sdt.content.content),
+                            TableCellContent::Table(table) => self.rows(&mut table.rows),
+                        }
+                    }
+                }
+                TableRowContent::Sdt(sdt) => self.cells(&mut sdt.content.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, content: &mut Vec<ParagraphContent<'a>>) {
+        let mut output = Vec::with_capacity(content.len());
+        let mut field: Option<Field<'a>> = None;
+
+        for content in content.drain(..) {
+            match content {
+                ParagraphContent::Run(run) => self.run(run, &mut output, &mut field),
+                other => match &mut field {
+                    Some(field) => field.original.push(other),
+                    None => self.container(other, &mut output),
+                },
+            }
+        }
+
+        if let Some(field) = field {
+            output.extend(field.original);
+        }
+
+        *content = output;
+    }
+
+    fn container(
+        &mut self,
+        mut content: ParagraphContent<'a>,
+        output: &mut Vec<ParagraphContent<'a>>,
+    ) {
+        match &mut content {
+            ParagraphContent::SimpleField(field) => {
+                let property = first_run_property(&field.content);
+                match self.evaluate(&field.instr) {
+                    Some(result) => {
+                        if !result.is_empty() {
+                            output.push(ParagraphContent::Run(Run {
+                                property,
+                                content: result,
+                            }));
+                        }
+                        return;
+                    }
+                    None => self.paragraph(&mut field.content),
+                }
+            }
+            ParagraphContent::SmartTag(tag) => self.paragraph(&mut tag.content),
+            ParagraphContent::Sdt(sdt) => self.paragraph(&mut sdt.content.content),
+            ParagraphContent::Insertion(ins) => self.paragraph(&mut ins.content),
+            ParagraphContent::MoveTo(to) => self.paragraph(&mut to.content),
+            _ => (),
+        }
+        output.push(content);
+    }
+
+    fn run(
+        &mut self,
+        run: Run<'a>,
+        output: &mut Vec<ParagraphContent<'a>>,
+        field: &mut Option<Field<'a>>,
+    ) {
+        let Run { property, content } = run;
+        let mut segment = Vec::new();
+
+        macro_rules! flush {
+            ($target:expr) => {
+                if !segment.is_empty() {
+                    $target.push(ParagraphContent::Run(Run {
+                        property: property.clone(),
+                        content: mem::take(&mut segment),
+                    }));
+                }
+            };
+        }
+
+        for content in content {
+            let current = match field {
+                Some(current) => current,
+                None => {
+                    if matches!(&content, RunContent::FieldChar(c) if c.ty == FieldCharType::Begin)
+                    {
+                        flush!(output);
+                        *field = Some(Field {
+                            depth: 0,
+                            separated: false,
+                            nested: false,
+                            instr: String::new(),
+                            property: property.clone(),
+                            has_result: false,
+                            original: Vec::new(),
+                        });
+                    } else {
+                        segment.push(content);
+                        continue;
+                    }
+                    field.as_mut().unwrap()
+                }
+            };
+
+            match &content {
+                RunContent::FieldChar(c) => match c.ty {
+                    FieldCharType::Begin => {
+                        current.nested |= current.depth > 0;
+                        current.depth += 1;
+                    }
+                    FieldCharType::Separate => current.separated |= current.depth == 1,
+                    FieldCharType::End => current.depth -= 1,
+                },
+                RunContent::InstrText(instr) if current.depth == 1 && !current.separated => {
+                    current.instr.push_str(&instr.text);
+                }
+                RunContent::Text(_) if current.separated && !current.has_result => {
+                    current.property = property.clone();
+                    current.has_result = true;
+                }
+                _ => (),
+            }
+            segment.push(content);
+
+            if current.depth == 0 {
+                flush!(current.original);
+                if let Some(finished) = field.take() {
+                    self.finish(finished, output);
+                }
+            }
+        }
+
+        match field {
+            Some(field) => flush!(field.original),
+            None => flush!(output),
+        }
+    }
+
+    fn finish(&mut self, field: Field<'a>, output: &mut Vec<ParagraphContent<'a>>) {
+        let result = if field.nested {
+            None
+        } else {
+            self.evaluate(&field.instr)
+        };
+
+        match result {
+            Some(result) if result.is_empty() => (),
+            Some(result) => output.push(ParagraphContent::Run(Run {
+                property: field.property,
+                content: result,
+            })),
+            None => output.extend(field.original),
+        }
+    }
+
+    /// Returns the result of a merge field, or `None` for other fields.
+    fn evaluate(&mut self, instr: &str) -> Option<Vec<RunContent<'a>>> {
+        let instruction = Instruction::parse(instr);
+
+        if instruction.ty.eq_ignore_ascii_case("MERGEREC") {
+            return Some(text_content(self.number.to_string()));
+        }
+        if !instruction.ty.eq_ignore_ascii_case("MERGEFIELD") {
+            return None;
+        }
+
+        let name = instruction.args.first().map(String::as_str).unwrap_or("");
+        let value = self.record.get(name).or_else(|| {
+            self.record
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        });
+
+        match value {
+            Some(MergeValue::Text(text)) => {
+                Some(text_content(instruction.format(text, self.locale)))
+            }
+            Some(MergeValue::Image(image)) => Some(self.picture(image)),
+            None => Some(Vec::new()),
+        }
+    }
+
+    fn picture(&mut self, image: &Image) -> Vec<RunContent<'a>> {
+        let pictures = match &mut self.pictures {
+            Some(pictures) => pictures,
+            None => return Vec::new(),
+        };
+
+        let extension = image.extension.trim_start_matches('.').to_ascii_lowercase();
+        let name = loop {
+            let name = format!("media/merge{}.{}", pictures.next_media, extension);
+            pictures.next_media += 1;
+            if !self.media.contains_key(&format!("word/{}", name)) {
+                break name;
+            }
+        };
+        self.media
+            .insert(format!("word/{}", name), image.data.clone());
+
+        let embed = self.rels.add_rel(SCHEMA_IMAGE, name);
+        let id = pictures.next_id;
+        pictures.next_id += 1;
+
+        let drawing = Drawing::picture(embed, image.width, image.height)
+            .id(id)
+            .name(format!("Picture {}", id));
+        vec![RunContent::Drawing(drawing)]
+    }
+}
+
+/// The instructions of a field, e.g. `MERGEFIELD Name \* Upper`
+struct Instruction {
+    ty: String,
+    args: Vec<String>,
+    switches: Vec<(String, Option<String>)>,
+}
+
+impl Instruction {
+    fn parse(instr: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = instr.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c == '"' {
+                chars.next();
+                let mut token = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' if chars.peek() == Some(&'"') => token.push('"'),
+                        '"' => break,
+                        c => token.push(c),
+                    }
+                }
+                tokens.push((token, true));
+            } else {
+                let mut token = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push((token, false));
+            }
+        }
+
+        let mut tokens = tokens.into_iter().peekable();
+        let ty = tokens.next().map(|(token, _)| token).unwrap_or_default();
+        let mut args = Vec::new();
+        let mut switches = Vec::new();
+
+        while let Some((token, quoted)) = tokens.next() {
+            match token.strip_prefix('\\') {
+                Some(switch) if !quoted => {
+                    let takes_argument = matches!(switch, "@" | "#" | "*" | "b" | "f");
+                    let argument = if takes_argument {
+                        tokens.next().map(|(token, _)| token)
+                    } else {
+                        None
+                    };
+                    switches.push((switch.to_owned(), argument));
+                }
+                _ => args.push(token),
+            }
+        }
+
+        Instruction { ty, args, switches }
+    }
+
+    fn format(&self, value: &str, locale: &Locale) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+
+        let mut result = value.to_owned();
+        let mut before = "";
+        let mut after = "";
+
+        for (switch, argument) in &self.switches {
+            let argument = argument.as_deref().unwrap_or_default();
+            match switch.as_str() {
+                "@" => {
+                    if let Some(date) = DateTime::parse(value) {
+                        result = date.format_with_names(argument, &locale.months, &locale.weekdays);
+                    }
+                }
+                "#" => {
+                    if let Ok(number) = value.trim().parse::<f64>() {
+                        result = format_number(number, argument, locale);
+                    }
+                }
+                "*" => result = format_case(&result, argument),
+                "b" => before = argument,
+                "f" => after = argument,
+                _ => (),
+            }
+        }
+
+        format!("{}{}{}", before, result, after)
+    }
+}
+
+fn format_case(text: &str, format: &str) -> String {
+    let mut chars = text.chars();
+    match format.to_ascii_lowercase().as_str() {
+        "upper" => text.to_uppercase(),
+        "lower" => text.to_lowercase(),
+        "firstcap" => match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        },
+        "caps" => {
+            let mut result = String::with_capacity(text.len());
+            let mut start = true;
+            for c in text.chars() {
+                if start {
+                    result.extend(c.to_uppercase());
+                } else {
+                    result.push(c);
+                }
+                start = c.is_whitespace();
+            }
+            result
+        }
+        _ => text.to_owned(),
+    }
+}
+
+/// Formats a number with a numeric picture, e.g. `#,##0.00`.
+///
+/// A second section separated by `;` is used for negative numbers.
+fn format_number(number: f64, picture: &str, locale: &Locale) -> String {
+    let mut sections = picture.split(';');
+    let positive = sections.next().unwrap_or_default();
+    let (picture, sign) = match sections.next() {
+        Some(negative) if number < 0.0 => (negative, ""),
+        _ if number < 0.0 => (positive, "-"),
+        _ => (positive, ""),
+    };
+
+    let is_digit = |c: char| c == '0' || c == '#';
+    let (start, end) = match (picture.find(is_digit), picture.rfind(is_digit)) {
+        (Some(start), Some(end)) => (start, end + 1),
+        _ => return picture.replace('\'', ""),
+    };
+    let prefix = picture[..start].replace('\'', "");
+    let suffix = picture[end..].replace('\'', "");
+    let digits = &picture[start..end];
+
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (digits, ""),
+    };
+    let min_integer = integer.chars().filter(|&c| c == '0').count();
+    let min_fraction = fraction.chars().filter(|&c| c == '0').count();
+    let max_fraction = fraction.chars().filter(|&c| is_digit(c)).count();
+    let grouped = integer.contains(',');
+
+    let formatted = format!("{:.*}", max_fraction, number.abs());
+    let (int_digits, mut frac_digits) = match formatted.split_once('.') {
+        Some((int_digits, frac_digits)) => (int_digits.to_owned(), frac_digits.to_owned()),
+        None => (formatted, String::new()),
+    };
+    while frac_digits.len() > min_fraction && frac_digits.ends_with('0') {
+        frac_digits.pop();
+    }
+
+    let int_digits = int_digits.trim_start_matches('0');
+    let int_digits = format!("{:0>width$}", int_digits, width = min_integer);
+
+    let mut result = String::new();
+    for (index, c) in int_digits.chars().enumerate() {
+        if grouped && index > 0 && (int_digits.len() - index) % 3 == 0 {
+            result.push(locale.group_separator);
+        }
+        result.push(c);
+    }
+    if !frac_digits.is_empty() {
+        result.push(locale.decimal_separator);
+        result.push_str(&frac_digits);
+    }
+
+    format!("{}{}{}{}", sign, prefix, result, suffix)
+}
+
+/// Turns text into run content, line breaks becoming breaks.
+fn text_content<'a>(text: String) -> Vec<RunContent<'a>> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut content = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            content.push(RunContent::Break(Break::from(None)));
+        }
+        if !line.is_empty() {
+            content.push(RunContent::Text(Text::from((
+                line.to_owned(),
+                TextSpace::Preserve,
+            ))));
+        }
+    }
+    content
+}
+
+fn first_run_property<'a>(content: &[ParagraphContent<'a>]) -> CharacterProperty<'a> {
+    content
+        .iter()
+        .find_map(|content| match content {
+            ParagraphContent::Run(run) => Some(run.property.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Calls `f` on the runs of paragraphs and tables.
+fn visit_runs<'a>(content: &[BodyContent<'a>], f: &mut dyn FnMut(&Run<'a>)) {
+    fn paragraph<'a>(content: &[ParagraphContent<'a>], f: &mut dyn FnMut(&Run<'a>)) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => f(run),
+                ParagraphContent::Link(link) => link.content.iter().for_each(&mut *f),
+                _ => (),
+            }
+        }
+    }
+
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => paragraph(&para.content, f),
+            BodyContent::Table(table) => {
+                for row in &table.rows {
+                    if let TableContent::Row(row) = row {
+                        for cell in &row.cells {
+                            if let TableRowContent::Cell(cell) = cell {
+                                for content in &cell.content {
+                                    if let TableCellContent::Paragraph(para) = content {
+                                        paragraph(&para.content, f);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            BodyContent::Sdt(sdt) => visit_runs(&sdt.content.content, f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{FieldChar, InstrText, SimpleField};
+    use crate::formatting::{HeaderReference, PageMargin, SectionProperty};
+
+    fn record(values: &[(&str, &str)]) -> Record {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), MergeValue::from(*value)))
+            .collect()
+    }
+
+    fn text(docx: &Docx) -> Vec<String> {
+        docx.document
+            .body
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                BodyContent::Paragraph(para) => {
+                    Some(para.iter_text().map(|text| text.to_string()).collect())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_complex_fields() {
+        let bold = CharacterProperty::default().bold(true);
+        let mut template = Docx::default();
+        template.document.push(
+            Paragraph::default()
+                .push_text("Hello ")
+                .push(Run::default().push(FieldChar::from(FieldCharType::Begin)))
+                .push(Run::default().push(InstrText::from(" MERGEFIELD name ")))
+                .push(Run::default().push(InstrText::from(r#"\* Upper \b "Dear " "#)))
+                .push(Run::default().push(FieldChar::from(FieldCharType::Separate)))
+                .push(Run::default().property(bold.clone()).push_text("«name»"))
+                .push(Run::default().push(FieldChar::from(FieldCharType::End)))
+                .push(
+                    Run::default()
+                        .push_text(", ")
+                        .push(FieldChar::from(FieldCharType::Begin))
+                        .push(InstrText::from(" PAGE "))
+                        .push(FieldChar::from(FieldCharType::End)),
+                ),
+        );
+
+        let docx = MailMerge::new(template).merge(&record(&[("Name", "john")]));
+
+        assert_eq!(text(&docx), vec!["Hello Dear JOHN, "]);
+        match &docx.document.body.content[0] {
+            BodyContent::Paragraph(para) => {
+                assert_eq!(para.content.len(), 4);
+                assert!(
+                    matches!(&para.content[1], ParagraphContent::Run(run) if run.property == bold)
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn merge_with_locale() {
+        let mut template = Docx::default();
+        template.document.push(
+            Paragraph::default()
+                .push(SimpleField::new(
+                    r#" MERGEFIELD date \@ "dddd, d. MMMM yyyy" "#,
+                ))
+                .push_text(" ")
+                .push(SimpleField::new(r##" MERGEFIELD total \# "#,##0.00 €" "##))
+                .push_text(" ")
+                .push(SimpleField::new(" MERGEFIELD missing ")),
+        );
+
+        let record = record(&[("date", "2021-03-01"), ("total", "1234567.5")]);
+        let docx = MailMerge::new(template.clone())
+            .locale(Locale::de_de())
+            .merge(&record);
+        assert_eq!(text(&docx), vec!["Montag, 1. März 2021 1.234.567,50 € "]);
+
+        let docx = MailMerge::new(template).merge(&record);
+        assert_eq!(text(&docx), vec!["Monday, 1. March 2021 1,234,567.50 € "]);
+
+        assert_eq!(format_number(-0.5, "0.0#", &Locale::en_us()), "-0.5");
+        assert_eq!(format_number(-3.0, "0;(0)", &Locale::en_us()), "(3)");
+        assert_eq!(format_number(42.0, "#", &Locale::en_us()), "42");
+    }
+
+    #[test]
+    fn merge_all_sections() {
+        let mut template = Docx::default();
+        template
+            .document
+            .push(Paragraph::default().push(SimpleField::new(" MERGEFIELD name ")));
+        let section = SectionProperty::default()
+            .push_header(HeaderReference::new("rId1"))
+            .page_size((12240usize, 15840usize))
+            .page_margin(PageMargin::default().top(1440isize));
+        template.document.body.section = Some(section.clone());
+
+        let records = vec![record(&[("name", "a")]), record(&[("name", "b")])];
+        let docx = MailMerge::new(template).merge_all(&records);
+
+        match &docx.document.body.content[1] {
+            BodyContent::Paragraph(para) => assert_eq!(
+                para.property.section,
+                Some(section.clone().ty(SectionType::NextPage))
+            ),
+            _ => panic!(),
+        }
+        assert_eq!(docx.document.body.section, Some(section));
+    }
+
+    #[test]
+    fn merge_all_records() {
+        let mut template = Docx::default();
+        template.document.push(
+            Paragraph::default()
+                .push(SimpleField::new(" MERGEREC "))
+                .push_text(". ")
+                .push(SimpleField::new(" MERGEFIELD name "))
+                .push(SimpleField::new(" MERGEFIELD photo ")),
+        );
+
+        let mut records = vec![record(&[("name", "a")]), record(&[("name", "b")])];
+        records[1].insert(
+            "photo".into(),
+            Image::new(vec![0u8, 1, 2], "PNG", 100, 200).into(),
+        );
+
+        let docx = MailMerge::new(template).merge_all(&records);
+
+        assert_eq!(text(&docx), vec!["1. a", "", "2. b"]);
+        assert_eq!(docx.media.len(), 1);
+        assert!(docx.media.contains_key("word/media/merge1.png"));
+
+        let rels = docx.document_rels.as_ref().unwrap();
+        let rel = rels
+            .relationships
+            .iter()
+            .find(|rel| rel.ty == SCHEMA_IMAGE)
+            .unwrap();
+        assert_eq!(rel.target, "media/merge1.png");
+
+        match &docx.document.body.content[2] {
+            BodyContent::Paragraph(para) => match para.content.last() {
+                Some(ParagraphContent::Run(run)) => assert_eq!(
+                    run.content,
+                    vec![RunContent::Drawing(
+                        Drawing::picture(rel.id.clone(), 100, 200)
+                            .id(1usize)
+                            .name("Picture 1")
+                    )]
+                ),
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn read_records() {
+        let records = read_csv("Name;City\nJohn;Paris\nJane\n".as_bytes(), ';').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                record(&[("Name", "John"), ("City", "Paris")]),
+                record(&[("Name", "Jane"), ("City", "")]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,870 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use bytes::Bytes;
+
+use crate::{
+    __setter,
+    convert::{is_on, InlineWriter, StyleIds},
+    document::{
+        BodyContent, Paragraph, RunContent, TableCellContent, TableContent, TableRowContent,
+    },
+    formatting::CharacterProperty,
+    numbering::ListCounter,
+    private_prelude::*,
+    toc::TableOfContents,
+    Docx,
+};
+
+/// Options of Markdown export
+#[derive(Clone, Debug)]
+pub struct MarkdownOptions<'a> {
+    /// Specifies the styles of headings, quotes and code.
+    pub styles: StyleIds<'a>,
+    /// Specifies the folder of the extracted images, relative to the
+    /// Markdown file.
+    pub image_dir: Cow<'a, str>,
+}
+
+impl Default for MarkdownOptions<'_> {
+    fn default() -> Self {
+        MarkdownOptions {
+            styles: StyleIds::default(),
+            image_dir: "images".into(),
+        }
+    }
+}
+
+impl<'a> MarkdownOptions<'a> {
+    __setter!(styles: StyleIds<'a>);
+    __setter!(image_dir: Cow<'a, str>);
+}
+
+/// A document converted to Markdown
+#[derive(Clone, Debug, Default)]
+pub struct Markdown {
+    /// The Markdown text.
+    pub text: String,
+    /// The images referenced by the text, by path relative to the Markdown
+    /// file.
+    pub images: BTreeMap<String, Bytes>,
+}
+
+impl Markdown {
+    /// Writes the Markdown text to a file, and the images next to it.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, &self.text)?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for (name, data) in &self.images {
+            let image_path = dir.join(name);
+            if let Some(parent) = image_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(image_path, data)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts the document to Markdown.
+    ///
+    /// Paragraphs with a heading style or an outline level become headings,
+    /// numbered and bulleted paragraphs become lists, and the first row of
+    /// tables becomes their header. Footnotes are written at the end.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    /// use docx::formatting::{CharacterProperty, ParagraphProperty};
+    /// use docx::markdown::MarkdownOptions;
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Release notes"),
+    /// );
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .push_text("Now ")
+    ///         .push(Run::default().property(CharacterProperty::default().bold(true)).push_text("faster ")),
+    /// );
+    ///
+    /// let markdown = docx.to_markdown(&MarkdownOptions::default());
+    /// assert_eq!(markdown.text, "# Release notes\n\nNow **faster**\n");
+    /// ```
+    pub fn to_markdown(&self, options: &MarkdownOptions) -> Markdown {
+        let numbering = Default::default();
+        let mut writer = Writer {
+            docx: self,
+            options,
+            toc: options
+                .styles
+                .headings
+                .iter()
+                .enumerate()
+                .fold(TableOfContents::default(), |toc, (level, style_id)| {
+                    toc.heading_style(style_id.clone(), level + 1)
+                }),
+            lists: ListCounter::new(self.numbering.as_ref().unwrap_or(&numbering)),
+            markdown: Markdown::default(),
+            last_list_item: false,
+            code: Vec::new(),
+            footnotes: Vec::new(),
+        };
+
+        writer.blocks(&self.document.body.content);
+        writer.flush_code();
+
+        let footnotes = std::mem::take(&mut writer.footnotes);
+        for id in footnotes {
+            let note = self.footnotes.as_ref().and_then(|notes| notes.get(&id));
+            let text: Vec<String> = note
+                .iter()
+                .flat_map(|note| &note.content)
+                .filter_map(|content| match content {
+                    BodyContent::Paragraph(para) => Some(writer.paragraph_text(para)),
+                    _ => None,
+                })
+                .collect();
+            writer.block(format!("[^{}]: {}", id, text.join(" ")), false);
+        }
+
+        if !writer.markdown.text.is_empty() {
+            writer.markdown.text.push('\n');
+        }
+        writer.markdown
+    }
+}
+
+struct Writer<'d, 'o, 'n, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'o MarkdownOptions<'o>,
+    toc: TableOfContents<'o>,
+    lists: ListCounter<'n, 'a>,
+    markdown: Markdown,
+    last_list_item: bool,
+    /// Lines of the current code block.
+    code: Vec<String>,
+    /// Ids of the referenced footnotes.
+    footnotes: Vec<String>,
+}
+
+/// Formatting of a piece of text
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Format {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    code: bool,
+}
+
+impl<'d, 'o, 'n, 'a> Writer<'d, 'o, 'n, 'a> {
+    /// Appends a block, separated by a blank line unless it continues a list.
+    fn block(&mut self, markdown: String, list_item: bool) {
+        let text = &mut self.markdown.text;
+        if !text.is_empty() {
+            text.push_str(if list_item && self.last_list_item {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        text.push_str(&markdown);
+        self.last_list_item = list_item;
+    }
+
+    fn flush_code(&mut self) {
+        if self.code.is_empty() {
+            return;
+        }
+        let code = std::mem::take(&mut self.code).join("\n");
+        let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+        self.block(format!("{}\n{}\n{}", fence, code, fence), false);
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para),
+                BodyContent::Table(table) => {
+                    self.flush_code();
+                    self.table(&table.rows);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph) {
+        let style_id = para.property.style_id.as_ref().map(|id| &*id.value);
+        let styles = &self.options.styles;
+
+        if style_id == Some(&*styles.code_block) {
+            let line = para.iter_text().map(|text| &**text).collect::<String>();
+            self.code.push(line);
+            return;
+        }
+        self.flush_code();
+
+        let text = self.paragraph_text(para);
+
+        if let Some(level) = self.toc.heading_level(para, &self.docx.styles) {
+            if !text.is_empty() {
+                self.block(format!("{} {}", "#".repeat(level.min(6)), text), false);
+            }
+            return;
+        }
+
+        if let Some(item) = para
+            .property
+            .numbering
+            .as_ref()
+            .and_then(|numbering| self.lists.next(numbering))
+        {
+            let marker = if item.is_bullet() {
+                "-".to_owned()
+            } else {
+                format!("{}.", item.number)
+            };
+            let indent = "    ".repeat(item.level);
+            self.block(format!("{}{} {}", indent, marker, text), true);
+            return;
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        // lines after a break could start a block too
+        let text: Vec<String> = text.split('\n').map(escape_line_start).collect();
+        let text = text.join("\n");
+        if style_id == Some(&*styles.quote) {
+            self.block(format!("> {}", text.replace('\n', "\n> ")), false);
+        } else {
+            self.block(text, false);
+        }
+    }
+
+    fn table(&mut self, rows: &[TableContent]) {
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .flat_map(TableContent::iter_rows)
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .flat_map(TableRowContent::iter_cells)
+                    .map(|cell| {
+                        let mut paragraphs = Vec::new();
+                        self.cell_paragraphs(&cell.content, &mut paragraphs);
+                        paragraphs.join("<br>")
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        if columns == 0 {
+            return;
+        }
+
+        let line = |cells: &[String]| {
+            let cells: Vec<&str> = (0..columns)
+                .map(|column| cells.get(column).map_or("", |cell| cell.as_str()))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![line(&rows[0]), line(&vec!["---".to_owned(); columns])];
+        lines.extend(rows[1..].iter().map(|row| line(row)));
+        self.block(lines.join("\n"), false);
+    }
+
+    fn cell_paragraphs(&mut self, content: &[TableCellContent], paragraphs: &mut Vec<String>) {
+        for content in content {
+            match content {
+                TableCellContent::Paragraph(para) => paragraphs.push(self.cell_paragraph(para)),
+                TableCellContent::Sdt(sdt) => {
+                    for content in &sdt.content.content {
+                        if let BodyContent::Paragraph(para) = content {
+                            paragraphs.push(self.cell_paragraph(para));
+                        }
+                    }
+                }
+                // tables can't be nested in Markdown
+                TableCellContent::Table(table) => {
+                    for cell in table.iter_rows().flat_map(|row| row.iter_cells()) {
+                        self.cell_paragraphs(&cell.content, paragraphs);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts a paragraph of a cell, which must fit on one line.
+    fn cell_paragraph(&mut self, para: &Paragraph) -> String {
+        self.paragraph_text(para).replace("\\\n", "<br>")
+    }
+
+    /// Converts the content of a paragraph, without trailing whitespace.
+    fn paragraph_text(&mut self, para: &Paragraph) -> String {
+        self.inline(&para.content).trim_end().to_owned()
+    }
+
+    /// Extracts an image, and returns its path.
+    fn image(&mut self, embed: &str) -> Option<String> {
+        let (name, data) = self.docx.embedded_media(embed)?;
+        let file_name = name.rsplit('/').next()?;
+        let path = format!(
+            "{}/{}",
+            self.options.image_dir.trim_end_matches('/'),
+            file_name
+        );
+        self.markdown.images.insert(path.clone(), data.clone());
+
+        Some(path.replace(' ', "%20"))
+    }
+}
+
+impl InlineWriter for Writer<'_, '_, '_, '_> {
+    type Format = Format;
+
+    fn docx(&self) -> &Docx<'_> {
+        self.docx
+    }
+
+    fn format(&self, prop: &CharacterProperty) -> Format {
+        Format {
+            bold: prop.bold.as_ref().is_some_and(|bold| is_on(bold.value)),
+            italic: prop
+                .italics
+                .as_ref()
+                .is_some_and(|italics| is_on(italics.value)),
+            strike: prop
+                .strike
+                .as_ref()
+                .is_some_and(|strike| is_on(strike.value))
+                || prop
+                    .dstrike
+                    .as_ref()
+                    .is_some_and(|dstrike| is_on(dstrike.value)),
+            code: prop
+                .style_id
+                .as_ref()
+                .is_some_and(|id| id.value == self.options.styles.code_span),
+        }
+    }
+
+    fn run_content(&mut self, content: &RunContent, format: &Format) -> Option<String> {
+        // code spans aren't escaped
+        let text = |text: &str| {
+            if format.code {
+                text.to_owned()
+            } else {
+                escape(text)
+            }
+        };
+        match content {
+            RunContent::Text(content) => Some(text(&content.text)),
+            RunContent::Tab(_) => Some(text("\t")),
+            RunContent::Break(_) => Some("\\\n".to_owned()),
+            RunContent::FootnoteReference(reference) => {
+                self.footnotes.push(reference.id.to_string());
+                Some(format!("[^{}]", reference.id))
+            }
+            RunContent::Drawing(drawing) => {
+                let path = self.image(drawing.embed.as_deref()?)?;
+                let alt = drawing.name.as_deref().unwrap_or_default();
+                Some(format!("![{}]({})", escape(alt), path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Writes the emphasis, keeping whitespace outside of the markers.
+    fn formatted(format: &Format, text: String) -> String {
+        if format.code {
+            if text.is_empty() {
+                return text;
+            }
+            let fence = "`".repeat(longest_run(&text, '`') + 1);
+            let padding = if text.starts_with('`') || text.ends_with('`') {
+                " "
+            } else {
+                ""
+            };
+            return format!("{}{}{}{}{}", fence, padding, text, padding, fence);
+        }
+
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return text;
+        }
+
+        let mut open = String::new();
+        if format.bold {
+            open += "**";
+        }
+        if format.italic {
+            open += "*";
+        }
+        if format.strike {
+            open += "~~";
+        }
+        let close: String = open.chars().rev().collect();
+
+        let start = text.len() - text.trim_start().len();
+        let end = text.trim_end().len();
+        format!(
+            "{}{}{}{}{}",
+            &text[..start],
+            open,
+            trimmed,
+            close,
+            &text[end..]
+        )
+    }
+
+    fn link(&self, url: &str, text: String) -> String {
+        if text.trim().is_empty() {
+            return text;
+        }
+        format!("[{}]({})", text, url.replace(' ', "%20"))
+    }
+}
+
+/// Escapes the characters with a meaning in Markdown.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes the start of a line which would be read as a block marker, and
+/// the leading whitespace which would be stripped or start a code block.
+fn escape_line_start(text: &str) -> String {
+    let rest = text.trim_start_matches([' ', '\t']);
+    if rest.len() < text.len() {
+        let whitespace = &text[..text.len() - rest.len()];
+        let escaped: String = whitespace
+            .chars()
+            .map(|c| if c == '\t' { "&#9;" } else { "&#32;" })
+            .collect();
+        return escaped + rest;
+    }
+
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    match text[digits..].chars().next() {
+        Some('.') | Some(')') if digits > 0 => format!("{}\\{}", &text[..digits], &text[digits..]),
+        Some('#') | Some('+') | Some('-') | Some('=') if digits == 0 => format!("\\{}", text),
+        _ => text.to_owned(),
+    }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for ch in text.chars() {
+        if ch == c {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        document::{Drawing, FootnoteReference, Hyperlink, Run, Table, TableCell, TableRow},
+        footnotes::{Footnote, Footnotes},
+        formatting::{NumberingProperty, ParagraphProperty},
+        numbering::{
+            AbstractNumbering, AbstractNumberingId, Level, LevelText, Numbering, NumberingFormat,
+            NumberingFormatVal, Numberings,
+        },
+        rels::Relationships,
+        schema::{SCHEMA_HYPERLINK, SCHEMA_IMAGE},
+    };
+
+    fn style(style_id: &'static str) -> ParagraphProperty<'static> {
+        ParagraphProperty::default().style_id(style_id)
+    }
+
+    fn list(num_id: usize, format: NumberingFormatVal) -> (AbstractNumbering<'static>, Numbering) {
+        let levels = (0..2)
+            .map(|index| Level {
+                index,
+                start: None,
+                numbering_format: Some(NumberingFormat { value: format }),
+                level_text: LevelText {
+                    value: "%1.".into(),
+                },
+            })
+            .collect();
+        (
+            AbstractNumbering {
+                abstract_num_id: num_id,
+                levels,
+                ..Default::default()
+            },
+            Numbering {
+                num_id,
+                abstract_num_id: AbstractNumberingId { value: num_id },
+            },
+        )
+    }
+
+    fn item(num_id: usize, level: usize, text: &'static str) -> Paragraph<'static> {
+        Paragraph::default()
+            .property(
+                ParagraphProperty::default().numbering(NumberingProperty::from((num_id, level))),
+            )
+            .push_text(text)
+    }
+
+    #[test]
+    fn to_markdown() {
+        let (bullets, bullets_num) = list(1, NumberingFormatVal::Bullet);
+        let (numbers, numbers_num) = list(2, NumberingFormatVal::Decimal);
+
+        let mut rels = Relationships::default();
+        let link = rels.add_external_rel(SCHEMA_HYPERLINK, "https://example.com");
+        let image = rels.add_rel(SCHEMA_IMAGE, "media/image1.png");
+
+        let mut docx = Docx {
+            numbering: Some(Numberings {
+                abstract_nums: vec![bullets, numbers],
+                nums: vec![bullets_num, numbers_num],
+            }),
+            document_rels: Some(rels),
+            footnotes: Some(Footnotes {
+                notes: vec![Footnote::new("1").push(Paragraph::default().push_text("A note."))],
+            }),
+            ..Default::default()
+        };
+        docx.media.insert(
+            "word/media/image1.png".to_owned(),
+            Bytes::from_static(b"png"),
+        );
+
+        let bold = CharacterProperty::default().bold(true);
+        let document = &mut docx.document;
+        document.push(
+            Paragraph::default()
+                .property(style("Heading2"))
+                .push_text("Title"),
+        );
+        document.push(
+            Paragraph::default()
+                .push_text("1. Plain *text*, ")
+                .push(Run::default().property(bold.clone()).push_text("bold "))
+                .push(
+                    Run::default()
+                        .property(bold.italics(true))
+                        .push_text("and more"),
+                )
+                .push(
+                    Hyperlink::default()
+                        .id(link)
+                        .content(Run::default().push_text("a link")),
+                )
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().style_id("VerbatimChar"))
+                        .push_text("x `y`"),
+                )
+                .push(Run::default().push(FootnoteReference::from("1"))),
+        );
+        document.push(item(1, 0, "one"));
+        document.push(item(1, 1, "nested"));
+        document.push(item(2, 0, "first"));
+        document.push(item(2, 0, "second"));
+        document.push(
+            Paragraph::default()
+                .property(style("Quote"))
+                .push_text("Quoted"),
+        );
+        document.push(
+            Paragraph::default()
+                .property(style("SourceCode"))
+                .push_text("let a = 1;"),
+        );
+        document.push(
+            Paragraph::default()
+                .property(style("SourceCode"))
+                .push_text("a *= 2;"),
+        );
+        document.push(
+            Table::default()
+                .push_row(
+                    TableRow::default()
+                        .push_cell(Paragraph::default().push_text("Name"))
+                        .push_cell(Paragraph::default().push_text("A|B")),
+                )
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("x"))),
+        );
+        document.push(
+            Paragraph::default()
+                .push(Run::default().push(Drawing::picture(image, 1, 1).name("Logo"))),
+        );
+
+        let markdown = docx.to_markdown(&MarkdownOptions::default());
+        assert_eq!(
+            markdown.text,
+            "## Title\n\
+             \n\
+             1\\. Plain \\*text\\*, **bold** ***and more***[a link](https://example.com)`` x `y` ``[^1]\n\
+             \n\
+             - one\n    \
+             - nested\n\
+             1. first\n\
+             2. second\n\
+             \n\
+             > Quoted\n\
+             \n\
+             ```\nlet a = 1;\na *= 2;\n```\n\
+             \n\
+             | Name | A\\|B |\n\
+             | --- | --- |\n\
+             | x |  |\n\
+             \n\
+             ![Logo](images/image1.png)\n\
+             \n\
+             [^1]: A note.\n"
+        );
+        assert_eq!(
+            markdown.images.keys().collect::<Vec<_>>(),
+            vec!["images/image1.png"]
+        );
+    }
+
+    fn markdown(docx: &Docx) -> String {
+        docx.to_markdown(&MarkdownOptions::default()).text
+    }
+
+    #[test]
+    fn lists() {
+        let mut numbering = Numberings::default();
+        let bullets = numbering.push_list(NumberingFormatVal::Bullet, 1);
+        let numbers = numbering.push_list(NumberingFormatVal::Decimal, 3);
+        let mut docx = Docx {
+            numbering: Some(numbering),
+            ..Default::default()
+        };
+
+        let document = &mut docx.document;
+        document.push(item(bullets, 0, "a"));
+        document.push(item(bullets, 1, "b"));
+        document.push(item(bullets, 2, "c"));
+        document.push(item(bullets, 0, "d"));
+        document.push(Paragraph::default().push_text("between"));
+        document.push(item(numbers, 0, "three"));
+        // every level of the list starts from 3
+        document.push(item(numbers, 1, "nested"));
+        document.push(item(numbers, 0, "four"));
+        // an empty item keeps its marker
+        document.push(item(numbers, 0, ""));
+
+        assert_eq!(
+            markdown(&docx),
+            "- a\n    - b\n        - c\n- d\n\
+             \n\
+             between\n\
+             \n\
+             3. three\n    3. nested\n4. four\n5. \n"
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        let mut docx = Docx::default();
+        let quote = || Paragraph::default().property(style("Quote"));
+        docx.document.push(quote().push_text("first"));
+        docx.document.push(
+            quote()
+                .push_text("broken")
+                .push(Run::default().push_break(None))
+                .push_text("# line"),
+        );
+        docx.document.push(quote());
+        docx.document.push(Paragraph::default().push_text("after"));
+
+        // each paragraph is a quote, and empty ones are left out
+        assert_eq!(
+            markdown(&docx),
+            "> first\n\
+             \n\
+             > broken\\\n> \\# line\n\
+             \n\
+             after\n"
+        );
+    }
+
+    #[test]
+    fn code_blocks() {
+        let mut docx = Docx::default();
+        let code = |text: &'static str| {
+            Paragraph::default()
+                .property(style("SourceCode"))
+                .push_text(text)
+        };
+        docx.document.push(code("fn main() {"));
+        docx.document.push(code("    let s = \"``\";"));
+        docx.document.push(code(""));
+        docx.document.push(code("}"));
+        docx.document.push(Paragraph::default().push_text("*text*"));
+        docx.document.push(code("a * b"));
+
+        // lines are kept as they are, in a fence longer than their backticks
+        assert_eq!(
+            markdown(&docx),
+            "```\nfn main() {\n    let s = \"``\";\n\n}\n```\n\
+             \n\
+             \\*text\\*\n\
+             \n\
+             ```\na * b\n```\n"
+        );
+
+        let mut docx = Docx::default();
+        docx.document.push(code("```"));
+        assert_eq!(markdown(&docx), "````\n```\n````\n");
+    }
+
+    #[test]
+    fn tables() {
+        let nested = Table::default().push_row(
+            TableRow::default()
+                .push_cell(Paragraph::default().push_text("x"))
+                .push_cell(Paragraph::default().push_text("y")),
+        );
+        let mut docx = Docx::default();
+        docx.document.push(
+            Table::default()
+                .push_row(
+                    TableRow::default()
+                        .push_cell(Paragraph::default().push_text("Key"))
+                        .push_cell(Paragraph::default().push_text("Value")),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(TableCell {
+                            property: Default::default(),
+                            content: vec![
+                                Paragraph::default().push_text("two").into(),
+                                Paragraph::default()
+                                    .push_text("broken")
+                                    .push(Run::default().push_break(None))
+                                    .push_text("lines")
+                                    .into(),
+                            ],
+                        })
+                        .push_cell(TableCell::from(nested)),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(Paragraph::default().push_text("a|b"))
+                        .push_cell(Paragraph::default().push_text("c"))
+                        .push_cell(Paragraph::default().push_text("extra")),
+                ),
+        );
+        // a table without cells is left out
+        docx.document.push(Table::default());
+
+        assert_eq!(
+            markdown(&docx),
+            "| Key | Value |  |\n\
+             | --- | --- | --- |\n\
+             | two<br>broken<br>lines | x<br>y |  |\n\
+             | a\\|b | c | extra |\n"
+        );
+    }
+
+    #[test]
+    fn links() {
+        let mut rels = Relationships::default();
+        let link = rels.add_external_rel(SCHEMA_HYPERLINK, "https://example.com/a b");
+        let mut docx = Docx {
+            document_rels: Some(rels),
+            ..Default::default()
+        };
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    Hyperlink::default()
+                        .id(link)
+                        .content(Run::default().push_text("spaced")),
+                )
+                .push_text(", ")
+                .push(
+                    Hyperlink::default().anchor("intro").content(
+                        Run::default()
+                            .property(CharacterProperty::default().bold(true))
+                            .push_text("anchor"),
+                    ),
+                )
+                .push(
+                    Hyperlink::default()
+                        .anchor("blank")
+                        .content(Run::default().push_text(" ")),
+                )
+                .push(
+                    Hyperlink::default()
+                        .id("rId404")
+                        .content(Run::default().push_text("broken")),
+                ),
+        );
+
+        // links without text or target keep their text only
+        assert_eq!(
+            markdown(&docx),
+            "[spaced](https://example.com/a%20b), [**anchor**](#intro) broken\n"
+        );
+    }
+
+    #[test]
+    fn images() {
+        let mut docx = Docx::default();
+        let (logo, _) = docx.embed_media(Bytes::from_static(b"png"), "png");
+        let (photo, _) = docx.embed_media(Bytes::from_static(b"jpeg"), "jpeg");
+        docx.document.push(
+            Paragraph::default()
+                .push(Run::default().push(Drawing::picture(logo, 1, 1).name("The [logo]")))
+                .push_text(" and ")
+                .push(Run::default().push(Drawing::picture(photo, 1, 1)))
+                .push(Run::default().push(Drawing::picture("rId404", 1, 1).name("missing"))),
+        );
+
+        let markdown = docx.to_markdown(&MarkdownOptions::default().image_dir("media files/"));
+        assert_eq!(
+            markdown.text,
+            "![The \\[logo\\]](media%20files/image1.png) and ![](media%20files/image2.jpeg)\n"
+        );
+        assert_eq!(
+            markdown.images,
+            BTreeMap::from([
+                (
+                    "media files/image1.png".to_owned(),
+                    Bytes::from_static(b"png")
+                ),
+                (
+                    "media files/image2.jpeg".to_owned(),
+                    Bytes::from_static(b"jpeg")
+                ),
+            ])
+        );
+    }
+}
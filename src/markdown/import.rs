@@ -0,0 +1,866 @@
+use std::collections::HashMap;
+
+use pulldown_cmark::{Alignment, Event, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    __setter,
+    convert::{text_of, Image, StyleIds, TABLE_WIDTH},
+    document::{
+        BodyContent, Break, Drawing, Hyperlink, Paragraph, ParagraphContent, Run, RunContent,
+        Table, TableCell, TableCellContent, TableRow,
+    },
+    formatting::{
+        BorderStyle, Borders, BottomBorder, CharacterProperty, Justification, JustificationVal,
+        NumberingProperty, ParagraphProperty, TableBorders, TableProperty, TopBorder,
+    },
+    numbering::NumberingFormatVal,
+    rels::Relationships,
+    schema::SCHEMA_HYPERLINK,
+    Docx,
+};
+
+/// Options of Markdown import
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownImportOptions<'a> {
+    /// Specifies the styles of headings, quotes, code, lists, links and tables.
+    pub styles: StyleIds<'a>,
+    /// Specifies the images, by their destination in the Markdown text.
+    ///
+    /// Images which aren't given are replaced by their description.
+    pub images: HashMap<String, Image>,
+}
+
+impl<'a> MarkdownImportOptions<'a> {
+    __setter!(styles: StyleIds<'a>);
+
+    /// Adds the image of a destination.
+    pub fn image<T: Into<String>>(mut self, destination: T, image: Image) -> Self {
+        self.images.insert(destination.into(), image);
+        self
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts CommonMark to a document.
+    ///
+    /// GitHub tables, strikethrough and task lists are supported as well.
+    ///
+    /// ```rust
+    /// use docx::document::BodyContent;
+    /// use docx::markdown::MarkdownImportOptions;
+    /// use docx::Docx;
+    ///
+    /// let docx = Docx::from_markdown(
+    ///     "# Release notes\n\n- Faster **startup**\n- [Docs](https://example.com)\n",
+    ///     &MarkdownImportOptions::default(),
+    /// );
+    ///
+    /// assert_eq!(docx.document.body.content.len(), 3);
+    /// assert!(docx.styles.get("Heading1").is_some());
+    /// assert_eq!(docx.numbering.unwrap().nums.len(), 1);
+    /// ```
+    pub fn from_markdown(markdown: &str, options: &MarkdownImportOptions<'a>) -> Docx<'a> {
+        let mut docx = Docx::default();
+        docx.import_markdown(markdown, options);
+        docx
+    }
+
+    /// Appends CommonMark to the document.
+    ///
+    /// Styles which the document doesn't define yet are added from
+    /// [`StyleIds::definitions`], so a template can provide its own.
+    pub fn import_markdown(&mut self, markdown: &str, options: &MarkdownImportOptions<'a>) {
+        options.styles.add_definitions(&mut self.styles);
+
+        let parser = Parser::new_ext(
+            markdown,
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+        );
+
+        let mut reader = Reader {
+            docx: self,
+            options,
+            blocks: Vec::new(),
+            paragraph: None,
+            text_run: false,
+            format: Format::default(),
+            link: None,
+            image: None,
+            code: None,
+            heading: None,
+            quotes: 0,
+            lists: Vec::new(),
+            item: None,
+            table: None,
+        };
+        for event in parser {
+            reader.event(event);
+        }
+        reader.end_paragraph();
+
+        let blocks = reader.blocks;
+        self.document.body.content.extend(blocks);
+    }
+}
+
+struct Reader<'d, 'o, 'a> {
+    docx: &'d mut Docx<'a>,
+    options: &'o MarkdownImportOptions<'a>,
+    blocks: Vec<BodyContent<'a>>,
+    paragraph: Option<Paragraph<'a>>,
+    /// Whether the last content of the paragraph is a run of text in the
+    /// current format, which the next text is appended to.
+    text_run: bool,
+    format: Format,
+    /// The current link, and its text.
+    link: Option<(Hyperlink<'a>, String)>,
+    /// The destination and the description of the current image.
+    image: Option<(String, String)>,
+    /// The text of the current code block.
+    code: Option<String>,
+    heading: Option<usize>,
+    quotes: usize,
+    /// The numbering ids of the open lists.
+    lists: Vec<usize>,
+    /// The numbering of the first paragraph of the current list item.
+    item: Option<NumberingProperty>,
+    table: Option<TableReader<'a>>,
+}
+
+#[derive(Default)]
+struct Format {
+    strong: usize,
+    emphasis: usize,
+    strike: usize,
+}
+
+struct TableReader<'a> {
+    table: Table<'a>,
+    alignments: Vec<Alignment>,
+    head: bool,
+    row: Vec<TableCell<'a>>,
+    cell: Option<Vec<TableCellContent<'a>>>,
+}
+
+impl<'d, 'o, 'a> Reader<'d, 'o, 'a> {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.push_str(&text);
+                } else {
+                    self.text(&text);
+                }
+            }
+            Event::Code(text) => {
+                if let Some((_, link_text)) = &mut self.link {
+                    link_text.push_str(&text);
+                } else {
+                    let property = self.character_property(true);
+                    self.push(Run::default().property(property).push_text(text_of(&text)));
+                }
+            }
+            Event::InlineHtml(html) if is_break(&html) => self.hard_break(),
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.hard_break(),
+            Event::Rule => {
+                self.end_paragraph();
+                let border = BottomBorder::default()
+                    .style(BorderStyle::Single)
+                    .size(6usize)
+                    .color("auto");
+                self.paragraph = Some(Paragraph::default().property(
+                    ParagraphProperty::default().border(Borders::default().bottom(border)),
+                ));
+                self.end_paragraph();
+            }
+            Event::TaskListMarker(checked) => self.text(if checked { "☒ " } else { "☐ " }),
+            _ => (),
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_paragraph(),
+            Tag::Heading { level, .. } => {
+                self.end_paragraph();
+                self.heading = Some(level as usize - 1);
+                self.start_paragraph();
+            }
+            Tag::BlockQuote(_) => {
+                self.end_paragraph();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.end_paragraph();
+                self.code = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.end_paragraph();
+                let num_id = self.numbering(start);
+                self.lists.push(num_id);
+            }
+            Tag::Item => {
+                self.end_paragraph();
+                if let Some(num_id) = self.lists.last() {
+                    self.item = Some(NumberingProperty::from((*num_id, self.lists.len() - 1)));
+                }
+            }
+            Tag::Table(alignments) => {
+                self.end_paragraph();
+                let columns = alignments.len().max(1);
+                let table = Table::default()
+                    .property(
+                        TableProperty::default()
+                            .style_id(self.options.styles.table.clone())
+                            .borders(
+                                TableBorders::default()
+                                    .top(TopBorder::default().style(BorderStyle::Single))
+                                    .bottom(BottomBorder::default().style(BorderStyle::Single)),
+                            ),
+                    )
+                    .push_grid(vec![TABLE_WIDTH / columns; columns]);
+                self.table = Some(TableReader {
+                    table,
+                    alignments,
+                    head: false,
+                    row: Vec::new(),
+                    cell: None,
+                });
+            }
+            Tag::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.head = true;
+                }
+            }
+            Tag::TableCell => {
+                if let Some(table) = &mut self.table {
+                    table.cell = Some(Vec::new());
+                }
+            }
+            Tag::Emphasis => self.format(|format| format.emphasis += 1),
+            Tag::Strong => self.format(|format| format.strong += 1),
+            Tag::Strikethrough => self.format(|format| format.strike += 1),
+            Tag::Link { dest_url, .. } => {
+                let hyperlink = if let Some(anchor) = dest_url.strip_prefix('#') {
+                    Hyperlink::default().anchor(anchor.to_owned())
+                } else {
+                    let id = self
+                        .docx
+                        .document_rels
+                        .get_or_insert_with(Relationships::default)
+                        .add_external_rel(SCHEMA_HYPERLINK, dest_url.into_string());
+                    Hyperlink::default().id(id)
+                };
+                self.link = Some((hyperlink, String::new()));
+            }
+            Tag::Image { dest_url, .. } => {
+                self.image = Some((dest_url.into_string(), String::new()));
+            }
+            _ => (),
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_paragraph(),
+            TagEnd::Heading(_) => {
+                self.end_paragraph();
+                self.heading = None;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.end_paragraph();
+                self.quotes -= 1;
+            }
+            TagEnd::CodeBlock => {
+                // the lines are styled as code until the block is closed
+                let code = self.code.as_mut().map(std::mem::take).unwrap_or_default();
+                let code = code.strip_suffix('\n').unwrap_or(&code);
+                for line in code.split('\n') {
+                    self.start_paragraph();
+                    if !line.is_empty() {
+                        self.push(Run::default().push_text(text_of(line)));
+                    }
+                    self.end_paragraph();
+                }
+                self.code = None;
+            }
+            TagEnd::List(_) => {
+                self.end_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::Item => {
+                self.end_paragraph();
+                self.item = None;
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.blocks.push(table.table.into());
+                }
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                if let Some(table) = &mut self.table {
+                    let row = std::mem::take(&mut table.row)
+                        .into_iter()
+                        .fold(TableRow::default(), TableRow::push_cell);
+                    table.table = std::mem::take(&mut table.table).push_row(row);
+                    table.head = false;
+                }
+            }
+            TagEnd::TableCell => {
+                if self.paragraph.is_none() {
+                    self.start_paragraph();
+                }
+                self.end_paragraph();
+                if let Some(table) = &mut self.table {
+                    let content = table.cell.take().unwrap_or_default();
+                    table.row.push(TableCell {
+                        property: Default::default(),
+                        content,
+                    });
+                }
+            }
+            TagEnd::Emphasis => self.format(|format| format.emphasis -= 1),
+            TagEnd::Strong => self.format(|format| format.strong -= 1),
+            TagEnd::Strikethrough => self.format(|format| format.strike -= 1),
+            TagEnd::Link => {
+                if let Some((hyperlink, text)) = self.link.take() {
+                    if !text.is_empty() {
+                        let property = self
+                            .character_property(false)
+                            .style_id(self.options.styles.hyperlink.clone());
+                        let run = Run::default().property(property).push_text(text_of(&text));
+                        self.push(hyperlink.content(run));
+                    }
+                }
+            }
+            TagEnd::Image => {
+                if let Some((destination, description)) = self.image.take() {
+                    self.image(&destination, description);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn format(&mut self, f: impl FnOnce(&mut Format)) {
+        f(&mut self.format);
+        self.text_run = false;
+    }
+
+    fn character_property(&self, code: bool) -> CharacterProperty<'a> {
+        let mut property = CharacterProperty::default();
+        if self.format.strong > 0 || self.table.as_ref().is_some_and(|table| table.head) {
+            property = property.bold(true);
+        }
+        if self.format.emphasis > 0 {
+            property = property.italics(true);
+        }
+        if self.format.strike > 0 {
+            property = property.strike(true);
+        }
+        if code {
+            property = property.style_id(self.options.styles.code_span.clone());
+        }
+        property
+    }
+
+    fn start_paragraph(&mut self) {
+        self.end_paragraph();
+
+        let styles = &self.options.styles;
+        let style_id = if let Some(level) = self.heading {
+            Some(&styles.headings[level.min(5)])
+        } else if self.code.is_some() {
+            Some(&styles.code_block)
+        } else if self.quotes > 0 {
+            Some(&styles.quote)
+        } else if !self.lists.is_empty() {
+            Some(&styles.list)
+        } else {
+            None
+        };
+
+        let mut property = ParagraphProperty::default();
+        if let Some(style_id) = style_id {
+            property = property.style_id(style_id.clone());
+        }
+        if let Some(numbering) = self.item.take() {
+            property = property.numbering(numbering);
+        }
+        if let Some(table) = &self.table {
+            let column = table.row.len();
+            let justification = match table.alignments.get(column) {
+                Some(Alignment::Left) => Some(JustificationVal::Left),
+                Some(Alignment::Center) => Some(JustificationVal::Center),
+                Some(Alignment::Right) => Some(JustificationVal::Right),
+                _ => None,
+            };
+            if let Some(justification) = justification {
+                property = property.justification(Justification::from(justification));
+            }
+        }
+
+        self.paragraph = Some(Paragraph::default().property(property));
+    }
+
+    fn end_paragraph(&mut self) {
+        self.text_run = false;
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        match self.table.as_mut().and_then(|table| table.cell.as_mut()) {
+            Some(cell) => cell.push(paragraph.into()),
+            None => self.blocks.push(paragraph.into()),
+        }
+    }
+
+    /// Appends content to the current paragraph.
+    fn push<T: Into<ParagraphContent<'a>>>(&mut self, content: T) {
+        if self.paragraph.is_none() {
+            self.start_paragraph();
+        }
+        if let Some(paragraph) = &mut self.paragraph {
+            paragraph.content.push(content.into());
+        }
+        self.text_run = false;
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, description)) = &mut self.image {
+            description.push_str(text);
+            return;
+        }
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push_str(text);
+            return;
+        }
+
+        if self.text_run {
+            let last = self
+                .paragraph
+                .as_mut()
+                .and_then(|paragraph| paragraph.content.last_mut());
+            if let Some(ParagraphContent::Run(run)) = last {
+                if let Some(RunContent::Text(last)) = run.content.last_mut() {
+                    let mut joined = last.text.to_string();
+                    joined.push_str(text);
+                    *last = text_of(&joined);
+                    return;
+                }
+            }
+        }
+
+        let property = self.character_property(false);
+        self.push(Run::default().property(property).push_text(text_of(text)));
+        self.text_run = true;
+    }
+
+    fn hard_break(&mut self) {
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push(' ');
+        } else {
+            self.push(Run::default().push_break(Break::default()));
+        }
+    }
+
+    /// Creates the numbering of a list, ordered from the given start.
+    fn numbering(&mut self, start: Option<u64>) -> usize {
+        let numberings = self.docx.numbering.get_or_insert_with(Default::default);
+        match start {
+            Some(start) => {
+                numberings.push_list(ORDERED_FORMATS[self.lists.len() % 3], start as usize)
+            }
+            None => numberings.push_list(NumberingFormatVal::Bullet, 1),
+        }
+    }
+
+    fn image(&mut self, destination: &str, description: String) {
+        let Some(image) = self.options.images.get(destination) else {
+            self.text(&description);
+            return;
+        };
+
+        let (embed, number) = self.docx.embed_media(image.data.clone(), &image.extension);
+        let drawing = Drawing::picture(embed, image.width, image.height)
+            .id(number)
+            .name(description);
+        self.push(Run::default().push(drawing));
+    }
+}
+
+/// The numbering formats of ordered lists, by nesting level.
+const ORDERED_FORMATS: [NumberingFormatVal; 3] = [
+    NumberingFormatVal::Decimal,
+    NumberingFormatVal::LowerLetter,
+    NumberingFormatVal::LowerRoman,
+];
+
+fn is_break(html: &str) -> bool {
+    matches!(
+        html.trim().to_ascii_lowercase().as_str(),
+        "<br>" | "<br/>" | "<br />"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::MarkdownOptions;
+
+    #[test]
+    fn from_markdown() {
+        let markdown = "\
+## Title
+
+Plain *text*, **bold** and ~~gone~~ with `code` and [a link](https://example.com).
+
+> Quoted
+
+1. first
+2. second
+   - nested
+3. third
+
+```
+let a = 1;
+
+a *= 2;
+```
+
+| Name | Value |
+| :--- | ----: |
+| x | 1 |
+
+![Logo](logo.png) ![Missing](missing.png)
+";
+        let options = MarkdownImportOptions::default()
+            .image("logo.png", Image::new(&b"png"[..], "png", 100, 200));
+        let mut docx = Docx::from_markdown(markdown, &options);
+
+        let numbering = docx.numbering.as_ref().unwrap();
+        assert_eq!(numbering.abstract_nums.len(), 2);
+        assert_eq!(numbering.nums.len(), 2);
+        assert!(docx.media.contains_key("word/media/image1.png"));
+        assert!(docx.styles.get("VerbatimChar").is_some());
+
+        // the export reads the import back
+        let exported = docx.to_markdown(&MarkdownOptions::default());
+        assert_eq!(
+            exported.text,
+            "## Title\n\
+             \n\
+             Plain *text*, **bold** and ~~gone~~ with `code` and [a link](https://example.com).\n\
+             \n\
+             > Quoted\n\
+             \n\
+             1. first\n\
+             2. second\n    \
+             - nested\n\
+             3. third\n\
+             \n\
+             ```\nlet a = 1;\n\na *= 2;\n```\n\
+             \n\
+             | **Name** | **Value** |\n\
+             | --- | --- |\n\
+             | x | 1 |\n\
+             \n\
+             ![Logo](images/image1.png) Missing\n"
+        );
+
+        // the generated parts are written and read back
+        let written = docx.write(std::io::Cursor::new(Vec::new())).unwrap();
+        let file = crate::DocxFile::from_reader(written).unwrap();
+        let read = file.parse().unwrap();
+        assert_eq!(
+            read.to_markdown(&MarkdownOptions::default()).text,
+            exported.text
+        );
+    }
+
+    /// The style, the numbering and the text of a paragraph
+    type Summary<'d> = (Option<&'d str>, Option<(usize, usize)>, String);
+
+    fn paragraphs<'d>(docx: &'d Docx) -> Vec<Summary<'d>> {
+        docx.document
+            .body
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                BodyContent::Paragraph(para) => Some(para),
+                _ => None,
+            })
+            .map(|para| {
+                let numbering = para.property.numbering.as_ref().map(|numbering| {
+                    (
+                        numbering.id.as_ref().map_or(0, |id| id.value),
+                        numbering.level.as_ref().map_or(0, |level| level.value),
+                    )
+                });
+                (
+                    para.property.style_id.as_ref().map(|id| &*id.value),
+                    numbering,
+                    para.iter_text().map(|text| &**text).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn runs<'p, 'a>(para: &'p Paragraph<'a>) -> impl Iterator<Item = &'p Run<'a>> {
+        para.content.iter().filter_map(|content| match content {
+            ParagraphContent::Run(run) => Some(run),
+            _ => None,
+        })
+    }
+
+    fn import(markdown: &str) -> Docx<'static> {
+        Docx::from_markdown(markdown, &MarkdownImportOptions::default())
+    }
+
+    #[test]
+    fn lists() {
+        let docx = import("3. three\n   - nested\n4. four\n\n- a\n  1. b\n");
+
+        let item = |num_id, level, text: &str| {
+            (
+                Some("ListParagraph"),
+                Some((num_id, level)),
+                text.to_owned(),
+            )
+        };
+        assert_eq!(
+            paragraphs(&docx),
+            vec![
+                item(1, 0, "three"),
+                item(2, 1, "nested"),
+                item(1, 0, "four"),
+                item(3, 0, "a"),
+                item(4, 1, "b"),
+            ]
+        );
+
+        // ordered lists start from their first number, nested ones being
+        // lettered
+        let numbering = docx.numbering.as_ref().unwrap();
+        let first = |num_id: usize, level: usize| {
+            let level = &numbering.levels(num_id).unwrap()[level];
+            (
+                level.numbering_format.as_ref().map(|format| format.value),
+                level.start.as_ref().map(|start| start.value),
+            )
+        };
+        assert_eq!(first(1, 0), (Some(NumberingFormatVal::Decimal), Some(3)));
+        assert_eq!(first(2, 1), (Some(NumberingFormatVal::Bullet), None));
+        assert_eq!(
+            first(4, 1),
+            (Some(NumberingFormatVal::LowerLetter), Some(1))
+        );
+    }
+
+    #[test]
+    fn nested_quotes() {
+        let docx = import("> outer\n>\n> > inner\n> > more\n\nafter\n");
+
+        // every level of quotes has the quote style
+        assert_eq!(
+            paragraphs(&docx),
+            vec![
+                (Some("Quote"), None, "outer".to_owned()),
+                (Some("Quote"), None, "inner more".to_owned()),
+                (None, None, "after".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks() {
+        let docx = import("```rust\nif a {\n\n    *b = `c`;\n}\n```\n\n    indented\n");
+
+        // each line is a paragraph, blank lines included
+        let code = |text: &str| (Some("SourceCode"), None, text.to_owned());
+        assert_eq!(
+            paragraphs(&docx),
+            vec![
+                code("if a {"),
+                code(""),
+                code("    *b = `c`;"),
+                code("}"),
+                code("indented"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tables() {
+        let docx = import("| Left | Center | Right |\n| :--- | :---: | ---: |\n| a | | *c* |\n");
+
+        let table = match &docx.document.body.content[0] {
+            BodyContent::Table(table) => table,
+            content => panic!("expected a table, found {:?}", content),
+        };
+        let cells: Vec<Vec<_>> = table
+            .iter_rows()
+            .map(|row| {
+                row.iter_cells()
+                    .map(|cell| match &cell.content[..] {
+                        [TableCellContent::Paragraph(para)] => (
+                            para.property
+                                .justification
+                                .as_ref()
+                                .map(|justification| &justification.value),
+                            // whether the text is bold or italic
+                            runs(para).next().map(|run| {
+                                run.property.bold.is_some() || run.property.italics.is_some()
+                            }),
+                            para.iter_text().map(|text| &**text).collect(),
+                        ),
+                        content => panic!("expected a paragraph, found {:?}", content),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let left = Some(&JustificationVal::Left);
+        let center = Some(&JustificationVal::Center);
+        let right = Some(&JustificationVal::Right);
+        assert_eq!(
+            cells,
+            vec![
+                vec![
+                    (left, Some(true), "Left".to_owned()),
+                    (center, Some(true), "Center".to_owned()),
+                    (right, Some(true), "Right".to_owned()),
+                ],
+                vec![
+                    (left, Some(false), "a".to_owned()),
+                    // an empty cell still has a paragraph
+                    (center, None, String::new()),
+                    (right, Some(true), "c".to_owned()),
+                ],
+            ]
+        );
+        assert_eq!(table.grids[0].columns.len(), 3);
+    }
+
+    #[test]
+    fn links() {
+        let docx = import("[site](https://example.com/a) and [`code`](#intro) []()\n");
+
+        let para = match &docx.document.body.content[0] {
+            BodyContent::Paragraph(para) => para,
+            content => panic!("expected a paragraph, found {:?}", content),
+        };
+        let links: Vec<(Option<&str>, Option<&str>, String)> = para
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Link(link) => Some(link),
+                _ => None,
+            })
+            .map(|link| {
+                let target = link.id.as_ref().and_then(|id| {
+                    docx.document_rels
+                        .as_ref()
+                        .and_then(|rels| rels.get_target(id))
+                });
+                let text = link
+                    .content
+                    .iter()
+                    .flat_map(|run| run.iter_text())
+                    .map(|text| &**text)
+                    .collect();
+                (target, link.anchor.as_deref(), text)
+            })
+            .collect();
+
+        // links without text are left out
+        assert_eq!(
+            links,
+            vec![
+                (Some("https://example.com/a"), None, "site".to_owned()),
+                (None, Some("intro"), "code".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn images() {
+        let options = MarkdownImportOptions::default()
+            .image("a.png", Image::new(&b"png"[..], "png", 100, 200))
+            .image("b.jpeg", Image::new(&b"jpeg"[..], "jpeg", 300, 400));
+        let docx = Docx::from_markdown(
+            "![First](a.png) ![Second](b.jpeg) ![Again](a.png) ![Missing](c.png)\n",
+            &options,
+        );
+
+        let para = match &docx.document.body.content[0] {
+            BodyContent::Paragraph(para) => para,
+            content => panic!("expected a paragraph, found {:?}", content),
+        };
+        let drawings: Vec<_> = runs(para)
+            .flat_map(|run| &run.content)
+            .filter_map(|content| match content {
+                RunContent::Drawing(drawing) => Some(drawing),
+                _ => None,
+            })
+            .map(|drawing| {
+                let (_, data) = docx
+                    .embedded_media(drawing.embed.as_deref().unwrap())
+                    .unwrap();
+                (
+                    drawing.name.as_deref(),
+                    drawing.width,
+                    drawing.height,
+                    &data[..],
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            drawings,
+            vec![
+                (Some("First"), Some(100), Some(200), &b"png"[..]),
+                (Some("Second"), Some(300), Some(400), &b"jpeg"[..]),
+                (Some("Again"), Some(100), Some(200), &b"png"[..]),
+            ]
+        );
+        // a missing image keeps its description
+        assert!(para.iter_text().any(|text| text.contains("Missing")));
+    }
+
+    #[test]
+    fn round_trip() {
+        let markdowns = [
+            "# One\n\n## Two\n\n###### Six\n",
+            "Some *emphasis*, **strong**, ***both***, ~~strike~~ and `code`.\n",
+            "A line\\\nbroken.\n",
+            "&#32;&#32;indented\\\n&#9;tabbed\n",
+            "- a\n    - b\n        - c\n- d\n",
+            "1. one\n    - nested\n2. two\n\ntext\n\n7. seven\n8. eight\n",
+            "> quoted\n\n> again\n",
+            "```\nfn main() {\n\n    println!(\"``\");\n}\n```\n",
+            "| **A** | **B** |\n| --- | --- |\n| 1 | two<br>lines |\n",
+            "[link](https://example.com) and [anchor](#intro)\n",
+            "![Logo](images/image1.png) and text\n",
+        ];
+        let options = MarkdownImportOptions::default().image(
+            "images/image1.png",
+            Image::new(&b"png"[..], "png", 100, 100),
+        );
+
+        for markdown in markdowns {
+            let docx = Docx::from_markdown(markdown, &options);
+            let exported = docx.to_markdown(&MarkdownOptions::default());
+            assert_eq!(exported.text, markdown);
+
+            // the exported text reads the same
+            let again = Docx::from_markdown(&exported.text, &options);
+            assert_eq!(
+                again.to_markdown(&MarkdownOptions::default()).text,
+                markdown
+            );
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Markdown conversion
+//!
+//! Converts documents to CommonMark with the GitHub extensions for tables,
+//! strikethrough and footnotes, and CommonMark to documents.
+//!
+//! Markdown constructs without a direct counterpart in documents are mapped
+//! to styles, see [`StyleIds`].
+//!
+//! [`StyleIds`]: ../convert/struct.StyleIds.html
+
+mod export;
+mod import;
+
+pub use self::{export::*, import::*};
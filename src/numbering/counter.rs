@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::{
+    formatting::NumberingProperty,
+    numbering::{Level, NumberingFormatVal, Numberings},
+};
+
+/// A list item of a numbered or bulleted paragraph
+#[derive(Clone, Debug)]
+pub struct ListItem {
+    /// The id of the numbering of the list.
+    pub num_id: usize,
+    /// The level of the item, from 0.
+    pub level: usize,
+    /// The number of the item in its level.
+    pub number: usize,
+    /// The format of the numbers of the level.
+    pub format: NumberingFormatVal,
+    /// The label of the item, e.g. `1.`, `a)` or `•`.
+    pub label: String,
+}
+
+impl ListItem {
+    /// Returns `true` if the item is a bullet rather than a number.
+    pub fn is_bullet(&self) -> bool {
+        matches!(self.format, NumberingFormatVal::Bullet)
+    }
+}
+
+/// Counts the items of the lists of a document to compute their labels
+///
+/// Paragraphs are passed in document order. Items are counted by abstract
+/// numbering, so lists sharing it continue each other, and an item resets
+/// the counters of the deeper levels.
+///
+/// ```rust
+/// use docx::formatting::NumberingProperty;
+/// use docx::numbering::*;
+///
+/// let numberings = Numberings {
+///     abstract_nums: vec![AbstractNumbering {
+///         abstract_num_id: 0,
+///         levels: vec![Level {
+///             index: 0,
+///             start: Some(Start { value: 1 }),
+///             numbering_format: Some(NumberingFormat { value: NumberingFormatVal::LowerRoman }),
+///             level_text: LevelText { value: "%1.".into() },
+///         }],
+///         ..Default::default()
+///     }],
+///     nums: vec![Numbering { num_id: 1, abstract_num_id: AbstractNumberingId { value: 0 } }],
+/// };
+///
+/// let mut counter = ListCounter::new(&numberings);
+/// let item = NumberingProperty::from((1, 0));
+/// assert_eq!(counter.next(&item).unwrap().label, "i.");
+/// assert_eq!(counter.next(&item).unwrap().label, "ii.");
+/// ```
+pub struct ListCounter<'n, 'a> {
+    numberings: &'n Numberings<'a>,
+    counters: HashMap<usize, Vec<Option<usize>>>,
+}
+
+impl<'n, 'a> ListCounter<'n, 'a> {
+    pub fn new(numberings: &'n Numberings<'a>) -> Self {
+        ListCounter {
+            numberings,
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Counts a list item and returns it, or returns `None` if the
+    /// numbering isn't defined.
+    pub fn next(&mut self, property: &NumberingProperty) -> Option<ListItem> {
+        let num_id = property.id?.value;
+        // numbering id 0 removes the numbering
+        if num_id == 0 {
+            return None;
+        }
+        let level = property.level.map_or(0, |level| level.value);
+
+        let abstract_num_id = self
+            .numberings
+            .nums
+            .iter()
+            .find(|num| num.num_id == num_id)?
+            .abstract_num_id
+            .value;
+        let levels = &self
+            .numberings
+            .abstract_nums
+            .iter()
+            .find(|num| num.abstract_num_id == abstract_num_id)?
+            .levels;
+        let find_level = |index: usize| levels.iter().find(|lvl| lvl.index == index);
+        let lvl = find_level(level)?;
+
+        let counters = self.counters.entry(abstract_num_id).or_default();
+        if counters.len() <= level {
+            counters.resize(level + 1, None);
+        }
+        counters.truncate(level + 1);
+        let count = counters[level].map_or_else(|| start(lvl), |count| count + 1);
+        counters[level] = Some(count);
+
+        let mut label = String::new();
+        let mut chars = lvl.level_text.value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek().and_then(|c| c.to_digit(10))) {
+                ('%', Some(digit)) if digit > 0 => {
+                    chars.next();
+                    let index = digit as usize - 1;
+                    let lvl = find_level(index);
+                    let count = counters
+                        .get(index)
+                        .copied()
+                        .flatten()
+                        .unwrap_or_else(|| lvl.map_or(1, start));
+                    label += &format_number(count, lvl.map_or(NumberingFormatVal::Decimal, format));
+                }
+                // bullets of symbol fonts are in the private use area
+                ('\u{f000}'..='\u{f0ff}', _) => label.push('•'),
+                (c, _) => label.push(c),
+            }
+        }
+
+        Some(ListItem {
+            num_id,
+            level,
+            number: count,
+            format: format(lvl),
+            label,
+        })
+    }
+}
+
+fn start(level: &Level) -> usize {
+    level.start.as_ref().map_or(1, |start| start.value)
+}
+
+fn format(level: &Level) -> NumberingFormatVal {
+    level
+        .numbering_format
+        .as_ref()
+        .map_or(NumberingFormatVal::Decimal, |format| format.value)
+}
+
+/// Formats a number of a list.
+pub(crate) fn format_number(number: usize, format: NumberingFormatVal) -> String {
+    match format {
+        NumberingFormatVal::DecimalZero if number < 10 => format!("0{}", number),
+        NumberingFormatVal::LowerLetter => letters(number),
+        NumberingFormatVal::UpperLetter => letters(number).to_uppercase(),
+        NumberingFormatVal::LowerRoman => roman(number),
+        NumberingFormatVal::UpperRoman => roman(number).to_uppercase(),
+        NumberingFormatVal::Ordinal => {
+            let suffix = match (number % 10, number % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{}{}", number, suffix)
+        }
+        NumberingFormatVal::Bullet | NumberingFormatVal::None => String::new(),
+        _ => number.to_string(),
+    }
+}
+
+/// Formats a number as letters, repeating the letter after `z`: `a`, ..., `z`, `aa`, ...
+fn letters(number: usize) -> String {
+    if number == 0 {
+        return String::new();
+    }
+    let letter = (b'a' + ((number - 1) % 26) as u8) as char;
+    std::iter::repeat(letter).take((number - 1) / 26 + 1).collect()
+}
+
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS.iter() {
+        while number >= *value {
+            roman += numeral;
+            number -= value;
+        }
+    }
+    roman
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_numbers() {
+        assert_eq!(format_number(4, NumberingFormatVal::DecimalZero), "04");
+        assert_eq!(format_number(28, NumberingFormatVal::LowerLetter), "bb");
+        assert_eq!(
+            format_number(1994, NumberingFormatVal::UpperRoman),
+            "MCMXCIV"
+        );
+        assert_eq!(format_number(12, NumberingFormatVal::Ordinal), "12th");
+        assert_eq!(format_number(22, NumberingFormatVal::Ordinal), "22nd");
+    }
+}
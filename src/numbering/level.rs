@@ -1,24 +1,24 @@
 use crate::__string_enum;
 use crate::private_prelude::*;
 
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:lvl")]
 pub struct Level<'a> {
     #[xml(attr = "w:ilvl")]
     pub index: usize,
 
-    #[xml(child = "w:lvlText")]
-    pub level_text: LevelText<'a>,
+    #[xml(child = "w:start")]
+    pub start: Option<Start>,
 
     #[xml(child = "w:numFmt")]
     pub numbering_format: Option<NumberingFormat>,
 
-    #[xml(child = "w:start")]
-    pub start: Option<Start>,
+    #[xml(child = "w:lvlText")]
+    pub level_text: LevelText<'a>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:lvlText")]
 pub struct LevelText<'a> {
@@ -26,7 +26,7 @@ pub struct LevelText<'a> {
     pub value: Cow<'a, str>,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:numFmt")]
 pub struct NumberingFormat {
@@ -34,11 +34,12 @@ pub struct NumberingFormat {
     pub value: NumberingFormatVal,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum NumberingFormatVal {
     ArabicAlpha,
     Bullet,
+    #[default]
     Decimal,
     DecimalZero,
     LowerLetter,
@@ -49,12 +50,6 @@ pub enum NumberingFormatVal {
     None,
 }
 
-impl Default for NumberingFormatVal {
-    fn default() -> Self {
-        Self::Decimal
-    }
-}
-
 __string_enum! {
     NumberingFormatVal {
         ArabicAlpha = "arabicAlpha",
@@ -70,7 +65,7 @@ __string_enum! {
     }
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:start")]
 pub struct Start {
@@ -2,16 +2,20 @@
 //!
 //! The corresponding ZIP item is `/word/numbering.xml`.
 
+mod counter;
 mod level;
 
 pub use self::{
+    counter::*,
     level::*,
 };
 
-use crate::private_prelude::*;
+use std::io::Write;
+
+use crate::{private_prelude::*, schema::SCHEMA_MAIN};
 
 /// The root element of the numbering document part.
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:numbering")]
 pub struct Numberings<'a> {
@@ -22,7 +26,109 @@ pub struct Numberings<'a> {
     pub nums: Vec<Numbering>,
 }
 
-#[derive(Debug, Default, XmlRead)]
+impl<'a> Numberings<'a> {
+    /// Adds the numbering of a new list and returns its id.
+    ///
+    /// Every level uses the given format, starting from `start`. Bulleted
+    /// levels alternate between `•`, `◦` and `▪`, the others are labelled
+    /// like `1.`.
+    pub fn push_list(&mut self, format: NumberingFormatVal, start: usize) -> usize {
+        const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+        let levels = (0..9)
+            .map(|index| {
+                let bullet = matches!(format, NumberingFormatVal::Bullet);
+                Level {
+                    index,
+                    start: (!bullet).then_some(Start { value: start }),
+                    numbering_format: Some(NumberingFormat { value: format }),
+                    level_text: LevelText {
+                        value: if bullet {
+                            BULLETS[index % 3].into()
+                        } else {
+                            format!("%{}.", index + 1).into()
+                        },
+                    },
+                }
+            })
+            .collect();
+
+        self.push_levels(levels)
+    }
+
+    /// Adds the numbering of a new list with the given levels and returns
+    /// its id.
+    pub fn push_levels(&mut self, levels: Vec<Level<'a>>) -> usize {
+        let abstract_num_id = self
+            .abstract_nums
+            .iter()
+            .map(|num| num.abstract_num_id + 1)
+            .max()
+            .unwrap_or_default();
+        self.abstract_nums.push(AbstractNumbering {
+            abstract_num_id,
+            levels,
+            ..Default::default()
+        });
+
+        let num_id = self.nums.iter().map(|num| num.num_id).max().unwrap_or_default() + 1;
+        self.nums.push(Numbering {
+            num_id,
+            abstract_num_id: AbstractNumberingId {
+                value: abstract_num_id,
+            },
+        });
+
+        num_id
+    }
+
+    /// Returns the levels of a numbering.
+    pub fn levels(&self, num_id: usize) -> Option<&[Level<'a>]> {
+        let abstract_num_id = self
+            .nums
+            .iter()
+            .find(|num| num.num_id == num_id)?
+            .abstract_num_id
+            .value;
+        self.abstract_nums
+            .iter()
+            .find(|num| num.abstract_num_id == abstract_num_id)
+            .map(|num| num.levels.as_slice())
+    }
+}
+
+impl<'a> XmlWrite for Numberings<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let Numberings {
+            abstract_nums,
+            nums,
+        } = self;
+
+        log::debug!("[Numberings] Started writing.");
+
+        writer.write_element_start("w:numbering")?;
+
+        writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
+
+        writer.write_element_end_open()?;
+
+        for ele in abstract_nums {
+            ele.to_writer(writer)?;
+        }
+
+        for ele in nums {
+            ele.to_writer(writer)?;
+        }
+
+        writer.write_element_end_close("w:numbering")?;
+
+        log::debug!("[Numberings] Finished writing.");
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:abstractNum")]
 pub struct AbstractNumbering<'a> {
@@ -30,14 +136,14 @@ pub struct AbstractNumbering<'a> {
     #[xml(attr = "w:abstractNumId")]
     pub abstract_num_id: usize,
 
-    #[xml(child = "w:lvl")]
-    pub levels: Vec<Level<'a>>,
-
     #[xml(child = "w:numStyleLink")]
     pub num_style_link: Option<NumStyleLink<'a>>,
+
+    #[xml(child = "w:lvl")]
+    pub levels: Vec<Level<'a>>,
 }
 
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:num")]
 pub struct Numbering {
@@ -58,7 +164,7 @@ pub struct AbstractNumberingId {
     pub value: usize,
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:numStyleLink")]
 pub struct NumStyleLink<'a> {
@@ -0,0 +1,1402 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{Seek, Write},
+    path::Path,
+};
+
+use bytes::Bytes;
+use hard_xml::utils::xml_escape;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    content_type::image_content_type,
+    convert::is_on,
+    document::{
+        BodyContent, Break, BreakType, Drawing, Paragraph, ParagraphContent, Run, RunContent,
+        Table, TableCell, TableCellContent,
+    },
+    error::DocxResult,
+    formatting::{
+        BorderStyle, CharacterProperty, HeaderFooterType, JustificationVal, PageOrientation,
+        ParagraphProperty, SectionProperty, TabLeader, TabStopType, TableJustificationVal,
+        UnderlineStyle, VerticalAlignVal,
+    },
+    numbering::{Level, NumberingFormatVal, Numberings},
+    odt::{emus_length, style_name, twips_length, ConversionLog, PREFIXES},
+    styles::StyleType,
+    toc::TableOfContents,
+    Docx,
+};
+
+/// The media type of text documents.
+pub(crate) const MIME_TYPE: &str = "application/vnd.oasis.opendocument.text";
+
+/// Returns the namespaces declared by the root element of each part.
+fn namespaces() -> String {
+    let mut xml: String = PREFIXES
+        .iter()
+        .map(|(prefix, uri)| format!(" xmlns:{}=\"{}\"", prefix, uri))
+        .collect();
+    xml += " office:version=\"1.3\"";
+    xml
+}
+
+/// A document converted to an ODT package
+#[derive(Clone, Debug, Default)]
+pub struct Odt {
+    /// The `content.xml` part.
+    pub content: String,
+    /// The `styles.xml` part.
+    pub styles: String,
+    /// The `meta.xml` part.
+    pub meta: String,
+    /// The pictures, by path in the package.
+    pub pictures: BTreeMap<String, Bytes>,
+    /// The constructs which couldn't be converted faithfully.
+    pub log: ConversionLog,
+}
+
+impl Odt {
+    /// Writes the package.
+    pub fn write<W: Write + Seek>(&self, writer: W) -> DocxResult<W> {
+        let mut zip = ZipWriter::new(writer);
+
+        // the media type comes first and uncompressed, so that it can be
+        // recognized without unzipping
+        zip.start_file(
+            "mimetype",
+            FileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(MIME_TYPE.as_bytes())?;
+
+        let opt = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file("META-INF/manifest.xml", opt)?;
+        zip.write_all(self.manifest().as_bytes())?;
+
+        for (name, part) in [
+            ("content.xml", &self.content),
+            ("styles.xml", &self.styles),
+            ("meta.xml", &self.meta),
+        ] {
+            zip.start_file(name, opt)?;
+            zip.write_all(part.as_bytes())?;
+        }
+
+        for (name, data) in &self.pictures {
+            zip.start_file(name, opt)?;
+            zip.write_all(data)?;
+        }
+
+        Ok(zip.finish()?)
+    }
+
+    /// Writes the package to a file.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> DocxResult<File> {
+        let file = File::create(path)?;
+        self.write(file)
+    }
+
+    fn manifest(&self) -> String {
+        let mut manifest = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" manifest:version=\"1.3\">",
+        );
+        let mut entry = |path: &str, ty: &str| {
+            manifest += &format!(
+                "<manifest:file-entry manifest:full-path=\"{}\" manifest:media-type=\"{}\"/>",
+                xml_escape(path),
+                ty
+            );
+        };
+        entry("/", MIME_TYPE);
+        for name in ["content.xml", "styles.xml", "meta.xml"] {
+            entry(name, "text/xml");
+        }
+        for name in self.pictures.keys() {
+            let ext = name.rsplit('.').next().unwrap_or_default();
+            entry(
+                name,
+                image_content_type(&ext.to_ascii_lowercase()).unwrap_or("application/octet-stream"),
+            );
+        }
+        manifest += "</manifest:manifest>";
+        manifest
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Converts the document to an ODT package.
+    ///
+    /// Styles become named styles and direct formatting automatic styles.
+    /// The headers, footers and page layout are taken from the last section.
+    /// Tracked changes are accepted.
+    ///
+    /// ```rust
+    /// use docx::document::Paragraph;
+    /// use docx::formatting::ParagraphProperty;
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Minutes"),
+    /// );
+    ///
+    /// let odt = docx.to_odt();
+    /// assert!(odt.content.contains(
+    ///     "<text:h text:style-name=\"Heading1\" text:outline-level=\"1\">Minutes</text:h>"
+    /// ));
+    /// assert!(odt.log.is_empty());
+    /// ```
+    pub fn to_odt(&self) -> Odt {
+        let numbering = Default::default();
+        let mut writer = Writer {
+            docx: self,
+            numberings: self.numbering.as_ref().unwrap_or(&numbering),
+            toc: (1..=6).fold(TableOfContents::default(), |toc, level| {
+                toc.heading_style(format!("Heading{}", level), level)
+            }),
+            log: ConversionLog::default(),
+            out: String::new(),
+            automatic: AutomaticStyles::default(),
+            lists: Vec::new(),
+            continued_lists: HashMap::new(),
+            pictures: BTreeMap::new(),
+            bookmarks: HashMap::new(),
+            break_before: None,
+            space: true,
+            notes: 0,
+            tables: 0,
+            part: None,
+        };
+
+        // the header and footer, whose automatic styles go to the styles part
+        let section = self.last_section(&mut writer.log);
+        let header = writer.header_footer(section, true);
+        let footer = writer.header_footer(section, false);
+        let master_styles = std::mem::take(&mut writer.automatic);
+
+        writer.blocks(&self.document.body.content);
+        writer.close_lists();
+        let body = std::mem::take(&mut writer.out);
+
+        let content = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-content{}><office:automatic-styles>{}</office:automatic-styles>\
+             <office:body><office:text>{}</office:text></office:body></office:document-content>",
+            namespaces(),
+            writer.automatic.xml,
+            body
+        );
+
+        let mut master_page = String::from(
+            "<style:master-page style:name=\"Standard\" style:page-layout-name=\"pm1\">",
+        );
+        if let Some(header) = &header {
+            master_page += &format!("<style:header>{}</style:header>", header);
+        }
+        if let Some(footer) = &footer {
+            master_page += &format!("<style:footer>{}</style:footer>", footer);
+        }
+        master_page += "</style:master-page>";
+
+        let styles = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-styles{}><office:styles>{}</office:styles>\
+             <office:automatic-styles>{}{}</office:automatic-styles>\
+             <office:master-styles>{}</office:master-styles></office:document-styles>",
+            namespaces(),
+            writer.named_styles(),
+            page_layout(section, header.is_some(), footer.is_some()),
+            master_styles.xml,
+            master_page
+        );
+
+        Odt {
+            content,
+            styles,
+            meta: self.meta(),
+            pictures: writer.pictures,
+            log: writer.log,
+        }
+    }
+
+    /// Returns the properties of the last section, which apply to the whole
+    /// converted document.
+    fn last_section(&self, log: &mut ConversionLog) -> Option<&SectionProperty<'a>> {
+        let mut sections = Vec::new();
+        for content in &self.document.body.content {
+            if let BodyContent::Paragraph(para) = content {
+                if let Some(section) = &para.property.section {
+                    sections.push(section);
+                }
+            }
+        }
+        if !sections.is_empty() {
+            log.report(
+                "w:sectPr",
+                "section breaks are dropped, the last section applies to the whole document",
+            );
+        }
+        self.document.body.section.as_ref().or(sections.pop())
+    }
+
+    fn meta(&self) -> String {
+        let mut meta = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-meta{}><office:meta><meta:generator>docx-rs</meta:generator>",
+            namespaces()
+        );
+        if let Some(core) = &self.core {
+            let mut element = |name: &str, value: Option<&str>| {
+                if let Some(value) = value.filter(|value| !value.is_empty()) {
+                    meta += &format!("<{}>{}</{}>", name, xml_escape(value), name);
+                }
+            };
+            element("dc:title", core.title.as_deref());
+            element("dc:subject", core.subject.as_deref());
+            element("dc:description", core.description.as_deref());
+            element("meta:initial-creator", core.creator.as_deref());
+            element("dc:creator", core.last_modified_by.as_deref());
+            for keyword in core
+                .keywords
+                .iter()
+                .flat_map(|keywords| keywords.split([',', ';']))
+            {
+                element("meta:keyword", Some(keyword.trim()));
+            }
+            if let Some(revision) = core
+                .revision
+                .as_ref()
+                .and_then(|revision| revision.trim().parse::<usize>().ok())
+            {
+                element("meta:editing-cycles", Some(&revision.to_string()));
+            }
+        }
+        meta += "</office:meta></office:document-meta>";
+        meta
+    }
+}
+
+/// The automatic styles of a part
+#[derive(Default)]
+struct AutomaticStyles {
+    /// The names of the styles, by definition.
+    names: HashMap<String, String>,
+    /// The number of styles, by prefix.
+    counts: HashMap<&'static str, usize>,
+    xml: String,
+}
+
+impl AutomaticStyles {
+    /// Returns the name of a paragraph, text, table or cell style, defining
+    /// it the first time.
+    fn style(&mut self, prefix: &'static str, family: &str, attrs: &str, content: &str) -> String {
+        self.define(prefix, |name| {
+            format!(
+                "<style:style style:name=\"{}\" style:family=\"{}\"{}>{}</style:style>",
+                name, family, attrs, content
+            )
+        })
+    }
+
+    /// Returns the name of an automatic style, defined by a function of its
+    /// name.
+    fn define(&mut self, prefix: &'static str, definition: impl Fn(&str) -> String) -> String {
+        let key = definition("");
+        if let Some(name) = self.names.get(&key) {
+            return name.clone();
+        }
+        let count = self.counts.entry(prefix).or_default();
+        *count += 1;
+        let name = format!("{}{}", prefix, count);
+        self.xml += &definition(&name);
+        self.names.insert(key, name.clone());
+        name
+    }
+}
+
+struct Writer<'d, 'n, 'a> {
+    docx: &'d Docx<'a>,
+    numberings: &'n Numberings<'a>,
+    toc: TableOfContents<'static>,
+    log: ConversionLog,
+    out: String,
+    automatic: AutomaticStyles,
+    /// The numbering ids of the lists being written, the innermost last.
+    lists: Vec<usize>,
+    /// The ids of the last lists written, by numbering id, which the next
+    /// lists continue.
+    continued_lists: HashMap<usize, String>,
+    pictures: BTreeMap<String, Bytes>,
+    /// The names of the bookmarks, by id.
+    bookmarks: HashMap<String, String>,
+    /// The break of a run, which the next paragraph starts with.
+    break_before: Option<&'static str>,
+    /// Whether the text so far ends with a space, which the following
+    /// space would collapse into.
+    space: bool,
+    notes: usize,
+    tables: usize,
+    /// The ZIP item name of the header or footer part being written, whose
+    /// relationships resolve the pictures and hyperlinks.
+    part: Option<&'d str>,
+}
+
+impl<'d, 'n, 'a> Writer<'d, 'n, 'a> {
+    /// Writes the content of the default header or footer of a section.
+    fn header_footer(
+        &mut self,
+        section: Option<&SectionProperty<'a>>,
+        header: bool,
+    ) -> Option<String> {
+        let docx = self.docx;
+        let references: Vec<(Option<&HeaderFooterType>, &str)> = match section {
+            Some(section) if header => section
+                .headers
+                .iter()
+                .map(|reference| (reference.ty.as_ref(), &*reference.id))
+                .collect(),
+            Some(section) => section
+                .footers
+                .iter()
+                .map(|reference| (reference.ty.as_ref(), &*reference.id))
+                .collect(),
+            None => Vec::new(),
+        };
+        let (element, first_part) = if header {
+            ("w:headerReference", docx.headers.keys().next())
+        } else {
+            ("w:footerReference", docx.footers.keys().next())
+        };
+
+        let mut name = None;
+        for (ty, id) in references {
+            if matches!(ty, None | Some(HeaderFooterType::Default)) {
+                name = docx
+                    .document_rels
+                    .as_ref()
+                    .and_then(|rels| rels.get_target(id))
+                    .map(|target| format!("word/{}", target.trim_start_matches('/')));
+            } else {
+                self.log
+                    .report(element, "first and even page variants are dropped");
+            }
+        }
+        // the parts which no section references, like in documents built
+        // before section properties were modeled
+        let name = name.or_else(|| match section {
+            Some(section) if !section.headers.is_empty() || !section.footers.is_empty() => None,
+            _ => first_part.cloned(),
+        })?;
+
+        let (name, content) = if header {
+            let (name, header) = docx.headers.get_key_value(&name)?;
+            (name, &header.content)
+        } else {
+            let (name, footer) = docx.footers.get_key_value(&name)?;
+            (name, &footer.content)
+        };
+
+        self.part = Some(name);
+        let content = self.nested(|writer| writer.blocks(content));
+        self.part = None;
+        Some(content)
+    }
+
+    /// Writes blocks to a string of their own, and returns it.
+    fn nested(&mut self, write: impl FnOnce(&mut Self)) -> String {
+        let out = std::mem::take(&mut self.out);
+        let lists = std::mem::take(&mut self.lists);
+        let space = self.space;
+
+        write(self);
+        self.close_lists();
+
+        self.space = space;
+        self.lists = lists;
+        std::mem::replace(&mut self.out, out)
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para),
+                BodyContent::Table(table) => {
+                    self.close_lists();
+                    self.table(table);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph) {
+        let break_before = self.break_before.take();
+        let inline = self.inline(&para.content);
+        let style = self.paragraph_style(&para.property, break_before);
+        let style_attr = style
+            .map(|style| format!(" text:style-name=\"{}\"", style))
+            .unwrap_or_default();
+
+        if let Some(level) = self.toc.heading_level(para, &self.docx.styles) {
+            self.close_lists();
+            self.out += &format!(
+                "<text:h{} text:outline-level=\"{}\">{}</text:h>",
+                style_attr, level, inline
+            );
+            return;
+        }
+
+        let item = para.property.numbering.as_ref().and_then(|numbering| {
+            let num_id = numbering.id?.value;
+            self.numberings.levels(num_id)?;
+            Some((num_id, numbering.level.map_or(0, |level| level.value)))
+        });
+        match item {
+            Some((num_id, level)) => self.list_item(num_id, level),
+            None => self.close_lists(),
+        }
+
+        self.out += &format!("<text:p{}>{}</text:p>", style_attr, inline);
+    }
+
+    /// Returns the style of a paragraph, an automatic style if it has direct
+    /// formatting.
+    fn paragraph_style(
+        &mut self,
+        property: &ParagraphProperty,
+        break_before: Option<&str>,
+    ) -> Option<String> {
+        let parent = property
+            .style_id
+            .as_ref()
+            .map(|style_id| style_name(&style_id.value));
+
+        let (mut attrs, children) = paragraph_properties(property);
+        if let Some(break_before) = break_before {
+            attrs += &format!(" fo:break-before=\"{}\"", break_before);
+        }
+        if attrs.is_empty() && children.is_empty() {
+            return parent;
+        }
+
+        let parent_attr = parent
+            .map(|parent| format!(" style:parent-style-name=\"{}\"", parent))
+            .unwrap_or_default();
+        let content = format!(
+            "<style:paragraph-properties{}>{}</style:paragraph-properties>",
+            attrs, children
+        );
+        Some(
+            self.automatic
+                .style("P", "paragraph", &parent_attr, &content),
+        )
+    }
+
+    fn list_item(&mut self, num_id: usize, level: usize) {
+        if self.lists.first().is_some_and(|first| *first != num_id) {
+            self.close_lists();
+        }
+        while self.lists.len() > level + 1 {
+            self.close_list();
+        }
+        if self.lists.len() == level + 1 {
+            self.out += "</text:list-item><text:list-item>";
+        }
+        while self.lists.len() < level + 1 {
+            if self.lists.is_empty() {
+                let style = self.list_style(num_id);
+                let id = format!("list{}_{}", num_id, self.continued_lists.len() + 1);
+                self.out += &format!("<text:list xml:id=\"{}\" text:style-name=\"{}\"", id, style);
+                if let Some(continued) = self.continued_lists.insert(num_id, id) {
+                    self.out += &format!(" text:continue-list=\"{}\"", continued);
+                }
+                self.out += "><text:list-item>";
+            } else {
+                self.out += "<text:list><text:list-item>";
+            }
+            self.lists.push(num_id);
+        }
+    }
+
+    fn close_list(&mut self) {
+        if self.lists.pop().is_some() {
+            self.out += "</text:list-item></text:list>";
+        }
+    }
+
+    fn close_lists(&mut self) {
+        while !self.lists.is_empty() {
+            self.close_list();
+        }
+    }
+
+    /// Returns the list style of a numbering, defining it the first time.
+    fn list_style(&mut self, num_id: usize) -> String {
+        let levels = self.numberings.levels(num_id).unwrap_or_default();
+        if levels.is_empty() {
+            self.log
+                .report("w:numStyleLink", "numbering styles are dropped");
+        }
+
+        let mut content = String::new();
+        for level in levels.iter().filter(|level| level.index < 10) {
+            content += &list_level(level, &mut self.log);
+        }
+        self.automatic.define("L", |name| {
+            format!(
+                "<text:list-style style:name=\"{}\">{}</text:list-style>",
+                name, content
+            )
+        })
+    }
+
+    fn table(&mut self, table: &Table) {
+        self.tables += 1;
+
+        // the cells of each row, with their first column
+        let rows: Vec<Vec<(usize, &TableCell)>> = table
+            .iter_rows()
+            .map(|row| {
+                let mut column = 0;
+                row.iter_cells()
+                    .map(|cell| {
+                        let start = column;
+                        column += span(cell);
+                        (start, cell)
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = table
+            .grids
+            .first()
+            .map(|grid| grid.columns.iter().map(|column| column.width).collect())
+            .unwrap_or_default();
+        let columns = rows
+            .iter()
+            .filter_map(|cells| cells.last().map(|(start, cell)| start + span(cell)))
+            .chain(Some(widths.len()))
+            .max()
+            .unwrap_or_default();
+
+        let align = match table
+            .property
+            .justification
+            .as_ref()
+            .and_then(|justification| justification.value.as_ref())
+        {
+            Some(TableJustificationVal::Center) => "center",
+            Some(TableJustificationVal::Right) | Some(TableJustificationVal::End) => "right",
+            _ if widths.is_empty() => "margins",
+            _ => "left",
+        };
+        let mut table_attrs = format!(" table:align=\"{}\"", align);
+        if !widths.is_empty() {
+            let width = widths.iter().sum::<usize>() as isize;
+            table_attrs += &format!(" style:width=\"{}\"", twips_length(width));
+        }
+        let table_style = self.automatic.style(
+            "Table",
+            "table",
+            "",
+            &format!("<style:table-properties{}/>", table_attrs),
+        );
+        let cell_style = (table.property.borders.is_some() || table.property.style_id.is_some())
+            .then(|| {
+                self.automatic.style(
+                    "Cell",
+                    "table-cell",
+                    "",
+                    "<style:table-cell-properties fo:padding=\"0.04in\" fo:border=\"0.5pt solid #000000\"/>",
+                )
+            });
+
+        self.out += &format!(
+            "<table:table table:name=\"Table{}\" table:style-name=\"{}\">",
+            self.tables, table_style
+        );
+        for column in 0..columns {
+            match widths.get(column) {
+                Some(width) => {
+                    let style = self.automatic.style(
+                        "Column",
+                        "table-column",
+                        "",
+                        &format!(
+                            "<style:table-column-properties style:column-width=\"{}\"/>",
+                            twips_length(*width as isize)
+                        ),
+                    );
+                    self.out += &format!("<table:table-column table:style-name=\"{}\"/>", style);
+                }
+                None => self.out += "<table:table-column/>",
+            }
+        }
+
+        for (index, cells) in rows.iter().enumerate() {
+            self.out += "<table:table-row>";
+            let mut column = 0;
+            for (start, cell) in cells {
+                let span = span(cell);
+                column = start + span;
+
+                let merge = cell.property.vertical_merge.as_ref();
+                if merge.is_some_and(|merge| !merge.is_restart()) {
+                    self.out += &"<table:covered-table-cell/>".repeat(span);
+                    continue;
+                }
+
+                let mut attrs = String::new();
+                if let Some(style) = &cell_style {
+                    attrs += &format!(" table:style-name=\"{}\"", style);
+                }
+                attrs += " office:value-type=\"string\"";
+                if span > 1 {
+                    attrs += &format!(" table:number-columns-spanned=\"{}\"", span);
+                }
+                if merge.is_some() {
+                    // the cells below which continue the merge
+                    let rows_spanned = 1 + rows[index + 1..]
+                        .iter()
+                        .take_while(|cells| {
+                            cells.iter().any(|(other, cell)| {
+                                other == start
+                                    && cell
+                                        .property
+                                        .vertical_merge
+                                        .as_ref()
+                                        .is_some_and(|merge| !merge.is_restart())
+                            })
+                        })
+                        .count();
+                    attrs += &format!(" table:number-rows-spanned=\"{}\"", rows_spanned);
+                }
+
+                let content = self.nested(|writer| writer.cell_blocks(&cell.content));
+                self.out += &format!("<table:table-cell{}>{}</table:table-cell>", attrs, content);
+                self.out += &"<table:covered-table-cell/>".repeat(span - 1);
+            }
+            // rows may end before the last column
+            for _ in column..columns {
+                self.out += "<table:table-cell/>";
+            }
+            self.out += "</table:table-row>";
+        }
+        self.out += "</table:table>";
+    }
+
+    fn cell_blocks(&mut self, content: &[TableCellContent]) {
+        for content in content {
+            match content {
+                TableCellContent::Paragraph(para) => self.paragraph(para),
+                TableCellContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+                TableCellContent::Table(table) => {
+                    self.close_lists();
+                    self.table(table);
+                }
+            }
+        }
+    }
+
+    /// Converts the content of a paragraph.
+    fn inline(&mut self, content: &[ParagraphContent]) -> String {
+        let mut xml = String::new();
+        self.space = true;
+        self.inline_content(content, &mut xml);
+        xml
+    }
+
+    fn inline_content(&mut self, content: &[ParagraphContent], xml: &mut String) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => self.run(run, xml),
+                ParagraphContent::Link(hyperlink) => {
+                    let href = match (&hyperlink.id, &hyperlink.anchor) {
+                        (Some(id), _) => self
+                            .docx
+                            .relationships(self.part)
+                            .and_then(|rels| rels.get_target(id))
+                            .map(str::to_owned),
+                        (None, Some(anchor)) => Some(format!("#{}", anchor)),
+                        (None, None) => None,
+                    };
+                    let Some(run) = &hyperlink.content else {
+                        continue;
+                    };
+                    match href {
+                        Some(href) => {
+                            *xml += &format!(
+                                "<text:a xlink:type=\"simple\" xlink:href=\"{}\">",
+                                xml_escape(&href)
+                            );
+                            self.run(run, xml);
+                            *xml += "</text:a>";
+                        }
+                        None => self.run(run, xml),
+                    }
+                }
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    if let Some(name) = &bookmark.name {
+                        // the bookmark of the last editing position
+                        if name != "_GoBack" {
+                            if let Some(id) = &bookmark.id {
+                                self.bookmarks.insert(id.to_string(), name.to_string());
+                            }
+                            *xml += &format!(
+                                "<text:bookmark-start text:name=\"{}\"/>",
+                                xml_escape(name)
+                            );
+                        }
+                    }
+                }
+                ParagraphContent::BookmarkEnd(bookmark) => {
+                    let name = bookmark
+                        .id
+                        .as_ref()
+                        .and_then(|id| self.bookmarks.remove(&**id));
+                    if let Some(name) = name {
+                        *xml +=
+                            &format!("<text:bookmark-end text:name=\"{}\"/>", xml_escape(&name));
+                    }
+                }
+                ParagraphContent::SmartTag(tag) => self.inline_content(&tag.content, xml),
+                ParagraphContent::Sdt(sdt) => self.inline_content(&sdt.content.content, xml),
+                ParagraphContent::SimpleField(field) => self.inline_content(&field.content, xml),
+                ParagraphContent::Insertion(ins) => {
+                    self.log.report("w:ins", "tracked changes are accepted");
+                    self.inline_content(&ins.content, xml);
+                }
+                ParagraphContent::MoveTo(to) => {
+                    self.log.report("w:moveTo", "tracked changes are accepted");
+                    self.inline_content(&to.content, xml);
+                }
+                ParagraphContent::Deletion(_) => {
+                    self.log.report("w:del", "tracked changes are accepted");
+                }
+                ParagraphContent::MoveFrom(_) => {
+                    self.log
+                        .report("w:moveFrom", "tracked changes are accepted");
+                }
+                ParagraphContent::CommentRangeStart(_) | ParagraphContent::CommentRangeEnd(_) => {}
+            }
+        }
+    }
+
+    fn run(&mut self, run: &Run, xml: &mut String) {
+        let mut content = String::new();
+        for run_content in &run.content {
+            match run_content {
+                RunContent::Text(text) => self.text(&text.text, &mut content),
+                RunContent::Tab(_) => {
+                    content += "<text:tab/>";
+                    self.space = true;
+                }
+                RunContent::Break(Break {
+                    ty: Some(BreakType::Page),
+                }) => self.break_before = Some("page"),
+                RunContent::Break(Break {
+                    ty: Some(BreakType::Column),
+                }) => self.break_before = Some("column"),
+                RunContent::Break(_) => {
+                    content += "<text:line-break/>";
+                    self.space = true;
+                }
+                RunContent::Drawing(drawing) => self.image(drawing, &mut content),
+                RunContent::FootnoteReference(reference) => {
+                    self.note("footnote", &reference.id, &mut content)
+                }
+                RunContent::EndnoteReference(reference) => {
+                    self.note("endnote", &reference.id, &mut content)
+                }
+                RunContent::CommentReference(reference) => {
+                    self.annotation(&reference.id, &mut content)
+                }
+                RunContent::InstrText(_) => self
+                    .log
+                    .report("w:instrText", "fields are replaced by their results"),
+                RunContent::FieldChar(_)
+                | RunContent::DeletedText(_)
+                | RunContent::FootnoteRef(_)
+                | RunContent::EndnoteRef(_) => (),
+            }
+        }
+        if content.is_empty() {
+            return;
+        }
+
+        // a span of the character style, around a span of the direct formatting
+        let mut spans = Vec::new();
+        if let Some(style_id) = &run.property.style_id {
+            spans.push(style_name(&style_id.value));
+        }
+        let attrs = text_properties(&run.property);
+        if !attrs.is_empty() {
+            spans.push(self.automatic.style(
+                "T",
+                "text",
+                "",
+                &format!("<style:text-properties{}/>", attrs),
+            ));
+        }
+
+        for span in &spans {
+            *xml += &format!("<text:span text:style-name=\"{}\">", span);
+        }
+        *xml += &content;
+        for _ in &spans {
+            *xml += "</text:span>";
+        }
+    }
+
+    /// Writes text, where consecutive spaces are explicit.
+    fn text(&mut self, text: &str, xml: &mut String) {
+        let mut spaces = 0;
+        for c in text.chars() {
+            if c == ' ' {
+                if self.space {
+                    spaces += 1;
+                } else {
+                    xml.push(' ');
+                    self.space = true;
+                }
+                continue;
+            }
+
+            write_spaces(spaces, xml);
+            spaces = 0;
+            match c {
+                '\t' => *xml += "<text:tab/>",
+                '\n' => *xml += "<text:line-break/>",
+                '&' => *xml += "&amp;",
+                '<' => *xml += "&lt;",
+                '>' => *xml += "&gt;",
+                _ => xml.push(c),
+            }
+            self.space = matches!(c, '\t' | '\n');
+        }
+        write_spaces(spaces, xml);
+    }
+
+    fn image(&mut self, drawing: &Drawing, xml: &mut String) {
+        let media = drawing
+            .embed
+            .as_deref()
+            .and_then(|embed| self.docx.part_media(self.part, embed));
+        let Some((name, data)) = media else {
+            self.log
+                .report("w:drawing", "drawings other than pictures are dropped");
+            return;
+        };
+        let file_name = name.rsplit('/').next().unwrap_or(name);
+        let path = format!("Pictures/{}", file_name);
+        self.pictures.insert(path.clone(), data.clone());
+
+        let mut attrs = String::new();
+        if let (Some(width), Some(height)) = (drawing.width, drawing.height) {
+            attrs += &format!(
+                " svg:width=\"{}\" svg:height=\"{}\"",
+                emus_length(width),
+                emus_length(height)
+            );
+        }
+        let frame_name = match &drawing.name {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => format!("Image{}", self.pictures.len()),
+        };
+        *xml += &format!(
+            "<draw:frame draw:name=\"{}\" text:anchor-type=\"as-char\"{}>\
+             <draw:image xlink:href=\"{}\" xlink:type=\"simple\" xlink:show=\"embed\" xlink:actuate=\"onLoad\"/>\
+             </draw:frame>",
+            xml_escape(&frame_name),
+            attrs,
+            xml_escape(&path)
+        );
+        self.space = false;
+    }
+
+    fn note(&mut self, class: &str, id: &str, xml: &mut String) {
+        let docx = self.docx;
+        let content = if class == "footnote" {
+            docx.footnotes
+                .as_ref()
+                .and_then(|notes| notes.get(id))
+                .map(|note| &note.content)
+        } else {
+            docx.endnotes
+                .as_ref()
+                .and_then(|notes| notes.get(id))
+                .map(|note| &note.content)
+        };
+        let Some(content) = content else {
+            return;
+        };
+
+        self.notes += 1;
+        let body = self.nested(|writer| writer.blocks(content));
+        *xml += &format!(
+            "<text:note text:id=\"{}{}\" text:note-class=\"{}\"><text:note-citation>{}</text:note-citation>\
+             <text:note-body>{}</text:note-body></text:note>",
+            &class[..3],
+            self.notes,
+            class,
+            self.notes,
+            body
+        );
+        self.space = false;
+    }
+
+    fn annotation(&mut self, id: &str, xml: &mut String) {
+        let docx = self.docx;
+        let Some(comment) = docx
+            .comments
+            .as_ref()
+            .and_then(|comments| comments.comments.iter().find(|comment| comment.id == id))
+        else {
+            return;
+        };
+
+        let body = self.nested(|writer| {
+            for para in &comment.content {
+                writer.paragraph(para);
+            }
+        });
+        *xml += &format!(
+            "<office:annotation><dc:creator>{}</dc:creator>",
+            xml_escape(&comment.author)
+        );
+        if !comment.date.is_empty() {
+            *xml += &format!("<dc:date>{}</dc:date>", xml_escape(&comment.date));
+        }
+        *xml += &body;
+        *xml += "</office:annotation>";
+    }
+
+    /// Writes the document defaults and the paragraph and character styles.
+    fn named_styles(&mut self) -> String {
+        let styles = &self.docx.styles;
+
+        let (paragraph, children) = paragraph_properties(&styles.default.paragraph.inner);
+        let text = text_properties(&styles.default.character.inner);
+        let mut xml = format!(
+            "<style:default-style style:family=\"paragraph\">{}</style:default-style>",
+            properties(&paragraph, &children, &text)
+        );
+
+        for style in &styles.styles {
+            let family = match style.ty {
+                StyleType::Paragraph => "paragraph",
+                StyleType::Character => "text",
+                StyleType::Table | StyleType::Numbering => {
+                    self.log
+                        .report("w:style", "table and numbering styles are dropped");
+                    continue;
+                }
+            };
+
+            let mut attrs = format!(
+                " style:name=\"{}\" style:family=\"{}\"",
+                style_name(&style.style_id),
+                family
+            );
+            if let Some(name) = &style.name {
+                attrs += &format!(" style:display-name=\"{}\"", xml_escape(&name.value));
+            }
+            if let Some(based_on) = &style.based_on {
+                attrs += &format!(
+                    " style:parent-style-name=\"{}\"",
+                    style_name(&based_on.value)
+                );
+            }
+
+            let text = text_properties(&style.character);
+            let content = if style.ty == StyleType::Paragraph {
+                let para = Paragraph::default()
+                    .property(ParagraphProperty::default().style_id(style.style_id.clone()));
+                if let Some(level) = self.toc.heading_level(&para, styles) {
+                    attrs += &format!(" style:default-outline-level=\"{}\"", level);
+                }
+                if style.paragraph.numbering.is_some() {
+                    self.log
+                        .report("w:numPr", "the numbering of paragraph styles is dropped");
+                }
+                let (paragraph, children) = paragraph_properties(&style.paragraph);
+                properties(&paragraph, &children, &text)
+            } else {
+                properties("", "", &text)
+            };
+
+            xml += &format!("<style:style{}>{}</style:style>", attrs, content);
+        }
+
+        xml
+    }
+}
+
+fn span(cell: &TableCell) -> usize {
+    cell.property
+        .grid_span
+        .as_ref()
+        .map_or(1, |span| span.value.max(1))
+}
+
+fn write_spaces(spaces: usize, xml: &mut String) {
+    match spaces {
+        0 => (),
+        1 => *xml += "<text:s/>",
+        _ => *xml += &format!("<text:s text:c=\"{}\"/>", spaces),
+    }
+}
+
+/// Writes the paragraph and text properties elements of a style.
+fn properties(paragraph: &str, children: &str, text: &str) -> String {
+    let mut xml = String::new();
+    if !paragraph.is_empty() || !children.is_empty() {
+        xml += &format!(
+            "<style:paragraph-properties{}>{}</style:paragraph-properties>",
+            paragraph, children
+        );
+    }
+    if !text.is_empty() {
+        xml += &format!("<style:text-properties{}/>", text);
+    }
+    xml
+}
+
+/// Returns the attributes of the text properties of a style.
+fn text_properties(prop: &CharacterProperty) -> String {
+    let mut attrs = String::new();
+
+    if let Some(bold) = &prop.bold {
+        let weight = if is_on(bold.value) { "bold" } else { "normal" };
+        attrs += &format!(" fo:font-weight=\"{}\"", weight);
+    }
+    if let Some(italics) = &prop.italics {
+        let style = if is_on(italics.value) {
+            "italic"
+        } else {
+            "normal"
+        };
+        attrs += &format!(" fo:font-style=\"{}\"", style);
+    }
+    if let Some(underline) = &prop.underline {
+        let style = match underline.val {
+            Some(UnderlineStyle::None) => "none",
+            Some(UnderlineStyle::Dotted) | Some(UnderlineStyle::DottedHeavy) => "dotted",
+            Some(UnderlineStyle::Dash)
+            | Some(UnderlineStyle::DashedHeavy)
+            | Some(UnderlineStyle::DashLong)
+            | Some(UnderlineStyle::DashLongHeavy) => "dash",
+            Some(UnderlineStyle::Wave)
+            | Some(UnderlineStyle::WavyDouble)
+            | Some(UnderlineStyle::WavyHeavy) => "wave",
+            _ => "solid",
+        };
+        attrs += &format!(" style:text-underline-style=\"{}\"", style);
+        if style != "none" {
+            attrs +=
+                " style:text-underline-width=\"auto\" style:text-underline-color=\"font-color\"";
+        }
+        if matches!(underline.val, Some(UnderlineStyle::Double)) {
+            attrs += " style:text-underline-type=\"double\"";
+        }
+    }
+    match (&prop.strike, &prop.dstrike) {
+        (_, Some(dstrike)) if is_on(dstrike.value) => {
+            attrs +=
+                " style:text-line-through-style=\"solid\" style:text-line-through-type=\"double\""
+        }
+        (Some(strike), _) if is_on(strike.value) => {
+            attrs += " style:text-line-through-style=\"solid\""
+        }
+        (None, None) => (),
+        _ => attrs += " style:text-line-through-style=\"none\"",
+    }
+    if let Some(color) = &prop.color {
+        if color.value != "auto" {
+            attrs += &format!(" fo:color=\"#{}\"", xml_escape(&color.value));
+        }
+    }
+    if let Some(size) = &prop.size {
+        // in half-points
+        attrs += &format!(" fo:font-size=\"{}pt\"", size.value as f64 / 2.0);
+    }
+    if let Some(fill) = prop
+        .shading
+        .as_ref()
+        .and_then(|shading| shading.fill.as_ref())
+    {
+        if fill != "auto" {
+            attrs += &format!(" fo:background-color=\"#{}\"", xml_escape(fill));
+        }
+    }
+    if let Some(vertical_align) = &prop.vertical_align {
+        let position = match vertical_align.value {
+            VerticalAlignVal::Superscript => "super 58%",
+            VerticalAlignVal::Subscript => "sub 58%",
+            VerticalAlignVal::Baseline => "0% 100%",
+        };
+        attrs += &format!(" style:text-position=\"{}\"", position);
+    }
+    if let Some(outline) = &prop.outline {
+        attrs += &format!(" style:text-outline=\"{}\"", is_on(outline.value));
+    }
+    if prop
+        .vanish
+        .as_ref()
+        .is_some_and(|vanish| is_on(vanish.value))
+    {
+        attrs += " text:display=\"none\"";
+    }
+
+    attrs
+}
+
+/// Returns the attributes and the children of the paragraph properties of
+/// a style.
+fn paragraph_properties(prop: &ParagraphProperty) -> (String, String) {
+    let mut attrs = String::new();
+    let mut children = String::new();
+
+    if let Some(justification) = &prop.justification {
+        let align = match justification.value {
+            JustificationVal::Left | JustificationVal::Start => "start",
+            JustificationVal::Center => "center",
+            JustificationVal::Right | JustificationVal::End => "end",
+            _ => "justify",
+        };
+        attrs += &format!(" fo:text-align=\"{}\"", align);
+    }
+    if let Some(fill) = prop
+        .shading
+        .as_ref()
+        .and_then(|shading| shading.fill.as_ref())
+    {
+        if fill != "auto" {
+            attrs += &format!(" fo:background-color=\"#{}\"", xml_escape(fill));
+        }
+    }
+
+    if let Some(borders) = &prop.border {
+        macro_rules! border {
+            ($($side:ident),*) => {
+                $(
+                    if let Some(border) = &borders.$side {
+                        attrs += &format!(
+                            " fo:border-{}=\"{}\"",
+                            stringify!($side),
+                            border_value(border.style.as_ref(), border.size, border.color.as_deref())
+                        );
+                    }
+                )*
+            };
+        }
+        border!(top, bottom, left, right);
+    }
+
+    if let Some(tabs) = &prop.tabs {
+        let mut stops = String::new();
+        for tab in &tabs.tabs {
+            let ty = match tab.ty {
+                TabStopType::Left | TabStopType::Start => "left",
+                TabStopType::Center => "center",
+                TabStopType::Right | TabStopType::End => "right",
+                TabStopType::Decimal => "char",
+                _ => continue,
+            };
+            stops += &format!(
+                "<style:tab-stop style:position=\"{}\" style:type=\"{}\"",
+                twips_length(tab.position),
+                ty
+            );
+            if ty == "char" {
+                stops += " style:char=\".\"";
+            }
+            match tab.leader {
+                Some(TabLeader::Dot) | Some(TabLeader::MiddleDot) => {
+                    stops += " style:leader-style=\"dotted\" style:leader-text=\".\""
+                }
+                Some(TabLeader::Hyphen) => {
+                    stops += " style:leader-style=\"dash\" style:leader-text=\"-\""
+                }
+                Some(TabLeader::Underscore) | Some(TabLeader::Heavy) => {
+                    stops += " style:leader-style=\"solid\" style:leader-text=\"_\""
+                }
+                _ => (),
+            }
+            stops += "/>";
+        }
+        if !stops.is_empty() {
+            children += &format!("<style:tab-stops>{}</style:tab-stops>", stops);
+        }
+    }
+
+    (attrs, children)
+}
+
+fn border_value(style: Option<&BorderStyle>, size: Option<usize>, color: Option<&str>) -> String {
+    let style = match style {
+        Some(BorderStyle::None) | Some(BorderStyle::Nil) => return "none".to_owned(),
+        Some(BorderStyle::Double) => "double",
+        Some(BorderStyle::Dotted) => "dotted",
+        Some(BorderStyle::Dashed) | Some(BorderStyle::DashSmallGap) => "dashed",
+        _ => "solid",
+    };
+    // in eighths of a point
+    let width = size.map_or(0.5, |size| size as f64 / 8.0);
+    let color = match color {
+        Some(color) if color != "auto" => color,
+        _ => "000000",
+    };
+    format!("{}pt {} #{}", width, style, xml_escape(color))
+}
+
+/// Writes the definition of a list level.
+fn list_level(level: &Level, log: &mut ConversionLog) -> String {
+    let number = level.index + 1;
+    let properties = format!(
+        "<style:list-level-properties text:list-level-position-and-space-mode=\"label-alignment\">\
+         <style:list-level-label-alignment text:label-followed-by=\"listtab\" \
+         text:list-tab-stop-position=\"{margin}\" fo:text-indent=\"-0.25in\" fo:margin-left=\"{margin}\"/>\
+         </style:list-level-properties>",
+        margin = format!("{}in", 0.5 * number as f64)
+    );
+    let format = level
+        .numbering_format
+        .as_ref()
+        .map_or(NumberingFormatVal::Decimal, |format| format.value);
+    let text = &level.level_text.value;
+
+    if matches!(format, NumberingFormatVal::Bullet) {
+        let bullet = match text.chars().next() {
+            // bullets of symbol fonts are in the private use area
+            Some('\u{f000}'..='\u{f0ff}') | None => '•',
+            Some(c) => c,
+        };
+        return format!(
+            "<text:list-level-style-bullet text:level=\"{}\" text:bullet-char=\"{}\">{}</text:list-level-style-bullet>",
+            number,
+            xml_escape(&bullet.to_string()),
+            properties
+        );
+    }
+
+    let num_format = match format {
+        NumberingFormatVal::LowerLetter => "a",
+        NumberingFormatVal::UpperLetter => "A",
+        NumberingFormatVal::LowerRoman => "i",
+        NumberingFormatVal::UpperRoman => "I",
+        NumberingFormatVal::None => "",
+        NumberingFormatVal::Decimal => "1",
+        _ => {
+            log.report("w:numFmt", "numbering formats are converted to decimal");
+            "1"
+        }
+    };
+
+    // the text around the placeholders, like `%1.` or `(%2)`
+    let placeholders: Vec<usize> = text
+        .match_indices('%')
+        .filter(|(index, _)| text[index + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        .map(|(index, _)| index)
+        .collect();
+    let (prefix, suffix) = match (placeholders.first(), placeholders.last()) {
+        (Some(first), Some(last)) => (&text[..*first], &text[last + 2..]),
+        _ => ("", &text[..]),
+    };
+
+    let mut attrs = format!(
+        " text:level=\"{}\" style:num-format=\"{}\"",
+        number, num_format
+    );
+    if !prefix.is_empty() {
+        attrs += &format!(" style:num-prefix=\"{}\"", xml_escape(prefix));
+    }
+    if !suffix.is_empty() {
+        attrs += &format!(" style:num-suffix=\"{}\"", xml_escape(suffix));
+    }
+    if placeholders.len() > 1 {
+        attrs += &format!(" text:display-levels=\"{}\"", placeholders.len());
+    }
+    if let Some(start) = &level.start {
+        attrs += &format!(" text:start-value=\"{}\"", start.value);
+    }
+    format!(
+        "<text:list-level-style-number{}>{}</text:list-level-style-number>",
+        attrs, properties
+    )
+}
+
+/// Writes the page layout of a section.
+fn page_layout(section: Option<&SectionProperty>, header: bool, footer: bool) -> String {
+    let size = section.and_then(|section| section.page_size.as_ref());
+    let margin = section.and_then(|section| section.page_margin.as_ref());
+
+    let mut attrs = String::new();
+    if let Some(size) = size {
+        if let Some(width) = size.width {
+            attrs += &format!(" fo:page-width=\"{}\"", twips_length(width as isize));
+        }
+        if let Some(height) = size.height {
+            attrs += &format!(" fo:page-height=\"{}\"", twips_length(height as isize));
+        }
+        if let Some(orientation) = &size.orientation {
+            let orientation = match orientation {
+                PageOrientation::Portrait => "portrait",
+                PageOrientation::Landscape => "landscape",
+            };
+            attrs += &format!(" style:print-orientation=\"{}\"", orientation);
+        }
+    }
+
+    let mut header_style = String::new();
+    let mut footer_style = String::new();
+    if let Some(margin) = margin {
+        // the page margins of ODF end at the header and the footer, which
+        // are spaced from the body
+        let mut top = margin.top;
+        let mut bottom = margin.bottom;
+        if let (true, Some(distance)) = (header, margin.header) {
+            let spacing = top.unwrap_or_default().abs() - distance as isize;
+            header_style = header_footer_style("bottom", spacing);
+            top = Some(distance as isize);
+        }
+        if let (true, Some(distance)) = (footer, margin.footer) {
+            let spacing = bottom.unwrap_or_default().abs() - distance as isize;
+            footer_style = header_footer_style("top", spacing);
+            bottom = Some(distance as isize);
+        }
+
+        for (side, value) in [
+            ("top", top.map(isize::abs)),
+            ("bottom", bottom.map(isize::abs)),
+            ("left", margin.left.map(|left| left as isize)),
+            ("right", margin.right.map(|right| right as isize)),
+        ] {
+            if let Some(value) = value {
+                attrs += &format!(" fo:margin-{}=\"{}\"", side, twips_length(value));
+            }
+        }
+    }
+
+    format!(
+        "<style:page-layout style:name=\"pm1\"><style:page-layout-properties{}/>\
+         <style:header-style>{}</style:header-style><style:footer-style>{}</style:footer-style>\
+         </style:page-layout>",
+        attrs, header_style, footer_style
+    )
+}
+
+/// Writes the properties of a header or a footer, spaced from the body on
+/// the given side.
+fn header_footer_style(side: &str, spacing: isize) -> String {
+    format!(
+        "<style:header-footer-properties fo:min-height=\"0in\" fo:margin-{}=\"{}\"/>",
+        side,
+        twips_length(spacing.max(0))
+    )
+}
@@ -0,0 +1,1927 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    comments::{Comment, Comments},
+    convert::{merged_cell, text_of, VerticalMerges, TABLE_WIDTH},
+    core::Core,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, BreakType, CommentReference, Drawing, EndnoteRef,
+        EndnoteReference, FootnoteRef, FootnoteReference, Hyperlink, Paragraph, ParagraphContent,
+        Run, RunContent, Table, TableCellContent, TableRow,
+    },
+    error::DocxResult,
+    footnotes::{Endnote, Endnotes, Footnote, Footnotes},
+    formatting::{
+        BorderStyle, BottomBorder, CharacterProperty, FooterReference, HeaderReference,
+        Justification, JustificationVal, LeftBorder, NumberingProperty, PageMargin,
+        PageOrientation, PageSize, ParagraphProperty, RightBorder, SectionProperty, TopBorder,
+        UnderlineStyle, VerticalAlign, VerticalAlignVal, VerticalMergeVal,
+    },
+    header_footer::{Footer, Header},
+    numbering::{Level, LevelText, NumberingFormat, NumberingFormatVal, Start},
+    odt::{decode_style_name, export::MIME_TYPE, parse_emus, parse_twips, ConversionLog, PREFIXES},
+    schema::{SCHEMA_FOOTER, SCHEMA_HEADER, SCHEMA_HYPERLINK},
+    styles::{Style, StyleType},
+    xml_tree::{self, Attribute, Element, Node},
+    Docx,
+};
+
+impl<'a> Docx<'a> {
+    /// Reads an ODT package, returning the document and the constructs
+    /// which couldn't be converted faithfully.
+    ///
+    /// Named styles keep their inheritance, automatic styles become direct
+    /// formatting and the first master page provides the page layout, the
+    /// header and the footer. Unknown elements are replaced by their text.
+    ///
+    /// ```rust
+    /// use docx::document::Paragraph;
+    /// use docx::Docx;
+    /// use std::io::Cursor;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(Paragraph::default().push_text("Minutes"));
+    /// let odt = docx.to_odt().write(Cursor::new(Vec::new())).unwrap();
+    ///
+    /// let (docx, log) = Docx::from_odt(odt).unwrap();
+    /// assert_eq!(docx.document.body.content.len(), 1);
+    /// assert!(log.is_empty());
+    /// ```
+    pub fn from_odt<R: Read + Seek>(reader: R) -> DocxResult<(Docx<'a>, ConversionLog)> {
+        let mut zip = ZipArchive::new(reader)?;
+        let mut log = ConversionLog::default();
+
+        let mut parts = HashMap::new();
+        let mut files = HashMap::new();
+        for index in 0..zip.len() {
+            let mut entry = zip.by_index(index)?;
+            let name = entry.name().to_owned();
+            if matches!(
+                name.as_str(),
+                "mimetype" | "content.xml" | "styles.xml" | "meta.xml"
+            ) {
+                let mut buffer = String::new();
+                entry.read_to_string(&mut buffer)?;
+                parts.insert(name, buffer);
+            } else if !name.ends_with('/') && !name.starts_with("META-INF/") {
+                let mut data = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut data)?;
+                files.insert(name, data);
+            }
+        }
+
+        if let Some(mimetype) = parts.get("mimetype") {
+            if mimetype.trim() != MIME_TYPE {
+                log.report("mimetype", "the package isn't a text document");
+            }
+        }
+        let content = match parts.get("content.xml") {
+            Some(content) => xml_tree::parse(content, PREFIXES)?,
+            None => return Err(ZipError::FileNotFound.into()),
+        };
+        let styles = parts
+            .get("styles.xml")
+            .map(|styles| xml_tree::parse(styles, PREFIXES))
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut reader = Reader {
+            docx: Docx::default(),
+            log,
+            automatic: HashMap::new(),
+            list_styles: HashMap::new(),
+            files,
+            list_ids: HashMap::new(),
+            last_lists: HashMap::new(),
+            blocks: Vec::new(),
+            paragraph: None,
+            item: None,
+            space: true,
+            bookmarks: HashMap::new(),
+            in_table: false,
+            part: None,
+        };
+
+        if let Some(office_styles) = styles.child("office:styles") {
+            reader.named_styles(office_styles);
+        }
+
+        // the master page, whose content uses the automatic styles of the
+        // styles part
+        reader.automatic_styles(styles.child("office:automatic-styles"));
+        let section = reader.master_page(&styles);
+
+        reader.automatic_styles(content.child("office:automatic-styles"));
+        if let Some(text) = content
+            .child("office:body")
+            .and_then(|body| body.child("office:text"))
+        {
+            reader.blocks(text, &Context::default());
+        }
+        reader.end_paragraph();
+
+        let mut docx = reader.docx;
+        docx.document.body.content = reader.blocks;
+        docx.document.body.section = Some(section);
+        if let Some(meta) = parts.get("meta.xml") {
+            docx.core = core(&xml_tree::parse(meta, PREFIXES)?);
+        }
+
+        Ok((docx, reader.log))
+    }
+
+    /// Reads an ODT file.
+    pub fn from_odt_file<P: AsRef<Path>>(path: P) -> DocxResult<(Docx<'a>, ConversionLog)> {
+        Docx::from_odt(File::open(path)?)
+    }
+}
+
+struct Reader<'x, 'a> {
+    docx: Docx<'a>,
+    log: ConversionLog,
+    /// The automatic styles of the part being read, by name.
+    automatic: HashMap<&'x str, &'x Element>,
+    /// The named and automatic list styles, by name.
+    list_styles: HashMap<&'x str, &'x Element>,
+    /// The files of the package other than its XML parts, like pictures.
+    files: HashMap<String, Vec<u8>>,
+    /// The numbering ids of the lists, by their XML id.
+    list_ids: HashMap<String, usize>,
+    /// The numbering id of the last list of each list style.
+    last_lists: HashMap<String, usize>,
+    /// The blocks of the body, or of the current table cell or note.
+    blocks: Vec<BodyContent<'a>>,
+    paragraph: Option<Paragraph<'a>>,
+    /// The numbering of the first paragraph of the current list item.
+    item: Option<NumberingProperty>,
+    /// Whether the text so far ends with a space, which the following
+    /// whitespace collapses into.
+    space: bool,
+    /// The ids of the bookmarks, by name.
+    bookmarks: HashMap<String, String>,
+    in_table: bool,
+    /// The ZIP item name of the header or footer part being read, whose
+    /// relationships the pictures and hyperlinks are added to.
+    part: Option<String>,
+}
+
+/// The formatting inherited from the enclosing elements
+#[derive(Clone, Default)]
+struct Context<'a> {
+    character: CharacterProperty<'a>,
+    /// The numbering id and the level of the enclosing list.
+    list: Option<(usize, usize)>,
+}
+
+impl<'x, 'a> Reader<'x, 'a> {
+    fn automatic_styles(&mut self, styles: Option<&'x Element>) {
+        self.automatic.clear();
+        for style in styles.into_iter().flat_map(Element::elements) {
+            if let Some(name) = style.attr("style:name") {
+                match style.name.as_str() {
+                    "style:style" => {
+                        self.automatic.insert(name, style);
+                    }
+                    "text:list-style" => {
+                        self.list_styles.insert(name, style);
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn named_styles(&mut self, styles: &'x Element) {
+        for element in styles.elements() {
+            match element.name.as_str() {
+                "style:default-style" if element.attr("style:family") == Some("paragraph") => {
+                    let (paragraph, character, _) = self.properties(element);
+                    let default = &mut self.docx.styles.default;
+                    default.paragraph.inner = paragraph;
+                    default.character.inner = character;
+                }
+                "style:style" => self.named_style(element),
+                "text:list-style" => {
+                    if let Some(name) = element.attr("style:name") {
+                        self.list_styles.insert(name, element);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn named_style(&mut self, element: &'x Element) {
+        let (Some(name), Some(family)) = (element.attr("style:name"), element.attr("style:family"))
+        else {
+            return;
+        };
+        let ty = match family {
+            "paragraph" => StyleType::Paragraph,
+            "text" => StyleType::Character,
+            // the styles of tables, graphics and other objects
+            _ => return,
+        };
+
+        let (mut paragraph, character, _) = self.properties(element);
+        if let Some(level) = element
+            .attr("style:default-outline-level")
+            .and_then(|level| level.parse::<usize>().ok())
+            .filter(|level| (1..=9).contains(level))
+        {
+            paragraph = paragraph.outline_level(level - 1);
+        }
+        if element.attr("style:list-style-name").is_some() {
+            self.log.report(
+                "style:list-style-name",
+                "the numbering of paragraph styles is dropped",
+            );
+        }
+
+        let display_name = element
+            .attr("style:display-name")
+            .map(str::to_owned)
+            .unwrap_or_else(|| decode_style_name(name));
+        let mut style = Style::new(ty, style_id(name))
+            .name(display_name)
+            .paragraph(paragraph)
+            .character(character);
+        if let Some(parent) = element.attr("style:parent-style-name") {
+            style = style.based_on(style_id(parent));
+        }
+        self.docx.styles.push(style);
+    }
+
+    /// Returns the properties of a style element, and the break its
+    /// paragraphs start with.
+    fn properties(
+        &mut self,
+        style: &Element,
+    ) -> (
+        ParagraphProperty<'a>,
+        CharacterProperty<'a>,
+        Option<BreakType>,
+    ) {
+        let mut paragraph = ParagraphProperty::default();
+        let mut character = CharacterProperty::default();
+        let mut break_before = None;
+
+        if let Some(properties) = style.child("style:paragraph-properties") {
+            for Attribute { name, value, .. } in &properties.attrs {
+                match name.as_str() {
+                    "fo:text-align" => {
+                        let value = match value.as_str() {
+                            "start" | "left" => JustificationVal::Left,
+                            "center" => JustificationVal::Center,
+                            "end" | "right" => JustificationVal::Right,
+                            _ => JustificationVal::Both,
+                        };
+                        paragraph = paragraph.justification(Justification::from(value));
+                    }
+                    "fo:background-color" => {
+                        if let Some(color) = parse_color(value) {
+                            paragraph = paragraph.shading(color);
+                        }
+                    }
+                    "fo:border" | "fo:border-top" | "fo:border-bottom" | "fo:border-left"
+                    | "fo:border-right" => {
+                        let (style, size, color) = parse_border(value);
+                        let mut borders = paragraph.border.take().unwrap_or_default();
+                        macro_rules! border {
+                            ($side:ident, $ty:ident) => {
+                                borders.$side = Some($ty {
+                                    style: Some(style.clone()),
+                                    size,
+                                    color: color.clone().map(Into::into),
+                                    ..Default::default()
+                                });
+                            };
+                        }
+                        let side = name.trim_start_matches("fo:border").trim_start_matches('-');
+                        if matches!(side, "" | "top") {
+                            border!(top, TopBorder);
+                        }
+                        if matches!(side, "" | "bottom") {
+                            border!(bottom, BottomBorder);
+                        }
+                        if matches!(side, "" | "left") {
+                            border!(left, LeftBorder);
+                        }
+                        if matches!(side, "" | "right") {
+                            border!(right, RightBorder);
+                        }
+                        paragraph.border = Some(borders);
+                    }
+                    "fo:break-before" => {
+                        break_before = match value.as_str() {
+                            "page" => Some(BreakType::Page),
+                            "column" => Some(BreakType::Column),
+                            _ => None,
+                        }
+                    }
+                    _ => self.log.report(name, "the style property is dropped"),
+                }
+            }
+            if properties.child("style:tab-stops").is_some() {
+                self.log
+                    .report("style:tab-stops", "the style property is dropped");
+            }
+        }
+
+        if let Some(properties) = style.child("style:text-properties") {
+            for Attribute { name, value, .. } in &properties.attrs {
+                match name.as_str() {
+                    "fo:font-weight" => {
+                        character = character.bold(!matches!(value.as_str(), "normal" | "400"))
+                    }
+                    "fo:font-style" => character = character.italics(value != "normal"),
+                    "style:text-underline-style" => {
+                        let ty = properties.attr("style:text-underline-type");
+                        let underline = match value.as_str() {
+                            "none" => UnderlineStyle::None,
+                            _ if ty == Some("double") => UnderlineStyle::Double,
+                            "dotted" => UnderlineStyle::Dotted,
+                            "dash" => UnderlineStyle::Dash,
+                            "long-dash" => UnderlineStyle::DashLong,
+                            "dot-dash" => UnderlineStyle::DotDash,
+                            "dot-dot-dash" => UnderlineStyle::DotDotDash,
+                            "wave" => UnderlineStyle::Wave,
+                            _ => UnderlineStyle::Single,
+                        };
+                        character = character.underline(underline);
+                    }
+                    "style:text-line-through-style" => {
+                        let double =
+                            properties.attr("style:text-line-through-type") == Some("double");
+                        character = match value.as_str() {
+                            "none" => character.strike(false),
+                            _ if double => character.dstrike(true),
+                            _ => character.strike(true),
+                        };
+                    }
+                    "fo:color" => {
+                        if let Some(color) = parse_color(value) {
+                            character = character.color(color);
+                        }
+                    }
+                    "fo:font-size" => match parse_twips(value) {
+                        // in half-points
+                        Some(size) if size > 0 => character = character.size((size / 10) as usize),
+                        _ => self.log.report(name, "relative font sizes are dropped"),
+                    },
+                    "fo:background-color" => {
+                        if let Some(color) = parse_color(value) {
+                            character = character.shading(color);
+                        }
+                    }
+                    "style:text-position" => {
+                        let position = value.split_whitespace().next().unwrap_or_default();
+                        let align = match position {
+                            "super" => VerticalAlignVal::Superscript,
+                            "sub" => VerticalAlignVal::Subscript,
+                            _ if position.starts_with('-') => VerticalAlignVal::Subscript,
+                            _ if position.trim_end_matches('%').parse() == Ok(0.0) => {
+                                VerticalAlignVal::Baseline
+                            }
+                            _ => VerticalAlignVal::Superscript,
+                        };
+                        character = character.vertical_align(VerticalAlign::from(align));
+                    }
+                    "style:text-outline" => character = character.outline(value == "true"),
+                    "text:display" => {
+                        if value == "none" {
+                            character = character.vanish(true);
+                        }
+                    }
+                    // the details of the properties above
+                    "style:text-underline-type"
+                    | "style:text-underline-width"
+                    | "style:text-underline-color"
+                    | "style:text-line-through-type" => (),
+                    _ => self.log.report(name, "the style property is dropped"),
+                }
+            }
+        }
+
+        (paragraph, character, break_before)
+    }
+
+    fn blocks(&mut self, element: &Element, context: &Context<'a>) {
+        for child in element.elements() {
+            self.block(child, context);
+        }
+    }
+
+    fn block(&mut self, element: &Element, context: &Context<'a>) {
+        let name = element.name.as_str();
+        match name {
+            "text:p" | "text:h" => self.paragraph(element, context),
+            "text:list" => self.list(element, context),
+            "table:table" => self.table(element, context),
+            "text:section" => self.blocks(element, context),
+            "text:table-of-content"
+            | "text:illustration-index"
+            | "text:table-index"
+            | "text:object-index"
+            | "text:user-index"
+            | "text:alphabetical-index"
+            | "text:bibliography" => {
+                self.log.report(name, "indexes are converted to text");
+                if let Some(body) = element.child("text:index-body") {
+                    self.blocks(body, context);
+                }
+            }
+            "text:tracked-changes" => self.log.report(name, "tracked changes are accepted"),
+            "text:sequence-decls"
+            | "text:variable-decls"
+            | "text:user-field-decls"
+            | "text:dde-connection-decls"
+            | "office:forms"
+            | "text:soft-page-break" => (),
+            _ => {
+                self.log.report(name, "the element is replaced by its text");
+                self.end_paragraph();
+                self.inline(&element.children, context);
+                self.end_paragraph();
+            }
+        }
+    }
+
+    fn paragraph(&mut self, element: &Element, context: &Context<'a>) {
+        self.end_paragraph();
+
+        let mut context = context.clone();
+        let (mut property, break_before) = match element.attr("text:style-name") {
+            Some(name) => match self.automatic.get(name).copied() {
+                Some(style) => {
+                    let (mut paragraph, character, break_before) = self.properties(style);
+                    if let Some(parent) = style.attr("style:parent-style-name") {
+                        paragraph = paragraph.style_id(style_id(parent));
+                    }
+                    context.character.merge(&character);
+                    (paragraph, break_before)
+                }
+                None => (ParagraphProperty::default().style_id(style_id(name)), None),
+            },
+            None => Default::default(),
+        };
+
+        if element.name == "text:h" {
+            let level = element
+                .attr("text:outline-level")
+                .and_then(|level| level.parse::<usize>().ok())
+                .unwrap_or(1)
+                .clamp(1, 9);
+            let style_level = property
+                .style_id
+                .as_ref()
+                .and_then(|style_id| self.docx.styles.outline_level(&style_id.value));
+            if style_level != Some(level - 1) {
+                property = property.outline_level(level - 1);
+            }
+        }
+        if let Some(numbering) = self.item.take() {
+            property = property.numbering(numbering);
+        }
+
+        let mut paragraph = Paragraph::default().property(property);
+        if let Some(ty) = break_before {
+            paragraph = paragraph.push(Run::default().push_break(ty));
+        }
+        self.paragraph = Some(paragraph);
+        self.inline(&element.children, &context);
+        self.end_paragraph();
+    }
+
+    fn list(&mut self, element: &Element, context: &Context<'a>) {
+        self.end_paragraph();
+        // an item which starts with a nested list isn't numbered
+        self.item = None;
+
+        let (num_id, level) = match context.list {
+            Some((num_id, level)) => (num_id, level + 1),
+            None => {
+                let style = element.attr("text:style-name");
+                let continued = element
+                    .attr("text:continue-list")
+                    .and_then(|id| self.list_ids.get(id))
+                    .or_else(|| {
+                        (element.attr("text:continue-numbering") == Some("true"))
+                            .then(|| self.last_lists.get(style.unwrap_or_default()))
+                            .flatten()
+                    })
+                    .copied();
+                let num_id = match continued {
+                    Some(num_id) => num_id,
+                    None => self.numbering(style),
+                };
+                if let Some(id) = element.attr("xml:id") {
+                    self.list_ids.insert(id.to_owned(), num_id);
+                }
+                self.last_lists
+                    .insert(style.unwrap_or_default().to_owned(), num_id);
+                (num_id, 0)
+            }
+        };
+
+        let context = Context {
+            list: Some((num_id, level)),
+            ..context.clone()
+        };
+        for child in element.elements() {
+            match child.name.as_str() {
+                "text:list-item" => {
+                    self.item = Some(NumberingProperty::from((num_id, level)));
+                    self.blocks(child, &context);
+                    self.item = None;
+                }
+                "text:list-header" => self.blocks(child, &context),
+                _ => (),
+            }
+        }
+    }
+
+    /// Adds the numbering of a list style and returns its id.
+    fn numbering(&mut self, style: Option<&str>) -> usize {
+        let mut levels = Vec::new();
+        let definitions = style
+            .and_then(|style| self.list_styles.get(style))
+            .map(|style| style.elements().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        for definition in definitions {
+            let Some(index) = definition
+                .attr("text:level")
+                .and_then(|level| level.parse::<usize>().ok())
+                .filter(|level| (1..=9).contains(level))
+                .map(|level| level - 1)
+            else {
+                continue;
+            };
+
+            let (format, text) = match definition.name.as_str() {
+                "text:list-level-style-bullet" => {
+                    let bullet = definition.attr("text:bullet-char").unwrap_or("•");
+                    (NumberingFormatVal::Bullet, bullet.to_owned())
+                }
+                "text:list-level-style-number" => {
+                    let format = match definition.attr("style:num-format").unwrap_or_default() {
+                        "1" => NumberingFormatVal::Decimal,
+                        "a" => NumberingFormatVal::LowerLetter,
+                        "A" => NumberingFormatVal::UpperLetter,
+                        "i" => NumberingFormatVal::LowerRoman,
+                        "I" => NumberingFormatVal::UpperRoman,
+                        "" => NumberingFormatVal::None,
+                        _ => {
+                            self.log.report(
+                                "style:num-format",
+                                "numbering formats are converted to decimal",
+                            );
+                            NumberingFormatVal::Decimal
+                        }
+                    };
+                    let shown = definition
+                        .attr("text:display-levels")
+                        .and_then(|levels| levels.parse::<usize>().ok())
+                        .unwrap_or(1)
+                        .clamp(1, index + 1);
+                    let placeholders: Vec<String> = (index + 2 - shown..=index + 1)
+                        .map(|level| format!("%{}", level))
+                        .collect();
+                    let text = format!(
+                        "{}{}{}",
+                        definition.attr("style:num-prefix").unwrap_or_default(),
+                        placeholders.join("."),
+                        definition.attr("style:num-suffix").unwrap_or_default()
+                    );
+                    (format, text)
+                }
+                _ => {
+                    self.log
+                        .report(&definition.name, "picture bullets are replaced by •");
+                    (NumberingFormatVal::Bullet, "•".to_owned())
+                }
+            };
+            let start = definition
+                .attr("text:start-value")
+                .and_then(|start| start.parse().ok())
+                .filter(|_| !matches!(format, NumberingFormatVal::Bullet));
+
+            levels.push(Level {
+                index,
+                start: start.map(|value| Start { value }),
+                numbering_format: Some(NumberingFormat { value: format }),
+                level_text: LevelText { value: text.into() },
+            });
+        }
+
+        let numbering = self.docx.numbering.get_or_insert_with(Default::default);
+        if levels.is_empty() {
+            numbering.push_list(NumberingFormatVal::Bullet, 1)
+        } else {
+            numbering.push_levels(levels)
+        }
+    }
+
+    fn table(&mut self, element: &Element, context: &Context<'a>) {
+        self.end_paragraph();
+
+        let mut widths = Vec::new();
+        let mut rows = Vec::new();
+        self.table_parts(element, &mut widths, &mut rows);
+
+        let mut table = Table::default();
+        let mut merges = VerticalMerges::default();
+        let mut columns = 0;
+        let in_table = std::mem::replace(&mut self.in_table, true);
+
+        for row in rows {
+            let mut cells = Vec::new();
+            let mut column = 0;
+            // the covered cells of a cell spanning columns
+            let mut covered = 0;
+
+            for cell in row.elements() {
+                let repeated = cell
+                    .attr("table:number-columns-repeated")
+                    .and_then(|repeated| repeated.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, 1000);
+
+                for _ in 0..repeated {
+                    match cell.name.as_str() {
+                        "table:table-cell" => {
+                            if cell.attr("table:style-name").is_some() {
+                                self.log.report(
+                                    "table:table-cell",
+                                    "the formatting of cells is dropped",
+                                );
+                            }
+                            let span = span_attr(cell, "table:number-columns-spanned");
+                            let rows_spanned = span_attr(cell, "table:number-rows-spanned");
+                            let merge = merges.start(column, span, rows_spanned);
+
+                            let content = self.cell(cell, context);
+                            cells.push(merged_cell(span, merge, content));
+                            column += span;
+                            covered = span - 1;
+                        }
+                        "table:covered-table-cell" => {
+                            if let Some(span) = merges.next(column) {
+                                cells.push(merged_cell(
+                                    span,
+                                    Some(VerticalMergeVal::Continue),
+                                    Vec::new(),
+                                ));
+                                column += span;
+                                covered = span - 1;
+                            } else if covered > 0 {
+                                covered -= 1;
+                            } else {
+                                cells.push(merged_cell(1, None, Vec::new()));
+                                column += 1;
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            columns = columns.max(column);
+            table = table.push_row(
+                cells
+                    .into_iter()
+                    .fold(TableRow::default(), TableRow::push_cell),
+            );
+        }
+        self.in_table = in_table;
+
+        let columns = columns.max(1);
+        if widths.len() != columns || widths.contains(&0) {
+            widths = vec![TABLE_WIDTH / columns; columns];
+        }
+        table = table.push_grid(widths);
+        self.blocks.push(table.into());
+    }
+
+    /// Collects the column widths and the rows of a table, which may be in
+    /// groups.
+    fn table_parts(
+        &mut self,
+        element: &'_ Element,
+        widths: &mut Vec<usize>,
+        rows: &mut Vec<Element>,
+    ) {
+        for child in element.elements() {
+            let repeated = |name: &str| {
+                child
+                    .attr(name)
+                    .and_then(|repeated| repeated.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, 1000)
+            };
+            match child.name.as_str() {
+                "table:table-column" => {
+                    let width = child
+                        .attr("table:style-name")
+                        .and_then(|name| self.automatic.get(name))
+                        .and_then(|style| style.child("style:table-column-properties"))
+                        .and_then(|properties| properties.attr("style:column-width"))
+                        .and_then(parse_twips)
+                        .map_or(0, |width| width.max(0) as usize);
+                    for _ in 0..repeated("table:number-columns-repeated") {
+                        widths.push(width);
+                    }
+                }
+                "table:table-row" => {
+                    for _ in 0..repeated("table:number-rows-repeated") {
+                        rows.push(child.clone());
+                    }
+                }
+                "table:table-columns"
+                | "table:table-header-columns"
+                | "table:table-column-group"
+                | "table:table-rows"
+                | "table:table-header-rows"
+                | "table:table-row-group" => self.table_parts(child, widths, rows),
+                _ => (),
+            }
+        }
+    }
+
+    /// Reads the content of a table cell.
+    fn cell(&mut self, element: &Element, context: &Context<'a>) -> Vec<TableCellContent<'a>> {
+        let context = Context {
+            character: context.character.clone(),
+            list: None,
+        };
+        let content = self.nested(|reader| reader.blocks(element, &context));
+
+        let mut cell_content = Vec::new();
+        for block in content {
+            match block {
+                BodyContent::Paragraph(paragraph) => cell_content.push(paragraph.into()),
+                BodyContent::Table(table) => cell_content.push(table.into()),
+                BodyContent::Sdt(_) => (),
+            }
+        }
+        cell_content
+    }
+
+    /// Reads blocks apart from the current ones, and returns them.
+    fn nested(&mut self, read: impl FnOnce(&mut Self)) -> Vec<BodyContent<'a>> {
+        let blocks = std::mem::take(&mut self.blocks);
+        let paragraph = self.paragraph.take();
+        let item = self.item.take();
+        let space = self.space;
+
+        read(self);
+        self.end_paragraph();
+
+        self.space = space;
+        self.item = item;
+        self.paragraph = paragraph;
+        std::mem::replace(&mut self.blocks, blocks)
+    }
+
+    fn inline(&mut self, nodes: &[Node], context: &Context<'a>) {
+        for node in nodes {
+            match node {
+                Node::Element(element) => self.inline_element(element, context),
+                Node::Text(text) => {
+                    let text = self.collapse(text);
+                    self.push_text(&text, context);
+                }
+            }
+        }
+    }
+
+    fn inline_element(&mut self, element: &Element, context: &Context<'a>) {
+        let name = element.name.as_str();
+        match name {
+            "text:span" => {
+                let context = self.span_context(element, context);
+                self.inline(&element.children, &context);
+            }
+            "text:s" => {
+                let count = element
+                    .attr("text:c")
+                    .and_then(|count| count.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, 1000);
+                self.push_text(&" ".repeat(count), context);
+                self.space = false;
+            }
+            "text:tab" => {
+                self.push(Run::default().push(RunContent::Tab(Default::default())));
+                self.space = false;
+            }
+            "text:line-break" => {
+                self.push(Run::default().push_break(None));
+                self.space = true;
+            }
+            "text:a" => self.link(element, context),
+            "text:bookmark" | "text:bookmark-start" => {
+                let bookmark = element.attr("text:name").unwrap_or_default();
+                let id = self.bookmarks.len().to_string();
+                self.bookmarks.insert(bookmark.to_owned(), id.clone());
+                self.push(
+                    BookmarkStart::default()
+                        .id(id.clone())
+                        .name(bookmark.to_owned()),
+                );
+                if name == "text:bookmark" {
+                    self.push(BookmarkEnd::default().id(id));
+                }
+            }
+            "text:bookmark-end" => {
+                let id = element
+                    .attr("text:name")
+                    .and_then(|name| self.bookmarks.get(name))
+                    .cloned();
+                if let Some(id) = id {
+                    self.push(BookmarkEnd::default().id(id));
+                }
+            }
+            "text:note" => self.note(element, context),
+            "office:annotation" => self.annotation(element),
+            "draw:frame" => self.frame(element, context),
+            "draw:a" => self.inline(&element.children, context),
+            "office:annotation-end"
+            | "text:soft-page-break"
+            | "text:reference-mark"
+            | "text:reference-mark-start"
+            | "text:reference-mark-end"
+            | "text:toc-mark"
+            | "text:toc-mark-start"
+            | "text:toc-mark-end"
+            | "text:alphabetical-index-mark"
+            | "text:alphabetical-index-mark-start"
+            | "text:alphabetical-index-mark-end" => (),
+            "text:change" | "text:change-start" | "text:change-end" => {
+                self.log.report(name, "tracked changes are accepted")
+            }
+            _ if name.starts_with("draw:") => self.log.report(name, "shapes are dropped"),
+            _ if name.starts_with("text:") && element.elements().next().is_none() => {
+                // fields, like page numbers or dates
+                self.log.report(name, "fields are converted to text");
+                self.inline(&element.children, context);
+            }
+            _ => {
+                self.log.report(name, "the element is replaced by its text");
+                self.inline(&element.children, context);
+            }
+        }
+    }
+
+    /// Returns the context of the content of a span.
+    fn span_context(&mut self, element: &Element, context: &Context<'a>) -> Context<'a> {
+        let mut context = context.clone();
+        if let Some(name) = element.attr("text:style-name") {
+            match self.automatic.get(name).copied() {
+                Some(style) => {
+                    let (_, character, _) = self.properties(style);
+                    if let Some(parent) = style.attr("style:parent-style-name") {
+                        context.character.style_id = Some(style_id(parent).into());
+                    }
+                    context.character.merge(&character);
+                }
+                None => context.character.style_id = Some(style_id(name).into()),
+            }
+        }
+        context
+    }
+
+    fn link(&mut self, element: &Element, context: &Context<'a>) {
+        // the formatting of a span around the whole text
+        let mut context = context.clone();
+        let mut children = &element.children;
+        while let [Node::Element(span)] = children.as_slice() {
+            if span.name != "text:span" {
+                break;
+            }
+            context = self.span_context(span, &context);
+            children = &span.children;
+        }
+
+        let text = self.collapse(&element.text());
+        let href = element.attr("xlink:href").unwrap_or_default().trim();
+        if text.is_empty() || href.is_empty() {
+            return self.inline(&element.children, &context);
+        }
+
+        let hyperlink = if let Some(anchor) = href.strip_prefix('#') {
+            Hyperlink::default().anchor(anchor.to_owned())
+        } else {
+            let id = self
+                .docx
+                .relationships_mut(self.part.as_deref())
+                .add_external_rel(SCHEMA_HYPERLINK, href.to_owned());
+            Hyperlink::default().id(id)
+        };
+        let run = Run::default()
+            .property(context.character.clone())
+            .push_text(text_of(&text));
+        self.push(hyperlink.content(run));
+    }
+
+    fn note(&mut self, element: &Element, context: &Context<'a>) {
+        let endnote = element.attr("text:note-class") == Some("endnote");
+        let mut content = match element.child("text:note-body") {
+            Some(body) => self.nested(|reader| reader.blocks(body, &Context::default())),
+            None => Vec::new(),
+        };
+
+        // the mark of the note starts its first paragraph
+        let mark = if endnote {
+            RunContent::from(EndnoteRef)
+        } else {
+            RunContent::from(FootnoteRef)
+        };
+        match content.first_mut() {
+            Some(BodyContent::Paragraph(paragraph)) => paragraph
+                .content
+                .insert(0, Run::default().push(mark).into()),
+            _ => content.insert(
+                0,
+                Paragraph::default().push(Run::default().push(mark)).into(),
+            ),
+        }
+
+        let run = if endnote {
+            let notes = self.docx.endnotes.get_or_insert_with(Endnotes::default);
+            let id = (notes.notes.len() + 1).to_string();
+            notes.push(Endnote {
+                content,
+                ..Endnote::new(id.clone())
+            });
+            Run::default().push(EndnoteReference::from(id))
+        } else {
+            let notes = self.docx.footnotes.get_or_insert_with(Footnotes::default);
+            let id = (notes.notes.len() + 1).to_string();
+            notes.push(Footnote {
+                content,
+                ..Footnote::new(id.clone())
+            });
+            Run::default().push(FootnoteReference::from(id))
+        };
+        let property = context
+            .character
+            .clone()
+            .vertical_align(VerticalAlign::from(VerticalAlignVal::Superscript));
+        self.push(run.property(property));
+        self.space = false;
+    }
+
+    fn annotation(&mut self, element: &Element) {
+        let text = |name: &str| element.child(name).map(Element::text).unwrap_or_default();
+        let author = text("dc:creator");
+        let date = text("dc:date");
+
+        let content = self.nested(|reader| reader.blocks(element, &Context::default()));
+        let paragraphs = content
+            .into_iter()
+            .filter_map(|block| match block {
+                BodyContent::Paragraph(paragraph) => Some(paragraph),
+                _ => None,
+            })
+            .collect();
+
+        let comments = self.docx.comments.get_or_insert_with(Comments::default);
+        let id = comments.comments.len().to_string();
+        comments.comments.push(Comment {
+            id: id.clone().into(),
+            author: author.into(),
+            date: date.into(),
+            content: paragraphs,
+        });
+        self.push(Run::default().push(CommentReference::from(id)));
+    }
+
+    fn frame(&mut self, element: &Element, context: &Context<'a>) {
+        if let Some(image) = element.child("draw:image") {
+            return self.image(element, image);
+        }
+        if let Some(text_box) = element.child("draw:text-box") {
+            self.log.report(
+                "draw:text-box",
+                "text boxes are dropped, their text is kept",
+            );
+            let text = self.collapse(&text_box.text());
+            return self.push_text(&text, context);
+        }
+        self.log
+            .report("draw:frame", "frames other than pictures are dropped");
+    }
+
+    fn image(&mut self, frame: &Element, image: &Element) {
+        let href = image.attr("xlink:href").unwrap_or_default();
+        let href = href.strip_prefix("./").unwrap_or(href);
+        let extension = href
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let extension = match extension.as_str() {
+            "png" => "png",
+            "jpg" | "jpeg" => "jpg",
+            "gif" => "gif",
+            "bmp" => "bmp",
+            "tif" | "tiff" => "tiff",
+            "svg" => "svg",
+            _ => "",
+        };
+        let Some(data) = self.files.get(href).filter(|_| !extension.is_empty()) else {
+            self.log.report(
+                "draw:image",
+                "linked pictures, and pictures of unknown formats, are dropped",
+            );
+            return;
+        };
+
+        // one inch by default
+        let size = |name: &str| frame.attr(name).and_then(parse_emus).unwrap_or(914400);
+        let (width, height) = (size("svg:width"), size("svg:height"));
+        let data = data.clone();
+        let (embed, number) =
+            self.docx
+                .embed_part_media(self.part.as_deref(), data.into(), extension);
+        let drawing = Drawing::picture(embed, width, height)
+            .id(number)
+            .name(frame.attr("draw:name").unwrap_or_default().to_owned());
+        self.push(Run::default().push(drawing));
+        self.space = false;
+    }
+
+    /// Reads the page layout, the header and the footer of the first master
+    /// page, and returns the section properties referencing them.
+    fn master_page(&mut self, styles: &'x Element) -> SectionProperty<'a> {
+        let mut section = SectionProperty::default();
+        let Some(master_page) = styles
+            .child("office:master-styles")
+            .and_then(|master_styles| master_styles.child("style:master-page"))
+        else {
+            return section;
+        };
+
+        for child in master_page.elements() {
+            match child.name.as_str() {
+                "style:header" | "style:footer" => {
+                    let header = child.name == "style:header";
+                    let name = if header {
+                        format!("header{}.xml", self.docx.headers.len() + 1)
+                    } else {
+                        format!("footer{}.xml", self.docx.footers.len() + 1)
+                    };
+                    let part = format!("word/{}", name);
+
+                    self.part = Some(part.clone());
+                    let content = self.nested(|reader| reader.blocks(child, &Context::default()));
+                    self.part = None;
+
+                    let rels = self.docx.relationships_mut(None);
+                    if header {
+                        let id = rels.add_rel(SCHEMA_HEADER, name);
+                        self.docx.headers.insert(part, Header { content });
+                        section = section.push_header(HeaderReference::new(id));
+                    } else {
+                        let id = rels.add_rel(SCHEMA_FOOTER, name);
+                        self.docx.footers.insert(part, Footer { content });
+                        section = section.push_footer(FooterReference::new(id));
+                    }
+                }
+                name => self
+                    .log
+                    .report(name, "first and left page variants are dropped"),
+            }
+        }
+
+        let layout = master_page.attr("style:page-layout-name").and_then(|name| {
+            styles
+                .child("office:automatic-styles")?
+                .elements()
+                .find(|style| {
+                    style.name == "style:page-layout" && style.attr("style:name") == Some(name)
+                })
+        });
+        if let Some(layout) = layout {
+            page_layout(
+                layout,
+                &mut section,
+                master_page.child("style:header").is_some(),
+                master_page.child("style:footer").is_some(),
+            );
+        }
+
+        section
+    }
+
+    fn collapse(&mut self, text: &str) -> String {
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, ' ' | '\t' | '\r' | '\n') {
+                if !self.space {
+                    collapsed.push(' ');
+                    self.space = true;
+                }
+            } else {
+                collapsed.push(c);
+                self.space = false;
+            }
+        }
+        collapsed
+    }
+
+    fn push_text(&mut self, text: &str, context: &Context<'a>) {
+        if text.is_empty() {
+            return;
+        }
+        let run = Run::default()
+            .property(context.character.clone())
+            .push_text(text_of(text));
+        self.push(run);
+    }
+
+    /// Appends content to the current paragraph.
+    fn push<T: Into<ParagraphContent<'a>>>(&mut self, content: T) {
+        // content outside of paragraphs, like in unknown elements, starts one
+        let paragraph = self.paragraph.get_or_insert_with(Paragraph::default);
+        paragraph.content.push(content.into());
+    }
+
+    fn end_paragraph(&mut self) {
+        self.space = true;
+        let Some(mut paragraph) = self.paragraph.take() else {
+            return;
+        };
+
+        // the whitespace at the end of a paragraph isn't displayed
+        if let Some(ParagraphContent::Run(run)) = paragraph.content.last_mut() {
+            if let Some(RunContent::Text(text)) = run.content.last_mut() {
+                if text.text.ends_with(' ') {
+                    *text = text_of(text.text.trim_end_matches(' '));
+                }
+            }
+        }
+
+        self.blocks.push(paragraph.into());
+    }
+}
+
+/// Returns the style id of a style name, without its spaces.
+fn style_id(name: &str) -> String {
+    decode_style_name(name).replace(' ', "")
+}
+
+fn span_attr(element: &Element, name: &str) -> usize {
+    element
+        .attr(name)
+        .and_then(|span| span.trim().parse().ok())
+        .unwrap_or(1)
+        .clamp(1, 1000)
+}
+
+/// Parses a color like `#1f3864`, returning its hexadecimal digits.
+fn parse_color(value: &str) -> Option<String> {
+    let hex = value.trim().strip_prefix('#')?;
+    (hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_ascii_uppercase())
+}
+
+/// Parses a border like `0.5pt solid #000000`, returning its style, its
+/// width in eighths of a point and its color.
+fn parse_border(value: &str) -> (BorderStyle, Option<usize>, Option<String>) {
+    let mut style = BorderStyle::Single;
+    let mut size = None;
+    let mut color = None;
+    for part in value.split_whitespace() {
+        match part {
+            "none" | "hidden" => style = BorderStyle::None,
+            "double" => style = BorderStyle::Double,
+            "dotted" => style = BorderStyle::Dotted,
+            "dashed" => style = BorderStyle::Dashed,
+            "solid" => style = BorderStyle::Single,
+            _ => {
+                if let Some(hex) = parse_color(part) {
+                    color = Some(hex);
+                } else if let Some(twips) = parse_twips(part) {
+                    // 2.5 twips per eighth of a point
+                    size = Some((twips.max(0) as f64 / 2.5).round().max(1.0) as usize);
+                }
+            }
+        }
+    }
+    (style, size, color)
+}
+
+/// Reads a page layout into section properties.
+fn page_layout(layout: &Element, section: &mut SectionProperty, header: bool, footer: bool) {
+    let Some(properties) = layout.child("style:page-layout-properties") else {
+        return;
+    };
+    let length = |name: &str| properties.attr(name).and_then(parse_twips);
+
+    let size = PageSize {
+        width: length("fo:page-width").map(|width| width.max(0) as usize),
+        height: length("fo:page-height").map(|height| height.max(0) as usize),
+        orientation: match properties.attr("style:print-orientation") {
+            Some("landscape") => Some(PageOrientation::Landscape),
+            Some("portrait") => Some(PageOrientation::Portrait),
+            _ => None,
+        },
+    };
+    if size.width.is_some() || size.height.is_some() {
+        section.page_size = Some(size);
+    }
+
+    let mut margin = PageMargin {
+        top: length("fo:margin-top"),
+        bottom: length("fo:margin-bottom"),
+        left: length("fo:margin-left").map(|left| left.max(0) as usize),
+        right: length("fo:margin-right").map(|right| right.max(0) as usize),
+        ..Default::default()
+    };
+
+    // the page margins of ODF end at the header and the footer, which are
+    // spaced from the body
+    let spacing = |style: &str, side: &str| {
+        let properties = layout
+            .child(style)?
+            .child("style:header-footer-properties")?;
+        let length = |name: &str| properties.attr(name).and_then(parse_twips);
+        Some(
+            length("fo:min-height").unwrap_or_default()
+                + length(&format!("fo:margin-{}", side)).unwrap_or_default(),
+        )
+    };
+    if header {
+        margin.header = margin.top.map(|top| top.max(0) as usize);
+        if let (Some(top), Some(spacing)) = (margin.top, spacing("style:header-style", "bottom")) {
+            margin.top = Some(top + spacing);
+        }
+    }
+    if footer {
+        margin.footer = margin.bottom.map(|bottom| bottom.max(0) as usize);
+        if let (Some(bottom), Some(spacing)) = (margin.bottom, spacing("style:footer-style", "top"))
+        {
+            margin.bottom = Some(bottom + spacing);
+        }
+    }
+    if margin.top.is_some() || margin.left.is_some() {
+        section.page_margin = Some(margin);
+    }
+}
+
+/// Reads the metadata into core properties.
+fn core<'a>(meta: &Element) -> Option<Core<'a>> {
+    let meta = meta.child("office:meta")?;
+    let text = |name: &str| {
+        meta.child(name)
+            .map(Element::text)
+            .filter(|text| !text.is_empty())
+            .map(Into::into)
+    };
+    let keywords: Vec<String> = meta
+        .elements()
+        .filter(|element| element.name == "meta:keyword")
+        .map(Element::text)
+        .collect();
+
+    let core = Core {
+        title: text("dc:title"),
+        subject: text("dc:subject"),
+        description: text("dc:description"),
+        creator: text("meta:initial-creator"),
+        last_modified_by: text("dc:creator"),
+        keywords: (!keywords.is_empty()).then(|| keywords.join(", ").into()),
+        revision: text("meta:editing-cycles"),
+    };
+    let empty = core.title.is_none()
+        && core.subject.is_none()
+        && core.description.is_none()
+        && core.creator.is_none()
+        && core.last_modified_by.is_none()
+        && core.keywords.is_none()
+        && core.revision.is_none();
+    (!empty).then_some(core)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        document::TableCell,
+        formatting::{GridSpan, TableCellProperty},
+    };
+    use bytes::Bytes;
+    use std::io::Cursor;
+
+    #[test]
+    fn resolve_prefixes() {
+        use std::io::Write;
+        use zip::{write::FileOptions, ZipWriter};
+
+        // the prefixes are bound by the document, and `text` isn't the text
+        // namespace here
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <o:document-content xmlns:o="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+                xmlns:t="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+                xmlns:text="urn:other">
+                <o:body><o:text>
+                    <t:h t:outline-level="1">Title</t:h>
+                    <t:p>Body <text:span>text</text:span></t:p>
+                </o:text></o:body>
+            </o:document-content>"#;
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("mimetype", FileOptions::default()).unwrap();
+        zip.write_all(MIME_TYPE.as_bytes()).unwrap();
+        zip.start_file("content.xml", FileOptions::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+        let odt = zip.finish().unwrap();
+
+        let (docx, _) = Docx::from_odt(odt).unwrap();
+        let texts: Vec<String> = paragraphs(&docx.document.body.content)
+            .into_iter()
+            .map(text)
+            .collect();
+        assert_eq!(texts, ["Title", "Body text"]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut docx = Docx::default();
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, "Heading1")
+                .name("Heading 1")
+                .paragraph(ParagraphProperty::default().outline_level(0))
+                .character(CharacterProperty::default().bold(true)),
+        );
+        docx.core = Some(Core {
+            title: Some("Minutes".into()),
+            ..Default::default()
+        });
+        let header_id = docx
+            .document_rels
+            .get_or_insert_with(Default::default)
+            .add_rel(SCHEMA_HEADER, "header1.xml");
+        docx.headers.insert(
+            "word/header1.xml".to_owned(),
+            Header {
+                content: vec![Paragraph::default().push_text("Draft").into()],
+            },
+        );
+        docx.document.body.section = Some(
+            SectionProperty::default()
+                .push_header(HeaderReference::new(header_id))
+                .page_size(PageSize {
+                    width: Some(12240),
+                    height: Some(15840),
+                    orientation: None,
+                })
+                .page_margin(
+                    PageMargin::default()
+                        .top(1440isize)
+                        .bottom(1440isize)
+                        .header(720usize),
+                ),
+        );
+        let num_id = docx
+            .numbering
+            .get_or_insert_with(Default::default)
+            .push_list(NumberingFormatVal::Decimal, 3);
+
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Heading1"))
+                .push_text("Title"),
+        );
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().justification(JustificationVal::Center))
+                .push_text("Plain  ")
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().italics(true).color("FF0000"))
+                        .push_text("red"),
+                ),
+        );
+        for item in &["one", "two"] {
+            docx.document.push(
+                Paragraph::default()
+                    .property(
+                        ParagraphProperty::default()
+                            .numbering(NumberingProperty::from((num_id, 0))),
+                    )
+                    .push_text(*item),
+            );
+        }
+        let spanning = TableCell {
+            property: TableCellProperty {
+                grid_span: Some(GridSpan { value: 2 }),
+                ..Default::default()
+            },
+            ..TableCell::from(Paragraph::default().push_text("Head"))
+        };
+        docx.document.push(
+            Table::default()
+                .push_grid(vec![2000, 3000])
+                .push_row(TableRow::default().push_cell(spanning))
+                .push_row(
+                    TableRow::default()
+                        .push_cell(Paragraph::default().push_text("A"))
+                        .push_cell(Paragraph::default().push_text("B")),
+                ),
+        );
+
+        let odt = docx.to_odt().write(Cursor::new(Vec::new())).unwrap();
+        let (docx, log) = Docx::from_odt(odt).unwrap();
+        assert!(log.is_empty(), "{}", log);
+
+        let style = docx.styles.get("Heading1").unwrap();
+        assert_eq!(style.name.as_ref().unwrap().value, "Heading 1");
+        assert_eq!(docx.styles.outline_level("Heading1"), Some(0));
+        assert_eq!(
+            docx.core.as_ref().and_then(|core| core.title.as_deref()),
+            Some("Minutes")
+        );
+
+        let header = &docx.headers["word/header1.xml"];
+        assert_eq!(header.content.len(), 1);
+        let section = docx.document.body.section.as_ref().unwrap();
+        assert_eq!(section.headers.len(), 1);
+        assert_eq!(section.page_size.as_ref().unwrap().width, Some(12240));
+        let margin = section.page_margin.as_ref().unwrap();
+        assert_eq!((margin.top, margin.header), (Some(1440), Some(720)));
+
+        let content = &docx.document.body.content;
+        assert_eq!(content.len(), 5);
+        let BodyContent::Paragraph(heading) = &content[0] else {
+            panic!("expected a paragraph");
+        };
+        assert_eq!(
+            heading.property.style_id.as_ref().unwrap().value,
+            "Heading1"
+        );
+        assert!(heading.property.outline_level.is_none());
+
+        let BodyContent::Paragraph(paragraph) = &content[1] else {
+            panic!("expected a paragraph");
+        };
+        assert!(matches!(
+            paragraph.property.justification.as_ref().map(|j| &j.value),
+            Some(JustificationVal::Center)
+        ));
+        let runs: Vec<_> = paragraph.iter_text().map(|t| t.to_string()).collect();
+        assert_eq!(runs.concat(), "Plain  red");
+        let ParagraphContent::Run(red) = paragraph.content.last().unwrap() else {
+            panic!("expected a run");
+        };
+        assert_eq!(red.property.color.as_ref().unwrap().value, "FF0000");
+        assert!(red.property.italics.is_some());
+
+        let numbering = |block: &BodyContent| match block {
+            BodyContent::Paragraph(paragraph) => paragraph.property.numbering,
+            _ => None,
+        };
+        let first = numbering(&content[2]).unwrap();
+        let second = numbering(&content[3]).unwrap();
+        let num_id = first.id.as_ref().unwrap().value;
+        assert_eq!(second.id.as_ref().unwrap().value, num_id);
+        let levels = docx.numbering.as_ref().unwrap().levels(num_id).unwrap();
+        assert_eq!(levels[0].start.as_ref().unwrap().value, 3);
+
+        let BodyContent::Table(table) = &content[4] else {
+            panic!("expected a table");
+        };
+        let widths: Vec<_> = table.grids[0]
+            .columns
+            .iter()
+            .map(|column| column.width)
+            .collect();
+        assert_eq!(widths, [2000, 3000]);
+        let first_row: Vec<_> = table.iter_rows().next().unwrap().iter_cells().collect();
+        assert_eq!(first_row.len(), 1);
+        assert_eq!(first_row[0].property.grid_span.as_ref().unwrap().value, 2);
+    }
+
+    /// Writes a document as ODT and reads it back.
+    fn round_trip_odt(docx: &Docx) -> Docx<'static> {
+        let odt = docx.to_odt().write(Cursor::new(Vec::new())).unwrap();
+        let (docx, log) = Docx::from_odt(odt).unwrap();
+        assert!(log.is_empty(), "{}", log);
+        docx
+    }
+
+    fn paragraphs<'d, 'a>(content: &'d [BodyContent<'a>]) -> Vec<&'d Paragraph<'a>> {
+        content
+            .iter()
+            .filter_map(|content| match content {
+                BodyContent::Paragraph(para) => Some(para),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn text(para: &Paragraph) -> String {
+        para.iter_text().map(|text| &**text).collect()
+    }
+
+    #[test]
+    fn round_trip_styles() {
+        let mut docx = Docx::default();
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, "Base")
+                .name("Base")
+                .character(CharacterProperty::default().size(24usize)),
+        );
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, "Note")
+                .name("Side Note")
+                .based_on("Base")
+                .paragraph(ParagraphProperty::default().justification(JustificationVal::Center))
+                .character(CharacterProperty::default().bold(true).color("336699")),
+        );
+        docx.styles.push(
+            Style::new(StyleType::Character, "Term")
+                .name("Term")
+                .character(
+                    CharacterProperty::default()
+                        .italics(true)
+                        .underline(UnderlineStyle::Double),
+                ),
+        );
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Note"))
+                .push_text("A ")
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().style_id("Term"))
+                        .push_text("term"),
+                ),
+        );
+
+        let docx = round_trip_odt(&docx);
+
+        let base = docx.styles.get("Base").unwrap();
+        assert_eq!(base.character.size.as_ref().unwrap().value, 24);
+        let note = docx.styles.get("Note").unwrap();
+        assert_eq!(note.ty, StyleType::Paragraph);
+        assert_eq!(note.name.as_ref().unwrap().value, "Side Note");
+        assert_eq!(note.based_on.as_ref().unwrap().value, "Base");
+        assert!(matches!(
+            note.paragraph.justification.as_ref().map(|j| &j.value),
+            Some(JustificationVal::Center)
+        ));
+        assert!(note.character.bold.is_some());
+        assert_eq!(note.character.color.as_ref().unwrap().value, "336699");
+        let term = docx.styles.get("Term").unwrap();
+        assert_eq!(term.ty, StyleType::Character);
+        assert!(term.character.italics.is_some());
+        assert!(matches!(
+            term.character
+                .underline
+                .as_ref()
+                .and_then(|u| u.val.as_ref()),
+            Some(UnderlineStyle::Double)
+        ));
+
+        // the paragraph and the run keep their styles
+        let paragraphs = paragraphs(&docx.document.body.content);
+        assert_eq!(paragraphs.len(), 1);
+        let para = paragraphs[0];
+        assert_eq!(para.property.style_id.as_ref().unwrap().value, "Note");
+        assert_eq!(text(para), "A term");
+        let ParagraphContent::Run(term) = para.content.last().unwrap() else {
+            panic!("expected a run");
+        };
+        assert_eq!(term.property.style_id.as_ref().unwrap().value, "Term");
+    }
+
+    #[test]
+    fn round_trip_lists() {
+        let mut docx = Docx::default();
+        let numbering = docx.numbering.get_or_insert_with(Default::default);
+        let bullets = numbering.push_list(NumberingFormatVal::Bullet, 1);
+        let roman = numbering.push_list(NumberingFormatVal::LowerRoman, 2);
+
+        let item = |num_id: usize, level: usize, text: &'static str| {
+            Paragraph::default()
+                .property(
+                    ParagraphProperty::default()
+                        .numbering(NumberingProperty::from((num_id, level))),
+                )
+                .push_text(text)
+        };
+        docx.document.push(item(bullets, 0, "fruit"));
+        docx.document.push(item(bullets, 1, "apple"));
+        docx.document.push(item(bullets, 1, "pear"));
+        docx.document.push(item(bullets, 0, "vegetables"));
+        docx.document
+            .push(Paragraph::default().push_text("between"));
+        docx.document.push(item(roman, 0, "ii"));
+        docx.document.push(item(roman, 0, "iii"));
+
+        let docx = round_trip_odt(&docx);
+
+        let items: Vec<(Option<(usize, usize)>, String)> = paragraphs(&docx.document.body.content)
+            .into_iter()
+            .map(|para| {
+                let numbering = para.property.numbering.as_ref().map(|numbering| {
+                    (
+                        numbering.id.as_ref().unwrap().value,
+                        numbering.level.as_ref().unwrap().value,
+                    )
+                });
+                (numbering, text(para))
+            })
+            .collect();
+        let bullets = items[0].0.unwrap().0;
+        let roman = items[5].0.unwrap().0;
+        assert_ne!(bullets, roman);
+        assert_eq!(
+            items,
+            vec![
+                (Some((bullets, 0)), "fruit".to_owned()),
+                (Some((bullets, 1)), "apple".to_owned()),
+                (Some((bullets, 1)), "pear".to_owned()),
+                (Some((bullets, 0)), "vegetables".to_owned()),
+                (None, "between".to_owned()),
+                (Some((roman, 0)), "ii".to_owned()),
+                (Some((roman, 0)), "iii".to_owned()),
+            ]
+        );
+
+        let numbering = docx.numbering.as_ref().unwrap();
+        let level = |num_id: usize, index: usize| {
+            let level = &numbering.levels(num_id).unwrap()[index];
+            (
+                level.numbering_format.as_ref().map(|format| format.value),
+                level.start.as_ref().map(|start| start.value),
+                level.level_text.value.to_string(),
+            )
+        };
+        assert_eq!(
+            level(bullets, 0),
+            (Some(NumberingFormatVal::Bullet), None, "•".to_owned())
+        );
+        assert_eq!(
+            level(bullets, 1),
+            (Some(NumberingFormatVal::Bullet), None, "◦".to_owned())
+        );
+        assert_eq!(
+            level(roman, 0),
+            (
+                Some(NumberingFormatVal::LowerRoman),
+                Some(2),
+                "%1.".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn round_trip_tables() {
+        let cell = |text: &'static str| TableCell::from(Paragraph::default().push_text(text));
+        let merged = |text: &'static str, merge: VerticalMergeVal| {
+            cell(text).property(TableCellProperty::default().vertical_merge(merge))
+        };
+        let nested = Table::default()
+            .push_grid(vec![1000])
+            .push_row(TableRow::default().push_cell(cell("inner")));
+        let mut docx = Docx::default();
+        docx.document.push(
+            Table::default()
+                .push_grid(vec![1000, 2000, 3000])
+                .push_row(
+                    TableRow::default()
+                        .push_cell(
+                            cell("wide").property(TableCellProperty::default().grid_span(2usize)),
+                        )
+                        .push_cell(cell("c")),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(merged("tall", VerticalMergeVal::Restart))
+                        .push_cell(cell("d"))
+                        .push_cell(TableCell {
+                            property: Default::default(),
+                            content: vec![
+                                Paragraph::default().push_text("outer").into(),
+                                nested.into(),
+                            ],
+                        }),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(merged("", VerticalMergeVal::Continue))
+                        .push_cell(cell("e"))
+                        .push_cell(cell("f")),
+                ),
+        );
+
+        let docx = round_trip_odt(&docx);
+
+        let BodyContent::Table(table) = &docx.document.body.content[0] else {
+            panic!("expected a table");
+        };
+        let widths: Vec<_> = table.grids[0]
+            .columns
+            .iter()
+            .map(|column| column.width)
+            .collect();
+        assert_eq!(widths, [1000, 2000, 3000]);
+
+        // the span, the merge and the text of each cell
+        let rows: Vec<Vec<(usize, Option<bool>, String)>> = table
+            .iter_rows()
+            .map(|row| {
+                row.iter_cells()
+                    .map(|cell| {
+                        let span = cell
+                            .property
+                            .grid_span
+                            .as_ref()
+                            .map_or(1, |span| span.value);
+                        let restart = cell
+                            .property
+                            .vertical_merge
+                            .as_ref()
+                            .map(|merge| merge.is_restart());
+                        let text = cell
+                            .content
+                            .iter()
+                            .filter_map(|content| match content {
+                                TableCellContent::Paragraph(para) => Some(text(para)),
+                                _ => None,
+                            })
+                            .collect();
+                        (span, restart, text)
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec![(2, None, "wide".to_owned()), (1, None, "c".to_owned())],
+                vec![
+                    (1, Some(true), "tall".to_owned()),
+                    (1, None, "d".to_owned()),
+                    (1, None, "outer".to_owned()),
+                ],
+                vec![
+                    (1, Some(false), String::new()),
+                    (1, None, "e".to_owned()),
+                    (1, None, "f".to_owned()),
+                ],
+            ]
+        );
+
+        // the nested table is kept in its cell
+        let outer = table
+            .iter_rows()
+            .nth(1)
+            .unwrap()
+            .iter_cells()
+            .nth(2)
+            .unwrap();
+        let TableCellContent::Table(nested) = &outer.content[1] else {
+            panic!("expected a nested table");
+        };
+        let inner = nested
+            .iter_rows()
+            .next()
+            .unwrap()
+            .iter_cells()
+            .next()
+            .unwrap();
+        let TableCellContent::Paragraph(para) = &inner.content[0] else {
+            panic!("expected a paragraph");
+        };
+        assert_eq!(text(para), "inner");
+    }
+
+    #[test]
+    fn round_trip_images() {
+        let mut docx = Docx::default();
+        let (logo, _) = docx.embed_media(Bytes::from_static(b"png data"), "png");
+        let (photo, _) = docx.embed_media(Bytes::from_static(b"jpeg data"), "jpeg");
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    Run::default()
+                        .push(Drawing::picture(logo.clone(), 914400, 457200).name("Logo")),
+                )
+                .push_text(" and ")
+                .push(Run::default().push(Drawing::picture(photo, 1828800, 914400))),
+        );
+        docx.document.push(
+            Paragraph::default().push(Run::default().push(Drawing::picture(logo, 457200, 457200))),
+        );
+
+        let docx = round_trip_odt(&docx);
+
+        let drawings: Vec<_> = paragraphs(&docx.document.body.content)
+            .into_iter()
+            .flat_map(|para| &para.content)
+            .filter_map(|content| match content {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .flat_map(|run| &run.content)
+            .filter_map(|content| match content {
+                RunContent::Drawing(drawing) => Some(drawing),
+                _ => None,
+            })
+            .map(|drawing| {
+                let (_, data) = docx
+                    .embedded_media(drawing.embed.as_deref().unwrap())
+                    .unwrap();
+                (
+                    drawing.name.as_deref().unwrap_or_default().to_owned(),
+                    drawing.width,
+                    drawing.height,
+                    data.clone(),
+                )
+            })
+            .collect();
+
+        // unnamed pictures are named after their number
+        assert_eq!(
+            drawings,
+            vec![
+                (
+                    "Logo".to_owned(),
+                    Some(914400),
+                    Some(457200),
+                    Bytes::from_static(b"png data")
+                ),
+                (
+                    "Image2".to_owned(),
+                    Some(1828800),
+                    Some(914400),
+                    Bytes::from_static(b"jpeg data")
+                ),
+                (
+                    "Image2".to_owned(),
+                    Some(457200),
+                    Some(457200),
+                    Bytes::from_static(b"png data")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip_header_images() {
+        let mut docx = Docx::default();
+        let (logo, _) =
+            docx.embed_part_media(Some("word/header1.xml"), Bytes::from_static(b"png"), "png");
+        let header_id = docx
+            .relationships_mut(None)
+            .add_rel(SCHEMA_HEADER, "header1.xml");
+        docx.headers.insert(
+            "word/header1.xml".to_owned(),
+            Header {
+                content: vec![Paragraph::default()
+                    .push(Run::default().push(Drawing::picture(logo, 914400, 914400)))
+                    .into()],
+            },
+        );
+        docx.document.body.section =
+            Some(SectionProperty::default().push_header(HeaderReference::new(header_id)));
+
+        let docx = round_trip_odt(&docx);
+
+        let Some(BodyContent::Paragraph(para)) = docx.headers["word/header1.xml"].content.first()
+        else {
+            panic!("expected a paragraph");
+        };
+        let embed = para
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .flat_map(|run| &run.content)
+            .find_map(|content| match content {
+                RunContent::Drawing(drawing) => drawing.embed.as_deref(),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            docx.part_media(Some("word/header1.xml"), embed).unwrap().1,
+            &Bytes::from_static(b"png")
+        );
+        assert!(docx.embedded_media(embed).is_none());
+    }
+}
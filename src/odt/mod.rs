@@ -0,0 +1,231 @@
+//! OpenDocument Text conversion
+//!
+//! Converts documents to and from ODT packages: the `content.xml`,
+//! `styles.xml` and `meta.xml` parts, the pictures and the manifest.
+//!
+//! Paragraphs, headings, runs, styles with their inheritance, lists, tables,
+//! images, links, bookmarks, notes, comments, headers, footers and the page
+//! layout are mapped to their counterparts. The constructs which have none,
+//! or which are only partly converted, are reported in a [`ConversionLog`].
+
+mod export;
+mod import;
+
+pub use self::export::*;
+
+use std::fmt;
+
+/// The prefixes of the namespaces of the parts, by URI.
+pub(crate) const PREFIXES: &[(&str, &str)] = &[
+    ("office", "urn:oasis:names:tc:opendocument:xmlns:office:1.0"),
+    ("style", "urn:oasis:names:tc:opendocument:xmlns:style:1.0"),
+    ("text", "urn:oasis:names:tc:opendocument:xmlns:text:1.0"),
+    ("table", "urn:oasis:names:tc:opendocument:xmlns:table:1.0"),
+    ("draw", "urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"),
+    (
+        "fo",
+        "urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0",
+    ),
+    ("xlink", "http://www.w3.org/1999/xlink"),
+    ("dc", "http://purl.org/dc/elements/1.1/"),
+    ("meta", "urn:oasis:names:tc:opendocument:xmlns:meta:1.0"),
+    (
+        "svg",
+        "urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0",
+    ),
+];
+
+/// The constructs which couldn't be converted faithfully
+#[derive(Clone, Debug, Default)]
+pub struct ConversionLog {
+    pub entries: Vec<LogEntry>,
+}
+
+/// A construct which couldn't be converted faithfully
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    /// The name of the construct, like an element name.
+    pub construct: String,
+    /// What happened to it.
+    pub message: String,
+    /// How many times it happened.
+    pub count: usize,
+}
+
+impl ConversionLog {
+    /// Returns `true` if the conversion was faithful.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reports a construct, counting the repeated reports.
+    pub(crate) fn report(&mut self, construct: &str, message: &str) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.construct == construct && entry.message == message)
+        {
+            Some(entry) => entry.count += 1,
+            None => self.entries.push(LogEntry {
+                construct: construct.to_owned(),
+                message: message.to_owned(),
+                count: 1,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ConversionLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            write!(f, "{}: {}", entry.construct, entry.message)?;
+            if entry.count > 1 {
+                write!(f, " ({} times)", entry.count)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Twentieths of a point per inch.
+const TWIPS_PER_INCH: f64 = 1440.0;
+
+/// EMUs per inch.
+const EMUS_PER_INCH: f64 = 914400.0;
+
+/// Writes a length in inches, with at most four decimals.
+fn inches(value: f64) -> String {
+    format!("{}in", (value * 10000.0).round() / 10000.0)
+}
+
+/// Writes a length given in twentieths of a point.
+pub(crate) fn twips_length(twips: isize) -> String {
+    inches(twips as f64 / TWIPS_PER_INCH)
+}
+
+/// Writes a length given in EMUs.
+pub(crate) fn emus_length(emus: usize) -> String {
+    inches(emus as f64 / EMUS_PER_INCH)
+}
+
+/// Parses a length with its unit, returning it in inches.
+fn parse_inches(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let number: f64 = value[..split].trim().parse().ok()?;
+    let per_inch = match &value[split..] {
+        "in" | "inch" => 1.0,
+        "cm" => 2.54,
+        "mm" => 25.4,
+        "pt" => 72.0,
+        "pc" => 6.0,
+        "px" => 96.0,
+        _ => return None,
+    };
+    Some(number / per_inch)
+}
+
+/// Parses a length, returning it in twentieths of a point.
+pub(crate) fn parse_twips(value: &str) -> Option<isize> {
+    parse_inches(value).map(|inches| (inches * TWIPS_PER_INCH).round() as isize)
+}
+
+/// Parses a length, returning it in EMUs.
+pub(crate) fn parse_emus(value: &str) -> Option<usize> {
+    parse_inches(value)
+        .filter(|inches| *inches >= 0.0)
+        .map(|inches| (inches * EMUS_PER_INCH).round() as usize)
+}
+
+/// Returns the ODF name of a style id.
+///
+/// Style names are XML names, the other characters are encoded like `_20_`
+/// the way office suites do.
+pub(crate) fn style_name(style_id: &str) -> String {
+    let mut name = String::with_capacity(style_id.len());
+    for (index, c) in style_id.chars().enumerate() {
+        if c.is_alphanumeric() && !(index == 0 && c.is_numeric())
+            || (index > 0 && matches!(c, '-' | '.'))
+            || c == '_'
+        {
+            name.push(c);
+        } else {
+            name += &format!("_{:x}_", c as u32);
+        }
+    }
+    name
+}
+
+/// Decodes the characters encoded in an ODF style name.
+pub(crate) fn decode_style_name(name: &str) -> String {
+    let mut decoded = String::with_capacity(name.len());
+    let mut rest = name;
+
+    while let Some(start) = rest.find('_') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        // only the characters which `style_name` encodes are decoded
+        let encoded = rest.find('_').filter(|end| *end >= 2).and_then(|end| {
+            let c = u32::from_str_radix(&rest[..end], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|c| !c.is_alphanumeric() || (decoded.is_empty() && c.is_numeric()))?;
+            Some((c, end))
+        });
+        match encoded {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => decoded.push('_'),
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_lengths() {
+        assert_eq!(twips_length(1440), "1in");
+        assert_eq!(twips_length(-720), "-0.5in");
+        assert_eq!(emus_length(457200), "0.5in");
+        assert_eq!(parse_twips("2.54cm"), Some(1440));
+        assert_eq!(parse_twips("12pt"), Some(240));
+        assert_eq!(parse_twips("0.5in"), Some(720));
+        assert_eq!(parse_twips("10%"), None);
+        assert_eq!(parse_emus("96px"), Some(914400));
+    }
+
+    #[test]
+    fn convert_style_names() {
+        assert_eq!(style_name("Heading1"), "Heading1");
+        assert_eq!(style_name("Text body"), "Text_20_body");
+        assert_eq!(style_name("1st"), "_31_st");
+        assert_eq!(decode_style_name("Text_20_body"), "Text body");
+        assert_eq!(decode_style_name("_31_st"), "1st");
+        assert_eq!(decode_style_name("snake_case"), "snake_case");
+        assert_eq!(decode_style_name("a_bc_d"), "a_bc_d");
+    }
+
+    #[test]
+    fn report_constructs() {
+        let mut log = ConversionLog::default();
+        log.report("draw:custom-shape", "dropped");
+        log.report("draw:custom-shape", "dropped");
+        log.report("text:index", "converted to text");
+        assert_eq!(log.entries.len(), 2);
+        assert_eq!(
+            log.to_string(),
+            "draw:custom-shape: dropped (2 times)\ntext:index: converted to text\n"
+        );
+    }
+}
@@ -0,0 +1,674 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    convert::is_on,
+    core::Core,
+    document::{
+        BodyContent, BreakType, Drawing, Paragraph, ParagraphContent, Run, RunContent, Table,
+        TableCellContent,
+    },
+    formatting::{CharacterProperty, UnderlineStyle, VerticalAlignVal, VerticalMergeVal},
+    numbering::{ListCounter, ListItem, NumberingFormatVal},
+    odt::emus_length,
+    pandoc::{
+        stringify, Alignment, Attr, Block, Cell, ColWidth, Inline, ListAttributes, ListNumberDelim,
+        ListNumberStyle, MetaValue, Pandoc, PandocOptions, Row, CUSTOM_STYLE,
+    },
+    toc::TableOfContents,
+    Docx,
+};
+
+impl<'a> Docx<'a> {
+    /// Converts the document to pandoc's AST.
+    ///
+    /// Headings, lists, quotes and code are recognized by their styles, and
+    /// the other paragraph and character styles become the `custom-style` of
+    /// divs and spans. Notes are placed where they're referenced and images
+    /// are inlined as data URIs.
+    ///
+    /// ```rust
+    /// use docx::document::*;
+    /// use docx::formatting::{CharacterProperty, ParagraphProperty};
+    /// use docx::pandoc::{Block, Inline, PandocOptions};
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .property(ParagraphProperty::default().style_id("Heading1"))
+    ///         .push_text("Release notes"),
+    /// );
+    /// docx.document.push(
+    ///     Paragraph::default()
+    ///         .push_text("Now ")
+    ///         .push(Run::default().property(CharacterProperty::default().bold(true)).push_text("faster")),
+    /// );
+    ///
+    /// let pandoc = docx.to_pandoc(&PandocOptions::default());
+    /// assert!(matches!(&pandoc.blocks[0], Block::Header(1, _, _)));
+    /// assert_eq!(
+    ///     pandoc.blocks[1],
+    ///     Block::Para(vec![
+    ///         Inline::Str("Now".into()),
+    ///         Inline::Space,
+    ///         Inline::Strong(vec![Inline::Str("faster".into())]),
+    ///     ])
+    /// );
+    /// ```
+    pub fn to_pandoc(&self, options: &PandocOptions) -> Pandoc {
+        let numbering = Default::default();
+        let mut writer = Writer {
+            docx: self,
+            options,
+            toc: options
+                .styles
+                .headings
+                .iter()
+                .enumerate()
+                .fold(TableOfContents::default(), |toc, (level, style_id)| {
+                    toc.heading_style(style_id.clone(), level + 1)
+                }),
+            lists: ListCounter::new(self.numbering.as_ref().unwrap_or(&numbering)),
+        };
+
+        let mut builder = Builder::default();
+        writer.blocks(&self.document.body.content, &mut builder);
+
+        Pandoc {
+            meta: self.core.as_ref().map(meta).unwrap_or_default(),
+            blocks: builder.finish(),
+            ..Default::default()
+        }
+    }
+}
+
+struct Writer<'d, 'o, 'n, 'a> {
+    docx: &'d Docx<'a>,
+    options: &'o PandocOptions<'o>,
+    toc: TableOfContents<'o>,
+    lists: ListCounter<'n, 'a>,
+}
+
+/// Collects blocks, nesting the items of lists by level.
+#[derive(Default)]
+struct Builder {
+    blocks: Vec<Block>,
+    lists: Vec<List>,
+}
+
+/// A list which may be followed by more items
+struct List {
+    level: usize,
+    /// The attributes of ordered lists.
+    ordered: Option<ListAttributes>,
+    items: Vec<Vec<Block>>,
+}
+
+impl Builder {
+    fn push(&mut self, block: Block) {
+        while !self.lists.is_empty() {
+            self.close_list();
+        }
+        push_block(&mut self.blocks, block);
+    }
+
+    fn push_item(&mut self, level: usize, ordered: Option<ListAttributes>, block: Block) {
+        while self.lists.last().is_some_and(|list| list.level > level) {
+            self.close_list();
+        }
+
+        let continues = self
+            .lists
+            .last()
+            .filter(|list| list.level == level)
+            .map(|list| list.ordered.is_some() == ordered.is_some());
+        match (continues, self.lists.last_mut()) {
+            (Some(true), Some(list)) => list.items.push(vec![block]),
+            (continues, _) => {
+                if continues == Some(false) {
+                    self.close_list();
+                }
+                self.lists.push(List {
+                    level,
+                    ordered,
+                    items: vec![vec![block]],
+                });
+            }
+        }
+    }
+
+    fn close_list(&mut self) {
+        let Some(list) = self.lists.pop() else {
+            return;
+        };
+        let block = match list.ordered {
+            Some(attributes) => Block::OrderedList(attributes, list.items),
+            None => Block::BulletList(list.items),
+        };
+        match self
+            .lists
+            .last_mut()
+            .and_then(|parent| parent.items.last_mut())
+        {
+            Some(item) => item.push(block),
+            None => self.blocks.push(block),
+        }
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        while !self.lists.is_empty() {
+            self.close_list();
+        }
+        self.blocks
+    }
+}
+
+impl<'d, 'o, 'n, 'a> Writer<'d, 'o, 'n, 'a> {
+    fn blocks(&mut self, content: &[BodyContent], builder: &mut Builder) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para, builder),
+                BodyContent::Table(table) => {
+                    let block = self.table(table);
+                    builder.push(block);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content, builder),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph, builder: &mut Builder) {
+        let style_id = para.property.style_id.as_ref().map(|id| &*id.value);
+        let styles = &self.options.styles;
+
+        if style_id == Some(&*styles.code_block) {
+            let line = para.iter_text().map(|text| &**text).collect::<String>();
+            return builder.push(Block::CodeBlock(Attr::default(), line));
+        }
+
+        let mut inlines = Vec::new();
+        self.inlines(&para.content, &mut inlines);
+        trim(&mut inlines);
+
+        if let Some(level) = self.toc.heading_level(para, &self.docx.styles) {
+            // the first bookmark identifies the heading
+            let bookmark = inlines.iter().position(|inline| match inline {
+                Inline::Span(attr, content) => content.is_empty() && !attr.0.is_empty(),
+                _ => false,
+            });
+            let id = match bookmark.map(|index| inlines.remove(index)) {
+                Some(Inline::Span(attr, _)) => attr.0,
+                _ => String::new(),
+            };
+            if !inlines.is_empty() {
+                builder.push(Block::Header(level, (id, Vec::new(), Vec::new()), inlines));
+            }
+            return;
+        }
+
+        if let Some(item) = para
+            .property
+            .numbering
+            .as_ref()
+            .and_then(|numbering| self.lists.next(numbering))
+        {
+            let ordered = (!item.is_bullet()).then(|| list_attributes(&item));
+            builder.push_item(item.level, ordered, Block::Plain(inlines));
+            return;
+        }
+
+        if inlines.is_empty() {
+            return;
+        }
+
+        let block = Block::Para(inlines);
+        match style_id {
+            Some(style_id) if style_id == styles.quote => {
+                builder.push(Block::BlockQuote(vec![block]))
+            }
+            Some(style_id) if style_id != "Normal" => {
+                let attr = self.custom_style(style_id);
+                builder.push(Block::Div(attr, vec![block]));
+            }
+            _ => builder.push(block),
+        }
+    }
+
+    /// Returns the attributes carrying the name of a style.
+    fn custom_style(&self, style_id: &str) -> Attr {
+        let name = self
+            .docx
+            .styles
+            .get(style_id)
+            .and_then(|style| style.name.as_ref())
+            .map_or(style_id, |name| &*name.value);
+        (
+            String::new(),
+            Vec::new(),
+            vec![(CUSTOM_STYLE.to_owned(), name.to_owned())],
+        )
+    }
+
+    fn table(&mut self, table: &Table) -> Block {
+        // the grid column, the column span, the vertical merge and the cell
+        let rows: Vec<Vec<_>> = table
+            .iter_rows()
+            .map(|row| {
+                let mut column = 0;
+                row.iter_cells()
+                    .map(|cell| {
+                        let span = cell
+                            .property
+                            .grid_span
+                            .as_ref()
+                            .map_or(1, |span| span.value.max(1));
+                        let merge = cell.property.vertical_merge.as_ref().map(|merge| {
+                            if merge.is_restart() {
+                                VerticalMergeVal::Restart
+                            } else {
+                                VerticalMergeVal::Continue
+                            }
+                        });
+                        column += span;
+                        (column - span, span, merge, cell)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut body = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells: Vec<Cell> = Vec::new();
+            for (column, span, merge, cell) in row {
+                if matches!(merge, Some(VerticalMergeVal::Continue)) {
+                    continue;
+                }
+                let mut row_span = 1;
+                if matches!(merge, Some(VerticalMergeVal::Restart)) {
+                    row_span += rows[index + 1..]
+                        .iter()
+                        .take_while(|row| {
+                            row.iter().any(|(other, _, merge, _)| {
+                                other == column && matches!(merge, Some(VerticalMergeVal::Continue))
+                            })
+                        })
+                        .count();
+                }
+
+                let mut builder = Builder::default();
+                for content in &cell.content {
+                    match content {
+                        TableCellContent::Paragraph(para) => self.paragraph(para, &mut builder),
+                        TableCellContent::Sdt(sdt) => {
+                            self.blocks(&sdt.content.content, &mut builder)
+                        }
+                        TableCellContent::Table(table) => {
+                            let block = self.table(table);
+                            builder.push(block);
+                        }
+                    }
+                }
+                cells.push((
+                    Attr::default(),
+                    Alignment::AlignDefault,
+                    row_span,
+                    *span,
+                    builder.finish(),
+                ));
+            }
+            body.push((Attr::default(), cells) as Row);
+        }
+
+        let columns = rows
+            .iter()
+            .filter_map(|row| row.last().map(|(column, span, _, _)| column + span))
+            .max()
+            .unwrap_or_default();
+        let widths: Vec<usize> = table
+            .grids
+            .first()
+            .map(|grid| grid.columns.iter().map(|column| column.width).collect())
+            .unwrap_or_default();
+        let total: usize = widths.iter().sum();
+        let col_specs = (0..columns.max(widths.len()))
+            .map(|column| {
+                let width = match widths.get(column) {
+                    Some(width) if total > 0 => ColWidth::ColWidth(*width as f64 / total as f64),
+                    _ => ColWidth::ColWidthDefault,
+                };
+                (Alignment::AlignDefault, width)
+            })
+            .collect();
+
+        Block::Table(
+            Attr::default(),
+            (None, Vec::new()),
+            col_specs,
+            (Attr::default(), Vec::new()),
+            vec![(Attr::default(), 0, Vec::new(), body)],
+            (Attr::default(), Vec::new()),
+        )
+    }
+
+    fn inlines(&mut self, content: &[ParagraphContent], inlines: &mut Vec<Inline>) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => self.run(run, inlines),
+                ParagraphContent::Link(hyperlink) => {
+                    let url = match (&hyperlink.id, &hyperlink.anchor) {
+                        (Some(id), _) => self
+                            .docx
+                            .document_rels
+                            .as_ref()
+                            .and_then(|rels| rels.get_target(id))
+                            .map(str::to_owned),
+                        (None, Some(anchor)) => Some(format!("#{}", anchor)),
+                        (None, None) => None,
+                    };
+                    let mut text = Vec::new();
+                    if let Some(run) = &hyperlink.content {
+                        self.run(run, &mut text);
+                    }
+                    match url {
+                        Some(url) if !text.is_empty() => push_inline(
+                            inlines,
+                            Inline::Link(Attr::default(), text, (url, String::new())),
+                        ),
+                        _ => text
+                            .into_iter()
+                            .for_each(|inline| push_inline(inlines, inline)),
+                    }
+                }
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    // bookmarks starting with `_` are hidden, e.g. `_GoBack`
+                    if let Some(name) = bookmark
+                        .name
+                        .as_deref()
+                        .filter(|name| !name.starts_with('_'))
+                    {
+                        inlines.push(Inline::Span(
+                            (name.to_owned(), Vec::new(), Vec::new()),
+                            Vec::new(),
+                        ));
+                    }
+                }
+                ParagraphContent::SmartTag(tag) => self.inlines(&tag.content, inlines),
+                ParagraphContent::Sdt(sdt) => self.inlines(&sdt.content.content, inlines),
+                ParagraphContent::SimpleField(field) => self.inlines(&field.content, inlines),
+                ParagraphContent::Insertion(ins) => self.inlines(&ins.content, inlines),
+                ParagraphContent::MoveTo(to) => self.inlines(&to.content, inlines),
+                _ => (),
+            }
+        }
+    }
+
+    fn run(&mut self, run: &Run, inlines: &mut Vec<Inline>) {
+        let prop = &run.property;
+        if prop
+            .vanish
+            .as_ref()
+            .is_some_and(|vanish| is_on(vanish.value))
+        {
+            return;
+        }
+
+        let docx = self.docx;
+        let mut text = Vec::new();
+        for content in &run.content {
+            let note = match content {
+                RunContent::Text(content) => {
+                    for inline in words(&content.text) {
+                        push_inline(&mut text, inline);
+                    }
+                    continue;
+                }
+                RunContent::Tab(_) => {
+                    push_inline(&mut text, Inline::Space);
+                    continue;
+                }
+                RunContent::Break(br) => {
+                    if !matches!(br.ty, Some(BreakType::Page) | Some(BreakType::Column)) {
+                        text.push(Inline::LineBreak);
+                    }
+                    continue;
+                }
+                RunContent::Drawing(drawing) => {
+                    text.extend(self.image(drawing));
+                    continue;
+                }
+                RunContent::FootnoteReference(reference) => docx
+                    .footnotes
+                    .as_ref()
+                    .and_then(|notes| notes.get(&reference.id))
+                    .map(|note| &note.content),
+                RunContent::EndnoteReference(reference) => docx
+                    .endnotes
+                    .as_ref()
+                    .and_then(|notes| notes.get(&reference.id))
+                    .map(|note| &note.content),
+                _ => continue,
+            };
+
+            // the formatting applies to the text, not to the notes
+            for inline in self.format(prop, std::mem::take(&mut text)) {
+                push_inline(inlines, inline);
+            }
+            if let Some(content) = note {
+                let mut builder = Builder::default();
+                self.blocks(content, &mut builder);
+                inlines.push(Inline::Note(builder.finish()));
+            }
+        }
+        for inline in self.format(prop, text) {
+            push_inline(inlines, inline);
+        }
+    }
+
+    /// Wraps inlines in the formatting of a run.
+    fn format(&self, prop: &CharacterProperty, mut inlines: Vec<Inline>) -> Vec<Inline> {
+        if inlines.is_empty() {
+            return inlines;
+        }
+
+        let styles = &self.options.styles;
+        let style_id = prop.style_id.as_ref().map(|id| &*id.value);
+        let code = style_id == Some(&*styles.code_span);
+        if code {
+            inlines = vec![Inline::Code(Attr::default(), stringify(&inlines))];
+        }
+        match prop.vertical_align.as_ref().map(|align| &align.value) {
+            Some(VerticalAlignVal::Superscript) => inlines = vec![Inline::Superscript(inlines)],
+            Some(VerticalAlignVal::Subscript) => inlines = vec![Inline::Subscript(inlines)],
+            _ => (),
+        }
+        if prop
+            .strike
+            .as_ref()
+            .is_some_and(|strike| is_on(strike.value))
+            || prop
+                .dstrike
+                .as_ref()
+                .is_some_and(|dstrike| is_on(dstrike.value))
+        {
+            inlines = vec![Inline::Strikeout(inlines)];
+        }
+        if prop
+            .underline
+            .as_ref()
+            .is_some_and(|underline| !matches!(underline.val, None | Some(UnderlineStyle::None)))
+        {
+            inlines = vec![Inline::Underline(inlines)];
+        }
+        if prop
+            .italics
+            .as_ref()
+            .is_some_and(|italics| is_on(italics.value))
+        {
+            inlines = vec![Inline::Emph(inlines)];
+        }
+        if prop.bold.as_ref().is_some_and(|bold| is_on(bold.value)) {
+            inlines = vec![Inline::Strong(inlines)];
+        }
+        match style_id {
+            Some(style_id) if !code && style_id != styles.hyperlink => {
+                vec![Inline::Span(self.custom_style(style_id), inlines)]
+            }
+            _ => inlines,
+        }
+    }
+
+    fn image(&self, drawing: &Drawing) -> Option<Inline> {
+        let (name, data) = self.docx.embedded_media(drawing.embed.as_deref()?)?;
+        let ext = name.rsplit('.').next().unwrap_or_default();
+        let ty = crate::content_type::image_content_type(&ext.to_ascii_lowercase())
+            .unwrap_or("application/octet-stream");
+        let url = format!("data:{};base64,{}", ty, crate::base64::encode(data));
+
+        let mut attributes = Vec::new();
+        if let (Some(width), Some(height)) = (drawing.width, drawing.height) {
+            attributes.push(("width".to_owned(), emus_length(width)));
+            attributes.push(("height".to_owned(), emus_length(height)));
+        }
+        let alt = words(drawing.name.as_deref().unwrap_or_default());
+
+        Some(Inline::Image(
+            (String::new(), Vec::new(), attributes),
+            alt,
+            (url, String::new()),
+        ))
+    }
+}
+
+/// Appends a block, merging it with the previous one if they're both code,
+/// quotes, or divs of the same style.
+fn push_block(blocks: &mut Vec<Block>, block: Block) {
+    match (blocks.last_mut(), block) {
+        (Some(Block::Div(attr, last)), Block::Div(next, content)) if *attr == next => {
+            last.extend(content)
+        }
+        (Some(Block::BlockQuote(last)), Block::BlockQuote(content)) => last.extend(content),
+        (Some(Block::CodeBlock(_, last)), Block::CodeBlock(_, code)) => {
+            last.push('\n');
+            last.push_str(&code);
+        }
+        (_, block) => blocks.push(block),
+    }
+}
+
+/// Appends an inline, merging it with the previous one if they're both text
+/// or have the same formatting.
+fn push_inline(inlines: &mut Vec<Inline>, inline: Inline) {
+    macro_rules! merge {
+        ($last:expr, $content:expr) => {
+            for inline in $content {
+                push_inline($last, inline);
+            }
+        };
+    }
+
+    match (inlines.last_mut(), inline) {
+        (Some(Inline::Str(last)), Inline::Str(text)) => last.push_str(&text),
+        (Some(Inline::Space), Inline::Space) => (),
+        (Some(Inline::Emph(last)), Inline::Emph(content))
+        | (Some(Inline::Underline(last)), Inline::Underline(content))
+        | (Some(Inline::Strong(last)), Inline::Strong(content))
+        | (Some(Inline::Strikeout(last)), Inline::Strikeout(content))
+        | (Some(Inline::Superscript(last)), Inline::Superscript(content))
+        | (Some(Inline::Subscript(last)), Inline::Subscript(content)) => merge!(last, content),
+        (Some(Inline::Code(_, last)), Inline::Code(_, code)) => last.push_str(&code),
+        (Some(Inline::Span(attr, last)), Inline::Span(next, content))
+            if *attr == next && attr.0.is_empty() =>
+        {
+            merge!(last, content)
+        }
+        (Some(Inline::Link(attr, last, target)), Inline::Link(next, content, next_target))
+            if *attr == next && *target == next_target =>
+        {
+            merge!(last, content)
+        }
+        (_, inline) => inlines.push(inline),
+    }
+}
+
+/// Splits text into words and spaces.
+fn words(text: &str) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    for (index, word) in text.split(char::is_whitespace).enumerate() {
+        if index > 0 {
+            push_inline(&mut inlines, Inline::Space);
+        }
+        if !word.is_empty() {
+            push_inline(&mut inlines, Inline::Str(word.to_owned()));
+        }
+    }
+    inlines
+}
+
+/// Removes the spaces at the start and at the end of inlines.
+fn trim(inlines: &mut Vec<Inline>) {
+    while matches!(inlines.first(), Some(Inline::Space)) {
+        inlines.remove(0);
+    }
+    while matches!(inlines.last(), Some(Inline::Space)) {
+        inlines.pop();
+    }
+}
+
+fn list_attributes(item: &ListItem) -> ListAttributes {
+    let style = match item.format {
+        NumberingFormatVal::Decimal | NumberingFormatVal::DecimalZero => ListNumberStyle::Decimal,
+        NumberingFormatVal::LowerLetter => ListNumberStyle::LowerAlpha,
+        NumberingFormatVal::UpperLetter => ListNumberStyle::UpperAlpha,
+        NumberingFormatVal::LowerRoman => ListNumberStyle::LowerRoman,
+        NumberingFormatVal::UpperRoman => ListNumberStyle::UpperRoman,
+        _ => ListNumberStyle::DefaultStyle,
+    };
+    let delim = if item.label.ends_with(')') {
+        if item.label.starts_with('(') {
+            ListNumberDelim::TwoParens
+        } else {
+            ListNumberDelim::OneParen
+        }
+    } else if item.label.ends_with('.') {
+        ListNumberDelim::Period
+    } else {
+        ListNumberDelim::DefaultDelim
+    };
+    (item.number as i64, style, delim)
+}
+
+/// Maps the core properties to metadata.
+fn meta(core: &Core) -> BTreeMap<String, MetaValue> {
+    let mut meta = BTreeMap::new();
+    let mut insert = |key: &str, value: Option<&str>| {
+        if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+            meta.insert(key.to_owned(), MetaValue::MetaInlines(words(value.trim())));
+        }
+    };
+    insert("title", core.title.as_deref());
+    insert("subject", core.subject.as_deref());
+    insert("description", core.description.as_deref());
+
+    if let Some(creator) = core
+        .creator
+        .as_deref()
+        .filter(|creator| !creator.is_empty())
+    {
+        meta.insert(
+            "author".to_owned(),
+            MetaValue::MetaList(vec![MetaValue::MetaInlines(words(creator))]),
+        );
+    }
+    if let Some(keywords) = core.keywords.as_deref() {
+        let keywords: Vec<MetaValue> = keywords
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(|keyword| MetaValue::MetaInlines(words(keyword)))
+            .collect();
+        if !keywords.is_empty() {
+            meta.insert("keywords".to_owned(), MetaValue::MetaList(keywords));
+        }
+    }
+    meta
+}
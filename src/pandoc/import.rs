@@ -0,0 +1,832 @@
+use std::borrow::Cow;
+
+use crate::{
+    convert::{decode_data_uri, merged_cell, pixel_size, text_of, VerticalMerges, TABLE_WIDTH},
+    core::Core,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, Break, Drawing, FootnoteRef, FootnoteReference,
+        Hyperlink, Paragraph, ParagraphContent, Run, RunContent, Table, TableCellContent, TableRow,
+    },
+    footnotes::{Footnote, Footnotes},
+    formatting::{
+        BorderStyle, Borders, BottomBorder, CharacterProperty, Justification, JustificationVal,
+        NumberingProperty, ParagraphProperty, TableBorders, TableProperty, TopBorder,
+        UnderlineStyle, VerticalAlign, VerticalAlignVal, VerticalMergeVal,
+    },
+    numbering::NumberingFormatVal,
+    odt::parse_emus,
+    pandoc::{
+        attr_value, stringify, Alignment, Attr, Block, Caption, ColWidth, Inline, ListNumberStyle,
+        MetaValue, Pandoc, PandocOptions, QuoteType, Row, CUSTOM_STYLE,
+    },
+    rels::Relationships,
+    schema::SCHEMA_HYPERLINK,
+    styles::{Style, StyleType},
+    Docx,
+};
+
+/// EMUs per pixel.
+const EMUS_PER_PIXEL: usize = 9525;
+
+impl<'a> Docx<'a> {
+    /// Converts pandoc's AST to a document.
+    ///
+    /// ```rust
+    /// use docx::pandoc::{Pandoc, PandocOptions};
+    /// use docx::Docx;
+    ///
+    /// let pandoc = Pandoc::from_json(r#"{
+    ///     "pandoc-api-version": [1, 23, 1],
+    ///     "meta": {},
+    ///     "blocks": [
+    ///         {"t": "Header", "c": [1, ["", [], []], [{"t": "Str", "c": "Notes"}]]},
+    ///         {"t": "Div", "c": [["", [], [["custom-style", "Body Text"]]], [
+    ///             {"t": "Para", "c": [{"t": "Strong", "c": [{"t": "Str", "c": "Bold"}]}]}
+    ///         ]]}
+    ///     ]
+    /// }"#)
+    /// .unwrap();
+    ///
+    /// let docx = Docx::from_pandoc(&pandoc, &PandocOptions::default());
+    /// assert_eq!(docx.document.body.content.len(), 2);
+    /// assert!(docx.styles.get("BodyText").is_some());
+    /// ```
+    pub fn from_pandoc(pandoc: &Pandoc, options: &PandocOptions<'a>) -> Docx<'a> {
+        let mut docx = Docx::default();
+        docx.import_pandoc(pandoc, options);
+        docx
+    }
+
+    /// Appends pandoc's AST to the document.
+    ///
+    /// Styles which the document doesn't define yet are added from
+    /// [`StyleIds::definitions`], so a template can provide its own.
+    /// The `custom-style` of divs and spans refers to styles by name, and
+    /// the missing ones are added too. The metadata replaces the core
+    /// properties.
+    ///
+    /// [`StyleIds::definitions`]: crate::convert::StyleIds::definitions
+    pub fn import_pandoc(&mut self, pandoc: &Pandoc, options: &PandocOptions<'a>) {
+        options.styles.add_definitions(&mut self.styles);
+
+        let core = core(pandoc);
+        let mut reader = Reader {
+            docx: self,
+            options,
+            blocks: Vec::new(),
+            item: None,
+            text_run: false,
+            bookmarks: 0,
+        };
+        reader.blocks(&pandoc.blocks, &Context::default());
+
+        let blocks = reader.blocks;
+        self.document.body.content.extend(blocks);
+        if core.is_some() {
+            self.core = core;
+        }
+    }
+}
+
+struct Reader<'d, 'o, 'a> {
+    docx: &'d mut Docx<'a>,
+    options: &'o PandocOptions<'a>,
+    blocks: Vec<BodyContent<'a>>,
+    /// The numbering of the first paragraph of the current list item.
+    item: Option<NumberingProperty>,
+    /// Whether the last content of the paragraph is a run of text in the
+    /// current format, which the next text is appended to.
+    text_run: bool,
+    bookmarks: usize,
+}
+
+/// The formatting inherited from the enclosing elements
+#[derive(Clone, Default)]
+struct Context<'a> {
+    style_id: Option<Cow<'a, str>>,
+    character: CharacterProperty<'a>,
+    justification: Option<JustificationVal>,
+    /// The depth of the enclosing lists.
+    lists: usize,
+}
+
+impl<'d, 'o, 'a> Reader<'d, 'o, 'a> {
+    fn blocks(&mut self, blocks: &[Block], context: &Context<'a>) {
+        for block in blocks {
+            self.block(block, context);
+        }
+    }
+
+    fn block(&mut self, block: &Block, context: &Context<'a>) {
+        let styles = &self.options.styles;
+        match block {
+            Block::Plain(inlines) | Block::Para(inlines) => self.paragraph(inlines, context),
+            Block::LineBlock(lines) => {
+                let mut inlines = Vec::new();
+                for (index, line) in lines.iter().enumerate() {
+                    if index > 0 {
+                        inlines.push(Inline::LineBreak);
+                    }
+                    inlines.extend(line.iter().cloned());
+                }
+                self.paragraph(&inlines, context);
+            }
+            Block::CodeBlock(_, code) => {
+                let context = Context {
+                    style_id: Some(styles.code_block.clone()),
+                    ..context.clone()
+                };
+                for line in code.split('\n') {
+                    let mut paragraph = self.start_paragraph(&context);
+                    if !line.is_empty() {
+                        paragraph = paragraph.push(Run::default().push_text(text_of(line)));
+                    }
+                    self.blocks.push(paragraph.into());
+                }
+            }
+            Block::RawBlock(..) => (),
+            Block::BlockQuote(blocks) => {
+                let context = Context {
+                    style_id: Some(styles.quote.clone()),
+                    ..context.clone()
+                };
+                self.blocks(blocks, &context);
+            }
+            Block::OrderedList((start, style, _), items) => {
+                let format = match style {
+                    ListNumberStyle::LowerRoman => NumberingFormatVal::LowerRoman,
+                    ListNumberStyle::UpperRoman => NumberingFormatVal::UpperRoman,
+                    ListNumberStyle::LowerAlpha => NumberingFormatVal::LowerLetter,
+                    ListNumberStyle::UpperAlpha => NumberingFormatVal::UpperLetter,
+                    _ => NumberingFormatVal::Decimal,
+                };
+                self.list(items, format, (*start).max(0) as usize, context);
+            }
+            Block::BulletList(items) => self.list(items, NumberingFormatVal::Bullet, 1, context),
+            Block::DefinitionList(items) => {
+                for (term, definitions) in items {
+                    let term_context = Context {
+                        character: context.character.clone().bold(true),
+                        ..context.clone()
+                    };
+                    self.paragraph(term, &term_context);
+                    for definition in definitions {
+                        self.blocks(definition, context);
+                    }
+                }
+            }
+            Block::Header(level, attr, inlines) => {
+                let context = Context {
+                    style_id: Some(styles.headings[level.saturating_sub(1).min(5)].clone()),
+                    ..context.clone()
+                };
+                let mut paragraph = self.start_paragraph(&context);
+                self.bookmarked(attr, &mut paragraph, |reader, paragraph| {
+                    reader.inlines(inlines, &context, paragraph)
+                });
+                self.blocks.push(paragraph.into());
+            }
+            Block::HorizontalRule => {
+                let border = BottomBorder::default()
+                    .style(BorderStyle::Single)
+                    .size(6usize)
+                    .color("auto");
+                self.blocks.push(
+                    Paragraph::default()
+                        .property(
+                            ParagraphProperty::default().border(Borders::default().bottom(border)),
+                        )
+                        .into(),
+                );
+            }
+            Block::Table(_, caption, col_specs, head, bodies, foot) => {
+                self.caption(caption, context);
+                let head_context = Context {
+                    character: context.character.clone().bold(true),
+                    ..context.clone()
+                };
+
+                let mut rows: Vec<(&Row, &Context)> = Vec::new();
+                rows.extend(head.1.iter().map(|row| (row, &head_context)));
+                for (_, _, head, body) in bodies {
+                    rows.extend(head.iter().map(|row| (row, &head_context)));
+                    rows.extend(body.iter().map(|row| (row, context)));
+                }
+                rows.extend(foot.1.iter().map(|row| (row, context)));
+
+                let table = self.table(col_specs, &rows);
+                self.blocks.push(table.into());
+            }
+            Block::Figure(_, caption, blocks) => {
+                self.blocks(blocks, context);
+                self.caption(caption, context);
+            }
+            Block::Div(attr, blocks) => {
+                let mut context = context.clone();
+                if let Some(name) = attr_value(attr, CUSTOM_STYLE) {
+                    context.style_id = Some(self.style_id(name, StyleType::Paragraph).into());
+                }
+                self.blocks(blocks, &context);
+            }
+        }
+    }
+
+    fn start_paragraph(&mut self, context: &Context<'a>) -> Paragraph<'a> {
+        self.text_run = false;
+
+        let style_id = context
+            .style_id
+            .clone()
+            .or_else(|| (context.lists > 0).then(|| self.options.styles.list.clone()));
+        let mut property = ParagraphProperty::default();
+        if let Some(style_id) = style_id {
+            property = property.style_id(style_id);
+        }
+        if let Some(numbering) = self.item.take() {
+            property = property.numbering(numbering);
+        }
+        if let Some(justification) = &context.justification {
+            property = property.justification(Justification::from(justification.clone()));
+        }
+        Paragraph::default().property(property)
+    }
+
+    fn paragraph(&mut self, inlines: &[Inline], context: &Context<'a>) {
+        let mut paragraph = self.start_paragraph(context);
+        self.inlines(inlines, context, &mut paragraph);
+        self.blocks.push(paragraph.into());
+    }
+
+    fn caption(&mut self, (_, blocks): &Caption, context: &Context<'a>) {
+        self.blocks(blocks, context);
+    }
+
+    fn list(
+        &mut self,
+        items: &[Vec<Block>],
+        format: NumberingFormatVal,
+        start: usize,
+        context: &Context<'a>,
+    ) {
+        // an item which starts with a nested list isn't numbered
+        self.item = None;
+        let num_id = self
+            .docx
+            .numbering
+            .get_or_insert_with(Default::default)
+            .push_list(format, start);
+
+        let context = Context {
+            lists: context.lists + 1,
+            ..context.clone()
+        };
+        for item in items {
+            self.item = Some(NumberingProperty::from((num_id, context.lists - 1)));
+            self.blocks(item, &context);
+            self.item = None;
+        }
+    }
+
+    fn table(
+        &mut self,
+        col_specs: &[(Alignment, ColWidth)],
+        rows: &[(&Row, &Context<'a>)],
+    ) -> Table<'a> {
+        let columns = col_specs.len().max(1);
+        let fixed: f64 = col_specs
+            .iter()
+            .map(|(_, width)| match width {
+                ColWidth::ColWidth(width) => width.max(0.0),
+                ColWidth::ColWidthDefault => 0.0,
+            })
+            .sum();
+        let defaults = col_specs
+            .iter()
+            .filter(|(_, width)| matches!(width, ColWidth::ColWidthDefault))
+            .count();
+        let default_width = if fixed < 1.0 && defaults > 0 {
+            (1.0 - fixed) / defaults as f64
+        } else {
+            1.0 / columns as f64
+        };
+        let widths: Vec<usize> = if col_specs.is_empty() {
+            vec![TABLE_WIDTH]
+        } else {
+            col_specs
+                .iter()
+                .map(|(_, width)| {
+                    let width = match width {
+                        ColWidth::ColWidth(width) if fixed > 0.0 => width / fixed.max(1.0),
+                        _ => default_width,
+                    };
+                    (width * TABLE_WIDTH as f64).round().max(1.0) as usize
+                })
+                .collect()
+        };
+
+        let mut table = Table::default()
+            .property(
+                TableProperty::default()
+                    .style_id(self.options.styles.table.clone())
+                    .borders(
+                        TableBorders::default()
+                            .top(TopBorder::default().style(BorderStyle::Single))
+                            .bottom(BottomBorder::default().style(BorderStyle::Single)),
+                    ),
+            )
+            .push_grid(widths);
+
+        let mut merges = VerticalMerges::default();
+        for ((_, cells), context) in rows {
+            let mut row = TableRow::default();
+            let mut column = 0;
+            let mut cells = cells.iter();
+
+            loop {
+                if let Some(span) = merges.next(column) {
+                    row = row.push_cell(merged_cell(
+                        span,
+                        Some(VerticalMergeVal::Continue),
+                        Vec::new(),
+                    ));
+                    column += span;
+                    continue;
+                }
+
+                let Some((_, alignment, row_span, col_span, blocks)) = cells.next() else {
+                    break;
+                };
+                let span = (*col_span).max(1);
+                let merge = merges.start(column, span, *row_span);
+
+                let alignment = match alignment {
+                    Alignment::AlignDefault => col_specs
+                        .get(column)
+                        .map_or(Alignment::AlignDefault, |(alignment, _)| *alignment),
+                    alignment => *alignment,
+                };
+                let context = Context {
+                    justification: match alignment {
+                        Alignment::AlignLeft => Some(JustificationVal::Left),
+                        Alignment::AlignCenter => Some(JustificationVal::Center),
+                        Alignment::AlignRight => Some(JustificationVal::Right),
+                        Alignment::AlignDefault => None,
+                    },
+                    lists: 0,
+                    ..(*context).clone()
+                };
+                let content = self.cell(blocks, &context);
+                row = row.push_cell(merged_cell(span, merge, content));
+                column += span;
+            }
+
+            table = table.push_row(row);
+        }
+
+        table
+    }
+
+    /// Reads the content of a table cell.
+    fn cell(&mut self, blocks: &[Block], context: &Context<'a>) -> Vec<TableCellContent<'a>> {
+        let outer = std::mem::take(&mut self.blocks);
+        let item = self.item.take();
+        self.blocks(blocks, context);
+        self.item = item;
+        let content = std::mem::replace(&mut self.blocks, outer);
+
+        let mut cell_content = Vec::new();
+        for block in content {
+            match block {
+                BodyContent::Paragraph(paragraph) => cell_content.push(paragraph.into()),
+                BodyContent::Table(table) => cell_content.push(table.into()),
+                BodyContent::Sdt(_) => (),
+            }
+        }
+        cell_content
+    }
+
+    /// Reads content surrounded by a bookmark if the attributes have an id.
+    fn bookmarked(
+        &mut self,
+        attr: &Attr,
+        paragraph: &mut Paragraph<'a>,
+        read: impl FnOnce(&mut Self, &mut Paragraph<'a>),
+    ) {
+        if attr.0.is_empty() {
+            return read(self, paragraph);
+        }
+
+        let id = self.bookmarks.to_string();
+        self.bookmarks += 1;
+        paragraph.content.push(
+            BookmarkStart::default()
+                .id(id.clone())
+                .name(attr.0.clone())
+                .into(),
+        );
+        read(self, paragraph);
+        paragraph.content.push(BookmarkEnd::default().id(id).into());
+        self.text_run = false;
+    }
+
+    fn inlines(
+        &mut self,
+        inlines: &[Inline],
+        context: &Context<'a>,
+        paragraph: &mut Paragraph<'a>,
+    ) {
+        for inline in inlines {
+            self.inline(inline, context, paragraph);
+        }
+    }
+
+    fn inline(&mut self, inline: &Inline, context: &Context<'a>, paragraph: &mut Paragraph<'a>) {
+        let formatted = |format: fn(CharacterProperty<'a>) -> CharacterProperty<'a>| Context {
+            character: format(context.character.clone()),
+            ..context.clone()
+        };
+
+        match inline {
+            Inline::Str(text) => self.text(text, context, paragraph),
+            Inline::Space | Inline::SoftBreak => self.text(" ", context, paragraph),
+            Inline::LineBreak => self.push(Run::default().push_break(Break::default()), paragraph),
+            Inline::Emph(inlines) => {
+                self.formatted(inlines, &formatted(|p| p.italics(true)), paragraph)
+            }
+            Inline::Strong(inlines) => {
+                self.formatted(inlines, &formatted(|p| p.bold(true)), paragraph)
+            }
+            Inline::Underline(inlines) => self.formatted(
+                inlines,
+                &formatted(|p| p.underline(UnderlineStyle::Single)),
+                paragraph,
+            ),
+            Inline::Strikeout(inlines) => {
+                self.formatted(inlines, &formatted(|p| p.strike(true)), paragraph)
+            }
+            Inline::Superscript(inlines) => self.formatted(
+                inlines,
+                &formatted(|p| {
+                    p.vertical_align(VerticalAlign::from(VerticalAlignVal::Superscript))
+                }),
+                paragraph,
+            ),
+            Inline::Subscript(inlines) => self.formatted(
+                inlines,
+                &formatted(|p| p.vertical_align(VerticalAlign::from(VerticalAlignVal::Subscript))),
+                paragraph,
+            ),
+            Inline::SmallCaps(inlines) | Inline::Cite(_, inlines) => {
+                self.inlines(inlines, context, paragraph)
+            }
+            Inline::Quoted(ty, inlines) => {
+                let (open, close) = match ty {
+                    QuoteType::SingleQuote => ("‘", "’"),
+                    QuoteType::DoubleQuote => ("“", "”"),
+                };
+                self.text(open, context, paragraph);
+                self.inlines(inlines, context, paragraph);
+                self.text(close, context, paragraph);
+            }
+            Inline::Code(_, code) => {
+                let property = context
+                    .character
+                    .clone()
+                    .style_id(self.options.styles.code_span.clone());
+                self.push(
+                    Run::default().property(property).push_text(text_of(code)),
+                    paragraph,
+                );
+            }
+            Inline::Math(_, math) => self.text(math, context, paragraph),
+            Inline::RawInline(..) => (),
+            Inline::Link(_, inlines, (url, _)) => self.link(inlines, url, context, paragraph),
+            Inline::Image(attr, inlines, (url, _)) => {
+                self.image(attr, inlines, url, context, paragraph)
+            }
+            Inline::Note(blocks) => self.note(blocks, context, paragraph),
+            Inline::Span(attr, inlines) => {
+                let mut context = context.clone();
+                if let Some(name) = attr_value(attr, CUSTOM_STYLE) {
+                    context.character.style_id =
+                        Some(self.style_id(name, StyleType::Character).into());
+                }
+                self.text_run = false;
+                self.bookmarked(attr, paragraph, |reader, paragraph| {
+                    reader.inlines(inlines, &context, paragraph)
+                });
+                self.text_run = false;
+            }
+        }
+    }
+
+    /// Reads inlines in another format.
+    fn formatted(
+        &mut self,
+        inlines: &[Inline],
+        context: &Context<'a>,
+        paragraph: &mut Paragraph<'a>,
+    ) {
+        self.text_run = false;
+        self.inlines(inlines, context, paragraph);
+        self.text_run = false;
+    }
+
+    fn link(
+        &mut self,
+        inlines: &[Inline],
+        url: &str,
+        context: &Context<'a>,
+        paragraph: &mut Paragraph<'a>,
+    ) {
+        let text = stringify(inlines);
+        if text.is_empty() || url.is_empty() {
+            return self.inlines(inlines, context, paragraph);
+        }
+
+        let hyperlink = if let Some(anchor) = url.strip_prefix('#') {
+            Hyperlink::default().anchor(anchor.to_owned())
+        } else {
+            let id = self
+                .docx
+                .document_rels
+                .get_or_insert_with(Relationships::default)
+                .add_external_rel(SCHEMA_HYPERLINK, url.to_owned());
+            Hyperlink::default().id(id)
+        };
+
+        // a link holds a single run, in the format around the whole text
+        let mut property = context.character.clone();
+        let mut content = inlines;
+        loop {
+            property = match content {
+                [Inline::Emph(inner)] => {
+                    content = inner;
+                    property.italics(true)
+                }
+                [Inline::Strong(inner)] => {
+                    content = inner;
+                    property.bold(true)
+                }
+                [Inline::Strikeout(inner)] => {
+                    content = inner;
+                    property.strike(true)
+                }
+                [Inline::Underline(inner)] => {
+                    content = inner;
+                    property.underline(UnderlineStyle::Single)
+                }
+                _ => break,
+            };
+        }
+        let property = property.style_id(self.options.styles.hyperlink.clone());
+        let run = Run::default().property(property).push_text(text_of(&text));
+        self.push(hyperlink.content(run), paragraph);
+    }
+
+    fn image(
+        &mut self,
+        attr: &Attr,
+        inlines: &[Inline],
+        url: &str,
+        context: &Context<'a>,
+        paragraph: &mut Paragraph<'a>,
+    ) {
+        let description = stringify(inlines);
+        let (data, extension, natural) = match self.options.images.get(url) {
+            Some(image) => (
+                image.data.clone(),
+                image.extension.as_str(),
+                Some((image.width, image.height)),
+            ),
+            None => match decode_data_uri(url) {
+                Some((data, extension)) => {
+                    let natural = pixel_size(&data)
+                        .map(|(width, height)| (width * EMUS_PER_PIXEL, height * EMUS_PER_PIXEL));
+                    (data.into(), extension, natural)
+                }
+                None => return self.text(&description, context, paragraph),
+            },
+        };
+
+        let dimension = |name: &str| attr_value(attr, name).and_then(parse_emus);
+        let (natural_width, natural_height) = natural.unwrap_or((914400, 914400));
+        let (width, height) = match (dimension("width"), dimension("height")) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, width * natural_height / natural_width.max(1)),
+            (None, Some(height)) => (height * natural_width / natural_height.max(1), height),
+            (None, None) => (natural_width, natural_height),
+        };
+
+        let extension = extension.to_owned();
+        let (embed, number) = self.docx.embed_media(data, &extension);
+        let drawing = Drawing::picture(embed, width, height)
+            .id(number)
+            .name(description);
+        self.push(Run::default().push(drawing), paragraph);
+    }
+
+    fn note(&mut self, blocks: &[Block], context: &Context<'a>, paragraph: &mut Paragraph<'a>) {
+        let outer = std::mem::take(&mut self.blocks);
+        let item = self.item.take();
+        self.blocks(blocks, &Context::default());
+        self.item = item;
+        let mut content = std::mem::replace(&mut self.blocks, outer);
+
+        // the mark of the note starts its first paragraph
+        let mark = Run::default().push(RunContent::from(FootnoteRef));
+        match content.first_mut() {
+            Some(BodyContent::Paragraph(first)) => first.content.insert(0, mark.into()),
+            _ => content.insert(0, Paragraph::default().push(mark).into()),
+        }
+
+        let notes = self.docx.footnotes.get_or_insert_with(Footnotes::default);
+        let id = notes
+            .notes
+            .iter()
+            .filter_map(|note| note.id.parse::<isize>().ok())
+            .max()
+            .map_or(1, |id| id.max(0) + 1)
+            .to_string();
+        notes.push(Footnote {
+            content,
+            ..Footnote::new(id.clone())
+        });
+
+        let property = context
+            .character
+            .clone()
+            .vertical_align(VerticalAlign::from(VerticalAlignVal::Superscript));
+        self.push(
+            Run::default()
+                .property(property)
+                .push(FootnoteReference::from(id)),
+            paragraph,
+        );
+    }
+
+    /// Returns the id of the style of the given name, adding it if needed.
+    fn style_id(&mut self, name: &str, ty: StyleType) -> String {
+        let existing = self.docx.styles.styles.iter().find(|style| {
+            style.style_id == name
+                || style
+                    .name
+                    .as_ref()
+                    .is_some_and(|style_name| style_name.value == name)
+        });
+        if let Some(style) = existing {
+            return style.style_id.to_string();
+        }
+
+        let style_id: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+        self.docx
+            .styles
+            .push(Style::new(ty, style_id.clone()).name(name.to_owned()));
+        style_id
+    }
+
+    /// Appends content to the paragraph.
+    fn push<T: Into<ParagraphContent<'a>>>(&mut self, content: T, paragraph: &mut Paragraph<'a>) {
+        paragraph.content.push(content.into());
+        self.text_run = false;
+    }
+
+    fn text(&mut self, text: &str, context: &Context<'a>, paragraph: &mut Paragraph<'a>) {
+        if self.text_run {
+            if let Some(ParagraphContent::Run(run)) = paragraph.content.last_mut() {
+                if let Some(RunContent::Text(last)) = run.content.last_mut() {
+                    let mut joined = last.text.to_string();
+                    joined.push_str(text);
+                    *last = text_of(&joined);
+                    return;
+                }
+            }
+        }
+
+        let run = Run::default()
+            .property(context.character.clone())
+            .push_text(text_of(text));
+        self.push(run, paragraph);
+        self.text_run = true;
+    }
+}
+
+/// Maps the metadata to core properties.
+fn core<'a>(pandoc: &Pandoc) -> Option<Core<'a>> {
+    let text = |key: &str, separator: &str| {
+        pandoc
+            .meta
+            .get(key)
+            .map(|value| meta_text(value, separator))
+            .filter(|text| !text.is_empty())
+            .map(Cow::Owned)
+    };
+
+    let core = Core {
+        title: text("title", " "),
+        subject: text("subject", " "),
+        description: text("description", " ").or_else(|| text("abstract", " ")),
+        creator: text("author", "; "),
+        keywords: text("keywords", ", "),
+        ..Default::default()
+    };
+    let empty = core.title.is_none()
+        && core.subject.is_none()
+        && core.description.is_none()
+        && core.creator.is_none()
+        && core.keywords.is_none();
+    (!empty).then_some(core)
+}
+
+/// Returns the text of a metadata value, joining lists with a separator.
+fn meta_text(value: &MetaValue, separator: &str) -> String {
+    match value {
+        MetaValue::MetaString(text) => text.clone(),
+        MetaValue::MetaBool(value) => value.to_string(),
+        MetaValue::MetaInlines(inlines) => stringify(inlines),
+        MetaValue::MetaBlocks(blocks) => blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Plain(inlines) | Block::Para(inlines) => Some(stringify(inlines)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        MetaValue::MetaList(values) => values
+            .iter()
+            .map(|value| meta_text(value, separator))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator),
+        MetaValue::MetaMap(map) => map
+            .values()
+            .map(|value| meta_text(value, separator))
+            .collect::<Vec<_>>()
+            .join(separator),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"pandoc-api-version":[1,23,1],"meta":{"title":{"t":"MetaInlines","c":[{"t":"Str","c":"Minutes"}]},"author":{"t":"MetaList","c":[{"t":"MetaInlines","c":[{"t":"Str","c":"Ada"}]}]}},"blocks":[
+            {"t":"Header","c":[2,["agenda",[],[]],[{"t":"Str","c":"Agenda"}]]},
+            {"t":"Para","c":[{"t":"Str","c":"Plain"},{"t":"Space"},{"t":"Emph","c":[{"t":"Str","c":"emphasis"}]},{"t":"Str","c":","},{"t":"Space"},{"t":"Strikeout","c":[{"t":"Str","c":"gone"}]},{"t":"Space"},{"t":"Str","c":"x"},{"t":"Superscript","c":[{"t":"Str","c":"2"}]},{"t":"Space"},{"t":"Link","c":[["",[],[]],[{"t":"Strong","c":[{"t":"Str","c":"docs"}]}],["https://example.com",""]]},{"t":"Note","c":[{"t":"Para","c":[{"t":"Str","c":"A"},{"t":"Space"},{"t":"Str","c":"note."}]}]}]},
+            {"t":"OrderedList","c":[[3,{"t":"Decimal"},{"t":"Period"}],[[{"t":"Plain","c":[{"t":"Str","c":"three"}]},{"t":"BulletList","c":[[{"t":"Plain","c":[{"t":"Str","c":"nested"}]}]]}],[{"t":"Plain","c":[{"t":"Str","c":"four"}]}]]]},
+            {"t":"Div","c":[["",[],[["custom-style","Body Text"]]],[{"t":"Para","c":[{"t":"Span","c":[["",[],[["custom-style","Key Term"]]],[{"t":"Str","c":"Term"}]]}]}]]},
+            {"t":"Table","c":[["",[],[]],[null,[]],[[{"t":"AlignDefault"},{"t":"ColWidth","c":0.25}],[{"t":"AlignRight"},{"t":"ColWidth","c":0.75}]],[["",[],[]],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,2,[{"t":"Plain","c":[{"t":"Str","c":"Head"}]}]]]]]],[[["",[],[]],0,[],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},2,1,[{"t":"Plain","c":[{"t":"Str","c":"A"}]}]],[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"1"}]}]]]],[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"2"}]}]]]]]]],[["",[],[]],[]]]}
+        ]}"#;
+        let pandoc = Pandoc::from_json(json).unwrap();
+        let options = PandocOptions::default();
+        let docx = Docx::from_pandoc(&pandoc, &options);
+
+        let core = docx.core.as_ref().unwrap();
+        assert_eq!(core.title.as_deref(), Some("Minutes"));
+        assert_eq!(core.creator.as_deref(), Some("Ada"));
+        assert_eq!(docx.footnotes.as_ref().unwrap().notes.len(), 1);
+        assert_eq!(docx.numbering.as_ref().unwrap().nums.len(), 2);
+        assert_eq!(
+            docx.styles.get("BodyText").unwrap().ty,
+            StyleType::Paragraph
+        );
+        assert_eq!(docx.styles.get("KeyTerm").unwrap().ty, StyleType::Character);
+
+        let BodyContent::Table(table) = docx.document.body.content.last().unwrap() else {
+            panic!("expected a table");
+        };
+        let widths: Vec<_> = table.grids[0]
+            .columns
+            .iter()
+            .map(|column| column.width)
+            .collect();
+        assert_eq!(widths, [TABLE_WIDTH / 4, TABLE_WIDTH * 3 / 4]);
+        let rows: Vec<usize> = table
+            .iter_rows()
+            .map(|row| row.iter_cells().count())
+            .collect();
+        assert_eq!(rows, [1, 2, 2]);
+
+        // the export reads the import back
+        let exported = docx.to_pandoc(&options);
+        let expected = Pandoc {
+            blocks: pandoc.blocks.clone(),
+            ..Default::default()
+        };
+        assert_eq!(exported.meta["title"], pandoc.meta["title"]);
+        assert_eq!(exported.meta["author"], pandoc.meta["author"]);
+        assert_eq!(exported.blocks[..4], expected.blocks[..4]);
+
+        let Block::Table(_, _, col_specs, _, bodies, _) = &exported.blocks[4] else {
+            panic!("expected a table");
+        };
+        assert_eq!(col_specs.len(), 2);
+        let rows = &bodies[0].3;
+        assert_eq!(rows.len(), 3);
+        assert_eq!((rows[0].1[0].2, rows[0].1[0].3), (1, 2));
+        assert_eq!((rows[1].1[0].2, rows[1].1[0].3), (2, 1));
+        assert_eq!(rows[2].1.len(), 1);
+    }
+}
@@ -0,0 +1,310 @@
+//! Pandoc conversion
+//!
+//! Converts documents to and from the JSON AST of pandoc, which its filters
+//! operate on and which `pandoc -f json` and `pandoc -t json` read and write.
+//!
+//! Paragraph and character styles without a counterpart in pandoc are kept
+//! as the `custom-style` attribute of divs and spans, the way pandoc's docx
+//! reader and writer do with the `styles` extension.
+
+mod export;
+mod import;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    __setter,
+    convert::{Image, StyleIds},
+};
+
+/// Options of pandoc conversion
+#[derive(Clone, Debug, Default)]
+pub struct PandocOptions<'a> {
+    /// Specifies the styles of headings, quotes, code, lists and links.
+    pub styles: StyleIds<'a>,
+    /// Specifies the images of imported documents, by their URL.
+    ///
+    /// Images inlined as data URIs are decoded, the others which aren't given
+    /// are replaced by their description.
+    pub images: HashMap<String, Image>,
+}
+
+impl<'a> PandocOptions<'a> {
+    __setter!(styles: StyleIds<'a>);
+
+    /// Adds the image of a URL.
+    pub fn image<T: Into<String>>(mut self, url: T, image: Image) -> Self {
+        self.images.insert(url.into(), image);
+        self
+    }
+}
+
+/// The version of the pandoc types which the AST follows.
+pub const API_VERSION: [u32; 3] = [1, 23, 1];
+
+/// The name of the attribute carrying style names.
+pub const CUSTOM_STYLE: &str = "custom-style";
+
+/// A pandoc document
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pandoc {
+    #[serde(rename = "pandoc-api-version")]
+    pub api_version: Vec<u32>,
+    pub meta: BTreeMap<String, MetaValue>,
+    pub blocks: Vec<Block>,
+}
+
+impl Default for Pandoc {
+    fn default() -> Self {
+        Pandoc {
+            api_version: API_VERSION.to_vec(),
+            meta: BTreeMap::new(),
+            blocks: Vec::new(),
+        }
+    }
+}
+
+impl Pandoc {
+    /// Parses the JSON AST, like the output of `pandoc -t json`.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the JSON AST.
+    pub fn to_json(&self) -> String {
+        // maps with string keys and plain data can't fail to serialize
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// The identifier, the classes and the key-value pairs of an element
+pub type Attr = (String, Vec<String>, Vec<(String, String)>);
+
+/// The URL and the title of a link or an image
+pub type Target = (String, String);
+
+/// The start number, the style and the delimiter of an ordered list
+pub type ListAttributes = (i64, ListNumberStyle, ListNumberDelim);
+
+/// The short caption and the caption of a table or a figure
+pub type Caption = (Option<Vec<Inline>>, Vec<Block>);
+
+/// The alignment and the width of a table column
+pub type ColSpec = (Alignment, ColWidth);
+
+/// A table row
+pub type Row = (Attr, Vec<Cell>);
+
+/// A table cell, with its alignment, its row span and its column span
+pub type Cell = (Attr, Alignment, usize, usize, Vec<Block>);
+
+/// The header rows of a table
+pub type TableHead = (Attr, Vec<Row>);
+
+/// A table body, with its number of row header columns, its intermediate
+/// header rows and its rows
+pub type TableBody = (Attr, usize, Vec<Row>, Vec<Row>);
+
+/// The footer rows of a table
+pub type TableFoot = (Attr, Vec<Row>);
+
+/// A metadata value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum MetaValue {
+    MetaMap(BTreeMap<String, MetaValue>),
+    MetaList(Vec<MetaValue>),
+    MetaBool(bool),
+    MetaString(String),
+    MetaInlines(Vec<Inline>),
+    MetaBlocks(Vec<Block>),
+}
+
+/// A block element
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+#[allow(clippy::large_enum_variant)]
+pub enum Block {
+    Plain(Vec<Inline>),
+    Para(Vec<Inline>),
+    LineBlock(Vec<Vec<Inline>>),
+    CodeBlock(Attr, String),
+    RawBlock(String, String),
+    BlockQuote(Vec<Block>),
+    OrderedList(ListAttributes, Vec<Vec<Block>>),
+    BulletList(Vec<Vec<Block>>),
+    DefinitionList(Vec<(Vec<Inline>, Vec<Vec<Block>>)>),
+    Header(usize, Attr, Vec<Inline>),
+    HorizontalRule,
+    Table(
+        Attr,
+        Caption,
+        Vec<ColSpec>,
+        TableHead,
+        Vec<TableBody>,
+        TableFoot,
+    ),
+    Figure(Attr, Caption, Vec<Block>),
+    Div(Attr, Vec<Block>),
+}
+
+/// An inline element
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum Inline {
+    Str(String),
+    Emph(Vec<Inline>),
+    Underline(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikeout(Vec<Inline>),
+    Superscript(Vec<Inline>),
+    Subscript(Vec<Inline>),
+    SmallCaps(Vec<Inline>),
+    Quoted(QuoteType, Vec<Inline>),
+    Cite(Vec<Citation>, Vec<Inline>),
+    Code(Attr, String),
+    Space,
+    SoftBreak,
+    LineBreak,
+    Math(MathType, String),
+    RawInline(String, String),
+    Link(Attr, Vec<Inline>, Target),
+    Image(Attr, Vec<Inline>, Target),
+    Note(Vec<Block>),
+    Span(Attr, Vec<Inline>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    pub citation_id: String,
+    pub citation_prefix: Vec<Inline>,
+    pub citation_suffix: Vec<Inline>,
+    pub citation_mode: CitationMode,
+    pub citation_note_num: i64,
+    pub citation_hash: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum CitationMode {
+    AuthorInText,
+    SuppressAuthor,
+    NormalCitation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum QuoteType {
+    SingleQuote,
+    DoubleQuote,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum MathType {
+    DisplayMath,
+    InlineMath,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum ListNumberStyle {
+    DefaultStyle,
+    Example,
+    Decimal,
+    LowerRoman,
+    UpperRoman,
+    LowerAlpha,
+    UpperAlpha,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum ListNumberDelim {
+    DefaultDelim,
+    Period,
+    OneParen,
+    TwoParens,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum Alignment {
+    AlignLeft,
+    AlignRight,
+    AlignCenter,
+    AlignDefault,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum ColWidth {
+    ColWidth(f64),
+    ColWidthDefault,
+}
+
+/// Returns the value of a key of attributes.
+pub fn attr_value<'x>(attr: &'x Attr, key: &str) -> Option<&'x str> {
+    attr.2
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Returns the plain text of inlines.
+pub fn stringify(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Str(content) | Inline::Code(_, content) | Inline::Math(_, content) => {
+                text += content
+            }
+            Inline::Space | Inline::SoftBreak | Inline::LineBreak => text.push(' '),
+            Inline::Emph(content)
+            | Inline::Underline(content)
+            | Inline::Strong(content)
+            | Inline::Strikeout(content)
+            | Inline::Superscript(content)
+            | Inline::Subscript(content)
+            | Inline::SmallCaps(content)
+            | Inline::Cite(_, content)
+            | Inline::Link(_, content, _)
+            | Inline::Image(_, content, _)
+            | Inline::Span(_, content) => text += &stringify(content),
+            Inline::Quoted(QuoteType::SingleQuote, content) => {
+                text += &format!("‘{}’", stringify(content))
+            }
+            Inline::Quoted(QuoteType::DoubleQuote, content) => {
+                text += &format!("“{}”", stringify(content))
+            }
+            Inline::RawInline(..) | Inline::Note(_) => (),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_write_json() {
+        let json = r#"{"pandoc-api-version":[1,23,1],"meta":{"title":{"t":"MetaInlines","c":[{"t":"Str","c":"Notes"}]}},"blocks":[{"t":"Header","c":[1,["intro",[],[]],[{"t":"Str","c":"Intro"}]]},{"t":"Para","c":[{"t":"Emph","c":[{"t":"Str","c":"a"}]},{"t":"Space"},{"t":"Quoted","c":[{"t":"DoubleQuote"},[{"t":"Str","c":"b"}]]}]},{"t":"OrderedList","c":[[3,{"t":"Decimal"},{"t":"Period"}],[[{"t":"Plain","c":[{"t":"Str","c":"c"}]}]]]},{"t":"HorizontalRule"},{"t":"Table","c":[["",[],[]],[null,[]],[[{"t":"AlignDefault"},{"t":"ColWidth","c":0.5}],[{"t":"AlignLeft"},{"t":"ColWidthDefault"}]],[["",[],[]],[]],[[["",[],[]],0,[],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,2,[]]]]]]],[["",[],[]],[]]]}]}"#;
+
+        let pandoc = Pandoc::from_json(json).unwrap();
+        assert_eq!(pandoc.blocks.len(), 5);
+        assert!(matches!(
+            &pandoc.blocks[2],
+            Block::OrderedList((3, ListNumberStyle::Decimal, ListNumberDelim::Period), _)
+        ));
+        let Block::Para(inlines) = &pandoc.blocks[1] else {
+            panic!("expected a paragraph");
+        };
+        assert_eq!(stringify(inlines), "a “b”");
+
+        assert_eq!(pandoc.to_json(), json);
+    }
+}
@@ -0,0 +1,469 @@
+use crate::{
+    __setter,
+    document::{
+        BodyContent, Document, Paragraph, ParagraphContent, Run, RunContent, Table,
+        TableCellContent, TableContent, TableRowContent,
+    },
+    numbering::{ListCounter, Numberings},
+    Docx,
+};
+
+/// How tables are written
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableFormat {
+    /// A line per row, with cells separated by tabs.
+    Tabs,
+    /// An ASCII grid.
+    Grid,
+}
+
+/// Options of plain text extraction
+#[derive(Clone, Debug)]
+pub struct PlainTextOptions {
+    /// Specifies how tables are written.
+    pub tables: TableFormat,
+    /// Specifies whether to start list items with their labels.
+    pub list_labels: bool,
+    /// Specifies whether to include headers and footers, before and after
+    /// the body.
+    pub headers_footers: bool,
+    /// Specifies whether to include footnotes and endnotes after the body,
+    /// each starting with its id in brackets.
+    pub notes: bool,
+    /// Specifies whether to include comments after the body, each starting
+    /// with its author.
+    pub comments: bool,
+    /// Specifies whether to include text deleted as a tracked revision.
+    pub deleted_text: bool,
+    /// Specifies whether to include hidden text.
+    pub hidden_text: bool,
+}
+
+impl Default for PlainTextOptions {
+    fn default() -> Self {
+        PlainTextOptions {
+            tables: TableFormat::Tabs,
+            list_labels: true,
+            headers_footers: false,
+            notes: false,
+            comments: false,
+            deleted_text: false,
+            hidden_text: false,
+        }
+    }
+}
+
+impl PlainTextOptions {
+    __setter!(tables: TableFormat);
+    __setter!(list_labels: bool);
+    __setter!(headers_footers: bool);
+    __setter!(notes: bool);
+    __setter!(comments: bool);
+    __setter!(deleted_text: bool);
+    __setter!(hidden_text: bool);
+}
+
+impl<'a> Docx<'a> {
+    /// Returns the text of the document.
+    pub fn to_plain_text(&self, options: &PlainTextOptions) -> String {
+        let mut writer = Writer::new(options, self.numbering.as_ref());
+
+        if options.headers_footers {
+            for header in self.headers.values() {
+                writer.blocks(&header.content);
+            }
+        }
+
+        writer.blocks(&self.document.body.content);
+
+        if options.headers_footers {
+            for footer in self.footers.values() {
+                writer.blocks(&footer.content);
+            }
+        }
+
+        if options.notes {
+            let footnotes = self.footnotes.iter().flat_map(|notes| &notes.notes);
+            let endnotes = self.endnotes.iter().flat_map(|notes| &notes.notes);
+            let notes = footnotes
+                .map(|note| (note.ty, &note.id, &note.content))
+                .chain(endnotes.map(|note| (note.ty, &note.id, &note.content)));
+            // separators have no ids worth showing
+            for (_, id, content) in notes.filter(|(ty, _, _)| ty.is_none()) {
+                writer.text.push_str(&format!("[{}] ", id));
+                writer.blocks(content);
+            }
+        }
+
+        if options.comments {
+            for comment in self.comments.iter().flat_map(|comments| &comments.comments) {
+                writer.text.push_str(&format!("{}: ", comment.author));
+                for para in &comment.content {
+                    writer.paragraph(para);
+                }
+            }
+        }
+
+        writer.text
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns the text of the body.
+    ///
+    /// The labels of list items need the numbering part, so they're only
+    /// written by [`Docx::to_plain_text`].
+    ///
+    /// [`Docx::to_plain_text`]: ../struct.Docx.html#method.to_plain_text
+    pub fn to_plain_text(&self, options: &PlainTextOptions) -> String {
+        let mut writer = Writer::new(options, None);
+        writer.blocks(&self.body.content);
+        writer.text
+    }
+}
+
+struct Writer<'o, 'n, 'a> {
+    options: &'o PlainTextOptions,
+    lists: Option<ListCounter<'n, 'a>>,
+    text: String,
+}
+
+impl<'o, 'n, 'a> Writer<'o, 'n, 'a> {
+    fn new(options: &'o PlainTextOptions, numbering: Option<&'n Numberings<'a>>) -> Self {
+        Writer {
+            options,
+            lists: numbering
+                .filter(|_| options.list_labels)
+                .map(ListCounter::new),
+            text: String::new(),
+        }
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for content in content {
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para),
+                BodyContent::Table(table) => self.table(table),
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content),
+            }
+        }
+    }
+
+    fn table(&mut self, table: &Table) {
+        let mut rows = Vec::new();
+        for row in table.rows.iter().flat_map(TableContent::iter_rows) {
+            let mut cells = Vec::new();
+            for cell in row.cells.iter().flat_map(TableRowContent::iter_cells) {
+                let mut writer = Writer {
+                    options: self.options,
+                    lists: self.lists.take(),
+                    text: String::new(),
+                };
+                // the continuation of a vertically merged cell is left empty
+                let continued = cell
+                    .property
+                    .vertical_merge
+                    .as_ref()
+                    .is_some_and(|merge| !merge.is_restart());
+                if !continued {
+                    for content in &cell.content {
+                        match content {
+                            TableCellContent::Paragraph(para) => writer.paragraph(para),
+                            TableCellContent::Sdt(sdt) => writer.blocks(&sdt.content.content),
+                            TableCellContent::Table(table) => writer.table(table),
+                        }
+                    }
+                }
+                self.lists = writer.lists;
+                cells.push(writer.text.trim_end_matches('\n').to_owned());
+
+                // a cell spanning columns is followed by empty ones, keeping
+                // the cells below aligned
+                let span = cell
+                    .property
+                    .grid_span
+                    .as_ref()
+                    .map_or(1, |span| span.value);
+                cells.extend((1..span).map(|_| String::new()));
+            }
+            rows.push(cells);
+        }
+        match self.options.tables {
+            TableFormat::Tabs => self.tabs(&rows),
+            TableFormat::Grid => self.grid(&rows),
+        }
+    }
+
+    fn paragraph(&mut self, para: &Paragraph) {
+        if let (Some(lists), Some(numbering)) = (&mut self.lists, &para.property.numbering) {
+            if let Some(item) = lists.next(numbering) {
+                self.text.push_str(&item.label);
+                self.text.push(' ');
+            }
+        }
+        self.inline(&para.content);
+        self.text.push('\n');
+    }
+
+    fn inline(&mut self, content: &[ParagraphContent]) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => self.run(run),
+                ParagraphContent::Link(link) => link.content.iter().for_each(|run| self.run(run)),
+                ParagraphContent::SmartTag(tag) => self.inline(&tag.content),
+                ParagraphContent::Sdt(sdt) => self.inline(&sdt.content.content),
+                ParagraphContent::SimpleField(field) => self.inline(&field.content),
+                ParagraphContent::Insertion(ins) => self.inline(&ins.content),
+                ParagraphContent::MoveTo(to) => self.inline(&to.content),
+                ParagraphContent::Deletion(del) if self.options.deleted_text => {
+                    self.inline(&del.content)
+                }
+                ParagraphContent::MoveFrom(from) if self.options.deleted_text => {
+                    self.inline(&from.content)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn run(&mut self, run: &Run) {
+        let hidden = run
+            .property
+            .vanish
+            .as_ref()
+            .is_some_and(|vanish| vanish.value != Some(false));
+        if hidden && !self.options.hidden_text {
+            return;
+        }
+
+        for content in &run.content {
+            match content {
+                RunContent::Text(text) => self.text.push_str(&text.text),
+                RunContent::DeletedText(text) if self.options.deleted_text => {
+                    self.text.push_str(&text.text)
+                }
+                RunContent::Tab(_) => self.text.push('\t'),
+                RunContent::Break(_) => self.text.push('\n'),
+                RunContent::FootnoteReference(reference) if self.options.notes => {
+                    self.text.push_str(&format!("[{}]", reference.id))
+                }
+                RunContent::EndnoteReference(reference) if self.options.notes => {
+                    self.text.push_str(&format!("[{}]", reference.id))
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Writes a line per row, with tabs between cells and spaces between the
+    /// lines of a cell.
+    fn tabs(&mut self, rows: &[Vec<String>]) {
+        for row in rows {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|cell| cell.replace(['\n', '\t'], " "))
+                .collect();
+            self.text.push_str(&cells.join("\t"));
+            self.text.push('\n');
+        }
+    }
+
+    fn grid(&mut self, rows: &[Vec<String>]) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        if columns == 0 {
+            return;
+        }
+
+        let mut widths = vec![0; columns];
+        for row in rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                for line in cell.lines() {
+                    *width = (*width).max(line.replace('\t', " ").chars().count());
+                }
+            }
+        }
+
+        let border: String = widths
+            .iter()
+            .map(|width| format!("+{}", "-".repeat(width + 2)))
+            .collect::<String>()
+            + "+\n";
+
+        self.text.push_str(&border);
+        for row in rows {
+            let lines: Vec<Vec<String>> = (0..columns)
+                .map(|column| {
+                    row.get(column)
+                        .map_or("", String::as_str)
+                        .lines()
+                        .map(|line| line.replace('\t', " "))
+                        .collect()
+                })
+                .collect();
+            let height = lines.iter().map(Vec::len).max().unwrap_or_default().max(1);
+
+            for line in 0..height {
+                for (cell, width) in lines.iter().zip(&widths) {
+                    let text = cell.get(line).map_or("", String::as_str);
+                    let padding = width - text.chars().count();
+                    self.text
+                        .push_str(&format!("| {}{} ", text, " ".repeat(padding)));
+                }
+                self.text.push_str("|\n");
+            }
+            self.text.push_str(&border);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        comments::{Comment, Comments},
+        document::{Break, DeletedText, Deletion, FootnoteReference, Tab, TableCell, TableRow},
+        footnotes::{Footnote, Footnotes, NoteType},
+        formatting::{
+            CharacterProperty, NumberingProperty, ParagraphProperty, TableCellProperty,
+            VerticalMergeVal,
+        },
+        header_footer::Header,
+        numbering::{
+            AbstractNumbering, AbstractNumberingId, Level, LevelText, Numbering, NumberingFormat,
+            NumberingFormatVal,
+        },
+    };
+
+    fn level(index: usize, format: NumberingFormatVal, text: &'static str) -> Level<'static> {
+        Level {
+            index,
+            start: None,
+            numbering_format: Some(NumberingFormat { value: format }),
+            level_text: LevelText { value: text.into() },
+        }
+    }
+
+    fn item(text: &'static str, level: usize) -> Paragraph<'static> {
+        Paragraph::default()
+            .property(ParagraphProperty::default().numbering(NumberingProperty::from((1, level))))
+            .push_text(text)
+    }
+
+    #[test]
+    fn plain_text() {
+        let mut docx = Docx {
+            numbering: Some(Numberings {
+                abstract_nums: vec![AbstractNumbering {
+                    abstract_num_id: 3,
+                    levels: vec![
+                        level(0, NumberingFormatVal::Decimal, "%1."),
+                        level(1, NumberingFormatVal::LowerLetter, "%1.%2)"),
+                    ],
+                    ..Default::default()
+                }],
+                nums: vec![Numbering {
+                    num_id: 1,
+                    abstract_num_id: AbstractNumberingId { value: 3 },
+                }],
+            }),
+            ..Default::default()
+        };
+        docx.document.push(
+            Paragraph::default()
+                .push(
+                    Run::default()
+                        .push_text("a")
+                        .push(Tab)
+                        .push_text("b")
+                        .push(Break::default())
+                        .push_text("c")
+                        .push(FootnoteReference::from("1")),
+                )
+                .push(Deletion::new("1").push(Run::default().push(DeletedText::from("old"))))
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().vanish(true))
+                        .push_text(" hidden"),
+                ),
+        );
+        docx.document.push(item("one", 0));
+        docx.document.push(item("sub", 1));
+        docx.document.push(item("two", 0));
+        docx.document.push(item("sub", 1));
+
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("Header"));
+        docx.headers.insert("word/header1.xml".to_owned(), header);
+        docx.footnotes = Some(Footnotes {
+            notes: vec![
+                Footnote::new("-1").ty(NoteType::Separator),
+                Footnote::new("1").push(Paragraph::default().push_text("Note")),
+            ],
+        });
+        docx.comments = Some(Comments {
+            comments: vec![Comment {
+                author: "Ann".into(),
+                content: vec![Paragraph::default().push_text("Check")],
+                ..Default::default()
+            }],
+        });
+
+        assert_eq!(
+            docx.to_plain_text(&PlainTextOptions::default()),
+            "a\tb\nc\n1. one\n1.a) sub\n2. two\n2.a) sub\n"
+        );
+        assert_eq!(
+            docx.to_plain_text(
+                &PlainTextOptions::default()
+                    .list_labels(false)
+                    .headers_footers(true)
+                    .notes(true)
+                    .comments(true)
+                    .deleted_text(true)
+                    .hidden_text(true)
+            ),
+            "Header\na\tb\nc[1]old hidden\none\nsub\ntwo\nsub\n[1] Note\nAnn: Check\n"
+        );
+    }
+
+    #[test]
+    fn merged_cells() {
+        let cell = |text: &'static str, property: TableCellProperty| {
+            TableCell::pargraph(Paragraph::default().push_text(text)).property(property)
+        };
+        let mut docx = Docx::default();
+        docx.document.push(
+            Table::default()
+                .push_row(
+                    TableRow::default()
+                        .push_cell(cell("wide", TableCellProperty::default().grid_span(2usize)))
+                        .push_cell(cell(
+                            "tall",
+                            TableCellProperty::default().vertical_merge(VerticalMergeVal::Restart),
+                        )),
+                )
+                .push_row(
+                    TableRow::default()
+                        .push_cell(cell("a", TableCellProperty::default()))
+                        .push_cell(cell("b", TableCellProperty::default()))
+                        .push_cell(cell(
+                            "",
+                            TableCellProperty::default().vertical_merge(VerticalMergeVal::Continue),
+                        )),
+                ),
+        );
+
+        assert_eq!(
+            docx.to_plain_text(&PlainTextOptions::default()),
+            "wide\t\ttall\na\tb\t\n"
+        );
+        assert_eq!(
+            docx.to_plain_text(&PlainTextOptions::default().tables(TableFormat::Grid)),
+            "+------+---+------+\n\
+             | wide |   | tall |\n\
+             +------+---+------+\n\
+             | a    | b |      |\n\
+             +------+---+------+\n"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{
+    __setter,
+    convert::TABLE_WIDTH,
+    csv,
+    document::{
+        Break, BreakType, Paragraph, Run, Tab, Table, TableCell, TableGrid, TableRow, TextSpace,
+    },
+    formatting::{CharacterProperty, TableHeader, TableProperty, TableRowProperty, TableWidthUnit},
+    private_prelude::*,
+    Docx,
+};
+
+/// Options of CSV import
+#[derive(Clone, Debug)]
+pub struct CsvOptions<'a> {
+    /// Specifies the character separating fields, e.g. `\t` for TSV.
+    pub delimiter: char,
+    /// Specifies whether the first row is a header row, repeated at the top
+    /// of each page. Its text is bold unless a table style is specified.
+    pub header: bool,
+    /// Specifies the style of the table.
+    pub style_id: Option<Cow<'a, str>>,
+    /// Specifies the width of the table in twentieths of a point, shared by
+    /// the columns in proportion to their longest line.
+    pub width: usize,
+}
+
+impl Default for CsvOptions<'_> {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            header: true,
+            style_id: None,
+            width: TABLE_WIDTH,
+        }
+    }
+}
+
+impl<'a> CsvOptions<'a> {
+    __setter!(delimiter: char);
+    __setter!(header: bool);
+    __setter!(style_id: Option<Cow<'a, str>>);
+    __setter!(width: usize);
+}
+
+impl<'a> Docx<'a> {
+    /// Builds a document from plain text.
+    ///
+    /// Paragraphs are separated by blank lines, the other line breaks being
+    /// kept within paragraphs. Tabs are kept, form feeds become page breaks,
+    /// and spaces are preserved.
+    ///
+    /// ```rust
+    /// use docx::Docx;
+    ///
+    /// let docx = Docx::from_plain_text("Report\n\nName:\tAda\nRole:\tAuthor\n\x0cAppendix\n");
+    /// assert_eq!(docx.document.body.content.len(), 3);
+    /// assert_eq!(
+    ///     docx.to_plain_text(&Default::default()),
+    ///     "Report\nName:\tAda\nRole:\tAuthor\n\nAppendix\n"
+    /// );
+    /// ```
+    pub fn from_plain_text(text: &str) -> Docx<'a> {
+        let text = text
+            .trim_start_matches('\u{feff}')
+            .replace("\r\n", "\n")
+            .replace('\r', "\n");
+
+        let mut docx = Docx::default();
+        for (page, text) in text.split('\u{c}').enumerate() {
+            let mut paragraphs = paragraphs(text);
+            // the page break goes in the first paragraph of the page
+            if page > 0 && paragraphs.is_empty() {
+                paragraphs.push(Vec::new());
+            }
+
+            for (index, lines) in paragraphs.iter().enumerate() {
+                let mut run = Run::default();
+                if page > 0 && index == 0 {
+                    run = run.push_break(BreakType::Page);
+                }
+                for (index, line) in lines.iter().enumerate() {
+                    if index > 0 {
+                        run = run.push_break(Break::default());
+                    }
+                    run = push_line(run, line);
+                }
+                docx.document.push(Paragraph::default().push(run));
+            }
+        }
+
+        docx
+    }
+}
+
+impl<'a> Table<'a> {
+    /// Builds a table from delimiter-separated values, e.g. CSV or TSV.
+    ///
+    /// Rows missing fields are completed with empty cells, and line breaks
+    /// in quoted fields are kept.
+    ///
+    /// ```rust
+    /// use docx::document::Table;
+    /// use docx::plain_text::CsvOptions;
+    ///
+    /// let table = Table::from_csv(
+    ///     "Name,Score\nAda,\"1,200\"\nGrace,950\n",
+    ///     &CsvOptions::default().style_id("GridTable"),
+    /// );
+    /// assert_eq!(table.rows.len(), 3);
+    /// assert_eq!(table.grids[0].columns.len(), 2);
+    /// ```
+    pub fn from_csv(text: &str, options: &CsvOptions) -> Table<'a> {
+        let rows = csv::parse(text, options.delimiter);
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+
+        // the longest line of each column, with a minimum for short ones
+        let lengths: Vec<usize> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .filter_map(|row| row.get(column))
+                    .flat_map(|field| field.lines())
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or_default()
+                    .max(3)
+            })
+            .collect();
+        let total: usize = lengths.iter().sum();
+        let grid = lengths.iter().fold(TableGrid::default(), |grid, length| {
+            grid.push_column(options.width * length / total)
+        });
+
+        let mut property = TableProperty::default().width((options.width, TableWidthUnit::Dxa));
+        if let Some(style_id) = &options.style_id {
+            property = property.style_id(style_id.to_string());
+        }
+        let mut table = Table::default().property(property).push_grid(grid);
+
+        for (index, fields) in rows.iter().enumerate() {
+            let header = options.header && index == 0;
+            let mut row = TableRow::default();
+            if header {
+                row = row.property(TableRowProperty::default().header(TableHeader::default()));
+            }
+
+            for column in 0..columns {
+                let mut run = Run::default();
+                if header && options.style_id.is_none() {
+                    run = run.property(CharacterProperty::default().bold(true));
+                }
+                let field = fields.get(column).map_or("", String::as_str);
+                for (index, line) in field.lines().enumerate() {
+                    if index > 0 {
+                        run = run.push_break(Break::default());
+                    }
+                    run = push_line(run, line);
+                }
+                row = row.push_cell(TableCell::from(Paragraph::default().push(run)));
+            }
+
+            table = table.push_row(row);
+        }
+
+        table
+    }
+}
+
+/// Splits text into paragraphs of lines, at blank lines.
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = Vec::new();
+    let mut lines = Vec::new();
+    for line in text.split('\n') {
+        if line.trim().is_empty() {
+            if !lines.is_empty() {
+                paragraphs.push(std::mem::take(&mut lines));
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+    if !lines.is_empty() {
+        paragraphs.push(lines);
+    }
+    paragraphs
+}
+
+/// Appends a line to a run, with its tabs.
+fn push_line<'a>(mut run: Run<'a>, line: &str) -> Run<'a> {
+    for (index, text) in line.split('\t').enumerate() {
+        if index > 0 {
+            run = run.push(Tab);
+        }
+        if !text.is_empty() {
+            run = run.push_text((text.to_owned(), TextSpace::Preserve));
+        }
+    }
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{BodyContent, RunContent, TableContent, TableRowContent, Text};
+
+    #[test]
+    fn from_plain_text() {
+        let docx = Docx::from_plain_text("  indented\r\n\r\n\r\na\tb\nc\u{c}\u{c}");
+        let runs: Vec<&Run> = docx
+            .document
+            .body
+            .content
+            .iter()
+            .map(|content| match content {
+                BodyContent::Paragraph(para) => match &para.content[..] {
+                    [crate::document::ParagraphContent::Run(run)] => run,
+                    _ => panic!("expected a run"),
+                },
+                _ => panic!("expected a paragraph"),
+            })
+            .collect();
+
+        assert_eq!(runs.len(), 4);
+        assert_eq!(
+            runs[0].content,
+            vec![RunContent::Text(Text::from((
+                "  indented",
+                TextSpace::Preserve
+            )))]
+        );
+        assert_eq!(
+            runs[1].content,
+            vec![
+                RunContent::Text(Text::from(("a", TextSpace::Preserve))),
+                RunContent::Tab(Tab),
+                RunContent::Text(Text::from(("b", TextSpace::Preserve))),
+                RunContent::Break(Break::default()),
+                RunContent::Text(Text::from(("c", TextSpace::Preserve))),
+            ]
+        );
+        // a page break for each form feed
+        for run in &runs[2..] {
+            assert_eq!(
+                run.content,
+                vec![RunContent::Break(Break::from(BreakType::Page))]
+            );
+        }
+    }
+
+    #[test]
+    fn from_csv() {
+        let table = Table::from_csv(
+            "Item\tNotes\nTea\t\"green\nloose leaf\"\nCoffee\n",
+            &CsvOptions::default().delimiter('\t').width(5000usize),
+        );
+
+        assert!(table.property.style_id.is_none());
+        let widths: Vec<usize> = table.grids[0]
+            .columns
+            .iter()
+            .map(|column| column.width)
+            .collect();
+        assert_eq!(widths, vec![1875, 3125]);
+
+        let rows: Vec<&TableRow> = table
+            .rows
+            .iter()
+            .map(|row| match row {
+                TableContent::Row(row) => row,
+                _ => panic!("expected a row"),
+            })
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].property.header.is_some());
+        assert!(rows[1].property.header.is_none());
+
+        let cell = |row: &TableRow<'static>, index: usize| match &row.cells[index] {
+            TableRowContent::Cell(cell) => cell.clone(),
+            _ => panic!("expected a cell"),
+        };
+        let text = |cell: &TableCell| match &cell.content[..] {
+            [crate::document::TableCellContent::Paragraph(para)] => para
+                .iter_text()
+                .map(|text| text.to_string())
+                .collect::<Vec<_>>(),
+            _ => panic!("expected a paragraph"),
+        };
+        assert_eq!(text(&cell(rows[1], 1)), vec!["green", "loose leaf"]);
+        // the missing field
+        assert!(text(&cell(rows[2], 1)).is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+//! Plain text extraction and import
+//!
+//! Converts a document to plain text: paragraphs end with a line break,
+//! tabs and breaks are kept, list items start with their label, and tables
+//! are written either as tab-separated values or as an ASCII grid.
+//!
+//! Documents are also built from plain text, and tables from CSV or TSV.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::plain_text::{PlainTextOptions, TableFormat};
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(Paragraph::default().push_text("Prices"));
+//! docx.document.push(
+//!     Table::default()
+//!         .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Tea")).push_cell(Paragraph::default().push_text("2")))
+//!         .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Coffee")).push_cell(Paragraph::default().push_text("3"))),
+//! );
+//!
+//! assert_eq!(
+//!     docx.to_plain_text(&PlainTextOptions::default()),
+//!     "Prices\nTea\t2\nCoffee\t3\n"
+//! );
+//! assert_eq!(
+//!     docx.to_plain_text(&PlainTextOptions::default().tables(TableFormat::Grid)),
+//!     "Prices\n+--------+---+\n| Tea    | 2 |\n+--------+---+\n| Coffee | 3 |\n+--------+---+\n"
+//! );
+//! ```
+
+mod export;
+mod import;
+
+pub use self::{export::*, import::*};
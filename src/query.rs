@@ -0,0 +1,1119 @@
+//! Querying nodes of a document
+//!
+//! A [`Predicate`] selects paragraphs, tables, rows, cells, runs and
+//! hyperlinks of the body by their kind, style, formatting, text and
+//! position in the tree. Predicates compose with [`Predicate::and`],
+//! [`Predicate::or`] and `!`, or can be parsed from a selector string in a
+//! syntax close to CSS:
+//!
+//! | Selector | Selects |
+//! |----------|---------|
+//! | `p`, `table`, `tr`, `tc`, `r`, `a`, `*` | paragraphs, tables, rows, cells, runs, hyperlinks, any node |
+//! | `.Heading2`, `[style="Heading 2"]` | nodes with the style id |
+//! | `[bold]`, `[italic]`, `[underline]`, `[strike]` | runs and hyperlinks with the formatting |
+//! | `[color=FF0000]` | runs and hyperlinks with the color |
+//! | `[href]`, `[anchor]` | hyperlinks to a relationship, e.g. an external address, or to a bookmark |
+//! | `:contains("text")`, `:matches("regex")` | nodes whose text contains the text, or matches the regex with the `regex` feature |
+//! | `:first`, `:nth(2)` | nodes by their position among the nodes of their kind in their parent, from 1 |
+//! | `:has(selector)`, `:not(selector)` | nodes containing a node matching the selector, or not matching it |
+//! | `A B`, `A > B` | nodes matching B inside, or directly inside, a node matching A |
+//! | `A, B` | nodes matching A or B |
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::{CharacterProperty, ParagraphProperty};
+//! use docx::query::{Node, Predicate};
+//!
+//! let mut document = Document::default();
+//! document.push(
+//!     Paragraph::default()
+//!         .property(ParagraphProperty::default().style_id("Heading2"))
+//!         .push_text("Summary"),
+//! );
+//! document.push(
+//!     Table::default()
+//!         .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Total")))
+//!         .push_row(TableRow::default().push_cell(Paragraph::default().push_text("42"))),
+//! );
+//!
+//! let headings: Vec<_> = document.select(&Predicate::paragraph().and(Predicate::style("Heading2"))).collect();
+//! assert_eq!(headings[0].text, "Summary");
+//!
+//! let tables: Vec<_> = document.query("table:has(tr:first:contains('Total'))").unwrap().collect();
+//! assert!(matches!(tables[0].node, Node::Table(_)));
+//! assert_eq!(tables[0].path, vec![1]);
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::Not,
+    str::FromStr,
+};
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::{
+    document::{
+        BodyContent, Document, Hyperlink, Paragraph, ParagraphContent, Run, Table, TableCell,
+        TableCellContent, TableContent, TableRow, TableRowContent,
+    },
+    find::Pattern,
+    formatting::{CharacterProperty, UnderlineStyle},
+};
+
+/// The kind of a node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Paragraph,
+    Table,
+    Row,
+    Cell,
+    Run,
+    Hyperlink,
+}
+
+/// A reference to a node
+#[derive(Clone, Copy, Debug)]
+pub enum Node<'s, 'a> {
+    Paragraph(&'s Paragraph<'a>),
+    Table(&'s Table<'a>),
+    Row(&'s TableRow<'a>),
+    Cell(&'s TableCell<'a>),
+    Run(&'s Run<'a>),
+    Hyperlink(&'s Hyperlink<'a>),
+}
+
+impl<'s, 'a> Node<'s, 'a> {
+    pub fn kind(&self) -> Kind {
+        match self {
+            Node::Paragraph(_) => Kind::Paragraph,
+            Node::Table(_) => Kind::Table,
+            Node::Row(_) => Kind::Row,
+            Node::Cell(_) => Kind::Cell,
+            Node::Run(_) => Kind::Run,
+            Node::Hyperlink(_) => Kind::Hyperlink,
+        }
+    }
+
+    /// Returns the style id of a paragraph, a table, a run or a hyperlink.
+    pub fn style_id(&self) -> Option<&'s str> {
+        match self {
+            Node::Paragraph(para) => para.property.style_id.as_ref().map(|id| &*id.value),
+            Node::Table(table) => table.property.style_id.as_ref().map(|id| &*id.value),
+            _ => self
+                .character_property()?
+                .style_id
+                .as_ref()
+                .map(|id| &*id.value),
+        }
+    }
+
+    /// Returns the character properties of a run, or of the run of a hyperlink.
+    pub fn character_property(&self) -> Option<&'s CharacterProperty<'a>> {
+        match self {
+            Node::Run(run) => Some(&run.property),
+            Node::Hyperlink(link) => link.content.as_ref().map(|run| &run.property),
+            _ => None,
+        }
+    }
+}
+
+/// A mutable reference to a node
+#[derive(Debug)]
+pub enum NodeMut<'s, 'a> {
+    Paragraph(&'s mut Paragraph<'a>),
+    Table(&'s mut Table<'a>),
+    Row(&'s mut TableRow<'a>),
+    Cell(&'s mut TableCell<'a>),
+    Run(&'s mut Run<'a>),
+    Hyperlink(&'s mut Hyperlink<'a>),
+}
+
+/// A node selected by a query
+#[derive(Clone, Debug)]
+pub struct Selected<'s, 'a> {
+    /// The node.
+    pub node: Node<'s, 'a>,
+    /// The indices leading to the node: the path of a paragraph is the same
+    /// as in [`Match::path`], tables and rows and cells have the prefix of
+    /// the paths of their paragraphs, and the index of the run or of the
+    /// hyperlink is appended to the path of the paragraph for runs and
+    /// hyperlinks, counting runs as in [`TextPosition::run`].
+    ///
+    /// [`Match::path`]: ../find/struct.Match.html#structfield.path
+    /// [`TextPosition::run`]: ../find/struct.TextPosition.html#structfield.run
+    pub path: Vec<usize>,
+    /// The nodes containing the node, from the outermost.
+    pub ancestors: Vec<Node<'s, 'a>>,
+    /// The text of the node, with the paragraphs of tables, rows and cells
+    /// separated by `\n`. Deleted text isn't included.
+    pub text: String,
+}
+
+/// A node selected by a mutable query
+#[derive(Debug)]
+pub struct SelectedMut<'s, 'a> {
+    /// The node.
+    pub node: NodeMut<'s, 'a>,
+    /// The indices leading to the node, see [`Selected::path`].
+    ///
+    /// [`Selected::path`]: struct.Selected.html#structfield.path
+    pub path: Vec<usize>,
+}
+
+/// A condition on nodes
+#[derive(Clone, Debug)]
+pub enum Predicate {
+    /// Matches any node.
+    Any,
+    /// Matches nodes of the kind.
+    Kind(Kind),
+    /// Matches nodes with the style id.
+    Style(String),
+    /// Matches bold runs and hyperlinks.
+    Bold,
+    /// Matches italic runs and hyperlinks.
+    Italic,
+    /// Matches underlined runs and hyperlinks.
+    Underline,
+    /// Matches struck through runs and hyperlinks.
+    Strike,
+    /// Matches runs and hyperlinks with the color, compared case-insensitively.
+    Color(String),
+    /// Matches nodes whose text matches the pattern.
+    Text(Pattern),
+    /// Matches hyperlinks to a relationship, e.g. an external address.
+    ExternalLink,
+    /// Matches hyperlinks to a bookmark of the document.
+    InternalLink,
+    /// Matches nodes at the index among the nodes of their kind in their
+    /// parent, from 0. Runs are counted in their paragraph.
+    Nth(usize),
+    /// Matches nodes whose parent matches the predicate.
+    Parent(Box<Predicate>),
+    /// Matches nodes with an ancestor matching the predicate.
+    Ancestor(Box<Predicate>),
+    /// Matches nodes with a descendant matching the predicate.
+    Descendant(Box<Predicate>),
+    /// Matches nodes matching all the predicates.
+    And(Vec<Predicate>),
+    /// Matches nodes matching any of the predicates.
+    Or(Vec<Predicate>),
+    /// Matches nodes not matching the predicate.
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn paragraph() -> Self {
+        Predicate::Kind(Kind::Paragraph)
+    }
+
+    pub fn table() -> Self {
+        Predicate::Kind(Kind::Table)
+    }
+
+    pub fn row() -> Self {
+        Predicate::Kind(Kind::Row)
+    }
+
+    pub fn cell() -> Self {
+        Predicate::Kind(Kind::Cell)
+    }
+
+    pub fn run() -> Self {
+        Predicate::Kind(Kind::Run)
+    }
+
+    pub fn hyperlink() -> Self {
+        Predicate::Kind(Kind::Hyperlink)
+    }
+
+    pub fn style<S: Into<String>>(style_id: S) -> Self {
+        Predicate::Style(style_id.into())
+    }
+
+    pub fn color<S: Into<String>>(color: S) -> Self {
+        Predicate::Color(color.into())
+    }
+
+    pub fn contains<P: Into<Pattern>>(pattern: P) -> Self {
+        Predicate::Text(pattern.into())
+    }
+
+    pub fn first() -> Self {
+        Predicate::Nth(0)
+    }
+
+    pub fn child_of(parent: Predicate) -> Self {
+        Predicate::Parent(Box::new(parent))
+    }
+
+    pub fn within(ancestor: Predicate) -> Self {
+        Predicate::Ancestor(Box::new(ancestor))
+    }
+
+    pub fn has(descendant: Predicate) -> Self {
+        Predicate::Descendant(Box::new(descendant))
+    }
+
+    /// Combines two predicates, matching nodes matching both.
+    pub fn and(self, other: Predicate) -> Self {
+        match self {
+            Predicate::And(mut predicates) => {
+                predicates.push(other);
+                Predicate::And(predicates)
+            }
+            predicate => Predicate::And(vec![predicate, other]),
+        }
+    }
+
+    /// Combines two predicates, matching nodes matching either.
+    pub fn or(self, other: Predicate) -> Self {
+        match self {
+            Predicate::Or(mut predicates) => {
+                predicates.push(other);
+                Predicate::Or(predicates)
+            }
+            predicate => Predicate::Or(vec![predicate, other]),
+        }
+    }
+
+    fn test(&self, entries: &[Entry], index: usize) -> bool {
+        let entry = &entries[index];
+        let property = || entry.node.character_property();
+
+        match self {
+            Predicate::Any => true,
+            Predicate::Kind(kind) => entry.node.kind() == *kind,
+            Predicate::Style(style_id) => entry.node.style_id() == Some(style_id.as_str()),
+            Predicate::Bold => property()
+                .and_then(|prop| prop.bold.as_ref())
+                .is_some_and(|bold| bold.value != Some(false)),
+            Predicate::Italic => property()
+                .and_then(|prop| prop.italics.as_ref())
+                .is_some_and(|italics| italics.value != Some(false)),
+            Predicate::Strike => property()
+                .and_then(|prop| prop.strike.as_ref())
+                .is_some_and(|strike| strike.value != Some(false)),
+            Predicate::Underline => property()
+                .and_then(|prop| prop.underline.as_ref())
+                .is_some_and(|underline| !matches!(underline.val, Some(UnderlineStyle::None))),
+            Predicate::Color(color) => property()
+                .and_then(|prop| prop.color.as_ref())
+                .is_some_and(|value| value.value.eq_ignore_ascii_case(color)),
+            Predicate::Text(pattern) => pattern.is_match(&entry.text),
+            Predicate::ExternalLink => {
+                matches!(entry.node, Node::Hyperlink(link) if link.id.is_some())
+            }
+            Predicate::InternalLink => {
+                matches!(entry.node, Node::Hyperlink(link) if link.id.is_none() && link.anchor.is_some())
+            }
+            Predicate::Nth(nth) => entry.index == *nth,
+            Predicate::Parent(predicate) => entry
+                .parent
+                .is_some_and(|parent| predicate.test(entries, parent)),
+            Predicate::Ancestor(predicate) => {
+                let mut ancestor = entry.parent;
+                while let Some(index) = ancestor {
+                    if predicate.test(entries, index) {
+                        return true;
+                    }
+                    ancestor = entries[index].parent;
+                }
+                false
+            }
+            Predicate::Descendant(predicate) => (index + 1..descendants_end(entries, index))
+                .any(|descendant| predicate.test(entries, descendant)),
+            Predicate::And(predicates) => predicates.iter().all(|p| p.test(entries, index)),
+            Predicate::Or(predicates) => predicates.iter().any(|p| p.test(entries, index)),
+            Predicate::Not(predicate) => !predicate.test(entries, index),
+        }
+    }
+}
+
+impl Not for Predicate {
+    type Output = Predicate;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Predicate::Not(predicate) => *predicate,
+            predicate => Predicate::Not(Box::new(predicate)),
+        }
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns the nodes of the body matching a predicate, in document order.
+    pub fn select<'s>(&'s self, predicate: &Predicate) -> std::vec::IntoIter<Selected<'s, 'a>> {
+        let entries = Collector::collect(self);
+
+        (0..entries.len())
+            .filter(|&index| predicate.test(&entries, index))
+            .map(|index| {
+                let entry = &entries[index];
+                let mut ancestors = Vec::new();
+                let mut ancestor = entry.parent;
+                while let Some(index) = ancestor {
+                    ancestors.insert(0, entries[index].node);
+                    ancestor = entries[index].parent;
+                }
+                Selected {
+                    node: entry.node,
+                    path: entry.path.clone(),
+                    ancestors,
+                    text: entry.text.clone(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the nodes of the body matching a predicate mutably, in
+    /// document order.
+    ///
+    /// Nodes inside another selected node are left out, since they can't be
+    /// borrowed at the same time.
+    pub fn select_mut<'s>(
+        &'s mut self,
+        predicate: &Predicate,
+    ) -> std::vec::IntoIter<SelectedMut<'s, 'a>> {
+        let keys: HashSet<(Kind, Vec<usize>)> = self
+            .select(predicate)
+            .map(|selected| (selected.node.kind(), selected.path))
+            .collect();
+
+        let mut collector = CollectorMut {
+            keys: &keys,
+            found: Vec::new(),
+        };
+        collector.blocks(&mut self.body.content, &mut Vec::new());
+
+        collector.found.into_iter()
+    }
+
+    /// Returns the nodes of the body matching a selector, see the [module
+    /// documentation](index.html) for its syntax.
+    pub fn query<'s>(
+        &'s self,
+        selector: &str,
+    ) -> Result<std::vec::IntoIter<Selected<'s, 'a>>, SelectorError> {
+        Ok(self.select(&selector.parse()?))
+    }
+
+    /// Returns the nodes of the body matching a selector mutably, see
+    /// [`Document::select_mut`].
+    ///
+    /// [`Document::select_mut`]: #method.select_mut
+    pub fn query_mut<'s>(
+        &'s mut self,
+        selector: &str,
+    ) -> Result<std::vec::IntoIter<SelectedMut<'s, 'a>>, SelectorError> {
+        Ok(self.select_mut(&selector.parse()?))
+    }
+}
+
+/// A node of the flattened tree, in document order.
+struct Entry<'s, 'a> {
+    node: Node<'s, 'a>,
+    path: Vec<usize>,
+    parent: Option<usize>,
+    depth: usize,
+    index: usize,
+    text: String,
+}
+
+/// Returns the end of the descendants of an entry, which follow it.
+fn descendants_end(entries: &[Entry], index: usize) -> usize {
+    let depth = entries[index].depth;
+    entries[index + 1..]
+        .iter()
+        .position(|entry| entry.depth <= depth)
+        .map_or(entries.len(), |end| index + 1 + end)
+}
+
+struct Collector<'s, 'a> {
+    entries: Vec<Entry<'s, 'a>>,
+    counters: HashMap<(Option<usize>, Kind), usize>,
+}
+
+impl<'s, 'a> Collector<'s, 'a> {
+    fn collect(document: &'s Document<'a>) -> Vec<Entry<'s, 'a>> {
+        let mut collector = Collector {
+            entries: Vec::new(),
+            counters: HashMap::new(),
+        };
+        collector.blocks(&document.body.content, &mut Vec::new(), None);
+
+        let mut entries = collector.entries;
+        for index in (0..entries.len()).rev() {
+            if let Node::Table(_) | Node::Row(_) | Node::Cell(_) = entries[index].node {
+                let texts: Vec<&str> = entries[index + 1..descendants_end(&entries, index)]
+                    .iter()
+                    .filter(|entry| entry.node.kind() == Kind::Paragraph)
+                    .map(|entry| entry.text.as_str())
+                    .collect();
+                entries[index].text = texts.join("\n");
+            }
+        }
+        entries
+    }
+
+    fn push(&mut self, node: Node<'s, 'a>, path: &[usize], parent: Option<usize>) -> usize {
+        // runs of hyperlinks are counted in their paragraph
+        let container = match parent {
+            Some(index) if self.entries[index].node.kind() == Kind::Hyperlink => {
+                self.entries[index].parent
+            }
+            _ => parent,
+        };
+        let counter = self.counters.entry((container, node.kind())).or_default();
+        let index = *counter;
+        *counter += 1;
+
+        let text = match node {
+            Node::Paragraph(para) => para.iter_text().map(|text| &**text).collect(),
+            Node::Run(run) => run.iter_text().map(|text| &**text).collect(),
+            Node::Hyperlink(link) => link
+                .content
+                .iter()
+                .flat_map(Run::iter_text)
+                .map(|text| &**text)
+                .collect(),
+            _ => String::new(),
+        };
+
+        self.entries.push(Entry {
+            node,
+            path: path.to_vec(),
+            parent,
+            depth: parent.map_or(0, |parent| self.entries[parent].depth + 1),
+            index,
+            text,
+        });
+        self.entries.len() - 1
+    }
+
+    fn blocks(
+        &mut self,
+        content: &'s [BodyContent<'a>],
+        path: &mut Vec<usize>,
+        parent: Option<usize>,
+    ) {
+        for (index, content) in content.iter().enumerate() {
+            path.push(index);
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para, path, parent),
+                BodyContent::Table(table) => {
+                    let table_id = self.push(Node::Table(table), path, parent);
+                    self.rows(&table.rows, path, table_id);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content.content, path, parent),
+            }
+            path.pop();
+        }
+    }
+
+    fn rows(&mut self, content: &'s [TableContent<'a>], path: &mut Vec<usize>, table_id: usize) {
+        for (index, content) in content.iter().enumerate() {
+            path.push(index);
+            match content {
+                TableContent::Row(row) => {
+                    let row_id = self.push(Node::Row(row), path, Some(table_id));
+                    self.cells(&row.cells, path, row_id);
+                }
+                TableContent::Sdt(sdt) => self.rows(&sdt.content.content, path, table_id),
+            }
+            path.pop();
+        }
+    }
+
+    fn cells(&mut self, content: &'s [TableRowContent<'a>], path: &mut Vec<usize>, row_id: usize) {
+        for (index, content) in content.iter().enumerate() {
+            path.push(index);
+            match content {
+                TableRowContent::Cell(cell) => {
+                    let cell_id = self.push(Node::Cell(cell), path, Some(row_id));
+                    for (index, content) in cell.content.iter().enumerate() {
+                        path.push(index);
+                        match content {
+                            TableCellContent::Paragraph(para) => {
+                                self.paragraph(para, path, Some(cell_id))
+                            }
+                            TableCellContent::Sdt(sdt) => {
+                                self.blocks(&sdt.content.content, path, Some(cell_id))
+                            }
+                            TableCellContent::Table(table) => {
+                                let table_id = self.push(Node::Table(table), path, Some(cell_id));
+                                self.rows(&table.rows, path, table_id);
+                            }
+                        }
+                        path.pop();
+                    }
+                }
+                TableRowContent::Sdt(sdt) => self.cells(&sdt.content.content, path, row_id),
+            }
+            path.pop();
+        }
+    }
+
+    fn paragraph(&mut self, para: &'s Paragraph<'a>, path: &mut Vec<usize>, parent: Option<usize>) {
+        let para_id = self.push(Node::Paragraph(para), path, parent);
+        self.inline(&para.content, path, para_id, &mut 0, &mut 0);
+    }
+
+    fn inline(
+        &mut self,
+        content: &'s [ParagraphContent<'a>],
+        path: &mut Vec<usize>,
+        para_id: usize,
+        runs: &mut usize,
+        links: &mut usize,
+    ) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => {
+                    path.push(*runs);
+                    self.push(Node::Run(run), path, Some(para_id));
+                    path.pop();
+                    *runs += 1;
+                }
+                ParagraphContent::Link(link) => {
+                    path.push(*links);
+                    let link_id = self.push(Node::Hyperlink(link), path, Some(para_id));
+                    path.pop();
+                    *links += 1;
+                    if let Some(run) = &link.content {
+                        path.push(*runs);
+                        self.push(Node::Run(run), path, Some(link_id));
+                        path.pop();
+                        *runs += 1;
+                    }
+                }
+                ParagraphContent::SmartTag(tag) => {
+                    self.inline(&tag.content, path, para_id, runs, links)
+                }
+                ParagraphContent::Sdt(sdt) => {
+                    self.inline(&sdt.content.content, path, para_id, runs, links)
+                }
+                ParagraphContent::SimpleField(field) => {
+                    self.inline(&field.content, path, para_id, runs, links)
+                }
+                ParagraphContent::Insertion(ins) => {
+                    self.inline(&ins.content, path, para_id, runs, links)
+                }
+                ParagraphContent::MoveTo(to) => {
+                    self.inline(&to.content, path, para_id, runs, links)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Walks the body like `Collector` and borrows the selected nodes mutably.
+struct CollectorMut<'k, 's, 'a> {
+    keys: &'k HashSet<(Kind, Vec<usize>)>,
+    found: Vec<SelectedMut<'s, 'a>>,
+}
+
+impl<'k, 's, 'a> CollectorMut<'k, 's, 'a> {
+    fn hit(&self, kind: Kind, path: &[usize]) -> bool {
+        self.keys.contains(&(kind, path.to_vec()))
+    }
+
+    fn found(&mut self, node: NodeMut<'s, 'a>, path: &[usize]) {
+        self.found.push(SelectedMut {
+            node,
+            path: path.to_vec(),
+        });
+    }
+
+    fn blocks(&mut self, content: &'s mut [BodyContent<'a>], path: &mut Vec<usize>) {
+        for (index, content) in content.iter_mut().enumerate() {
+            path.push(index);
+            match content {
+                BodyContent::Paragraph(para) => self.paragraph(para, path),
+                BodyContent::Table(table) => {
+                    if self.hit(Kind::Table, path) {
+                        self.found(NodeMut::Table(table), path);
+                    } else {
+                        self.rows(&mut table.rows, path);
+                    }
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&mut sdt.content.content, path),
+            }
+            path.pop();
+        }
+    }
+
+    fn rows(&mut self, content: &'s mut [TableContent<'a>], path: &mut Vec<usize>) {
+        for (index, content) in content.iter_mut().enumerate() {
+            path.push(index);
+            match content {
+                TableContent::Row(row) => {
+                    if self.hit(Kind::Row, path) {
+                        self.found(NodeMut::Row(row), path);
+                    } else {
+                        self.cells(&mut row.cells, path);
+                    }
+                }
+                TableContent::Sdt(sdt) => self.rows(&mut sdt.content.content, path),
+            }
+            path.pop();
+        }
+    }
+
+    fn cells(&mut self, content: &'s mut [TableRowContent<'a>], path: &mut Vec<usize>) {
+        for (index, content) in content.iter_mut().enumerate() {
+            path.push(index);
+            match content {
+                TableRowContent::Cell(cell) => {
+                    if self.hit(Kind::Cell, path) {
+                        self.found(NodeMut::Cell(cell), path);
+                    } else {
+                        for (index, content) in cell.content.iter_mut().enumerate() {
+                            path.push(index);
+                            match content {
+                                TableCellContent::Paragraph(para) => self.paragraph(para, path),
+                                TableCellContent::Sdt(sdt) => {
+                                    self.blocks(&mut sdt.content.content, path)
+                                }
+                                TableCellContent::Table(table) => {
+                                    if self.hit(Kind::Table, path) {
+                                        self.found(NodeMut::Table(table), path);
+                                    } else {
+                                        self.rows(&mut table.rows, path);
+                                    }
+                                }
+                            }
+                            path.pop();
+                        }
+                    }
+                }
+                TableRowContent::Sdt(sdt) => self.cells(&mut sdt.content.content, path),
+            }
+            path.pop();
+        }
+    }
+
+    fn paragraph(&mut self, para: &'s mut Paragraph<'a>, path: &mut Vec<usize>) {
+        if self.hit(Kind::Paragraph, path) {
+            self.found(NodeMut::Paragraph(para), path);
+        } else {
+            self.inline(&mut para.content, path, &mut 0, &mut 0);
+        }
+    }
+
+    fn inline(
+        &mut self,
+        content: &'s mut [ParagraphContent<'a>],
+        path: &mut Vec<usize>,
+        runs: &mut usize,
+        links: &mut usize,
+    ) {
+        for content in content {
+            match content {
+                ParagraphContent::Run(run) => {
+                    path.push(*runs);
+                    if self.hit(Kind::Run, path) {
+                        self.found(NodeMut::Run(run), path);
+                    }
+                    path.pop();
+                    *runs += 1;
+                }
+                ParagraphContent::Link(link) => {
+                    path.push(*links);
+                    let hit = self.hit(Kind::Hyperlink, path);
+                    *links += 1;
+                    if hit {
+                        *runs += link.content.is_some() as usize;
+                        self.found(NodeMut::Hyperlink(link), path);
+                        path.pop();
+                        continue;
+                    }
+                    path.pop();
+                    if let Some(run) = &mut link.content {
+                        path.push(*runs);
+                        if self.hit(Kind::Run, path) {
+                            self.found(NodeMut::Run(run), path);
+                        }
+                        path.pop();
+                        *runs += 1;
+                    }
+                }
+                ParagraphContent::SmartTag(tag) => self.inline(&mut tag.content, path, runs, links),
+                ParagraphContent::Sdt(sdt) => {
+                    self.inline(&mut sdt.content.content, path, runs, links)
+                }
+                ParagraphContent::SimpleField(field) => {
+                    self.inline(&mut field.content, path, runs, links)
+                }
+                ParagraphContent::Insertion(ins) => {
+                    self.inline(&mut ins.content, path, runs, links)
+                }
+                ParagraphContent::MoveTo(to) => self.inline(&mut to.content, path, runs, links),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Error of parsing a selector
+#[derive(Debug, PartialEq)]
+pub struct SelectorError {
+    /// The offset in bytes in the selector where the error was found.
+    pub position: usize,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl FromStr for Predicate {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { selector, pos: 0 };
+        let predicate = parser.selector_list()?;
+        parser.skip_whitespace();
+        if parser.pos < selector.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(predicate)
+    }
+}
+
+struct Parser<'s> {
+    selector: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn error(&self, message: &str) -> SelectorError {
+        SelectorError {
+            position: self.pos,
+            message: message.to_owned(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.selector[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SelectorError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        self.pos > start
+    }
+
+    fn ident(&mut self) -> Result<&'s str, SelectorError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            self.bump();
+        }
+        if self.pos == start {
+            return Err(self.error("expected a name"));
+        }
+        Ok(&self.selector[start..self.pos])
+    }
+
+    /// Parses a quoted string, or a name.
+    fn value(&mut self) -> Result<String, SelectorError> {
+        let quote = match self.peek() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return self.ident().map(str::to_owned),
+        };
+        self.bump();
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => value.extend(self.bump()),
+                Some(c) if c == quote => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn selector_list(&mut self) -> Result<Predicate, SelectorError> {
+        let mut predicate = self.complex()?;
+        loop {
+            self.skip_whitespace();
+            if !self.eat(',') {
+                return Ok(predicate);
+            }
+            predicate = predicate.or(self.complex()?);
+        }
+    }
+
+    /// Parses compound selectors separated by combinators.
+    fn complex(&mut self) -> Result<Predicate, SelectorError> {
+        self.skip_whitespace();
+        let mut predicate = self.compound()?;
+        loop {
+            let start = self.pos;
+            let whitespace = self.skip_whitespace();
+            if self.eat('>') {
+                self.skip_whitespace();
+                predicate = self.compound()?.and(Predicate::child_of(predicate));
+            } else if whitespace && self.peek().is_some_and(|c| c != ',' && c != ')') {
+                predicate = self.compound()?.and(Predicate::within(predicate));
+            } else {
+                self.pos = start;
+                return Ok(predicate);
+            }
+        }
+    }
+
+    fn compound(&mut self) -> Result<Predicate, SelectorError> {
+        let mut predicates = Vec::new();
+
+        if self.eat('*') {
+            predicates.push(Predicate::Any);
+        } else if self.peek().is_some_and(char::is_alphabetic) {
+            let kind = match self.ident()? {
+                "p" | "paragraph" => Kind::Paragraph,
+                "table" => Kind::Table,
+                "tr" | "row" => Kind::Row,
+                "tc" | "cell" => Kind::Cell,
+                "r" | "run" => Kind::Run,
+                "a" | "hyperlink" => Kind::Hyperlink,
+                _ => return Err(self.error("unknown node kind")),
+            };
+            predicates.push(Predicate::Kind(kind));
+        }
+
+        loop {
+            if self.eat('.') {
+                predicates.push(Predicate::Style(self.ident()?.to_owned()));
+            } else if self.eat('[') {
+                predicates.push(self.attribute()?);
+                self.expect(']')?;
+            } else if self.eat(':') {
+                predicates.push(self.pseudo_class()?);
+            } else {
+                break;
+            }
+        }
+
+        match predicates.len() {
+            0 => Err(self.error("expected a selector")),
+            1 => Ok(predicates.pop().unwrap()),
+            _ => Ok(Predicate::And(predicates)),
+        }
+    }
+
+    fn attribute(&mut self) -> Result<Predicate, SelectorError> {
+        let name = self.ident()?;
+        let value = if self.eat('=') {
+            Some(self.value()?)
+        } else {
+            None
+        };
+
+        match (name, value) {
+            ("bold", None) => Ok(Predicate::Bold),
+            ("italic", None) => Ok(Predicate::Italic),
+            ("underline", None) => Ok(Predicate::Underline),
+            ("strike", None) => Ok(Predicate::Strike),
+            ("href", None) => Ok(Predicate::ExternalLink),
+            ("anchor", None) => Ok(Predicate::InternalLink),
+            ("color", Some(color)) => Ok(Predicate::Color(color)),
+            ("style", Some(style_id)) => Ok(Predicate::Style(style_id)),
+            _ => Err(self.error("unknown attribute")),
+        }
+    }
+
+    fn pseudo_class(&mut self) -> Result<Predicate, SelectorError> {
+        match self.ident()? {
+            "first" => Ok(Predicate::first()),
+            "nth" => {
+                self.expect('(')?;
+                let start = self.pos;
+                let nth = self.ident()?.parse::<usize>().ok().filter(|&nth| nth > 0);
+                let nth = nth.ok_or(SelectorError {
+                    position: start,
+                    message: "expected a position from 1".to_owned(),
+                })?;
+                self.expect(')')?;
+                Ok(Predicate::Nth(nth - 1))
+            }
+            "contains" => {
+                self.expect('(')?;
+                let text = self.value()?;
+                self.expect(')')?;
+                Ok(Predicate::contains(text))
+            }
+            #[cfg(feature = "regex")]
+            "matches" => {
+                self.expect('(')?;
+                let start = self.pos;
+                let regex = self.value()?;
+                let regex = Regex::new(&regex).map_err(|err| SelectorError {
+                    position: start,
+                    message: err.to_string(),
+                })?;
+                self.expect(')')?;
+                Ok(Predicate::contains(regex))
+            }
+            "has" => {
+                self.expect('(')?;
+                let predicate = self.selector_list()?;
+                self.expect(')')?;
+                Ok(Predicate::has(predicate))
+            }
+            "not" => {
+                self.expect('(')?;
+                let predicate = self.selector_list()?;
+                self.expect(')')?;
+                Ok(!predicate)
+            }
+            _ => Err(self.error("unknown pseudo-class")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        document::{Hyperlink, SmartTag},
+        formatting::ParagraphProperty,
+    };
+
+    fn document() -> Document<'static> {
+        let red_bold = CharacterProperty::default().bold(true).color("ff0000");
+
+        let mut document = Document::default();
+        document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().style_id("Heading2"))
+                .push_text("Prices"),
+        );
+        document.push(
+            Paragraph::default()
+                .push(Run::default().property(red_bold.clone()).push_text("Sale"))
+                .push(
+                    Hyperlink::default()
+                        .id("rId5")
+                        .content(Run::default().push_text("shop")),
+                )
+                .push(SmartTag {
+                    content: vec![Run::default().property(red_bold).push_text("now").into()],
+                }),
+        );
+        document.push(
+            Table::default()
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Total")))
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("42"))),
+        );
+        document.push(
+            Table::default()
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Name")))
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Total"))),
+        );
+        document
+    }
+
+    fn paths(document: &Document, selector: &str) -> Vec<Vec<usize>> {
+        document
+            .query(selector)
+            .unwrap()
+            .map(|selected| selected.path)
+            .collect()
+    }
+
+    #[test]
+    fn select_nodes() {
+        let document = document();
+
+        let headings = Predicate::paragraph().and(Predicate::style("Heading2"));
+        assert_eq!(document.select(&headings).count(), 1);
+        assert_eq!(paths(&document, "p.Heading2"), vec![vec![0]]);
+
+        let red_bold = Predicate::run()
+            .and(Predicate::Bold)
+            .and(Predicate::color("FF0000"));
+        let runs: Vec<_> = document.select(&red_bold).map(|s| s.path).collect();
+        assert_eq!(runs, vec![vec![1, 0], vec![1, 2]]);
+        assert_eq!(paths(&document, "r[bold][color=FF0000]"), runs);
+
+        assert_eq!(paths(&document, "a[href]"), vec![vec![1, 0]]);
+        assert_eq!(paths(&document, "a > r"), vec![vec![1, 1]]);
+        assert_eq!(
+            paths(&document, "table:has(tr:first:contains('Total'))"),
+            vec![vec![2]]
+        );
+        assert_eq!(
+            paths(
+                &document,
+                "table tr:nth(2) p:not(:contains(Total)), p.Heading2"
+            ),
+            vec![vec![0], vec![2, 1, 0, 0]]
+        );
+
+        let cell = document.query("tc:contains('42')").unwrap().next().unwrap();
+        assert_eq!(cell.ancestors.len(), 2);
+        assert_eq!(cell.text, "42");
+    }
+
+    #[test]
+    fn select_mut_and_errors() {
+        let mut document = document();
+
+        for selected in document.query_mut("p, r").unwrap() {
+            if let NodeMut::Paragraph(para) = selected.node {
+                para.property = ParagraphProperty::default().style_id("Body");
+            }
+        }
+        assert_eq!(paths(&document, "p.Body").len(), 6);
+        assert_eq!(document.query_mut("r").unwrap().count(), 8);
+
+        assert_eq!(
+            "p:nth(0)".parse::<Predicate>().unwrap_err(),
+            SelectorError {
+                position: 6,
+                message: "expected a position from 1".to_owned()
+            }
+        );
+        assert!("p[".parse::<Predicate>().is_err());
+        assert!("section".parse::<Predicate>().is_err());
+    }
+}
@@ -0,0 +1,944 @@
+//! Positions and ranges
+//!
+//! A [`Position`] addresses a character of a paragraph of the document body
+//! by the path of the paragraph, as in [`Match::path`], and an offset in
+//! characters in its text. Tabs, breaks and pictures count as one character,
+//! and deleted text isn't counted. A [`Range`] spans from one position to
+//! another, possibly in other paragraphs.
+//!
+//! Editing a range splits runs at its boundaries as needed, so the text
+//! around it keeps its formatting.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::CharacterProperty;
+//! use docx::range::{Position, Range};
+//!
+//! let mut document = Document::default();
+//! document.push(Paragraph::default().push_text("Hello, world!"));
+//! document.push(Paragraph::default().push_text("Goodbye."));
+//!
+//! // make "world" bold
+//! let world = Range::new(Position::new(vec![0], 7), Position::new(vec![0], 12));
+//! document
+//!     .apply_property(&world, &CharacterProperty::default().bold(true))
+//!     .unwrap();
+//!
+//! // delete from "!" to "Goodbye", merging the paragraphs
+//! let range = Range::new(Position::new(vec![0], 12), Position::new(vec![1], 7));
+//! document.delete_range(&range).unwrap();
+//!
+//! assert_eq!(document.body.content.len(), 1);
+//! ```
+//!
+//! [`Match::path`]: ../find/struct.Match.html#structfield.path
+
+use std::{fmt, mem};
+
+use crate::{
+    comments::Comment,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart,
+        CommentReference, Document, Hyperlink, Paragraph, ParagraphContent, Run, RunContent,
+        TableCellContent, TableContent, TableRowContent, Text, TextSpace,
+    },
+    find::{self, Match, Part, TextIndex},
+    formatting::CharacterProperty,
+    schema::SCHEMA_HYPERLINK,
+    visit::Visit,
+    Docx,
+};
+
+/// A position in the text of a paragraph of the body
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    /// The path of the paragraph, see [`Match::path`].
+    ///
+    /// [`Match::path`]: ../find/struct.Match.html#structfield.path
+    pub path: Vec<usize>,
+    /// The offset in characters in the text of the paragraph.
+    pub offset: usize,
+}
+
+impl Position {
+    pub fn new(path: Vec<usize>, offset: usize) -> Self {
+        Position { path, offset }
+    }
+}
+
+/// A range between two positions of the body
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Range {
+    /// Creates a range, swapping the positions if `end` is before `start`.
+    pub fn new(start: Position, end: Position) -> Self {
+        if end < start {
+            Range {
+                start: end,
+                end: start,
+            }
+        } else {
+            Range { start, end }
+        }
+    }
+
+    /// Returns `true` if the range starts and ends in the same paragraph.
+    pub fn is_single_paragraph(&self) -> bool {
+        self.start.path == self.end.path
+    }
+}
+
+/// Error of addressing or editing a range
+#[derive(Debug, PartialEq)]
+pub enum RangeError {
+    /// The path doesn't lead to a paragraph, or the offset is after its end.
+    InvalidPosition(Position),
+    /// The operation needs both positions in the same table cell or content
+    /// control, e.g. deleting a range across paragraphs.
+    DifferentContainers,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::InvalidPosition(pos) => {
+                write!(f, "invalid position {:?}:{}", pos.path, pos.offset)
+            }
+            RangeError::DifferentContainers => {
+                write!(f, "range spans different table cells or content controls")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+impl Match {
+    /// Returns the range of a match in the body.
+    pub fn range(&self, document: &Document) -> Option<Range> {
+        if self.part != Part::Body {
+            return None;
+        }
+
+        let para = document.paragraph_at(&self.path)?;
+        let index = TextIndex::new(para);
+        let offset = |pos: &find::TextPosition| {
+            let run_start = index.run_starts.get(pos.run).copied()?;
+            Some(index.text[..run_start].chars().count() + pos.offset)
+        };
+
+        Some(Range::new(
+            Position::new(self.path.clone(), offset(&self.start)?),
+            Position::new(self.path.clone(), offset(&self.end)?),
+        ))
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Returns the paragraph at the given path.
+    pub fn paragraph_at(&self, path: &[usize]) -> Option<&Paragraph<'a>> {
+        fn blocks<'s, 'a>(
+            content: &'s [BodyContent<'a>],
+            path: &[usize],
+        ) -> Option<&'s Paragraph<'a>> {
+            match (content.get(*path.first()?)?, &path[1..]) {
+                (BodyContent::Paragraph(para), []) => Some(para),
+                (BodyContent::Table(table), rest) => rows(&table.rows, rest),
+                (BodyContent::Sdt(sdt), rest) => blocks(&sdt.content.content, rest),
+                _ => None,
+            }
+        }
+
+        fn rows<'s, 'a>(
+            content: &'s [TableContent<'a>],
+            path: &[usize],
+        ) -> Option<&'s Paragraph<'a>> {
+            match content.get(*path.first()?)? {
+                TableContent::Row(row) => cells(&row.cells, &path[1..]),
+                TableContent::Sdt(sdt) => rows(&sdt.content.content, &path[1..]),
+            }
+        }
+
+        fn cells<'s, 'a>(
+            content: &'s [TableRowContent<'a>],
+            path: &[usize],
+        ) -> Option<&'s Paragraph<'a>> {
+            match content.get(*path.first()?)? {
+                TableRowContent::Cell(cell) => {
+                    match (cell.content.get(*path.get(1)?)?, &path[2..]) {
+                        (TableCellContent::Paragraph(para), []) => Some(para),
+                        (TableCellContent::Sdt(sdt), rest) => blocks(&sdt.content.content, rest),
+                        (TableCellContent::Table(table), rest) => rows(&table.rows, rest),
+                        _ => None,
+                    }
+                }
+                TableRowContent::Sdt(sdt) => cells(&sdt.content.content, &path[1..]),
+            }
+        }
+
+        blocks(&self.body.content, path)
+    }
+
+    /// Returns the paragraph at the given path mutably.
+    pub fn paragraph_at_mut(&mut self, path: &[usize]) -> Option<&mut Paragraph<'a>> {
+        let (blocks, index) = parent_mut(&mut self.body.content, path)?;
+        blocks.into_paragraph(index)
+    }
+
+    /// Splits the run at a position, so that a run starts there.
+    pub fn split_run_at(&mut self, pos: &Position) -> Result<(), RangeError> {
+        let para = self.checked_paragraph(pos)?;
+        split_content(&mut para.content, pos.offset, false);
+        Ok(())
+    }
+
+    /// Inserts a run at a position.
+    pub fn insert_run(&mut self, pos: &Position, run: Run<'a>) -> Result<(), RangeError> {
+        self.insert_at(pos, false, ParagraphContent::Run(run))
+    }
+
+    /// Splits a paragraph in two at a position, the second paragraph keeping
+    /// the properties of the first.
+    pub fn split_paragraph(&mut self, pos: &Position) -> Result<(), RangeError> {
+        self.checked_paragraph(pos)?;
+        let (mut blocks, index) = parent_mut(&mut self.body.content, &pos.path)
+            .ok_or_else(|| RangeError::InvalidPosition(pos.clone()))?;
+        let para = blocks.paragraph_mut(index).unwrap();
+
+        let path = split_content(&mut para.content, pos.offset, false);
+        let second = Paragraph {
+            para_id: None,
+            property: para.property.clone(),
+            content: split_off(&mut para.content, &path),
+        };
+        blocks.insert(index + 1, second);
+
+        Ok(())
+    }
+
+    /// Inserts a paragraph at a position, splitting the paragraph there
+    /// unless the position is at its start or its end.
+    pub fn insert_paragraph(
+        &mut self,
+        pos: &Position,
+        para: Paragraph<'a>,
+    ) -> Result<(), RangeError> {
+        let len = text_len(self.checked_paragraph(pos)?);
+        if pos.offset != 0 && pos.offset != len {
+            self.split_paragraph(pos)?;
+        }
+
+        let (mut blocks, index) = parent_mut(&mut self.body.content, &pos.path)
+            .ok_or_else(|| RangeError::InvalidPosition(pos.clone()))?;
+        if pos.offset == 0 {
+            blocks.insert(index, para);
+        } else {
+            blocks.insert(index + 1, para);
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the content of a range. A range across paragraphs merges
+    /// the paragraph of its start with the rest of the paragraph of its end,
+    /// removing the blocks in between.
+    pub fn delete_range(&mut self, range: &Range) -> Result<(), RangeError> {
+        let Range { start, end } = range;
+        self.checked_paragraph(end)?;
+        let start_len = text_len(self.checked_paragraph(start)?);
+
+        if range.is_single_paragraph() {
+            let para = self.paragraph_at_mut(&start.path).unwrap();
+            delete_text(para, start.offset, end.offset);
+            return Ok(());
+        }
+
+        let depth = start.path.len();
+        if depth != end.path.len() || start.path[..depth - 1] != end.path[..depth - 1] {
+            return Err(RangeError::DifferentContainers);
+        }
+
+        let (first, last) = (start.path[depth - 1], end.path[depth - 1]);
+        let (mut blocks, _) = parent_mut(&mut self.body.content, &start.path).unwrap();
+
+        let last_para = blocks.paragraph_mut(last).unwrap();
+        delete_text(last_para, 0, end.offset);
+        let rest = mem::take(&mut last_para.content);
+
+        let first_para = blocks.paragraph_mut(first).unwrap();
+        delete_text(first_para, start.offset, start_len);
+        first_para.content.extend(rest);
+
+        blocks.remove(first + 1..last + 1);
+
+        Ok(())
+    }
+
+    /// Wraps a range in a bookmark, and returns the id of the bookmark.
+    pub fn bookmark_range(&mut self, range: &Range, name: &str) -> Result<String, RangeError> {
+        let mut next_id = NextBookmarkId::default();
+        next_id.visit_document(self);
+        let id = next_id.0.to_string();
+
+        self.wrap(
+            range,
+            BookmarkStart::default()
+                .id(id.clone())
+                .name(name.to_owned())
+                .into(),
+            BookmarkEnd::default().id(id.clone()).into(),
+        )?;
+
+        Ok(id)
+    }
+
+    /// Wraps a range in the range of a comment, and inserts the reference
+    /// to the comment after it.
+    ///
+    /// The comment itself is stored in the comments part, see
+    /// [`Docx::add_comment`].
+    ///
+    /// [`Docx::add_comment`]: ../struct.Docx.html#method.add_comment
+    pub fn comment_range(&mut self, range: &Range, id: &str) -> Result<(), RangeError> {
+        self.wrap(
+            range,
+            CommentRangeStart::default().id(id.to_owned()).into(),
+            CommentRangeEnd::default().id(id.to_owned()).into(),
+        )?;
+
+        let reference = Run::default().push(CommentReference::from(id.to_owned()));
+        let para = self.paragraph_at_mut(&range.end.path).unwrap();
+        // the boundary after the end of the comment range
+        let path = split_content(&mut para.content, range.end.offset, true);
+        insert_content(&mut para.content, &path, ParagraphContent::Run(reference));
+
+        Ok(())
+    }
+
+    /// Wraps the runs of a range in hyperlinks, each run of the range in a
+    /// copy of `link`.
+    pub fn link_range(&mut self, range: &Range, link: Hyperlink<'a>) -> Result<(), RangeError> {
+        self.edit_runs(range, &mut |content| {
+            if let ParagraphContent::Run(run) = content {
+                let run = mem::take(run);
+                *content = ParagraphContent::Link(link.clone().content(run));
+            }
+        })
+    }
+
+    /// Calls `f` on the properties of the runs of a range.
+    pub fn format_range<F>(&mut self, range: &Range, mut f: F) -> Result<(), RangeError>
+    where
+        F: FnMut(&mut CharacterProperty<'a>),
+    {
+        self.edit_runs(range, &mut |content| match content {
+            ParagraphContent::Run(run) => f(&mut run.property),
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &mut link.content {
+                    f(&mut run.property);
+                }
+            }
+            _ => (),
+        })
+    }
+
+    /// Applies the specified character properties to the runs of a range.
+    pub fn apply_property(
+        &mut self,
+        range: &Range,
+        property: &CharacterProperty<'a>,
+    ) -> Result<(), RangeError> {
+        self.format_range(range, |prop| prop.merge(property))
+    }
+
+    fn checked_paragraph(&mut self, pos: &Position) -> Result<&mut Paragraph<'a>, RangeError> {
+        match self.paragraph_at_mut(&pos.path) {
+            Some(para) if pos.offset <= text_len(para) => Ok(para),
+            _ => Err(RangeError::InvalidPosition(pos.clone())),
+        }
+    }
+
+    pub(crate) fn insert_at(
+        &mut self,
+        pos: &Position,
+        is_end: bool,
+        item: ParagraphContent<'a>,
+    ) -> Result<(), RangeError> {
+        let para = self.checked_paragraph(pos)?;
+        let path = split_content(&mut para.content, pos.offset, is_end);
+        insert_content(&mut para.content, &path, item);
+        Ok(())
+    }
+
+    /// Inserts markers at the start and the end of a range.
+    fn wrap(
+        &mut self,
+        range: &Range,
+        start: ParagraphContent<'a>,
+        end: ParagraphContent<'a>,
+    ) -> Result<(), RangeError> {
+        self.checked_paragraph(&range.start)?;
+        self.checked_paragraph(&range.end)?;
+        self.insert_at(&range.start, false, start)?;
+        self.insert_at(&range.end, true, end)
+    }
+
+    /// Calls `f` on the runs and hyperlinks inside a range, after splitting
+    /// runs at its boundaries.
+    pub(crate) fn edit_runs(
+        &mut self,
+        range: &Range,
+        f: &mut dyn FnMut(&mut ParagraphContent<'a>),
+    ) -> Result<(), RangeError> {
+        self.checked_paragraph(&range.start)?;
+        self.checked_paragraph(&range.end)?;
+
+        find::visit_paragraphs_mut(
+            &mut self.body.content,
+            &mut Vec::new(),
+            &mut |path, para| {
+                if path < &range.start.path[..] || path > &range.end.path[..] {
+                    return;
+                }
+                let start = if path == &range.start.path[..] {
+                    range.start.offset
+                } else {
+                    0
+                };
+                let end = if path == &range.end.path[..] {
+                    range.end.offset
+                } else {
+                    text_len(para)
+                };
+
+                split_content(&mut para.content, start, false);
+                split_content(&mut para.content, end, true);
+                visit_range(&mut para.content, &mut 0, start, end, f);
+            },
+        );
+
+        Ok(())
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Adds a comment on a range of the body, and returns the id of the
+    /// comment.
+    ///
+    /// ```rust
+    /// use docx::comments::Comment;
+    /// use docx::document::*;
+    /// use docx::range::{Position, Range};
+    /// use docx::Docx;
+    ///
+    /// let mut docx = Docx::default();
+    /// docx.document.push(Paragraph::default().push_text("Hello, world!"));
+    ///
+    /// let comment = Comment {
+    ///     author: "John".into(),
+    ///     content: vec![Paragraph::default().push_text("Too informal")],
+    ///     ..Default::default()
+    /// };
+    /// let range = Range::new(Position::new(vec![0], 0), Position::new(vec![0], 5));
+    /// let id = docx.add_comment(&range, comment).unwrap();
+    ///
+    /// assert_eq!(docx.comments.unwrap().comments[0].id, id);
+    /// ```
+    pub fn add_comment(
+        &mut self,
+        range: &Range,
+        mut comment: Comment<'a>,
+    ) -> Result<String, RangeError> {
+        let comments = &mut self.comments.get_or_insert_with(Default::default).comments;
+        let id = comments
+            .iter()
+            .filter_map(|comment| comment.id.parse::<usize>().ok())
+            .map(|id| id + 1)
+            .max()
+            .unwrap_or_default()
+            .to_string();
+
+        self.document.comment_range(range, &id)?;
+
+        comment.id = id.clone().into();
+        self.comments
+            .get_or_insert_with(Default::default)
+            .comments
+            .push(comment);
+
+        Ok(id)
+    }
+
+    /// Turns a range of the body into a hyperlink to an external address.
+    pub fn add_hyperlink(&mut self, range: &Range, url: &str) -> Result<(), RangeError> {
+        self.document.checked_paragraph(&range.start)?;
+        self.document.checked_paragraph(&range.end)?;
+
+        let id = self
+            .document_rels
+            .get_or_insert_with(Default::default)
+            .add_external_rel(SCHEMA_HYPERLINK, url.to_owned());
+
+        self.document.link_range(range, Hyperlink::default().id(id))
+    }
+}
+
+/// The blocks containing a paragraph
+enum Blocks<'s, 'a> {
+    Body(&'s mut Vec<BodyContent<'a>>),
+    Cell(&'s mut Vec<TableCellContent<'a>>),
+}
+
+impl<'s, 'a> Blocks<'s, 'a> {
+    fn into_paragraph(self, index: usize) -> Option<&'s mut Paragraph<'a>> {
+        match self {
+            Blocks::Body(content) => match content.get_mut(index)? {
+                BodyContent::Paragraph(para) => Some(para),
+                _ => None,
+            },
+            Blocks::Cell(content) => match content.get_mut(index)? {
+                TableCellContent::Paragraph(para) => Some(para),
+                _ => None,
+            },
+        }
+    }
+
+    fn paragraph_mut(&mut self, index: usize) -> Option<&mut Paragraph<'a>> {
+        match self {
+            Blocks::Body(content) => match content.get_mut(index)? {
+                BodyContent::Paragraph(para) => Some(para),
+                _ => None,
+            },
+            Blocks::Cell(content) => match content.get_mut(index)? {
+                TableCellContent::Paragraph(para) => Some(para),
+                _ => None,
+            },
+        }
+    }
+
+    fn insert(&mut self, index: usize, para: Paragraph<'a>) {
+        match self {
+            Blocks::Body(content) => content.insert(index, para.into()),
+            Blocks::Cell(content) => content.insert(index, para.into()),
+        }
+    }
+
+    fn remove(&mut self, range: std::ops::Range<usize>) {
+        match self {
+            Blocks::Body(content) => drop(content.drain(range)),
+            Blocks::Cell(content) => drop(content.drain(range)),
+        }
+    }
+}
+
+/// Returns the blocks containing the paragraph at the path, and its index.
+fn parent_mut<'s, 'a>(
+    content: &'s mut Vec<BodyContent<'a>>,
+    path: &[usize],
+) -> Option<(Blocks<'s, 'a>, usize)> {
+    fn rows<'s, 'a>(
+        content: &'s mut [TableContent<'a>],
+        path: &[usize],
+    ) -> Option<(Blocks<'s, 'a>, usize)> {
+        match content.get_mut(*path.first()?)? {
+            TableContent::Row(row) => cells(&mut row.cells, &path[1..]),
+            TableContent::Sdt(sdt) => rows(&mut sdt.content.content, &path[1..]),
+        }
+    }
+
+    fn cells<'s, 'a>(
+        content: &'s mut [TableRowContent<'a>],
+        path: &[usize],
+    ) -> Option<(Blocks<'s, 'a>, usize)> {
+        match content.get_mut(*path.first()?)? {
+            TableRowContent::Cell(cell) => {
+                let index = *path.get(1)?;
+                if path.len() == 2 {
+                    return Some((Blocks::Cell(&mut cell.content), index));
+                }
+                match cell.content.get_mut(index)? {
+                    TableCellContent::Sdt(sdt) => parent_mut(&mut sdt.content.content, &path[2..]),
+                    TableCellContent::Table(table) => rows(&mut table.rows, &path[2..]),
+                    _ => None,
+                }
+            }
+            TableRowContent::Sdt(sdt) => cells(&mut sdt.content.content, &path[1..]),
+        }
+    }
+
+    let index = *path.first()?;
+    if path.len() == 1 {
+        return Some((Blocks::Body(content), index));
+    }
+    match content.get_mut(index)? {
+        BodyContent::Table(table) => rows(&mut table.rows, &path[1..]),
+        BodyContent::Sdt(sdt) => parent_mut(&mut sdt.content.content, &path[1..]),
+        _ => None,
+    }
+}
+
+/// Returns the length in characters of the text of a paragraph.
+fn text_len(para: &Paragraph) -> usize {
+    TextIndex::new(para).text.chars().count()
+}
+
+fn content_len(content: &ParagraphContent) -> usize {
+    let mut runs = Vec::new();
+    find::runs_of(std::slice::from_ref(content), &mut runs);
+    runs.iter()
+        .flat_map(|run| run.content.iter().filter_map(find::piece_text))
+        .map(|text| text.chars().count())
+        .sum()
+}
+
+fn run_content_len(content: &RunContent) -> usize {
+    find::piece_text(content).map_or(0, |text| text.chars().count())
+}
+
+/// Returns the contents of a container, e.g. a smart tag.
+pub(crate) fn container_mut<'s, 'a>(
+    content: &'s mut ParagraphContent<'a>,
+) -> Option<&'s mut Vec<ParagraphContent<'a>>> {
+    match content {
+        ParagraphContent::SmartTag(tag) => Some(&mut tag.content),
+        ParagraphContent::Sdt(sdt) => Some(&mut sdt.content.content),
+        ParagraphContent::SimpleField(field) => Some(&mut field.content),
+        ParagraphContent::Insertion(ins) => Some(&mut ins.content),
+        ParagraphContent::MoveTo(to) => Some(&mut to.content),
+        _ => None,
+    }
+}
+
+/// Splits runs so that a boundary between contents lies at the offset, and
+/// returns the path of the boundary: the index of the content following
+/// it, preceded by the indices of the containers it lies in.
+///
+/// Contents without text at the offset, e.g. bookmarks, are placed before
+/// the boundary if `is_end` is `true`, and after it otherwise.
+fn split_content(content: &mut Vec<ParagraphContent>, offset: usize, is_end: bool) -> Vec<usize> {
+    let mut acc = 0;
+    let mut index = 0;
+
+    while index < content.len() {
+        let len = content_len(&content[index]);
+
+        if acc == offset && !(is_end && len == 0) {
+            return vec![index];
+        }
+
+        if acc < offset && offset < acc + len {
+            let at = offset - acc;
+            match &mut content[index] {
+                ParagraphContent::Run(run) => {
+                    let second = split_run(run, at);
+                    content.insert(index + 1, ParagraphContent::Run(second));
+                    return vec![index + 1];
+                }
+                ParagraphContent::Link(link) => {
+                    let mut second = link.clone();
+                    second.content = link.content.as_mut().map(|run| split_run(run, at));
+                    content.insert(index + 1, ParagraphContent::Link(second));
+                    return vec![index + 1];
+                }
+                other => {
+                    if let Some(inner) = container_mut(other) {
+                        let mut path = vec![index];
+                        path.extend(split_content(inner, at, is_end));
+                        return path;
+                    }
+                }
+            }
+        }
+
+        acc += len;
+        index += 1;
+    }
+
+    vec![content.len()]
+}
+
+/// Splits a run at an offset in characters, and returns the second part.
+fn split_run<'a>(run: &mut Run<'a>, offset: usize) -> Run<'a> {
+    let mut acc = 0;
+    let mut index = 0;
+
+    while index < run.content.len() {
+        let len = run_content_len(&run.content[index]);
+        if acc + len > offset {
+            if acc < offset {
+                if let RunContent::Text(text) = &mut run.content[index] {
+                    let at = text
+                        .text
+                        .char_indices()
+                        .nth(offset - acc)
+                        .map_or(text.text.len(), |(at, _)| at);
+                    let tail = text.text.to_mut().split_off(at);
+                    text.space = Some(TextSpace::Preserve);
+                    run.content.insert(
+                        index + 1,
+                        RunContent::Text(Text::from((tail, TextSpace::Preserve))),
+                    );
+                    index += 1;
+                }
+            }
+            break;
+        }
+        acc += len;
+        index += 1;
+    }
+
+    Run {
+        property: run.property.clone(),
+        content: run.content.split_off(index),
+    }
+}
+
+/// Splits the contents at a boundary, and returns the contents after it,
+/// copying the containers the boundary lies in.
+fn split_off<'a>(
+    content: &mut Vec<ParagraphContent<'a>>,
+    path: &[usize],
+) -> Vec<ParagraphContent<'a>> {
+    match path {
+        [] => Vec::new(),
+        [index] => content.split_off(*index),
+        [index, rest @ ..] => {
+            let mut tail = content.split_off(*index + 1);
+            let mut container = content[*index].clone();
+            if let (Some(inner), Some(copy)) = (
+                container_mut(&mut content[*index]),
+                container_mut(&mut container),
+            ) {
+                *copy = split_off(inner, rest);
+            }
+            tail.insert(0, container);
+            tail
+        }
+    }
+}
+
+fn insert_content<'a>(
+    content: &mut Vec<ParagraphContent<'a>>,
+    path: &[usize],
+    item: ParagraphContent<'a>,
+) {
+    match path {
+        [] => (),
+        [index] => content.insert((*index).min(content.len()), item),
+        [index, rest @ ..] => {
+            if let Some(inner) = content.get_mut(*index).and_then(container_mut) {
+                insert_content(inner, rest, item);
+            }
+        }
+    }
+}
+
+/// Deletes the text between two offsets in characters.
+fn delete_text(para: &mut Paragraph, start: usize, end: usize) {
+    if start >= end {
+        return;
+    }
+
+    let index = TextIndex::new(para);
+    let byte = |offset: usize| {
+        index
+            .text
+            .char_indices()
+            .nth(offset)
+            .map_or(index.text.len(), |(at, _)| at)
+    };
+    let edits = [(byte(start), byte(end), String::new())];
+    find::replace_text(para, &index, &edits);
+}
+
+/// Calls `f` on the runs and hyperlinks between two offsets.
+fn visit_range<'a>(
+    content: &mut [ParagraphContent<'a>],
+    acc: &mut usize,
+    start: usize,
+    end: usize,
+    f: &mut dyn FnMut(&mut ParagraphContent<'a>),
+) {
+    for content in content {
+        let len = content_len(content);
+        match content {
+            ParagraphContent::Run(_) | ParagraphContent::Link(_) => {
+                if len > 0 && start <= *acc && *acc + len <= end {
+                    f(content);
+                }
+                *acc += len;
+            }
+            other => match container_mut(other) {
+                Some(inner) => visit_range(inner, acc, start, end, f),
+                None => *acc += len,
+            },
+        }
+    }
+}
+
+/// Finds the next free numeric bookmark id.
+#[derive(Default)]
+struct NextBookmarkId(usize);
+
+impl<'a> Visit<'a> for NextBookmarkId {
+    fn visit_bookmark_start(&mut self, bookmark: &BookmarkStart<'a>) {
+        if let Some(Ok(id)) = bookmark.id.as_ref().map(|id| id.parse::<usize>()) {
+            self.0 = self.0.max(id + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{Table, TableRow};
+
+    fn bold() -> CharacterProperty<'static> {
+        CharacterProperty::default().bold(true)
+    }
+
+    fn runs(document: &Document, path: &[usize]) -> Vec<(String, bool)> {
+        let mut runs = Vec::new();
+        find::runs_of(&document.paragraph_at(path).unwrap().content, &mut runs);
+        runs.iter()
+            .map(|run| {
+                let text = run.iter_text().map(|text| text.to_string()).collect();
+                (text, run.property.bold.is_some())
+            })
+            .collect()
+    }
+
+    fn range(start: (usize, usize), end: (usize, usize)) -> Range {
+        Range::new(
+            Position::new(vec![start.0], start.1),
+            Position::new(vec![end.0], end.1),
+        )
+    }
+
+    #[test]
+    fn format_and_insert() {
+        let mut document = Document::default();
+        document.push(
+            Paragraph::default()
+                .push(Run::default().push_text("Hello, "))
+                .push(Run::default().property(bold()).push_text("big world")),
+        );
+
+        document
+            .format_range(&range((0, 4), (0, 9)), |prop| {
+                prop.italics = Some(true.into())
+            })
+            .unwrap();
+        document
+            .insert_run(
+                &Position::new(vec![0], 11),
+                Run::default().push_text("wide "),
+            )
+            .unwrap();
+
+        assert_eq!(
+            runs(&document, &[0]),
+            vec![
+                ("Hell".to_owned(), false),
+                ("o, ".to_owned(), false),
+                ("bi".to_owned(), true),
+                ("g ".to_owned(), true),
+                ("wide ".to_owned(), false),
+                ("world".to_owned(), true),
+            ]
+        );
+
+        assert_eq!(
+            document.insert_run(&Position::new(vec![0], 100), Run::default()),
+            Err(RangeError::InvalidPosition(Position::new(vec![0], 100)))
+        );
+    }
+
+    #[test]
+    fn delete_and_split() {
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("first"));
+        document.push(Table::default().push_row(TableRow::default()));
+        document.push(Paragraph::default().push(Run::default().property(bold()).push_text("last")));
+
+        document.delete_range(&range((0, 3), (2, 1))).unwrap();
+        assert_eq!(document.body.content.len(), 1);
+        assert_eq!(
+            runs(&document, &[0]),
+            vec![("fir".to_owned(), false), ("ast".to_owned(), true)]
+        );
+
+        document
+            .split_paragraph(&Position::new(vec![0], 4))
+            .unwrap();
+        assert_eq!(
+            runs(&document, &[0]),
+            vec![("fir".to_owned(), false), ("a".to_owned(), true)]
+        );
+        assert_eq!(runs(&document, &[1]), vec![("st".to_owned(), true)]);
+
+        document
+            .insert_paragraph(
+                &Position::new(vec![1], 0),
+                Paragraph::default().push_text("new"),
+            )
+            .unwrap();
+        assert_eq!(runs(&document, &[1]), vec![("new".to_owned(), false)]);
+    }
+
+    #[test]
+    fn wrap_ranges() {
+        let mut docx = Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("Hello, world!"));
+
+        let id = docx
+            .document
+            .bookmark_range(&range((0, 7), (0, 12)), "place")
+            .unwrap();
+        assert_eq!(id, "0");
+        docx.add_hyperlink(&range((0, 7), (0, 12)), "https://example.com")
+            .unwrap();
+        let comment = docx
+            .add_comment(&range((0, 0), (0, 5)), Comment::default())
+            .unwrap();
+        assert_eq!(comment, "0");
+
+        let para = docx.document.paragraph_at(&[0]).unwrap();
+        let kinds: Vec<&str> = para
+            .content
+            .iter()
+            .map(|content| match content {
+                ParagraphContent::Run(run) if run.iter_text().next().is_none() => "reference",
+                ParagraphContent::Run(_) => "run",
+                ParagraphContent::Link(_) => "link",
+                ParagraphContent::BookmarkStart(_) => "bookmarkStart",
+                ParagraphContent::BookmarkEnd(_) => "bookmarkEnd",
+                ParagraphContent::CommentRangeStart(_) => "commentStart",
+                ParagraphContent::CommentRangeEnd(_) => "commentEnd",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "commentStart",
+                "run",
+                "commentEnd",
+                "reference",
+                "run",
+                "bookmarkStart",
+                "link",
+                "bookmarkEnd",
+                "run"
+            ]
+        );
+
+        let found = docx.document.find("world");
+        assert_eq!(found[0].range(&docx.document), Some(range((0, 7), (0, 12))));
+    }
+}
@@ -8,7 +8,16 @@ use std::io::Write;
 use crate::private_prelude::*;
 use crate::schema::SCHEMA_RELATIONSHIPS;
 
-#[derive(Debug, Default, XmlRead)]
+/// Returns the ZIP item name of the relationships of a part, e.g.
+/// `word/_rels/header1.xml.rels` for `word/header1.xml`.
+pub(crate) fn rels_name(name: &str) -> String {
+    match name.rsplit_once('/') {
+        Some((dir, file)) => format!("{}/_rels/{}.rels", dir, file),
+        None => format!("_rels/{}.rels", name),
+    }
+}
+
+#[derive(Clone, Debug, Default, XmlRead)]
 #[xml(tag = "Relationships")]
 pub struct Relationships<'a> {
     #[xml(child = "Relationship")]
@@ -42,13 +51,60 @@ impl<'a> XmlWrite for Relationships<'a> {
 }
 
 impl<'a> Relationships<'a> {
-    pub fn add_rel(&mut self, schema: &'a str, target: &'a str) {
-        let len = self.relationships.len();
+    /// Adds a relationship, unless an identical one exists, and returns its id.
+    pub fn add_rel<T: Into<Cow<'a, str>>>(&mut self, schema: &'a str, target: T) -> String {
+        let target = target.into();
+
+        if let Some(rel) = self
+            .relationships
+            .iter()
+            .find(|r| r.ty == schema && r.target == target)
+        {
+            return rel.id.to_string();
+        }
+
+        let id = self.next_id();
         self.relationships.push(Relationship {
-            id: format!("rId{}", len).into(),
-            target: target.into(),
+            id: id.clone().into(),
+            target,
             ty: schema.into(),
+            target_mode: None,
         });
+
+        id
+    }
+
+    /// Adds a relationship to an external resource, e.g. the target of a
+    /// hyperlink, unless an identical one exists, and returns its id.
+    pub fn add_external_rel<T: Into<Cow<'a, str>>>(
+        &mut self,
+        schema: &'a str,
+        target: T,
+    ) -> String {
+        let target = target.into();
+
+        if let Some(rel) = self.relationships.iter().find(|r| {
+            r.ty == schema && r.target == target && r.target_mode.as_deref() == Some("External")
+        }) {
+            return rel.id.to_string();
+        }
+
+        let id = self.next_id();
+        self.relationships.push(Relationship {
+            id: id.clone().into(),
+            target,
+            ty: schema.into(),
+            target_mode: Some("External".into()),
+        });
+
+        id
+    }
+
+    fn next_id(&self) -> String {
+        (self.relationships.len() + 1..)
+            .map(|n| format!("rId{}", n))
+            .find(|id| self.relationships.iter().all(|r| r.id != *id))
+            .unwrap_or_default()
     }
 
     pub fn get_target(&self, id: &str) -> Option<&str> {
@@ -59,7 +115,7 @@ impl<'a> Relationships<'a> {
     }
 }
 
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[xml(tag = "Relationship")]
 pub struct Relationship<'a> {
     #[xml(attr = "Id")]
@@ -68,4 +124,6 @@ pub struct Relationship<'a> {
     pub target: Cow<'a, str>,
     #[xml(attr = "Type")]
     pub ty: Cow<'a, str>,
+    #[xml(attr = "TargetMode")]
+    pub target_mode: Option<Cow<'a, str>>,
 }
@@ -0,0 +1,816 @@
+//! Tracked changes
+//!
+//! Lists, accepts and rejects the revisions recorded in the main document.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//!
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .push_text("hello")
+//!         .push(Insertion::new("1").author("John").push(Run::default().push_text(" world"))),
+//! );
+//!
+//! assert_eq!(docx.document.revisions_by("John").len(), 1);
+//!
+//! docx.document.accept_all();
+//!
+//! assert!(docx.document.revisions().is_empty());
+//! ```
+
+mod tracked;
+
+pub use self::tracked::*;
+
+use std::mem;
+
+use crate::document::{
+    BodyContent, DeletedText, Deletion, Document, Insertion, MoveFrom, MoveTo, Paragraph,
+    ParagraphContent, Run, RunContent, Table, TableCell, TableCellContent, TableContent, TableRow,
+    Text,
+};
+
+/// The kind of a tracked revision
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevisionKind {
+    /// Inserted run content (`w:ins`)
+    Insertion,
+    /// Deleted run content (`w:del`)
+    Deletion,
+    /// Source of moved run content (`w:moveFrom`)
+    MoveFrom,
+    /// Destination of moved run content (`w:moveTo`)
+    MoveTo,
+    /// Changed run properties (`w:rPrChange`)
+    CharacterProperty,
+    /// Changed paragraph properties (`w:pPrChange`)
+    ParagraphProperty,
+    /// Changed table properties (`w:tblPrChange`)
+    TableProperty,
+}
+
+/// A tracked revision found in the document
+#[derive(Clone, Debug, PartialEq)]
+pub struct Revision<'r> {
+    /// Specifies the kind of the revision.
+    pub kind: RevisionKind,
+    /// Specifies the identifier of the revision.
+    pub id: &'r str,
+    /// Specifies the author of the revision.
+    pub author: Option<&'r str>,
+    /// Specifies the date of the revision.
+    pub date: Option<&'r str>,
+    /// Specifies the text affected by an insertion, deletion or move.
+    pub text: String,
+}
+
+impl<'a> Document<'a> {
+    /// Returns all revisions in document order.
+    pub fn revisions(&self) -> Vec<Revision<'_>> {
+        let mut revisions = Vec::new();
+        for content in &self.body.content {
+            collect_body_content(content, &mut revisions);
+        }
+        revisions
+    }
+
+    /// Returns all revisions made by the given author.
+    pub fn revisions_by(&self, author: &str) -> Vec<Revision<'_>> {
+        self.revisions()
+            .into_iter()
+            .filter(|revision| revision.author == Some(author))
+            .collect()
+    }
+
+    /// Accepts all revisions.
+    pub fn accept_all(&mut self) {
+        Resolver::new(None, Action::Accept).body(&mut self.body.content);
+    }
+
+    /// Rejects all revisions.
+    pub fn reject_all(&mut self) {
+        Resolver::new(None, Action::Reject).body(&mut self.body.content);
+    }
+
+    /// Accepts the revisions with the given id, returns `false` if there is none.
+    pub fn accept_revision(&mut self, id: &str) -> bool {
+        let mut resolver = Resolver::new(Some(id), Action::Accept);
+        resolver.body(&mut self.body.content);
+        resolver.found
+    }
+
+    /// Rejects the revisions with the given id, returns `false` if there is none.
+    pub fn reject_revision(&mut self, id: &str) -> bool {
+        let mut resolver = Resolver::new(Some(id), Action::Reject);
+        resolver.body(&mut self.body.content);
+        resolver.found
+    }
+}
+
+fn collect_body_content<'r>(content: &'r BodyContent, revisions: &mut Vec<Revision<'r>>) {
+    match content {
+        BodyContent::Paragraph(para) => collect_paragraph(para, revisions),
+        BodyContent::Table(table) => collect_table(table, revisions),
+        BodyContent::Sdt(sdt) => {
+            for content in &sdt.content.content {
+                collect_body_content(content, revisions);
+            }
+        }
+    }
+}
+
+fn collect_table<'r>(table: &'r Table, revisions: &mut Vec<Revision<'r>>) {
+    if let Some(change) = &table.property.change {
+        revisions.push(Revision {
+            kind: RevisionKind::TableProperty,
+            id: &change.id,
+            author: change.author.as_deref(),
+            date: change.date.as_deref(),
+            text: String::new(),
+        });
+    }
+
+    for row in table.iter_rows() {
+        if let Some(ins) = &row.property.insertion {
+            revisions.push(Revision {
+                kind: RevisionKind::Insertion,
+                id: &ins.id,
+                author: ins.author.as_deref(),
+                date: ins.date.as_deref(),
+                text: String::new(),
+            });
+        }
+        if let Some(del) = &row.property.deletion {
+            revisions.push(Revision {
+                kind: RevisionKind::Deletion,
+                id: &del.id,
+                author: del.author.as_deref(),
+                date: del.date.as_deref(),
+                text: String::new(),
+            });
+        }
+
+        for cell in row.iter_cells() {
+            for content in &cell.content {
+                match content {
+                    TableCellContent::Paragraph(para) => collect_paragraph(para, revisions),
+                    TableCellContent::Sdt(sdt) => {
+                        for content in &sdt.content.content {
+                            collect_body_content(content, revisions);
+                        }
+                    }
+                    TableCellContent::Table(table) => collect_table(table, revisions),
+                }
+            }
+        }
+    }
+}
+
+fn collect_paragraph<'r>(para: &'r Paragraph, revisions: &mut Vec<Revision<'r>>) {
+    if let Some(change) = &para.property.change {
+        revisions.push(Revision {
+            kind: RevisionKind::ParagraphProperty,
+            id: &change.id,
+            author: change.author.as_deref(),
+            date: change.date.as_deref(),
+            text: String::new(),
+        });
+    }
+
+    collect_content(&para.content, revisions);
+
+    if let Some(mark) = &para.property.mark {
+        if let Some(ins) = &mark.insertion {
+            revisions.push(Revision {
+                kind: RevisionKind::Insertion,
+                id: &ins.id,
+                author: ins.author.as_deref(),
+                date: ins.date.as_deref(),
+                text: String::new(),
+            });
+        }
+        if let Some(del) = &mark.deletion {
+            revisions.push(Revision {
+                kind: RevisionKind::Deletion,
+                id: &del.id,
+                author: del.author.as_deref(),
+                date: del.date.as_deref(),
+                text: String::new(),
+            });
+        }
+    }
+}
+
+fn collect_content<'r>(content: &'r [ParagraphContent], revisions: &mut Vec<Revision<'r>>) {
+    for content in content {
+        let (kind, id, author, date, inner) = match content {
+            ParagraphContent::Run(run) => {
+                collect_run(run, revisions);
+                continue;
+            }
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &link.content {
+                    collect_run(run, revisions);
+                }
+                continue;
+            }
+            ParagraphContent::SmartTag(tag) => {
+                collect_content(&tag.content, revisions);
+                continue;
+            }
+            ParagraphContent::SimpleField(field) => {
+                collect_content(&field.content, revisions);
+                continue;
+            }
+            ParagraphContent::Sdt(sdt) => {
+                collect_content(&sdt.content.content, revisions);
+                continue;
+            }
+            ParagraphContent::Insertion(Insertion {
+                id,
+                author,
+                date,
+                content,
+            }) => (RevisionKind::Insertion, id, author, date, content),
+            ParagraphContent::Deletion(Deletion {
+                id,
+                author,
+                date,
+                content,
+            }) => (RevisionKind::Deletion, id, author, date, content),
+            ParagraphContent::MoveFrom(MoveFrom {
+                id,
+                author,
+                date,
+                content,
+            }) => (RevisionKind::MoveFrom, id, author, date, content),
+            ParagraphContent::MoveTo(MoveTo {
+                id,
+                author,
+                date,
+                content,
+            }) => (RevisionKind::MoveTo, id, author, date, content),
+            _ => continue,
+        };
+
+        revisions.push(Revision {
+            kind,
+            id,
+            author: author.as_deref(),
+            date: date.as_deref(),
+            text: revision_text(inner),
+        });
+
+        collect_content(inner, revisions);
+    }
+}
+
+fn collect_run<'r>(run: &'r Run, revisions: &mut Vec<Revision<'r>>) {
+    if let Some(change) = &run.property.change {
+        revisions.push(Revision {
+            kind: RevisionKind::CharacterProperty,
+            id: &change.id,
+            author: change.author.as_deref(),
+            date: change.date.as_deref(),
+            text: run.iter_text().map(|text| &**text).collect(),
+        });
+    }
+}
+
+/// Concatenates both the current and the deleted text of the content.
+fn revision_text(content: &[ParagraphContent]) -> String {
+    let mut text = String::new();
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => push_run_text(run, &mut text),
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &link.content {
+                    push_run_text(run, &mut text);
+                }
+            }
+            ParagraphContent::SmartTag(tag) => text.push_str(&revision_text(&tag.content)),
+            ParagraphContent::SimpleField(field) => text.push_str(&revision_text(&field.content)),
+            ParagraphContent::Sdt(sdt) => text.push_str(&revision_text(&sdt.content.content)),
+            ParagraphContent::Insertion(Insertion { content, .. })
+            | ParagraphContent::Deletion(Deletion { content, .. })
+            | ParagraphContent::MoveFrom(MoveFrom { content, .. })
+            | ParagraphContent::MoveTo(MoveTo { content, .. }) => {
+                text.push_str(&revision_text(content))
+            }
+            _ => (),
+        }
+    }
+    text
+}
+
+fn push_run_text(run: &Run, text: &mut String) {
+    for content in &run.content {
+        match content {
+            RunContent::Text(t) => text.push_str(&t.text),
+            RunContent::DeletedText(t) => text.push_str(&t.text),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Action {
+    Accept,
+    Reject,
+}
+
+/// Rewrites the tree by accepting or rejecting the matching revisions.
+struct Resolver<'s> {
+    id: Option<&'s str>,
+    action: Action,
+    found: bool,
+}
+
+impl<'s> Resolver<'s> {
+    fn new(id: Option<&'s str>, action: Action) -> Self {
+        Resolver {
+            id,
+            action,
+            found: false,
+        }
+    }
+
+    fn matches(&mut self, id: &str) -> bool {
+        let matches = self.id.map_or(true, |target| target == id);
+        self.found |= matches;
+        matches
+    }
+
+    fn body(&mut self, content: &mut Vec<BodyContent>) {
+        let mut index = 0;
+        while index < content.len() {
+            let merge = match &mut content[index] {
+                BodyContent::Paragraph(para) => {
+                    self.paragraph(para);
+                    self.mark(para)
+                }
+                BodyContent::Sdt(sdt) => {
+                    self.body(&mut sdt.content.content);
+                    index += 1;
+                    continue;
+                }
+                BodyContent::Table(table) => {
+                    let rows = table.rows.len();
+                    self.table(table);
+                    // drops tables whose rows are all removed
+                    if rows > 0 && table.rows.is_empty() {
+                        content.remove(index);
+                    } else {
+                        index += 1;
+                    }
+                    continue;
+                }
+            };
+
+            if !merge {
+                index += 1;
+                continue;
+            }
+
+            // a removed paragraph mark joins the paragraph with the next one
+            if let BodyContent::Paragraph(para) = content.remove(index) {
+                match content.get_mut(index) {
+                    Some(BodyContent::Paragraph(next)) => {
+                        next.content.splice(0..0, para.content);
+                    }
+                    _ if para.content.is_empty() => (),
+                    _ => {
+                        content.insert(index, para.into());
+                        index += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn table(&mut self, table: &mut Table) {
+        let matches = match &table.property.change {
+            Some(change) => self.matches(&change.id),
+            None => false,
+        };
+
+        if matches {
+            let change = table.property.change.take().unwrap();
+            if self.action == Action::Reject {
+                table.property = *change.property;
+            }
+        }
+
+        self.rows(&mut table.rows);
+
+        for row in table.iter_rows_mut() {
+            for cell in row.iter_cells_mut() {
+                self.cell(cell);
+            }
+        }
+    }
+
+    fn rows(&mut self, rows: &mut Vec<TableContent>) {
+        rows.retain_mut(|content| match content {
+            TableContent::Row(row) => !self.row_mark(row),
+            TableContent::Sdt(sdt) => {
+                self.rows(&mut sdt.content.content);
+                true
+            }
+        });
+    }
+
+    fn cell(&mut self, cell: &mut TableCell) {
+        let mut index = 0;
+        while index < cell.content.len() {
+            let merge = match &mut cell.content[index] {
+                TableCellContent::Paragraph(para) => {
+                    self.paragraph(para);
+                    // the last paragraph of a cell is always kept
+                    self.mark(para) && index + 1 < cell.content.len()
+                }
+                TableCellContent::Sdt(sdt) => {
+                    self.body(&mut sdt.content.content);
+                    false
+                }
+                TableCellContent::Table(table) => {
+                    self.table(table);
+                    false
+                }
+            };
+
+            if !merge {
+                index += 1;
+                continue;
+            }
+
+            if let TableCellContent::Paragraph(para) = cell.content.remove(index) {
+                match &mut cell.content[index] {
+                    TableCellContent::Paragraph(next) => {
+                        next.content.splice(0..0, para.content);
+                    }
+                    _ if para.content.is_empty() => (),
+                    _ => {
+                        cell.content.insert(index, para.into());
+                        index += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn paragraph(&mut self, para: &mut Paragraph) {
+        let matches = match &para.property.change {
+            Some(change) => self.matches(&change.id),
+            None => false,
+        };
+
+        if matches {
+            let change = para.property.change.take().unwrap();
+            if self.action == Action::Reject {
+                let mark = para.property.mark.take();
+                para.property = *change.property;
+                para.property.mark = mark;
+            }
+        }
+
+        self.content(&mut para.content);
+    }
+
+    /// Resolves the revisions of the table row, returns `true` if the row is
+    /// removed.
+    fn row_mark(&mut self, row: &mut TableRow) -> bool {
+        let mut removed = false;
+
+        if let Some(ins) = &row.property.insertion {
+            if self.matches(&ins.id) {
+                row.property.insertion = None;
+                removed |= self.action == Action::Reject;
+            }
+        }
+
+        if let Some(del) = &row.property.deletion {
+            if self.matches(&del.id) {
+                row.property.deletion = None;
+                removed |= self.action == Action::Accept;
+            }
+        }
+
+        removed
+    }
+
+    /// Resolves the revisions of the paragraph mark, returns `true` if the
+    /// mark is removed.
+    fn mark(&mut self, para: &mut Paragraph) -> bool {
+        let mark = match &mut para.property.mark {
+            Some(mark) => mark,
+            None => return false,
+        };
+
+        let mut removed = false;
+
+        if let Some(ins) = &mark.insertion {
+            if self.matches(&ins.id) {
+                mark.insertion = None;
+                removed |= self.action == Action::Reject;
+            }
+        }
+
+        if let Some(del) = &mark.deletion {
+            if self.matches(&del.id) {
+                mark.deletion = None;
+                removed |= self.action == Action::Accept;
+            }
+        }
+
+        if mark.insertion.is_none() && mark.deletion.is_none() {
+            para.property.mark = None;
+        }
+
+        removed
+    }
+
+    fn run(&mut self, run: &mut Run) {
+        let matches = match &run.property.change {
+            Some(change) => self.matches(&change.id),
+            None => false,
+        };
+
+        if matches {
+            let change = run.property.change.take().unwrap();
+            if self.action == Action::Reject {
+                run.property = *change.property;
+            }
+        }
+    }
+
+    fn content<'a>(&mut self, content: &mut Vec<ParagraphContent<'a>>) {
+        for item in mem::take(content) {
+            // the content of a matching revision is either unwrapped into
+            // its parent or dropped together with the revision
+            let (unwrap, mut inner) = match item {
+                ParagraphContent::Insertion(ins) if self.matches(&ins.id) => {
+                    (self.action == Action::Accept, ins.content)
+                }
+                ParagraphContent::MoveTo(to) if self.matches(&to.id) => {
+                    (self.action == Action::Accept, to.content)
+                }
+                ParagraphContent::Deletion(del) if self.matches(&del.id) => {
+                    (self.action == Action::Reject, del.content)
+                }
+                ParagraphContent::MoveFrom(from) if self.matches(&from.id) => {
+                    (self.action == Action::Reject, from.content)
+                }
+                mut other => {
+                    match &mut other {
+                        ParagraphContent::Run(run) => self.run(run),
+                        ParagraphContent::Link(link) => {
+                            if let Some(run) = &mut link.content {
+                                self.run(run);
+                            }
+                        }
+                        ParagraphContent::SmartTag(tag) => self.content(&mut tag.content),
+                        ParagraphContent::SimpleField(field) => self.content(&mut field.content),
+                        ParagraphContent::Sdt(sdt) => self.content(&mut sdt.content.content),
+                        ParagraphContent::Insertion(Insertion { content, .. })
+                        | ParagraphContent::Deletion(Deletion { content, .. })
+                        | ParagraphContent::MoveFrom(MoveFrom { content, .. })
+                        | ParagraphContent::MoveTo(MoveTo { content, .. }) => self.content(content),
+                        _ => (),
+                    }
+                    content.push(other);
+                    continue;
+                }
+            };
+
+            if unwrap {
+                restore_deleted_text(&mut inner);
+                self.content(&mut inner);
+                content.extend(inner);
+            }
+        }
+    }
+}
+
+/// Turns deleted text back into regular text.
+fn restore_deleted_text(content: &mut [ParagraphContent]) {
+    fn restore_run(run: &mut Run) {
+        for content in &mut run.content {
+            if let RunContent::DeletedText(text) = content {
+                *content = RunContent::Text(Text {
+                    space: text.space.take(),
+                    text: mem::take(&mut text.text),
+                });
+            }
+        }
+    }
+
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => restore_run(run),
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &mut link.content {
+                    restore_run(run);
+                }
+            }
+            ParagraphContent::SmartTag(tag) => restore_deleted_text(&mut tag.content),
+            ParagraphContent::SimpleField(field) => restore_deleted_text(&mut field.content),
+            ParagraphContent::Sdt(sdt) => restore_deleted_text(&mut sdt.content.content),
+            _ => (),
+        }
+    }
+}
+
+/// Turns the text of runs into deleted text.
+pub(crate) fn mark_deleted(content: &mut ParagraphContent) {
+    fn mark_run(run: &mut Run) {
+        for content in &mut run.content {
+            if let RunContent::Text(text) = content {
+                *content = RunContent::DeletedText(DeletedText {
+                    space: text.space.take(),
+                    text: mem::take(&mut text.text),
+                });
+            }
+        }
+    }
+
+    match content {
+        ParagraphContent::Run(run) => mark_run(run),
+        ParagraphContent::Link(link) => {
+            if let Some(run) = &mut link.content {
+                mark_run(run);
+            }
+        }
+        ParagraphContent::SmartTag(tag) => tag.content.iter_mut().for_each(mark_deleted),
+        ParagraphContent::SimpleField(field) => field.content.iter_mut().for_each(mark_deleted),
+        ParagraphContent::Sdt(sdt) => sdt.content.content.iter_mut().for_each(mark_deleted),
+        ParagraphContent::Insertion(ins) => ins.content.iter_mut().for_each(mark_deleted),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatting::{CharacterProperty, CharacterPropertyChange};
+
+    fn sample() -> Document<'static> {
+        let mut document = Document::default();
+        document.push(
+            Paragraph::default()
+                .push_text("The ")
+                .push(
+                    Deletion::new("1")
+                        .author("Alice")
+                        .push(Run::default().push(DeletedText::from("quick "))),
+                )
+                .push(
+                    Insertion::new("2")
+                        .author("Bob")
+                        .push(Run::default().push_text("slow ")),
+                )
+                .push(
+                    Run::default()
+                        .property(
+                            CharacterProperty::default().bold(true).change(
+                                CharacterPropertyChange::new("3", CharacterProperty::default())
+                                    .author("Alice"),
+                            ),
+                        )
+                        .push_text("fox"),
+                ),
+        );
+        document
+    }
+
+    fn text(document: &Document) -> String {
+        match &document.body.content[0] {
+            BodyContent::Paragraph(para) => para.iter_text().map(|t| &**t).collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn list_revisions() {
+        let document = sample();
+        let revisions = document.revisions();
+
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[0].kind, RevisionKind::Deletion);
+        assert_eq!(revisions[0].text, "quick ");
+        assert_eq!(revisions[1].kind, RevisionKind::Insertion);
+        assert_eq!(revisions[2].kind, RevisionKind::CharacterProperty);
+        assert_eq!(document.revisions_by("Alice").len(), 2);
+    }
+
+    #[test]
+    fn accept_and_reject_all() {
+        let mut document = sample();
+        document.accept_all();
+        assert_eq!(text(&document), "The slow fox");
+        assert!(document.revisions().is_empty());
+
+        let mut document = sample();
+        document.reject_all();
+        assert_eq!(text(&document), "The quick fox");
+        assert!(document.revisions().is_empty());
+    }
+
+    #[test]
+    fn resolve_single_revision() {
+        let mut document = sample();
+
+        assert!(document.reject_revision("3"));
+        assert!(!document.reject_revision("3"));
+        assert!(document.accept_revision("1"));
+
+        assert_eq!(text(&document), "The slow fox");
+        assert_eq!(document.revisions().len(), 1);
+
+        match &document.body.content[0] {
+            BodyContent::Paragraph(para) => match para.content.last() {
+                Some(ParagraphContent::Run(run)) => assert!(run.property.bold.is_none()),
+                _ => panic!("expected a run"),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn marked() -> Document<'static> {
+        use crate::formatting::{ParagraphMarkProperty, ParagraphProperty};
+
+        let mut document = Document::default();
+        document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().mark(
+                    ParagraphMarkProperty::default().deletion(Deletion::new("1").author("Alice")),
+                ))
+                .push_text("first "),
+        );
+        document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().mark(
+                    ParagraphMarkProperty::default().insertion(Insertion::new("2").author("Bob")),
+                ))
+                .push(Insertion::new("2").push(Run::default().push_text("inserted"))),
+        );
+        document.push(Paragraph::default().push_text("second"));
+        document
+    }
+
+    #[test]
+    fn resolve_paragraph_marks() {
+        let mut document = marked();
+        assert_eq!(document.revisions_by("Alice").len(), 1);
+
+        document.reject_all();
+        assert_eq!(document.body.content.len(), 2);
+        assert_eq!(text(&document), "first ");
+
+        let mut document = marked();
+        document.accept_all();
+        assert_eq!(document.body.content.len(), 2);
+        assert_eq!(text(&document), "first inserted");
+        assert!(document.revisions().is_empty());
+    }
+
+    #[test]
+    fn resolve_table_rows() {
+        use crate::document::TableCell;
+        use crate::formatting::TableRowProperty;
+
+        let table = || {
+            Table::default()
+                .push_row(
+                    TableRow::default()
+                        .property(TableRowProperty::default().insertion(Insertion::new("1")))
+                        .push_cell(Paragraph::default().push_text("inserted")),
+                )
+                .push_row(
+                    TableRow::default()
+                        .property(TableRowProperty::default().deletion(Deletion::new("2")))
+                        .push_cell(TableCell::from(Paragraph::default().push_text("deleted"))),
+                )
+        };
+
+        let mut document = Document::default();
+        document.push(table());
+        assert_eq!(document.revisions().len(), 2);
+        assert!(document.reject_revision("1"));
+        assert!(document.accept_revision("2"));
+        // both rows are gone, and so is the table
+        assert!(document.body.content.is_empty());
+
+        let mut document = Document::default();
+        document.push(table());
+        document.accept_all();
+        match &document.body.content[0] {
+            BodyContent::Table(table) => assert_eq!(table.rows.len(), 1),
+            _ => panic!("expected a table"),
+        }
+        assert!(document.revisions().is_empty());
+    }
+}
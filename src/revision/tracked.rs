@@ -0,0 +1,584 @@
+use std::mem;
+
+use crate::{
+    comments::Comment,
+    document::{
+        BookmarkEnd, BookmarkStart, CommentRangeEnd, CommentRangeStart, CommentReference, Deletion,
+        Document, Insertion, MoveFrom, MoveTo, ParagraphContent, Run, Text,
+    },
+    find,
+    formatting::{
+        CharacterProperty, CharacterPropertyChange, ParagraphProperty, ParagraphPropertyChange,
+        TableProperty, TableRowProperty,
+    },
+    private_prelude::*,
+    range::{self, Position, Range, RangeError},
+    settings::Settings,
+    visit::{walk, Visit},
+    Docx,
+};
+
+use super::mark_deleted;
+
+/// Tracked Editing Mode
+///
+/// Edits made through this type are recorded as revisions of the given author
+/// and date, instead of changing the document silently. Text is addressed by
+/// [`Position`]s and [`Range`]s, so paragraphs inside tables and content
+/// controls can be edited at any character.
+///
+/// ```rust
+/// use docx::document::*;
+/// use docx::formatting::*;
+/// use docx::range::{Position, Range};
+/// use docx::Docx;
+///
+/// let mut docx = Docx::default();
+/// docx.document.push(Paragraph::default().push_text("Hello world"));
+///
+/// let mut tracked = docx.track_changes("Bot", "2020-01-01T00:00:00Z");
+/// tracked.insert_text(&Position::new(vec![0], 5), ", dear").unwrap();
+///
+/// // make "Hello" bold
+/// let hello = Range::new(Position::new(vec![0], 0), Position::new(vec![0], 5));
+/// tracked
+///     .set_character_property(&hello, CharacterProperty::default().bold(true))
+///     .unwrap();
+/// ```
+///
+/// [`Position`]: ../range/struct.Position.html
+/// [`Range`]: ../range/struct.Range.html
+pub struct TrackedDocument<'d, 'a> {
+    document: &'d mut Document<'a>,
+    author: Cow<'a, str>,
+    date: Cow<'a, str>,
+    next_id: usize,
+}
+
+impl<'a> Document<'a> {
+    /// Starts editing the document as tracked changes.
+    pub fn track_changes<A, D>(&mut self, author: A, date: D) -> TrackedDocument<'_, 'a>
+    where
+        A: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        let mut ids = AnnotationIds::default();
+        ids.visit_document(self);
+        let next_id = ids.next;
+
+        TrackedDocument {
+            document: self,
+            author: author.into(),
+            date: date.into(),
+            next_id,
+        }
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Turns on `w:trackRevisions` and starts editing the main document as tracked changes.
+    ///
+    /// The ids of the revisions follow the ids of the annotations of all
+    /// parts, which share them.
+    pub fn track_changes<A, D>(&mut self, author: A, date: D) -> TrackedDocument<'_, 'a>
+    where
+        A: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .track_revisions = Some(true.into());
+
+        let mut ids = AnnotationIds::default();
+        self.visit(&mut ids);
+
+        let mut tracked = self.document.track_changes(author, date);
+        tracked.next_id = tracked.next_id.max(ids.next);
+        tracked
+    }
+}
+
+impl<'d, 'a> TrackedDocument<'d, 'a> {
+    /// Allocates the id of a revision after it has been applied.
+    fn next_id(&mut self) -> String {
+        let id = self.next_id;
+        self.next_id += 1;
+        id.to_string()
+    }
+
+    /// Inserts a run at a position as an insertion, splitting the run there.
+    ///
+    /// Returns the id of the revision.
+    pub fn insert_run(&mut self, pos: &Position, run: Run<'a>) -> Result<String, RangeError> {
+        let ins = Insertion::new(self.next_id.to_string())
+            .author(self.author.clone())
+            .date(self.date.clone())
+            .push(run);
+
+        self.document.insert_at(pos, false, ins.into())?;
+
+        Ok(self.next_id())
+    }
+
+    /// Inserts a text at a position as an insertion.
+    ///
+    /// Returns the id of the revision.
+    pub fn insert_text<T: Into<Text<'a>>>(
+        &mut self,
+        pos: &Position,
+        text: T,
+    ) -> Result<String, RangeError> {
+        self.insert_run(pos, Run::default().push_text(text))
+    }
+
+    /// Marks the text of a range as deleted. The marks of the paragraphs
+    /// before the end of the range are deleted as well, so that accepting the
+    /// deletion merges them.
+    ///
+    /// Returns the id of the revision.
+    pub fn delete(&mut self, range: &Range) -> Result<String, RangeError> {
+        let Range { start, end } = range;
+        if !range.is_single_paragraph() {
+            let depth = start.path.len();
+            if depth != end.path.len() || start.path[..depth - 1] != end.path[..depth - 1] {
+                return Err(RangeError::DifferentContainers);
+            }
+        }
+
+        let id = self.next_id.to_string();
+        let del = Deletion::new(id.clone())
+            .author(self.author.clone())
+            .date(self.date.clone());
+
+        self.document.edit_runs(range, &mut |content| {
+            let mut deleted = mem::replace(content, ParagraphContent::Run(Run::default()));
+            mark_deleted(&mut deleted);
+            *content = del.clone().push(deleted).into();
+        })?;
+
+        find::visit_paragraphs_mut(
+            &mut self.document.body.content,
+            &mut Vec::new(),
+            &mut |path, para| {
+                if path < &start.path[..] || path > &end.path[..] {
+                    return;
+                }
+                merge_deletions(&mut para.content, &id);
+                if path != &end.path[..] {
+                    para.property
+                        .mark
+                        .get_or_insert_with(Default::default)
+                        .deletion = Some(del.clone());
+                }
+            },
+        );
+
+        Ok(self.next_id())
+    }
+
+    /// Replaces the properties of the runs of a range, recording the previous
+    /// ones.
+    ///
+    /// Returns the id of the revision.
+    pub fn set_character_property(
+        &mut self,
+        range: &Range,
+        property: CharacterProperty<'a>,
+    ) -> Result<String, RangeError> {
+        let change = CharacterPropertyChange::new(self.next_id.to_string(), Default::default())
+            .author(self.author.clone())
+            .date(self.date.clone());
+
+        self.document.edit_runs(range, &mut |content| {
+            let run = match content {
+                ParagraphContent::Run(run) => run,
+                ParagraphContent::Link(link) => match &mut link.content {
+                    Some(run) => run,
+                    None => return,
+                },
+                _ => return,
+            };
+
+            let mut old = mem::replace(&mut run.property, property.clone());
+            // keep the original properties if the run was already changed
+            let previous = match old.change.take() {
+                Some(change) => *change.property,
+                None => old,
+            };
+            let mut change = change.clone();
+            *change.property = previous;
+            run.property.change = Some(change);
+        })?;
+
+        Ok(self.next_id())
+    }
+
+    /// Replaces the properties of the paragraph at a path, recording the
+    /// previous ones.
+    ///
+    /// Returns the id of the revision.
+    pub fn set_paragraph_property(
+        &mut self,
+        path: &[usize],
+        property: ParagraphProperty<'a>,
+    ) -> Result<String, RangeError> {
+        let para = self
+            .document
+            .paragraph_at_mut(path)
+            .ok_or_else(|| RangeError::InvalidPosition(Position::new(path.to_vec(), 0)))?;
+
+        let mut old = mem::replace(&mut para.property, property);
+        // the section break and the paragraph mark are not part of the change
+        if para.property.section.is_none() {
+            para.property.section = old.section.take();
+        }
+        if para.property.mark.is_none() {
+            para.property.mark = old.mark.take();
+        }
+        // keep the original properties if the paragraph was already changed
+        let mut previous = match old.change.take() {
+            Some(change) => *change.property,
+            None => old,
+        };
+        previous.section = None;
+        previous.mark = None;
+        previous.change = None;
+        para.property.change = Some(
+            ParagraphPropertyChange::new(self.next_id.to_string(), previous)
+                .author(self.author.clone())
+                .date(self.date.clone()),
+        );
+
+        Ok(self.next_id())
+    }
+}
+
+/// Finds the id following the greatest one of the annotations, i.e.
+/// bookmarks, comments and revisions, which share the `w:id` space.
+#[derive(Default)]
+struct AnnotationIds {
+    next: usize,
+}
+
+impl AnnotationIds {
+    fn add(&mut self, id: &str) {
+        if let Ok(id) = id.parse::<usize>() {
+            self.next = self.next.max(id + 1);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for AnnotationIds {
+    fn visit_comment(&mut self, comment: &Comment<'a>) {
+        self.add(&comment.id);
+        walk::comment(self, comment);
+    }
+
+    fn visit_paragraph_property(&mut self, property: &ParagraphProperty<'a>) {
+        if let Some(change) = &property.change {
+            self.add(&change.id);
+        }
+        if let Some(mark) = &property.mark {
+            if let Some(ins) = &mark.insertion {
+                self.add(&ins.id);
+            }
+            if let Some(del) = &mark.deletion {
+                self.add(&del.id);
+            }
+        }
+    }
+
+    fn visit_table_property(&mut self, property: &TableProperty<'a>) {
+        if let Some(change) = &property.change {
+            self.add(&change.id);
+        }
+    }
+
+    fn visit_table_row_property(&mut self, property: &TableRowProperty<'a>) {
+        if let Some(ins) = &property.insertion {
+            self.add(&ins.id);
+        }
+        if let Some(del) = &property.deletion {
+            self.add(&del.id);
+        }
+    }
+
+    fn visit_character_property(&mut self, property: &CharacterProperty<'a>) {
+        if let Some(change) = &property.change {
+            self.add(&change.id);
+        }
+    }
+
+    fn visit_insertion(&mut self, ins: &Insertion<'a>) {
+        self.add(&ins.id);
+        walk::insertion(self, ins);
+    }
+
+    fn visit_deletion(&mut self, del: &Deletion<'a>) {
+        self.add(&del.id);
+        walk::deletion(self, del);
+    }
+
+    fn visit_move_from(&mut self, from: &MoveFrom<'a>) {
+        self.add(&from.id);
+        walk::move_from(self, from);
+    }
+
+    fn visit_move_to(&mut self, to: &MoveTo<'a>) {
+        self.add(&to.id);
+        walk::move_to(self, to);
+    }
+
+    fn visit_bookmark_start(&mut self, bookmark: &BookmarkStart<'a>) {
+        if let Some(id) = &bookmark.id {
+            self.add(id);
+        }
+    }
+
+    fn visit_bookmark_end(&mut self, bookmark: &BookmarkEnd<'a>) {
+        if let Some(id) = &bookmark.id {
+            self.add(id);
+        }
+    }
+
+    fn visit_comment_range_start(&mut self, range: &CommentRangeStart<'a>) {
+        self.add(&range.id);
+    }
+
+    fn visit_comment_range_end(&mut self, range: &CommentRangeEnd<'a>) {
+        self.add(&range.id);
+    }
+
+    fn visit_comment_reference(&mut self, reference: &CommentReference<'a>) {
+        self.add(&reference.id);
+    }
+}
+
+/// Merges the adjacent deletions of a revision, which are made one per run.
+fn merge_deletions(content: &mut Vec<ParagraphContent>, id: &str) {
+    let mut index = 0;
+    while index < content.len() {
+        if let Some(inner) = range::container_mut(&mut content[index]) {
+            merge_deletions(inner, id);
+        }
+
+        if index > 0 {
+            if let [ParagraphContent::Deletion(prev), ParagraphContent::Deletion(next)] =
+                &mut content[index - 1..=index]
+            {
+                if prev.id == id && next.id == id {
+                    prev.content.append(&mut next.content);
+                    content.remove(index);
+                    continue;
+                }
+            }
+        }
+
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{
+            BookmarkEnd, BookmarkStart, CommentRangeStart, Paragraph, Run, Table, TableCell,
+            TableRow,
+        },
+        formatting::{
+            CharacterProperty, ParagraphMarkProperty, ParagraphProperty, SectionProperty,
+            SectionType,
+        },
+        header_footer::Header,
+        range::{Position, Range, RangeError},
+        revision::RevisionKind,
+        Docx,
+    };
+
+    fn text(docx: &Docx, path: &[usize]) -> String {
+        let para = docx.document.paragraph_at(path).unwrap();
+        para.iter_text().map(|t| &**t).collect()
+    }
+
+    fn range(start: (&[usize], usize), end: (&[usize], usize)) -> Range {
+        Range::new(
+            Position::new(start.0.to_vec(), start.1),
+            Position::new(end.0.to_vec(), end.1),
+        )
+    }
+
+    #[test]
+    fn tracked_edits() {
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .push_text("The ")
+                .push_text("quick ")
+                .push(Run::default().push_text("fox")),
+        );
+
+        let mut tracked = docx.track_changes("Bot", "2020-01-01T00:00:00Z");
+        assert_eq!(tracked.delete(&range((&[0], 4), (&[0], 10))).unwrap(), "0");
+        assert_eq!(
+            tracked
+                .insert_text(&Position::new(vec![0], 4), "slow ")
+                .unwrap(),
+            "1"
+        );
+        assert_eq!(
+            tracked
+                .set_character_property(
+                    &range((&[0], 11), (&[0], 12)),
+                    CharacterProperty::default().bold(true)
+                )
+                .unwrap(),
+            "2"
+        );
+        assert_eq!(
+            tracked
+                .set_paragraph_property(&[0], ParagraphProperty::default().style_id("Quote"))
+                .unwrap(),
+            "3"
+        );
+        assert_eq!(
+            tracked.insert_text(&Position::new(vec![1], 0), "out of bounds"),
+            Err(RangeError::InvalidPosition(Position::new(vec![1], 0)))
+        );
+
+        assert!(docx.settings.as_ref().unwrap().track_revisions.is_some());
+        assert_eq!(text(&docx, &[0]), "The slow fox");
+
+        let kinds: Vec<_> = docx
+            .document
+            .revisions_by("Bot")
+            .iter()
+            .map(|revision| revision.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                RevisionKind::ParagraphProperty,
+                RevisionKind::Insertion,
+                RevisionKind::Deletion,
+                RevisionKind::CharacterProperty,
+            ]
+        );
+
+        docx.document.reject_all();
+        assert_eq!(text(&docx, &[0]), "The quick fox");
+
+        // ids continue after the existing revisions
+        let mut docx = Docx::default();
+        docx.document.push(Paragraph::default().push_text("text"));
+        let start = Position::new(vec![0], 0);
+        docx.track_changes("A", "2020")
+            .insert_text(&start, "a")
+            .unwrap();
+        assert_eq!(
+            docx.track_changes("B", "2020")
+                .insert_text(&start, "b")
+                .unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn tracked_edits_in_tables() {
+        let mut docx = Docx::default();
+        let mut cell = TableCell::from(Paragraph::default().push_text("first"));
+        cell.content
+            .push(Paragraph::default().push_text("second").into());
+        docx.document
+            .push(Table::default().push_row(TableRow::default().push_cell(cell)));
+
+        let mut tracked = docx.track_changes("Bot", "2020");
+        let id = tracked
+            .delete(&range((&[0, 0, 0, 0], 3), (&[0, 0, 0, 1], 3)))
+            .unwrap();
+        // the deleted text doesn't count in offsets
+        tracked
+            .insert_text(&Position::new(vec![0, 0, 0, 1], 0), "!")
+            .unwrap();
+
+        // the deleted text is kept and the first mark is deleted
+        assert_eq!(text(&docx, &[0, 0, 0, 0]), "fir");
+        assert_eq!(text(&docx, &[0, 0, 0, 1]), "!ond");
+        let para = docx.document.paragraph_at(&[0, 0, 0, 0]).unwrap();
+        let mark = para.property.mark.as_ref().unwrap();
+        assert_eq!(mark.deletion.as_ref().unwrap().id, id);
+
+        docx.document.accept_all();
+        assert_eq!(text(&docx, &[0, 0, 0, 0]), "fir!ond");
+    }
+
+    #[test]
+    fn keep_sections_in_paragraph_changes() {
+        let section = SectionProperty::default()
+            .ty(SectionType::NextPage)
+            .page_size((12240usize, 15840usize));
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .property(
+                    ParagraphProperty::default()
+                        .style_id("Body")
+                        .mark(ParagraphMarkProperty::default())
+                        .section(section.clone()),
+                )
+                .push_text("end of section"),
+        );
+
+        let mut tracked = docx.track_changes("Bot", "2020");
+        tracked
+            .set_paragraph_property(&[0], ParagraphProperty::default().style_id("Quote"))
+            .unwrap();
+        tracked
+            .set_paragraph_property(&[0], ParagraphProperty::default().style_id("Title"))
+            .unwrap();
+
+        let property = &docx.document.paragraph_at(&[0]).unwrap().property;
+        assert_eq!(property.section.as_ref(), Some(&section));
+        assert!(property.mark.is_some());
+        assert_eq!(property.style_id.as_ref().unwrap().value, "Title");
+
+        // only the properties of the paragraph itself are recorded
+        let change = property.change.as_ref().unwrap();
+        assert_eq!(change.property.style_id.as_ref().unwrap().value, "Body");
+        assert!(change.property.section.is_none());
+        assert!(change.property.mark.is_none());
+        assert!(change.property.change.is_none());
+    }
+
+    #[test]
+    fn ids_follow_all_annotations() {
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .push(BookmarkStart::default().id("4").name("start"))
+                .push(CommentRangeStart::default().id("6"))
+                .push_text("text")
+                .push(BookmarkEnd::default().id("4")),
+        );
+        let mut header = Header::default();
+        header.push(
+            Paragraph::default()
+                .push(BookmarkStart::default().id("11").name("header"))
+                .push(BookmarkEnd::default().id("11")),
+        );
+        docx.headers.insert("word/header1.xml".into(), header);
+
+        // the document alone only knows the ids of the body
+        let id = docx
+            .document
+            .track_changes("Bot", "2020")
+            .insert_text(&Position::new(vec![0], 0), "body ")
+            .unwrap();
+        assert_eq!(id, "7");
+
+        let id = docx
+            .track_changes("Bot", "2020")
+            .insert_text(&Position::new(vec![0], 0), "new ")
+            .unwrap();
+        assert_eq!(id, "12");
+    }
+}
@@ -6,8 +6,32 @@ pub const SCHEMA_OFFICE_DOCUMENT: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
 pub const SCHEMA_FONT_TABLE: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable";
+pub const SCHEMA_SETTINGS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings";
 pub const SCHEMA_STYLES: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles";
+pub const SCHEMA_NUMBERING: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering";
+pub const SCHEMA_COMMENTS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+pub const SCHEMA_FOOTNOTES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes";
+pub const SCHEMA_ENDNOTES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/endnotes";
+pub const SCHEMA_HEADER: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/header";
+pub const SCHEMA_FOOTER: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer";
+pub const SCHEMA_HYPERLINK: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+pub const SCHEMA_IMAGE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+pub const SCHEMA_CUSTOM_XML: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml";
+pub const SCHEMA_CUSTOM_XML_PROPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps";
+pub const SCHEMA_CUSTOM_XML_DATA_STORE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/customXml";
 pub const SCHEMA_DOC_PROPS_V_TYPES: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes";
 pub const SCHEMA_CONTENT_TYPES: &str =
@@ -17,3 +41,13 @@ pub const SCHEMA_RELATIONSHIPS: &str =
     "http://schemas.openxmlformats.org/package/2006/relationships";
 pub const SCHEMAS_EXTENDED: &str =
     "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+pub const SCHEMA_DOCUMENT_RELATIONSHIPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+pub const SCHEMA_MARKUP_COMPATIBILITY: &str =
+    "http://schemas.openxmlformats.org/markup-compatibility/2006";
+pub const SCHEMA_WORD_2010: &str = "http://schemas.microsoft.com/office/word/2010/wordml";
+pub const SCHEMA_WORD_2012: &str = "http://schemas.microsoft.com/office/word/2012/wordml";
+pub const SCHEMA_WORDPROCESSING_DRAWING: &str =
+    "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+pub const SCHEMA_DRAWINGML: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+pub const SCHEMA_PICTURE: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
@@ -0,0 +1,542 @@
+//! Document Settings part
+//!
+//! The corresponding ZIP item is `/word/settings.xml`.
+
+use std::io::Write;
+
+use hard_xml::{
+    xmlparser::{ElementEnd, Token},
+    XmlError, XmlReader,
+};
+
+use crate::private_prelude::*;
+use crate::schema::SCHEMA_MAIN;
+use crate::{__setter, __string_enum, __xml_test_suites};
+
+/// The root element of the document settings part.
+///
+/// The settings which aren't modeled are kept as read, so that a document
+/// loses none of them when it's written back.
+///
+/// ```rust
+/// use docx::settings::*;
+///
+/// let settings = Settings::default()
+///     .track_revisions(true)
+///     .update_fields(true);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct Settings<'a> {
+    /// Specifies that edits shall be tracked as revisions.
+    pub track_revisions: Option<TrackRevisions>,
+    /// Specifies the mail merge data source of the document.
+    pub mail_merge: Option<MailMergeSettings<'a>>,
+    /// Specifies that fields shall be updated when the document is opened.
+    pub update_fields: Option<UpdateFields>,
+    /// Specifies the attributes of the root element besides `xmlns:w`, e.g.
+    /// the namespaces used by the other settings.
+    pub attributes: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    /// Specifies the settings which aren't modeled, e.g. `w:defaultTabStop`
+    /// or `w:compat`.
+    pub others: Vec<RawElement<'a>>,
+}
+
+impl<'a> Settings<'a> {
+    __setter!(track_revisions: Option<TrackRevisions>);
+    __setter!(mail_merge: Option<MailMergeSettings<'a>>);
+    __setter!(update_fields: Option<UpdateFields>);
+}
+
+impl<'a> XmlRead<'a> for Settings<'a> {
+    fn from_reader(reader: &mut XmlReader<'a>) -> XmlResult<Self> {
+        log::debug!("[Settings] Started reading.");
+
+        let mut settings = Settings::default();
+
+        reader.read_till_element_start("w:settings")?;
+
+        while let Some((key, value)) = reader.find_attribute()? {
+            if key != "xmlns:w" {
+                settings.attributes.push((key.into(), value));
+            }
+        }
+
+        if let Some(Token::ElementEnd {
+            end: ElementEnd::Open,
+            ..
+        }) = reader.next().transpose()?
+        {
+            while let Some(tag) = reader.find_element_start(Some("w:settings"))? {
+                match tag {
+                    "w:trackRevisions" => {
+                        settings.track_revisions = Some(TrackRevisions::from_reader(reader)?)
+                    }
+                    "w:mailMerge" => {
+                        settings.mail_merge = Some(MailMergeSettings::from_reader(reader)?)
+                    }
+                    "w:updateFields" => {
+                        settings.update_fields = Some(UpdateFields::from_reader(reader)?)
+                    }
+                    tag => settings.others.push(RawElement {
+                        tag: tag.into(),
+                        xml: read_raw(reader)?.into(),
+                    }),
+                }
+            }
+        }
+
+        log::debug!("[Settings] Finished reading.");
+
+        Ok(settings)
+    }
+}
+
+impl<'a> XmlWrite for Settings<'a> {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        let Settings {
+            track_revisions,
+            mail_merge,
+            update_fields,
+            attributes,
+            others,
+        } = self;
+
+        log::debug!("[Settings] Started writing.");
+
+        // the children are written in the order of the schema, which Word
+        // enforces, the unknown ones last
+        let mut children: Vec<(usize, Cow<str>)> = others
+            .iter()
+            .map(|ele| (schema_position(&ele.tag), Cow::Borrowed(&*ele.xml)))
+            .collect();
+        if let Some(ele) = track_revisions {
+            children.push((schema_position("w:trackRevisions"), ele.to_string()?.into()));
+        }
+        if let Some(ele) = mail_merge {
+            children.push((schema_position("w:mailMerge"), ele.to_string()?.into()));
+        }
+        if let Some(ele) = update_fields {
+            children.push((schema_position("w:updateFields"), ele.to_string()?.into()));
+        }
+        children.sort_by_key(|(position, _)| *position);
+
+        writer.write_element_start("w:settings")?;
+
+        writer.write_attribute("xmlns:w", SCHEMA_MAIN)?;
+
+        for (key, value) in attributes {
+            writer.write_attribute(key, value)?;
+        }
+
+        if children.is_empty() {
+            writer.write_element_end_empty()?;
+        } else {
+            writer.write_element_end_open()?;
+            for (_, xml) in children {
+                writer.inner.write_all(xml.as_bytes())?;
+            }
+            writer.write_element_end_close("w:settings")?;
+        }
+
+        log::debug!("[Settings] Finished writing.");
+
+        Ok(())
+    }
+}
+
+/// An element which isn't modeled, kept as read
+#[derive(Clone, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct RawElement<'a> {
+    /// Specifies the qualified name of the element, e.g. `w:compat`.
+    pub tag: Cow<'a, str>,
+    /// Specifies the markup of the element.
+    pub xml: Cow<'a, str>,
+}
+
+/// Reads the markup of the next element.
+fn read_raw(reader: &mut XmlReader) -> XmlResult<String> {
+    let mut xml = String::new();
+    let mut depth = 0;
+
+    while let Some(token) = reader.next() {
+        let token = token?;
+        if let Token::Attribute { .. } = token {
+            xml.push(' ');
+        }
+        xml.push_str(token.span().as_str());
+
+        match token {
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                ..
+            } => depth += 1,
+            Token::ElementEnd {
+                end: ElementEnd::Close(..),
+                ..
+            } => depth -= 1,
+            Token::ElementEnd {
+                end: ElementEnd::Empty,
+                ..
+            } => (),
+            _ => continue,
+        }
+
+        if depth == 0 {
+            return Ok(xml);
+        }
+    }
+
+    Err(XmlError::UnexpectedEof)
+}
+
+/// The children of `w:settings`, in the order of the schema.
+const SCHEMA_ORDER: &[&str] = &[
+    "w:writeProtection",
+    "w:view",
+    "w:zoom",
+    "w:removePersonalInformation",
+    "w:removeDateAndTime",
+    "w:doNotDisplayPageBoundaries",
+    "w:displayBackgroundShape",
+    "w:printPostScriptOverText",
+    "w:printFractionalCharacterWidth",
+    "w:printFormsData",
+    "w:embedTrueTypeFonts",
+    "w:embedSystemFonts",
+    "w:saveSubsetFonts",
+    "w:saveFormsData",
+    "w:mirrorMargins",
+    "w:alignBordersAndEdges",
+    "w:bordersDoNotSurroundHeader",
+    "w:bordersDoNotSurroundFooter",
+    "w:gutterAtTop",
+    "w:hideSpellingErrors",
+    "w:hideGrammaticalErrors",
+    "w:activeWritingStyle",
+    "w:proofState",
+    "w:formsDesign",
+    "w:attachedTemplate",
+    "w:linkStyles",
+    "w:stylePaneFormatFilter",
+    "w:stylePaneSortMethod",
+    "w:documentType",
+    "w:mailMerge",
+    "w:revisionView",
+    "w:trackRevisions",
+    "w:doNotTrackMoves",
+    "w:doNotTrackFormatting",
+    "w:documentProtection",
+    "w:autoFormatOverride",
+    "w:styleLockTheme",
+    "w:styleLockQFSet",
+    "w:defaultTabStop",
+    "w:autoHyphenation",
+    "w:consecutiveHyphenLimit",
+    "w:hyphenationZone",
+    "w:doNotHyphenateCaps",
+    "w:showEnvelope",
+    "w:summaryLength",
+    "w:clickAndTypeStyle",
+    "w:defaultTableStyle",
+    "w:evenAndOddHeaders",
+    "w:bookFoldRevPrinting",
+    "w:bookFoldPrinting",
+    "w:bookFoldPrintingSheets",
+    "w:drawingGridHorizontalSpacing",
+    "w:drawingGridVerticalSpacing",
+    "w:displayHorizontalDrawingGridEvery",
+    "w:displayVerticalDrawingGridEvery",
+    "w:doNotUseMarginsForDrawingGridOrigin",
+    "w:drawingGridHorizontalOrigin",
+    "w:drawingGridVerticalOrigin",
+    "w:doNotShadeFormData",
+    "w:noPunctuationKerning",
+    "w:characterSpacingControl",
+    "w:printTwoOnOne",
+    "w:strictFirstAndLastChars",
+    "w:noLineBreaksAfter",
+    "w:noLineBreaksBefore",
+    "w:savePreviewPicture",
+    "w:doNotValidateAgainstSchema",
+    "w:saveInvalidXml",
+    "w:ignoreMixedContent",
+    "w:alwaysShowPlaceholderText",
+    "w:doNotDemarcateInvalidXml",
+    "w:saveXmlDataOnly",
+    "w:useXSLTWhenSaving",
+    "w:saveThroughXslt",
+    "w:showXMLTags",
+    "w:alwaysMergeEmptyNamespace",
+    "w:updateFields",
+    "w:hdrShapeDefaults",
+    "w:footnotePr",
+    "w:endnotePr",
+    "w:compat",
+    "w:docVars",
+    "w:rsids",
+    "m:mathPr",
+    "w:attachedSchema",
+    "w:themeFontLang",
+    "w:clrSchemeMapping",
+    "w:doNotIncludeSubdocsInStats",
+    "w:doNotAutoCompressPictures",
+    "w:forceUpgrade",
+    "w:captions",
+    "w:readModeInkLockDown",
+    "w:smartTagType",
+    "sl:schemaLibrary",
+    "w:shapeDefaults",
+    "w:doNotEmbedSmartTags",
+    "w:decimalSymbol",
+    "w:listSeparator",
+];
+
+fn schema_position(tag: &str) -> usize {
+    SCHEMA_ORDER
+        .iter()
+        .position(|name| *name == tag)
+        .unwrap_or(SCHEMA_ORDER.len())
+}
+
+/// Track Revisions
+///
+/// Specifies that edits shall be tracked as revisions.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:trackRevisions")]
+pub struct TrackRevisions {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for TrackRevisions {
+    fn from(val: T) -> Self {
+        TrackRevisions { value: val.into() }
+    }
+}
+
+/// Mail Merge Settings
+///
+/// Specifies that the document is a mail merge main document and how it's
+/// connected to its data source.
+///
+/// ```rust
+/// use docx::settings::*;
+///
+/// let mail_merge = MailMergeSettings::new(MainDocumentType::FormLetters)
+///     .data_type(MailMergeDataType::Native)
+///     .query("SELECT * FROM `Sheet1$`");
+/// ```
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:mailMerge")]
+pub struct MailMergeSettings<'a> {
+    /// Specifies the kind of the main document.
+    #[xml(child = "w:mainDocumentType")]
+    pub main_document_type: MainDocumentTypeValue,
+    /// Specifies the kind of connection to the data source.
+    #[xml(child = "w:dataType")]
+    pub data_type: Option<MailMergeDataTypeValue>,
+    /// Specifies the connection string to the data source.
+    #[xml(child = "w:connectString")]
+    pub connect_string: Option<ConnectString<'a>>,
+    /// Specifies the query selecting the records of the data source.
+    #[xml(child = "w:query")]
+    pub query: Option<Query<'a>>,
+}
+
+impl<'a> MailMergeSettings<'a> {
+    pub fn new<T: Into<MainDocumentTypeValue>>(main_document_type: T) -> Self {
+        MailMergeSettings {
+            main_document_type: main_document_type.into(),
+            data_type: None,
+            connect_string: None,
+            query: None,
+        }
+    }
+
+    __setter!(data_type: Option<MailMergeDataTypeValue>);
+    __setter!(connect_string: Option<ConnectString<'a>>);
+    __setter!(query: Option<Query<'a>>);
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:mainDocumentType")]
+pub struct MainDocumentTypeValue {
+    #[xml(attr = "w:val")]
+    pub value: MainDocumentType,
+}
+
+impl From<MainDocumentType> for MainDocumentTypeValue {
+    fn from(value: MainDocumentType) -> Self {
+        MainDocumentTypeValue { value }
+    }
+}
+
+/// Specifies the kind of a mail merge main document
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MainDocumentType {
+    Catalog,
+    Envelopes,
+    MailingLabels,
+    FormLetters,
+    Email,
+    Fax,
+}
+
+__string_enum! {
+    MainDocumentType {
+        Catalog = "catalog",
+        Envelopes = "envelopes",
+        MailingLabels = "mailingLabels",
+        FormLetters = "formLetters",
+        Email = "email",
+        Fax = "fax",
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:dataType")]
+pub struct MailMergeDataTypeValue {
+    #[xml(attr = "w:val")]
+    pub value: MailMergeDataType,
+}
+
+impl From<MailMergeDataType> for MailMergeDataTypeValue {
+    fn from(value: MailMergeDataType) -> Self {
+        MailMergeDataTypeValue { value }
+    }
+}
+
+/// Specifies the kind of connection to a mail merge data source
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MailMergeDataType {
+    TextFile,
+    Database,
+    Spreadsheet,
+    Query,
+    Odbc,
+    Native,
+}
+
+__string_enum! {
+    MailMergeDataType {
+        TextFile = "textFile",
+        Database = "database",
+        Spreadsheet = "spreadsheet",
+        Query = "query",
+        Odbc = "odbc",
+        Native = "native",
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:connectString")]
+pub struct ConnectString<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for ConnectString<'a> {
+    fn from(val: T) -> Self {
+        ConnectString { value: val.into() }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:query")]
+pub struct Query<'a> {
+    #[xml(attr = "w:val")]
+    pub value: Cow<'a, str>,
+}
+
+impl<'a, T: Into<Cow<'a, str>>> From<T> for Query<'a> {
+    fn from(val: T) -> Self {
+        Query { value: val.into() }
+    }
+}
+
+/// Update Fields
+///
+/// Specifies that all fields shall be recalculated when the document is opened.
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
+#[xml(tag = "w:updateFields")]
+pub struct UpdateFields {
+    #[xml(attr = "w:val")]
+    pub value: Option<bool>,
+}
+
+impl<T: Into<Option<bool>>> From<T> for UpdateFields {
+    fn from(val: T) -> Self {
+        UpdateFields { value: val.into() }
+    }
+}
+
+__xml_test_suites!(
+    Settings,
+    Settings::default(),
+    format!(r#"<w:settings xmlns:w="{}"/>"#, SCHEMA_MAIN).as_str(),
+    Settings::default().update_fields(true),
+    format!(
+        r#"<w:settings xmlns:w="{}"><w:updateFields w:val="true"/></w:settings>"#,
+        SCHEMA_MAIN
+    )
+    .as_str(),
+    Settings::default().track_revisions(true),
+    format!(
+        r#"<w:settings xmlns:w="{}"><w:trackRevisions w:val="true"/></w:settings>"#,
+        SCHEMA_MAIN
+    )
+    .as_str(),
+    Settings::default().mail_merge(
+        MailMergeSettings::new(MainDocumentType::FormLetters)
+            .data_type(MailMergeDataType::TextFile)
+            .query("SELECT * FROM data.csv")
+    ),
+    format!(
+        r#"<w:settings xmlns:w="{}"><w:mailMerge><w:mainDocumentType w:val="formLetters"/><w:dataType w:val="textFile"/><w:query w:val="SELECT * FROM data.csv"/></w:mailMerge></w:settings>"#,
+        SCHEMA_MAIN
+    )
+    .as_str(),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_other_settings() -> XmlResult<()> {
+        let xml = format!(
+            r#"<w:settings xmlns:w="{}" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml" mc:Ignorable="w14"><w:zoom w:percent="100"/><w:proofState w:spelling="clean" w:grammar="clean"/><w:defaultTabStop w:val="720"/><w:characterSpacingControl w:val="doNotCompress"/><w:compat><w:compatSetting w:name="compatibilityMode" w:uri="http://schemas.microsoft.com/office/word" w:val="15"/></w:compat><w:rsids><w:rsidRoot w:val="00A1B2C3"/></w:rsids><w:themeFontLang w:val="en-US"/><w14:docId w14:val="1A2B3C4D"/></w:settings>"#,
+            SCHEMA_MAIN
+        );
+
+        let settings = Settings::from_str(&xml)?;
+        assert_eq!(settings.others.len(), 8);
+        assert_eq!(
+            settings.others[4].xml,
+            r#"<w:compat><w:compatSetting w:name="compatibilityMode" w:uri="http://schemas.microsoft.com/office/word" w:val="15"/></w:compat>"#
+        );
+        assert_eq!(settings.to_string()?, xml);
+
+        // modeled settings go to their place in the schema
+        let settings = settings.track_revisions(true).update_fields(true);
+        let written = settings.to_string()?;
+        let position = |tag: &str| written.find(tag).unwrap();
+        assert!(position("w:proofState") < position("w:trackRevisions"));
+        assert!(position("w:trackRevisions") < position("w:defaultTabStop"));
+        assert!(position("w:characterSpacingControl") < position("w:updateFields"));
+        assert!(position("w:updateFields") < position("w:compat"));
+        assert!(position("w:themeFontLang") < position("w14:docId"));
+        assert_eq!(Settings::from_str(&written)?, settings);
+
+        Ok(())
+    }
+}
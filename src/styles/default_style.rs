@@ -16,7 +16,7 @@ use crate::{
 ///     .character(CharacterProperty::default())
 ///     .paragraph(ParagraphProperty::default());
 /// ```
-#[derive(Debug, Default, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, Default, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:docDefaults")]
 pub struct DefaultStyle<'a> {
@@ -32,7 +32,7 @@ impl<'a> DefaultStyle<'a> {
 }
 
 /// Default Character Properties
-#[derive(Default, Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Default, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:rPrDefault")]
 pub struct DefaultCharacterProperty<'a> {
@@ -48,7 +48,7 @@ impl<'a, T: Into<CharacterProperty<'a>>> From<T> for DefaultCharacterProperty<'a
 }
 
 /// Default Paragraph Properties
-#[derive(Default, Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Default, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:pPrDefault")]
 pub struct DefaultParagraphProperty<'a> {
@@ -24,7 +24,7 @@ use crate::schema::SCHEMA_MAIN;
 ///     .default(DefaultStyle::default())
 ///     .push(Style::new(StyleType::Paragraph, "style_id"));
 /// ```
-#[derive(Debug, Default, XmlRead)]
+#[derive(Clone, Debug, Default, XmlRead)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:styles")]
 pub struct Styles<'a> {
@@ -77,6 +77,28 @@ impl<'a> Styles<'a> {
         self.styles.push(style);
         self
     }
+
+    /// Returns the style with the given style id.
+    pub fn get(&self, style_id: &str) -> Option<&Style<'a>> {
+        self.styles.iter().find(|style| style.style_id == style_id)
+    }
+
+    /// Returns the outline level of a paragraph style, following its `basedOn` chain.
+    pub fn outline_level(&self, style_id: &str) -> Option<usize> {
+        let mut style = self.get(style_id);
+        // guards against cyclic `basedOn` chains
+        for _ in 0..self.styles.len() {
+            let current = style?;
+            if let Some(level) = current.paragraph.outline_level {
+                return Some(level.value);
+            }
+            style = current
+                .based_on
+                .as_ref()
+                .and_then(|based_on| self.get(&based_on.value));
+        }
+        None
+    }
 }
 
 __xml_test_suites!(
@@ -92,7 +114,7 @@ __xml_test_suites!(
         ..Default::default()
     },
     format!(
-        r#"<w:styles xmlns:w="{}"><w:docDefaults><w:rPrDefault><w:rPr/></w:rPrDefault><w:pPrDefault><w:pPr/></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:styleId="id"><w:pPr/><w:rPr/></w:style></w:styles>"#,
+        r#"<w:styles xmlns:w="{}"><w:docDefaults><w:rPrDefault><w:rPr/></w:rPrDefault><w:pPrDefault><w:pPr/></w:pPrDefault></w:docDefaults><w:style w:styleId="id" w:type="paragraph"><w:pPr/><w:rPr/></w:style></w:styles>"#,
         SCHEMA_MAIN
     )
     .as_str(),
@@ -19,7 +19,7 @@ use crate::{
 ///     .paragraph(ParagraphProperty::default())
 ///     .character(CharacterProperty::default());
 /// ```
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:style")]
 pub struct Style<'a> {
@@ -69,19 +69,27 @@ impl<'a> Style<'a> {
     }
 
     __setter!(ty: StyleType);
+    __setter!(based_on: Option<BasedOn<'a>>);
     __setter!(name: Option<StyleName<'a>>);
     __setter!(paragraph: ParagraphProperty<'a>);
     __setter!(character: CharacterProperty<'a>);
 }
 
-#[derive(Debug, XmlRead, XmlWrite)]
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
+#[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:basedOn")]
 pub struct BasedOn<'a> {
     #[xml(attr = "w:val")]
     pub value: Cow<'a, str>,
 }
 
-#[derive(Debug, XmlRead, XmlWrite)]
+impl<'a, S: Into<Cow<'a, str>>> From<S> for BasedOn<'a> {
+    fn from(val: S) -> Self {
+        BasedOn { value: val.into() }
+    }
+}
+
+#[derive(Clone, Debug, XmlRead, XmlWrite)]
 #[cfg_attr(test, derive(PartialEq))]
 #[xml(tag = "w:name")]
 pub struct StyleName<'a> {
@@ -95,8 +103,7 @@ impl<'a, S: Into<Cow<'a, str>>> From<S> for StyleName<'a> {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StyleType {
     Character,
     Paragraph,
@@ -116,11 +123,13 @@ __string_enum! {
 __xml_test_suites!(
     Style,
     Style::new(StyleType::Numbering, "id"),
-    r#"<w:style w:type="numbering" w:styleId="id"><w:pPr/><w:rPr/></w:style>"#,
+    r#"<w:style w:styleId="id" w:type="numbering"><w:pPr/><w:rPr/></w:style>"#,
     Style::new(StyleType::Table, "id").name("name"),
-    r#"<w:style w:type="table" w:styleId="id"><w:name w:val="name"/><w:pPr/><w:rPr/></w:style>"#,
+    r#"<w:style w:styleId="id" w:type="table"><w:name w:val="name"/><w:pPr/><w:rPr/></w:style>"#,
     Style::new(StyleType::Paragraph, "id"),
-    r#"<w:style w:type="paragraph" w:styleId="id"><w:pPr/><w:rPr/></w:style>"#,
+    r#"<w:style w:styleId="id" w:type="paragraph"><w:pPr/><w:rPr/></w:style>"#,
     Style::new(StyleType::Character, "id"),
-    r#"<w:style w:type="character" w:styleId="id"><w:pPr/><w:rPr/></w:style>"#,
+    r#"<w:style w:styleId="id" w:type="character"><w:pPr/><w:rPr/></w:style>"#,
+    Style::new(StyleType::Paragraph, "id").based_on("base"),
+    r#"<w:style w:styleId="id" w:type="paragraph"><w:basedOn w:val="base"/><w:pPr/><w:rPr/></w:style>"#,
 );
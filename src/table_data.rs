@@ -0,0 +1,373 @@
+//! Table data extraction
+//!
+//! Converts tables to rectangular grids of cell text, merged cells being
+//! expanded into every position of the grid they cover, and writes them as
+//! CSV or JSON. Tables nested in cells are kept with the cell containing
+//! them, their rows being appended to its text.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::TableCellProperty;
+//! use docx::Docx;
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(Paragraph::default().push_text("Table 1: Revenue"));
+//! docx.document.push(
+//!     Table::default()
+//!         .push_row(
+//!             TableRow::default().push_cell(
+//!                 TableCell::from(Paragraph::default().push_text("2024"))
+//!                     .property(TableCellProperty::default().grid_span(2usize)),
+//!             ),
+//!         )
+//!         .push_row(
+//!             TableRow::default()
+//!                 .push_cell(Paragraph::default().push_text("Q1"))
+//!                 .push_cell(Paragraph::default().push_text("1,200")),
+//!         ),
+//! );
+//!
+//! for table in docx.document.iter_tables() {
+//!     assert_eq!(table.caption.as_deref(), Some("Table 1: Revenue"));
+//!     let data = table.table.to_rows();
+//!     assert_eq!(data.to_csv(','), "2024,2024\r\nQ1,\"1,200\"\r\n");
+//! }
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{
+    csv,
+    document::{BodyContent, Document, Paragraph, Table, TableCellContent},
+};
+
+/// The cells of a table, in a rectangular grid
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TableData {
+    /// The rows of the grid, all with the same number of cells.
+    pub rows: Vec<Vec<CellData>>,
+}
+
+/// A position of the grid of a table
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CellData {
+    /// The text of the cell, paragraphs and rows of nested tables being
+    /// separated by line breaks, and cells of nested tables by tabs.
+    ///
+    /// Positions covered by a merged cell repeat its text.
+    pub text: String,
+    /// The number of rows spanned by the cell.
+    pub row_span: usize,
+    /// The number of columns spanned by the cell.
+    pub column_span: usize,
+    /// The row and the column of the merged cell covering the position,
+    /// `None` for the position where the cell starts.
+    pub merged_into: Option<(usize, usize)>,
+    /// The tables nested in the cell.
+    pub tables: Vec<TableData>,
+}
+
+impl TableData {
+    /// Returns the text of the cells.
+    pub fn texts(&self) -> Vec<Vec<&str>> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.text.as_str()).collect())
+            .collect()
+    }
+
+    /// Writes the text of the cells as delimiter-separated values, e.g. CSV
+    /// or TSV.
+    pub fn to_csv(&self, delimiter: char) -> String {
+        csv::write(&self.texts(), delimiter)
+    }
+
+    /// Writes the grid as JSON, with the merges and the nested tables.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        // plain data can't fail to serialize
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl<'a> Table<'a> {
+    /// Returns the cells of the table in a rectangular grid.
+    ///
+    /// A cell spanning columns with `gridSpan` or rows with `vMerge` covers
+    /// several positions, which repeat its text and refer to the position
+    /// where it starts. Rows with fewer cells are completed with empty ones.
+    pub fn to_rows(&self) -> TableData {
+        let mut rows: Vec<Vec<CellData>> = Vec::new();
+
+        for row in self.iter_rows() {
+            let index = rows.len();
+            let mut cells: Vec<CellData> = Vec::new();
+
+            for cell in row.iter_cells() {
+                let column = cells.len();
+                let span = cell
+                    .property
+                    .grid_span
+                    .as_ref()
+                    .map_or(1, |span| span.value.max(1));
+
+                // the cell starting the vertical merge which this one continues
+                let origin = cell
+                    .property
+                    .vertical_merge
+                    .as_ref()
+                    .filter(|merge| !merge.is_restart())
+                    .and_then(|_| rows.last()?.get(column))
+                    .map(|above| above.merged_into.unwrap_or((index - 1, column)));
+
+                match origin {
+                    Some((row, column)) => {
+                        let origin = &mut rows[row][column];
+                        origin.row_span += 1;
+                        let text = origin.text.clone();
+                        cells.extend((0..span).map(|_| CellData {
+                            text: text.clone(),
+                            row_span: 1,
+                            column_span: 1,
+                            merged_into: Some((row, column)),
+                            tables: Vec::new(),
+                        }));
+                    }
+                    None => {
+                        let mut tables = Vec::new();
+                        let mut lines = Vec::new();
+                        cell_text(&cell.content, &mut lines, &mut tables);
+                        let text = lines.join("\n");
+                        cells.push(CellData {
+                            text: text.clone(),
+                            row_span: 1,
+                            column_span: span,
+                            merged_into: None,
+                            tables,
+                        });
+                        cells.extend((1..span).map(|_| CellData {
+                            text: text.clone(),
+                            row_span: 1,
+                            column_span: 1,
+                            merged_into: Some((index, column)),
+                            tables: Vec::new(),
+                        }));
+                    }
+                }
+            }
+
+            rows.push(cells);
+        }
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        for row in &mut rows {
+            row.resize_with(columns, || CellData {
+                row_span: 1,
+                column_span: 1,
+                ..Default::default()
+            });
+        }
+
+        TableData { rows }
+    }
+}
+
+/// Collects the lines of text and the nested tables of a cell.
+fn cell_text(content: &[TableCellContent], lines: &mut Vec<String>, tables: &mut Vec<TableData>) {
+    for content in content {
+        match content {
+            TableCellContent::Paragraph(para) => lines.push(paragraph_text(para)),
+            TableCellContent::Sdt(sdt) => block_text(&sdt.content.content, lines, tables),
+            TableCellContent::Table(table) => table_text(table, lines, tables),
+        }
+    }
+}
+
+fn block_text(content: &[BodyContent], lines: &mut Vec<String>, tables: &mut Vec<TableData>) {
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => lines.push(paragraph_text(para)),
+            BodyContent::Table(table) => table_text(table, lines, tables),
+            BodyContent::Sdt(sdt) => block_text(&sdt.content.content, lines, tables),
+        }
+    }
+}
+
+fn table_text(table: &Table, lines: &mut Vec<String>, tables: &mut Vec<TableData>) {
+    let data = table.to_rows();
+    lines.extend(data.texts().iter().map(|row| row.join("\t")));
+    tables.push(data);
+}
+
+fn paragraph_text(para: &Paragraph) -> String {
+    para.iter_text().map(|text| &**text).collect()
+}
+
+/// A table of a document, with its caption
+#[derive(Clone, Debug)]
+pub struct CaptionedTable<'t, 'a> {
+    pub table: &'t Table<'a>,
+    /// The text of the paragraph preceding the table, unless it's empty.
+    pub caption: Option<String>,
+}
+
+impl<'a> Document<'a> {
+    /// Iterates over the tables of the body in document order, including the
+    /// ones in content controls and the ones nested in cells, which follow
+    /// the table containing them.
+    ///
+    /// The caption of a nested table is the paragraph preceding it in its
+    /// cell.
+    pub fn iter_tables(&self) -> impl Iterator<Item = CaptionedTable<'_, 'a>> {
+        let mut tables = Vec::new();
+        collect_tables(&self.body.content, &mut tables);
+        tables.into_iter()
+    }
+}
+
+fn collect_tables<'t, 'a>(
+    content: &'t [BodyContent<'a>],
+    tables: &mut Vec<CaptionedTable<'t, 'a>>,
+) {
+    let mut previous = None;
+    for content in content {
+        match content {
+            BodyContent::Paragraph(para) => {
+                previous = Some(para);
+                continue;
+            }
+            BodyContent::Table(table) => collect_table(table, previous, tables),
+            BodyContent::Sdt(sdt) => collect_tables(&sdt.content.content, tables),
+        }
+        previous = None;
+    }
+}
+
+fn collect_table<'t, 'a>(
+    table: &'t Table<'a>,
+    previous: Option<&Paragraph>,
+    tables: &mut Vec<CaptionedTable<'t, 'a>>,
+) {
+    tables.push(CaptionedTable {
+        table,
+        caption: previous
+            .map(|para| paragraph_text(para).trim().to_owned())
+            .filter(|caption| !caption.is_empty()),
+    });
+
+    for cell in table.iter_rows().flat_map(|row| row.iter_cells()) {
+        let mut previous = None;
+        for content in &cell.content {
+            match content {
+                TableCellContent::Paragraph(para) => {
+                    previous = Some(para);
+                    continue;
+                }
+                TableCellContent::Sdt(sdt) => collect_tables(&sdt.content.content, tables),
+                TableCellContent::Table(nested) => collect_table(nested, previous, tables),
+            }
+            previous = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        document::{TableCell, TableRow},
+        formatting::{TableCellProperty, VerticalMergeVal},
+    };
+    use hard_xml::XmlRead;
+
+    fn cell(text: &'static str, property: TableCellProperty) -> TableCell<'static> {
+        TableCell::from(Paragraph::default().push_text(text)).property(property)
+    }
+
+    #[test]
+    fn to_rows() {
+        let nested = Table::default().push_row(
+            TableRow::default()
+                .push_cell(Paragraph::default().push_text("x"))
+                .push_cell(Paragraph::default().push_text("y")),
+        );
+        let table = Table::default()
+            .push_row(
+                TableRow::default()
+                    .push_cell(cell(
+                        "Region",
+                        TableCellProperty::default().vertical_merge(VerticalMergeVal::Restart),
+                    ))
+                    .push_cell(cell("Q1", TableCellProperty::default().grid_span(2usize))),
+            )
+            .push_row(
+                TableRow::default()
+                    .push_cell(cell(
+                        "",
+                        TableCellProperty::default().vertical_merge(VerticalMergeVal::Continue),
+                    ))
+                    .push_cell(Paragraph::default().push_text("Jan"))
+                    .push_cell(Paragraph::default().push_text("Feb")),
+            );
+
+        let data = table.to_rows();
+        assert_eq!(
+            data.texts(),
+            vec![vec!["Region", "Q1", "Q1"], vec!["Region", "Jan", "Feb"]]
+        );
+        assert_eq!(data.rows[0][0].row_span, 2);
+        assert_eq!(data.rows[0][1].column_span, 2);
+        assert_eq!(data.rows[0][2].merged_into, Some((0, 1)));
+        assert_eq!(data.rows[1][0].merged_into, Some((0, 0)));
+
+        assert_eq!(data.to_csv('\t'), "Region\tQ1\tQ1\r\nRegion\tJan\tFeb\r\n");
+        #[cfg(feature = "serde")]
+        assert!(data.to_json().starts_with(
+            "{\"rows\":[[{\"text\":\"Region\",\"row_span\":2,\"column_span\":1,\"merged_into\":null,\"tables\":[]}"
+        ));
+
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("Sales"));
+        document.push(table);
+        document.push(nested);
+        let captions: Vec<Option<String>> =
+            document.iter_tables().map(|table| table.caption).collect();
+        assert_eq!(captions, vec![Some("Sales".to_owned()), None]);
+    }
+
+    #[test]
+    fn nested_tables() {
+        let table = Table::from_str(
+            r#"<w:tbl><w:tblPr/><w:tblGrid/><w:tr><w:tc><w:p><w:r><w:t>North</w:t></w:r></w:p><w:tbl><w:tblPr/><w:tblGrid/><w:tr><w:tc><w:p><w:r><w:t>x</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>y</w:t></w:r></w:p></w:tc></w:tr></w:tbl><w:p/></w:tc><w:tc><w:p><w:r><w:t>South</w:t></w:r></w:p></w:tc></w:tr></w:tbl>"#,
+        )
+        .unwrap();
+
+        let cell = table
+            .iter_rows()
+            .next()
+            .unwrap()
+            .iter_cells()
+            .next()
+            .unwrap();
+        let nested = match &cell.content[1] {
+            TableCellContent::Table(nested) => nested,
+            content => panic!("expected a nested table, found {:?}", content),
+        };
+
+        let data = table.to_rows();
+        assert_eq!(data.texts(), vec![vec!["North\nx\ty\n", "South"]]);
+        assert_eq!(data.rows[0][0].tables, vec![nested.to_rows()]);
+        assert!(data.rows[0][1].tables.is_empty());
+
+        let mut document = Document::default();
+        document.push(table.clone());
+        let tables: Vec<_> = document.iter_tables().collect();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].table, &table);
+        assert_eq!(tables[1].table, nested);
+        assert_eq!(tables[1].caption.as_deref(), Some("North"));
+    }
+}
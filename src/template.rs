@@ -0,0 +1,1117 @@
+//! Templates
+//!
+//! Replaces `{{name}}` placeholders with values, even if Word split them into
+//! several runs, and keeps the formatting of the run where each placeholder
+//! starts. Placeholders are found in runs, hyperlinks, smart tags and content
+//! controls of paragraphs, tables, headers and footers.
+//!
+//! A placeholder without a value is an error, see [`TemplateError::Missing`].
+//! Runs left without text by a replacement are removed.
+//!
+//! Sections show, hide or repeat content:
+//!
+//! - `{{#if name}}...{{else}}...{{/if}}` shows its content if the value is
+//!   `true`, a non-empty text or a non-empty list.
+//! - `{{#unless name}}...{{/unless}}` shows its content otherwise.
+//! - `{{#each name}}...{{/each}}` repeats its content for every record of a
+//!   list. Names are looked up in the record first, then in the enclosing
+//!   records.
+//!
+//! A section opened and closed in the same paragraph applies to the text in
+//! between. A section spanning paragraphs applies to whole paragraphs, and
+//! paragraphs containing only its tags are removed. A section spanning table
+//! cells applies to whole table rows, from the row where it's opened to the
+//! row where it's closed.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::template::{Value, Values};
+//!
+//! let mut document = Document::default();
+//! document.push(Paragraph::default().push_text("{{#each items}}"));
+//! document.push(
+//!     Paragraph::default()
+//!         .push_text("{{na")
+//!         .push_text("me}}{{#if last}}.{{else}},{{/if}}"),
+//! );
+//! document.push(Paragraph::default().push_text("{{/each}}"));
+//!
+//! let item = |name: &str, last: bool| -> Values {
+//!     vec![("name".into(), name.into()), ("last".into(), last.into())]
+//!         .into_iter()
+//!         .collect()
+//! };
+//!
+//! let mut values = Values::new();
+//! values.insert(
+//!     "items".into(),
+//!     Value::List(vec![item("apple", false), item("pear", true)]),
+//! );
+//!
+//! document.render_template(&values).unwrap();
+//!
+//! assert_eq!(document.body.content.len(), 2);
+//! ```
+
+use std::{collections::BTreeMap, fmt, mem, ops::Range};
+
+use crate::{
+    document::{
+        BodyContent, Document, Paragraph, ParagraphContent, Run, RunContent, TableCellContent,
+        TableContent, TableRow, Text, TextSpace,
+    },
+    Docx,
+};
+
+/// Values of a template, by name
+pub type Values = BTreeMap<String, Value>;
+
+/// A value substituted into a template
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Text(String),
+    Bool(bool),
+    /// Records repeated by `{{#each}}` sections
+    List(Vec<Values>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Text(text) => !text.is_empty(),
+            Value::Bool(value) => *value,
+            Value::List(records) => !records.is_empty(),
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Value::Text(text) => text.clone(),
+            Value::Bool(value) => value.to_string(),
+            Value::List(_) => String::new(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<Vec<Values>> for Value {
+    fn from(records: Vec<Values>) -> Self {
+        Value::List(records)
+    }
+}
+
+/// Error of rendering a template
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    /// A section isn't closed, e.g. `{{#each items}}` without `{{/each}}`.
+    Unclosed(String),
+    /// A closing tag or `{{else}}` doesn't match the opened section.
+    Unexpected(String),
+    /// A placeholder has no value, e.g. `{{name}}` without `name` in the
+    /// values or the enclosing records. Sections treat missing values as
+    /// `false` instead.
+    Missing(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Unclosed(tag) => write!(f, "unclosed section {}", tag),
+            TemplateError::Unexpected(tag) => write!(f, "unexpected tag {}", tag),
+            TemplateError::Missing(name) => write!(f, "missing value {}", name),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl<'a> Docx<'a> {
+    /// Renders the template in the document, headers and footers.
+    pub fn render_template(&mut self, values: &Values) -> Result<(), TemplateError> {
+        let scope = Scope::new(values);
+        self.document.body.content =
+            render_blocks(mem::take(&mut self.document.body.content), &scope)?;
+        for header in self.headers.values_mut() {
+            header.content = render_blocks(mem::take(&mut header.content), &scope)?;
+        }
+        for footer in self.footers.values_mut() {
+            footer.content = render_blocks(mem::take(&mut footer.content), &scope)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Renders the template in the document.
+    pub fn render_template(&mut self, values: &Values) -> Result<(), TemplateError> {
+        let scope = Scope::new(values);
+        self.body.content = render_blocks(mem::take(&mut self.body.content), &scope)?;
+        Ok(())
+    }
+}
+
+/// The values visible from a part of the template
+struct Scope<'v> {
+    values: &'v Values,
+    parent: Option<&'v Scope<'v>>,
+}
+
+impl<'v> Scope<'v> {
+    fn new(values: &'v Values) -> Self {
+        Scope {
+            values,
+            parent: None,
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&'v Value> {
+        self.values
+            .get(name)
+            .or_else(|| self.parent.and_then(|parent| parent.lookup(name)))
+    }
+
+    fn text(&self, name: &str) -> Result<String, TemplateError> {
+        self.lookup(name)
+            .map(Value::text)
+            .ok_or_else(|| TemplateError::Missing(name.to_owned()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    If,
+    Unless,
+    Each,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tag {
+    Value(String),
+    Open(Kind, String),
+    Else,
+    Close(Kind),
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword = |kind| match kind {
+            Kind::If => "if",
+            Kind::Unless => "unless",
+            Kind::Each => "each",
+        };
+        match self {
+            Tag::Value(name) => write!(f, "{{{{{}}}}}", name),
+            Tag::Open(kind, name) => write!(f, "{{{{#{} {}}}}}", keyword(*kind), name),
+            Tag::Else => write!(f, "{{{{else}}}}"),
+            Tag::Close(kind) => write!(f, "{{{{/{}}}}}", keyword(*kind)),
+        }
+    }
+}
+
+/// A tag found in a text
+struct Scanned {
+    range: Range<usize>,
+    tag: Tag,
+}
+
+/// A replacement of a range of the text
+type Edit = (Range<usize>, String);
+
+/// Which content of a section is rendered
+enum Choice<'v> {
+    Body,
+    Records(&'v [Values]),
+    Alternative,
+}
+
+fn choose(kind: Kind, value: Option<&Value>) -> Choice<'_> {
+    let truthy = value.is_some_and(Value::is_truthy);
+    match (kind, value) {
+        (Kind::Each, Some(Value::List(records))) if truthy => Choice::Records(records),
+        (Kind::If, _) | (Kind::Each, _) if truthy => Choice::Body,
+        (Kind::Unless, _) if !truthy => Choice::Body,
+        _ => Choice::Alternative,
+    }
+}
+
+fn scan(text: &str) -> Vec<Scanned> {
+    let mut tags = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find("{{") {
+        let mut start = offset + start;
+        let end = match text[start + 2..].find("}}") {
+            Some(end) => start + 2 + end + 2,
+            None => break,
+        };
+        // `{{ {{name}}` only opens the tag at the last braces
+        if let Some(inner) = text[start + 2..end - 2].rfind("{{") {
+            start += 2 + inner;
+        }
+        let inner = text[start + 2..end - 2].trim();
+        if !inner.is_empty() {
+            tags.push(Scanned {
+                range: start..end,
+                tag: parse_tag(inner),
+            });
+        }
+        offset = end;
+    }
+
+    tags
+}
+
+fn parse_tag(inner: &str) -> Tag {
+    let (keyword, name) = match inner.split_once(char::is_whitespace) {
+        Some((keyword, name)) => (keyword, name.trim()),
+        None => (inner, ""),
+    };
+
+    match (keyword, name) {
+        ("#if", name) if !name.is_empty() => Tag::Open(Kind::If, name.to_owned()),
+        ("#unless", name) if !name.is_empty() => Tag::Open(Kind::Unless, name.to_owned()),
+        ("#each", name) if !name.is_empty() => Tag::Open(Kind::Each, name.to_owned()),
+        ("else", "") => Tag::Else,
+        ("/if", "") => Tag::Close(Kind::If),
+        ("/unless", "") => Tag::Close(Kind::Unless),
+        ("/each", "") => Tag::Close(Kind::Each),
+        _ => Tag::Value(inner.to_owned()),
+    }
+}
+
+/// Returns the indices of the section tags which aren't matched within the
+/// given tags, i.e. the sections spanning paragraphs or rows.
+fn unbalanced(tags: &[&Tag]) -> Result<Vec<usize>, TemplateError> {
+    let mut stack: Vec<(Kind, Vec<usize>)> = Vec::new();
+    let mut markers = Vec::new();
+
+    for (index, tag) in tags.iter().enumerate() {
+        match tag {
+            Tag::Open(kind, _) => stack.push((*kind, vec![index])),
+            Tag::Else => match stack.last_mut() {
+                Some((_, indices)) => indices.push(index),
+                None => markers.push(index),
+            },
+            Tag::Close(kind) => match stack.pop() {
+                Some((open, _)) if open == *kind => (),
+                Some(_) => return Err(TemplateError::Unexpected(tag.to_string())),
+                None => markers.push(index),
+            },
+            Tag::Value(_) => (),
+        }
+    }
+
+    markers.extend(stack.into_iter().flat_map(|(_, indices)| indices));
+    markers.sort_unstable();
+    Ok(markers)
+}
+
+/// Returns the indices of `{{else}}`, if any, and of the tag closing the section
+/// opened at `open`.
+fn matching(tags: &[Scanned], open: usize) -> Result<(Option<usize>, usize), TemplateError> {
+    let kind = match &tags[open].tag {
+        Tag::Open(kind, _) => *kind,
+        tag => return Err(TemplateError::Unexpected(tag.to_string())),
+    };
+
+    let mut depth = 0;
+    let mut alternative = None;
+
+    for (index, scanned) in tags.iter().enumerate().skip(open + 1) {
+        match &scanned.tag {
+            Tag::Open(..) => depth += 1,
+            Tag::Else if depth == 0 && alternative.is_some() => {
+                return Err(TemplateError::Unexpected(scanned.tag.to_string()));
+            }
+            Tag::Else if depth == 0 => alternative = Some(index),
+            Tag::Close(close) if depth == 0 => {
+                return if *close == kind {
+                    Ok((alternative, index))
+                } else {
+                    Err(TemplateError::Unexpected(scanned.tag.to_string()))
+                };
+            }
+            Tag::Close(_) => depth -= 1,
+            _ => (),
+        }
+    }
+
+    Err(TemplateError::Unclosed(tags[open].tag.to_string()))
+}
+
+/// Computes the edits rendering the tags of a text, in ascending order.
+fn inline_edits(
+    text: &str,
+    tags: &[Scanned],
+    scope: &Scope,
+    edits: &mut Vec<Edit>,
+) -> Result<(), TemplateError> {
+    let mut index = 0;
+
+    while index < tags.len() {
+        let open = &tags[index];
+        let name = match &open.tag {
+            Tag::Value(name) => {
+                edits.push((open.range.clone(), scope.text(name)?));
+                index += 1;
+                continue;
+            }
+            Tag::Open(_, name) => name,
+            tag => return Err(TemplateError::Unexpected(tag.to_string())),
+        };
+
+        let (alternative, close) = matching(tags, index)?;
+        let kind = match open.tag {
+            Tag::Open(kind, _) => kind,
+            _ => unreachable!(),
+        };
+        let close_range = &tags[close].range;
+        let body_end = alternative.map_or(close_range.start, |index| tags[index].range.start);
+
+        match choose(kind, scope.lookup(name)) {
+            Choice::Body => {
+                edits.push((open.range.clone(), String::new()));
+                inline_edits(
+                    text,
+                    &tags[index + 1..alternative.unwrap_or(close)],
+                    scope,
+                    edits,
+                )?;
+                edits.push((body_end..close_range.end, String::new()));
+            }
+            Choice::Records(records) => {
+                let body = &text[open.range.end..body_end];
+                let mut rendered = String::new();
+                for record in records {
+                    let scope = Scope {
+                        values: record,
+                        parent: Some(scope),
+                    };
+                    rendered.push_str(&render_text(body, &scope)?);
+                }
+                edits.push((open.range.start..close_range.end, rendered));
+            }
+            Choice::Alternative => match alternative {
+                Some(alternative) => {
+                    edits.push((open.range.start..tags[alternative].range.end, String::new()));
+                    inline_edits(text, &tags[alternative + 1..close], scope, edits)?;
+                    edits.push((close_range.clone(), String::new()));
+                }
+                None => edits.push((open.range.start..close_range.end, String::new())),
+            },
+        }
+
+        index = close + 1;
+    }
+
+    Ok(())
+}
+
+fn render_text(text: &str, scope: &Scope) -> Result<String, TemplateError> {
+    let mut edits = Vec::new();
+    inline_edits(text, &scan(text), scope, &mut edits)?;
+
+    let mut result = text.to_owned();
+    for (range, replacement) in edits.iter().rev() {
+        result.replace_range(range.clone(), replacement);
+    }
+    Ok(result)
+}
+
+/// The texts of a paragraph, in reading order
+struct Slots<'s, 'a> {
+    texts: Vec<&'s mut Text<'a>>,
+    /// The offset of each text in the concatenated text
+    starts: Vec<usize>,
+    text: String,
+}
+
+impl<'s, 'a> Slots<'s, 'a> {
+    fn new(content: &'s mut [ParagraphContent<'a>]) -> Self {
+        let mut texts = Vec::new();
+        collect_texts(content, &mut texts);
+
+        let mut starts = Vec::with_capacity(texts.len());
+        let mut text = String::new();
+        for slot in &texts {
+            starts.push(text.len());
+            text.push_str(&slot.text);
+        }
+
+        Slots {
+            texts,
+            starts,
+            text,
+        }
+    }
+
+    /// Applies the edits, given in ascending order. A replacement goes to
+    /// the text where its range starts, keeping the formatting of that run.
+    ///
+    /// Returns the indices of the texts left empty, see [`remove_texts`].
+    fn apply(&mut self, edits: &[Edit]) -> Vec<usize> {
+        let len = self.text.len();
+        let mut touched = Vec::new();
+
+        for (range, replacement) in edits.iter().rev() {
+            let mut replacement = Some(replacement.as_str());
+
+            for (index, slot) in self.texts.iter_mut().enumerate() {
+                let start = self.starts[index];
+                let end = self.starts.get(index + 1).copied().unwrap_or(len);
+                if end <= range.start || start == end {
+                    continue;
+                }
+                if start >= range.end {
+                    break;
+                }
+
+                let local = range.start.max(start) - start..range.end.min(end) - start;
+                slot.text
+                    .to_mut()
+                    .replace_range(local, replacement.take().unwrap_or_default());
+                slot.space = Some(TextSpace::Preserve);
+                touched.push(index);
+            }
+        }
+
+        touched.sort_unstable();
+        touched.dedup();
+        touched.retain(|&index| self.texts[index].text.is_empty());
+        touched
+    }
+}
+
+fn collect_texts<'s, 'a>(
+    content: &'s mut [ParagraphContent<'a>],
+    texts: &mut Vec<&'s mut Text<'a>>,
+) {
+    for content in content {
+        match content {
+            ParagraphContent::Run(run) => run_texts(run, texts),
+            ParagraphContent::Link(link) => {
+                if let Some(run) = &mut link.content {
+                    run_texts(run, texts);
+                }
+            }
+            ParagraphContent::SmartTag(tag) => collect_texts(&mut tag.content, texts),
+            ParagraphContent::SimpleField(field) => collect_texts(&mut field.content, texts),
+            ParagraphContent::Sdt(sdt) => collect_texts(&mut sdt.content.content, texts),
+            ParagraphContent::Insertion(ins) => collect_texts(&mut ins.content, texts),
+            ParagraphContent::MoveTo(to) => collect_texts(&mut to.content, texts),
+            _ => (),
+        }
+    }
+}
+
+fn run_texts<'s, 'a>(run: &'s mut Run<'a>, texts: &mut Vec<&'s mut Text<'a>>) {
+    for content in &mut run.content {
+        if let RunContent::Text(text) = content {
+            texts.push(text);
+        }
+    }
+}
+
+/// Removes the texts at the given indices, counted like [`Slots`], and the
+/// runs and hyperlinks left without content.
+fn remove_texts(content: &mut Vec<ParagraphContent>, indices: &[usize]) {
+    if !indices.is_empty() {
+        retain_texts(content, indices, &mut 0);
+    }
+}
+
+fn retain_texts(content: &mut Vec<ParagraphContent>, indices: &[usize], index: &mut usize) {
+    content.retain_mut(|content| match content {
+        ParagraphContent::Run(run) => retain_run_texts(run, indices, index),
+        ParagraphContent::Link(link) => match &mut link.content {
+            Some(run) => retain_run_texts(run, indices, index),
+            None => true,
+        },
+        ParagraphContent::SmartTag(tag) => {
+            retain_texts(&mut tag.content, indices, index);
+            true
+        }
+        ParagraphContent::SimpleField(field) => {
+            retain_texts(&mut field.content, indices, index);
+            true
+        }
+        ParagraphContent::Sdt(sdt) => {
+            retain_texts(&mut sdt.content.content, indices, index);
+            true
+        }
+        ParagraphContent::Insertion(ins) => {
+            retain_texts(&mut ins.content, indices, index);
+            true
+        }
+        ParagraphContent::MoveTo(to) => {
+            retain_texts(&mut to.content, indices, index);
+            true
+        }
+        _ => true,
+    });
+}
+
+/// Returns `false` if the run only held texts, which were all removed.
+fn retain_run_texts(run: &mut Run, indices: &[usize], index: &mut usize) -> bool {
+    let was_empty = run.content.is_empty();
+    run.content.retain(|content| match content {
+        RunContent::Text(_) => {
+            *index += 1;
+            !indices.contains(&(*index - 1))
+        }
+        _ => true,
+    });
+    was_empty || !run.content.is_empty()
+}
+
+/// Returns `true` if the paragraph holds anything else than text, e.g. a drawing.
+fn has_objects(content: &[ParagraphContent]) -> bool {
+    let run_has_objects = |run: &Run| {
+        run.content
+            .iter()
+            .any(|content| !matches!(content, RunContent::Text(_)))
+    };
+
+    content.iter().any(|content| match content {
+        ParagraphContent::Run(run) => run_has_objects(run),
+        ParagraphContent::Link(link) => link.content.iter().any(run_has_objects),
+        ParagraphContent::SmartTag(tag) => has_objects(&tag.content),
+        ParagraphContent::SimpleField(_) => true,
+        ParagraphContent::Sdt(_) => true,
+        ParagraphContent::Insertion(ins) => has_objects(&ins.content),
+        ParagraphContent::MoveTo(to) => has_objects(&to.content),
+        _ => false,
+    })
+}
+
+fn render_paragraph(para: &mut Paragraph, scope: &Scope) -> Result<(), TemplateError> {
+    let mut slots = Slots::new(&mut para.content);
+
+    let tags = scan(&slots.text);
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let mut edits = Vec::new();
+    inline_edits(&slots.text, &tags, scope, &mut edits)?;
+    let emptied = slots.apply(&edits);
+    remove_texts(&mut para.content, &emptied);
+    Ok(())
+}
+
+/// The section tags of a block belonging to the enclosing container
+#[derive(Default)]
+struct Extracted {
+    before: Vec<Tag>,
+    after: Vec<Tag>,
+    /// `false` if the block only holds section tags
+    keep: bool,
+}
+
+/// Removes the tags of the sections spanning paragraphs. Tags before the
+/// content of the paragraph apply before it, the others after it.
+fn paragraph_markers(para: &mut Paragraph) -> Result<Extracted, TemplateError> {
+    let objects = has_objects(&para.content);
+    let mut slots = Slots::new(&mut para.content);
+
+    let tags = scan(&slots.text);
+    let markers = unbalanced(&tags.iter().map(|scanned| &scanned.tag).collect::<Vec<_>>())?;
+    if markers.is_empty() {
+        return Ok(Extracted {
+            keep: true,
+            ..Default::default()
+        });
+    }
+
+    let content_start = slots.text.char_indices().find_map(|(offset, c)| {
+        let in_marker = markers
+            .iter()
+            .any(|&index| tags[index].range.contains(&offset));
+        (!in_marker && !c.is_whitespace()).then_some(offset)
+    });
+
+    let mut extracted = Extracted {
+        keep: content_start.is_some() || objects,
+        ..Default::default()
+    };
+    let mut edits = Vec::new();
+    for &index in &markers {
+        let Scanned { range, tag } = &tags[index];
+        if content_start.map_or(true, |start| range.start < start) {
+            extracted.before.push(tag.clone());
+        } else {
+            extracted.after.push(tag.clone());
+        }
+        edits.push((range.clone(), String::new()));
+    }
+
+    let emptied = slots.apply(&edits);
+    remove_texts(&mut para.content, &emptied);
+    Ok(extracted)
+}
+
+/// Removes the tags of the sections spanning cells. Opening tags and
+/// `{{else}}` apply before the row, closing tags after it.
+fn row_markers(row: &mut TableRow) -> Result<Extracted, TemplateError> {
+    let mut extracted = Extracted::default();
+    let mut has_content = false;
+    let mut has_markers = false;
+
+    for cell in row.iter_cells_mut() {
+        let mut slots: Vec<Slots> = cell
+            .content
+            .iter_mut()
+            .filter_map(|content| match content {
+                TableCellContent::Paragraph(para) => Some(Slots::new(&mut para.content)),
+                TableCellContent::Sdt(_) | TableCellContent::Table(_) => None,
+            })
+            .collect();
+
+        let tags: Vec<(usize, Scanned)> = slots
+            .iter()
+            .enumerate()
+            .flat_map(|(index, slots)| scan(&slots.text).into_iter().map(move |tag| (index, tag)))
+            .collect();
+        let markers = unbalanced(
+            &tags
+                .iter()
+                .map(|(_, scanned)| &scanned.tag)
+                .collect::<Vec<_>>(),
+        )?;
+
+        let mut edits = vec![Vec::new(); slots.len()];
+        for &index in &markers {
+            let (slot, Scanned { range, tag }) = &tags[index];
+            match tag {
+                Tag::Close(_) => extracted.after.push(tag.clone()),
+                _ => extracted.before.push(tag.clone()),
+            }
+            edits[*slot].push((range.clone(), String::new()));
+        }
+        has_markers |= !markers.is_empty();
+
+        let mut emptied = Vec::with_capacity(slots.len());
+        for (slots, edits) in slots.iter_mut().zip(&edits) {
+            has_content |= slots.text.char_indices().any(|(offset, c)| {
+                !c.is_whitespace() && !edits.iter().any(|(range, _)| range.contains(&offset))
+            });
+            emptied.push(slots.apply(edits));
+        }
+
+        let paragraphs = cell.content.iter_mut().filter_map(|content| match content {
+            TableCellContent::Paragraph(para) => Some(para),
+            TableCellContent::Sdt(_) | TableCellContent::Table(_) => None,
+        });
+        for (para, emptied) in paragraphs.zip(&emptied) {
+            remove_texts(&mut para.content, emptied);
+        }
+    }
+
+    extracted.keep = has_content || !has_markers;
+    Ok(extracted)
+}
+
+/// A block-level element of the template
+trait Block: Clone {
+    /// Removes the tags of the sections spanning several blocks.
+    fn extract_markers(&mut self) -> Result<Extracted, TemplateError>;
+
+    /// Renders the content of the block.
+    fn render(&mut self, scope: &Scope) -> Result<(), TemplateError>;
+}
+
+impl<'a> Block for BodyContent<'a> {
+    fn extract_markers(&mut self) -> Result<Extracted, TemplateError> {
+        match self {
+            BodyContent::Paragraph(para) => paragraph_markers(para),
+            BodyContent::Table(_) | BodyContent::Sdt(_) => Ok(Extracted {
+                keep: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn render(&mut self, scope: &Scope) -> Result<(), TemplateError> {
+        match self {
+            BodyContent::Paragraph(para) => render_paragraph(para, scope),
+            BodyContent::Table(table) => {
+                table.rows = render_blocks(mem::take(&mut table.rows), scope)?;
+                Ok(())
+            }
+            BodyContent::Sdt(sdt) => {
+                sdt.content.content = render_blocks(mem::take(&mut sdt.content.content), scope)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> Block for TableCellContent<'a> {
+    fn extract_markers(&mut self) -> Result<Extracted, TemplateError> {
+        match self {
+            TableCellContent::Paragraph(para) => paragraph_markers(para),
+            TableCellContent::Sdt(_) | TableCellContent::Table(_) => Ok(Extracted {
+                keep: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn render(&mut self, scope: &Scope) -> Result<(), TemplateError> {
+        match self {
+            TableCellContent::Paragraph(para) => render_paragraph(para, scope),
+            TableCellContent::Sdt(sdt) => {
+                sdt.content.content = render_blocks(mem::take(&mut sdt.content.content), scope)?;
+                Ok(())
+            }
+            TableCellContent::Table(table) => {
+                table.rows = render_blocks(mem::take(&mut table.rows), scope)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> Block for TableContent<'a> {
+    fn extract_markers(&mut self) -> Result<Extracted, TemplateError> {
+        match self {
+            TableContent::Row(row) => row_markers(row),
+            TableContent::Sdt(_) => Ok(Extracted {
+                keep: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn render(&mut self, scope: &Scope) -> Result<(), TemplateError> {
+        match self {
+            TableContent::Row(row) => {
+                for cell in row.iter_cells_mut() {
+                    cell.content = render_blocks(mem::take(&mut cell.content), scope)?;
+                }
+            }
+            TableContent::Sdt(sdt) => {
+                sdt.content.content = render_blocks(mem::take(&mut sdt.content.content), scope)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Item<B> {
+    Marker(Tag),
+    Block(B),
+}
+
+enum Node<B> {
+    Block(B),
+    Section {
+        kind: Kind,
+        name: String,
+        body: Vec<Node<B>>,
+        alternative: Option<Vec<Node<B>>>,
+    },
+}
+
+fn render_blocks<B: Block>(blocks: Vec<B>, scope: &Scope) -> Result<Vec<B>, TemplateError> {
+    let mut items = Vec::with_capacity(blocks.len());
+    for mut block in blocks {
+        let Extracted {
+            before,
+            after,
+            keep,
+        } = block.extract_markers()?;
+        items.extend(before.into_iter().map(Item::Marker));
+        if keep {
+            items.push(Item::Block(block));
+        }
+        items.extend(after.into_iter().map(Item::Marker));
+    }
+
+    let nodes = build(items)?;
+    let mut output = Vec::new();
+    render_nodes(&nodes, scope, &mut output)?;
+    Ok(output)
+}
+
+fn build<B>(items: Vec<Item<B>>) -> Result<Vec<Node<B>>, TemplateError> {
+    let mut root = Vec::new();
+    let mut stack: Vec<Node<B>> = Vec::new();
+
+    fn current<'n, B>(
+        root: &'n mut Vec<Node<B>>,
+        stack: &'n mut [Node<B>],
+    ) -> &'n mut Vec<Node<B>> {
+        match stack.last_mut() {
+            Some(Node::Section {
+                alternative: Some(alternative),
+                ..
+            }) => alternative,
+            Some(Node::Section { body, .. }) => body,
+            _ => root,
+        }
+    }
+
+    for item in items {
+        match item {
+            Item::Block(block) => current(&mut root, &mut stack).push(Node::Block(block)),
+            Item::Marker(Tag::Open(kind, name)) => stack.push(Node::Section {
+                kind,
+                name,
+                body: Vec::new(),
+                alternative: None,
+            }),
+            Item::Marker(Tag::Else) => match stack.last_mut() {
+                Some(Node::Section { alternative, .. }) if alternative.is_none() => {
+                    *alternative = Some(Vec::new())
+                }
+                _ => return Err(TemplateError::Unexpected(Tag::Else.to_string())),
+            },
+            Item::Marker(Tag::Close(close)) => match stack.pop() {
+                Some(section @ Node::Section { .. }) if matches!(section, Node::Section { kind, .. } if kind == close) => {
+                    current(&mut root, &mut stack).push(section)
+                }
+                _ => return Err(TemplateError::Unexpected(Tag::Close(close).to_string())),
+            },
+            Item::Marker(Tag::Value(_)) => (),
+        }
+    }
+
+    match stack.pop() {
+        Some(Node::Section { kind, name, .. }) => {
+            Err(TemplateError::Unclosed(Tag::Open(kind, name).to_string()))
+        }
+        _ => Ok(root),
+    }
+}
+
+fn render_nodes<B: Block>(
+    nodes: &[Node<B>],
+    scope: &Scope,
+    output: &mut Vec<B>,
+) -> Result<(), TemplateError> {
+    for node in nodes {
+        match node {
+            Node::Block(block) => {
+                let mut block = block.clone();
+                block.render(scope)?;
+                output.push(block);
+            }
+            Node::Section {
+                kind,
+                name,
+                body,
+                alternative,
+            } => match choose(*kind, scope.lookup(name)) {
+                Choice::Body => render_nodes(body, scope, output)?,
+                Choice::Records(records) => {
+                    for record in records {
+                        let scope = Scope {
+                            values: record,
+                            parent: Some(scope),
+                        };
+                        render_nodes(body, &scope, output)?;
+                    }
+                }
+                Choice::Alternative => {
+                    if let Some(alternative) = alternative {
+                        render_nodes(alternative, scope, output)?;
+                    }
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        document::{Hyperlink, SmartTag, Table, TableCell},
+        formatting::CharacterProperty,
+        header_footer::Header,
+    };
+
+    fn values() -> Values {
+        let item =
+            |name: &str, price: &str| -> Value { Value::Text(format!("{}:{}", name, price)) };
+        let record = |name: &str, price: &str| -> Values {
+            let mut values = Values::new();
+            values.insert("name".into(), name.into());
+            values.insert("price".into(), price.into());
+            values.insert("line".into(), item(name, price));
+            values
+        };
+
+        let mut values = Values::new();
+        values.insert("customer".into(), "John".into());
+        values.insert("vip".into(), true.into());
+        values.insert("note".into(), "".into());
+        values.insert(
+            "items".into(),
+            Value::List(vec![record("apple", "1"), record("pear", "2")]),
+        );
+        values
+    }
+
+    fn paragraph_text(content: &BodyContent) -> String {
+        match content {
+            BodyContent::Paragraph(para) => {
+                let mut para = para.clone();
+                let slots = Slots::new(&mut para.content);
+                slots.text
+            }
+            _ => panic!("expected a paragraph"),
+        }
+    }
+
+    #[test]
+    fn render_split_placeholders() {
+        let mut document = Document::default();
+        document.push(
+            Paragraph::default()
+                .push(
+                    Run::default()
+                        .property(CharacterProperty::default().bold(true))
+                        .push_text("Dear {{cust"),
+                )
+                .push(SmartTag {
+                    content: vec![Run::default().push_text("om").into()],
+                })
+                .push(Hyperlink::default().content(Run::default().push_text("er}}")))
+                .push_text(", {{#if missing}}{{ missing }}{{/if}}{{#if vip}}welcome back{{else}}welcome{{/if}}!"),
+        );
+
+        document.render_template(&values()).unwrap();
+
+        let para = match &document.body.content[0] {
+            BodyContent::Paragraph(para) => para,
+            _ => unreachable!(),
+        };
+        match &para.content[0] {
+            ParagraphContent::Run(run) => {
+                assert_eq!(run.iter_text().next().unwrap(), "Dear John");
+                assert_eq!(run.property.bold.as_ref().unwrap().value, Some(true));
+            }
+            _ => panic!("expected a run"),
+        }
+        // the runs emptied by the placeholder are removed, with the hyperlink
+        match &para.content[1] {
+            ParagraphContent::SmartTag(tag) => assert!(tag.content.is_empty()),
+            _ => panic!("expected a smart tag"),
+        }
+        assert_eq!(para.content.len(), 3);
+        assert_eq!(
+            paragraph_text(&document.body.content[0]),
+            "Dear John, welcome back!"
+        );
+    }
+
+    #[test]
+    fn render_sections() {
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("{{#each items}}"));
+        document.push(Paragraph::default().push_text("{{name}} costs {{price}} ({{customer}})"));
+        document.push(Paragraph::default().push_text("{{/each}}"));
+        document.push(Paragraph::default().push_text("{{#if note}}"));
+        document.push(Paragraph::default().push_text("Note: {{note}}"));
+        document.push(Paragraph::default().push_text("{{else}}"));
+        document.push(Paragraph::default().push_text("No note"));
+        document.push(Paragraph::default().push_text("{{/if}}"));
+        document.push(Paragraph::default().push_text("{{#each items}}{{line}};{{/each}}"));
+        document.push(
+            Table::default()
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("Name")))
+                .push_row(
+                    TableRow::default()
+                        .push_cell(Paragraph::default().push_text("{{#each items}}{{name}}"))
+                        .push_cell(Paragraph::default().push_text("{{price}}{{/each}}")),
+                ),
+        );
+
+        document.render_template(&values()).unwrap();
+
+        let texts: Vec<_> = document.body.content[..4]
+            .iter()
+            .map(paragraph_text)
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "apple costs 1 (John)",
+                "pear costs 2 (John)",
+                "No note",
+                "apple:1;pear:2;"
+            ]
+        );
+
+        let table = match &document.body.content[4] {
+            BodyContent::Table(table) => table,
+            _ => panic!("expected a table"),
+        };
+        let rows: Vec<Vec<String>> = table
+            .iter_rows()
+            .map(|row| {
+                row.iter_cells()
+                    .map(|cell: &TableCell| match &cell.content[0] {
+                        TableCellContent::Paragraph(para) => {
+                            para.iter_text().map(|t| &**t).collect()
+                        }
+                        _ => String::new(),
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, [vec!["Name"], vec!["apple", "1"], vec!["pear", "2"]]);
+    }
+
+    #[test]
+    fn render_headers_and_errors() {
+        let mut docx = Docx::default();
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("{{customer}}"));
+        docx.headers.insert("word/header1.xml".into(), header);
+        docx.render_template(&values()).unwrap();
+        assert_eq!(
+            paragraph_text(&docx.headers["word/header1.xml"].content[0]),
+            "John"
+        );
+
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("{{#each items}}"));
+        assert_eq!(
+            document.render_template(&values()),
+            Err(TemplateError::Unclosed("{{#each items}}".into()))
+        );
+
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("{{#if vip}}{{/each}}"));
+        let error = document.render_template(&values()).unwrap_err();
+        assert_eq!(error, TemplateError::Unexpected("{{/each}}".into()));
+        assert_eq!(error.to_string(), "unexpected tag {{/each}}");
+
+        let mut document = Document::default();
+        document.push(Paragraph::default().push_text("Dear {{ nickname }}"));
+        let error = document.render_template(&values()).unwrap_err();
+        assert_eq!(error, TemplateError::Missing("nickname".into()));
+        assert_eq!(error.to_string(), "missing value nickname");
+    }
+}
@@ -0,0 +1,574 @@
+//! Table of contents
+//!
+//! Generates a `TOC` field from the headings of the main document.
+
+use std::collections::HashSet;
+
+use crate::{
+    __setter,
+    document::{
+        BodyContent, BookmarkEnd, BookmarkStart, FieldChar, FieldCharType, Hyperlink, InstrText,
+        Paragraph, ParagraphContent, Run, RunContent, Tab,
+    },
+    find::{visit_paragraphs, visit_paragraphs_mut},
+    formatting::{ParagraphProperty, TabLeader, TabStop, TabStopType, Tabs},
+    private_prelude::*,
+    styles::{Style, StyleType, Styles},
+    Docx,
+};
+
+/// Prefix of the bookmarks that a table of contents links to.
+const TOC_BOOKMARK_PREFIX: &str = "_Toc";
+
+/// Result of the page number fields until Word updates them.
+const PAGE_PLACEHOLDER: &str = "?";
+
+/// Table of Contents Options
+///
+/// Levels are one-based, i.e. `Heading1` is level 1.
+///
+/// ```rust
+/// use docx::formatting::TabLeader;
+/// use docx::toc::TableOfContents;
+/// use docx::Docx;
+///
+/// let mut docx = Docx::default();
+///
+/// let toc = TableOfContents::default()
+///     .levels(1, 2)
+///     .heading_style("Title", 1)
+///     .tab_leader(TabLeader::Hyphen);
+///
+/// docx.insert_toc(0, toc);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TableOfContents<'a> {
+    /// Specifies the lowest heading level to include.
+    pub min_level: usize,
+    /// Specifies the highest heading level to include.
+    pub max_level: usize,
+    /// Specifies additional paragraph styles to treat as headings, with their level.
+    pub heading_styles: Vec<(Cow<'a, str>, usize)>,
+    /// Specifies the paragraph style of the entries of each level, instead of `TOC1..TOC9`.
+    pub entry_styles: Vec<(usize, Cow<'a, str>)>,
+    /// Specifies the character between an entry and its page number.
+    pub tab_leader: Option<TabLeader>,
+    /// Specifies the position of the page numbers in twentieths of a point.
+    pub tab_position: isize,
+}
+
+impl Default for TableOfContents<'_> {
+    fn default() -> Self {
+        TableOfContents {
+            min_level: 1,
+            max_level: 3,
+            heading_styles: Vec::new(),
+            entry_styles: Vec::new(),
+            tab_leader: Some(TabLeader::Dot),
+            tab_position: 9350,
+        }
+    }
+}
+
+impl<'a> TableOfContents<'a> {
+    __setter!(tab_leader: Option<TabLeader>);
+    __setter!(tab_position: isize);
+
+    pub fn levels(mut self, min: usize, max: usize) -> Self {
+        self.min_level = min.max(1);
+        self.max_level = max.min(9);
+        self
+    }
+
+    pub fn heading_style<T: Into<Cow<'a, str>>>(mut self, style_id: T, level: usize) -> Self {
+        self.heading_styles.push((style_id.into(), level));
+        self
+    }
+
+    pub fn entry_style<T: Into<Cow<'a, str>>>(mut self, level: usize, style_id: T) -> Self {
+        self.entry_styles.push((level, style_id.into()));
+        self
+    }
+
+    /// Returns the one-based heading level of a paragraph, if it is a heading.
+    pub fn heading_level(&self, para: &Paragraph, styles: &Styles) -> Option<usize> {
+        if let Some(level) = para.property.outline_level {
+            // outline level 9 is body text
+            return Some(level.value + 1).filter(|level| *level <= 9);
+        }
+
+        let style_id = &para.property.style_id.as_ref()?.value;
+
+        if let Some((_, level)) = self.heading_styles.iter().find(|(id, _)| id == style_id) {
+            return Some(*level);
+        }
+
+        styles
+            .outline_level(style_id)
+            .map(|level| level + 1)
+            .filter(|level| *level <= 9)
+    }
+
+    fn includes(&self, level: usize) -> bool {
+        self.min_level <= level && level <= self.max_level
+    }
+
+    fn entry_style_id(&self, level: usize) -> Cow<'a, str> {
+        self.entry_styles
+            .iter()
+            .find(|(lvl, _)| *lvl == level)
+            .map(|(_, id)| id.clone())
+            .unwrap_or_else(|| format!("TOC{}", level).into())
+    }
+
+    /// Returns the field instructions, e.g. ` TOC \o "1-3" \h \z \u `.
+    pub fn instruction(&self) -> String {
+        let mut instr = format!(
+            r#" TOC \o "{}-{}" \h \z \u "#,
+            self.min_level, self.max_level
+        );
+
+        if !self.heading_styles.is_empty() {
+            let styles: Vec<_> = self
+                .heading_styles
+                .iter()
+                .map(|(id, level)| format!("{},{}", id, level))
+                .collect();
+            instr.push_str(&format!(r#"\t "{}" "#, styles.join(",")));
+        }
+
+        instr
+    }
+}
+
+impl<'a> Docx<'a> {
+    /// Inserts a table of contents field into the body at `index`.
+    ///
+    /// The headings are looked for in the whole body, tables and content
+    /// controls included. Every included heading gets a `_Toc` bookmark which
+    /// its entry links to, unique among the bookmarks of all parts.
+    /// Page numbers are left to Word, which refreshes the field on open because
+    /// `w:updateFields` is turned on. Returns the number of entries.
+    pub fn insert_toc(&mut self, index: usize, toc: TableOfContents<'a>) -> usize {
+        let mut bookmarks = Bookmarks::of(self);
+        let mut entries = Vec::new();
+        let styles = &self.styles;
+
+        let mut add_entry = |_: &[usize], para: &mut Paragraph<'a>| {
+            let level = match toc.heading_level(para, styles) {
+                Some(level) if toc.includes(level) => level,
+                _ => return,
+            };
+
+            let text: String = para.iter_text().map(|text| &**text).collect();
+
+            let name = match toc_bookmark(para) {
+                Some(name) => name,
+                None => {
+                    let name = bookmarks.unique_name();
+                    let id = bookmarks.next_id.to_string();
+                    bookmarks.next_id += 1;
+
+                    para.content.insert(
+                        0,
+                        BookmarkStart::default()
+                            .id(id.clone())
+                            .name(name.clone())
+                            .into(),
+                    );
+                    para.content.push(BookmarkEnd::default().id(id).into());
+
+                    name
+                }
+            };
+
+            entries.push((level, text, name));
+        };
+        visit_paragraphs_mut(
+            &mut self.document.body.content,
+            &mut Vec::new(),
+            &mut add_entry,
+        );
+
+        let count = entries.len();
+        let mut paras = Vec::with_capacity(count + 1);
+        let mut levels = Vec::new();
+
+        for (i, (level, text, name)) in entries.into_iter().enumerate() {
+            let mut para = Paragraph::default().property(
+                ParagraphProperty::default()
+                    .style_id(toc.entry_style_id(level))
+                    .tabs(Tabs::default().push(TabStop {
+                        ty: TabStopType::Right,
+                        leader: toc.tab_leader,
+                        position: toc.tab_position,
+                    })),
+            );
+
+            if i == 0 {
+                para = push_field_start(para, toc.instruction());
+            }
+
+            // the page number field sits in the link, so that it is clickable
+            // as well, with a placeholder result until Word updates it
+            let link = Hyperlink::default().anchor(name.clone()).content(
+                Run::default()
+                    .push_text(text)
+                    .push(RunContent::Tab(Tab))
+                    .push(FieldChar::from(FieldCharType::Begin))
+                    .push(InstrText::from(format!(r#" PAGEREF {} \h "#, name)))
+                    .push(FieldChar::from(FieldCharType::Separate))
+                    .push_text(PAGE_PLACEHOLDER)
+                    .push(FieldChar::from(FieldCharType::End)),
+            );
+
+            para = para.push(link);
+
+            paras.push(BodyContent::Paragraph(para));
+            levels.push(level);
+        }
+
+        let mut last = Paragraph::default();
+        if count == 0 {
+            last = push_field_start(last, toc.instruction());
+        }
+        paras.push(last.push(field_char(FieldCharType::End)).into());
+
+        let index = index.min(self.document.body.content.len());
+        self.document.body.content.splice(index..index, paras);
+
+        // levels without an entry style fall back to `TOC1..TOC9`
+        for level in levels {
+            if toc.entry_styles.iter().any(|(lvl, _)| *lvl == level) {
+                continue;
+            }
+            let style_id = format!("TOC{}", level);
+            if self.styles.get(&style_id).is_none() {
+                self.styles.push(
+                    Style::new(StyleType::Paragraph, style_id)
+                        .name(format!("toc {}", level))
+                        .based_on("Normal"),
+                );
+            }
+        }
+
+        self.settings
+            .get_or_insert_with(Default::default)
+            .update_fields = Some(true.into());
+
+        count
+    }
+}
+
+fn field_char(ty: FieldCharType) -> Run<'static> {
+    Run::default().push(FieldChar::from(ty))
+}
+
+/// Appends the `begin`, instructions and `separate` runs of a complex field.
+fn push_field_start<'a>(para: Paragraph<'a>, instr: String) -> Paragraph<'a> {
+    para.push(field_char(FieldCharType::Begin))
+        .push(Run::default().push(InstrText::from(instr)))
+        .push(field_char(FieldCharType::Separate))
+}
+
+fn toc_bookmark(para: &Paragraph) -> Option<String> {
+    para.content.iter().find_map(|content| match content {
+        ParagraphContent::BookmarkStart(BookmarkStart {
+            name: Some(name), ..
+        }) if name.starts_with(TOC_BOOKMARK_PREFIX) => Some(name.to_string()),
+        _ => None,
+    })
+}
+
+/// The bookmarks of a document
+#[derive(Default)]
+struct Bookmarks {
+    /// The id following the greatest one in use.
+    next_id: usize,
+    names: HashSet<String>,
+}
+
+impl Bookmarks {
+    /// Collects the bookmarks of the body, headers, footers, notes and
+    /// comments, which all share the ids and the names.
+    fn of(docx: &Docx) -> Self {
+        let mut bookmarks = Bookmarks::default();
+        let mut add = |_: &[usize], para: &Paragraph| bookmarks.add(&para.content);
+
+        visit_paragraphs(&docx.document.body.content, &mut Vec::new(), &mut add);
+        for header in docx.headers.values() {
+            visit_paragraphs(&header.content, &mut Vec::new(), &mut add);
+        }
+        for footer in docx.footers.values() {
+            visit_paragraphs(&footer.content, &mut Vec::new(), &mut add);
+        }
+        for note in docx.footnotes.iter().flat_map(|notes| &notes.notes) {
+            visit_paragraphs(&note.content, &mut Vec::new(), &mut add);
+        }
+        for note in docx.endnotes.iter().flat_map(|notes| &notes.notes) {
+            visit_paragraphs(&note.content, &mut Vec::new(), &mut add);
+        }
+        for comment in docx.comments.iter().flat_map(|comments| &comments.comments) {
+            for para in &comment.content {
+                add(&[], para);
+            }
+        }
+
+        bookmarks
+    }
+
+    fn add(&mut self, content: &[ParagraphContent]) {
+        for content in content {
+            match content {
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    if let Some(id) = bookmark.id.as_ref().and_then(|id| id.parse::<usize>().ok()) {
+                        self.next_id = self.next_id.max(id + 1);
+                    }
+                    if let Some(name) = &bookmark.name {
+                        self.names.insert(name.to_string());
+                    }
+                }
+                ParagraphContent::SmartTag(tag) => self.add(&tag.content),
+                ParagraphContent::Sdt(sdt) => self.add(&sdt.content.content),
+                ParagraphContent::SimpleField(field) => self.add(&field.content),
+                ParagraphContent::Insertion(ins) => self.add(&ins.content),
+                ParagraphContent::Deletion(del) => self.add(&del.content),
+                ParagraphContent::MoveFrom(from) => self.add(&from.content),
+                ParagraphContent::MoveTo(to) => self.add(&to.content),
+                _ => (),
+            }
+        }
+    }
+
+    fn unique_name(&mut self) -> String {
+        let mut n = 100_000_000 + self.names.len();
+        loop {
+            let name = format!("{}{}", TOC_BOOKMARK_PREFIX, n);
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+            n += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatting::OutlineLevel;
+
+    fn heading(style_id: &'static str, text: &'static str) -> Paragraph<'static> {
+        Paragraph::default()
+            .property(ParagraphProperty::default().style_id(style_id))
+            .push_text(text)
+    }
+
+    #[test]
+    fn insert_toc() {
+        let mut docx = Docx::default();
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, "Heading1")
+                .paragraph(ParagraphProperty::default().outline_level(OutlineLevel::from(0))),
+        );
+        docx.styles.push(
+            Style::new(StyleType::Paragraph, "Heading2")
+                .based_on("Heading1")
+                .paragraph(ParagraphProperty::default().outline_level(OutlineLevel::from(1))),
+        );
+        docx.styles
+            .push(Style::new(StyleType::Paragraph, "Heading4").based_on("Heading2"));
+        docx.document.push(heading("Heading1", "Intro"));
+        docx.document.push(Paragraph::default().push_text("body"));
+        docx.document.push(heading("Heading2", "Scope"));
+        docx.document.push(heading("Heading4", "Inherited"));
+        docx.document.push(heading("Appendix", "Custom"));
+
+        let toc = TableOfContents::default()
+            .levels(1, 2)
+            .heading_style("Appendix", 1);
+
+        assert_eq!(docx.insert_toc(0, toc), 4);
+        // four entries plus the paragraph closing the field
+        assert_eq!(docx.document.body.content.len(), 10);
+        assert!(docx.styles.get("TOC1").is_some());
+        assert!(docx.styles.get("TOC2").is_some());
+        assert_eq!(
+            docx.settings.unwrap().update_fields.unwrap().value,
+            Some(true)
+        );
+
+        let heading = match &docx.document.body.content[5] {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("expected a paragraph"),
+        };
+        let name = toc_bookmark(heading).unwrap();
+
+        let entry = match &docx.document.body.content[0] {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("expected a paragraph"),
+        };
+        assert!(entry.content.iter().any(|content| matches!(
+            content,
+            ParagraphContent::Link(Hyperlink { anchor: Some(anchor), .. }) if *anchor == name
+        )));
+    }
+
+    #[test]
+    fn reuse_bookmarks() {
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().outline_level(OutlineLevel::from(0)))
+                .push(BookmarkStart::default().id("7").name("_Toc42"))
+                .push_text("Intro")
+                .push(BookmarkEnd::default().id("7")),
+        );
+
+        docx.insert_toc(0, TableOfContents::default());
+
+        let heading = match &docx.document.body.content[2] {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("expected a paragraph"),
+        };
+        assert_eq!(heading.content.len(), 3);
+        assert_eq!(toc_bookmark(heading).as_deref(), Some("_Toc42"));
+    }
+
+    #[test]
+    fn nested_headings_and_bookmarks() {
+        use crate::{
+            document::{BlockSdt, Table, TableRow},
+            footnotes::{Footnote, Footnotes},
+            header_footer::Header,
+        };
+
+        let heading = || {
+            Paragraph::default()
+                .property(ParagraphProperty::default().outline_level(OutlineLevel::from(0)))
+        };
+
+        let mut docx = Docx::default();
+        docx.document.push(
+            Table::default().push_row(TableRow::default().push_cell(heading().push_text("Cell"))),
+        );
+        docx.document
+            .push(BlockSdt::default().push(heading().push_text("Control")));
+
+        // bookmarks taking the first generated name and the ids
+        let mut header = Header::default();
+        header.push(
+            Paragraph::default()
+                .push(BookmarkStart::default().id("4").name("_Toc100000000"))
+                .push(BookmarkEnd::default().id("4")),
+        );
+        docx.headers.insert("word/header1.xml".into(), header);
+        let mut footnotes = Footnotes::default();
+        footnotes.push(
+            Footnote::new("1")
+                .push(Paragraph::default().push(BookmarkStart::default().id("9").name("note"))),
+        );
+        docx.footnotes = Some(footnotes);
+
+        assert_eq!(docx.insert_toc(0, TableOfContents::default()), 2);
+
+        let mut bookmarks = Vec::new();
+        visit_paragraphs(
+            &docx.document.body.content[3..],
+            &mut Vec::new(),
+            &mut |_, para| {
+                bookmarks.extend(para.content.iter().filter_map(|content| match content {
+                    ParagraphContent::BookmarkStart(BookmarkStart {
+                        id: Some(id),
+                        name: Some(name),
+                    }) => Some((id.to_string(), name.to_string())),
+                    _ => None,
+                }))
+            },
+        );
+        assert_eq!(bookmarks.len(), 2);
+        for (id, name) in &bookmarks {
+            assert!(id.parse::<usize>().unwrap() >= 10);
+            assert_ne!(name, "_Toc100000000");
+        }
+    }
+
+    #[test]
+    fn fallback_entry_styles() {
+        let mut docx = Docx::default();
+        let heading = |level: usize, text: &'static str| {
+            Paragraph::default()
+                .property(ParagraphProperty::default().outline_level(OutlineLevel::from(level)))
+                .push_text(text)
+        };
+        docx.document.push(heading(0, "Intro"));
+        docx.document.push(heading(1, "Scope"));
+
+        let toc = TableOfContents::default().entry_style(1, "Contents1");
+        assert_eq!(docx.insert_toc(0, toc), 2);
+
+        // only the level without an entry style gets a fallback style
+        assert!(docx.styles.get("TOC1").is_none());
+        assert!(docx.styles.get("TOC2").is_some());
+        let style_ids: Vec<_> = docx.document.body.content[..2]
+            .iter()
+            .map(|content| match content {
+                BodyContent::Paragraph(para) => {
+                    para.property.style_id.as_ref().unwrap().value.to_string()
+                }
+                _ => panic!("expected a paragraph"),
+            })
+            .collect();
+        assert_eq!(style_ids, ["Contents1", "TOC2"]);
+    }
+
+    #[test]
+    fn page_fields_in_links() {
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty::default().outline_level(OutlineLevel::from(0)))
+                .push_text("Intro"),
+        );
+
+        docx.insert_toc(0, TableOfContents::default());
+
+        let entry = match &docx.document.body.content[0] {
+            BodyContent::Paragraph(para) => para,
+            _ => panic!("expected a paragraph"),
+        };
+        let link = entry
+            .content
+            .iter()
+            .find_map(|content| match content {
+                ParagraphContent::Link(link) => link.content.as_ref(),
+                _ => None,
+            })
+            .unwrap();
+        let instr = link.content.iter().find_map(|content| match content {
+            RunContent::InstrText(instr) => Some(instr.text.to_string()),
+            _ => None,
+        });
+        assert!(instr.unwrap().starts_with(" PAGEREF _Toc"));
+        let fields: Vec<_> = link
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                RunContent::FieldChar(field) => Some(&field.ty),
+                _ => None,
+            })
+            .collect();
+        assert!(matches!(
+            &fields[..],
+            [
+                FieldCharType::Begin,
+                FieldCharType::Separate,
+                FieldCharType::End
+            ]
+        ));
+        let text: String = link.iter_text().map(|text| &**text).collect();
+        assert_eq!(text, format!("Intro{}", PAGE_PLACEHOLDER));
+
+        // the field started before the entries is closed after them
+        assert_eq!(docx.document.body.content.len(), 3);
+    }
+}
@@ -0,0 +1,768 @@
+//! Visitors over the document model
+//!
+//! [`Visit`] walks the model by shared reference and [`VisitMut`] by mutable
+//! reference. Both have a method for every kind of node, which by default
+//! visits the children of the node, so an implementation only overrides the
+//! methods for the nodes it's interested in. An overriding method can call
+//! the matching function of [`walk`] or [`walk_mut`] to keep walking into the
+//! children.
+//!
+//! [`Docx::visit`] and [`Docx::visit_mut`] walk every part with content: the
+//! main document, headers, footers, comments, footnotes and endnotes.
+//!
+//! ```rust
+//! use docx::document::*;
+//! use docx::formatting::CharacterProperty;
+//! use docx::visit::{Visit, VisitMut};
+//! use docx::Docx;
+//!
+//! #[derive(Default)]
+//! struct Drawings(usize);
+//!
+//! impl<'a> Visit<'a> for Drawings {
+//!     fn visit_drawing(&mut self, _: &Drawing<'a>) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! struct StripColors;
+//!
+//! impl<'a> VisitMut<'a> for StripColors {
+//!     fn visit_character_property(&mut self, property: &mut CharacterProperty<'a>) {
+//!         property.color = None;
+//!     }
+//! }
+//!
+//! let mut docx = Docx::default();
+//! docx.document.push(
+//!     Paragraph::default()
+//!         .push(Run::default().property(CharacterProperty::default().color(0xff0000)).push_text("red"))
+//!         .push(Run::default().push(Drawing::default())),
+//! );
+//!
+//! let mut drawings = Drawings::default();
+//! docx.visit(&mut drawings);
+//! assert_eq!(drawings.0, 1);
+//!
+//! docx.visit_mut(&mut StripColors);
+//! ```
+//!
+//! [`Docx::visit`]: ../struct.Docx.html#method.visit
+//! [`Docx::visit_mut`]: ../struct.Docx.html#method.visit_mut
+
+use crate::{
+    comments::{Comment, Comments},
+    document::{
+        BlockSdt, Body, BodyContent, BookmarkEnd, BookmarkStart, Break, CellSdt, CommentRangeEnd,
+        CommentRangeStart, CommentReference, DeletedText, Deletion, Document, Drawing, EndnoteRef,
+        EndnoteReference, FieldChar, FootnoteRef, FootnoteReference, Hyperlink, Insertion,
+        InstrText, MoveFrom, MoveTo, Paragraph, ParagraphContent, RowSdt, Run, RunContent, RunSdt,
+        SdtProperty, SimpleField, SmartTag, Tab, Table, TableCell, TableCellContent, TableContent,
+        TableRow, TableRowContent, Text,
+    },
+    footnotes::{Endnote, Endnotes, Footnote, Footnotes},
+    formatting::{
+        CharacterProperty, ParagraphProperty, TableCellProperty, TableProperty, TableRowProperty,
+    },
+    header_footer::{Footer, Header},
+    Docx,
+};
+
+// `Visit` and `VisitMut` only differ by the kind of references they take.
+macro_rules! visitor {
+    (
+        $(#[$attr:meta])*
+        $trait:ident, $walk:ident, $docx_visit:ident, [$($mut:tt)?]
+    ) => {
+        $(#[$attr])*
+        pub trait $trait<'a> {
+            fn visit_document(&mut self, document: &$($mut)? Document<'a>) {
+                $walk::document(self, document)
+            }
+
+            fn visit_body(&mut self, body: &$($mut)? Body<'a>) {
+                $walk::body(self, body)
+            }
+
+            fn visit_header(&mut self, header: &$($mut)? Header<'a>) {
+                $walk::header(self, header)
+            }
+
+            fn visit_footer(&mut self, footer: &$($mut)? Footer<'a>) {
+                $walk::footer(self, footer)
+            }
+
+            fn visit_comments(&mut self, comments: &$($mut)? Comments<'a>) {
+                $walk::comments(self, comments)
+            }
+
+            fn visit_comment(&mut self, comment: &$($mut)? Comment<'a>) {
+                $walk::comment(self, comment)
+            }
+
+            fn visit_footnotes(&mut self, footnotes: &$($mut)? Footnotes<'a>) {
+                $walk::footnotes(self, footnotes)
+            }
+
+            fn visit_footnote(&mut self, footnote: &$($mut)? Footnote<'a>) {
+                $walk::footnote(self, footnote)
+            }
+
+            fn visit_endnotes(&mut self, endnotes: &$($mut)? Endnotes<'a>) {
+                $walk::endnotes(self, endnotes)
+            }
+
+            fn visit_endnote(&mut self, endnote: &$($mut)? Endnote<'a>) {
+                $walk::endnote(self, endnote)
+            }
+
+            fn visit_body_content(&mut self, content: &$($mut)? BodyContent<'a>) {
+                $walk::body_content(self, content)
+            }
+
+            fn visit_paragraph(&mut self, para: &$($mut)? Paragraph<'a>) {
+                $walk::paragraph(self, para)
+            }
+
+            fn visit_paragraph_property(&mut self, _property: &$($mut)? ParagraphProperty<'a>) {}
+
+            fn visit_paragraph_content(&mut self, content: &$($mut)? ParagraphContent<'a>) {
+                $walk::paragraph_content(self, content)
+            }
+
+            fn visit_table(&mut self, table: &$($mut)? Table<'a>) {
+                $walk::table(self, table)
+            }
+
+            fn visit_table_property(&mut self, _property: &$($mut)? TableProperty<'a>) {}
+
+            fn visit_table_content(&mut self, content: &$($mut)? TableContent<'a>) {
+                $walk::table_content(self, content)
+            }
+
+            fn visit_table_row(&mut self, row: &$($mut)? TableRow<'a>) {
+                $walk::table_row(self, row)
+            }
+
+            fn visit_table_row_property(&mut self, _property: &$($mut)? TableRowProperty<'a>) {}
+
+            fn visit_table_row_content(&mut self, content: &$($mut)? TableRowContent<'a>) {
+                $walk::table_row_content(self, content)
+            }
+
+            fn visit_table_cell(&mut self, cell: &$($mut)? TableCell<'a>) {
+                $walk::table_cell(self, cell)
+            }
+
+            fn visit_table_cell_property(&mut self, _property: &$($mut)? TableCellProperty) {}
+
+            fn visit_table_cell_content(&mut self, content: &$($mut)? TableCellContent<'a>) {
+                $walk::table_cell_content(self, content)
+            }
+
+            fn visit_block_sdt(&mut self, sdt: &$($mut)? BlockSdt<'a>) {
+                $walk::block_sdt(self, sdt)
+            }
+
+            fn visit_row_sdt(&mut self, sdt: &$($mut)? RowSdt<'a>) {
+                $walk::row_sdt(self, sdt)
+            }
+
+            fn visit_cell_sdt(&mut self, sdt: &$($mut)? CellSdt<'a>) {
+                $walk::cell_sdt(self, sdt)
+            }
+
+            fn visit_run_sdt(&mut self, sdt: &$($mut)? RunSdt<'a>) {
+                $walk::run_sdt(self, sdt)
+            }
+
+            fn visit_sdt_property(&mut self, _property: &$($mut)? SdtProperty<'a>) {}
+
+            fn visit_run(&mut self, run: &$($mut)? Run<'a>) {
+                $walk::run(self, run)
+            }
+
+            fn visit_character_property(&mut self, _property: &$($mut)? CharacterProperty<'a>) {}
+
+            fn visit_hyperlink(&mut self, link: &$($mut)? Hyperlink<'a>) {
+                $walk::hyperlink(self, link)
+            }
+
+            fn visit_smart_tag(&mut self, tag: &$($mut)? SmartTag<'a>) {
+                $walk::smart_tag(self, tag)
+            }
+
+            fn visit_simple_field(&mut self, field: &$($mut)? SimpleField<'a>) {
+                $walk::simple_field(self, field)
+            }
+
+            fn visit_insertion(&mut self, ins: &$($mut)? Insertion<'a>) {
+                $walk::insertion(self, ins)
+            }
+
+            fn visit_deletion(&mut self, del: &$($mut)? Deletion<'a>) {
+                $walk::deletion(self, del)
+            }
+
+            fn visit_move_from(&mut self, from: &$($mut)? MoveFrom<'a>) {
+                $walk::move_from(self, from)
+            }
+
+            fn visit_move_to(&mut self, to: &$($mut)? MoveTo<'a>) {
+                $walk::move_to(self, to)
+            }
+
+            fn visit_bookmark_start(&mut self, _bookmark: &$($mut)? BookmarkStart<'a>) {}
+
+            fn visit_bookmark_end(&mut self, _bookmark: &$($mut)? BookmarkEnd<'a>) {}
+
+            fn visit_comment_range_start(&mut self, _range: &$($mut)? CommentRangeStart<'a>) {}
+
+            fn visit_comment_range_end(&mut self, _range: &$($mut)? CommentRangeEnd<'a>) {}
+
+            fn visit_run_content(&mut self, content: &$($mut)? RunContent<'a>) {
+                $walk::run_content(self, content)
+            }
+
+            fn visit_text(&mut self, _text: &$($mut)? Text<'a>) {}
+
+            fn visit_deleted_text(&mut self, _text: &$($mut)? DeletedText<'a>) {}
+
+            fn visit_instr_text(&mut self, _text: &$($mut)? InstrText<'a>) {}
+
+            fn visit_field_char(&mut self, _field_char: &$($mut)? FieldChar<'a>) {}
+
+            fn visit_break(&mut self, _br: &$($mut)? Break) {}
+
+            fn visit_tab(&mut self, _tab: &$($mut)? Tab) {}
+
+            fn visit_drawing(&mut self, _drawing: &$($mut)? Drawing<'a>) {}
+
+            fn visit_footnote_reference(&mut self, _reference: &$($mut)? FootnoteReference<'a>) {}
+
+            fn visit_endnote_reference(&mut self, _reference: &$($mut)? EndnoteReference<'a>) {}
+
+            fn visit_footnote_ref(&mut self, _reference: &$($mut)? FootnoteRef) {}
+
+            fn visit_endnote_ref(&mut self, _reference: &$($mut)? EndnoteRef) {}
+
+            fn visit_comment_reference(&mut self, _reference: &$($mut)? CommentReference<'a>) {}
+        }
+
+        /// Functions visiting the children of a node, which are the default
+        /// implementations of the visitor methods.
+        pub mod $walk {
+            use super::*;
+
+            pub fn document<'a, V: $trait<'a> + ?Sized>(v: &mut V, document: &$($mut)? Document<'a>) {
+                v.visit_body(&$($mut)? document.body);
+            }
+
+            pub fn body<'a, V: $trait<'a> + ?Sized>(v: &mut V, body: &$($mut)? Body<'a>) {
+                for content in &$($mut)? body.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn header<'a, V: $trait<'a> + ?Sized>(v: &mut V, header: &$($mut)? Header<'a>) {
+                for content in &$($mut)? header.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn footer<'a, V: $trait<'a> + ?Sized>(v: &mut V, footer: &$($mut)? Footer<'a>) {
+                for content in &$($mut)? footer.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn comments<'a, V: $trait<'a> + ?Sized>(v: &mut V, comments: &$($mut)? Comments<'a>) {
+                for comment in &$($mut)? comments.comments {
+                    v.visit_comment(comment);
+                }
+            }
+
+            pub fn comment<'a, V: $trait<'a> + ?Sized>(v: &mut V, comment: &$($mut)? Comment<'a>) {
+                for para in &$($mut)? comment.content {
+                    v.visit_paragraph(para);
+                }
+            }
+
+            pub fn footnotes<'a, V: $trait<'a> + ?Sized>(v: &mut V, footnotes: &$($mut)? Footnotes<'a>) {
+                for note in &$($mut)? footnotes.notes {
+                    v.visit_footnote(note);
+                }
+            }
+
+            pub fn footnote<'a, V: $trait<'a> + ?Sized>(v: &mut V, footnote: &$($mut)? Footnote<'a>) {
+                for content in &$($mut)? footnote.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn endnotes<'a, V: $trait<'a> + ?Sized>(v: &mut V, endnotes: &$($mut)? Endnotes<'a>) {
+                for note in &$($mut)? endnotes.notes {
+                    v.visit_endnote(note);
+                }
+            }
+
+            pub fn endnote<'a, V: $trait<'a> + ?Sized>(v: &mut V, endnote: &$($mut)? Endnote<'a>) {
+                for content in &$($mut)? endnote.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn body_content<'a, V: $trait<'a> + ?Sized>(v: &mut V, content: &$($mut)? BodyContent<'a>) {
+                match content {
+                    BodyContent::Paragraph(para) => v.visit_paragraph(para),
+                    BodyContent::Table(table) => v.visit_table(table),
+                    BodyContent::Sdt(sdt) => v.visit_block_sdt(sdt),
+                }
+            }
+
+            pub fn paragraph<'a, V: $trait<'a> + ?Sized>(v: &mut V, para: &$($mut)? Paragraph<'a>) {
+                v.visit_paragraph_property(&$($mut)? para.property);
+                for content in &$($mut)? para.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn paragraph_content<'a, V: $trait<'a> + ?Sized>(
+                v: &mut V,
+                content: &$($mut)? ParagraphContent<'a>,
+            ) {
+                match content {
+                    ParagraphContent::CommentRangeStart(range) => v.visit_comment_range_start(range),
+                    ParagraphContent::CommentRangeEnd(range) => v.visit_comment_range_end(range),
+                    ParagraphContent::Run(run) => v.visit_run(run),
+                    ParagraphContent::Link(link) => v.visit_hyperlink(link),
+                    ParagraphContent::BookmarkStart(bookmark) => v.visit_bookmark_start(bookmark),
+                    ParagraphContent::BookmarkEnd(bookmark) => v.visit_bookmark_end(bookmark),
+                    ParagraphContent::SmartTag(tag) => v.visit_smart_tag(tag),
+                    ParagraphContent::Sdt(sdt) => v.visit_run_sdt(sdt),
+                    ParagraphContent::SimpleField(field) => v.visit_simple_field(field),
+                    ParagraphContent::Insertion(ins) => v.visit_insertion(ins),
+                    ParagraphContent::Deletion(del) => v.visit_deletion(del),
+                    ParagraphContent::MoveFrom(from) => v.visit_move_from(from),
+                    ParagraphContent::MoveTo(to) => v.visit_move_to(to),
+                }
+            }
+
+            pub fn table<'a, V: $trait<'a> + ?Sized>(v: &mut V, table: &$($mut)? Table<'a>) {
+                v.visit_table_property(&$($mut)? table.property);
+                for content in &$($mut)? table.rows {
+                    v.visit_table_content(content);
+                }
+            }
+
+            pub fn table_content<'a, V: $trait<'a> + ?Sized>(v: &mut V, content: &$($mut)? TableContent<'a>) {
+                match content {
+                    TableContent::Row(row) => v.visit_table_row(row),
+                    TableContent::Sdt(sdt) => v.visit_row_sdt(sdt),
+                }
+            }
+
+            pub fn table_row<'a, V: $trait<'a> + ?Sized>(v: &mut V, row: &$($mut)? TableRow<'a>) {
+                v.visit_table_row_property(&$($mut)? row.property);
+                for content in &$($mut)? row.cells {
+                    v.visit_table_row_content(content);
+                }
+            }
+
+            pub fn table_row_content<'a, V: $trait<'a> + ?Sized>(
+                v: &mut V,
+                content: &$($mut)? TableRowContent<'a>,
+            ) {
+                match content {
+                    TableRowContent::Cell(cell) => v.visit_table_cell(cell),
+                    TableRowContent::Sdt(sdt) => v.visit_cell_sdt(sdt),
+                }
+            }
+
+            pub fn table_cell<'a, V: $trait<'a> + ?Sized>(v: &mut V, cell: &$($mut)? TableCell<'a>) {
+                v.visit_table_cell_property(&$($mut)? cell.property);
+                for content in &$($mut)? cell.content {
+                    v.visit_table_cell_content(content);
+                }
+            }
+
+            pub fn table_cell_content<'a, V: $trait<'a> + ?Sized>(
+                v: &mut V,
+                content: &$($mut)? TableCellContent<'a>,
+            ) {
+                match content {
+                    TableCellContent::Paragraph(para) => v.visit_paragraph(para),
+                    TableCellContent::Sdt(sdt) => v.visit_block_sdt(sdt),
+                    TableCellContent::Table(table) => v.visit_table(table),
+                }
+            }
+
+            pub fn block_sdt<'a, V: $trait<'a> + ?Sized>(v: &mut V, sdt: &$($mut)? BlockSdt<'a>) {
+                v.visit_sdt_property(&$($mut)? sdt.property);
+                if let Some(property) = &$($mut)? sdt.end_property {
+                    if let Some(property) = &$($mut)? property.property {
+                        v.visit_character_property(property);
+                    }
+                }
+                for content in &$($mut)? sdt.content.content {
+                    v.visit_body_content(content);
+                }
+            }
+
+            pub fn row_sdt<'a, V: $trait<'a> + ?Sized>(v: &mut V, sdt: &$($mut)? RowSdt<'a>) {
+                v.visit_sdt_property(&$($mut)? sdt.property);
+                if let Some(property) = &$($mut)? sdt.end_property {
+                    if let Some(property) = &$($mut)? property.property {
+                        v.visit_character_property(property);
+                    }
+                }
+                for content in &$($mut)? sdt.content.content {
+                    v.visit_table_content(content);
+                }
+            }
+
+            pub fn cell_sdt<'a, V: $trait<'a> + ?Sized>(v: &mut V, sdt: &$($mut)? CellSdt<'a>) {
+                v.visit_sdt_property(&$($mut)? sdt.property);
+                if let Some(property) = &$($mut)? sdt.end_property {
+                    if let Some(property) = &$($mut)? property.property {
+                        v.visit_character_property(property);
+                    }
+                }
+                for content in &$($mut)? sdt.content.content {
+                    v.visit_table_row_content(content);
+                }
+            }
+
+            pub fn run_sdt<'a, V: $trait<'a> + ?Sized>(v: &mut V, sdt: &$($mut)? RunSdt<'a>) {
+                v.visit_sdt_property(&$($mut)? sdt.property);
+                if let Some(property) = &$($mut)? sdt.end_property {
+                    if let Some(property) = &$($mut)? property.property {
+                        v.visit_character_property(property);
+                    }
+                }
+                for content in &$($mut)? sdt.content.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn run<'a, V: $trait<'a> + ?Sized>(v: &mut V, run: &$($mut)? Run<'a>) {
+                v.visit_character_property(&$($mut)? run.property);
+                for content in &$($mut)? run.content {
+                    v.visit_run_content(content);
+                }
+            }
+
+            pub fn hyperlink<'a, V: $trait<'a> + ?Sized>(v: &mut V, link: &$($mut)? Hyperlink<'a>) {
+                if let Some(run) = &$($mut)? link.content {
+                    v.visit_run(run);
+                }
+            }
+
+            pub fn smart_tag<'a, V: $trait<'a> + ?Sized>(v: &mut V, tag: &$($mut)? SmartTag<'a>) {
+                for content in &$($mut)? tag.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn simple_field<'a, V: $trait<'a> + ?Sized>(v: &mut V, field: &$($mut)? SimpleField<'a>) {
+                for content in &$($mut)? field.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn insertion<'a, V: $trait<'a> + ?Sized>(v: &mut V, ins: &$($mut)? Insertion<'a>) {
+                for content in &$($mut)? ins.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn deletion<'a, V: $trait<'a> + ?Sized>(v: &mut V, del: &$($mut)? Deletion<'a>) {
+                for content in &$($mut)? del.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn move_from<'a, V: $trait<'a> + ?Sized>(v: &mut V, from: &$($mut)? MoveFrom<'a>) {
+                for content in &$($mut)? from.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn move_to<'a, V: $trait<'a> + ?Sized>(v: &mut V, to: &$($mut)? MoveTo<'a>) {
+                for content in &$($mut)? to.content {
+                    v.visit_paragraph_content(content);
+                }
+            }
+
+            pub fn run_content<'a, V: $trait<'a> + ?Sized>(v: &mut V, content: &$($mut)? RunContent<'a>) {
+                match content {
+                    RunContent::Break(br) => v.visit_break(br),
+                    RunContent::Drawing(drawing) => v.visit_drawing(drawing),
+                    RunContent::Tab(tab) => v.visit_tab(tab),
+                    RunContent::Text(text) => v.visit_text(text),
+                    RunContent::FieldChar(field_char) => v.visit_field_char(field_char),
+                    RunContent::InstrText(text) => v.visit_instr_text(text),
+                    RunContent::DeletedText(text) => v.visit_deleted_text(text),
+                    RunContent::FootnoteReference(reference) => v.visit_footnote_reference(reference),
+                    RunContent::EndnoteReference(reference) => v.visit_endnote_reference(reference),
+                    RunContent::FootnoteRef(reference) => v.visit_footnote_ref(reference),
+                    RunContent::EndnoteRef(reference) => v.visit_endnote_ref(reference),
+                    RunContent::CommentReference(reference) => v.visit_comment_reference(reference),
+                }
+            }
+
+            /// Visits every part of a document with content.
+            pub fn docx<'a, V: $trait<'a> + ?Sized>(v: &mut V, docx: &$($mut)? Docx<'a>) {
+                v.visit_document(&$($mut)? docx.document);
+                for header in docx.headers.$docx_visit() {
+                    v.visit_header(header);
+                }
+                for footer in docx.footers.$docx_visit() {
+                    v.visit_footer(footer);
+                }
+                if let Some(comments) = &$($mut)? docx.comments {
+                    v.visit_comments(comments);
+                }
+                if let Some(footnotes) = &$($mut)? docx.footnotes {
+                    v.visit_footnotes(footnotes);
+                }
+                if let Some(endnotes) = &$($mut)? docx.endnotes {
+                    v.visit_endnotes(endnotes);
+                }
+            }
+        }
+    };
+}
+
+visitor!(
+    /// A visitor over a document by shared reference
+    Visit,
+    walk,
+    values,
+    []
+);
+
+visitor!(
+    /// A visitor over a document by mutable reference
+    VisitMut,
+    walk_mut,
+    values_mut,
+    [mut]
+);
+
+impl<'a> Docx<'a> {
+    /// Walks the main document, headers, footers, comments, footnotes and
+    /// endnotes, in this order.
+    pub fn visit<V: Visit<'a> + ?Sized>(&self, visitor: &mut V) {
+        walk::docx(visitor, self);
+    }
+
+    /// Walks the main document, headers, footers, comments, footnotes and
+    /// endnotes mutably, in this order.
+    pub fn visit_mut<V: VisitMut<'a> + ?Sized>(&mut self, visitor: &mut V) {
+        walk_mut::docx(visitor, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Texts(Vec<String>);
+
+    impl<'a> Visit<'a> for Texts {
+        fn visit_text(&mut self, text: &Text<'a>) {
+            self.0.push(text.text.to_string());
+        }
+    }
+
+    struct Upper;
+
+    impl<'a> VisitMut<'a> for Upper {
+        fn visit_text(&mut self, text: &mut Text<'a>) {
+            text.text = text.text.to_uppercase().into();
+        }
+
+        // skip deletions
+        fn visit_deletion(&mut self, _: &mut Deletion<'a>) {}
+    }
+
+    #[test]
+    fn visit_parts() {
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .push_text("body")
+                .push(SmartTag {
+                    content: vec![Run::default().push_text("tag").into()],
+                })
+                .push(Deletion::new("1").push(Run::default().push_text("deleted"))),
+        );
+        docx.document.push(
+            Table::default()
+                .push_row(TableRow::default().push_cell(Paragraph::default().push_text("cell"))),
+        );
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("header"));
+        docx.headers.insert("word/header1.xml".to_owned(), header);
+        docx.footnotes = Some(Footnotes {
+            notes: vec![Footnote::new("1").push(Paragraph::default().push_text("note"))],
+        });
+
+        docx.visit_mut(&mut Upper);
+
+        let mut texts = Texts::default();
+        docx.visit(&mut texts);
+        assert_eq!(
+            texts.0,
+            vec!["BODY", "TAG", "deleted", "CELL", "HEADER", "NOTE"]
+        );
+    }
+
+    /// Builds a document with text in every kind of container.
+    fn containers() -> Docx<'static> {
+        let run = |text: &'static str| Run::default().push_text(text);
+        let cell = |text: &'static str| TableCell::from(Paragraph::default().push_text(text));
+
+        let mut docx = Docx::default();
+        docx.document.push(
+            Paragraph::default()
+                .push(run("body"))
+                .push(Hyperlink::default().content(run("link")))
+                .push(RunSdt::default().push(run("run sdt")))
+                .push(SimpleField::new("PAGE").push(run("field")))
+                .push(Insertion::new("1").push(run("inserted")))
+                .push(MoveFrom::new("2").push(run("moved from")))
+                .push(MoveTo::new("3").push(run("moved to")))
+                .push(SmartTag {
+                    content: vec![run("tag").into()],
+                }),
+        );
+        let nested = Table::default().push_row(TableRow::default().push_cell(cell("nested")));
+        docx.document.push(
+            BlockSdt::default().push(
+                Table::default()
+                    .push_row(
+                        RowSdt::default().push(TableRow::default().push_cell(cell("row sdt"))),
+                    )
+                    .push_row(TableRow {
+                        property: Default::default(),
+                        cells: vec![CellSdt::default().push(cell("cell sdt")).into()],
+                    })
+                    .push_row(TableRow::default().push_cell(TableCell::from(nested))),
+            ),
+        );
+
+        let mut header = Header::default();
+        header.push(Paragraph::default().push_text("header"));
+        docx.headers.insert("word/header1.xml".to_owned(), header);
+        let mut footer = Footer::default();
+        footer.push(Paragraph::default().push_text("footer"));
+        docx.footers.insert("word/footer1.xml".to_owned(), footer);
+        docx.comments = Some(Comments {
+            comments: vec![Comment {
+                id: "0".into(),
+                author: "Ada".into(),
+                date: "2024-01-02T00:00:00Z".into(),
+                content: vec![Paragraph::default().push_text("comment")],
+            }],
+        });
+        docx.footnotes = Some(Footnotes {
+            notes: vec![Footnote::new("1").push(Paragraph::default().push_text("footnote"))],
+        });
+        docx.endnotes = Some(Endnotes {
+            notes: vec![Endnote::new("1").push(Paragraph::default().push_text("endnote"))],
+        });
+        docx
+    }
+
+    const CONTAINER_TEXTS: [&str; 16] = [
+        "body",
+        "link",
+        "run sdt",
+        "field",
+        "inserted",
+        "moved from",
+        "moved to",
+        "tag",
+        "row sdt",
+        "cell sdt",
+        "nested",
+        "header",
+        "footer",
+        "comment",
+        "footnote",
+        "endnote",
+    ];
+
+    #[derive(Default)]
+    struct Tables {
+        tables: usize,
+        rows: usize,
+        cells: usize,
+    }
+
+    impl<'a> Visit<'a> for Tables {
+        fn visit_table(&mut self, table: &Table<'a>) {
+            self.tables += 1;
+            walk::table(self, table);
+        }
+
+        fn visit_table_row(&mut self, row: &TableRow<'a>) {
+            self.rows += 1;
+            walk::table_row(self, row);
+        }
+
+        fn visit_table_cell(&mut self, cell: &TableCell<'a>) {
+            self.cells += 1;
+            walk::table_cell(self, cell);
+        }
+    }
+
+    #[test]
+    fn visit_containers() {
+        let docx = containers();
+
+        let mut texts = Texts::default();
+        docx.visit(&mut texts);
+        assert_eq!(texts.0, CONTAINER_TEXTS);
+
+        // the table in the content control, its rows and cells in content
+        // controls, and the table nested in a cell
+        let mut tables = Tables::default();
+        docx.visit(&mut tables);
+        assert_eq!((tables.tables, tables.rows, tables.cells), (2, 4, 4));
+    }
+
+    #[test]
+    fn visit_mut_containers() {
+        let mut docx = containers();
+        docx.visit_mut(&mut Upper);
+
+        let mut texts = Texts::default();
+        docx.visit(&mut texts);
+        let upper: Vec<String> = CONTAINER_TEXTS
+            .iter()
+            .map(|text| text.to_uppercase())
+            .collect();
+        assert_eq!(texts.0, upper);
+    }
+
+    #[test]
+    fn skip_children() {
+        struct SkipTables(Texts);
+
+        impl<'a> Visit<'a> for SkipTables {
+            fn visit_table(&mut self, _: &Table<'a>) {}
+
+            fn visit_text(&mut self, text: &Text<'a>) {
+                self.0.visit_text(text);
+            }
+        }
+
+        let mut visitor = SkipTables(Texts::default());
+        walk::document(&mut visitor, &containers().document);
+        assert_eq!(visitor.0 .0, &CONTAINER_TEXTS[..8]);
+    }
+}
@@ -0,0 +1,277 @@
+//! A generic XML tree, for the parts which aren't WordprocessingML
+//!
+//! Namespaces are resolved while parsing. Each element and attribute keeps
+//! its namespace and local name, and a name made of the prefix the caller
+//! gives to that namespace, like `text:p`, whatever the prefix written in
+//! the document. The children keep the order of the mixed content.
+
+use hard_xml::{
+    utils::xml_unescape,
+    xmlparser::{ElementEnd, Token, Tokenizer},
+    XmlError,
+};
+
+pub(crate) const SCHEMA_XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// A node of an XML tree
+#[derive(Clone, Debug)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An XML element, with its resolved name and attributes
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Element {
+    /// The name with the prefix given to its namespace, see [`parse`].
+    pub name: String,
+    pub namespace: Option<String>,
+    pub local: String,
+    pub attrs: Vec<Attribute>,
+    pub children: Vec<Node>,
+}
+
+/// An attribute, with its resolved name
+#[derive(Clone, Debug)]
+pub(crate) struct Attribute {
+    /// The name with the prefix given to its namespace, see [`parse`].
+    pub name: String,
+    pub namespace: Option<String>,
+    pub local: String,
+    pub value: String,
+}
+
+impl Element {
+    /// Returns the value of an attribute, by its prefixed name.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|attr| attr.name == name)
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// Returns the child elements.
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Returns the first child element of the given prefixed name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    /// Returns the text of the element and its descendants.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            match node {
+                Node::Element(element) => text += &element.text(),
+                Node::Text(content) => text += content,
+            }
+        }
+        text
+    }
+}
+
+/// An element being parsed, with the namespaces it declares
+struct Open {
+    element: Element,
+    prefix: String,
+    /// The declared namespaces, the empty prefix being the default namespace
+    declarations: Vec<(String, String)>,
+    /// The written prefixes of the attributes
+    attr_prefixes: Vec<String>,
+}
+
+/// Parses the root element of a part.
+///
+/// `prefixes` gives the prefix of each known namespace, by URI, which the
+/// names of the elements and attributes use, the `xml` prefix being always
+/// known. Names in other namespaces are written `{uri}local`, and names
+/// without a namespace are the local names.
+pub(crate) fn parse(xml: &str, prefixes: &[(&str, &str)]) -> Result<Element, XmlError> {
+    let mut stack: Vec<Open> = Vec::new();
+
+    for token in Tokenizer::from(xml) {
+        match token? {
+            Token::ElementStart { prefix, local, .. } => stack.push(Open {
+                element: Element {
+                    local: local.as_str().to_owned(),
+                    ..Default::default()
+                },
+                prefix: prefix.as_str().to_owned(),
+                declarations: Vec::new(),
+                attr_prefixes: Vec::new(),
+            }),
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => {
+                let open = stack.last_mut().ok_or(XmlError::UnexpectedEof)?;
+                let value = xml_unescape(value.as_str())?.into_owned();
+                match (prefix.as_str(), local.as_str()) {
+                    ("", "xmlns") => open.declarations.push((String::new(), value)),
+                    ("xmlns", local) => open.declarations.push((local.to_owned(), value)),
+                    (prefix, local) => {
+                        open.attr_prefixes.push(prefix.to_owned());
+                        open.element.attrs.push(Attribute {
+                            name: String::new(),
+                            namespace: None,
+                            local: local.to_owned(),
+                            value,
+                        });
+                    }
+                }
+            }
+            Token::Text { text } => {
+                if let Some(open) = stack.last_mut() {
+                    push_text(&mut open.element, &xml_unescape(text.as_str())?);
+                }
+            }
+            Token::Cdata { text, .. } => {
+                if let Some(open) = stack.last_mut() {
+                    push_text(&mut open.element, text.as_str());
+                }
+            }
+            Token::ElementEnd { end, .. } => {
+                if let ElementEnd::Open | ElementEnd::Empty = end {
+                    resolve(&mut stack, prefixes)?;
+                }
+                if let ElementEnd::Open = end {
+                    continue;
+                }
+
+                let open = stack.pop().ok_or(XmlError::UnexpectedEof)?;
+                if let ElementEnd::Close(prefix, local) = end {
+                    if prefix.as_str() != open.prefix || local.as_str() != open.element.local {
+                        return Err(XmlError::TagMismatch {
+                            expected: qualified(&open.prefix, &open.element.local),
+                            found: qualified(prefix.as_str(), local.as_str()),
+                        });
+                    }
+                }
+                match stack.last_mut() {
+                    Some(parent) => parent.element.children.push(Node::Element(open.element)),
+                    None => return Ok(open.element),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Err(XmlError::UnexpectedEof)
+}
+
+fn push_text(element: &mut Element, text: &str) {
+    if let Some(Node::Text(last)) = element.children.last_mut() {
+        last.push_str(text);
+    } else {
+        element.children.push(Node::Text(text.to_owned()));
+    }
+}
+
+/// Resolves the namespaces of the element just opened and of its attributes.
+fn resolve(stack: &mut [Open], prefixes: &[(&str, &str)]) -> Result<(), XmlError> {
+    let (open, ancestors) = stack.split_last_mut().ok_or(XmlError::UnexpectedEof)?;
+
+    let lookup = |prefix: &str| -> Option<String> {
+        if prefix == "xml" {
+            return Some(SCHEMA_XML.to_owned());
+        }
+        std::iter::once(&*open)
+            .chain(ancestors.iter().rev())
+            .flat_map(|open| &open.declarations)
+            .find(|(key, _)| key == prefix)
+            .map(|(_, uri)| uri.clone())
+            .filter(|uri| !uri.is_empty())
+    };
+    let name = |namespace: &Option<String>, local: &str| match namespace {
+        Some(uri) if uri == SCHEMA_XML => qualified("xml", local),
+        Some(uri) => match prefixes.iter().find(|(_, known)| known == uri) {
+            Some((prefix, _)) => qualified(prefix, local),
+            None => format!("{{{}}}{}", uri, local),
+        },
+        None => local.to_owned(),
+    };
+
+    let namespace = lookup(&open.prefix);
+    // unprefixed attributes are in no namespace
+    let namespaces: Vec<Option<String>> = open
+        .attr_prefixes
+        .iter()
+        .map(|prefix| {
+            Some(prefix)
+                .filter(|p| !p.is_empty())
+                .and_then(|p| lookup(p))
+        })
+        .collect();
+
+    open.element.name = name(&namespace, &open.element.local);
+    open.element.namespace = namespace;
+    for (attr, namespace) in open.element.attrs.iter_mut().zip(namespaces) {
+        attr.name = name(&namespace, &attr.local);
+        attr.namespace = namespace;
+    }
+
+    Ok(())
+}
+
+fn qualified(prefix: &str, local: &str) -> String {
+    if prefix.is_empty() {
+        local.to_owned()
+    } else {
+        format!("{}:{}", prefix, local)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mixed_content() {
+        let root = parse(
+            r#"<?xml version="1.0"?><o:text xmlns:o="urn:office" xmlns:t="urn:text"><t:p t:style-name="P1" id="1">a &amp; <t:span>b</t:span><t:s t:c="2"/><![CDATA[&amp;]]>c</t:p></o:text>"#,
+            &[("office", "urn:office"), ("text", "urn:text")],
+        )
+        .unwrap();
+        assert_eq!(root.name, "office:text");
+        assert_eq!(root.namespace.as_deref(), Some("urn:office"));
+        assert!(root.attrs.is_empty());
+
+        let p = root.child("text:p").unwrap();
+        assert_eq!(p.attr("text:style-name"), Some("P1"));
+        assert_eq!(p.attr("id"), Some("1"));
+        assert_eq!(p.children.len(), 4);
+        assert!(matches!(&p.children[0], Node::Text(text) if text == "a & "));
+        assert_eq!(p.elements().nth(1).unwrap().attr("text:c"), Some("2"));
+        assert_eq!(p.text(), "a & b&amp;c");
+
+        assert!(parse("<a><b></a>", &[]).is_err());
+        assert!(parse("<a>", &[]).is_err());
+    }
+
+    #[test]
+    fn resolve_namespaces() {
+        let root = parse(
+            r#"<root xmlns="urn:a" xmlns:b="urn:b"><b:item b:id="1" xml:lang="en"/><plain xmlns=""/><c:other xmlns:c="urn:c"/></root>"#,
+            &[("a", "urn:a"), ("b", "urn:b")],
+        )
+        .unwrap();
+        assert_eq!(root.name, "a:root");
+
+        let names: Vec<&str> = root.elements().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["b:item", "plain", "{urn:c}other"]);
+
+        let item = root.child("b:item").unwrap();
+        assert_eq!(item.attr("b:id"), Some("1"));
+        assert_eq!(item.attr("xml:lang"), Some("en"));
+        assert_eq!(item.attrs[1].namespace.as_deref(), Some(SCHEMA_XML));
+        assert_eq!(root.child("plain").unwrap().namespace, None);
+    }
+}